@@ -0,0 +1,67 @@
+//! Compares collecting all messages of a capture into a [`MessageArena`]
+//! with collecting them as one `Vec<u8>` per message.
+//!
+//! Run with `cargo run --release --example message_arena_bench`.
+use std::time::{Duration, Instant};
+
+use dlt_parse::{DltExtendedHeader, DltHeader, DltLogLevel, MessageArena, SliceIterator};
+
+const MESSAGES: usize = 100_000;
+const ROUNDS: u32 = 20;
+
+fn capture() -> Vec<u8> {
+    let mut result = Vec::new();
+    for i in 0..MESSAGES {
+        let payload_len = 8 + (i % 64);
+        let mut header = DltHeader {
+            is_big_endian: true,
+            message_counter: i as u8,
+            length: 0,
+            ecu_id: Some(*b"ECU1"),
+            session_id: None,
+            timestamp: Some(i as u32),
+            extended_header: Some(DltExtendedHeader::new_non_verbose_log(
+                DltLogLevel::Info,
+                *b"APP1",
+                *b"CTX1",
+            )),
+        };
+        header.length = header.header_len() + payload_len as u16;
+        result.extend_from_slice(&header.to_bytes());
+        result.extend((0..payload_len).map(|v| v as u8));
+    }
+    result
+}
+
+fn measure<F: FnMut() -> usize>(name: &str, mut f: F) {
+    let mut total = Duration::ZERO;
+    let mut check = 0;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        check += f();
+        total += start.elapsed();
+    }
+    println!(
+        "{:<20} {:>10.3} ms per capture ({} messages)",
+        name,
+        total.as_secs_f64() * 1000.0 / f64::from(ROUNDS),
+        check / ROUNDS as usize
+    );
+}
+
+fn main() {
+    let data = capture();
+    println!("capture size: {} bytes", data.len());
+
+    measure("Vec per message", || {
+        let messages: Vec<Vec<u8>> = SliceIterator::new(&data)
+            .map(|p| p.unwrap().slice().to_vec())
+            .collect();
+        messages.len()
+    });
+
+    measure("MessageArena", || {
+        let arena = MessageArena::from_slice(&data).unwrap();
+        arena.len()
+    });
+}
@@ -89,13 +89,9 @@ fn main() -> Result<(), Error> {
             };
 
             // determine ecu id
-            let ecu_id = if let Some(ecu_id) = dlt_packet.header().ecu_id {
-                ecu_id
-            } else {
-                // you might want to determine the ecu id via the ip here
-                // if you have that option
-                [0, 0, 0, 0]
-            };
+            // (you might want to determine the ecu id via the ip here
+            // if you have that option)
+            let ecu_id = dlt_packet.header().ecu_id.unwrap_or_default();
 
             // determine utc time (unwrap is ok, as all pcap timestamps start at UNIX_EPOCH)
             let d = packet.time.duration_since(UNIX_EPOCH).unwrap();
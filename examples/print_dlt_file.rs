@@ -77,6 +77,7 @@ fn main() -> Result<(), ReadError> {
                 NetworkV(_) => println!("verbose network message received"),
                 NetworkNv(_) => println!("non verbose network message received"),
                 ControlV(_) => println!("verbose control message received"),
+                ControlTime(_) => println!("control time message received"),
             }
         } else {
             println!("non verbose message with incomplete message id");
@@ -133,6 +133,7 @@ fn read(arguments: CommandLineArguments) -> Result<(), Error> {
                         NetworkV(_) => println!("verbose network message received"),
                         NetworkNv(_) => println!("non verbose network message received"),
                         ControlV(_) => println!("verbose control message received"),
+                        ControlTime(_) => println!("control time message received"),
                     }
                 } else {
                     println!("non verbose message with incomplete message id");
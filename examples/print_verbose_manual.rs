@@ -54,6 +54,7 @@ fn main() -> Result<(), ReadError> {
                 NetworkV(_) => println!("verbose network message received"),
                 NetworkNv(_) => println!("non verbose network message received"),
                 ControlV(_) => println!("verbose control message received"),
+                ControlTime(_) => println!("control time message received"),
                 LogNv(_) => println!("non verbose log message received"),
                 ControlNv(_) => println!("non verbose control message received"),
             }
@@ -0,0 +1,85 @@
+//! Measures the time needed to decode the arguments of verbose messages.
+//!
+//! Run with `cargo run --release --example verbose_decode_bench`.
+use std::time::{Duration, Instant};
+
+use dlt_parse::verbose::{RawValue, U16Value, VerboseIter, VerboseValue};
+use dlt_parse::{DltLogLevel, DltVerboseMessageBuilder};
+
+const ROUNDS: u32 = 20;
+const ITERATIONS: usize = 20_000;
+
+/// Returns the payload & number of arguments of a verbose message
+/// containing a mix of argument types.
+fn payload(is_big_endian: bool) -> (Vec<u8>, u16) {
+    let mut builder =
+        DltVerboseMessageBuilder::<0x10000>::new(*b"APP1", *b"CTX1", DltLogLevel::Info)
+            .with_big_endian(is_big_endian);
+    for i in 0..25u32 {
+        builder.add_u32_named("counter", "", i).unwrap();
+        builder
+            .add_i64_named("position", "mm", -i64::from(i))
+            .unwrap();
+        builder
+            .add_f64_named("speed", "km/h", f64::from(i) * 0.5)
+            .unwrap();
+        builder.add_bool_named("active", i % 2 == 0).unwrap();
+        builder.add_str_named("state", "running").unwrap();
+        builder
+            .add_value(&VerboseValue::U16(U16Value {
+                variable_info: None,
+                scaling: None,
+                value: i as u16,
+            }))
+            .unwrap();
+        builder
+            .add_value(&VerboseValue::Raw(RawValue {
+                name: None,
+                data: &[1, 2, 3, 4, 5, 6, 7, 8],
+            }))
+            .unwrap();
+    }
+    (
+        builder.payload().to_vec(),
+        u16::from(builder.number_of_arguments()),
+    )
+}
+
+fn measure<F: FnMut() -> usize>(name: &str, mut f: F) {
+    let mut total = Duration::ZERO;
+    let mut check = 0;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        check += f();
+        total += start.elapsed();
+    }
+    println!(
+        "{:<20} {:>10.3} ms per round ({} values)",
+        name,
+        total.as_secs_f64() * 1000.0 / f64::from(ROUNDS),
+        check / ROUNDS as usize
+    );
+}
+
+fn main() {
+    for is_big_endian in [false, true] {
+        let (data, number_of_arguments) = payload(is_big_endian);
+        measure(
+            if is_big_endian {
+                "big endian"
+            } else {
+                "little endian"
+            },
+            || {
+                let mut count = 0;
+                for _ in 0..ITERATIONS {
+                    for value in VerboseIter::new(is_big_endian, number_of_arguments, &data) {
+                        value.unwrap();
+                        count += 1;
+                    }
+                }
+                count
+            },
+        );
+    }
+}
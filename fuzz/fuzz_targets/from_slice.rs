@@ -0,0 +1,66 @@
+#![no_main]
+
+use dlt_parse::DltPacketSlice;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let packet = match DltPacketSlice::from_slice(data) {
+        Ok(packet) => packet,
+        Err(_) => {
+            // the strict decoding must never accept more then the lenient one
+            assert!(DltPacketSlice::from_slice_strict(data).is_err());
+            return;
+        }
+    };
+
+    // the packet is a prefix of the data & the rest is returned separately
+    let (with_rest, rest) = DltPacketSlice::from_slice_with_rest(data).unwrap();
+    assert_eq!(packet, with_rest);
+    assert_eq!(data.len(), packet.slice().len() + rest.len());
+    assert_eq!(packet.slice(), &data[..packet.slice().len()]);
+
+    // the unchecked header decoding matches the checked one
+    let header = packet.header();
+    assert_eq!(Ok(header.clone()), packet.try_header());
+    assert_eq!(usize::from(header.length), packet.slice().len());
+    assert_eq!(header.is_big_endian, packet.is_big_endian());
+    assert_eq!(header.ecu_id, packet.ecu_id());
+    assert_eq!(
+        header.extended_header.is_some(),
+        packet.has_extended_header()
+    );
+    assert_eq!(header.extended_header, packet.extended_header());
+
+    // re-serializing the header & payload yields the original bytes
+    // (except for the version bits which are always written as 1)
+    let mut bytes = header.to_bytes().to_vec();
+    bytes.extend_from_slice(packet.payload());
+    bytes[0] = (bytes[0] & 0b0001_1111) | (data[0] & 0b1110_0000);
+    assert_eq!(packet.slice(), &bytes[..]);
+
+    // remaining accessors must not panic
+    let _ = packet.is_verbose();
+    let _ = packet.message_type();
+    let _ = packet.message_id();
+    let _ = packet.message_id_and_payload();
+    let _ = packet.non_verbose_payload();
+    let _ = packet.is_empty_payload();
+    let _ = packet.is_keepalive();
+    let _ = packet.field_offsets();
+    let _ = packet.raw_bytes();
+    let _ = packet.payload_fingerprint();
+    let _ = packet.estimate_compressed_size();
+    let _ = packet.content_key();
+    let _ = packet.payload_preview(16);
+    let _ = packet.verbose_text_preview(16);
+    let _ = packet.control_service_id();
+    let _ = packet.typed_payload();
+    if let Some(iter) = packet.verbose_value_iter() {
+        for (index, value) in iter.enumerate() {
+            if value.is_err() {
+                break;
+            }
+            assert!(packet.verbose_arg_bytes(index).is_some());
+        }
+    }
+});
@@ -0,0 +1,40 @@
+#![no_main]
+
+use dlt_parse::SliceIterator;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // the first byte is used as prefix length to also cover the prefix
+    // handling of the iterator
+    let (prefix_len, data) = match data.split_first() {
+        Some((prefix_len, data)) => (usize::from(*prefix_len % 32), data),
+        None => (0, data),
+    };
+
+    let mut consumed = 0;
+    let mut iter = SliceIterator::new(data).with_prefix_len(prefix_len);
+    while let Some(result) = iter.next() {
+        match result {
+            Ok(packet) => {
+                consumed += prefix_len + packet.slice().len();
+                assert_eq!(data.len(), consumed + iter.slice().len());
+                let _ = packet.header();
+                let _ = packet.typed_payload();
+            }
+            Err(_) => {
+                // iteration ends after an error
+                assert!(iter.slice().is_empty());
+                assert!(iter.next().is_none());
+                break;
+            }
+        }
+    }
+
+    // skipping keep-alive messages never yields more packets
+    let all = SliceIterator::new(data).with_prefix_len(prefix_len).count();
+    let without_keepalive = SliceIterator::new(data)
+        .with_prefix_len(prefix_len)
+        .with_skip_keepalive(true)
+        .count();
+    assert!(without_keepalive <= all);
+});
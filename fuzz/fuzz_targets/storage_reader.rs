@@ -0,0 +1,50 @@
+#![no_main]
+
+use dlt_parse::storage::DltStorageReader;
+use dlt_parse::{DltFraming, DltReader};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // storage reader seeking the storage pattern after corrupted data
+    let mut reader = DltStorageReader::new(data);
+    while let Some(result) = reader.next_packet() {
+        if let Ok(slice) = result {
+            let _ = slice.packet.header();
+        }
+    }
+
+    // storage reader stopping at the first error
+    let mut reader = DltStorageReader::new_strict(data);
+    while let Some(result) = reader.next_packet() {
+        match result {
+            Ok(slice) => {
+                let _ = slice.packet.header();
+            }
+            Err(_) => {
+                assert!(reader.next_packet().is_none());
+                break;
+            }
+        }
+    }
+
+    // generic reader with all framings (including the serial header)
+    for framing in [
+        DltFraming::Raw,
+        DltFraming::SerialHeader,
+        DltFraming::StorageHeader,
+    ] {
+        let mut reader = DltReader::new(data, framing);
+        while let Some(result) = reader.next_packet() {
+            match result {
+                Ok(slice) => {
+                    let _ = slice.packet.header();
+                    let _ = slice.packet.typed_payload();
+                }
+                Err(_) => {
+                    assert!(reader.next_packet().is_none());
+                    break;
+                }
+            }
+        }
+    }
+});
@@ -0,0 +1,44 @@
+#![no_main]
+
+use arrayvec::ArrayVec;
+use dlt_parse::verbose::{VerboseIter, VerboseValue};
+use libfuzzer_sys::fuzz_target;
+
+/// Encodes the given value & returns the bytes (`None` if the value
+/// can not be encoded).
+fn encode(value: &VerboseValue<'_>, is_big_endian: bool) -> Option<ArrayVec<u8, 0xffff>> {
+    let mut buf = ArrayVec::new();
+    value.write(&mut buf, is_big_endian).ok()?;
+    Some(buf)
+}
+
+fuzz_target!(|data: &[u8]| {
+    // first byte: endianness, second byte: number of arguments
+    if data.len() < 2 {
+        return;
+    }
+    let is_big_endian = 0 != data[0] & 1;
+    let number_of_arguments = u16::from(data[1]);
+    let payload = &data[2..];
+
+    for value in VerboseIter::new(is_big_endian, number_of_arguments, payload) {
+        let value = match value {
+            Ok(value) => value,
+            Err(_) => break,
+        };
+        let _ = value.name();
+        let _ = value.unit();
+        let _ = value.as_i128();
+        let _ = value.as_u128();
+        let _ = value.as_f64();
+        let _ = value.physical_as_f64();
+
+        // re-encoding a decoded value yields a stable encoding
+        if let Some(encoded) = encode(&value, is_big_endian) {
+            assert_eq!(value.serialized_len(), encoded.len());
+            let (decoded, rest) = VerboseValue::from_slice(&encoded, is_big_endian).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(Some(&encoded), encode(&decoded, is_big_endian).as_ref());
+        }
+    }
+});
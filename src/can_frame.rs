@@ -0,0 +1,380 @@
+use crate::error::CanFrameError;
+use crate::*;
+
+/// Layout of the bus specific header block of CAN network trace messages.
+///
+/// The DLT specification does not define the content of the header block,
+/// so tracers use slightly different layouts. All supported layouts start
+/// with the CAN identifier as `u32` encoded in the endianness of the
+/// message.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CanHeaderFormat {
+    /// Selects [`CanHeaderFormat::Id`] for 4 byte headers &
+    /// [`CanHeaderFormat::IdFlags`] for longer headers.
+    Auto,
+
+    /// Header only contains the CAN identifier (e.g. as in the
+    /// dlt-daemon network trace examples).
+    ///
+    /// Extended identifiers are detected via bit 31 (`CAN_EFF_FLAG` as
+    /// used by SocketCAN) or an identifier above `0x7FF`. Bits 29 & 30
+    /// (SocketCAN RTR & error flags) are ignored. Frames with more then
+    /// 8 data bytes are considered CAN FD frames.
+    Id,
+
+    /// CAN identifier followed by a flags byte (bit 0 signals an extended
+    /// identifier & bit 1 a CAN FD frame). Additional bytes after the flags
+    /// byte (e.g. timestamps or channel numbers added by some tracers) are
+    /// ignored.
+    IdFlags,
+}
+
+impl Default for CanHeaderFormat {
+    /// Returns [`CanHeaderFormat::Auto`].
+    #[inline]
+    fn default() -> Self {
+        CanHeaderFormat::Auto
+    }
+}
+
+/// CAN frame decoded from a network trace message with the network type
+/// [`DltNetworkType::Can`].
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{CanFrame, DltPacketSlice};
+///
+/// let bytes = include_bytes!("../tests/fixtures/can_trace_standard.dlt");
+/// let packet = DltPacketSlice::from_slice(bytes).unwrap();
+///
+/// let frame = CanFrame::from_packet(&packet).unwrap();
+/// assert_eq!(0x123, frame.id);
+/// assert_eq!(false, frame.extended);
+/// assert_eq!(false, frame.fd);
+/// assert_eq!(&[0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88], frame.data);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CanFrame<'a> {
+    /// CAN identifier (11 bit for standard & 29 bit for extended frames).
+    pub id: u32,
+    /// True if the frame uses an extended (29 bit) identifier.
+    pub extended: bool,
+    /// True if the frame is a CAN FD frame.
+    pub fd: bool,
+    /// Data bytes of the frame (0-8 for classic CAN, up to 64 for CAN FD).
+    pub data: &'a [u8],
+}
+
+impl<'a> CanFrame<'a> {
+    /// Maximum value of a standard (11 bit) CAN identifier.
+    pub const MAX_STANDARD_ID: u32 = 0x7FF;
+
+    /// Mask of the bits of an extended (29 bit) CAN identifier.
+    pub const EXTENDED_ID_MASK: u32 = 0x1FFF_FFFF;
+
+    /// Flag signaling an extended identifier in [`CanHeaderFormat::Id`]
+    /// headers.
+    pub const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+
+    /// Maximum number of data bytes of a classic CAN frame.
+    pub const MAX_CLASSIC_DATA_LEN: usize = 8;
+
+    /// Maximum number of data bytes of a CAN FD frame.
+    pub const MAX_FD_DATA_LEN: usize = 64;
+
+    /// Decodes the CAN frame of a network trace packet (using
+    /// [`CanHeaderFormat::Auto`]).
+    pub fn from_packet(packet: &DltPacketSlice<'a>) -> Result<CanFrame<'a>, CanFrameError> {
+        CanFrame::from_trace(&NetworkTraceSlice::from_packet(packet)?)
+    }
+
+    /// Decodes the CAN frame of a network trace (using
+    /// [`CanHeaderFormat::Auto`]).
+    pub fn from_trace(trace: &NetworkTraceSlice<'a>) -> Result<CanFrame<'a>, CanFrameError> {
+        CanFrame::from_trace_with_format(trace, CanHeaderFormat::Auto)
+    }
+
+    /// Decodes the CAN frame of a network trace with the given header
+    /// format.
+    pub fn from_trace_with_format(
+        trace: &NetworkTraceSlice<'a>,
+        format: CanHeaderFormat,
+    ) -> Result<CanFrame<'a>, CanFrameError> {
+        use CanFrameError::*;
+        use CanHeaderFormat::*;
+
+        if DltNetworkType::Can != trace.net_type() {
+            return Err(NotACanTrace(trace.net_type()));
+        }
+
+        let header = trace.header();
+        let format = match (format, header.len()) {
+            (Auto, 4) => Id,
+            (Auto, len) if len > 4 => IdFlags,
+            (Id, 4) => Id,
+            (IdFlags, len) if len > 4 => IdFlags,
+            (_, len) => return Err(InvalidHeaderLen(len)),
+        };
+
+        let raw_id = [header[0], header[1], header[2], header[3]];
+        let raw_id = if trace.is_big_endian() {
+            u32::from_be_bytes(raw_id)
+        } else {
+            u32::from_le_bytes(raw_id)
+        };
+        let id = raw_id & CanFrame::EXTENDED_ID_MASK;
+        let data = trace.payload();
+
+        let (extended, fd) = if IdFlags == format {
+            (
+                0 != header[4] & 0b01 || id > CanFrame::MAX_STANDARD_ID,
+                0 != header[4] & 0b10,
+            )
+        } else {
+            (
+                0 != raw_id & CanFrame::EXTENDED_ID_FLAG || id > CanFrame::MAX_STANDARD_ID,
+                data.len() > CanFrame::MAX_CLASSIC_DATA_LEN,
+            )
+        };
+
+        let valid_len = if fd {
+            matches!(data.len(), 0..=8 | 12 | 16 | 20 | 24 | 32 | 48 | 64)
+        } else {
+            data.len() <= CanFrame::MAX_CLASSIC_DATA_LEN
+        };
+        if false == valid_len {
+            return Err(InvalidDataLen {
+                fd,
+                len: data.len(),
+            });
+        }
+
+        Ok(CanFrame {
+            id,
+            extended,
+            fd,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod can_frame_tests {
+    use super::*;
+    use crate::test_packets;
+    use alloc::{vec, vec::Vec};
+    use proptest::prelude::*;
+
+    /// Verbose CAN trace (big endian, standard id 0x123, header format
+    /// [`CanHeaderFormat::Id`], 8 data bytes).
+    const STANDARD: &[u8] = include_bytes!("../tests/fixtures/can_trace_standard.dlt");
+
+    /// Non verbose CAN trace (little endian, extended id 0x18DAF110,
+    /// header format [`CanHeaderFormat::IdFlags`], data `02 10 03`).
+    const EXTENDED: &[u8] = include_bytes!("../tests/fixtures/can_trace_extended.dlt");
+
+    /// Builds a non verbose network trace packet with the given header &
+    /// payload blocks.
+    fn packet(
+        net_type: DltNetworkType,
+        header_block: &[u8],
+        data: &[u8],
+        is_big_endian: bool,
+    ) -> Vec<u8> {
+        let to_bytes = |v: u16| {
+            if is_big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+        let mut payload = vec![0, 0, 0, 1];
+        payload.extend_from_slice(&to_bytes(header_block.len() as u16));
+        payload.extend_from_slice(header_block);
+        payload.extend_from_slice(&to_bytes(data.len() as u16));
+        payload.extend_from_slice(data);
+
+        let ext = DltExtendedHeader::new_non_verbose(
+            DltMessageType::NetworkTrace(net_type),
+            *b"NET\0",
+            *b"TRC\0",
+        )
+        .unwrap();
+        let header = DltHeader {
+            ecu_id: Some(*b"ECU1"),
+            ..test_packets::header(is_big_endian, Some(ext))
+        };
+        test_packets::packet(header, &payload)
+    }
+
+    fn decode(bytes: &[u8], format: CanHeaderFormat) -> Result<CanFrame<'_>, CanFrameError> {
+        let packet = DltPacketSlice::from_slice(bytes).unwrap();
+        let trace = NetworkTraceSlice::from_packet(&packet).unwrap();
+        CanFrame::from_trace_with_format(&trace, format)
+    }
+
+    fn encode_id(id: u32, is_big_endian: bool) -> [u8; 4] {
+        if is_big_endian {
+            id.to_be_bytes()
+        } else {
+            id.to_le_bytes()
+        }
+    }
+
+    #[test]
+    fn fixtures() {
+        let standard = DltPacketSlice::from_slice(STANDARD).unwrap();
+        assert!(standard.is_verbose());
+        assert_eq!(
+            Ok(CanFrame {
+                id: 0x123,
+                extended: false,
+                fd: false,
+                data: &[0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88],
+            }),
+            CanFrame::from_packet(&standard)
+        );
+
+        let extended = DltPacketSlice::from_slice(EXTENDED).unwrap();
+        assert!(false == extended.is_verbose());
+        assert_eq!(
+            Ok(CanFrame {
+                id: 0x18DA_F110,
+                extended: true,
+                fd: false,
+                data: &[0x02, 0x10, 0x03],
+            }),
+            CanFrame::from_packet(&extended)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn id_format(
+            id in 0..=CanFrame::EXTENDED_ID_MASK,
+            eff_flag in any::<bool>(),
+            ref data in proptest::collection::vec(any::<u8>(), 0..=8),
+            is_big_endian in any::<bool>(),
+        ) {
+            let raw_id = if eff_flag { id | CanFrame::EXTENDED_ID_FLAG } else { id };
+            let bytes = packet(DltNetworkType::Can, &encode_id(raw_id, is_big_endian), data, is_big_endian);
+            let expected = CanFrame {
+                id,
+                extended: eff_flag || id > CanFrame::MAX_STANDARD_ID,
+                fd: false,
+                data: &data[..],
+            };
+            prop_assert_eq!(Ok(expected.clone()), decode(&bytes, CanHeaderFormat::Id));
+            prop_assert_eq!(Ok(expected), decode(&bytes, CanHeaderFormat::Auto));
+
+            // header too long for the id format
+            let mut header_block = encode_id(raw_id, is_big_endian).to_vec();
+            header_block.push(0);
+            let bytes = packet(DltNetworkType::Can, &header_block, data, is_big_endian);
+            prop_assert_eq!(
+                Err(CanFrameError::InvalidHeaderLen(5)),
+                decode(&bytes, CanHeaderFormat::Id)
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn id_flags_format(
+            id in 0..=CanFrame::MAX_STANDARD_ID,
+            extended in any::<bool>(),
+            fd in any::<bool>(),
+            ref trailing in proptest::collection::vec(any::<u8>(), 0..4),
+            ref data in proptest::collection::vec(any::<u8>(), 0..=8),
+            is_big_endian in any::<bool>(),
+        ) {
+            let mut header_block = encode_id(id, is_big_endian).to_vec();
+            header_block.push(if extended { 0b01 } else { 0 } | if fd { 0b10 } else { 0 });
+            header_block.extend_from_slice(trailing);
+            let bytes = packet(DltNetworkType::Can, &header_block, data, is_big_endian);
+            let expected = CanFrame {
+                id,
+                extended,
+                fd,
+                data: &data[..],
+            };
+            prop_assert_eq!(Ok(expected.clone()), decode(&bytes, CanHeaderFormat::IdFlags));
+            prop_assert_eq!(Ok(expected), decode(&bytes, CanHeaderFormat::Auto));
+
+            // header too short for the flags
+            let bytes = packet(DltNetworkType::Can, &header_block[..4], data, is_big_endian);
+            prop_assert_eq!(
+                Err(CanFrameError::InvalidHeaderLen(4)),
+                decode(&bytes, CanHeaderFormat::IdFlags)
+            );
+        }
+    }
+
+    #[test]
+    fn data_len() {
+        for len in 0..=70usize {
+            let data = vec![0xAB; len];
+            let fd_len = matches!(len, 0..=8 | 12 | 16 | 20 | 24 | 32 | 48 | 64);
+
+            // id format (fd detected via the length)
+            let bytes = packet(DltNetworkType::Can, &[0, 0, 0, 1], &data, true);
+            let result = decode(&bytes, CanHeaderFormat::Id);
+            if fd_len {
+                assert_eq!(Ok(len > 8), result.map(|f| f.fd));
+            } else {
+                assert_eq!(Err(CanFrameError::InvalidDataLen { fd: true, len }), result);
+            }
+
+            // classic frame flagged via the flags byte
+            let bytes = packet(DltNetworkType::Can, &[0, 0, 0, 1, 0], &data, true);
+            let result = decode(&bytes, CanHeaderFormat::IdFlags);
+            if len <= 8 {
+                assert!(result.is_ok());
+            } else {
+                assert_eq!(
+                    Err(CanFrameError::InvalidDataLen { fd: false, len }),
+                    result
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        // other network type
+        let bytes = packet(DltNetworkType::Ethernet, &[0, 0, 0, 1], &[], false);
+        assert_eq!(
+            Err(CanFrameError::NotACanTrace(DltNetworkType::Ethernet)),
+            decode(&bytes, CanHeaderFormat::Auto)
+        );
+
+        // header too short
+        for len in 0..4 {
+            let bytes = packet(DltNetworkType::Can, &[0; 4][..len], &[], false);
+            for format in [
+                CanHeaderFormat::Auto,
+                CanHeaderFormat::Id,
+                CanHeaderFormat::IdFlags,
+            ] {
+                assert_eq!(
+                    Err(CanFrameError::InvalidHeaderLen(len)),
+                    decode(&bytes, format)
+                );
+            }
+        }
+
+        // not a network trace
+        let bytes = include_bytes!("../tests/fixtures/non_verbose_message.dlt");
+        let packet = DltPacketSlice::from_slice(bytes).unwrap();
+        assert!(matches!(
+            CanFrame::from_packet(&packet),
+            Err(CanFrameError::NetworkTrace(_))
+        ));
+    }
+
+    #[test]
+    fn default_format() {
+        assert_eq!(CanHeaderFormat::Auto, CanHeaderFormat::default());
+    }
+}
@@ -0,0 +1,352 @@
+use crate::{error::PacketSliceError, ContentKey, SliceIterator};
+use std::vec::Vec;
+
+/// Number of messages [`diff`] looks ahead in the other capture to
+/// re-align the captures after a difference.
+pub const DEFAULT_DIFF_LOOKAHEAD: usize = 64;
+
+/// Difference between two captures (see [`diff`]).
+///
+/// The indices are the positions of the messages in the captures
+/// (0 for the first message).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DiffEntry {
+    /// Message only present in the first capture.
+    OnlyInA {
+        /// Index of the message in the first capture.
+        index_a: usize,
+        /// Content key of the message.
+        key: ContentKey,
+    },
+    /// Message only present in the second capture.
+    OnlyInB {
+        /// Index of the message in the second capture.
+        index_b: usize,
+        /// Content key of the message.
+        key: ContentKey,
+    },
+    /// Message present in both captures with the same application id,
+    /// context id & message info but a differing payload.
+    Changed {
+        /// Index of the message in the first capture.
+        index_a: usize,
+        /// Index of the message in the second capture.
+        index_b: usize,
+        /// Content key of the message in the first capture.
+        key_a: ContentKey,
+        /// Content key of the message in the second capture.
+        key_b: ContentKey,
+    },
+}
+
+/// Compares the dlt messages of two captures and returns the messages
+/// that are only present in one of them or that differ.
+///
+/// Messages are compared via their [`ContentKey`] (so message counters,
+/// ecu ids, session ids & timestamps are ignored). After a difference the
+/// captures are re-aligned by searching the next [`DEFAULT_DIFF_LOOKAHEAD`]
+/// messages of the other capture for the current message (see
+/// [`diff_with_lookahead`]).
+///
+/// Both slices are expected to only contain dlt messages (without storage
+/// headers). Decoding errors are returned.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{diff, DiffEntry, DltHeader};
+///
+/// let packet = |counter: u8, payload: &[u8]| {
+///     let mut header = DltHeader {
+///         is_big_endian: true,
+///         message_counter: counter,
+///         length: 0,
+///         ecu_id: None,
+///         session_id: None,
+///         timestamp: None,
+///         extended_header: None,
+///     };
+///     header.length = header.header_len() + payload.len() as u16;
+///     let mut bytes = header.to_bytes().to_vec();
+///     bytes.extend_from_slice(payload);
+///     bytes
+/// };
+///
+/// let a = [packet(0, b"first"), packet(1, b"second")].concat();
+/// // message counters are ignored
+/// let b = [packet(5, b"first"), packet(6, b"inserted"), packet(7, b"second")].concat();
+///
+/// let entries = diff(&a, &b).unwrap();
+/// assert_eq!(1, entries.len());
+/// assert!(matches!(entries[0], DiffEntry::OnlyInB { index_b: 1, .. }));
+/// ```
+#[inline]
+pub fn diff(a: &[u8], b: &[u8]) -> Result<Vec<DiffEntry>, PacketSliceError> {
+    diff_with_lookahead(a, b, DEFAULT_DIFF_LOOKAHEAD)
+}
+
+/// Same as [`diff`] but with a custom number of messages to look ahead
+/// when re-aligning the captures.
+///
+/// If a message is not found in the lookahead window of the other capture
+/// (and vice versa) the two current messages are reported as
+/// [`DiffEntry::Changed`] if they only differ in the payload, otherwise as
+/// [`DiffEntry::OnlyInA`] & [`DiffEntry::OnlyInB`].
+pub fn diff_with_lookahead(
+    a: &[u8],
+    b: &[u8],
+    lookahead: usize,
+) -> Result<Vec<DiffEntry>, PacketSliceError> {
+    let a = content_keys(a)?;
+    let b = content_keys(b)?;
+
+    // position of `key` in `keys[start + 1..]` (relative to start)
+    let find = |keys: &[ContentKey], start: usize, key: &ContentKey| {
+        let end = keys
+            .len()
+            .min(start.saturating_add(1).saturating_add(lookahead));
+        keys[start + 1..end]
+            .iter()
+            .position(|k| k == key)
+            .map(|pos| pos + 1)
+    };
+
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            i += 1;
+            j += 1;
+            continue;
+        }
+        match (find(&b, j, &a[i]), find(&a, i, &b[j])) {
+            // messages were inserted in b
+            (Some(skip_b), skip_a) if skip_a.map(|s| skip_b <= s).unwrap_or(true) => {
+                result.extend((j..j + skip_b).map(|index_b| DiffEntry::OnlyInB {
+                    index_b,
+                    key: b[index_b],
+                }));
+                j += skip_b;
+            }
+            // messages were removed from a
+            (_, Some(skip_a)) => {
+                result.extend((i..i + skip_a).map(|index_a| DiffEntry::OnlyInA {
+                    index_a,
+                    key: a[index_a],
+                }));
+                i += skip_a;
+            }
+            (_, None) => {
+                if a[i].application_id == b[j].application_id
+                    && a[i].context_id == b[j].context_id
+                    && a[i].message_info == b[j].message_info
+                {
+                    result.push(DiffEntry::Changed {
+                        index_a: i,
+                        index_b: j,
+                        key_a: a[i],
+                        key_b: b[j],
+                    });
+                } else {
+                    result.push(DiffEntry::OnlyInA {
+                        index_a: i,
+                        key: a[i],
+                    });
+                    result.push(DiffEntry::OnlyInB {
+                        index_b: j,
+                        key: b[j],
+                    });
+                }
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    result.extend((i..a.len()).map(|index_a| DiffEntry::OnlyInA {
+        index_a,
+        key: a[index_a],
+    }));
+    result.extend((j..b.len()).map(|index_b| DiffEntry::OnlyInB {
+        index_b,
+        key: b[index_b],
+    }));
+    Ok(result)
+}
+
+fn content_keys(slice: &[u8]) -> Result<Vec<ContentKey>, PacketSliceError> {
+    SliceIterator::new(slice)
+        .map(|packet| packet.map(|p| p.content_key()))
+        .collect()
+}
+
+#[cfg(test)]
+mod capture_diff_tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use crate::test_packets;
+    use crate::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    fn packet(app_id: [u8; 4], payload: &[u8]) -> Vec<u8> {
+        let ext = DltExtendedHeader::new_non_verbose_log(DltLogLevel::Info, app_id, *b"CTX1");
+        test_packets::packet(test_packets::header(true, Some(ext)), payload)
+    }
+
+    fn key(bytes: &[u8]) -> ContentKey {
+        DltPacketSlice::from_slice(bytes).unwrap().content_key()
+    }
+
+    proptest! {
+        #[test]
+        fn same_capture(ref packets in prop::collection::vec(dlt_header_with_payload_any(), 0..8)) {
+            let mut buffer = Vec::new();
+            for (header, payload) in packets {
+                buffer.extend_from_slice(&header.to_bytes());
+                buffer.extend_from_slice(payload);
+            }
+            prop_assert_eq!(Ok(Vec::new()), diff(&buffer, &buffer));
+
+            // against an empty capture everything is only present in one
+            let all_a = diff(&buffer, &[]).unwrap();
+            prop_assert_eq!(packets.len(), all_a.len());
+            let only_a = all_a.iter().all(|e| matches!(e, DiffEntry::OnlyInA { .. }));
+            prop_assert!(only_a);
+            let all_b = diff(&[], &buffer).unwrap();
+            prop_assert_eq!(packets.len(), all_b.len());
+            let only_b = all_b.iter().all(|e| matches!(e, DiffEntry::OnlyInB { .. }));
+            prop_assert!(only_b);
+        }
+    }
+
+    #[test]
+    fn inserted_removed() {
+        let p0 = packet(*b"APP0", b"0");
+        let p1 = packet(*b"APP1", b"1");
+        let p2 = packet(*b"APP2", b"2");
+        let p3 = packet(*b"APP3", b"3");
+
+        // inserted in b
+        let a = [p0.clone(), p2.clone()].concat();
+        let b = [p0.clone(), p1.clone(), p3.clone(), p2.clone()].concat();
+        assert_eq!(
+            Ok(vec![
+                DiffEntry::OnlyInB {
+                    index_b: 1,
+                    key: key(&p1)
+                },
+                DiffEntry::OnlyInB {
+                    index_b: 2,
+                    key: key(&p3)
+                },
+            ]),
+            diff(&a, &b)
+        );
+
+        // removed from a
+        assert_eq!(
+            Ok(vec![
+                DiffEntry::OnlyInA {
+                    index_a: 1,
+                    key: key(&p1)
+                },
+                DiffEntry::OnlyInA {
+                    index_a: 2,
+                    key: key(&p3)
+                },
+            ]),
+            diff(&b, &a)
+        );
+
+        // trailing messages
+        let a = [p0.clone(), p1.clone()].concat();
+        let b = [p0.clone(), p2.clone()].concat();
+        assert_eq!(
+            Ok(vec![
+                DiffEntry::OnlyInA {
+                    index_a: 1,
+                    key: key(&p1)
+                },
+                DiffEntry::OnlyInB {
+                    index_b: 1,
+                    key: key(&p2)
+                },
+            ]),
+            diff(&a, &b)
+        );
+    }
+
+    #[test]
+    fn changed() {
+        let p0 = packet(*b"APP0", b"0");
+        let p1a = packet(*b"APP1", b"before");
+        let p1b = packet(*b"APP1", b"after");
+        let p2 = packet(*b"APP2", b"2");
+
+        let a = [p0.clone(), p1a.clone(), p2.clone()].concat();
+        let b = [p0.clone(), p1b.clone(), p2.clone()].concat();
+        assert_eq!(
+            Ok(vec![DiffEntry::Changed {
+                index_a: 1,
+                index_b: 1,
+                key_a: key(&p1a),
+                key_b: key(&p1b),
+            }]),
+            diff(&a, &b)
+        );
+    }
+
+    #[test]
+    fn lookahead() {
+        let p0 = packet(*b"APP0", b"0");
+        let p1 = packet(*b"APP1", b"1");
+        let p2 = packet(*b"APP2", b"2");
+
+        let a = [p0.clone(), p2.clone()].concat();
+        let b = [p0.clone(), p1.clone(), p2.clone()].concat();
+
+        // p2 is outside of the window -> p1 & p2 are reported as different
+        assert_eq!(
+            Ok(vec![
+                DiffEntry::OnlyInA {
+                    index_a: 1,
+                    key: key(&p2)
+                },
+                DiffEntry::OnlyInB {
+                    index_b: 1,
+                    key: key(&p1)
+                },
+                DiffEntry::OnlyInB {
+                    index_b: 2,
+                    key: key(&p2)
+                },
+            ]),
+            diff_with_lookahead(&a, &b, 0)
+        );
+        assert_eq!(
+            Ok(vec![DiffEntry::OnlyInB {
+                index_b: 1,
+                key: key(&p1)
+            }]),
+            diff_with_lookahead(&a, &b, 1)
+        );
+    }
+
+    #[test]
+    fn decode_error() {
+        let p0 = packet(*b"APP0", b"0");
+        let truncated = &p0[..p0.len() - 1];
+        assert!(diff(truncated, &p0).is_err());
+        assert!(diff(&p0, truncated).is_err());
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let entry = DiffEntry::OnlyInA {
+            index_a: 0,
+            key: key(&packet(*b"APP0", b"0")),
+        };
+        assert_eq!(entry, entry.clone());
+        assert!(format!("{:?}", entry).starts_with("OnlyInA {"));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::DltMessageInfo;
+
+/// Key identifying the content of a dlt message (see [`crate::DltPacketSlice::content_key`]).
+///
+/// The key intentionally excludes the message counter, session id, ecu id
+/// & timestamp so duplicate messages can be detected even if they were
+/// recorded with differing counters or timestamps (e.g. when merging
+/// multiple captures).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ContentKey {
+    /// Application id (if an extended header is present).
+    pub application_id: Option<[u8; 4]>,
+    /// Context id (if an extended header is present).
+    pub context_id: Option<[u8; 4]>,
+    /// Message info containing the message type & verbose flag
+    /// (if an extended header is present).
+    pub message_info: Option<DltMessageInfo>,
+    /// Fingerprint of the payload (see [`crate::DltPacketSlice::payload_fingerprint`]).
+    pub payload_fingerprint: u64,
+}
+
+/// Calculates the 64 bit FNV-1a hash of the given data.
+pub(crate) fn fnv1a_64(data: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    for byte in data {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq() {
+        let key = ContentKey {
+            application_id: Some([1, 2, 3, 4]),
+            context_id: None,
+            message_info: Some(DltMessageInfo(0x41)),
+            payload_fingerprint: 123,
+        };
+        assert_eq!(key, key.clone());
+        assert_eq!(
+            format!(
+                "ContentKey {{ application_id: {:?}, context_id: {:?}, message_info: {:?}, payload_fingerprint: {:?} }}",
+                key.application_id, key.context_id, key.message_info, key.payload_fingerprint
+            ),
+            format!("{:?}", key)
+        );
+    }
+
+    #[test]
+    fn fnv1a_64_reference() {
+        // reference values of the FNV-1a 64 bit hash
+        assert_eq!(0xcbf2_9ce4_8422_2325, fnv1a_64(b""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fnv1a_64(b"a"));
+        assert_eq!(0x8594_4171_f739_67e8, fnv1a_64(b"foobar"));
+    }
+}
@@ -0,0 +1,330 @@
+use super::context_params::{add_service_id, check_service_id, parse_response_status};
+use super::{ControlRequest, ControlResponse, ControlResponseStatus, DltServiceId};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Defines a control request with a single boolean "new status" byte as
+/// parameter & a response only consisting of the status.
+macro_rules! bool_status_request {
+    ($(#[$doc:meta])* $name:ident, $service:ident = $id:literal, $desc:literal) => {
+        $(#[$doc])*
+        ///
+        /// The parameters consist of a single byte (0 = off, 1 = on) & the
+        /// response only contains the status.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// # #[cfg(feature = "std")]
+        /// # {
+        #[doc = concat!("use dlt_parse::control::{ControlRequest, ", stringify!($name), "};")]
+        /// use dlt_parse::DltPacketSlice;
+        ///
+        #[doc = concat!("let request = ", stringify!($name), " { new_status: true };")]
+        /// let packet_bytes = request.build(true);
+        ///
+        /// let packet = DltPacketSlice::from_slice(&packet_bytes).unwrap();
+        /// let control = ControlRequest::from_packet(&packet).unwrap();
+        #[doc = concat!("assert_eq!(Ok(request), ", stringify!($name), "::parse(&control));")]
+        /// # }
+        /// ```
+        #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+        pub struct $name {
+            /// True if the setting should be turned on.
+            pub new_status: bool,
+        }
+
+        impl $name {
+            #[doc = concat!("Service id of the \"", $desc, "\" control message.")]
+            pub const SERVICE_ID: u32 = $id;
+
+            /// Length of the parameters (without service id).
+            pub const PARAMS_LEN: usize = 1;
+
+            #[doc = concat!("Decodes the parameters of a \"", $desc, "\" request.")]
+            ///
+            /// Data after the parameters is ignored.
+            pub fn parse(request: &ControlRequest<'_>) -> Result<$name, ControlDecodeError> {
+                check_service_id(DltServiceId::$service, request.service_id)?;
+                let new_status = match request.params_reader().read_u8()? {
+                    0 => false,
+                    1 => true,
+                    value => return Err(ControlDecodeError::InvalidBool(value)),
+                };
+                Ok($name { new_status })
+            }
+
+            #[doc = concat!("Returns the status of the response to a \"", $desc, "\" request.")]
+            #[inline]
+            pub fn parse_response(
+                response: &ControlResponse<'_>,
+            ) -> Result<ControlResponseStatus, ControlDecodeError> {
+                parse_response_status(DltServiceId::$service, response)
+            }
+
+            /// Adds the control payload (including the service id) to the given buffer.
+            pub fn add_to_msg<const CAP: usize>(
+                &self,
+                buf: &mut ArrayVec<u8, CAP>,
+                is_big_endian: bool,
+            ) -> Result<(), CapacityError> {
+                if buf.remaining_capacity() < 4 + $name::PARAMS_LEN {
+                    return Err(CapacityError::new(()));
+                }
+                add_service_id(DltServiceId::$service, buf, is_big_endian)?;
+                buf.try_extend_from_slice(&[u8::from(self.new_status)])
+            }
+
+            /// Returns the complete DLT packet of the request (non verbose
+            /// control request with the application & context id
+            /// [`ControlRequest::DEFAULT_APPLICATION_ID`] &
+            /// [`ControlRequest::DEFAULT_CONTEXT_ID`]).
+            #[cfg(feature = "std")]
+            #[inline]
+            pub fn build(&self, is_big_endian: bool) -> Vec<u8> {
+                ControlRequest::build_service_packet(
+                    DltServiceId::$service,
+                    is_big_endian,
+                    &[u8::from(self.new_status)],
+                )
+            }
+        }
+    };
+}
+
+bool_status_request!(
+    /// "Set verbose mode" control request (service id 0x09) switching the
+    /// ECU between verbose & non verbose logging.
+    SetVerboseModeRequest,
+    SetVerboseMode = 0x09,
+    "set verbose mode"
+);
+
+bool_status_request!(
+    /// "Set message filtering" control request (service id 0x0A) turning
+    /// the message filters of the ECU on or off.
+    ///
+    /// As disabling the filters changes which messages leave the ECU, the
+    /// [`fmt::Display`] output explicitly states the new filtering state.
+    SetMessageFilteringRequest,
+    SetMessageFiltering = 0x0A,
+    "set message filtering"
+);
+
+impl fmt::Display for SetMessageFilteringRequest {
+    /// Writes the service name & the new filtering state (e.g.
+    /// `set_message_filtering: filtering on`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: filtering {}",
+            DltServiceId::SetMessageFiltering,
+            if self.new_status { "on" } else { "off" }
+        )
+    }
+}
+
+bool_status_request!(
+    /// "Set timing packets" control request (service id 0x0B) turning the
+    /// sending of timing packets on or off.
+    SetTimingPacketsRequest,
+    SetTimingPackets = 0x0B,
+    "set timing packets"
+);
+
+#[cfg(test)]
+mod bool_status_requests_tests {
+    use super::*;
+    use crate::error::{Layer, UnexpectedEndOfSliceError};
+    #[cfg(feature = "std")]
+    use crate::{DltControlMessageType, DltMessageType, DltPacketSlice};
+    use alloc::format;
+
+    macro_rules! request_tests {
+        ($mod_name:ident, $name:ident, $service:ident, $id:literal, $other:ident) => {
+            mod $mod_name {
+                use super::*;
+
+                #[cfg(feature = "std")]
+                #[test]
+                fn build_parse() {
+                    assert_eq!($id, DltServiceId::$service.to_u32());
+                    assert_eq!($id, $name::SERVICE_ID);
+                    for is_big_endian in [false, true] {
+                        for new_status in [false, true] {
+                            let request = $name { new_status };
+                            let bytes = request.build(is_big_endian);
+                            let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+
+                            // standard header: version 1, extended header &
+                            // the endianness flag (no ecu id, session id or timestamp)
+                            let expected_header_type = if is_big_endian {
+                                0b0010_0011
+                            } else {
+                                0b0010_0001
+                            };
+                            assert_eq!(expected_header_type, bytes[0]);
+
+                            // extended header: non verbose control request
+                            // (message type 3, message type info 1) without arguments
+                            assert_eq!(
+                                &[0b0001_0110, 0, b'A', b'P', b'P', 0, b'C', b'O', b'N', 0],
+                                &bytes[4..14]
+                            );
+                            assert_eq!(
+                                Some(DltMessageType::Control(DltControlMessageType::Request)),
+                                packet.message_type()
+                            );
+
+                            // payload: service id in packet endianness & status
+                            let mut expected_payload = if is_big_endian {
+                                [0, 0, 0, $id, 0]
+                            } else {
+                                [$id, 0, 0, 0, 0]
+                            };
+                            expected_payload[4] = u8::from(new_status);
+                            assert_eq!(&expected_payload, packet.payload());
+
+                            let control = ControlRequest::from_packet(&packet).unwrap();
+                            assert_eq!(DltServiceId::$service, control.service_id);
+                            assert_eq!(Ok(request), $name::parse(&control));
+
+                            let mut buf = ArrayVec::<u8, 5>::new();
+                            request.add_to_msg(&mut buf, is_big_endian).unwrap();
+                            assert_eq!(packet.payload(), &buf[..]);
+                        }
+                    }
+
+                    // capacity error
+                    let mut small = ArrayVec::<u8, 4>::new();
+                    assert_eq!(
+                        Err(CapacityError::new(())),
+                        $name::default().add_to_msg(&mut small, true)
+                    );
+                    assert!(small.is_empty());
+                }
+
+                #[test]
+                fn parse_errors() {
+                    // wrong service id
+                    let request = ControlRequest {
+                        service_id: DltServiceId::$other,
+                        params: &[1],
+                        is_big_endian: true,
+                        payload_offset: 0,
+                    };
+                    assert_eq!(
+                        Err(ControlDecodeError::UnexpectedServiceId {
+                            expected: DltServiceId::$service,
+                            actual: DltServiceId::$other,
+                        }),
+                        $name::parse(&request)
+                    );
+
+                    // missing status
+                    let request = ControlRequest {
+                        service_id: DltServiceId::$service,
+                        params: &[],
+                        is_big_endian: true,
+                        payload_offset: 0,
+                    };
+                    assert_eq!(
+                        Err(ControlDecodeError::UnexpectedEndOfSlice(
+                            UnexpectedEndOfSliceError {
+                                layer: Layer::ControlMessage,
+                                minimum_size: 5,
+                                actual_size: 4,
+                            }
+                        )),
+                        $name::parse(&request)
+                    );
+
+                    // invalid boolean
+                    for value in 2..=u8::MAX {
+                        let params = [value];
+                        let request = ControlRequest {
+                            service_id: DltServiceId::$service,
+                            params: &params,
+                            is_big_endian: true,
+                            payload_offset: 0,
+                        };
+                        assert_eq!(
+                            Err(ControlDecodeError::InvalidBool(value)),
+                            $name::parse(&request)
+                        );
+                    }
+                }
+
+                #[test]
+                fn response() {
+                    for (status, expected) in [
+                        (0, ControlResponseStatus::Ok),
+                        (1, ControlResponseStatus::NotSupported),
+                        (2, ControlResponseStatus::Error),
+                    ] {
+                        let payload = [$id, 0, 0, 0, status];
+                        let response = ControlResponse::from_slice(&payload, false).unwrap();
+                        assert_eq!(Ok(expected), $name::parse_response(&response));
+                    }
+                    let response = ControlResponse::from_slice(&[0, 0, 0, 0x01, 0], true).unwrap();
+                    assert_eq!(
+                        Err(ControlDecodeError::UnexpectedServiceId {
+                            expected: DltServiceId::$service,
+                            actual: DltServiceId::SetLogLevel,
+                        }),
+                        $name::parse_response(&response)
+                    );
+                }
+
+                #[test]
+                fn debug_clone_eq() {
+                    let request = $name { new_status: true };
+                    assert_eq!(request, request.clone());
+                    assert_eq!(
+                        concat!(stringify!($name), " { new_status: true }"),
+                        format!("{:?}", request)
+                    );
+                }
+            }
+        };
+    }
+
+    request_tests!(
+        set_verbose_mode,
+        SetVerboseModeRequest,
+        SetVerboseMode,
+        0x09,
+        SetTimingPackets
+    );
+
+    request_tests!(
+        set_message_filtering,
+        SetMessageFilteringRequest,
+        SetMessageFiltering,
+        0x0A,
+        SetVerboseMode
+    );
+
+    #[test]
+    fn set_message_filtering_display() {
+        assert_eq!(
+            "set_message_filtering: filtering on",
+            format!("{}", SetMessageFilteringRequest { new_status: true })
+        );
+        assert_eq!(
+            "set_message_filtering: filtering off",
+            format!("{}", SetMessageFilteringRequest { new_status: false })
+        );
+    }
+
+    request_tests!(
+        set_timing_packets,
+        SetTimingPacketsRequest,
+        SetTimingPackets,
+        0x0B,
+        SetVerboseMode
+    );
+}
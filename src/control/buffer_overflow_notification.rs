@@ -0,0 +1,171 @@
+use super::context_params::{add_service_id, check_service_id};
+use super::{ControlResponse, ControlResponseStatus, DltServiceId};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+
+/// "Buffer overflow notification" sent by an ECU (service id 0x23) to
+/// report that messages were dropped because its message buffer was full.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{BufferOverflowNotification, ControlResponse, ControlResponseStatus};
+///
+/// // service id, status & overflow counter (big endian)
+/// let payload = [0, 0, 0, 0x23, 0, 0, 0, 0, 42];
+/// let response = ControlResponse::from_slice(&payload, true).unwrap();
+/// assert_eq!(
+///     Ok(BufferOverflowNotification {
+///         status: ControlResponseStatus::Ok,
+///         overflow_counter: 42,
+///     }),
+///     BufferOverflowNotification::parse(&response)
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BufferOverflowNotification {
+    /// Status of the notification.
+    pub status: ControlResponseStatus,
+    /// Number of messages dropped by the ECU.
+    pub overflow_counter: u32,
+}
+
+impl BufferOverflowNotification {
+    /// Service id of the "buffer overflow notification" control message.
+    pub const SERVICE_ID: u32 = 0x23;
+
+    /// Length of the control payload (service id, status & overflow counter).
+    pub const PAYLOAD_LEN: usize = 9;
+
+    /// Decodes the notification (sent as a control response).
+    pub fn parse(
+        response: &ControlResponse<'_>,
+    ) -> Result<BufferOverflowNotification, ControlDecodeError> {
+        check_service_id(
+            DltServiceId::BufferOverflowNotification,
+            response.service_id,
+        )?;
+        Ok(BufferOverflowNotification {
+            status: response.status,
+            overflow_counter: response.data_reader().read_u32()?,
+        })
+    }
+
+    /// Adds the control payload (service id, status & overflow counter)
+    /// to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        if buf.remaining_capacity() < BufferOverflowNotification::PAYLOAD_LEN {
+            return Err(CapacityError::new(()));
+        }
+        add_service_id(DltServiceId::BufferOverflowNotification, buf, is_big_endian)?;
+        buf.try_extend_from_slice(&[self.status.to_u8()])?;
+        if is_big_endian {
+            buf.try_extend_from_slice(&self.overflow_counter.to_be_bytes())
+        } else {
+            buf.try_extend_from_slice(&self.overflow_counter.to_le_bytes())
+        }
+    }
+}
+
+#[cfg(test)]
+mod buffer_overflow_notification_tests {
+    use super::*;
+    use crate::error::{Layer, UnexpectedEndOfSliceError};
+    use crate::{DltPacketSlice, SliceIterator};
+    use alloc::format;
+    use proptest::prelude::*;
+
+    /// Notification of 42 dropped messages in the layout sent by the
+    /// dlt-daemon (little endian with ecu id & timestamp).
+    const FIXTURE: &[u8] = include_bytes!("../../tests/fixtures/buffer_overflow_notification.dlt");
+
+    #[test]
+    fn fixture() {
+        let mut iter = SliceIterator::new(FIXTURE);
+        let packet = iter.next().unwrap().unwrap();
+        assert!(iter.next().is_none());
+
+        assert_eq!(Some(*b"ECU1"), packet.ecu_id());
+        let response = ControlResponse::from_packet(&packet).unwrap();
+        assert_eq!(
+            Ok(BufferOverflowNotification {
+                status: ControlResponseStatus::Ok,
+                overflow_counter: 42,
+            }),
+            BufferOverflowNotification::parse(&response)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn add_to_msg_parse(
+            is_big_endian in any::<bool>(),
+            status in any::<u8>(),
+            overflow_counter in any::<u32>(),
+        ) {
+            let expected = BufferOverflowNotification {
+                status: ControlResponseStatus::from_u8(status),
+                overflow_counter,
+            };
+            let mut buf = ArrayVec::<u8, { BufferOverflowNotification::PAYLOAD_LEN }>::new();
+            expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+            prop_assert_eq!(Ok(expected), BufferOverflowNotification::parse(&response));
+
+            // capacity error
+            let mut small = ArrayVec::<u8, { BufferOverflowNotification::PAYLOAD_LEN - 1 }>::new();
+            prop_assert_eq!(
+                Err(CapacityError::new(())),
+                expected.add_to_msg(&mut small, is_big_endian)
+            );
+            prop_assert!(small.is_empty());
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        // truncated overflow counter (fixture payload without the last byte)
+        let packet = DltPacketSlice::from_slice(FIXTURE).unwrap();
+        let payload = packet.payload();
+        let response =
+            ControlResponse::from_slice(&payload[..payload.len() - 1], packet.is_big_endian())
+                .unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 9,
+                    actual_size: 8,
+                }
+            )),
+            BufferOverflowNotification::parse(&response)
+        );
+
+        // wrong service id
+        let response = ControlResponse::from_slice(&[0, 0, 0, 0x14, 0, 0, 0, 0, 1], true).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::BufferOverflowNotification,
+                actual: DltServiceId::MessageBufferOverflow,
+            }),
+            BufferOverflowNotification::parse(&response)
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let notification = BufferOverflowNotification {
+            status: ControlResponseStatus::Ok,
+            overflow_counter: 1,
+        };
+        assert_eq!(notification, notification.clone());
+        assert_eq!(
+            "BufferOverflowNotification { status: Ok, overflow_counter: 1 }",
+            format!("{:?}", notification)
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use super::{ControlRequest, ControlResponse, ControlResponseStatus, DltServiceId};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+
+/// Parameters shared by the control requests changing a setting of a
+/// single context ("set log level" & "set trace status"):
+///
+/// ```text
+/// | app id (4 bytes) | context id (4 bytes) | value (i8) | com interface (4 bytes) |
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct ContextParams {
+    pub app_id: [u8; 4],
+    pub ctx_id: [u8; 4],
+    pub value: i8,
+    pub com_interface: [u8; 4],
+}
+
+impl ContextParams {
+    /// Length of the parameters (without service id).
+    pub const LEN: usize = 13;
+
+    /// Decodes the parameters of a request with the given service id.
+    pub fn parse(
+        expected: DltServiceId,
+        request: &ControlRequest<'_>,
+    ) -> Result<ContextParams, ControlDecodeError> {
+        check_service_id(expected, request.service_id)?;
+        let mut reader = request.params_reader();
+        Ok(ContextParams {
+            app_id: reader.read_id()?,
+            ctx_id: reader.read_id()?,
+            value: reader.read_i8()?,
+            com_interface: reader.read_id()?,
+        })
+    }
+
+    /// Adds the service id & the parameters to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        service_id: DltServiceId,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        if buf.remaining_capacity() < 4 + ContextParams::LEN {
+            return Err(CapacityError::new(()));
+        }
+        add_service_id(service_id, buf, is_big_endian)?;
+        buf.try_extend_from_slice(&self.app_id)?;
+        buf.try_extend_from_slice(&self.ctx_id)?;
+        buf.try_extend_from_slice(&self.value.to_ne_bytes())?;
+        buf.try_extend_from_slice(&self.com_interface)
+    }
+}
+
+/// Adds the service id in the given endianness to the buffer.
+pub(crate) fn add_service_id<const CAP: usize>(
+    service_id: DltServiceId,
+    buf: &mut ArrayVec<u8, CAP>,
+    is_big_endian: bool,
+) -> Result<(), CapacityError> {
+    let service_id = service_id.to_u32();
+    if is_big_endian {
+        buf.try_extend_from_slice(&service_id.to_be_bytes())
+    } else {
+        buf.try_extend_from_slice(&service_id.to_le_bytes())
+    }
+}
+
+/// Returns an [`ControlDecodeError::UnexpectedServiceId`] error if the
+/// service ids differ.
+pub(crate) fn check_service_id(
+    expected: DltServiceId,
+    actual: DltServiceId,
+) -> Result<(), ControlDecodeError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(ControlDecodeError::UnexpectedServiceId { expected, actual })
+    }
+}
+
+/// Returns the status of a response with the given service id.
+pub(crate) fn parse_response_status(
+    expected: DltServiceId,
+    response: &ControlResponse<'_>,
+) -> Result<ControlResponseStatus, ControlDecodeError> {
+    check_service_id(expected, response.service_id)?;
+    Ok(response.status)
+}
+
+/// Returns the status & the single value byte following it of a response
+/// with the given service id.
+///
+/// Responses with a status other than [`ControlResponseStatus::Ok`] are
+/// allowed to end after the status (the value is then `None`). Data after
+/// the value byte is ignored.
+pub(crate) fn parse_status_and_value(
+    expected: DltServiceId,
+    response: &ControlResponse<'_>,
+) -> Result<(ControlResponseStatus, Option<u8>), ControlDecodeError> {
+    check_service_id(expected, response.service_id)?;
+    let value = if response.data.is_empty() && response.status != ControlResponseStatus::Ok {
+        None
+    } else {
+        Some(response.data_reader().read_u8()?)
+    };
+    Ok((response.status, value))
+}
+
+/// Adds the service id, status & value byte (if present) of a response
+/// to the given buffer (nothing is added if they do not fit).
+pub(crate) fn add_status_and_value<const CAP: usize>(
+    service_id: DltServiceId,
+    status: ControlResponseStatus,
+    value: Option<u8>,
+    buf: &mut ArrayVec<u8, CAP>,
+    is_big_endian: bool,
+) -> Result<(), CapacityError> {
+    let len = if value.is_some() { 6 } else { 5 };
+    if buf.remaining_capacity() < len {
+        return Err(CapacityError::new(()));
+    }
+    add_service_id(service_id, buf, is_big_endian)?;
+    buf.try_extend_from_slice(&[status.to_u8()])?;
+    if let Some(value) = value {
+        buf.try_extend_from_slice(&[value])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod context_params_tests {
+    use super::*;
+    use crate::error::{Layer, UnexpectedEndOfSliceError};
+    use alloc::format;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn add_to_msg_parse(
+            app_id in any::<[u8; 4]>(),
+            ctx_id in any::<[u8; 4]>(),
+            value in any::<i8>(),
+            com_interface in any::<[u8; 4]>(),
+            is_big_endian in any::<bool>(),
+        ) {
+            let params = ContextParams { app_id, ctx_id, value, com_interface };
+            let mut buf = ArrayVec::<u8, 17>::new();
+            params.add_to_msg(DltServiceId::SetTraceStatus, &mut buf, is_big_endian).unwrap();
+            let request = ControlRequest::from_slice(&buf, is_big_endian).unwrap();
+            prop_assert_eq!(DltServiceId::SetTraceStatus, request.service_id);
+            prop_assert_eq!(Ok(params.clone()), ContextParams::parse(DltServiceId::SetTraceStatus, &request));
+
+            // too short
+            for len in 0..ContextParams::LEN {
+                let short = ControlRequest { params: &request.params[..len], ..request.clone() };
+                prop_assert!(matches!(
+                    ContextParams::parse(DltServiceId::SetTraceStatus, &short),
+                    Err(ControlDecodeError::UnexpectedEndOfSlice(_))
+                ));
+            }
+
+            // wrong service id
+            prop_assert_eq!(
+                Err(ControlDecodeError::UnexpectedServiceId {
+                    expected: DltServiceId::SetLogLevel,
+                    actual: DltServiceId::SetTraceStatus,
+                }),
+                ContextParams::parse(DltServiceId::SetLogLevel, &request)
+            );
+
+            // capacity error
+            let mut small = ArrayVec::<u8, 16>::new();
+            prop_assert_eq!(
+                Err(CapacityError::new(())),
+                params.add_to_msg(DltServiceId::SetTraceStatus, &mut small, is_big_endian)
+            );
+            prop_assert!(small.is_empty());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn status_and_value(
+            status in any::<u8>(),
+            value in proptest::option::of(any::<u8>()),
+            is_big_endian in any::<bool>(),
+        ) {
+            let status = ControlResponseStatus::from_u8(status);
+            let mut buf = ArrayVec::<u8, 6>::new();
+            add_status_and_value(DltServiceId::GetUseEcuId, status, value, &mut buf, is_big_endian).unwrap();
+            prop_assert_eq!(if value.is_some() { 6 } else { 5 }, buf.len());
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+
+            let result = parse_status_and_value(DltServiceId::GetUseEcuId, &response);
+            if value.is_none() && ControlResponseStatus::Ok == status {
+                // the value is required for ok responses
+                prop_assert_eq!(
+                    Err(ControlDecodeError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                        layer: Layer::ControlMessage,
+                        minimum_size: 6,
+                        actual_size: 5,
+                    })),
+                    result
+                );
+            } else {
+                prop_assert_eq!(Ok((status, value)), result);
+            }
+
+            // wrong service id
+            prop_assert_eq!(
+                Err(ControlDecodeError::UnexpectedServiceId {
+                    expected: DltServiceId::GetUseTimestamp,
+                    actual: DltServiceId::GetUseEcuId,
+                }),
+                parse_status_and_value(DltServiceId::GetUseTimestamp, &response)
+            );
+
+            // capacity error
+            let mut small = ArrayVec::<u8, 4>::new();
+            prop_assert_eq!(
+                Err(CapacityError::new(())),
+                add_status_and_value(DltServiceId::GetUseEcuId, status, value, &mut small, is_big_endian)
+            );
+            prop_assert!(small.is_empty());
+        }
+    }
+
+    #[test]
+    fn missing_com_interface() {
+        let request = ControlRequest {
+            service_id: DltServiceId::SetLogLevel,
+            params: b"APP1CTX1\x04rem",
+            is_big_endian: false,
+            payload_offset: 0,
+        };
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 17,
+                    actual_size: 16,
+                }
+            )),
+            ContextParams::parse(DltServiceId::SetLogLevel, &request)
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let params = ContextParams {
+            app_id: *b"APP\0",
+            ctx_id: *b"CTX\0",
+            value: 1,
+            com_interface: *b"remo",
+        };
+        assert_eq!(params, params.clone());
+        assert!(format!("{:?}", params).starts_with("ContextParams {"));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::error::ControlDecodeError;
+use crate::DltLogLevel;
+
+/// Log level as encoded in control messages (e.g. "set log level").
+///
+/// In contrast to [`DltLogLevel`] (the levels of log messages) control
+/// messages can also set the level to "off" (0) or reset it to the
+/// default level (-1).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::ControlLogLevel;
+/// use dlt_parse::DltLogLevel;
+///
+/// assert_eq!(Ok(ControlLogLevel::Default), ControlLogLevel::from_i8(-1));
+/// assert_eq!(Ok(ControlLogLevel::Level(DltLogLevel::Warn)), ControlLogLevel::from_i8(3));
+/// assert_eq!(-1, ControlLogLevel::Default.to_i8());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ControlLogLevel {
+    /// Use the default log level (-1).
+    Default,
+    /// Logging is turned off (0).
+    Off,
+    /// Messages up to the given level are logged (1..=6).
+    Level(DltLogLevel),
+}
+
+impl ControlLogLevel {
+    /// Decodes the log level value used in control messages.
+    pub fn from_i8(value: i8) -> Result<ControlLogLevel, ControlDecodeError> {
+        use ControlLogLevel::*;
+        use DltLogLevel::*;
+        Ok(match value {
+            -1 => Default,
+            0 => Off,
+            1 => Level(Fatal),
+            2 => Level(Error),
+            3 => Level(Warn),
+            4 => Level(Info),
+            5 => Level(Debug),
+            6 => Level(Verbose),
+            value => return Err(ControlDecodeError::InvalidLogLevel(value)),
+        })
+    }
+
+    /// Returns the value used to encode the log level in control messages.
+    pub fn to_i8(self) -> i8 {
+        match self {
+            ControlLogLevel::Default => -1,
+            ControlLogLevel::Off => 0,
+            ControlLogLevel::Level(level) => level as i8,
+        }
+    }
+}
+
+impl From<DltLogLevel> for ControlLogLevel {
+    #[inline]
+    fn from(value: DltLogLevel) -> ControlLogLevel {
+        ControlLogLevel::Level(value)
+    }
+}
+
+#[cfg(test)]
+mod control_log_level_tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn from_to_i8() {
+        use ControlLogLevel::*;
+        for value in i8::MIN..=i8::MAX {
+            let result = ControlLogLevel::from_i8(value);
+            if (-1..=6).contains(&value) {
+                assert_eq!(value, result.unwrap().to_i8());
+            } else {
+                assert_eq!(Err(ControlDecodeError::InvalidLogLevel(value)), result);
+            }
+        }
+        assert_eq!(Ok(Default), ControlLogLevel::from_i8(-1));
+        assert_eq!(Ok(Off), ControlLogLevel::from_i8(0));
+        assert_eq!(Ok(Level(DltLogLevel::Verbose)), ControlLogLevel::from_i8(6));
+        assert_eq!(Level(DltLogLevel::Info), DltLogLevel::Info.into());
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let level = ControlLogLevel::Off;
+        assert_eq!(level, level.clone());
+        assert_eq!("Off", format!("{:?}", level));
+    }
+}
@@ -0,0 +1,338 @@
+use super::{DltServiceId, PayloadReader};
+use crate::error::{ControlDecodeError, Layer, UnexpectedEndOfSliceError};
+use crate::{DltControlMessageType, DltMessageType, DltPacketSlice};
+#[cfg(feature = "std")]
+use crate::{DltExtendedHeader, DltHeader};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Control request (service id & the parameters following it).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{ControlRequest, DltServiceId};
+///
+/// // "set log level" request payload (big endian)
+/// let payload = [
+///     0, 0, 0, 0x01, // service id
+///     b'A', b'P', b'P', b'1', // application id
+///     b'C', b'T', b'X', b'1', // context id
+///     4, // log level
+///     b'r', b'e', b'm', b'o', // com interface
+/// ];
+/// let request = ControlRequest::from_slice(&payload, true).unwrap();
+/// assert_eq!(DltServiceId::SetLogLevel, request.service_id);
+/// assert_eq!(&payload[4..], request.params);
+///
+/// let mut reader = request.params_reader();
+/// assert_eq!(Ok(*b"APP1"), reader.read_id());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ControlRequest<'a> {
+    /// Service id of the request.
+    pub service_id: DltServiceId,
+    /// Parameters of the request (payload after the service id).
+    pub params: &'a [u8],
+    /// True if the parameters are encoded in big endian.
+    pub is_big_endian: bool,
+    /// Offset of the payload (starting with the service id) from the start
+    /// of the DLT message (0 if the request was not decoded from a packet).
+    pub payload_offset: usize,
+}
+
+impl<'a> ControlRequest<'a> {
+    /// Application id used in the extended header of control requests
+    /// by the dlt-daemon client library.
+    pub const DEFAULT_APPLICATION_ID: [u8; 4] = *b"APP\0";
+
+    /// Context id used in the extended header of control requests
+    /// by the dlt-daemon client library.
+    pub const DEFAULT_CONTEXT_ID: [u8; 4] = *b"CON\0";
+
+    /// Decodes the control request from the payload of a control message
+    /// (starting with the service id).
+    pub fn from_slice(
+        payload: &'a [u8],
+        is_big_endian: bool,
+    ) -> Result<ControlRequest<'a>, ControlDecodeError> {
+        let mut reader = PayloadReader::new(payload, is_big_endian);
+        let service_id = DltServiceId::from_u32(reader.read_u32()?);
+        Ok(ControlRequest {
+            service_id,
+            params: reader.rest(),
+            is_big_endian,
+            payload_offset: 0,
+        })
+    }
+
+    /// Decodes the control request contained in the given packet.
+    ///
+    /// Returns [`ControlDecodeError::NotAControlRequest`] if the message
+    /// type of the packet is not "control request" & an unexpected end
+    /// of slice error if the payload is too short for a service id.
+    pub fn from_packet(
+        packet: &DltPacketSlice<'a>,
+    ) -> Result<ControlRequest<'a>, ControlDecodeError> {
+        match packet.message_type() {
+            Some(DltMessageType::Control(DltControlMessageType::Request)) => {}
+            other => return Err(ControlDecodeError::NotAControlRequest(other)),
+        }
+        let payload = packet.payload();
+        if payload.len() < 4 {
+            return Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 4,
+                    actual_size: payload.len(),
+                },
+            ));
+        }
+        let mut result = ControlRequest::from_slice(payload, packet.is_big_endian())?;
+        result.payload_offset = packet.slice().len() - payload.len();
+        Ok(result)
+    }
+
+    /// Returns the parameters together with their offset from the start
+    /// of the DLT message (see [`ControlRequest::payload_offset`]).
+    #[inline]
+    pub fn params_with_offset(&self) -> (&'a [u8], usize) {
+        (self.params, self.payload_offset + 4)
+    }
+
+    /// Returns a reader for the parameters using the endianness of the
+    /// request (offsets in errors are relative to the start of the DLT
+    /// message, see [`ControlRequest::params_with_offset`]).
+    #[inline]
+    pub fn params_reader(&self) -> PayloadReader<'a> {
+        let (params, offset) = self.params_with_offset();
+        PayloadReader::with_offset(params, self.is_big_endian, offset)
+    }
+
+    /// Returns a complete non verbose control request packet with the
+    /// given payload (service id & parameters) & the default application
+    /// & context id.
+    #[cfg(feature = "std")]
+    pub(crate) fn build_packet(is_big_endian: bool, payload: &[u8]) -> Vec<u8> {
+        let mut result = ControlRequest::packet_header(is_big_endian, payload.len());
+        result.extend_from_slice(payload);
+        result
+    }
+
+    /// Returns a complete non verbose control request packet with the
+    /// given service id (encoded in the given endianness) followed by
+    /// the parameters.
+    #[cfg(feature = "std")]
+    pub(crate) fn build_service_packet(
+        service_id: DltServiceId,
+        is_big_endian: bool,
+        params: &[u8],
+    ) -> Vec<u8> {
+        let mut result = ControlRequest::packet_header(is_big_endian, 4 + params.len());
+        let service_id = service_id.to_u32();
+        if is_big_endian {
+            result.extend_from_slice(&service_id.to_be_bytes());
+        } else {
+            result.extend_from_slice(&service_id.to_le_bytes());
+        }
+        result.extend_from_slice(params);
+        result
+    }
+
+    /// Returns a buffer containing the header of a control request with
+    /// the given payload length (with enough capacity for the payload).
+    #[cfg(feature = "std")]
+    fn packet_header(is_big_endian: bool, payload_len: usize) -> Vec<u8> {
+        let mut header = DltHeader {
+            is_big_endian,
+            message_counter: 0,
+            length: 0,
+            ecu_id: None,
+            session_id: None,
+            timestamp: None,
+            // can not fail as control message types are always in range
+            extended_header: Some(
+                DltExtendedHeader::new_non_verbose(
+                    DltMessageType::Control(DltControlMessageType::Request),
+                    ControlRequest::DEFAULT_APPLICATION_ID,
+                    ControlRequest::DEFAULT_CONTEXT_ID,
+                )
+                .unwrap(),
+            ),
+        };
+        header.length = header.header_len() + payload_len as u16;
+        let mut result = Vec::with_capacity(usize::from(header.length));
+        result.extend_from_slice(&header.to_bytes());
+        result
+    }
+}
+
+#[cfg(test)]
+mod control_request_tests {
+    use super::*;
+    use crate::test_packets;
+    use crate::*;
+    use alloc::vec::Vec;
+
+    fn packet(
+        message_type: Option<DltMessageType>,
+        is_big_endian: bool,
+        payload: &[u8],
+    ) -> Vec<u8> {
+        let ext = message_type
+            .map(|t| DltExtendedHeader::new_non_verbose(t, *b"APP\0", *b"CTX\0").unwrap());
+        test_packets::packet(test_packets::header(is_big_endian, ext), payload)
+    }
+
+    #[test]
+    fn params_with_offset() {
+        use crate::control::GetTraceStatusRequest;
+        use crate::error::UnexpectedEndOfSliceError;
+
+        // get trace status request with only 6 of the 8 parameter bytes
+        let payload = [0, 0, 0, 0x1F, b'A', b'P', b'P', b'1', b'C', b'T'];
+        for ecu_id in [None, Some(*b"ECU1")] {
+            for session_id in [None, Some(1234)] {
+                for timestamp in [None, Some(5678)] {
+                    let mut header = DltHeader {
+                        is_big_endian: true,
+                        message_counter: 0,
+                        length: 0,
+                        ecu_id,
+                        session_id,
+                        timestamp,
+                        extended_header: Some(
+                            DltExtendedHeader::new_non_verbose(
+                                DltMessageType::Control(DltControlMessageType::Request),
+                                *b"APP\0",
+                                *b"CTX\0",
+                            )
+                            .unwrap(),
+                        ),
+                    };
+                    header.length = header.header_len() + payload.len() as u16;
+                    let mut buf = Vec::new();
+                    buf.extend_from_slice(&header.to_bytes());
+                    buf.extend_from_slice(&payload);
+
+                    let slice = DltPacketSlice::from_slice(&buf).unwrap();
+                    let request = ControlRequest::from_packet(&slice).unwrap();
+                    let header_len = usize::from(header.header_len());
+                    assert_eq!(header_len, request.payload_offset);
+                    assert_eq!(
+                        (&payload[4..], header_len + 4),
+                        request.params_with_offset()
+                    );
+                    let (params, offset) = request.params_with_offset();
+                    assert_eq!(params, &buf[offset..]);
+
+                    // typed parsers report offsets relative to the message
+                    assert_eq!(
+                        Err(ControlDecodeError::UnexpectedEndOfSlice(
+                            UnexpectedEndOfSliceError {
+                                layer: Layer::ControlMessage,
+                                minimum_size: buf.len() + 2,
+                                actual_size: buf.len(),
+                            }
+                        )),
+                        GetTraceStatusRequest::parse(&request)
+                    );
+
+                    // without packet the offsets are relative to the payload
+                    let request = ControlRequest::from_slice(&payload, true).unwrap();
+                    assert_eq!((&payload[4..], 4), request.params_with_offset());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn set_log_level() {
+        let request_type = Some(DltMessageType::Control(DltControlMessageType::Request));
+        for is_big_endian in [false, true] {
+            let mut payload = Vec::new();
+            if is_big_endian {
+                payload.extend_from_slice(&1u32.to_be_bytes());
+            } else {
+                payload.extend_from_slice(&1u32.to_le_bytes());
+            }
+            payload.extend_from_slice(b"APP1CTX1");
+            payload.push(4);
+            payload.extend_from_slice(b"remo");
+
+            let buf = packet(request_type, is_big_endian, &payload);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            let request = ControlRequest::from_packet(&slice).unwrap();
+            assert_eq!(
+                ControlRequest {
+                    service_id: control::DltServiceId::SetLogLevel,
+                    params: &payload[4..],
+                    is_big_endian,
+                    payload_offset: buf.len() - payload.len(),
+                },
+                request
+            );
+            assert_eq!(
+                Ok(ControlRequest {
+                    payload_offset: 0,
+                    ..request.clone()
+                }),
+                ControlRequest::from_slice(&payload, is_big_endian)
+            );
+
+            let mut reader = request.params_reader();
+            assert_eq!(is_big_endian, reader.is_big_endian());
+            assert_eq!(buf.len() - payload.len() + 4, reader.offset());
+            assert_eq!(Ok(*b"APP1"), reader.read_id());
+            assert_eq!(Ok(*b"CTX1"), reader.read_id());
+            assert_eq!(Ok(4), reader.read_i8());
+            assert_eq!(Ok(*b"remo"), reader.read_id());
+            assert_eq!(
+                Err(ControlDecodeError::UnexpectedEndOfSlice(
+                    UnexpectedEndOfSliceError {
+                        layer: Layer::ControlMessage,
+                        minimum_size: buf.len() + 1,
+                        actual_size: buf.len(),
+                    }
+                )),
+                reader.read_u8()
+            );
+        }
+    }
+
+    #[test]
+    fn errors() {
+        // not a control request
+        for message_type in [
+            None,
+            Some(DltMessageType::Log(DltLogLevel::Info)),
+            Some(DltMessageType::Control(DltControlMessageType::Response)),
+        ] {
+            let buf = packet(message_type, true, &[0, 0, 0, 1]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Err(ControlDecodeError::NotAControlRequest(message_type)),
+                ControlRequest::from_packet(&slice)
+            );
+        }
+
+        // payload too short
+        let request_type = Some(DltMessageType::Control(DltControlMessageType::Request));
+        for len in 0..4 {
+            let buf = packet(request_type, true, &[0, 0, 0, 1][..len]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            let expected = Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 4,
+                    actual_size: len,
+                },
+            ));
+            assert_eq!(expected, ControlRequest::from_packet(&slice));
+            assert_eq!(
+                expected,
+                ControlRequest::from_slice(&[0, 0, 0, 1][..len], true)
+            );
+        }
+    }
+}
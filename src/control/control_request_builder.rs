@@ -0,0 +1,423 @@
+use super::{ControlRequest, DltServiceId, TypedControlRequest};
+use crate::error::WriteError;
+use crate::{DltControlMessageType, DltExtendedHeader, DltHeader, DltMessageType};
+use arrayvec::ArrayVec;
+use std::{io, vec::Vec};
+
+/// Builder for complete control request packets (header, service id &
+/// parameters).
+///
+/// The packets are non verbose control requests without arguments & by
+/// default use the application & context id of the dlt-daemon client
+/// library ([`ControlRequest::DEFAULT_APPLICATION_ID`] &
+/// [`ControlRequest::DEFAULT_CONTEXT_ID`]). The message counter is
+/// increased (wrapping) after each written request.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{
+///     ControlLogLevel, ControlRequest, ControlRequestBuilder, SetLogLevelRequest,
+///     TypedControlRequest,
+/// };
+/// use dlt_parse::{DltLogLevel, DltPacketSlice};
+///
+/// let mut builder = ControlRequestBuilder::new().with_ecu_id(*b"ECU1");
+/// let request = TypedControlRequest::SetLogLevel(SetLogLevelRequest {
+///     app_id: *b"LOG\0",
+///     ctx_id: *b"TEST",
+///     log_level: ControlLogLevel::Level(DltLogLevel::Debug),
+///     com_interface: SetLogLevelRequest::DEFAULT_COM_INTERFACE,
+/// });
+/// let bytes = builder.build_typed(&request).unwrap();
+///
+/// let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+/// let control = ControlRequest::from_packet(&packet).unwrap();
+/// assert_eq!(Ok(request), TypedControlRequest::from_request(&control));
+/// assert_eq!(1, builder.message_counter());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ControlRequestBuilder {
+    is_big_endian: bool,
+    message_counter: u8,
+    ecu_id: Option<[u8; 4]>,
+    session_id: Option<u32>,
+    timestamp: Option<u32>,
+    application_id: [u8; 4],
+    context_id: [u8; 4],
+}
+
+impl ControlRequestBuilder {
+    /// Creates a builder for little endian requests without ecu id,
+    /// session id or timestamp & with the default application & context id.
+    pub fn new() -> ControlRequestBuilder {
+        ControlRequestBuilder {
+            is_big_endian: false,
+            message_counter: 0,
+            ecu_id: None,
+            session_id: None,
+            timestamp: None,
+            application_id: ControlRequest::DEFAULT_APPLICATION_ID,
+            context_id: ControlRequest::DEFAULT_CONTEXT_ID,
+        }
+    }
+
+    /// Sets if the requests are encoded in big endian.
+    pub fn with_big_endian(mut self, is_big_endian: bool) -> Self {
+        self.is_big_endian = is_big_endian;
+        self
+    }
+
+    /// Sets the message counter of the next request.
+    pub fn with_message_counter(mut self, message_counter: u8) -> Self {
+        self.message_counter = message_counter;
+        self
+    }
+
+    /// Sets the ecu id.
+    pub fn with_ecu_id(mut self, ecu_id: [u8; 4]) -> Self {
+        self.ecu_id = Some(ecu_id);
+        self
+    }
+
+    /// Sets the session id.
+    pub fn with_session_id(mut self, session_id: u32) -> Self {
+        self.session_id = Some(session_id);
+        self
+    }
+
+    /// Sets the timestamp (in 0.1 milliseconds).
+    pub fn with_timestamp(mut self, timestamp: u32) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the application id in the extended header.
+    pub fn with_application_id(mut self, application_id: [u8; 4]) -> Self {
+        self.application_id = application_id;
+        self
+    }
+
+    /// Sets the context id in the extended header.
+    pub fn with_context_id(mut self, context_id: [u8; 4]) -> Self {
+        self.context_id = context_id;
+        self
+    }
+
+    /// Message counter that will be used for the next request.
+    #[inline]
+    pub fn message_counter(&self) -> u8 {
+        self.message_counter
+    }
+
+    /// Returns the header of the next request for a control payload
+    /// (service id & parameters) with the given length.
+    ///
+    /// Returns [`WriteError::MessageTooLong`] if the length of the packet
+    /// can not be represented in the length field.
+    pub fn header(&self, payload_len: usize) -> Result<DltHeader, WriteError> {
+        let mut header = DltHeader {
+            is_big_endian: self.is_big_endian,
+            message_counter: self.message_counter,
+            length: 0,
+            ecu_id: self.ecu_id,
+            session_id: self.session_id,
+            timestamp: self.timestamp,
+            // can not fail as control message types are always in range
+            extended_header: Some(
+                DltExtendedHeader::new_non_verbose(
+                    DltMessageType::Control(DltControlMessageType::Request),
+                    self.application_id,
+                    self.context_id,
+                )
+                .unwrap(),
+            ),
+        };
+        let length = usize::from(header.header_len()) + payload_len;
+        header.length =
+            u16::try_from(length).map_err(|_| WriteError::MessageTooLong { index: 0, length })?;
+        Ok(header)
+    }
+
+    /// Writes a request with the given service id & parameters (already
+    /// encoded in the endianness of the builder) & increases the message
+    /// counter.
+    ///
+    /// As only one message is written the index in a returned error is
+    /// always 0.
+    pub fn write<W: io::Write>(
+        &mut self,
+        writer: &mut W,
+        service_id: DltServiceId,
+        params: &[u8],
+    ) -> Result<(), WriteError> {
+        let header = self.header(4 + params.len())?;
+        let service_id = service_id.to_u32();
+        let service_id = if self.is_big_endian {
+            service_id.to_be_bytes()
+        } else {
+            service_id.to_le_bytes()
+        };
+        header
+            .write(writer)
+            .and_then(|_| writer.write_all(&service_id))
+            .and_then(|_| writer.write_all(params))
+            .map_err(|err| WriteError::Io { index: 0, err })?;
+        self.message_counter = self.message_counter.wrapping_add(1);
+        Ok(())
+    }
+
+    /// Writes the given typed request (encoded in the endianness of the
+    /// builder) & increases the message counter.
+    ///
+    /// The parameters of [`TypedControlRequest::Other`] &
+    /// [`TypedControlRequest::Unknown`] are written unchanged.
+    pub fn write_typed<W: io::Write>(
+        &mut self,
+        writer: &mut W,
+        request: &TypedControlRequest<'_>,
+    ) -> Result<(), WriteError> {
+        match request {
+            TypedControlRequest::Other(r) => self.write(writer, r.service_id, r.params),
+            TypedControlRequest::Unknown {
+                service_id,
+                payload,
+                ..
+            } => self.write(writer, DltServiceId::from_u32(*service_id), payload),
+            _ => {
+                let mut payload =
+                    ArrayVec::<u8, { ControlRequestBuilder::MAX_TYPED_PAYLOAD_LEN }>::new();
+                // can not fail as the capacity fits the largest typed request
+                request
+                    .add_to_msg(&mut payload, self.is_big_endian)
+                    .unwrap();
+                let header = self.header(payload.len())?;
+                header
+                    .write(writer)
+                    .and_then(|_| writer.write_all(&payload))
+                    .map_err(|err| WriteError::Io { index: 0, err })?;
+                self.message_counter = self.message_counter.wrapping_add(1);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns a request with the given service id & parameters as
+    /// complete packet (see [`ControlRequestBuilder::write`]).
+    pub fn build(
+        &mut self,
+        service_id: DltServiceId,
+        params: &[u8],
+    ) -> Result<Vec<u8>, WriteError> {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer, service_id, params)?;
+        Ok(buffer)
+    }
+
+    /// Returns the given typed request as complete packet (see
+    /// [`ControlRequestBuilder::write_typed`]).
+    pub fn build_typed(
+        &mut self,
+        request: &TypedControlRequest<'_>,
+    ) -> Result<Vec<u8>, WriteError> {
+        let mut buffer = Vec::new();
+        self.write_typed(&mut buffer, request)?;
+        Ok(buffer)
+    }
+
+    /// Maximum length of the payload (service id & parameters) of the
+    /// requests with a typed representation.
+    const MAX_TYPED_PAYLOAD_LEN: usize = 4 + super::SetLogLevelRequest::PARAMS_LEN;
+}
+
+impl Default for ControlRequestBuilder {
+    fn default() -> Self {
+        ControlRequestBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod control_request_builder_tests {
+    use super::*;
+    use crate::control::*;
+    use crate::{DltLogLevel, DltPacketSlice};
+    use alloc::{format, vec};
+
+    /// Request in the layout sent by the dlt-daemon client library
+    /// (little endian with ecu id & timestamp).
+    const FIXTURE: &[u8] = include_bytes!("../../tests/fixtures/set_log_level_request.dlt");
+
+    #[test]
+    fn set_log_level_fixture() {
+        let request = SetLogLevelRequest {
+            app_id: *b"LOG\0",
+            ctx_id: *b"TEST",
+            log_level: ControlLogLevel::Level(DltLogLevel::Info),
+            com_interface: SetLogLevelRequest::DEFAULT_COM_INTERFACE,
+        };
+
+        // typed request
+        let mut builder = ControlRequestBuilder::new()
+            .with_ecu_id(*b"ECU1")
+            .with_timestamp(1234);
+        assert_eq!(
+            FIXTURE,
+            &builder
+                .build_typed(&TypedControlRequest::SetLogLevel(request.clone()))
+                .unwrap()[..]
+        );
+
+        // raw service id & parameters
+        let mut builder = ControlRequestBuilder::new()
+            .with_ecu_id(*b"ECU1")
+            .with_timestamp(1234);
+        let mut payload = ArrayVec::<u8, 17>::new();
+        request.add_to_msg(&mut payload, false).unwrap();
+        assert_eq!(
+            FIXTURE,
+            &builder
+                .build(DltServiceId::SetLogLevel, &payload[4..])
+                .unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn reparse() {
+        let requests = [
+            TypedControlRequest::SetLogLevel(SetLogLevelRequest {
+                app_id: *b"APP1",
+                ctx_id: *b"CTX1",
+                log_level: ControlLogLevel::Default,
+                com_interface: SetLogLevelRequest::DEFAULT_COM_INTERFACE,
+            }),
+            TypedControlRequest::SetTraceStatus(SetTraceStatusRequest {
+                app_id: *b"APP1",
+                ctx_id: *b"CTX1",
+                trace_status: ControlTraceStatus::On,
+                com_interface: SetTraceStatusRequest::DEFAULT_COM_INTERFACE,
+            }),
+            TypedControlRequest::GetDefaultLogLevel(GetDefaultLogLevelRequest),
+            TypedControlRequest::StoreConfiguration(StoreConfigurationRequest),
+            TypedControlRequest::SetVerboseMode(SetVerboseModeRequest { new_status: true }),
+            TypedControlRequest::GetLocalTime(GetLocalTimeRequest),
+            TypedControlRequest::SyncTimeStamp(SyncTimeStampRequest),
+            TypedControlRequest::Other(ControlRequest {
+                service_id: DltServiceId::UseEcuId,
+                params: &[1],
+                is_big_endian: false,
+                payload_offset: 0,
+            }),
+            TypedControlRequest::Unknown {
+                service_id: 0xF01,
+                payload: &[1, 2, 3],
+                is_big_endian: false,
+            },
+        ];
+        for is_big_endian in [false, true] {
+            let mut builder = ControlRequestBuilder::new()
+                .with_big_endian(is_big_endian)
+                .with_message_counter(0xfe)
+                .with_ecu_id(*b"ECU1")
+                .with_session_id(2)
+                .with_timestamp(3)
+                .with_application_id(*b"CA1\0")
+                .with_context_id(*b"CC1\0");
+            let mut buffer = Vec::new();
+            for request in requests.iter() {
+                builder.write_typed(&mut buffer, request).unwrap();
+            }
+
+            let mut expected_counter = 0xfeu8;
+            let mut rest = &buffer[..];
+            for request in requests.iter() {
+                let packet = DltPacketSlice::from_slice(rest).unwrap();
+                rest = &rest[packet.slice().len()..];
+
+                let header = packet.header();
+                assert_eq!(is_big_endian, header.is_big_endian);
+                assert_eq!(expected_counter, header.message_counter);
+                assert_eq!(Some(*b"ECU1"), header.ecu_id);
+                assert_eq!(Some(2), header.session_id);
+                assert_eq!(Some(3), header.timestamp);
+                let ext = header.extended_header.unwrap();
+                assert!(false == ext.is_verbose());
+                assert_eq!(0, ext.number_of_arguments);
+                assert_eq!(*b"CA1\0", ext.application_id);
+                assert_eq!(*b"CC1\0", ext.context_id);
+
+                let control = ControlRequest::from_packet(&packet).unwrap();
+                let decoded = TypedControlRequest::from_request(&control).unwrap();
+                assert_eq!(request.service_id(), decoded.service_id());
+                match (request, decoded) {
+                    (TypedControlRequest::Other(expected), TypedControlRequest::Other(r)) => {
+                        assert_eq!(expected.params, r.params);
+                    }
+                    (
+                        TypedControlRequest::Unknown {
+                            payload: expected, ..
+                        },
+                        TypedControlRequest::Unknown { payload, .. },
+                    ) => {
+                        assert_eq!(expected, &payload);
+                    }
+                    (expected, decoded) => assert_eq!(expected, &decoded),
+                }
+                expected_counter = expected_counter.wrapping_add(1);
+            }
+            assert!(rest.is_empty());
+            assert_eq!(expected_counter, builder.message_counter());
+        }
+    }
+
+    #[test]
+    fn defaults() {
+        let mut builder = ControlRequestBuilder::default();
+        assert_eq!(ControlRequestBuilder::new(), builder);
+        let bytes = builder
+            .build(DltServiceId::GetSoftwareVersion, &[])
+            .unwrap();
+        assert_eq!(
+            ControlRequest::build_service_packet(DltServiceId::GetSoftwareVersion, false, &[]),
+            bytes
+        );
+    }
+
+    #[test]
+    fn errors() {
+        // too long
+        let mut builder = ControlRequestBuilder::new();
+        let params = vec![0u8; usize::from(u16::MAX)];
+        let mut buffer = Vec::new();
+        match builder.write(&mut buffer, DltServiceId::UseEcuId, &params) {
+            Err(WriteError::MessageTooLong { index, length }) => {
+                assert_eq!(0, index);
+                assert_eq!(4 + 10 + 4 + usize::from(u16::MAX), length);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(buffer.is_empty());
+        assert_eq!(0, builder.message_counter());
+
+        // io error
+        let mut writer = [0u8; 4];
+        match builder.write(&mut &mut writer[..], DltServiceId::UseEcuId, &[]) {
+            Err(WriteError::Io { index, .. }) => assert_eq!(0, index),
+            other => panic!("unexpected result {:?}", other),
+        }
+        match builder.write_typed(
+            &mut &mut writer[..],
+            &TypedControlRequest::GetLocalTime(GetLocalTimeRequest),
+        ) {
+            Err(WriteError::Io { index, .. }) => assert_eq!(0, index),
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(0, builder.message_counter());
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let builder = ControlRequestBuilder::new();
+        assert_eq!(builder, builder.clone());
+        assert!(format!("{:?}", builder).starts_with("ControlRequestBuilder {"));
+    }
+}
@@ -0,0 +1,290 @@
+use super::{ControlResponseStatus, DltServiceId, PayloadReader};
+use crate::error::{ControlDecodeError, Layer, UnexpectedEndOfSliceError};
+use crate::{DltControlMessageType, DltMessageType, DltPacketSlice};
+
+/// Control response (service id, status & the service specific data
+/// following them).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{ControlResponse, ControlResponseStatus, DltServiceId};
+///
+/// // "get software version" response payload (big endian)
+/// let payload = [
+///     0, 0, 0, 0x13, // service id
+///     0, // status
+///     0, 0, 0, 2, // length of the version
+///     b'v', b'1', // version
+/// ];
+/// let response = ControlResponse::from_slice(&payload, true).unwrap();
+/// assert_eq!(DltServiceId::GetSoftwareVersion, response.service_id);
+/// assert_eq!(ControlResponseStatus::Ok, response.status);
+/// assert_eq!(&payload[5..], response.data);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ControlResponse<'a> {
+    /// Service id of the response.
+    pub service_id: DltServiceId,
+    /// Status of the response.
+    pub status: ControlResponseStatus,
+    /// Service specific data after the status byte (can be empty).
+    pub data: &'a [u8],
+    /// True if the data is encoded in big endian.
+    pub is_big_endian: bool,
+    /// Offset of the payload (starting with the service id) from the start
+    /// of the DLT message (0 if the response was not decoded from a packet).
+    pub payload_offset: usize,
+}
+
+impl<'a> ControlResponse<'a> {
+    /// Decodes the control response from the payload of a control message
+    /// (starting with the service id).
+    pub fn from_slice(
+        payload: &'a [u8],
+        is_big_endian: bool,
+    ) -> Result<ControlResponse<'a>, ControlDecodeError> {
+        if payload.len() < 5 {
+            return Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 5,
+                    actual_size: payload.len(),
+                },
+            ));
+        }
+        let mut reader = PayloadReader::new(payload, is_big_endian);
+        let service_id = DltServiceId::from_u32(reader.read_u32()?);
+        let status = ControlResponseStatus::from_u8(reader.read_u8()?);
+        Ok(ControlResponse {
+            service_id,
+            status,
+            data: reader.rest(),
+            is_big_endian,
+            payload_offset: 0,
+        })
+    }
+
+    /// Decodes the control response contained in the given packet.
+    ///
+    /// Returns [`ControlDecodeError::NotAControlResponse`] if the message
+    /// type of the packet is not "control response" & an unexpected end
+    /// of slice error if the payload is too short for a service id &
+    /// status.
+    pub fn from_packet(
+        packet: &DltPacketSlice<'a>,
+    ) -> Result<ControlResponse<'a>, ControlDecodeError> {
+        match packet.message_type() {
+            Some(DltMessageType::Control(DltControlMessageType::Response)) => {}
+            other => return Err(ControlDecodeError::NotAControlResponse(other)),
+        }
+        let payload = packet.payload();
+        let mut result = ControlResponse::from_slice(payload, packet.is_big_endian())?;
+        result.payload_offset = packet.slice().len() - payload.len();
+        Ok(result)
+    }
+
+    /// Returns the service specific data together with its offset from
+    /// the start of the DLT message (see [`ControlResponse::payload_offset`]).
+    #[inline]
+    pub fn data_with_offset(&self) -> (&'a [u8], usize) {
+        (self.data, self.payload_offset + 5)
+    }
+
+    /// Returns a reader for the service specific data using the endianness
+    /// of the response (offsets in errors are relative to the start of the
+    /// DLT message, see [`ControlResponse::data_with_offset`]).
+    #[inline]
+    pub fn data_reader(&self) -> PayloadReader<'a> {
+        let (data, offset) = self.data_with_offset();
+        PayloadReader::with_offset(data, self.is_big_endian, offset)
+    }
+}
+
+#[cfg(test)]
+mod control_response_tests {
+    use super::*;
+    use crate::control::GetSoftwareVersionResponse;
+    use crate::test_packets;
+    use crate::*;
+    use alloc::vec::Vec;
+
+    fn packet(
+        message_type: Option<DltMessageType>,
+        is_big_endian: bool,
+        payload: &[u8],
+    ) -> Vec<u8> {
+        let ext = message_type
+            .map(|t| DltExtendedHeader::new_non_verbose(t, *b"APP\0", *b"CTX\0").unwrap());
+        test_packets::packet(test_packets::header(is_big_endian, ext), payload)
+    }
+
+    const RESPONSE: Option<DltMessageType> =
+        Some(DltMessageType::Control(DltControlMessageType::Response));
+
+    #[test]
+    fn data_with_offset() {
+        use crate::control::BufferOverflowNotification;
+
+        // buffer overflow notification with only 3 of the 4 counter bytes
+        let payload = [0x23, 0, 0, 0, 0, 1, 2, 3];
+        for ecu_id in [None, Some(*b"ECU1")] {
+            for session_id in [None, Some(1234)] {
+                for timestamp in [None, Some(5678)] {
+                    let mut header = DltHeader {
+                        is_big_endian: false,
+                        message_counter: 0,
+                        length: 0,
+                        ecu_id,
+                        session_id,
+                        timestamp,
+                        extended_header: Some(
+                            DltExtendedHeader::new_non_verbose(
+                                RESPONSE.unwrap(),
+                                *b"APP\0",
+                                *b"CTX\0",
+                            )
+                            .unwrap(),
+                        ),
+                    };
+                    header.length = header.header_len() + payload.len() as u16;
+                    let mut buf = Vec::new();
+                    buf.extend_from_slice(&header.to_bytes());
+                    buf.extend_from_slice(&payload);
+
+                    let slice = DltPacketSlice::from_slice(&buf).unwrap();
+                    let response = ControlResponse::from_packet(&slice).unwrap();
+                    let header_len = usize::from(header.header_len());
+                    assert_eq!(header_len, response.payload_offset);
+                    assert_eq!((&payload[5..], header_len + 5), response.data_with_offset());
+                    let (data, offset) = response.data_with_offset();
+                    assert_eq!(data, &buf[offset..]);
+
+                    // typed parsers report offsets relative to the message
+                    assert_eq!(
+                        Err(ControlDecodeError::UnexpectedEndOfSlice(
+                            UnexpectedEndOfSliceError {
+                                layer: Layer::ControlMessage,
+                                minimum_size: buf.len() + 1,
+                                actual_size: buf.len(),
+                            }
+                        )),
+                        BufferOverflowNotification::parse(&response)
+                    );
+
+                    // without packet the offsets are relative to the payload
+                    let response = ControlResponse::from_slice(&payload, false).unwrap();
+                    assert_eq!((&payload[5..], 5), response.data_with_offset());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn get_software_version() {
+        for is_big_endian in [false, true] {
+            let mut payload = Vec::new();
+            let (service_id, len) = if is_big_endian {
+                (0x13u32.to_be_bytes(), 5u32.to_be_bytes())
+            } else {
+                (0x13u32.to_le_bytes(), 5u32.to_le_bytes())
+            };
+            payload.extend_from_slice(&service_id);
+            payload.push(0);
+            payload.extend_from_slice(&len);
+            payload.extend_from_slice(b"v1.0\0");
+
+            let buf = packet(RESPONSE, is_big_endian, &payload);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            let response = ControlResponse::from_packet(&slice).unwrap();
+            assert_eq!(
+                ControlResponse {
+                    service_id: DltServiceId::GetSoftwareVersion,
+                    status: ControlResponseStatus::Ok,
+                    data: &payload[5..],
+                    is_big_endian,
+                    payload_offset: buf.len() - payload.len(),
+                },
+                response
+            );
+            assert_eq!(
+                Ok(ControlResponse {
+                    payload_offset: 0,
+                    ..response.clone()
+                }),
+                ControlResponse::from_slice(&payload, is_big_endian)
+            );
+
+            let mut reader = response.data_reader();
+            assert_eq!(buf.len() - payload.len() + 5, reader.offset());
+            assert_eq!(Ok(5), reader.read_u32());
+            assert_eq!(Ok(&b"v1.0\0"[..]), reader.read_raw(5));
+
+            // same result as the service specific decoder
+            assert_eq!(
+                "v1.0",
+                GetSoftwareVersionResponse::from_slice(&payload[4..], is_big_endian)
+                    .unwrap()
+                    .sw_version
+            );
+        }
+    }
+
+    #[test]
+    fn not_supported_without_data() {
+        for is_big_endian in [false, true] {
+            let service_id = if is_big_endian {
+                0x04u32.to_be_bytes()
+            } else {
+                0x04u32.to_le_bytes()
+            };
+            let payload = [
+                service_id[0],
+                service_id[1],
+                service_id[2],
+                service_id[3],
+                ControlResponseStatus::NOT_SUPPORTED,
+            ];
+            let buf = packet(RESPONSE, is_big_endian, &payload);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            let response = ControlResponse::from_packet(&slice).unwrap();
+            assert_eq!(DltServiceId::GetDefaultLogLevel, response.service_id);
+            assert_eq!(ControlResponseStatus::NotSupported, response.status);
+            assert!(response.data.is_empty());
+            assert!(response.data_reader().read_u8().is_err());
+        }
+    }
+
+    #[test]
+    fn errors() {
+        // missing status byte
+        for len in 0..5 {
+            let payload = &[0, 0, 0, 0x13, 0][..len];
+            let buf = packet(RESPONSE, true, payload);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            let expected = Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 5,
+                    actual_size: len,
+                },
+            ));
+            assert_eq!(expected, ControlResponse::from_packet(&slice));
+            assert_eq!(expected, ControlResponse::from_slice(payload, true));
+        }
+
+        // not a control response
+        for message_type in [
+            None,
+            Some(DltMessageType::Log(DltLogLevel::Info)),
+            Some(DltMessageType::Control(DltControlMessageType::Request)),
+        ] {
+            let buf = packet(message_type, true, &[0, 0, 0, 0x13, 0]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Err(ControlDecodeError::NotAControlResponse(message_type)),
+                ControlResponse::from_packet(&slice)
+            );
+        }
+    }
+}
@@ -0,0 +1,102 @@
+/// Status of a control response.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::ControlResponseStatus;
+///
+/// assert_eq!(ControlResponseStatus::NotSupported, ControlResponseStatus::from(1));
+/// assert_eq!(ControlResponseStatus::Other(8), ControlResponseStatus::from(8));
+/// assert_eq!(8u8, ControlResponseStatus::Other(8).into());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ControlResponseStatus {
+    /// Request was successfully processed (0).
+    Ok,
+    /// Service is not supported by the receiver (1).
+    NotSupported,
+    /// Error while processing the request (2).
+    Error,
+    /// Service specific or unknown status value.
+    Other(u8),
+}
+
+impl ControlResponseStatus {
+    /// Status value of [`ControlResponseStatus::Ok`].
+    pub const OK: u8 = 0;
+
+    /// Status value of [`ControlResponseStatus::NotSupported`].
+    pub const NOT_SUPPORTED: u8 = 1;
+
+    /// Status value of [`ControlResponseStatus::Error`].
+    pub const ERROR: u8 = 2;
+
+    /// Converts the status byte to the enum.
+    #[inline]
+    pub fn from_u8(value: u8) -> ControlResponseStatus {
+        use ControlResponseStatus::*;
+        match value {
+            ControlResponseStatus::OK => Ok,
+            ControlResponseStatus::NOT_SUPPORTED => NotSupported,
+            ControlResponseStatus::ERROR => Error,
+            value => Other(value),
+        }
+    }
+
+    /// Returns the status byte.
+    #[inline]
+    pub fn to_u8(self) -> u8 {
+        use ControlResponseStatus::*;
+        match self {
+            Ok => ControlResponseStatus::OK,
+            NotSupported => ControlResponseStatus::NOT_SUPPORTED,
+            Error => ControlResponseStatus::ERROR,
+            Other(value) => value,
+        }
+    }
+}
+
+impl From<u8> for ControlResponseStatus {
+    #[inline]
+    fn from(value: u8) -> ControlResponseStatus {
+        ControlResponseStatus::from_u8(value)
+    }
+}
+
+impl From<ControlResponseStatus> for u8 {
+    #[inline]
+    fn from(value: ControlResponseStatus) -> u8 {
+        value.to_u8()
+    }
+}
+
+#[cfg(test)]
+mod control_response_status_tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq() {
+        let status = ControlResponseStatus::Other(3);
+        assert_eq!(status, status.clone());
+        assert_eq!("Other(3)", format!("{:?}", status));
+    }
+
+    #[test]
+    fn from_to_u8() {
+        use ControlResponseStatus::*;
+        for value in 0..=u8::MAX {
+            let status = ControlResponseStatus::from_u8(value);
+            let expected = match value {
+                0 => Ok,
+                1 => NotSupported,
+                2 => Error,
+                value => Other(value),
+            };
+            assert_eq!(expected, status);
+            assert_eq!(expected, ControlResponseStatus::from(value));
+            assert_eq!(value, status.to_u8());
+            assert_eq!(value, u8::from(status));
+        }
+    }
+}
@@ -0,0 +1,397 @@
+use super::{ControlRequest, DltServiceId, TypedControlRequest, TypedControlResponse};
+use crate::error::{ControlDecodeError, Layer, UnexpectedEndOfSliceError};
+use crate::{ControlNvPayload, DltControlMessageType, DltMessageType};
+use core::fmt;
+use std::boxed::Box;
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+/// Result of decoding a control message via a [`ControlServiceRegistry`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ControlServiceDecoded<'a, T> {
+    /// Message was decoded by a registered handler.
+    Handled(T),
+    /// No handler is registered for the service id (or the registered
+    /// handler returned `None`) & the message is a request.
+    Request(TypedControlRequest<'a>),
+    /// No handler is registered for the service id (or the registered
+    /// handler returned `None`) & the message is a response.
+    Response(TypedControlResponse<'a>),
+}
+
+/// Handler for the control messages of a service id.
+type ControlServiceHandler<T> = Box<dyn Fn(&ControlNvPayload<'_>, bool) -> Option<T>>;
+
+/// Registry of handlers for (usually user defined) control services
+/// keyed by service id.
+///
+/// Registered handlers are consulted before the built in decoding of
+/// [`TypedControlRequest`] & [`TypedControlResponse`] (which return
+/// user defined & unknown services as `Unknown`). Handlers get the
+/// control payload & the endianness of the message passed in.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{ControlServiceDecoded, ControlServiceRegistry};
+/// use dlt_parse::{ControlNvPayload, DltControlMessageType};
+///
+/// let mut registry = ControlServiceRegistry::new();
+/// registry.register(0xF01, |payload: &ControlNvPayload<'_>, _is_big_endian| {
+///     payload.payload.first().copied()
+/// });
+///
+/// let payload = ControlNvPayload {
+///     msg_type: DltControlMessageType::Request,
+///     service_id: 0xF01,
+///     payload: &[42],
+/// };
+/// assert_eq!(
+///     Ok(ControlServiceDecoded::Handled(42)),
+///     registry.decode(&payload, true)
+/// );
+/// ```
+pub struct ControlServiceRegistry<T> {
+    handlers: BTreeMap<u32, ControlServiceHandler<T>>,
+}
+
+impl<T> ControlServiceRegistry<T> {
+    /// Creates an empty registry.
+    pub fn new() -> ControlServiceRegistry<T> {
+        ControlServiceRegistry {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a handler for the given service id. Returns true if a
+    /// previously registered handler for the same service id was replaced.
+    pub fn register<F>(&mut self, service_id: u32, handler: F) -> bool
+    where
+        F: Fn(&ControlNvPayload<'_>, bool) -> Option<T> + 'static,
+    {
+        self.handlers
+            .insert(service_id, Box::new(handler))
+            .is_some()
+    }
+
+    /// Removes the handler for the given service id. Returns true if a
+    /// handler was registered.
+    pub fn unregister(&mut self, service_id: u32) -> bool {
+        self.handlers.remove(&service_id).is_some()
+    }
+
+    /// Number of registered handlers.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// Returns true if no handler is registered.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Decodes the given control payload with the handler registered for
+    /// its service id & falls back to [`TypedControlRequest::from_request`]
+    /// or [`TypedControlResponse::from_response`] if no handler is
+    /// registered or the handler returned `None`.
+    pub fn decode<'a>(
+        &self,
+        payload: &ControlNvPayload<'a>,
+        is_big_endian: bool,
+    ) -> Result<ControlServiceDecoded<'a, T>, ControlDecodeError> {
+        let handled = self
+            .handlers
+            .get(&payload.service_id)
+            .and_then(|handler| handler(payload, is_big_endian));
+        if let Some(value) = handled {
+            return Ok(ControlServiceDecoded::Handled(value));
+        }
+        match payload.msg_type {
+            DltControlMessageType::Request => {
+                let request = ControlRequest {
+                    service_id: DltServiceId::from_u32(payload.service_id),
+                    params: payload.payload,
+                    is_big_endian,
+                    payload_offset: 0,
+                };
+                TypedControlRequest::from_request(&request).map(ControlServiceDecoded::Request)
+            }
+            DltControlMessageType::Response => match payload.control_response(is_big_endian) {
+                Some(response) => TypedControlResponse::from_response(&response)
+                    .map(ControlServiceDecoded::Response),
+                // response without a status byte
+                None => Err(ControlDecodeError::UnexpectedEndOfSlice(
+                    UnexpectedEndOfSliceError {
+                        layer: Layer::ControlMessage,
+                        minimum_size: 5,
+                        actual_size: 4 + payload.payload.len(),
+                    },
+                )),
+            },
+            DltControlMessageType::Time => Err(ControlDecodeError::NotAControlResponse(Some(
+                DltMessageType::Control(payload.msg_type),
+            ))),
+        }
+    }
+}
+
+impl<T> Default for ControlServiceRegistry<T> {
+    fn default() -> Self {
+        ControlServiceRegistry::new()
+    }
+}
+
+impl<T> fmt::Debug for ControlServiceRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ControlServiceRegistry")
+            .field("service_ids", &self.handlers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod control_service_registry_tests {
+    use super::*;
+    use crate::control::*;
+    use crate::test_packets;
+    use crate::*;
+    use alloc::format;
+
+    /// Vendor specific message decoded by the test handler.
+    #[derive(Debug, Clone, Eq, PartialEq)]
+    struct VendorMessage {
+        is_request: bool,
+        value: u16,
+    }
+
+    fn registry() -> ControlServiceRegistry<VendorMessage> {
+        let mut registry = ControlServiceRegistry::new();
+        registry.register(0xF01, |payload: &ControlNvPayload<'_>, is_big_endian| {
+            // responses start with the status byte
+            let data = match payload.msg_type {
+                DltControlMessageType::Request => payload.payload,
+                _ => payload.payload.get(1..)?,
+            };
+            let bytes = [*data.first()?, *data.get(1)?];
+            Some(VendorMessage {
+                is_request: payload.msg_type == DltControlMessageType::Request,
+                value: if is_big_endian {
+                    u16::from_be_bytes(bytes)
+                } else {
+                    u16::from_le_bytes(bytes)
+                },
+            })
+        });
+        registry
+    }
+
+    fn packet(
+        message_type: DltControlMessageType,
+        is_big_endian: bool,
+        service_id: u32,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let ext = DltExtendedHeader::new_non_verbose(
+            DltMessageType::Control(message_type),
+            *b"APP\0",
+            *b"CON\0",
+        )
+        .unwrap();
+        let mut payload = Vec::new();
+        if is_big_endian {
+            payload.extend_from_slice(&service_id.to_be_bytes());
+        } else {
+            payload.extend_from_slice(&service_id.to_le_bytes());
+        }
+        payload.extend_from_slice(data);
+        test_packets::packet(test_packets::header(is_big_endian, Some(ext)), &payload)
+    }
+
+    fn decode<'a>(
+        registry: &ControlServiceRegistry<VendorMessage>,
+        packet: &DltPacketSlice<'a>,
+    ) -> Result<ControlServiceDecoded<'a, VendorMessage>, ControlDecodeError> {
+        match packet.typed_payload() {
+            Some(DltTypedPayload::ControlNv(payload)) => {
+                payload.decode_with(registry, packet.is_big_endian())
+            }
+            other => panic!("unexpected payload {:?}", other),
+        }
+    }
+
+    #[test]
+    fn registered_handler() {
+        use DltControlMessageType::*;
+        let registry = registry();
+        for is_big_endian in [false, true] {
+            let value = if is_big_endian { 0x1234 } else { 0x3412 };
+
+            // request
+            let buf = packet(Request, is_big_endian, 0xF01, &[0x12, 0x34]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Ok(ControlServiceDecoded::Handled(VendorMessage {
+                    is_request: true,
+                    value
+                })),
+                decode(&registry, &slice)
+            );
+
+            // response
+            let buf = packet(Response, is_big_endian, 0xF01, &[0, 0x12, 0x34]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Ok(ControlServiceDecoded::Handled(VendorMessage {
+                    is_request: false,
+                    value
+                })),
+                decode(&registry, &slice)
+            );
+
+            // handler returns None -> fallback to unknown
+            let buf = packet(Request, is_big_endian, 0xF01, &[0x12]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Ok(ControlServiceDecoded::Request(
+                    TypedControlRequest::Unknown {
+                        service_id: 0xF01,
+                        payload: &[0x12],
+                        is_big_endian,
+                    }
+                )),
+                decode(&registry, &slice)
+            );
+        }
+    }
+
+    #[test]
+    fn unregistered_service() {
+        use DltControlMessageType::*;
+        let registry = registry();
+        for is_big_endian in [false, true] {
+            // vendor specific request
+            let buf = packet(Request, is_big_endian, 0xF02, &[1, 2, 3]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Ok(ControlServiceDecoded::Request(
+                    TypedControlRequest::Unknown {
+                        service_id: 0xF02,
+                        payload: &[1, 2, 3],
+                        is_big_endian,
+                    }
+                )),
+                decode(&registry, &slice)
+            );
+
+            // vendor specific response
+            let buf = packet(Response, is_big_endian, 0xF02, &[2, 1, 2, 3]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Ok(ControlServiceDecoded::Response(
+                    TypedControlResponse::Unknown {
+                        service_id: 0xF02,
+                        status: ControlResponseStatus::Error,
+                        payload: &[1, 2, 3],
+                        is_big_endian,
+                    }
+                )),
+                decode(&registry, &slice)
+            );
+
+            // standard service
+            let buf = packet(Request, is_big_endian, 0x13, &[]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Ok(ControlServiceDecoded::Request(TypedControlRequest::Other(
+                    ControlRequest {
+                        service_id: DltServiceId::GetSoftwareVersion,
+                        params: &[],
+                        is_big_endian,
+                        payload_offset: 0,
+                    }
+                ))),
+                decode(&registry, &slice)
+            );
+
+            // decoding error of a standard service
+            let buf = packet(Request, is_big_endian, 0x09, &[2]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Err(ControlDecodeError::InvalidBool(2)),
+                decode(&registry, &slice)
+            );
+        }
+
+        // response without status byte
+        let payload = ControlNvPayload {
+            msg_type: DltControlMessageType::Response,
+            service_id: 0xF02,
+            payload: &[],
+        };
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 5,
+                    actual_size: 4,
+                }
+            )),
+            registry.decode(&payload, true)
+        );
+    }
+
+    #[test]
+    fn time_message() {
+        let payload = ControlNvPayload {
+            msg_type: DltControlMessageType::Time,
+            service_id: 0xF02,
+            payload: &[],
+        };
+        assert_eq!(
+            Err(ControlDecodeError::NotAControlResponse(Some(
+                DltMessageType::Control(DltControlMessageType::Time)
+            ))),
+            registry().decode(&payload, true)
+        );
+    }
+
+    #[test]
+    fn register_unregister() {
+        let mut registry = ControlServiceRegistry::<u8>::default();
+        assert!(registry.is_empty());
+        assert!(false == registry.register(0xF01, |_: &ControlNvPayload<'_>, _| Some(1)));
+        assert!(registry.register(0xF01, |_: &ControlNvPayload<'_>, _| Some(2)));
+        assert!(false == registry.register(0xF02, |_: &ControlNvPayload<'_>, _| None));
+        assert_eq!(2, registry.len());
+        assert_eq!(
+            "ControlServiceRegistry { service_ids: [3841, 3842] }",
+            format!("{:?}", registry)
+        );
+
+        let payload = ControlNvPayload {
+            msg_type: DltControlMessageType::Request,
+            service_id: 0xF01,
+            payload: &[],
+        };
+        assert_eq!(
+            Ok(ControlServiceDecoded::Handled(2)),
+            registry.decode(&payload, true)
+        );
+
+        assert!(registry.unregister(0xF01));
+        assert!(false == registry.unregister(0xF01));
+        assert_eq!(1, registry.len());
+        assert_eq!(
+            Ok(ControlServiceDecoded::Request(
+                TypedControlRequest::Unknown {
+                    service_id: 0xF01,
+                    payload: &[],
+                    is_big_endian: true,
+                }
+            )),
+            registry.decode(&payload, true)
+        );
+    }
+}
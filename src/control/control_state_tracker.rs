@@ -0,0 +1,760 @@
+use super::{
+    ControlLogLevel, ControlRequest, ControlResponse, ControlResponseStatus, ControlTraceStatus,
+    DltServiceId, GetDefaultLogLevelResponse, GetLogInfoResponse, TypedControlRequest,
+};
+use crate::error::ControlDecodeError;
+use crate::{DltControlMessageType, DltMessageType, DltPacketSlice};
+use core::fmt;
+use std::boxed::Box;
+use std::collections::{btree_map, BTreeMap, VecDeque};
+use std::vec::Vec;
+
+/// Application & context id identifying a logging channel of an ECU.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DltChannelId {
+    /// Application id.
+    pub app_id: [u8; 4],
+    /// Context id.
+    pub ctx_id: [u8; 4],
+}
+
+/// Logging configuration of a channel as observed by a
+/// [`ControlStateTracker`] (`None` if not yet observed).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ChannelState {
+    /// Log level of the channel.
+    pub log_level: Option<ControlLogLevel>,
+    /// Trace status of the channel.
+    pub trace_status: Option<ControlTraceStatus>,
+}
+
+/// Change of the logging configuration reported by a
+/// [`ControlStateTracker`] to its change callback.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ControlStateChange {
+    /// Log level of a channel changed.
+    LogLevel {
+        channel: DltChannelId,
+        old: Option<ControlLogLevel>,
+        new: ControlLogLevel,
+    },
+    /// Trace status of a channel changed.
+    TraceStatus {
+        channel: DltChannelId,
+        old: Option<ControlTraceStatus>,
+        new: ControlTraceStatus,
+    },
+    /// Default log level of the ECU changed.
+    DefaultLogLevel {
+        old: Option<ControlLogLevel>,
+        new: ControlLogLevel,
+    },
+}
+
+/// Callback called for each change of the tracked state.
+type ControlStateCallback = Box<dyn FnMut(&ControlStateChange)>;
+
+/// Reconstructs the logging configuration of an ECU (log level & trace
+/// status per channel plus the default log level) from the control
+/// messages exchanged with it.
+///
+/// The state is updated on "get log info" & "get default log level"
+/// responses. "Set log level", "set trace status" & "set default log
+/// level" requests are remembered until their response is seen & only
+/// applied if the response has the status [`ControlResponseStatus::Ok`].
+/// A response is paired with the outstanding request of the same service
+/// with the same message counter or, as the message counter of responses
+/// is set by the ECU, with the oldest outstanding request of the same
+/// service. At most [`ControlStateTracker::MAX_PENDING_REQUESTS`] requests
+/// are remembered (the oldest ones are dropped first).
+///
+/// Requests containing wildcard ids are applied to the channel with
+/// exactly the given ids.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{
+///     ControlLogLevel, ControlRequestBuilder, ControlStateTracker, DltChannelId,
+///     SetLogLevelRequest, TypedControlRequest,
+/// };
+/// use dlt_parse::{DltLogLevel, DltPacketSlice};
+///
+/// let mut tracker = ControlStateTracker::new();
+/// tracker.set_change_callback(|change| println!("{:?}", change));
+///
+/// let request = TypedControlRequest::SetLogLevel(SetLogLevelRequest {
+///     app_id: *b"APP1",
+///     ctx_id: *b"CTX1",
+///     log_level: ControlLogLevel::Level(DltLogLevel::Debug),
+///     com_interface: SetLogLevelRequest::DEFAULT_COM_INTERFACE,
+/// });
+/// let packet = ControlRequestBuilder::new().build_typed(&request).unwrap();
+/// tracker
+///     .process_packet(&DltPacketSlice::from_slice(&packet).unwrap())
+///     .unwrap();
+///
+/// // the request is only applied once the ECU acknowledged it
+/// let channel = DltChannelId { app_id: *b"APP1", ctx_id: *b"CTX1" };
+/// assert_eq!(None, tracker.log_level(&channel));
+/// assert_eq!(1, tracker.pending_requests());
+/// ```
+pub struct ControlStateTracker {
+    channels: BTreeMap<DltChannelId, ChannelState>,
+    default_log_level: Option<ControlLogLevel>,
+    pending: VecDeque<PendingRequest>,
+    on_change: Option<ControlStateCallback>,
+}
+
+/// Set request waiting for its response.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct PendingRequest {
+    message_counter: u8,
+    change: PendingChange,
+}
+
+/// Change requested by a set request.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum PendingChange {
+    LogLevel(DltChannelId, ControlLogLevel),
+    TraceStatus(DltChannelId, ControlTraceStatus),
+    DefaultLogLevel(ControlLogLevel),
+}
+
+impl PendingChange {
+    fn service_id(&self) -> DltServiceId {
+        match self {
+            PendingChange::LogLevel(..) => DltServiceId::SetLogLevel,
+            PendingChange::TraceStatus(..) => DltServiceId::SetTraceStatus,
+            PendingChange::DefaultLogLevel(_) => DltServiceId::SetDefaultLogLevel,
+        }
+    }
+}
+
+impl ControlStateTracker {
+    /// Maximum number of requests waiting for their response.
+    pub const MAX_PENDING_REQUESTS: usize = 64;
+
+    /// Creates a tracker without any known state.
+    pub fn new() -> ControlStateTracker {
+        ControlStateTracker {
+            channels: BTreeMap::new(),
+            default_log_level: None,
+            pending: VecDeque::new(),
+            on_change: None,
+        }
+    }
+
+    /// Sets the callback that is called for each change of the state
+    /// (replaces a previously set callback).
+    pub fn set_change_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(&ControlStateChange) + 'static,
+    {
+        self.on_change = Some(Box::new(callback));
+    }
+
+    /// Updates the state based on the given packet.
+    ///
+    /// Packets that are not control requests or responses are ignored.
+    /// An error is returned if the control message could not be decoded.
+    pub fn process_packet(
+        &mut self,
+        packet: &DltPacketSlice<'_>,
+    ) -> Result<(), ControlDecodeError> {
+        let message_counter = packet.header().message_counter;
+        match packet.message_type() {
+            Some(DltMessageType::Control(DltControlMessageType::Request)) => {
+                let request = ControlRequest::from_packet(packet)?;
+                self.process_request(
+                    message_counter,
+                    &TypedControlRequest::from_request(&request)?,
+                );
+                Ok(())
+            }
+            Some(DltMessageType::Control(DltControlMessageType::Response)) => {
+                let response = ControlResponse::from_packet(packet)?;
+                if DltServiceId::GetLogInfo == response.service_id {
+                    // the "get log info" response also starts at the status
+                    self.process_get_log_info(&GetLogInfoResponse::from_slice(
+                        &packet.payload()[4..],
+                        packet.is_big_endian(),
+                    )?)
+                } else {
+                    self.process_response(message_counter, &response)
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Remembers "set log level", "set trace status" & "set default log
+    /// level" requests until their response is processed (other requests
+    /// are ignored).
+    pub fn process_request(&mut self, message_counter: u8, request: &TypedControlRequest<'_>) {
+        let change = match request {
+            TypedControlRequest::SetLogLevel(r) => PendingChange::LogLevel(
+                DltChannelId {
+                    app_id: r.app_id,
+                    ctx_id: r.ctx_id,
+                },
+                r.log_level,
+            ),
+            TypedControlRequest::SetTraceStatus(r) => PendingChange::TraceStatus(
+                DltChannelId {
+                    app_id: r.app_id,
+                    ctx_id: r.ctx_id,
+                },
+                r.trace_status,
+            ),
+            TypedControlRequest::SetDefaultLogLevel(r) => {
+                PendingChange::DefaultLogLevel(r.log_level)
+            }
+            _ => return,
+        };
+        if self.pending.len() >= ControlStateTracker::MAX_PENDING_REQUESTS {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(PendingRequest {
+            message_counter,
+            change,
+        });
+    }
+
+    /// Updates the state based on a control response.
+    ///
+    /// "Get default log level" responses update the default log level,
+    /// responses to set requests apply the paired request (if the status
+    /// is ok). "Get log info" responses have to be decoded & passed to
+    /// [`ControlStateTracker::process_get_log_info`] instead, other
+    /// responses are ignored.
+    pub fn process_response(
+        &mut self,
+        message_counter: u8,
+        response: &ControlResponse<'_>,
+    ) -> Result<(), ControlDecodeError> {
+        match response.service_id {
+            DltServiceId::GetDefaultLogLevel => {
+                let response = GetDefaultLogLevelResponse::parse(response)?;
+                if let (ControlResponseStatus::Ok, Some(log_level)) =
+                    (response.status, response.log_level)
+                {
+                    self.set_default_log_level(log_level);
+                }
+            }
+            DltServiceId::SetLogLevel
+            | DltServiceId::SetTraceStatus
+            | DltServiceId::SetDefaultLogLevel => {
+                let service_id = response.service_id;
+                let index = self
+                    .pending
+                    .iter()
+                    .position(|p| {
+                        p.change.service_id() == service_id && p.message_counter == message_counter
+                    })
+                    .or_else(|| {
+                        self.pending
+                            .iter()
+                            .position(|p| p.change.service_id() == service_id)
+                    });
+                if let Some(pending) = index.and_then(|index| self.pending.remove(index)) {
+                    if ControlResponseStatus::Ok == response.status {
+                        match pending.change {
+                            PendingChange::LogLevel(channel, log_level) => {
+                                self.set_log_level(channel, log_level)
+                            }
+                            PendingChange::TraceStatus(channel, trace_status) => {
+                                self.set_trace_status(channel, trace_status)
+                            }
+                            PendingChange::DefaultLogLevel(log_level) => {
+                                self.set_default_log_level(log_level)
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Updates the log levels & trace statuses of all channels contained
+    /// in the given "get log info" response.
+    ///
+    /// If a log level or trace status in the response is invalid an error
+    /// is returned & the state is left unchanged.
+    pub fn process_get_log_info(
+        &mut self,
+        response: &GetLogInfoResponse<'_>,
+    ) -> Result<(), ControlDecodeError> {
+        // decode all values first so an error leaves the state unchanged
+        let mut updates = Vec::new();
+        for app in &response.app_ids {
+            for ctx in &app.contexts {
+                let channel = DltChannelId {
+                    app_id: app.app_id,
+                    ctx_id: ctx.ctx_id,
+                };
+                let log_level = ctx.log_level.map(ControlLogLevel::from_i8).transpose()?;
+                let trace_status = ctx
+                    .trace_status
+                    .map(ControlTraceStatus::from_i8)
+                    .transpose()?;
+                updates.push((channel, log_level, trace_status));
+            }
+        }
+        for (channel, log_level, trace_status) in updates {
+            if let Some(log_level) = log_level {
+                self.set_log_level(channel, log_level);
+            }
+            if let Some(trace_status) = trace_status {
+                self.set_trace_status(channel, trace_status);
+            }
+        }
+        Ok(())
+    }
+
+    /// Default log level of the ECU (`None` if not yet observed).
+    #[inline]
+    pub fn default_log_level(&self) -> Option<ControlLogLevel> {
+        self.default_log_level
+    }
+
+    /// Known state of the given channel (`None` if nothing was observed
+    /// for the channel).
+    #[inline]
+    pub fn channel_state(&self, channel: &DltChannelId) -> Option<ChannelState> {
+        self.channels.get(channel).copied()
+    }
+
+    /// Log level of the given channel (`None` if not yet observed).
+    #[inline]
+    pub fn log_level(&self, channel: &DltChannelId) -> Option<ControlLogLevel> {
+        self.channels.get(channel).and_then(|s| s.log_level)
+    }
+
+    /// Trace status of the given channel (`None` if not yet observed).
+    #[inline]
+    pub fn trace_status(&self, channel: &DltChannelId) -> Option<ControlTraceStatus> {
+        self.channels.get(channel).and_then(|s| s.trace_status)
+    }
+
+    /// Iterates over all channels with a known state (sorted by id).
+    #[inline]
+    pub fn channels(&self) -> btree_map::Iter<'_, DltChannelId, ChannelState> {
+        self.channels.iter()
+    }
+
+    /// Number of set requests waiting for their response.
+    #[inline]
+    pub fn pending_requests(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Forgets the whole state & all pending requests (the change
+    /// callback is kept & not called).
+    pub fn clear(&mut self) {
+        self.channels.clear();
+        self.default_log_level = None;
+        self.pending.clear();
+    }
+
+    fn set_log_level(&mut self, channel: DltChannelId, new: ControlLogLevel) {
+        let state = self.channels.entry(channel).or_default();
+        if state.log_level != Some(new) {
+            let old = state.log_level.replace(new);
+            self.notify(ControlStateChange::LogLevel { channel, old, new });
+        }
+    }
+
+    fn set_trace_status(&mut self, channel: DltChannelId, new: ControlTraceStatus) {
+        let state = self.channels.entry(channel).or_default();
+        if state.trace_status != Some(new) {
+            let old = state.trace_status.replace(new);
+            self.notify(ControlStateChange::TraceStatus { channel, old, new });
+        }
+    }
+
+    fn set_default_log_level(&mut self, new: ControlLogLevel) {
+        if self.default_log_level != Some(new) {
+            let old = self.default_log_level.replace(new);
+            self.notify(ControlStateChange::DefaultLogLevel { old, new });
+        }
+    }
+
+    fn notify(&mut self, change: ControlStateChange) {
+        if let Some(on_change) = self.on_change.as_mut() {
+            on_change(&change);
+        }
+    }
+}
+
+impl Default for ControlStateTracker {
+    fn default() -> Self {
+        ControlStateTracker::new()
+    }
+}
+
+impl fmt::Debug for ControlStateTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ControlStateTracker")
+            .field("channels", &self.channels)
+            .field("default_log_level", &self.default_log_level)
+            .field("pending", &self.pending)
+            .field("has_change_callback", &self.on_change.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod control_state_tracker_tests {
+    use super::*;
+    use crate::control::*;
+    use crate::*;
+    use alloc::{format, rc::Rc, vec};
+    use core::cell::RefCell;
+    use DltLogLevel::*;
+
+    const GET_LOG_INFO: &[u8] = include_bytes!("../../tests/fixtures/get_log_info_response.dlt");
+
+    fn channel(app_id: &[u8; 4], ctx_id: &[u8; 4]) -> DltChannelId {
+        DltChannelId {
+            app_id: *app_id,
+            ctx_id: *ctx_id,
+        }
+    }
+
+    fn request(message_counter: u8, request: TypedControlRequest<'_>) -> Vec<u8> {
+        ControlRequestBuilder::new()
+            .with_message_counter(message_counter)
+            .build_typed(&request)
+            .unwrap()
+    }
+
+    fn set_log_level(counter: u8, channel: DltChannelId, level: ControlLogLevel) -> Vec<u8> {
+        request(
+            counter,
+            TypedControlRequest::SetLogLevel(SetLogLevelRequest {
+                app_id: channel.app_id,
+                ctx_id: channel.ctx_id,
+                log_level: level,
+                com_interface: SetLogLevelRequest::DEFAULT_COM_INTERFACE,
+            }),
+        )
+    }
+
+    /// Little endian control response packet.
+    fn response(counter: u8, service_id: DltServiceId, status: u8, data: &[u8]) -> Vec<u8> {
+        let mut header = DltHeader {
+            is_big_endian: false,
+            message_counter: counter,
+            length: 0,
+            ecu_id: Some(*b"ECU1"),
+            session_id: None,
+            timestamp: None,
+            extended_header: Some(
+                DltExtendedHeader::new_non_verbose(
+                    DltMessageType::Control(DltControlMessageType::Response),
+                    *b"DA1\0",
+                    *b"DC1\0",
+                )
+                .unwrap(),
+            ),
+        };
+        header.length = header.header_len() + 5 + data.len() as u16;
+        let mut result = Vec::new();
+        header.write(&mut result).unwrap();
+        result.extend_from_slice(&service_id.to_u32().to_le_bytes());
+        result.push(status);
+        result.extend_from_slice(data);
+        result
+    }
+
+    fn process(tracker: &mut ControlStateTracker, packet: &[u8]) {
+        tracker
+            .process_packet(&DltPacketSlice::from_slice(packet).unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn scripted_sequence() {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        let mut tracker = ControlStateTracker::new();
+        {
+            let changes = changes.clone();
+            tracker.set_change_callback(move |change| changes.borrow_mut().push(*change));
+        }
+        let dc1 = channel(b"DA1\0", b"DC1\0");
+        let dc2 = channel(b"DA1\0", b"DC2\0");
+        let test = channel(b"LOG\0", b"TEST");
+        let new = channel(b"NEW\0", b"CTX\0");
+
+        // get log info response fills the channels
+        process(&mut tracker, GET_LOG_INFO);
+        assert_eq!(
+            Some(ChannelState {
+                log_level: Some(ControlLogLevel::Level(Info)),
+                trace_status: Some(ControlTraceStatus::Off),
+            }),
+            tracker.channel_state(&dc1)
+        );
+        assert_eq!(Some(ControlLogLevel::Level(Info)), tracker.log_level(&dc2));
+        assert_eq!(Some(ControlLogLevel::Default), tracker.log_level(&test));
+        assert_eq!(
+            Some(ControlTraceStatus::Default),
+            tracker.trace_status(&test)
+        );
+        assert_eq!(3, tracker.channels().count());
+        assert_eq!(None, tracker.default_log_level());
+        assert_eq!(6, changes.borrow().len());
+        assert_eq!(
+            ControlStateChange::LogLevel {
+                channel: dc1,
+                old: None,
+                new: ControlLogLevel::Level(Info)
+            },
+            changes.borrow()[0]
+        );
+        changes.borrow_mut().clear();
+
+        // repeated info does not cause changes
+        process(&mut tracker, GET_LOG_INFO);
+        assert!(changes.borrow().is_empty());
+
+        // set log level is only applied after the ok response
+        process(
+            &mut tracker,
+            &set_log_level(5, dc1, ControlLogLevel::Level(Debug)),
+        );
+        assert_eq!(Some(ControlLogLevel::Level(Info)), tracker.log_level(&dc1));
+        assert_eq!(1, tracker.pending_requests());
+        process(
+            &mut tracker,
+            &response(0, DltServiceId::SetLogLevel, 0, &[]),
+        );
+        assert_eq!(Some(ControlLogLevel::Level(Debug)), tracker.log_level(&dc1));
+        assert_eq!(0, tracker.pending_requests());
+        assert_eq!(
+            vec![ControlStateChange::LogLevel {
+                channel: dc1,
+                old: Some(ControlLogLevel::Level(Info)),
+                new: ControlLogLevel::Level(Debug)
+            }],
+            *changes.borrow()
+        );
+        changes.borrow_mut().clear();
+
+        // failed set trace status is dropped
+        process(
+            &mut tracker,
+            &request(
+                6,
+                TypedControlRequest::SetTraceStatus(SetTraceStatusRequest {
+                    app_id: *b"LOG\0",
+                    ctx_id: *b"TEST",
+                    trace_status: ControlTraceStatus::On,
+                    com_interface: SetTraceStatusRequest::DEFAULT_COM_INTERFACE,
+                }),
+            ),
+        );
+        assert_eq!(1, tracker.pending_requests());
+        process(
+            &mut tracker,
+            &response(1, DltServiceId::SetTraceStatus, 2, &[]),
+        );
+        assert_eq!(0, tracker.pending_requests());
+        assert_eq!(
+            Some(ControlTraceStatus::Default),
+            tracker.trace_status(&test)
+        );
+
+        // responses are paired by service id
+        process(
+            &mut tracker,
+            &request(
+                7,
+                TypedControlRequest::SetDefaultLogLevel(SetDefaultLogLevelRequest {
+                    log_level: ControlLogLevel::Level(Warn),
+                    com_interface: SetDefaultLogLevelRequest::DEFAULT_COM_INTERFACE,
+                }),
+            ),
+        );
+        process(&mut tracker, &set_log_level(8, new, ControlLogLevel::Off));
+        assert_eq!(2, tracker.pending_requests());
+        process(
+            &mut tracker,
+            &response(2, DltServiceId::SetDefaultLogLevel, 0, &[]),
+        );
+        assert_eq!(
+            Some(ControlLogLevel::Level(Warn)),
+            tracker.default_log_level()
+        );
+        assert_eq!(None, tracker.channel_state(&new));
+        assert_eq!(1, tracker.pending_requests());
+
+        // get default log level response
+        process(
+            &mut tracker,
+            &response(3, DltServiceId::GetDefaultLogLevel, 0, &[6]),
+        );
+        assert_eq!(
+            Some(ControlLogLevel::Level(Verbose)),
+            tracker.default_log_level()
+        );
+        // failed get default log level responses are ignored
+        process(
+            &mut tracker,
+            &response(4, DltServiceId::GetDefaultLogLevel, 1, &[]),
+        );
+        assert_eq!(
+            Some(ControlLogLevel::Level(Verbose)),
+            tracker.default_log_level()
+        );
+
+        process(
+            &mut tracker,
+            &response(5, DltServiceId::SetLogLevel, 0, &[]),
+        );
+        assert_eq!(Some(ControlLogLevel::Off), tracker.log_level(&new));
+        assert_eq!(None, tracker.trace_status(&new));
+        assert_eq!(0, tracker.pending_requests());
+        assert_eq!(
+            vec![
+                ControlStateChange::DefaultLogLevel {
+                    old: None,
+                    new: ControlLogLevel::Level(Warn)
+                },
+                ControlStateChange::DefaultLogLevel {
+                    old: Some(ControlLogLevel::Level(Warn)),
+                    new: ControlLogLevel::Level(Verbose)
+                },
+                ControlStateChange::LogLevel {
+                    channel: new,
+                    old: None,
+                    new: ControlLogLevel::Off
+                },
+            ],
+            *changes.borrow()
+        );
+        changes.borrow_mut().clear();
+
+        // responses without request, other requests & log messages are ignored
+        let before = format!("{:?}", tracker);
+        process(
+            &mut tracker,
+            &response(6, DltServiceId::SetLogLevel, 0, &[]),
+        );
+        process(
+            &mut tracker,
+            &request(
+                9,
+                TypedControlRequest::StoreConfiguration(StoreConfigurationRequest),
+            ),
+        );
+        process(
+            &mut tracker,
+            include_bytes!("../../tests/fixtures/non_verbose_message.dlt"),
+        );
+        assert_eq!(before, format!("{:?}", tracker));
+        assert!(changes.borrow().is_empty());
+
+        tracker.clear();
+        assert_eq!(0, tracker.channels().count());
+        assert_eq!(None, tracker.default_log_level());
+    }
+
+    #[test]
+    fn pairing_by_message_counter() {
+        let mut tracker = ControlStateTracker::default();
+        let a = channel(b"APP1", b"CTX1");
+        let b = channel(b"APP1", b"CTX2");
+        process(&mut tracker, &set_log_level(1, a, ControlLogLevel::Off));
+        process(&mut tracker, &set_log_level(2, b, ControlLogLevel::Off));
+
+        // matching counter is preferred over the oldest request
+        process(
+            &mut tracker,
+            &response(2, DltServiceId::SetLogLevel, 0, &[]),
+        );
+        assert_eq!(None, tracker.log_level(&a));
+        assert_eq!(Some(ControlLogLevel::Off), tracker.log_level(&b));
+        assert_eq!(1, tracker.pending_requests());
+    }
+
+    #[test]
+    fn max_pending_requests() {
+        let mut tracker = ControlStateTracker::new();
+        for i in 0..=ControlStateTracker::MAX_PENDING_REQUESTS {
+            let c = channel(b"APP1", &(i as u32).to_be_bytes());
+            process(
+                &mut tracker,
+                &set_log_level(i as u8, c, ControlLogLevel::Off),
+            );
+        }
+        assert_eq!(
+            ControlStateTracker::MAX_PENDING_REQUESTS,
+            tracker.pending_requests()
+        );
+
+        // the oldest request was dropped
+        process(
+            &mut tracker,
+            &response(200, DltServiceId::SetLogLevel, 0, &[]),
+        );
+        assert_eq!(None, tracker.log_level(&channel(b"APP1", &[0; 4])));
+        assert_eq!(
+            Some(ControlLogLevel::Off),
+            tracker.log_level(&channel(b"APP1", &1u32.to_be_bytes()))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut tracker = ControlStateTracker::new();
+
+        // invalid log level in get log info leaves the state unchanged
+        let info = GetLogInfoResponse {
+            status: 4,
+            app_ids: vec![AppIdInfo {
+                app_id: *b"APP1",
+                contexts: vec![
+                    ContextInfo {
+                        ctx_id: *b"CTX1",
+                        log_level: Some(4),
+                        trace_status: None,
+                        description: None,
+                    },
+                    ContextInfo {
+                        ctx_id: *b"CTX2",
+                        log_level: Some(7),
+                        trace_status: None,
+                        description: None,
+                    },
+                ],
+                description: None,
+            }],
+            com_interface: Some(*b"remo"),
+        };
+        assert_eq!(
+            Err(ControlDecodeError::InvalidLogLevel(7)),
+            tracker.process_get_log_info(&info)
+        );
+        assert_eq!(0, tracker.channels().count());
+
+        // invalid default log level
+        let packet = response(0, DltServiceId::GetDefaultLogLevel, 0, &[9]);
+        assert_eq!(
+            Err(ControlDecodeError::InvalidLogLevel(9)),
+            tracker.process_packet(&DltPacketSlice::from_slice(&packet).unwrap())
+        );
+        assert_eq!(None, tracker.default_log_level());
+    }
+
+    #[test]
+    fn debug() {
+        let mut tracker = ControlStateTracker::new();
+        assert!(format!("{:?}", tracker).contains("has_change_callback: false"));
+        tracker.set_change_callback(|_| {});
+        assert!(format!("{:?}", tracker).contains("has_change_callback: true"));
+    }
+}
@@ -0,0 +1,87 @@
+use crate::error::ControlDecodeError;
+
+/// Trace status as encoded in control messages (e.g. "set trace status").
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::ControlTraceStatus;
+///
+/// assert_eq!(Ok(ControlTraceStatus::On), ControlTraceStatus::from_i8(1));
+/// assert_eq!(-1, ControlTraceStatus::Default.to_i8());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ControlTraceStatus {
+    /// Use the default trace status (-1).
+    Default,
+    /// Tracing is turned off (0).
+    Off,
+    /// Tracing is turned on (1).
+    On,
+}
+
+impl ControlTraceStatus {
+    /// Decodes the trace status value used in control messages.
+    pub fn from_i8(value: i8) -> Result<ControlTraceStatus, ControlDecodeError> {
+        use ControlTraceStatus::*;
+        Ok(match value {
+            -1 => Default,
+            0 => Off,
+            1 => On,
+            value => return Err(ControlDecodeError::InvalidTraceStatus(value)),
+        })
+    }
+
+    /// Returns the value used to encode the trace status in control messages.
+    pub fn to_i8(self) -> i8 {
+        match self {
+            ControlTraceStatus::Default => -1,
+            ControlTraceStatus::Off => 0,
+            ControlTraceStatus::On => 1,
+        }
+    }
+}
+
+impl From<bool> for ControlTraceStatus {
+    /// Converts `true` to [`ControlTraceStatus::On`] & `false` to
+    /// [`ControlTraceStatus::Off`].
+    #[inline]
+    fn from(value: bool) -> ControlTraceStatus {
+        if value {
+            ControlTraceStatus::On
+        } else {
+            ControlTraceStatus::Off
+        }
+    }
+}
+
+#[cfg(test)]
+mod control_trace_status_tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn from_to_i8() {
+        use ControlTraceStatus::*;
+        for value in i8::MIN..=i8::MAX {
+            let result = ControlTraceStatus::from_i8(value);
+            if (-1..=1).contains(&value) {
+                assert_eq!(value, result.unwrap().to_i8());
+            } else {
+                assert_eq!(Err(ControlDecodeError::InvalidTraceStatus(value)), result);
+            }
+        }
+        assert_eq!(Ok(Default), ControlTraceStatus::from_i8(-1));
+        assert_eq!(Ok(Off), ControlTraceStatus::from_i8(0));
+        assert_eq!(Ok(On), ControlTraceStatus::from_i8(1));
+        assert_eq!(On, true.into());
+        assert_eq!(Off, false.into());
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let status = ControlTraceStatus::On;
+        assert_eq!(status, status.clone());
+        assert_eq!("On", format!("{:?}", status));
+    }
+}
@@ -0,0 +1,280 @@
+use core::fmt;
+
+macro_rules! service_ids {
+    ($($variant:ident = $id:literal, $name:literal;)*) => {
+        /// Service id of a DLT control message.
+        ///
+        /// Ids that are not defined in the DLT specification stay
+        /// representable via [`DltServiceId::UserDefined`] (ids reserved for
+        /// vendor specific services), [`DltServiceId::CallSwcInjection`] &
+        /// [`DltServiceId::Unknown`].
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use dlt_parse::control::DltServiceId;
+        ///
+        /// let id = DltServiceId::from_u32(0x13);
+        /// assert_eq!(DltServiceId::GetSoftwareVersion, id);
+        /// assert_eq!(0x13, id.to_u32());
+        /// assert_eq!("get_software_version", id.to_string());
+        ///
+        /// assert!(DltServiceId::from_u32(0x1000).is_injection());
+        /// ```
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+        pub enum DltServiceId {
+            $(
+                #[doc = concat!("`", $name, "` (service id ", stringify!($id), ").")]
+                $variant,
+            )*
+            /// Id in the range reserved for user defined services
+            /// (`0xF00..=0xFFE`).
+            UserDefined(u32),
+            /// Injection into a software component (`0xFFF` and above).
+            CallSwcInjection(u32),
+            /// Id that is not defined by the specification.
+            Unknown(u32),
+        }
+
+        impl DltServiceId {
+            /// Converts the service id value to the enum.
+            pub fn from_u32(value: u32) -> DltServiceId {
+                use DltServiceId::*;
+                match value {
+                    $($id => $variant,)*
+                    DltServiceId::MIN_USER_DEFINED..=DltServiceId::MAX_USER_DEFINED => UserDefined(value),
+                    DltServiceId::MIN_INJECTION..=u32::MAX => CallSwcInjection(value),
+                    _ => Unknown(value),
+                }
+            }
+
+            /// Returns the service id value.
+            pub fn to_u32(self) -> u32 {
+                use DltServiceId::*;
+                match self {
+                    $($variant => $id,)*
+                    UserDefined(value) | CallSwcInjection(value) | Unknown(value) => value,
+                }
+            }
+
+            /// Returns the canonical name of the service (`None` for user
+            /// defined & unknown ids).
+            pub fn name(self) -> Option<&'static str> {
+                use DltServiceId::*;
+                match self {
+                    $($variant => Some($name),)*
+                    CallSwcInjection(_) => Some("call_swc_injections"),
+                    UserDefined(_) | Unknown(_) => None,
+                }
+            }
+        }
+    };
+}
+
+service_ids! {
+    SetLogLevel = 0x01, "set_log_level";
+    SetTraceStatus = 0x02, "set_trace_status";
+    GetLogInfo = 0x03, "get_log_info";
+    GetDefaultLogLevel = 0x04, "get_default_log_level";
+    StoreConfiguration = 0x05, "store_configuration";
+    ResetToFactoryDefault = 0x06, "reset_to_factory_default";
+    SetComInterfaceStatus = 0x07, "set_com_interface_status";
+    SetComInterfaceMaxBandwidth = 0x08, "set_com_interface_max_bandwidth";
+    SetVerboseMode = 0x09, "set_verbose_mode";
+    SetMessageFiltering = 0x0A, "set_message_filtering";
+    SetTimingPackets = 0x0B, "set_timing_packets";
+    GetLocalTime = 0x0C, "get_local_time";
+    UseEcuId = 0x0D, "use_ecu_id";
+    UseSessionId = 0x0E, "use_session_id";
+    UseTimestamp = 0x0F, "use_timestamp";
+    UseExtendedHeader = 0x10, "use_extended_header";
+    SetDefaultLogLevel = 0x11, "set_default_log_level";
+    SetDefaultTraceStatus = 0x12, "set_default_trace_status";
+    GetSoftwareVersion = 0x13, "get_software_version";
+    MessageBufferOverflow = 0x14, "message_buffer_overflow";
+    GetDefaultTraceStatus = 0x15, "get_default_trace_status";
+    GetComInterfaceStatus = 0x16, "get_com_interface_status";
+    GetLogChannelNames = 0x17, "get_log_channel_names";
+    GetComInterfaceMaxBandwidth = 0x18, "get_com_interface_max_bandwidth";
+    GetVerboseModeStatus = 0x19, "get_verbose_mode_status";
+    GetMessageFilteringStatus = 0x1A, "get_message_filtering_status";
+    GetUseEcuId = 0x1B, "get_use_ecu_id";
+    GetUseSessionId = 0x1C, "get_use_session_id";
+    GetUseTimestamp = 0x1D, "get_use_timestamp";
+    GetUseExtendedHeader = 0x1E, "get_use_extended_header";
+    GetTraceStatus = 0x1F, "get_trace_status";
+    SetLogChannelAssignment = 0x20, "set_log_channel_assignment";
+    SetLogChannelThreshold = 0x21, "set_log_channel_threshold";
+    GetLogChannelThreshold = 0x22, "get_log_channel_threshold";
+    BufferOverflowNotification = 0x23, "buffer_overflow_notification";
+    SyncTimeStamp = 0x24, "sync_time_stamp";
+}
+
+impl DltServiceId {
+    /// Smallest id of the range reserved for user defined services.
+    pub const MIN_USER_DEFINED: u32 = 0xF00;
+
+    /// Largest id of the range reserved for user defined services.
+    pub const MAX_USER_DEFINED: u32 = 0xFFE;
+
+    /// Smallest id of the injection services
+    /// (same as [`super::InjectionMessage::MIN_SERVICE_ID`]).
+    pub const MIN_INJECTION: u32 = 0xFFF;
+
+    /// Returns true if the id is the id of an injection service (`>= 0xFFF`).
+    #[inline]
+    pub fn is_injection(self) -> bool {
+        matches!(self, DltServiceId::CallSwcInjection(_))
+    }
+}
+
+impl From<u32> for DltServiceId {
+    #[inline]
+    fn from(value: u32) -> DltServiceId {
+        DltServiceId::from_u32(value)
+    }
+}
+
+impl From<DltServiceId> for u32 {
+    #[inline]
+    fn from(value: DltServiceId) -> u32 {
+        value.to_u32()
+    }
+}
+
+impl fmt::Display for DltServiceId {
+    /// Writes the canonical name of the service. User defined & unknown ids
+    /// are written as `user_defined(0x..)` & `unknown(0x..)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.name(), self) {
+            (Some(name), _) => f.write_str(name),
+            (None, DltServiceId::UserDefined(value)) => write!(f, "user_defined(0x{:x})", value),
+            (None, _) => write!(f, "unknown(0x{:x})", self.to_u32()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod dlt_service_id_tests {
+    use super::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    #[test]
+    fn defined_ids() {
+        use DltServiceId::*;
+        let ids = [
+            (0x01, SetLogLevel, "set_log_level"),
+            (0x02, SetTraceStatus, "set_trace_status"),
+            (0x03, GetLogInfo, "get_log_info"),
+            (0x04, GetDefaultLogLevel, "get_default_log_level"),
+            (0x05, StoreConfiguration, "store_configuration"),
+            (0x06, ResetToFactoryDefault, "reset_to_factory_default"),
+            (0x07, SetComInterfaceStatus, "set_com_interface_status"),
+            (
+                0x08,
+                SetComInterfaceMaxBandwidth,
+                "set_com_interface_max_bandwidth",
+            ),
+            (0x09, SetVerboseMode, "set_verbose_mode"),
+            (0x0A, SetMessageFiltering, "set_message_filtering"),
+            (0x0B, SetTimingPackets, "set_timing_packets"),
+            (0x0C, GetLocalTime, "get_local_time"),
+            (0x0D, UseEcuId, "use_ecu_id"),
+            (0x0E, UseSessionId, "use_session_id"),
+            (0x0F, UseTimestamp, "use_timestamp"),
+            (0x10, UseExtendedHeader, "use_extended_header"),
+            (0x11, SetDefaultLogLevel, "set_default_log_level"),
+            (0x12, SetDefaultTraceStatus, "set_default_trace_status"),
+            (0x13, GetSoftwareVersion, "get_software_version"),
+            (0x14, MessageBufferOverflow, "message_buffer_overflow"),
+            (0x15, GetDefaultTraceStatus, "get_default_trace_status"),
+            (0x16, GetComInterfaceStatus, "get_com_interface_status"),
+            (0x17, GetLogChannelNames, "get_log_channel_names"),
+            (
+                0x18,
+                GetComInterfaceMaxBandwidth,
+                "get_com_interface_max_bandwidth",
+            ),
+            (0x19, GetVerboseModeStatus, "get_verbose_mode_status"),
+            (
+                0x1A,
+                GetMessageFilteringStatus,
+                "get_message_filtering_status",
+            ),
+            (0x1B, GetUseEcuId, "get_use_ecu_id"),
+            (0x1C, GetUseSessionId, "get_use_session_id"),
+            (0x1D, GetUseTimestamp, "get_use_timestamp"),
+            (0x1E, GetUseExtendedHeader, "get_use_extended_header"),
+            (0x1F, GetTraceStatus, "get_trace_status"),
+            (0x20, SetLogChannelAssignment, "set_log_channel_assignment"),
+            (0x21, SetLogChannelThreshold, "set_log_channel_threshold"),
+            (0x22, GetLogChannelThreshold, "get_log_channel_threshold"),
+            (
+                0x23,
+                BufferOverflowNotification,
+                "buffer_overflow_notification",
+            ),
+            (0x24, SyncTimeStamp, "sync_time_stamp"),
+        ];
+        for (value, expected, name) in ids {
+            let id = DltServiceId::from_u32(value);
+            assert_eq!(expected, id);
+            assert_eq!(id, DltServiceId::from(value));
+            assert_eq!(value, id.to_u32());
+            assert_eq!(value, u32::from(id));
+            assert_eq!(Some(name), id.name());
+            assert_eq!(name, format!("{}", id));
+            assert!(false == id.is_injection());
+            assert_eq!(id, id.clone());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn undefined_ids(value in any::<u32>()) {
+            use DltServiceId::*;
+            let id = DltServiceId::from_u32(value);
+            prop_assert_eq!(value, id.to_u32());
+            match value {
+                0 | 0x25..=0xEFF => {
+                    prop_assert_eq!(Unknown(value), id);
+                    prop_assert_eq!(None, id.name());
+                    prop_assert_eq!(format!("unknown(0x{:x})", value), format!("{}", id));
+                    prop_assert!(false == id.is_injection());
+                }
+                0xF00..=0xFFE => {
+                    prop_assert_eq!(UserDefined(value), id);
+                    prop_assert_eq!(None, id.name());
+                    prop_assert_eq!(format!("user_defined(0x{:x})", value), format!("{}", id));
+                    prop_assert!(false == id.is_injection());
+                }
+                0xFFF..=0xFFFF_FFFF => {
+                    prop_assert_eq!(CallSwcInjection(value), id);
+                    prop_assert_eq!(Some("call_swc_injections"), id.name());
+                    prop_assert_eq!("call_swc_injections", format!("{}", id));
+                    prop_assert!(id.is_injection());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn boundaries() {
+        use DltServiceId::*;
+        assert_eq!(Unknown(0), DltServiceId::from_u32(0));
+        assert_eq!(Unknown(0x25), DltServiceId::from_u32(0x25));
+        assert_eq!(Unknown(0xEFF), DltServiceId::from_u32(0xEFF));
+        assert_eq!(UserDefined(0xF00), DltServiceId::from_u32(0xF00));
+        assert_eq!(UserDefined(0xFFE), DltServiceId::from_u32(0xFFE));
+        assert!(false == DltServiceId::from_u32(0xFFE).is_injection());
+        assert_eq!(CallSwcInjection(0xFFF), DltServiceId::from_u32(0xFFF));
+        assert!(DltServiceId::from_u32(0xFFF).is_injection());
+        assert!(DltServiceId::from_u32(u32::MAX).is_injection());
+        assert_eq!(
+            DltServiceId::MIN_INJECTION,
+            crate::control::InjectionMessage::MIN_SERVICE_ID
+        );
+    }
+}
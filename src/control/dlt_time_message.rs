@@ -0,0 +1,257 @@
+use super::{ControlResponse, ControlResponseStatus, DltServiceId};
+use crate::error::ControlDecodeError;
+use crate::{DltControlMessageType, DltMessageType, DltPacketSlice, TimestampInterpretation};
+use core::time::Duration;
+
+/// Time of an ECU sent via a control "time" message (periodically if
+/// timing packets are enabled) or as response to a "get local time"
+/// request.
+///
+/// The time itself is not part of the payload but is the timestamp in
+/// the standard header of the message.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+/// use dlt_parse::control::DltTimeMessage;
+/// use dlt_parse::DltPacketSlice;
+///
+/// let data = [
+///     0b0011_0101, 0, 0, 22, // header type, counter & length
+///     b'E', b'C', b'U', b'1', // ecu id
+///     0, 0, 0x30, 0x39, // timestamp (12345 * 0.1 ms)
+///     0x36, 0, b'D', b'A', b'1', 0, b'D', b'C', b'1', 0, // extended header (control time)
+/// ];
+/// let packet = DltPacketSlice::from_slice(&data).unwrap();
+/// let time = DltTimeMessage::from_packet(&packet).unwrap();
+/// assert_eq!(Some(*b"ECU1"), time.ecu_id);
+/// assert_eq!(Duration::from_micros(1_234_500), time.timestamp);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DltTimeMessage {
+    /// Ecu id of the sender (if present in the header).
+    pub ecu_id: Option<[u8; 4]>,
+    /// Session id of the sender (if present in the header).
+    pub session_id: Option<u32>,
+    /// Timestamp as present in the header (in 0.1 milliseconds).
+    pub raw_timestamp: u32,
+    /// Timestamp converted to a duration (time since the ECU startup).
+    pub timestamp: Duration,
+    /// Status if the message is a response to a "get local time" request
+    /// (`None` for control time messages).
+    pub response_status: Option<ControlResponseStatus>,
+}
+
+impl DltTimeMessage {
+    /// Decodes a control time message or a response to a "get local time"
+    /// request.
+    ///
+    /// The timestamp is interpreted as time since the ECU startup. Use
+    /// [`DltTimeMessage::from_packet_with_interpretation`] for ECUs sending
+    /// absolute timestamps.
+    #[inline]
+    pub fn from_packet(packet: &DltPacketSlice<'_>) -> Result<DltTimeMessage, ControlDecodeError> {
+        DltTimeMessage::from_packet_with_interpretation(packet, TimestampInterpretation::Relative)
+    }
+
+    /// Decodes a control time message or a response to a "get local time"
+    /// request using the given interpretation for the timestamp.
+    pub fn from_packet_with_interpretation(
+        packet: &DltPacketSlice<'_>,
+        interpretation: TimestampInterpretation,
+    ) -> Result<DltTimeMessage, ControlDecodeError> {
+        let response_status = match packet.message_type() {
+            Some(DltMessageType::Control(DltControlMessageType::Time)) => None,
+            Some(DltMessageType::Control(DltControlMessageType::Response))
+                if packet.control_service_id() == Some(DltServiceId::GetLocalTime) =>
+            {
+                Some(ControlResponse::from_packet(packet)?.status)
+            }
+            other => return Err(ControlDecodeError::NotATimeMessage(other)),
+        };
+        let header = packet.header();
+        let raw_timestamp = header
+            .timestamp
+            .ok_or(ControlDecodeError::MissingTimestamp)?;
+        Ok(DltTimeMessage {
+            ecu_id: header.ecu_id,
+            session_id: header.session_id,
+            raw_timestamp,
+            timestamp: interpretation.to_duration(raw_timestamp),
+            response_status,
+        })
+    }
+}
+
+#[cfg(test)]
+mod dlt_time_message_tests {
+    use super::*;
+    use crate::test_packets;
+    use crate::*;
+    use alloc::format;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    /// Periodic time message followed by the response to a "get local
+    /// time" request in the layout sent by the dlt-daemon (little endian
+    /// with ecu id & timestamp).
+    const FIXTURE: &[u8] = include_bytes!("../../tests/fixtures/time_messages.dlt");
+
+    fn packet(
+        is_big_endian: bool,
+        message_type: DltMessageType,
+        ecu_id: Option<[u8; 4]>,
+        timestamp: Option<u32>,
+        payload: &[u8],
+    ) -> Vec<u8> {
+        let ext = DltExtendedHeader::new_non_verbose(message_type, *b"DA1\0", *b"DC1\0").unwrap();
+        let header = DltHeader {
+            ecu_id,
+            timestamp,
+            ..test_packets::header(is_big_endian, Some(ext))
+        };
+        test_packets::packet(header, payload)
+    }
+
+    #[test]
+    fn fixture() {
+        let packets: Vec<_> = SliceIterator::new(FIXTURE).map(|p| p.unwrap()).collect();
+        assert_eq!(2, packets.len());
+
+        assert_eq!(
+            Ok(DltTimeMessage {
+                ecu_id: Some(*b"ECU1"),
+                session_id: None,
+                raw_timestamp: 123_456_789,
+                timestamp: Duration::from_micros(12_345_678_900),
+                response_status: None,
+            }),
+            DltTimeMessage::from_packet(&packets[0])
+        );
+        assert_eq!(
+            Ok(DltTimeMessage {
+                ecu_id: Some(*b"ECU1"),
+                session_id: None,
+                raw_timestamp: 123_466_789,
+                timestamp: Duration::from_micros(12_346_678_900),
+                response_status: Some(ControlResponseStatus::Ok),
+            }),
+            DltTimeMessage::from_packet(&packets[1])
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn time_message(
+            is_big_endian in any::<bool>(),
+            ecu_id in proptest::option::of(any::<[u8; 4]>()),
+            timestamp in any::<u32>(),
+            status in any::<u8>(),
+        ) {
+            let time = DltMessageType::Control(DltControlMessageType::Time);
+            let bytes = packet(is_big_endian, time, ecu_id, Some(timestamp), &[]);
+            let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+            prop_assert_eq!(Some(time), slice.message_type());
+            prop_assert_eq!(
+                Ok(DltTimeMessage {
+                    ecu_id,
+                    session_id: None,
+                    raw_timestamp: timestamp,
+                    timestamp: TimestampInterpretation::Relative.to_duration(timestamp),
+                    response_status: None,
+                }),
+                DltTimeMessage::from_packet(&slice)
+            );
+            prop_assert_eq!(
+                Ok(Duration::from_secs(u64::from(timestamp))),
+                DltTimeMessage::from_packet_with_interpretation(
+                    &slice,
+                    TimestampInterpretation::Absolute
+                )
+                .map(|t| t.timestamp)
+            );
+
+            // get local time response
+            let mut payload = if is_big_endian {
+                0x0Cu32.to_be_bytes()
+            } else {
+                0x0Cu32.to_le_bytes()
+            }
+            .to_vec();
+            payload.push(status);
+            let response = DltMessageType::Control(DltControlMessageType::Response);
+            let bytes = packet(is_big_endian, response, ecu_id, Some(timestamp), &payload);
+            let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+            prop_assert_eq!(
+                Ok(Some(ControlResponseStatus::from_u8(status))),
+                DltTimeMessage::from_packet(&slice).map(|t| t.response_status)
+            );
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let time = DltMessageType::Control(DltControlMessageType::Time);
+        let response = DltMessageType::Control(DltControlMessageType::Response);
+
+        // no timestamp
+        let bytes = packet(true, time, None, None, &[]);
+        assert_eq!(
+            Err(ControlDecodeError::MissingTimestamp),
+            DltTimeMessage::from_packet(&DltPacketSlice::from_slice(&bytes).unwrap())
+        );
+
+        // other message types & responses to other services
+        for (message_type, payload) in [
+            (
+                DltMessageType::Log(DltLogLevel::Info),
+                &[0u8, 0, 0, 0x0C, 0][..],
+            ),
+            (
+                DltMessageType::Control(DltControlMessageType::Request),
+                &[0, 0, 0, 0x0C][..],
+            ),
+            (response, &[0, 0, 0, 0x0B, 0][..]),
+            (response, &[0, 0, 0][..]),
+        ] {
+            let bytes = packet(true, message_type, None, Some(1), payload);
+            assert_eq!(
+                Err(ControlDecodeError::NotATimeMessage(Some(message_type))),
+                DltTimeMessage::from_packet(&DltPacketSlice::from_slice(&bytes).unwrap())
+            );
+        }
+
+        // get local time response without status
+        let bytes = packet(true, response, None, Some(1), &[0, 0, 0, 0x0C]);
+        assert!(matches!(
+            DltTimeMessage::from_packet(&DltPacketSlice::from_slice(&bytes).unwrap()),
+            Err(ControlDecodeError::UnexpectedEndOfSlice(_))
+        ));
+
+        // no extended header
+        let mut header = DltHeader {
+            timestamp: Some(1),
+            ..Default::default()
+        };
+        header.length = header.header_len();
+        let bytes = header.to_bytes();
+        assert_eq!(
+            Err(ControlDecodeError::NotATimeMessage(None)),
+            DltTimeMessage::from_packet(&DltPacketSlice::from_slice(&bytes).unwrap())
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let time = DltTimeMessage {
+            ecu_id: None,
+            session_id: None,
+            raw_timestamp: 0,
+            timestamp: Duration::ZERO,
+            response_status: None,
+        };
+        assert_eq!(time, time.clone());
+        assert!(format!("{:?}", time).starts_with("DltTimeMessage {"));
+    }
+}
@@ -0,0 +1,137 @@
+use super::context_params::{add_service_id, check_service_id};
+use super::{ControlRequest, ControlResponse, DltServiceId, GetDefaultLogLevelResponse};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// "Get default log level" control request (service id 0x04) requesting
+/// the ECU wide default log level.
+///
+/// The request has no parameters (the payload only consists of the
+/// service id).
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use dlt_parse::control::{ControlRequest, GetDefaultLogLevelRequest};
+/// use dlt_parse::DltPacketSlice;
+///
+/// let packet_bytes = GetDefaultLogLevelRequest.build(true);
+///
+/// let packet = DltPacketSlice::from_slice(&packet_bytes).unwrap();
+/// let control = ControlRequest::from_packet(&packet).unwrap();
+/// assert_eq!(Ok(GetDefaultLogLevelRequest), GetDefaultLogLevelRequest::parse(&control));
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct GetDefaultLogLevelRequest;
+
+impl GetDefaultLogLevelRequest {
+    /// Service id of the "get default log level" control message.
+    pub const SERVICE_ID: u32 = 0x04;
+
+    /// Checks that the given request is a "get default log level" request.
+    ///
+    /// Data after the service id is ignored.
+    pub fn parse(
+        request: &ControlRequest<'_>,
+    ) -> Result<GetDefaultLogLevelRequest, ControlDecodeError> {
+        check_service_id(DltServiceId::GetDefaultLogLevel, request.service_id)?;
+        Ok(GetDefaultLogLevelRequest)
+    }
+
+    /// Decodes the response to a "get default log level" request
+    /// (see [`GetDefaultLogLevelResponse::parse`]).
+    #[inline]
+    pub fn parse_response(
+        response: &ControlResponse<'_>,
+    ) -> Result<GetDefaultLogLevelResponse, ControlDecodeError> {
+        GetDefaultLogLevelResponse::parse(response)
+    }
+
+    /// Adds the control payload (the service id) to the given buffer.
+    #[inline]
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        add_service_id(DltServiceId::GetDefaultLogLevel, buf, is_big_endian)
+    }
+
+    /// Returns the complete DLT packet of the request (non verbose
+    /// control request with the application & context id
+    /// [`ControlRequest::DEFAULT_APPLICATION_ID`] &
+    /// [`ControlRequest::DEFAULT_CONTEXT_ID`]).
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn build(&self, is_big_endian: bool) -> Vec<u8> {
+        ControlRequest::build_service_packet(DltServiceId::GetDefaultLogLevel, is_big_endian, &[])
+    }
+}
+
+#[cfg(test)]
+mod get_default_log_level_request_tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::{DltControlMessageType, DltMessageType, DltPacketSlice};
+    use alloc::format;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn build_parse() {
+        for is_big_endian in [false, true] {
+            let bytes = GetDefaultLogLevelRequest.build(is_big_endian);
+            let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+            assert_eq!(is_big_endian, packet.is_big_endian());
+            assert_eq!(
+                Some(DltMessageType::Control(DltControlMessageType::Request)),
+                packet.message_type()
+            );
+            if is_big_endian {
+                assert_eq!(&[0, 0, 0, 4], packet.payload());
+            } else {
+                assert_eq!(&[4, 0, 0, 0], packet.payload());
+            }
+            let control = ControlRequest::from_packet(&packet).unwrap();
+            assert_eq!(
+                Ok(GetDefaultLogLevelRequest),
+                GetDefaultLogLevelRequest::parse(&control)
+            );
+        }
+
+        // capacity error
+        let mut small = ArrayVec::<u8, 3>::new();
+        assert_eq!(
+            Err(CapacityError::new(())),
+            GetDefaultLogLevelRequest.add_to_msg(&mut small, true)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let request = ControlRequest {
+            service_id: DltServiceId::SetDefaultLogLevel,
+            params: &[],
+            is_big_endian: true,
+            payload_offset: 0,
+        };
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::GetDefaultLogLevel,
+                actual: DltServiceId::SetDefaultLogLevel,
+            }),
+            GetDefaultLogLevelRequest::parse(&request)
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let request = GetDefaultLogLevelRequest;
+        assert_eq!(request, request.clone());
+        assert_eq!("GetDefaultLogLevelRequest", format!("{:?}", request));
+    }
+}
@@ -0,0 +1,181 @@
+use super::context_params::{add_service_id, check_service_id};
+use super::{ControlLogLevel, ControlResponse, ControlResponseStatus, DltServiceId};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+
+/// Response to a "get default log level" control message (service id 0x04).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{
+///     ControlLogLevel, ControlResponse, ControlResponseStatus, GetDefaultLogLevelResponse,
+/// };
+/// use dlt_parse::DltLogLevel;
+///
+/// // service id, status & log level (big endian)
+/// let payload = [0, 0, 0, 0x04, 0, 4];
+/// let response = ControlResponse::from_slice(&payload, true).unwrap();
+/// assert_eq!(
+///     Ok(GetDefaultLogLevelResponse {
+///         status: ControlResponseStatus::Ok,
+///         log_level: Some(ControlLogLevel::Level(DltLogLevel::Info)),
+///     }),
+///     GetDefaultLogLevelResponse::parse(&response)
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GetDefaultLogLevelResponse {
+    /// Status of the response.
+    pub status: ControlResponseStatus,
+    /// Default log level of the ECU (`None` if the response only
+    /// contains a status, e.g. if the service is not supported).
+    pub log_level: Option<ControlLogLevel>,
+}
+
+impl GetDefaultLogLevelResponse {
+    /// Service id of the "get default log level" control message.
+    pub const SERVICE_ID: u32 = 0x04;
+
+    /// Decodes the response.
+    ///
+    /// Responses with a status other than [`ControlResponseStatus::Ok`] are
+    /// allowed to end after the status (`log_level` is then `None`). Log
+    /// level values outside of the defined range are reported as
+    /// [`ControlDecodeError::InvalidLogLevel`].
+    pub fn parse(
+        response: &ControlResponse<'_>,
+    ) -> Result<GetDefaultLogLevelResponse, ControlDecodeError> {
+        check_service_id(DltServiceId::GetDefaultLogLevel, response.service_id)?;
+        let log_level = if response.data.is_empty() && response.status != ControlResponseStatus::Ok
+        {
+            None
+        } else {
+            Some(ControlLogLevel::from_i8(response.data_reader().read_i8()?)?)
+        };
+        Ok(GetDefaultLogLevelResponse {
+            status: response.status,
+            log_level,
+        })
+    }
+
+    /// Adds the control payload (service id, status & log level if present)
+    /// to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        let len = if self.log_level.is_some() { 6 } else { 5 };
+        if buf.remaining_capacity() < len {
+            return Err(CapacityError::new(()));
+        }
+        add_service_id(DltServiceId::GetDefaultLogLevel, buf, is_big_endian)?;
+        buf.try_extend_from_slice(&[self.status.to_u8()])?;
+        if let Some(log_level) = self.log_level {
+            buf.try_extend_from_slice(&log_level.to_i8().to_ne_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod get_default_log_level_response_tests {
+    use super::*;
+    use crate::error::{Layer, UnexpectedEndOfSliceError};
+    use crate::DltLogLevel;
+    use alloc::format;
+
+    #[test]
+    fn add_to_msg_parse() {
+        for is_big_endian in [false, true] {
+            for status in [
+                ControlResponseStatus::Ok,
+                ControlResponseStatus::NotSupported,
+                ControlResponseStatus::Error,
+            ] {
+                for value in -1i8..=6 {
+                    let expected = GetDefaultLogLevelResponse {
+                        status,
+                        log_level: Some(ControlLogLevel::from_i8(value).unwrap()),
+                    };
+                    let mut buf = ArrayVec::<u8, 6>::new();
+                    expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+                    let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+                    assert_eq!(Ok(expected), GetDefaultLogLevelResponse::parse(&response));
+                }
+            }
+
+            // status only
+            let expected = GetDefaultLogLevelResponse {
+                status: ControlResponseStatus::NotSupported,
+                log_level: None,
+            };
+            let mut buf = ArrayVec::<u8, 6>::new();
+            expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+            assert_eq!(5, buf.len());
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+            assert_eq!(Ok(expected), GetDefaultLogLevelResponse::parse(&response));
+        }
+
+        // capacity error
+        let mut small = ArrayVec::<u8, 5>::new();
+        assert_eq!(
+            Err(CapacityError::new(())),
+            GetDefaultLogLevelResponse {
+                status: ControlResponseStatus::Ok,
+                log_level: Some(ControlLogLevel::Off),
+            }
+            .add_to_msg(&mut small, true)
+        );
+        assert!(small.is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        // out of range log level
+        let response = ControlResponse::from_slice(&[0, 0, 0, 4, 0, 7], true).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::InvalidLogLevel(7)),
+            GetDefaultLogLevelResponse::parse(&response)
+        );
+        let response = ControlResponse::from_slice(&[4, 0, 0, 0, 0, 0xfe], false).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::InvalidLogLevel(-2)),
+            GetDefaultLogLevelResponse::parse(&response)
+        );
+
+        // ok status without log level
+        let response = ControlResponse::from_slice(&[0, 0, 0, 4, 0], true).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 6,
+                    actual_size: 5,
+                }
+            )),
+            GetDefaultLogLevelResponse::parse(&response)
+        );
+
+        // wrong service id
+        let response = ControlResponse::from_slice(&[0, 0, 0, 0x11, 0, 4], true).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::GetDefaultLogLevel,
+                actual: DltServiceId::SetDefaultLogLevel,
+            }),
+            GetDefaultLogLevelResponse::parse(&response)
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let response = GetDefaultLogLevelResponse {
+            status: ControlResponseStatus::Ok,
+            log_level: Some(DltLogLevel::Warn.into()),
+        };
+        assert_eq!(response, response.clone());
+        assert!(format!("{:?}", response).starts_with("GetDefaultLogLevelResponse {"));
+    }
+}
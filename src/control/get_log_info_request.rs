@@ -0,0 +1,392 @@
+use super::context_params::{add_service_id, check_service_id};
+use super::{padded_id, ControlRequest, DltServiceId};
+use crate::error::{ControlDecodeError, RangeError};
+use arrayvec::{ArrayVec, CapacityError};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// "Get log info" control request (service id 0x03) querying the
+/// registered applications & contexts of an ECU.
+///
+/// The parameters have the layout:
+///
+/// ```text
+/// | options (u8) | app id (4 bytes) | context id (4 bytes) | com interface (4 bytes) |
+/// ```
+///
+/// The options (3..=7) determine how much information the response
+/// contains (see the statuses of [`super::GetLogInfoResponse`]). An app
+/// id or context id of zero requests the info of all applications or
+/// contexts.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use dlt_parse::control::{ControlRequest, GetLogInfoRequest};
+/// use dlt_parse::DltPacketSlice;
+///
+/// let request = GetLogInfoRequest::new(GetLogInfoRequest::OPTIONS_WITH_LOG_LEVEL)
+///     .unwrap()
+///     .with_app_id("APP1")
+///     .unwrap();
+/// let packet_bytes = request.build(false);
+///
+/// let packet = DltPacketSlice::from_slice(&packet_bytes).unwrap();
+/// let control = ControlRequest::from_packet(&packet).unwrap();
+/// assert_eq!(Ok(request), GetLogInfoRequest::parse(&control));
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GetLogInfoRequest {
+    /// Amount of information requested (3..=7).
+    pub options: u8,
+    /// Application id filter (all applications if zero).
+    pub app_id: [u8; 4],
+    /// Context id filter (all contexts if zero).
+    pub ctx_id: [u8; 4],
+    /// Communication interface (reserved).
+    pub com_interface: [u8; 4],
+}
+
+impl GetLogInfoRequest {
+    /// Service id of the "get log info" control message.
+    pub const SERVICE_ID: u32 = 0x03;
+
+    /// Communication interface value used by the dlt-daemon & dlt-viewer.
+    pub const DEFAULT_COM_INTERFACE: [u8; 4] = *b"remo";
+
+    /// Length of the parameters (without service id).
+    pub const PARAMS_LEN: usize = 13;
+
+    /// Options: application & context ids.
+    pub const OPTIONS_IDS: u8 = 3;
+
+    /// Options: ids & log levels.
+    pub const OPTIONS_WITH_LOG_LEVEL: u8 = 4;
+
+    /// Options: ids & trace status.
+    pub const OPTIONS_WITH_TRACE_STATUS: u8 = 5;
+
+    /// Options: ids, log levels & trace status.
+    pub const OPTIONS_WITH_LOG_LEVEL_AND_TRACE_STATUS: u8 = 6;
+
+    /// Options: ids, log levels, trace status & descriptions.
+    pub const OPTIONS_WITH_DESCRIPTIONS: u8 = 7;
+
+    /// Creates a request for the info of all applications & contexts
+    /// with the default com interface.
+    ///
+    /// Returns [`RangeError::GetLogInfoOptionsOutsideOfRange`] if the
+    /// options are not in the range 3..=7.
+    pub fn new(options: u8) -> Result<GetLogInfoRequest, RangeError> {
+        if false
+            == (GetLogInfoRequest::OPTIONS_IDS..=GetLogInfoRequest::OPTIONS_WITH_DESCRIPTIONS)
+                .contains(&options)
+        {
+            return Err(RangeError::GetLogInfoOptionsOutsideOfRange(options));
+        }
+        Ok(GetLogInfoRequest {
+            options,
+            app_id: [0; 4],
+            ctx_id: [0; 4],
+            com_interface: GetLogInfoRequest::DEFAULT_COM_INTERFACE,
+        })
+    }
+
+    /// Restricts the request to the application with the given id.
+    ///
+    /// Returns [`RangeError::IdTooLong`] if the id is longer than 4 bytes
+    /// (shorter ids are padded with zeros).
+    pub fn with_app_id(mut self, app_id: &str) -> Result<Self, RangeError> {
+        self.app_id = padded_id(app_id).ok_or(RangeError::IdTooLong(app_id.len()))?;
+        Ok(self)
+    }
+
+    /// Restricts the request to the context with the given id.
+    ///
+    /// Returns [`RangeError::IdTooLong`] if the id is longer than 4 bytes
+    /// (shorter ids are padded with zeros).
+    pub fn with_ctx_id(mut self, ctx_id: &str) -> Result<Self, RangeError> {
+        self.ctx_id = padded_id(ctx_id).ok_or(RangeError::IdTooLong(ctx_id.len()))?;
+        Ok(self)
+    }
+
+    /// Sets the communication interface.
+    pub fn with_com_interface(mut self, com_interface: [u8; 4]) -> Self {
+        self.com_interface = com_interface;
+        self
+    }
+
+    /// Decodes the parameters of a "get log info" request.
+    ///
+    /// Data after the parameters is ignored.
+    pub fn parse(request: &ControlRequest<'_>) -> Result<GetLogInfoRequest, ControlDecodeError> {
+        check_service_id(DltServiceId::GetLogInfo, request.service_id)?;
+        let mut reader = request.params_reader();
+        let options = reader.read_u8()?;
+        if false
+            == (GetLogInfoRequest::OPTIONS_IDS..=GetLogInfoRequest::OPTIONS_WITH_DESCRIPTIONS)
+                .contains(&options)
+        {
+            return Err(ControlDecodeError::InvalidGetLogInfoOptions(options));
+        }
+        Ok(GetLogInfoRequest {
+            options,
+            app_id: reader.read_id()?,
+            ctx_id: reader.read_id()?,
+            com_interface: reader.read_id()?,
+        })
+    }
+
+    /// Adds the control payload (including the service id) to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        if buf.remaining_capacity() < 4 + GetLogInfoRequest::PARAMS_LEN {
+            return Err(CapacityError::new(()));
+        }
+        add_service_id(DltServiceId::GetLogInfo, buf, is_big_endian)?;
+        buf.try_extend_from_slice(&[self.options])?;
+        buf.try_extend_from_slice(&self.app_id)?;
+        buf.try_extend_from_slice(&self.ctx_id)?;
+        buf.try_extend_from_slice(&self.com_interface)
+    }
+
+    /// Returns the complete DLT packet of the request (non verbose
+    /// control request with the application & context id
+    /// [`ControlRequest::DEFAULT_APPLICATION_ID`] &
+    /// [`ControlRequest::DEFAULT_CONTEXT_ID`]).
+    #[cfg(feature = "std")]
+    pub fn build(&self, is_big_endian: bool) -> Vec<u8> {
+        let mut payload = ArrayVec::<u8, { 4 + GetLogInfoRequest::PARAMS_LEN }>::new();
+        // can not fail as the capacity matches the payload length
+        self.add_to_msg(&mut payload, is_big_endian).unwrap();
+        ControlRequest::build_packet(is_big_endian, &payload)
+    }
+}
+
+#[cfg(test)]
+mod get_log_info_request_tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::control::{
+        AppIdInfo, ContextInfo, ControlResponse, ControlResponseStatus, GetLogInfoResponse,
+    };
+    #[cfg(feature = "std")]
+    use crate::test_packets;
+    #[cfg(feature = "std")]
+    use crate::{
+        DltControlMessageType, DltExtendedHeader, DltHeader, DltMessageType, DltPacketSlice,
+    };
+    use alloc::format;
+    #[cfg(feature = "std")]
+    use alloc::vec;
+    use proptest::prelude::*;
+
+    #[cfg(feature = "std")]
+    proptest! {
+        #[test]
+        fn build_parse(
+            options in 3u8..=7,
+            app_id in any::<[u8; 4]>(),
+            ctx_id in any::<[u8; 4]>(),
+            com_interface in any::<[u8; 4]>(),
+            is_big_endian in any::<bool>(),
+        ) {
+            let request = GetLogInfoRequest {
+                options,
+                app_id,
+                ctx_id,
+                com_interface,
+            };
+            let bytes = request.build(is_big_endian);
+            let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+            prop_assert_eq!(is_big_endian, packet.is_big_endian());
+            prop_assert_eq!(
+                Some(DltMessageType::Control(DltControlMessageType::Request)),
+                packet.message_type()
+            );
+
+            let control = ControlRequest::from_packet(&packet).unwrap();
+            prop_assert_eq!(DltServiceId::GetLogInfo, control.service_id);
+            prop_assert_eq!(Ok(request.clone()), GetLogInfoRequest::parse(&control));
+
+            // missing parameter bytes
+            for len in 0..GetLogInfoRequest::PARAMS_LEN {
+                let short = ControlRequest {
+                    params: &control.params[..len],
+                    ..control.clone()
+                };
+                let is_end_of_slice = matches!(
+                    GetLogInfoRequest::parse(&short),
+                    Err(ControlDecodeError::UnexpectedEndOfSlice(_))
+                );
+                prop_assert!(is_end_of_slice);
+            }
+
+            // capacity error
+            let mut small = ArrayVec::<u8, 16>::new();
+            prop_assert_eq!(Err(CapacityError::new(())), request.add_to_msg(&mut small, is_big_endian));
+            prop_assert!(small.is_empty());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn new_options(options in any::<u8>()) {
+            let result = GetLogInfoRequest::new(options);
+            if (3..=7).contains(&options) {
+                prop_assert_eq!(
+                    Ok(GetLogInfoRequest {
+                        options,
+                        app_id: [0; 4],
+                        ctx_id: [0; 4],
+                        com_interface: *b"remo",
+                    }),
+                    result
+                );
+            } else {
+                prop_assert_eq!(
+                    Err(RangeError::GetLogInfoOptionsOutsideOfRange(options)),
+                    result
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn filters() {
+        let request = GetLogInfoRequest::new(GetLogInfoRequest::OPTIONS_IDS)
+            .unwrap()
+            .with_app_id("APP")
+            .unwrap()
+            .with_ctx_id("CTX1")
+            .unwrap()
+            .with_com_interface(*b"seri");
+        assert_eq!(*b"APP\0", request.app_id);
+        assert_eq!(*b"CTX1", request.ctx_id);
+        assert_eq!(*b"seri", request.com_interface);
+
+        assert_eq!(
+            Err(RangeError::IdTooLong(5)),
+            request.clone().with_app_id("APP12")
+        );
+        assert_eq!(
+            Err(RangeError::IdTooLong(6)),
+            request.clone().with_ctx_id("CTX123")
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        // invalid options
+        for options in [0u8, 2, 8, 0xff] {
+            let params = [options, 0, 0, 0, 0, 0, 0, 0, 0, b'r', b'e', b'm', b'o'];
+            let request = ControlRequest {
+                service_id: DltServiceId::GetLogInfo,
+                params: &params,
+                is_big_endian: false,
+                payload_offset: 0,
+            };
+            assert_eq!(
+                Err(ControlDecodeError::InvalidGetLogInfoOptions(options)),
+                GetLogInfoRequest::parse(&request)
+            );
+        }
+
+        // other service id
+        let request = ControlRequest {
+            service_id: DltServiceId::SetLogLevel,
+            params: &[],
+            is_big_endian: false,
+            payload_offset: 0,
+        };
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::GetLogInfo,
+                actual: DltServiceId::SetLogLevel,
+            }),
+            GetLogInfoRequest::parse(&request)
+        );
+    }
+
+    /// Builds a request & parses a faked response of the ECU.
+    #[cfg(feature = "std")]
+    #[test]
+    fn discovery_round_trip() {
+        let request = GetLogInfoRequest::new(GetLogInfoRequest::OPTIONS_WITH_LOG_LEVEL)
+            .unwrap()
+            .with_app_id("APP1")
+            .unwrap();
+        let request_bytes = request.build(false);
+        let request_packet = DltPacketSlice::from_slice(&request_bytes).unwrap();
+        let received =
+            GetLogInfoRequest::parse(&ControlRequest::from_packet(&request_packet).unwrap())
+                .unwrap();
+
+        // response of the ECU only containing the requested application
+        let mut payload = GetLogInfoRequest::SERVICE_ID.to_le_bytes().to_vec();
+        payload.push(received.options);
+        payload.extend_from_slice(&1u16.to_le_bytes());
+        payload.extend_from_slice(&received.app_id);
+        payload.extend_from_slice(&2u16.to_le_bytes());
+        payload.extend_from_slice(b"CTX1");
+        payload.push(4);
+        payload.extend_from_slice(b"CTX2");
+        payload.push(0xff);
+        payload.extend_from_slice(&received.com_interface);
+
+        let ext = DltExtendedHeader::new_non_verbose(
+            DltMessageType::Control(DltControlMessageType::Response),
+            *b"DA1\0",
+            *b"DC1\0",
+        )
+        .unwrap();
+        let header = DltHeader {
+            ecu_id: Some(*b"ECU1"),
+            ..test_packets::header(false, Some(ext))
+        };
+        let response_bytes = test_packets::packet(header, &payload);
+
+        let response_packet = DltPacketSlice::from_slice(&response_bytes).unwrap();
+        let response = ControlResponse::from_packet(&response_packet).unwrap();
+        assert_eq!(DltServiceId::GetLogInfo, response.service_id);
+        assert_eq!(ControlResponseStatus::Other(4), response.status);
+        assert_eq!(
+            Ok(GetLogInfoResponse {
+                status: GetLogInfoRequest::OPTIONS_WITH_LOG_LEVEL,
+                app_ids: vec![AppIdInfo {
+                    app_id: *b"APP1",
+                    contexts: vec![
+                        ContextInfo {
+                            ctx_id: *b"CTX1",
+                            log_level: Some(4),
+                            trace_status: None,
+                            description: None,
+                        },
+                        ContextInfo {
+                            ctx_id: *b"CTX2",
+                            log_level: Some(-1),
+                            trace_status: None,
+                            description: None,
+                        },
+                    ],
+                    description: None,
+                }],
+                com_interface: Some(*b"remo"),
+            }),
+            GetLogInfoResponse::from_slice(&response_packet.payload()[4..], false)
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let request = GetLogInfoRequest::new(3).unwrap();
+        assert_eq!(request, request.clone());
+        assert!(format!("{:?}", request).starts_with("GetLogInfoRequest {"));
+    }
+}
@@ -0,0 +1,422 @@
+use super::PayloadReader;
+use crate::error::ControlDecodeError;
+use std::vec::Vec;
+
+/// Response to a "get log info" control message (service id 0x03).
+///
+/// The status of the response determines which fields are present:
+///
+/// | status | content                                                 |
+/// |--------|---------------------------------------------------------|
+/// | 3      | application & context ids                               |
+/// | 4      | ids & log levels                                        |
+/// | 5      | ids & trace status                                      |
+/// | 6      | ids, log levels & trace status                          |
+/// | 7      | ids, log levels, trace status & descriptions            |
+/// | 8      | no matching context ids (no log info)                   |
+/// | 9      | response data overflow (no log info)                    |
+///
+/// For the statuses without log info (1 = not supported, 2 = error, 8, 9 &
+/// unknown values) `app_ids` is empty & `com_interface` is `None`.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::GetLogInfoResponse;
+///
+/// // payload after the service id (big endian)
+/// let data = [
+///     4, // status: with log levels
+///     0, 1, // number of application ids
+///     b'A', b'P', b'P', b'1', // application id
+///     0, 1, // number of context ids
+///     b'C', b'T', b'X', b'1', // context id
+///     5, // log level
+///     b'r', b'e', b'm', b'o', // com interface
+/// ];
+/// let response = GetLogInfoResponse::from_slice(&data, true).unwrap();
+/// assert_eq!(*b"APP1", response.app_ids[0].app_id);
+/// assert_eq!(*b"CTX1", response.app_ids[0].contexts[0].ctx_id);
+/// assert_eq!(Some(5), response.app_ids[0].contexts[0].log_level);
+/// assert_eq!(None, response.app_ids[0].contexts[0].trace_status);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GetLogInfoResponse<'a> {
+    /// Status of the response (see table above).
+    pub status: u8,
+    /// Application ids with their contexts.
+    pub app_ids: Vec<AppIdInfo<'a>>,
+    /// Communication interface the response was sent over (only present
+    /// if the response contains log info).
+    pub com_interface: Option<[u8; 4]>,
+}
+
+/// Application id & its contexts in a [`GetLogInfoResponse`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AppIdInfo<'a> {
+    /// Application id.
+    pub app_id: [u8; 4],
+    /// Contexts registered for the application.
+    pub contexts: Vec<ContextInfo<'a>>,
+    /// Description of the application (only present with status 7).
+    pub description: Option<&'a str>,
+}
+
+/// Context information in a [`GetLogInfoResponse`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ContextInfo<'a> {
+    /// Context id.
+    pub ctx_id: [u8; 4],
+    /// Log level of the context (only present with status 4, 6 & 7).
+    pub log_level: Option<i8>,
+    /// Trace status of the context (only present with status 5, 6 & 7).
+    pub trace_status: Option<i8>,
+    /// Description of the context (only present with status 7).
+    pub description: Option<&'a str>,
+}
+
+impl<'a> GetLogInfoResponse<'a> {
+    /// Service id of the "get log info" control message.
+    pub const SERVICE_ID: u32 = 0x03;
+
+    /// Status: application & context ids without log level or trace status.
+    pub const STATUS_IDS: u8 = 3;
+
+    /// Status: with log levels.
+    pub const STATUS_WITH_LOG_LEVEL: u8 = 4;
+
+    /// Status: with trace status.
+    pub const STATUS_WITH_TRACE_STATUS: u8 = 5;
+
+    /// Status: with log levels & trace status.
+    pub const STATUS_WITH_LOG_LEVEL_AND_TRACE_STATUS: u8 = 6;
+
+    /// Status: with log levels, trace status & descriptions.
+    pub const STATUS_WITH_DESCRIPTIONS: u8 = 7;
+
+    /// Status: no matching context ids.
+    pub const STATUS_NO_MATCHING_CONTEXT: u8 = 8;
+
+    /// Status: response data overflow.
+    pub const STATUS_OVERFLOW: u8 = 9;
+
+    /// Decodes the response from the payload of a control message
+    /// (payload after the service id).
+    pub fn from_slice(
+        slice: &'a [u8],
+        is_big_endian: bool,
+    ) -> Result<GetLogInfoResponse<'a>, ControlDecodeError> {
+        let mut reader = PayloadReader::new(slice, is_big_endian);
+        let status = reader.read_u8()?;
+        if false
+            == (GetLogInfoResponse::STATUS_IDS..=GetLogInfoResponse::STATUS_WITH_DESCRIPTIONS)
+                .contains(&status)
+        {
+            return Ok(GetLogInfoResponse {
+                status,
+                app_ids: Vec::new(),
+                com_interface: None,
+            });
+        }
+
+        let with_log_level = matches!(status, 4 | 6 | 7);
+        let with_trace_status = matches!(status, 5..=7);
+        let with_description = status == GetLogInfoResponse::STATUS_WITH_DESCRIPTIONS;
+
+        let app_count = reader.read_u16()?;
+        // don't trust the count for the allocation (each app id
+        // needs at least 6 bytes)
+        let mut app_ids = Vec::with_capacity(usize::from(app_count).min(reader.rest().len() / 6));
+        for _ in 0..app_count {
+            let app_id = reader.read_id()?;
+            let ctx_count = reader.read_u16()?;
+            let mut contexts =
+                Vec::with_capacity(usize::from(ctx_count).min(reader.rest().len() / 4));
+            for _ in 0..ctx_count {
+                let ctx_id = reader.read_id()?;
+                let log_level = if with_log_level {
+                    Some(reader.read_i8()?)
+                } else {
+                    None
+                };
+                let trace_status = if with_trace_status {
+                    Some(reader.read_i8()?)
+                } else {
+                    None
+                };
+                let description = if with_description {
+                    Some(read_description(&mut reader)?)
+                } else {
+                    None
+                };
+                contexts.push(ContextInfo {
+                    ctx_id,
+                    log_level,
+                    trace_status,
+                    description,
+                });
+            }
+            let description = if with_description {
+                Some(read_description(&mut reader)?)
+            } else {
+                None
+            };
+            app_ids.push(AppIdInfo {
+                app_id,
+                contexts,
+                description,
+            });
+        }
+        let com_interface = reader.read_id()?;
+
+        Ok(GetLogInfoResponse {
+            status,
+            app_ids,
+            com_interface: Some(com_interface),
+        })
+    }
+}
+
+/// Reads a description string with an u16 length prefix.
+fn read_description<'a>(reader: &mut PayloadReader<'a>) -> Result<&'a str, ControlDecodeError> {
+    let len = reader.read_u16()?;
+    let data = reader.read_raw(usize::from(len))?;
+    Ok(core::str::from_utf8(data)?)
+}
+
+#[cfg(test)]
+mod get_log_info_response_tests {
+    use super::*;
+    use crate::control::{ControlResponse, ControlResponseStatus, DltServiceId};
+    use crate::error::{Layer, UnexpectedEndOfSliceError};
+    use crate::DltPacketSlice;
+    use alloc::vec;
+
+    /// Response in the layout sent by the dlt-daemon (little endian,
+    /// status 7 with two applications).
+    const FIXTURE: &[u8] = include_bytes!("../../tests/fixtures/get_log_info_response.dlt");
+
+    fn fixture_expected() -> GetLogInfoResponse<'static> {
+        GetLogInfoResponse {
+            status: 7,
+            app_ids: vec![
+                AppIdInfo {
+                    app_id: *b"DA1\0",
+                    contexts: vec![
+                        ContextInfo {
+                            ctx_id: *b"DC1\0",
+                            log_level: Some(4),
+                            trace_status: Some(0),
+                            description: Some("Context of main dlt system manager"),
+                        },
+                        ContextInfo {
+                            ctx_id: *b"DC2\0",
+                            log_level: Some(4),
+                            trace_status: Some(0),
+                            description: Some("Context of dlt client"),
+                        },
+                    ],
+                    description: Some("DLT Daemon"),
+                },
+                AppIdInfo {
+                    app_id: *b"LOG\0",
+                    contexts: vec![ContextInfo {
+                        ctx_id: *b"TEST",
+                        log_level: Some(-1),
+                        trace_status: Some(-1),
+                        description: Some("Test Context for Logging"),
+                    }],
+                    description: Some("Test Application for Logging"),
+                },
+            ],
+            com_interface: Some(*b"remo"),
+        }
+    }
+
+    /// Serializes the response (payload after the service id).
+    fn serialize(response: &GetLogInfoResponse<'_>, is_big_endian: bool) -> Vec<u8> {
+        let u16_bytes = |v: u16| {
+            if is_big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+        let mut result = vec![response.status];
+        result.extend_from_slice(&u16_bytes(response.app_ids.len() as u16));
+        for app in &response.app_ids {
+            result.extend_from_slice(&app.app_id);
+            result.extend_from_slice(&u16_bytes(app.contexts.len() as u16));
+            for ctx in &app.contexts {
+                result.extend_from_slice(&ctx.ctx_id);
+                if let Some(log_level) = ctx.log_level {
+                    result.push(log_level as u8);
+                }
+                if let Some(trace_status) = ctx.trace_status {
+                    result.push(trace_status as u8);
+                }
+                if let Some(description) = ctx.description {
+                    result.extend_from_slice(&u16_bytes(description.len() as u16));
+                    result.extend_from_slice(description.as_bytes());
+                }
+            }
+            if let Some(description) = app.description {
+                result.extend_from_slice(&u16_bytes(description.len() as u16));
+                result.extend_from_slice(description.as_bytes());
+            }
+        }
+        result.extend_from_slice(&response.com_interface.unwrap());
+        result
+    }
+
+    #[test]
+    fn fixture() {
+        let packet = DltPacketSlice::from_slice(FIXTURE).unwrap();
+        let response = ControlResponse::from_packet(&packet).unwrap();
+        assert_eq!(DltServiceId::GetLogInfo, response.service_id);
+        assert_eq!(ControlResponseStatus::Other(7), response.status);
+
+        let payload = &packet.payload()[4..];
+        assert_eq!(
+            Ok(fixture_expected()),
+            GetLogInfoResponse::from_slice(payload, packet.is_big_endian())
+        );
+
+        // every truncation results in an error
+        for len in 0..payload.len() {
+            assert_matches!(
+                GetLogInfoResponse::from_slice(&payload[..len], false),
+                Err(ControlDecodeError::UnexpectedEndOfSlice(_))
+            );
+        }
+    }
+
+    #[test]
+    fn statuses() {
+        for is_big_endian in [false, true] {
+            for status in 3..=7 {
+                let response = GetLogInfoResponse {
+                    status,
+                    app_ids: vec![AppIdInfo {
+                        app_id: *b"APP1",
+                        contexts: vec![
+                            ContextInfo {
+                                ctx_id: *b"CTX1",
+                                log_level: matches!(status, 4 | 6 | 7).then(|| 6),
+                                trace_status: matches!(status, 5..=7).then(|| 1),
+                                description: (status == 7).then(|| "ctx 1"),
+                            },
+                            ContextInfo {
+                                ctx_id: *b"CTX2",
+                                log_level: matches!(status, 4 | 6 | 7).then(|| -1),
+                                trace_status: matches!(status, 5..=7).then(|| 0),
+                                // empty description
+                                description: (status == 7).then(|| ""),
+                            },
+                        ],
+                        description: (status == 7).then(|| "app"),
+                    }],
+                    com_interface: Some(*b"remo"),
+                };
+                let data = serialize(&response, is_big_endian);
+                assert_eq!(
+                    Ok(response),
+                    GetLogInfoResponse::from_slice(&data, is_big_endian)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn zero_apps() {
+        for status in 3..=7 {
+            for is_big_endian in [false, true] {
+                let response = GetLogInfoResponse {
+                    status,
+                    app_ids: Vec::new(),
+                    com_interface: Some(*b"remo"),
+                };
+                let data = serialize(&response, is_big_endian);
+                assert_eq!(7, data.len());
+                assert_eq!(
+                    Ok(response),
+                    GetLogInfoResponse::from_slice(&data, is_big_endian)
+                );
+            }
+        }
+
+        // app without contexts
+        let response = GetLogInfoResponse {
+            status: 6,
+            app_ids: vec![AppIdInfo {
+                app_id: *b"APP1",
+                contexts: Vec::new(),
+                description: None,
+            }],
+            com_interface: Some(*b"remo"),
+        };
+        assert_eq!(
+            Ok(response.clone()),
+            GetLogInfoResponse::from_slice(&serialize(&response, true), true)
+        );
+    }
+
+    #[test]
+    fn without_log_info() {
+        for status in [0, 1, 2, 8, 9, 10, 0xff] {
+            assert_eq!(
+                Ok(GetLogInfoResponse {
+                    status,
+                    app_ids: Vec::new(),
+                    com_interface: None,
+                }),
+                GetLogInfoResponse::from_slice(&[status], true)
+            );
+        }
+    }
+
+    #[test]
+    fn errors() {
+        // missing status
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 1,
+                    actual_size: 0,
+                }
+            )),
+            GetLogInfoResponse::from_slice(&[], true)
+        );
+
+        // missing com interface
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 7,
+                    actual_size: 6,
+                }
+            )),
+            GetLogInfoResponse::from_slice(&[3, 0, 0, b'r', b'e', b'm'], true)
+        );
+
+        // huge counts with missing data
+        assert_matches!(
+            GetLogInfoResponse::from_slice(
+                &[3, 0xff, 0xff, b'A', b'P', b'P', b'1', 0xff, 0xff],
+                true
+            ),
+            Err(ControlDecodeError::UnexpectedEndOfSlice(_))
+        );
+
+        // invalid utf8 description
+        let data = [
+            7, 0, 1, b'A', b'P', b'P', b'1', 0, 0, 0, 2, 0xc3, 0x28, b'r', b'e', b'm', b'o',
+        ];
+        assert_matches!(
+            GetLogInfoResponse::from_slice(&data, true),
+            Err(ControlDecodeError::Utf8(_))
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use super::GetSoftwareVersionResponse;
+use crate::{DltControlMessageType, DltExtendedHeader, DltHeader, DltMessageType};
+use arrayvec::ArrayVec;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Builder for complete "get software version" control request messages
+/// (service id 0x13).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{ControlRequest, DltServiceId, GetSoftwareVersionRequestBuilder};
+/// use dlt_parse::DltPacketSlice;
+///
+/// let bytes = GetSoftwareVersionRequestBuilder::new(*b"CA1\0", *b"CC1\0")
+///     .with_ecu_id(*b"ECU1")
+///     .to_bytes();
+///
+/// let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+/// let request = ControlRequest::from_packet(&packet).unwrap();
+/// assert_eq!(DltServiceId::GetSoftwareVersion, request.service_id);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GetSoftwareVersionRequestBuilder {
+    is_big_endian: bool,
+    message_counter: u8,
+    ecu_id: Option<[u8; 4]>,
+    session_id: Option<u32>,
+    timestamp: Option<u32>,
+    application_id: [u8; 4],
+    context_id: [u8; 4],
+}
+
+impl GetSoftwareVersionRequestBuilder {
+    /// Maximum length of a serialized request.
+    pub const MAX_SERIALIZED_SIZE: usize = DltHeader::MAX_SERIALIZED_SIZE + 4;
+
+    /// Creates a builder for a little endian request without ecu id,
+    /// session id or timestamp.
+    pub fn new(application_id: [u8; 4], context_id: [u8; 4]) -> GetSoftwareVersionRequestBuilder {
+        GetSoftwareVersionRequestBuilder {
+            is_big_endian: false,
+            message_counter: 0,
+            ecu_id: None,
+            session_id: None,
+            timestamp: None,
+            application_id,
+            context_id,
+        }
+    }
+
+    /// Sets if the message is encoded in big endian.
+    pub fn with_big_endian(mut self, is_big_endian: bool) -> Self {
+        self.is_big_endian = is_big_endian;
+        self
+    }
+
+    /// Sets the message counter.
+    pub fn with_message_counter(mut self, message_counter: u8) -> Self {
+        self.message_counter = message_counter;
+        self
+    }
+
+    /// Sets the ecu id.
+    pub fn with_ecu_id(mut self, ecu_id: [u8; 4]) -> Self {
+        self.ecu_id = Some(ecu_id);
+        self
+    }
+
+    /// Sets the session id.
+    pub fn with_session_id(mut self, session_id: u32) -> Self {
+        self.session_id = Some(session_id);
+        self
+    }
+
+    /// Sets the timestamp (in 0.1 milliseconds).
+    pub fn with_timestamp(mut self, timestamp: u32) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Returns the header of the request (non verbose control request
+    /// with the length of the header & payload).
+    pub fn header(&self) -> DltHeader {
+        let mut header = DltHeader {
+            is_big_endian: self.is_big_endian,
+            message_counter: self.message_counter,
+            length: 0,
+            ecu_id: self.ecu_id,
+            session_id: self.session_id,
+            timestamp: self.timestamp,
+            // can not fail as control message types are always in range
+            extended_header: Some(
+                DltExtendedHeader::new_non_verbose(
+                    DltMessageType::Control(DltControlMessageType::Request),
+                    self.application_id,
+                    self.context_id,
+                )
+                .unwrap(),
+            ),
+        };
+        header.length = header.header_len() + 4;
+        header
+    }
+
+    /// Returns the payload of the request (the service id).
+    pub fn payload(&self) -> [u8; 4] {
+        if self.is_big_endian {
+            GetSoftwareVersionResponse::SERVICE_ID.to_be_bytes()
+        } else {
+            GetSoftwareVersionResponse::SERVICE_ID.to_le_bytes()
+        }
+    }
+
+    /// Returns the complete serialized request (header & payload).
+    pub fn to_bytes(
+        &self,
+    ) -> ArrayVec<u8, { GetSoftwareVersionRequestBuilder::MAX_SERIALIZED_SIZE }> {
+        let mut result = ArrayVec::new();
+        // can not fail as the capacity fits the maximum header size & payload
+        result
+            .try_extend_from_slice(&self.header().to_bytes())
+            .unwrap();
+        result.try_extend_from_slice(&self.payload()).unwrap();
+        result
+    }
+
+    /// Appends the complete request (header & payload) to the given vector.
+    #[cfg(feature = "std")]
+    pub fn build(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&self.to_bytes());
+    }
+}
+
+#[cfg(test)]
+mod get_software_version_request_builder_tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn reference_bytes() {
+        // request as sent by DLT clients (little endian with ecu id)
+        let expected_le = [
+            0x25, // header type: extended header, ecu id, version 1
+            0x00, // message counter
+            0x00, 0x16, // length
+            b'E', b'C', b'U', b'1', // ecu id
+            0x16, // message info: non verbose control request
+            0x00, // number of arguments
+            b'C', b'A', b'1', 0, // application id
+            b'C', b'C', b'1', 0, // context id
+            0x13, 0x00, 0x00, 0x00, // service id
+        ];
+        let builder =
+            GetSoftwareVersionRequestBuilder::new(*b"CA1\0", *b"CC1\0").with_ecu_id(*b"ECU1");
+        assert_eq!(&expected_le[..], &builder.to_bytes()[..]);
+
+        // big endian with all optional header fields
+        let expected_be = [
+            0x3f, // header type: extended header, msbf, ecu id, session id, timestamp, version 1
+            0x05, // message counter
+            0x00, 0x1e, // length
+            b'E', b'C', b'U', b'1', // ecu id
+            0x00, 0x00, 0x00, 0x02, // session id
+            0x00, 0x00, 0x00, 0x03, // timestamp
+            0x16, // message info: non verbose control request
+            0x00, // number of arguments
+            b'C', b'A', b'1', 0, // application id
+            b'C', b'C', b'1', 0, // context id
+            0x00, 0x00, 0x00, 0x13, // service id
+        ];
+        let builder = GetSoftwareVersionRequestBuilder::new(*b"CA1\0", *b"CC1\0")
+            .with_big_endian(true)
+            .with_message_counter(5)
+            .with_ecu_id(*b"ECU1")
+            .with_session_id(2)
+            .with_timestamp(3);
+        assert_eq!(&expected_be[..], &builder.to_bytes()[..]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn build_decode() {
+        use crate::control::{ControlRequest, DltServiceId};
+        use crate::DltPacketSlice;
+
+        for is_big_endian in [false, true] {
+            let builder = GetSoftwareVersionRequestBuilder::new(*b"APP1", *b"CTX1")
+                .with_big_endian(is_big_endian);
+            let mut buffer = Vec::new();
+            builder.build(&mut buffer);
+            assert_eq!(&builder.to_bytes()[..], &buffer[..]);
+
+            let packet = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(builder.header(), packet.header());
+            let request = ControlRequest::from_packet(&packet).unwrap();
+            assert_eq!(DltServiceId::GetSoftwareVersion, request.service_id);
+            assert!(request.params.is_empty());
+            assert_eq!(is_big_endian, request.is_big_endian);
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let builder = GetSoftwareVersionRequestBuilder::new(*b"APP1", *b"CTX1");
+        assert_eq!(builder, builder.clone());
+        assert!(format!("{:?}", builder).starts_with("GetSoftwareVersionRequestBuilder {"));
+    }
+}
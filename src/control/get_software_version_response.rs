@@ -0,0 +1,274 @@
+use crate::error::{ControlDecodeError, Layer, UnexpectedEndOfSliceError};
+use arrayvec::{ArrayVec, CapacityError};
+
+/// Response to a "get software version" control message (service id 0x13).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct GetSoftwareVersionResponse<'a> {
+    /// Status of the response (0 = ok, 1 = not supported, 2 = error).
+    pub status: u8,
+    /// Software version of the ECU.
+    pub sw_version: &'a str,
+}
+
+impl<'a> GetSoftwareVersionResponse<'a> {
+    /// Service id of the "get software version" control message.
+    pub const SERVICE_ID: u32 = 0x13;
+
+    /// Decodes the response from the payload of a control message
+    /// (payload after the service id).
+    ///
+    /// A zero termination at the end of the version string is not
+    /// part of the returned `sw_version`.
+    pub fn from_slice(
+        slice: &'a [u8],
+        is_big_endian: bool,
+    ) -> Result<GetSoftwareVersionResponse<'a>, ControlDecodeError> {
+        let (status, sw_version) = GetSoftwareVersionResponse::split(slice, is_big_endian)?;
+        Ok(GetSoftwareVersionResponse {
+            status,
+            sw_version: core::str::from_utf8(sw_version)?,
+        })
+    }
+
+    /// Decodes the response like [`GetSoftwareVersionResponse::from_slice`]
+    /// but falls back to decoding the version as latin-1 (ISO 8859-1) if
+    /// it is not valid UTF-8 (some ECUs send latin-1 encoded versions).
+    ///
+    /// Returns the status & the software version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::control::GetSoftwareVersionResponse;
+    ///
+    /// // version "v\xe9" ("vé" in latin-1)
+    /// let data = [0, 0, 0, 0, 2, b'v', 0xe9];
+    /// let (status, version) = GetSoftwareVersionResponse::from_slice_lenient(&data, true).unwrap();
+    /// assert_eq!(0, status);
+    /// assert_eq!("vé", version);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_slice_lenient(
+        slice: &'a [u8],
+        is_big_endian: bool,
+    ) -> Result<(u8, std::borrow::Cow<'a, str>), ControlDecodeError> {
+        use std::borrow::Cow;
+
+        let (status, sw_version) = GetSoftwareVersionResponse::split(slice, is_big_endian)?;
+        let sw_version = match core::str::from_utf8(sw_version) {
+            Ok(value) => Cow::Borrowed(value),
+            // latin-1 maps every byte to the unicode code point with the same value
+            Err(_) => Cow::Owned(sw_version.iter().map(|b| char::from(*b)).collect()),
+        };
+        Ok((status, sw_version))
+    }
+
+    /// Returns the status & the version bytes (without zero termination).
+    fn split(slice: &'a [u8], is_big_endian: bool) -> Result<(u8, &'a [u8]), ControlDecodeError> {
+        if slice.len() < 5 {
+            return Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 5,
+                    actual_size: slice.len(),
+                },
+            ));
+        }
+
+        let len_bytes = [slice[1], slice[2], slice[3], slice[4]];
+        let len = if is_big_endian {
+            u32::from_be_bytes(len_bytes)
+        } else {
+            u32::from_le_bytes(len_bytes)
+        } as usize;
+
+        let rest = &slice[5..];
+        if rest.len() < len {
+            return Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 5usize.saturating_add(len),
+                    actual_size: slice.len(),
+                },
+            ));
+        }
+
+        let sw_version = match &rest[..len] {
+            [start @ .., 0] => start,
+            value => value,
+        };
+        Ok((slice[0], sw_version))
+    }
+
+    /// Adds the response payload (without service id) to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        if buf.remaining_capacity() < 5 + self.sw_version.len() {
+            return Err(CapacityError::new(()));
+        }
+        let len = self.sw_version.len() as u32;
+        buf.try_extend_from_slice(&[self.status])?;
+        if is_big_endian {
+            buf.try_extend_from_slice(&len.to_be_bytes())?;
+        } else {
+            buf.try_extend_from_slice(&len.to_le_bytes())?;
+        }
+        buf.try_extend_from_slice(self.sw_version.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn write_read(
+            status in any::<u8>(),
+            ref sw_version in "\\PC{0,40}",
+            is_big_endian in any::<bool>(),
+        ) {
+            let value = GetSoftwareVersionResponse { status, sw_version };
+
+            let mut buf = ArrayVec::<u8, 200>::new();
+            value.add_to_msg(&mut buf, is_big_endian).unwrap();
+            prop_assert_eq!(5 + sw_version.len(), buf.len());
+            prop_assert_eq!(
+                Ok(value.clone()),
+                GetSoftwareVersionResponse::from_slice(&buf, is_big_endian)
+            );
+
+            // zero terminated version string
+            {
+                let mut terminated = Vec::with_capacity(buf.len() + 1);
+                let len = (sw_version.len() + 1) as u32;
+                terminated.push(status);
+                if is_big_endian {
+                    terminated.extend_from_slice(&len.to_be_bytes());
+                } else {
+                    terminated.extend_from_slice(&len.to_le_bytes());
+                }
+                terminated.extend_from_slice(sw_version.as_bytes());
+                terminated.push(0);
+                prop_assert_eq!(
+                    Ok(value.clone()),
+                    GetSoftwareVersionResponse::from_slice(&terminated, is_big_endian)
+                );
+            }
+
+            // length errors
+            for len in 0..buf.len() {
+                prop_assert_eq!(
+                    Err(ControlDecodeError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                        layer: Layer::ControlMessage,
+                        minimum_size: if len < 5 { 5 } else { buf.len() },
+                        actual_size: len,
+                    })),
+                    GetSoftwareVersionResponse::from_slice(&buf[..len], is_big_endian)
+                );
+            }
+
+            // capacity error
+            {
+                let mut small = ArrayVec::<u8, 4>::new();
+                prop_assert_eq!(
+                    Err(CapacityError::new(())),
+                    value.add_to_msg(&mut small, is_big_endian)
+                );
+                prop_assert!(small.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn length_exceeding_slice() {
+        let data = [0, 0xff, 0xff, 0xff, 0xff, b'a'];
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 5 + 0xffff_ffff,
+                    actual_size: 6,
+                }
+            )),
+            GetSoftwareVersionResponse::from_slice(&data, true)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn lenient() {
+        use std::borrow::Cow;
+
+        // valid utf8 is borrowed
+        let data = [2, 0, 0, 0, 4, b'v', b'1', b'.', b'0'];
+        assert_eq!(
+            Ok((2, Cow::Borrowed("v1.0"))),
+            GetSoftwareVersionResponse::from_slice_lenient(&data, true)
+        );
+
+        // latin-1 fallback (zero termination removed)
+        let data = [0, 4, 0, 0, 0, b'a', 0xe4, 0xff, 0];
+        assert_eq!(
+            Ok((0, Cow::Owned("aäÿ".into()))),
+            GetSoftwareVersionResponse::from_slice_lenient(&data, false)
+        );
+        assert_matches!(
+            GetSoftwareVersionResponse::from_slice(&data, false),
+            Err(ControlDecodeError::Utf8(_))
+        );
+
+        // length errors are still reported
+        let data = [0, 0, 0, 0, 5, 0xe4];
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 10,
+                    actual_size: 6,
+                }
+            )),
+            GetSoftwareVersionResponse::from_slice_lenient(&data, true)
+        );
+    }
+
+    #[test]
+    fn fixture() {
+        use crate::control::{ControlResponse, ControlResponseStatus, DltServiceId};
+        use crate::DltPacketSlice;
+
+        /// Response in the layout sent by the dlt-daemon (little endian,
+        /// zero terminated version).
+        const FIXTURE: &[u8] =
+            include_bytes!("../../tests/fixtures/get_software_version_response.dlt");
+
+        let packet = DltPacketSlice::from_slice(FIXTURE).unwrap();
+        let response = ControlResponse::from_packet(&packet).unwrap();
+        assert_eq!(DltServiceId::GetSoftwareVersion, response.service_id);
+        assert_eq!(ControlResponseStatus::Ok, response.status);
+        assert_eq!(
+            Ok(GetSoftwareVersionResponse {
+                status: 0,
+                sw_version: "GENIVI-DLT 2.18.8 STABLE",
+            }),
+            GetSoftwareVersionResponse::from_slice(&packet.payload()[4..], packet.is_big_endian())
+        );
+    }
+
+    #[test]
+    #[allow(invalid_from_utf8)]
+    fn utf8_error() {
+        let data = [0, 0, 0, 0, 4, 0, 159, 146, 150];
+        assert_eq!(
+            Err(ControlDecodeError::Utf8(
+                core::str::from_utf8(&[0, 159, 146, 150]).unwrap_err()
+            )),
+            GetSoftwareVersionResponse::from_slice(&data, true)
+        );
+    }
+}
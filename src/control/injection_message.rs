@@ -0,0 +1,210 @@
+use crate::error::{ControlDecodeError, Layer, UnexpectedEndOfSliceError};
+use arrayvec::{ArrayVec, CapacityError};
+
+/// Injection control message ("call SWC injection", service ids
+/// starting at 0xFFF) used to pass application specific data to
+/// a software component on the ECU.
+///
+/// The control payload of an injection message has the layout:
+///
+/// ```text
+/// | service id (u32) | data length (u32) | data (data length bytes) |
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use arrayvec::ArrayVec;
+/// use dlt_parse::control::InjectionMessage;
+///
+/// let msg = InjectionMessage {
+///     service_id: 0x1000,
+///     data: &[1, 2, 3],
+/// };
+///
+/// let mut payload = ArrayVec::<u8, 64>::new();
+/// msg.add_to_msg(&mut payload, true).unwrap();
+/// assert_eq!(
+///     &payload[..],
+///     &[0, 0, 0x10, 0, 0, 0, 0, 3, 1, 2, 3]
+/// );
+///
+/// assert_eq!(Ok(msg), InjectionMessage::from_slice(&payload, true));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct InjectionMessage<'a> {
+    /// Service id of the injection (at least [`InjectionMessage::MIN_SERVICE_ID`]).
+    pub service_id: u32,
+    /// Application specific data passed to the injection.
+    pub data: &'a [u8],
+}
+
+impl<'a> InjectionMessage<'a> {
+    /// Smallest service id used for injection messages.
+    pub const MIN_SERVICE_ID: u32 = 0xFFF;
+
+    /// Returns true if the given service id is the service id of an
+    /// injection message.
+    #[inline]
+    pub fn is_injection_service_id(service_id: u32) -> bool {
+        service_id >= InjectionMessage::MIN_SERVICE_ID
+    }
+
+    /// Decodes an injection message from the payload of a control
+    /// message (including the service id).
+    ///
+    /// Data after the injection data is ignored.
+    pub fn from_slice(
+        slice: &'a [u8],
+        is_big_endian: bool,
+    ) -> Result<InjectionMessage<'a>, ControlDecodeError> {
+        if slice.len() < 8 {
+            return Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 8,
+                    actual_size: slice.len(),
+                },
+            ));
+        }
+
+        let read_u32 = |bytes: [u8; 4]| {
+            if is_big_endian {
+                u32::from_be_bytes(bytes)
+            } else {
+                u32::from_le_bytes(bytes)
+            }
+        };
+
+        let service_id = read_u32([slice[0], slice[1], slice[2], slice[3]]);
+        if false == InjectionMessage::is_injection_service_id(service_id) {
+            return Err(ControlDecodeError::InvalidInjectionServiceId(service_id));
+        }
+
+        let len = read_u32([slice[4], slice[5], slice[6], slice[7]]) as usize;
+        let rest = &slice[8..];
+        if rest.len() < len {
+            return Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 8usize.saturating_add(len),
+                    actual_size: slice.len(),
+                },
+            ));
+        }
+
+        Ok(InjectionMessage {
+            service_id,
+            data: &rest[..len],
+        })
+    }
+
+    /// Adds the control payload (including the service id) to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        if buf.remaining_capacity() < 8 + self.data.len() {
+            return Err(CapacityError::new(()));
+        }
+        let len = self.data.len() as u32;
+        if is_big_endian {
+            buf.try_extend_from_slice(&self.service_id.to_be_bytes())?;
+            buf.try_extend_from_slice(&len.to_be_bytes())?;
+        } else {
+            buf.try_extend_from_slice(&self.service_id.to_le_bytes())?;
+            buf.try_extend_from_slice(&len.to_le_bytes())?;
+        }
+        buf.try_extend_from_slice(self.data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn write_read(
+            service_id in InjectionMessage::MIN_SERVICE_ID..=u32::MAX,
+            ref data in proptest::collection::vec(any::<u8>(), 0..40),
+            is_big_endian in any::<bool>(),
+        ) {
+            let value = InjectionMessage { service_id, data };
+
+            let mut buf = ArrayVec::<u8, 100>::new();
+            value.add_to_msg(&mut buf, is_big_endian).unwrap();
+            prop_assert_eq!(8 + data.len(), buf.len());
+            if is_big_endian {
+                prop_assert_eq!(&service_id.to_be_bytes(), &buf[..4]);
+                prop_assert_eq!(&(data.len() as u32).to_be_bytes(), &buf[4..8]);
+            } else {
+                prop_assert_eq!(&service_id.to_le_bytes(), &buf[..4]);
+                prop_assert_eq!(&(data.len() as u32).to_le_bytes(), &buf[4..8]);
+            }
+            prop_assert_eq!(&data[..], &buf[8..]);
+            prop_assert_eq!(
+                Ok(value.clone()),
+                InjectionMessage::from_slice(&buf, is_big_endian)
+            );
+
+            // trailing data is ignored
+            {
+                let mut with_trailing = buf.clone();
+                with_trailing.push(0xff);
+                prop_assert_eq!(
+                    Ok(value.clone()),
+                    InjectionMessage::from_slice(&with_trailing, is_big_endian)
+                );
+            }
+
+            // length errors
+            for len in 0..buf.len() {
+                prop_assert_eq!(
+                    Err(ControlDecodeError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                        layer: Layer::ControlMessage,
+                        minimum_size: if len < 8 { 8 } else { buf.len() },
+                        actual_size: len,
+                    })),
+                    InjectionMessage::from_slice(&buf[..len], is_big_endian)
+                );
+            }
+
+            // capacity error
+            {
+                let mut small = ArrayVec::<u8, 7>::new();
+                prop_assert_eq!(
+                    Err(CapacityError::new(())),
+                    value.add_to_msg(&mut small, is_big_endian)
+                );
+                prop_assert!(small.is_empty());
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn invalid_service_id(
+            service_id in 0..InjectionMessage::MIN_SERVICE_ID,
+            is_big_endian in any::<bool>(),
+        ) {
+            let value = InjectionMessage { service_id, data: &[] };
+            let mut buf = ArrayVec::<u8, 8>::new();
+            value.add_to_msg(&mut buf, is_big_endian).unwrap();
+            prop_assert_eq!(
+                Err(ControlDecodeError::InvalidInjectionServiceId(service_id)),
+                InjectionMessage::from_slice(&buf, is_big_endian)
+            );
+        }
+    }
+
+    #[test]
+    fn is_injection_service_id() {
+        assert!(false == InjectionMessage::is_injection_service_id(0));
+        assert!(false == InjectionMessage::is_injection_service_id(0xFFE));
+        assert!(InjectionMessage::is_injection_service_id(0xFFF));
+        assert!(InjectionMessage::is_injection_service_id(u32::MAX));
+    }
+}
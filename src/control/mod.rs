@@ -0,0 +1,99 @@
+mod bool_status_requests;
+pub use bool_status_requests::*;
+
+mod buffer_overflow_notification;
+pub use buffer_overflow_notification::*;
+
+mod context_params;
+
+mod control_log_level;
+pub use control_log_level::*;
+
+mod control_request;
+pub use control_request::*;
+
+#[cfg(feature = "std")]
+mod control_request_builder;
+#[cfg(feature = "std")]
+pub use control_request_builder::*;
+
+mod control_response;
+pub use control_response::*;
+
+mod control_response_status;
+pub use control_response_status::*;
+
+#[cfg(feature = "std")]
+mod control_service_registry;
+#[cfg(feature = "std")]
+pub use control_service_registry::*;
+
+#[cfg(feature = "std")]
+mod control_state_tracker;
+#[cfg(feature = "std")]
+pub use control_state_tracker::*;
+
+mod control_trace_status;
+pub use control_trace_status::*;
+
+mod dlt_service_id;
+pub use dlt_service_id::*;
+
+mod dlt_time_message;
+pub use dlt_time_message::*;
+
+mod get_default_log_level_request;
+pub use get_default_log_level_request::*;
+
+mod get_default_log_level_response;
+pub use get_default_log_level_response::*;
+
+mod get_log_info_request;
+pub use get_log_info_request::*;
+
+#[cfg(feature = "std")]
+mod get_log_info_response;
+#[cfg(feature = "std")]
+pub use get_log_info_response::*;
+
+mod get_software_version_request_builder;
+pub use get_software_version_request_builder::*;
+
+mod get_software_version_response;
+pub use get_software_version_response::*;
+
+mod injection_message;
+pub use injection_message::*;
+
+mod padded_id;
+pub use padded_id::*;
+
+mod parameterless_requests;
+pub use parameterless_requests::*;
+
+mod payload_reader;
+pub use payload_reader::*;
+
+mod set_default_log_level_request;
+pub use set_default_log_level_request::*;
+
+mod set_log_level_request;
+pub use set_log_level_request::*;
+
+mod set_trace_status_request;
+pub use set_trace_status_request::*;
+
+mod status_query_services;
+pub use status_query_services::*;
+
+mod sync_time_stamp_response;
+pub use sync_time_stamp_response::*;
+
+mod time_sync;
+pub use time_sync::*;
+
+mod typed_control_request;
+pub use typed_control_request::*;
+
+mod typed_control_response;
+pub use typed_control_response::*;
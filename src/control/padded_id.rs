@@ -0,0 +1,41 @@
+/// Converts a string into a 4 byte DLT id (e.g. application or context id)
+/// by padding it with zeros.
+///
+/// Returns `None` if the string is longer than 4 bytes.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::padded_id;
+///
+/// assert_eq!(Some(*b"APP\0"), padded_id("APP"));
+/// assert_eq!(Some(*b"CTX1"), padded_id("CTX1"));
+/// assert_eq!(None, padded_id("CTX12"));
+/// ```
+pub fn padded_id(id: &str) -> Option<[u8; 4]> {
+    let bytes = id.as_bytes();
+    if bytes.len() > 4 {
+        return None;
+    }
+    let mut result = [0u8; 4];
+    result[..bytes.len()].copy_from_slice(bytes);
+    Some(result)
+}
+
+#[cfg(test)]
+mod padded_id_tests {
+    use super::*;
+
+    #[test]
+    fn padding() {
+        assert_eq!(Some([0; 4]), padded_id(""));
+        assert_eq!(Some(*b"A\0\0\0"), padded_id("A"));
+        assert_eq!(Some(*b"AB\0\0"), padded_id("AB"));
+        assert_eq!(Some(*b"ABC\0"), padded_id("ABC"));
+        assert_eq!(Some(*b"ABCD"), padded_id("ABCD"));
+        assert_eq!(None, padded_id("ABCDE"));
+        // length is measured in bytes
+        assert_eq!(Some([0xc3, 0xa4, b'A', 0]), padded_id("äA"));
+        assert_eq!(None, padded_id("ääA"));
+    }
+}
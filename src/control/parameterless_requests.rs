@@ -0,0 +1,251 @@
+use super::context_params::{add_service_id, check_service_id, parse_response_status};
+use super::{ControlRequest, ControlResponse, ControlResponseStatus, DltServiceId};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Defines a control request without parameters & with a response only
+/// consisting of the status.
+macro_rules! parameterless_request {
+    ($(#[$doc:meta])* $name:ident, $service:ident = $id:literal, $desc:literal) => {
+        $(#[$doc])*
+        ///
+        /// The request has no parameters (the payload only consists of the
+        /// service id) & the response only contains the status.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// # #[cfg(feature = "std")]
+        /// # {
+        #[doc = concat!("use dlt_parse::control::{ControlRequest, ", stringify!($name), "};")]
+        /// use dlt_parse::DltPacketSlice;
+        ///
+        #[doc = concat!("let packet_bytes = ", stringify!($name), ".build(true);")]
+        ///
+        /// let packet = DltPacketSlice::from_slice(&packet_bytes).unwrap();
+        /// let control = ControlRequest::from_packet(&packet).unwrap();
+        #[doc = concat!("assert_eq!(Ok(", stringify!($name), "), ", stringify!($name), "::parse(&control));")]
+        /// # }
+        /// ```
+        #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+        pub struct $name;
+
+        impl $name {
+            #[doc = concat!("Service id of the \"", $desc, "\" control message.")]
+            pub const SERVICE_ID: u32 = $id;
+
+            #[doc = concat!("Checks that the given request is a \"", $desc, "\" request.")]
+            ///
+            /// Data after the service id is ignored.
+            pub fn parse(request: &ControlRequest<'_>) -> Result<$name, ControlDecodeError> {
+                check_service_id(DltServiceId::$service, request.service_id)?;
+                Ok($name)
+            }
+
+            #[doc = concat!("Returns the status of the response to a \"", $desc, "\" request.")]
+            #[inline]
+            pub fn parse_response(
+                response: &ControlResponse<'_>,
+            ) -> Result<ControlResponseStatus, ControlDecodeError> {
+                parse_response_status(DltServiceId::$service, response)
+            }
+
+            /// Adds the control payload (the service id) to the given buffer.
+            #[inline]
+            pub fn add_to_msg<const CAP: usize>(
+                &self,
+                buf: &mut ArrayVec<u8, CAP>,
+                is_big_endian: bool,
+            ) -> Result<(), CapacityError> {
+                add_service_id(DltServiceId::$service, buf, is_big_endian)
+            }
+
+            /// Returns the complete DLT packet of the request (non verbose
+            /// control request with the application & context id
+            /// [`ControlRequest::DEFAULT_APPLICATION_ID`] &
+            /// [`ControlRequest::DEFAULT_CONTEXT_ID`]).
+            #[cfg(feature = "std")]
+            #[inline]
+            pub fn build(&self, is_big_endian: bool) -> Vec<u8> {
+                ControlRequest::build_service_packet(DltServiceId::$service, is_big_endian, &[])
+            }
+        }
+    };
+}
+
+parameterless_request!(
+    /// "Store configuration" control request (service id 0x05) requesting
+    /// the ECU to persist the current log levels & trace states.
+    StoreConfigurationRequest,
+    StoreConfiguration = 0x05,
+    "store configuration"
+);
+
+parameterless_request!(
+    /// "Reset to factory default" control request (service id 0x06)
+    /// requesting the ECU to reset the stored configuration.
+    ResetToFactoryDefaultRequest,
+    ResetToFactoryDefault = 0x06,
+    "reset to factory default"
+);
+
+parameterless_request!(
+    /// "Get local time" control request (service id 0x0C) requesting the
+    /// ECU to send its local time (see [`super::DltTimeMessage`]).
+    GetLocalTimeRequest,
+    GetLocalTime = 0x0C,
+    "get local time"
+);
+
+parameterless_request!(
+    /// "Sync time stamp" control request (service id 0x24) requesting the
+    /// ECU to send its synchronized time (see
+    /// [`super::SyncTimeStampResponse`]).
+    SyncTimeStampRequest,
+    SyncTimeStamp = 0x24,
+    "sync time stamp"
+);
+
+#[cfg(test)]
+mod parameterless_requests_tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::{DltControlMessageType, DltMessageType, DltPacketSlice};
+    use alloc::format;
+
+    macro_rules! request_tests {
+        ($mod_name:ident, $name:ident, $service:ident, $id:literal, $other:ident) => {
+            mod $mod_name {
+                use super::*;
+
+                #[cfg(feature = "std")]
+                #[test]
+                fn build_parse() {
+                    assert_eq!($id, DltServiceId::$service.to_u32());
+                    assert_eq!($id, $name::SERVICE_ID);
+                    for is_big_endian in [false, true] {
+                        let bytes = $name.build(is_big_endian);
+                        let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+                        assert_eq!(is_big_endian, packet.is_big_endian());
+                        assert!(false == packet.is_verbose());
+                        assert_eq!(
+                            Some(DltMessageType::Control(DltControlMessageType::Request)),
+                            packet.message_type()
+                        );
+                        let ext = packet.extended_header().unwrap();
+                        assert_eq!(ControlRequest::DEFAULT_APPLICATION_ID, ext.application_id);
+                        assert_eq!(ControlRequest::DEFAULT_CONTEXT_ID, ext.context_id);
+                        if is_big_endian {
+                            assert_eq!(&[0, 0, 0, $id], packet.payload());
+                        } else {
+                            assert_eq!(&[$id, 0, 0, 0], packet.payload());
+                        }
+
+                        let control = ControlRequest::from_packet(&packet).unwrap();
+                        assert_eq!(DltServiceId::$service, control.service_id);
+                        assert_eq!(Ok($name), $name::parse(&control));
+
+                        let mut buf = ArrayVec::<u8, 4>::new();
+                        $name.add_to_msg(&mut buf, is_big_endian).unwrap();
+                        assert_eq!(packet.payload(), &buf[..]);
+                    }
+
+                    // capacity error
+                    let mut small = ArrayVec::<u8, 3>::new();
+                    assert_eq!(
+                        Err(CapacityError::new(())),
+                        $name.add_to_msg(&mut small, true)
+                    );
+                }
+
+                #[test]
+                fn parse_errors() {
+                    let request = ControlRequest {
+                        service_id: DltServiceId::$other,
+                        params: &[],
+                        is_big_endian: true,
+                        payload_offset: 0,
+                    };
+                    assert_eq!(
+                        Err(ControlDecodeError::UnexpectedServiceId {
+                            expected: DltServiceId::$service,
+                            actual: DltServiceId::$other,
+                        }),
+                        $name::parse(&request)
+                    );
+                }
+
+                #[test]
+                fn response() {
+                    for is_big_endian in [false, true] {
+                        for status in 0..=u8::MAX {
+                            let payload = if is_big_endian {
+                                [0, 0, 0, $id, status]
+                            } else {
+                                [$id, 0, 0, 0, status]
+                            };
+                            let response =
+                                ControlResponse::from_slice(&payload, is_big_endian).unwrap();
+                            let expected = match status {
+                                0 => ControlResponseStatus::Ok,
+                                1 => ControlResponseStatus::NotSupported,
+                                2 => ControlResponseStatus::Error,
+                                other => ControlResponseStatus::Other(other),
+                            };
+                            assert_eq!(Ok(expected), $name::parse_response(&response));
+                        }
+                    }
+                    let response = ControlResponse::from_slice(&[0, 0, 0, 0x01, 0], true).unwrap();
+                    assert_eq!(
+                        Err(ControlDecodeError::UnexpectedServiceId {
+                            expected: DltServiceId::$service,
+                            actual: DltServiceId::SetLogLevel,
+                        }),
+                        $name::parse_response(&response)
+                    );
+                }
+
+                #[test]
+                fn debug_clone_eq() {
+                    let request = $name;
+                    assert_eq!(request, request.clone());
+                    assert_eq!(stringify!($name), format!("{:?}", request));
+                }
+            }
+        };
+    }
+
+    request_tests!(
+        store_configuration,
+        StoreConfigurationRequest,
+        StoreConfiguration,
+        0x05,
+        ResetToFactoryDefault
+    );
+
+    request_tests!(
+        get_local_time,
+        GetLocalTimeRequest,
+        GetLocalTime,
+        0x0C,
+        StoreConfiguration
+    );
+
+    request_tests!(
+        sync_time_stamp,
+        SyncTimeStampRequest,
+        SyncTimeStamp,
+        0x24,
+        GetLocalTime
+    );
+
+    request_tests!(
+        reset_to_factory_default,
+        ResetToFactoryDefaultRequest,
+        ResetToFactoryDefault,
+        0x06,
+        StoreConfiguration
+    );
+}
@@ -0,0 +1,205 @@
+use crate::error::{ControlDecodeError, Layer, UnexpectedEndOfSliceError};
+
+/// Reader for the parameters of a control message payload.
+///
+/// Multi byte values are decoded with the endianness the reader
+/// was created with (the endianness of the DLT packet).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::PayloadReader;
+///
+/// let mut reader = PayloadReader::new(&[b'A', b'P', b'P', b'1', 0x12, 0x34], true);
+/// assert_eq!(Ok(*b"APP1"), reader.read_id());
+/// assert_eq!(Ok(0x1234), reader.read_u16());
+/// assert!(reader.rest().is_empty());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PayloadReader<'a> {
+    rest: &'a [u8],
+    offset: usize,
+    is_big_endian: bool,
+}
+
+impl<'a> PayloadReader<'a> {
+    /// Creates a reader for the given data.
+    #[inline]
+    pub fn new(data: &'a [u8], is_big_endian: bool) -> PayloadReader<'a> {
+        PayloadReader::with_offset(data, is_big_endian, 0)
+    }
+
+    /// Creates a reader for the given data where `offset` is the position of
+    /// `data` in the control payload (used in the errors).
+    #[inline]
+    pub fn with_offset(data: &'a [u8], is_big_endian: bool, offset: usize) -> PayloadReader<'a> {
+        PayloadReader {
+            rest: data,
+            offset,
+            is_big_endian,
+        }
+    }
+
+    /// Returns true if multi byte values are decoded as big endian.
+    #[inline]
+    pub fn is_big_endian(&self) -> bool {
+        self.is_big_endian
+    }
+
+    /// Number of bytes consumed (plus the offset the reader started with).
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Data not yet read.
+    #[inline]
+    pub fn rest(&self) -> &'a [u8] {
+        self.rest
+    }
+
+    /// Reads the given number of bytes.
+    pub fn read_raw(&mut self, len: usize) -> Result<&'a [u8], ControlDecodeError> {
+        if self.rest.len() < len {
+            return Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: self.offset.saturating_add(len),
+                    actual_size: self.offset + self.rest.len(),
+                },
+            ));
+        }
+        let (result, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        self.offset += len;
+        Ok(result)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ControlDecodeError> {
+        let mut result = [0u8; N];
+        result.copy_from_slice(self.read_raw(N)?);
+        Ok(result)
+    }
+
+    /// Reads an u8.
+    #[inline]
+    pub fn read_u8(&mut self) -> Result<u8, ControlDecodeError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    /// Reads an i8.
+    #[inline]
+    pub fn read_i8(&mut self) -> Result<i8, ControlDecodeError> {
+        Ok(i8::from_ne_bytes(self.read_array()?))
+    }
+
+    /// Reads an u16 in the endianness of the reader.
+    pub fn read_u16(&mut self) -> Result<u16, ControlDecodeError> {
+        let bytes = self.read_array()?;
+        Ok(if self.is_big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    }
+
+    /// Reads an u32 in the endianness of the reader.
+    pub fn read_u32(&mut self) -> Result<u32, ControlDecodeError> {
+        let bytes = self.read_array()?;
+        Ok(if self.is_big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    /// Reads an u64 in the endianness of the reader.
+    pub fn read_u64(&mut self) -> Result<u64, ControlDecodeError> {
+        let bytes = self.read_array()?;
+        Ok(if self.is_big_endian {
+            u64::from_be_bytes(bytes)
+        } else {
+            u64::from_le_bytes(bytes)
+        })
+    }
+
+    /// Reads a 4 byte id (e.g. application or context id).
+    #[inline]
+    pub fn read_id(&mut self) -> Result<[u8; 4], ControlDecodeError> {
+        self.read_array()
+    }
+}
+
+#[cfg(test)]
+mod payload_reader_tests {
+    use super::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn read(
+            is_big_endian in any::<bool>(),
+            offset in 0usize..100,
+            v8 in any::<u8>(),
+            v16 in any::<u16>(),
+            v32 in any::<u32>(),
+            v64 in any::<u64>(),
+            id in any::<[u8; 4]>(),
+        ) {
+            let mut data = alloc::vec::Vec::new();
+            data.push(v8);
+            data.push(v8);
+            if is_big_endian {
+                data.extend_from_slice(&v16.to_be_bytes());
+                data.extend_from_slice(&v32.to_be_bytes());
+                data.extend_from_slice(&v64.to_be_bytes());
+            } else {
+                data.extend_from_slice(&v16.to_le_bytes());
+                data.extend_from_slice(&v32.to_le_bytes());
+                data.extend_from_slice(&v64.to_le_bytes());
+            }
+            data.extend_from_slice(&id);
+            data.push(0xff);
+
+            let mut reader = PayloadReader::with_offset(&data, is_big_endian, offset);
+            prop_assert_eq!(is_big_endian, reader.is_big_endian());
+            prop_assert_eq!(offset, reader.offset());
+            prop_assert_eq!(Ok(v8), reader.read_u8());
+            prop_assert_eq!(Ok(v8 as i8), reader.read_i8());
+            prop_assert_eq!(Ok(v16), reader.read_u16());
+            prop_assert_eq!(Ok(v32), reader.read_u32());
+            prop_assert_eq!(Ok(v64), reader.read_u64());
+            prop_assert_eq!(Ok(id), reader.read_id());
+            prop_assert_eq!(offset + data.len() - 1, reader.offset());
+            prop_assert_eq!(&[0xff], reader.rest());
+
+            // not enough data
+            prop_assert_eq!(
+                Err(ControlDecodeError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: offset + data.len() + 1,
+                    actual_size: offset + data.len(),
+                })),
+                reader.read_raw(2)
+            );
+            // nothing consumed on error
+            prop_assert_eq!(Ok(&[0xffu8][..]), reader.read_raw(1));
+            prop_assert!(reader.read_u8().is_err());
+            prop_assert!(reader.read_u16().is_err());
+            prop_assert!(reader.read_u32().is_err());
+            prop_assert!(reader.read_u64().is_err());
+            prop_assert!(reader.read_id().is_err());
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let reader = PayloadReader::new(&[1], false);
+        assert_eq!(reader, reader.clone());
+        assert_eq!(
+            "PayloadReader { rest: [1], offset: 0, is_big_endian: false }",
+            format!("{:?}", reader)
+        );
+    }
+}
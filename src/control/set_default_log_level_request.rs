@@ -0,0 +1,220 @@
+use super::context_params::{add_service_id, check_service_id, parse_response_status};
+use super::{
+    ControlLogLevel, ControlRequest, ControlResponse, ControlResponseStatus, DltServiceId,
+};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// "Set default log level" control request (service id 0x11) changing the
+/// ECU wide default log level.
+///
+/// The parameters have the layout:
+///
+/// ```text
+/// | log level (i8) | com interface (4 bytes) |
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use dlt_parse::control::{ControlLogLevel, ControlRequest, SetDefaultLogLevelRequest};
+/// use dlt_parse::DltPacketSlice;
+///
+/// let request = SetDefaultLogLevelRequest {
+///     log_level: ControlLogLevel::Off,
+///     com_interface: SetDefaultLogLevelRequest::DEFAULT_COM_INTERFACE,
+/// };
+/// let packet_bytes = request.build(false);
+///
+/// let packet = DltPacketSlice::from_slice(&packet_bytes).unwrap();
+/// let control = ControlRequest::from_packet(&packet).unwrap();
+/// assert_eq!(Ok(request), SetDefaultLogLevelRequest::parse(&control));
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SetDefaultLogLevelRequest {
+    /// New default log level.
+    pub log_level: ControlLogLevel,
+    /// Communication interface (reserved, `"remo"` by default).
+    pub com_interface: [u8; 4],
+}
+
+impl SetDefaultLogLevelRequest {
+    /// Service id of the "set default log level" control message.
+    pub const SERVICE_ID: u32 = 0x11;
+
+    /// Communication interface value used by the dlt-daemon & dlt-viewer.
+    pub const DEFAULT_COM_INTERFACE: [u8; 4] = *b"remo";
+
+    /// Length of the parameters (without service id).
+    pub const PARAMS_LEN: usize = 5;
+
+    /// Decodes the parameters of a "set default log level" request.
+    ///
+    /// Data after the parameters is ignored.
+    pub fn parse(
+        request: &ControlRequest<'_>,
+    ) -> Result<SetDefaultLogLevelRequest, ControlDecodeError> {
+        check_service_id(DltServiceId::SetDefaultLogLevel, request.service_id)?;
+        let mut reader = request.params_reader();
+        let log_level = reader.read_i8()?;
+        let com_interface = reader.read_id()?;
+        Ok(SetDefaultLogLevelRequest {
+            log_level: ControlLogLevel::from_i8(log_level)?,
+            com_interface,
+        })
+    }
+
+    /// Returns the status of the response to a "set default log level" request.
+    #[inline]
+    pub fn parse_response(
+        response: &ControlResponse<'_>,
+    ) -> Result<ControlResponseStatus, ControlDecodeError> {
+        parse_response_status(DltServiceId::SetDefaultLogLevel, response)
+    }
+
+    /// Adds the control payload (including the service id) to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        if buf.remaining_capacity() < 4 + SetDefaultLogLevelRequest::PARAMS_LEN {
+            return Err(CapacityError::new(()));
+        }
+        add_service_id(DltServiceId::SetDefaultLogLevel, buf, is_big_endian)?;
+        buf.try_extend_from_slice(&self.log_level.to_i8().to_ne_bytes())?;
+        buf.try_extend_from_slice(&self.com_interface)
+    }
+
+    /// Returns the complete DLT packet of the request (non verbose
+    /// control request with the application & context id
+    /// [`ControlRequest::DEFAULT_APPLICATION_ID`] &
+    /// [`ControlRequest::DEFAULT_CONTEXT_ID`]).
+    #[cfg(feature = "std")]
+    pub fn build(&self, is_big_endian: bool) -> Vec<u8> {
+        let mut payload = ArrayVec::<u8, { 4 + SetDefaultLogLevelRequest::PARAMS_LEN }>::new();
+        // can not fail as the capacity matches the payload length
+        self.add_to_msg(&mut payload, is_big_endian).unwrap();
+        ControlRequest::build_packet(is_big_endian, &payload)
+    }
+}
+
+#[cfg(test)]
+mod set_default_log_level_request_tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::DltPacketSlice;
+    use alloc::format;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn build_parse() {
+        for is_big_endian in [false, true] {
+            for value in -1i8..=6 {
+                let request = SetDefaultLogLevelRequest {
+                    log_level: ControlLogLevel::from_i8(value).unwrap(),
+                    com_interface: *b"remo",
+                };
+                let bytes = request.build(is_big_endian);
+                let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+                let control = ControlRequest::from_packet(&packet).unwrap();
+                assert_eq!(DltServiceId::SetDefaultLogLevel, control.service_id);
+                assert_eq!(
+                    Ok(request.clone()),
+                    SetDefaultLogLevelRequest::parse(&control)
+                );
+
+                // missing parameter bytes
+                for len in 0..SetDefaultLogLevelRequest::PARAMS_LEN {
+                    let short = ControlRequest {
+                        params: &control.params[..len],
+                        ..control.clone()
+                    };
+                    assert!(matches!(
+                        SetDefaultLogLevelRequest::parse(&short),
+                        Err(ControlDecodeError::UnexpectedEndOfSlice(_))
+                    ));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let request = SetDefaultLogLevelRequest {
+            log_level: ControlLogLevel::Default,
+            com_interface: *b"remo",
+        };
+        let mut payload = ArrayVec::<u8, 16>::new();
+        request.add_to_msg(&mut payload, false).unwrap();
+        assert_eq!(&[0x11, 0, 0, 0, 0xff, b'r', b'e', b'm', b'o'], &payload[..]);
+
+        // capacity error
+        let mut small = ArrayVec::<u8, 8>::new();
+        assert_eq!(
+            Err(CapacityError::new(())),
+            request.add_to_msg(&mut small, true)
+        );
+        assert!(small.is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let request = ControlRequest {
+            service_id: DltServiceId::SetDefaultLogLevel,
+            params: &[7, b'r', b'e', b'm', b'o'],
+            is_big_endian: true,
+            payload_offset: 0,
+        };
+        assert_eq!(
+            Err(ControlDecodeError::InvalidLogLevel(7)),
+            SetDefaultLogLevelRequest::parse(&request)
+        );
+
+        let request = ControlRequest {
+            service_id: DltServiceId::SetLogLevel,
+            params: &[1, b'r', b'e', b'm', b'o'],
+            is_big_endian: true,
+            payload_offset: 0,
+        };
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::SetDefaultLogLevel,
+                actual: DltServiceId::SetLogLevel,
+            }),
+            SetDefaultLogLevelRequest::parse(&request)
+        );
+    }
+
+    #[test]
+    fn response() {
+        let response = ControlResponse::from_slice(&[0x11, 0, 0, 0, 1], false).unwrap();
+        assert_eq!(
+            Ok(ControlResponseStatus::NotSupported),
+            SetDefaultLogLevelRequest::parse_response(&response)
+        );
+        let response = ControlResponse::from_slice(&[0, 0, 0, 0x04, 0], true).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::SetDefaultLogLevel,
+                actual: DltServiceId::GetDefaultLogLevel,
+            }),
+            SetDefaultLogLevelRequest::parse_response(&response)
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let request = SetDefaultLogLevelRequest {
+            log_level: ControlLogLevel::Off,
+            com_interface: *b"remo",
+        };
+        assert_eq!(request, request.clone());
+        assert!(format!("{:?}", request).starts_with("SetDefaultLogLevelRequest {"));
+    }
+}
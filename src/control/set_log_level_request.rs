@@ -0,0 +1,293 @@
+use super::context_params::{parse_response_status, ContextParams};
+use super::{
+    ControlLogLevel, ControlRequest, ControlResponse, ControlResponseStatus, DltServiceId,
+};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// "Set log level" control request (service id 0x01) changing the log
+/// level of a context.
+///
+/// The parameters have the layout:
+///
+/// ```text
+/// | app id (4 bytes) | context id (4 bytes) | log level (i8) | com interface (4 bytes) |
+/// ```
+///
+/// Ids shorter than 4 characters are padded with zeros (see
+/// [`super::padded_id`]).
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use dlt_parse::control::{ControlLogLevel, ControlRequest, SetLogLevelRequest};
+/// use dlt_parse::{DltLogLevel, DltPacketSlice};
+///
+/// let request = SetLogLevelRequest {
+///     app_id: *b"LOG\0",
+///     ctx_id: *b"TEST",
+///     log_level: ControlLogLevel::Level(DltLogLevel::Debug),
+///     com_interface: SetLogLevelRequest::DEFAULT_COM_INTERFACE,
+/// };
+/// let packet_bytes = request.build(false);
+///
+/// let packet = DltPacketSlice::from_slice(&packet_bytes).unwrap();
+/// let control = ControlRequest::from_packet(&packet).unwrap();
+/// assert_eq!(Ok(request), SetLogLevelRequest::parse(&control));
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SetLogLevelRequest {
+    /// Application id of the context.
+    pub app_id: [u8; 4],
+    /// Context id of the context.
+    pub ctx_id: [u8; 4],
+    /// New log level of the context.
+    pub log_level: ControlLogLevel,
+    /// Communication interface (reserved, `"remo"` by default).
+    pub com_interface: [u8; 4],
+}
+
+impl SetLogLevelRequest {
+    /// Service id of the "set log level" control message.
+    pub const SERVICE_ID: u32 = 0x01;
+
+    /// Communication interface value used by the dlt-daemon & dlt-viewer.
+    pub const DEFAULT_COM_INTERFACE: [u8; 4] = *b"remo";
+
+    /// Length of the parameters (without service id).
+    pub const PARAMS_LEN: usize = ContextParams::LEN;
+
+    /// Decodes the parameters of a "set log level" request.
+    ///
+    /// Data after the parameters is ignored.
+    pub fn parse(request: &ControlRequest<'_>) -> Result<SetLogLevelRequest, ControlDecodeError> {
+        let params = ContextParams::parse(DltServiceId::SetLogLevel, request)?;
+        Ok(SetLogLevelRequest {
+            app_id: params.app_id,
+            ctx_id: params.ctx_id,
+            log_level: ControlLogLevel::from_i8(params.value)?,
+            com_interface: params.com_interface,
+        })
+    }
+
+    /// Returns the status of the response to a "set log level" request.
+    #[inline]
+    pub fn parse_response(
+        response: &ControlResponse<'_>,
+    ) -> Result<ControlResponseStatus, ControlDecodeError> {
+        parse_response_status(DltServiceId::SetLogLevel, response)
+    }
+
+    /// Adds the control payload (including the service id) to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        ContextParams {
+            app_id: self.app_id,
+            ctx_id: self.ctx_id,
+            value: self.log_level.to_i8(),
+            com_interface: self.com_interface,
+        }
+        .add_to_msg(DltServiceId::SetLogLevel, buf, is_big_endian)
+    }
+
+    /// Returns the complete DLT packet of the request (non verbose
+    /// control request with the application & context id
+    /// [`ControlRequest::DEFAULT_APPLICATION_ID`] &
+    /// [`ControlRequest::DEFAULT_CONTEXT_ID`]).
+    #[cfg(feature = "std")]
+    pub fn build(&self, is_big_endian: bool) -> Vec<u8> {
+        let mut payload = ArrayVec::<u8, { 4 + SetLogLevelRequest::PARAMS_LEN }>::new();
+        // can not fail as the capacity matches the payload length
+        self.add_to_msg(&mut payload, is_big_endian).unwrap();
+        ControlRequest::build_packet(is_big_endian, &payload)
+    }
+}
+
+#[cfg(test)]
+mod set_log_level_request_tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::{DltControlMessageType, DltMessageType};
+    use crate::{DltLogLevel, DltPacketSlice};
+    use alloc::format;
+    #[cfg(feature = "std")]
+    use proptest::prelude::*;
+
+    /// Request in the layout sent by the dlt-daemon client library
+    /// (little endian with ecu id & timestamp).
+    const FIXTURE: &[u8] = include_bytes!("../../tests/fixtures/set_log_level_request.dlt");
+
+    #[cfg(feature = "std")]
+    fn control_log_level_any() -> impl Strategy<Value = ControlLogLevel> {
+        (-1i8..=6).prop_map(|v| ControlLogLevel::from_i8(v).unwrap())
+    }
+
+    #[cfg(feature = "std")]
+    proptest! {
+        #[test]
+        fn build_parse(
+            app_id in any::<[u8; 4]>(),
+            ctx_id in any::<[u8; 4]>(),
+            log_level in control_log_level_any(),
+            com_interface in any::<[u8; 4]>(),
+            is_big_endian in any::<bool>(),
+        ) {
+            let request = SetLogLevelRequest {
+                app_id,
+                ctx_id,
+                log_level,
+                com_interface,
+            };
+            let bytes = request.build(is_big_endian);
+            let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+            prop_assert_eq!(is_big_endian, packet.is_big_endian());
+            prop_assert_eq!(
+                Some(DltMessageType::Control(DltControlMessageType::Request)),
+                packet.message_type()
+            );
+            prop_assert!(false == packet.is_verbose());
+            let ext = packet.extended_header().unwrap();
+            prop_assert_eq!(0, ext.number_of_arguments);
+            prop_assert_eq!(ControlRequest::DEFAULT_APPLICATION_ID, ext.application_id);
+            prop_assert_eq!(ControlRequest::DEFAULT_CONTEXT_ID, ext.context_id);
+
+            let control = ControlRequest::from_packet(&packet).unwrap();
+            prop_assert_eq!(Ok(request.clone()), SetLogLevelRequest::parse(&control));
+
+            // missing parameter bytes
+            for len in 0..SetLogLevelRequest::PARAMS_LEN {
+                let short = ControlRequest {
+                    params: &control.params[..len],
+                    ..control.clone()
+                };
+                prop_assert!(matches!(
+                    SetLogLevelRequest::parse(&short),
+                    Err(ControlDecodeError::UnexpectedEndOfSlice(_))
+                ));
+            }
+
+            // capacity error
+            let mut small = ArrayVec::<u8, 16>::new();
+            prop_assert_eq!(Err(CapacityError::new(())), request.add_to_msg(&mut small, is_big_endian));
+            prop_assert!(small.is_empty());
+        }
+    }
+
+    #[test]
+    fn fixture() {
+        let packet = DltPacketSlice::from_slice(FIXTURE).unwrap();
+        let control = ControlRequest::from_packet(&packet).unwrap();
+        let request = SetLogLevelRequest::parse(&control).unwrap();
+        assert_eq!(
+            SetLogLevelRequest {
+                app_id: *b"LOG\0",
+                ctx_id: *b"TEST",
+                log_level: ControlLogLevel::Level(DltLogLevel::Info),
+                com_interface: *b"remo",
+            },
+            request
+        );
+
+        // same payload encoding as the dlt-daemon
+        let mut payload = ArrayVec::<u8, 32>::new();
+        request.add_to_msg(&mut payload, false).unwrap();
+        assert_eq!(packet.payload(), &payload[..]);
+        #[cfg(feature = "std")]
+        {
+            let built = request.build(false);
+            assert_eq!(
+                packet.payload(),
+                DltPacketSlice::from_slice(&built).unwrap().payload()
+            );
+        }
+    }
+
+    #[test]
+    fn default_level_encoding() {
+        let request = SetLogLevelRequest {
+            app_id: *b"APP\0",
+            ctx_id: *b"CTX\0",
+            log_level: ControlLogLevel::Default,
+            com_interface: *b"remo",
+        };
+        let mut payload = ArrayVec::<u8, 32>::new();
+        request.add_to_msg(&mut payload, true).unwrap();
+        assert_eq!(
+            &[0, 0, 0, 1, b'A', b'P', b'P', 0, b'C', b'T', b'X', 0, 0xff, b'r', b'e', b'm', b'o'],
+            &payload[..]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        // wrong service id
+        let request = ControlRequest {
+            service_id: DltServiceId::SetTraceStatus,
+            params: &[0; 13],
+            is_big_endian: true,
+            payload_offset: 0,
+        };
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::SetLogLevel,
+                actual: DltServiceId::SetTraceStatus,
+            }),
+            SetLogLevelRequest::parse(&request)
+        );
+
+        // invalid log level
+        let mut params = [0u8; 13];
+        params[8] = 7;
+        let request = ControlRequest {
+            service_id: DltServiceId::SetLogLevel,
+            params: &params,
+            is_big_endian: true,
+            payload_offset: 0,
+        };
+        assert_eq!(
+            Err(ControlDecodeError::InvalidLogLevel(7)),
+            SetLogLevelRequest::parse(&request)
+        );
+    }
+
+    #[test]
+    fn response() {
+        for (status, expected) in [
+            (0, ControlResponseStatus::Ok),
+            (1, ControlResponseStatus::NotSupported),
+            (2, ControlResponseStatus::Error),
+        ] {
+            let payload = [0, 0, 0, 1, status];
+            let response = ControlResponse::from_slice(&payload, true).unwrap();
+            assert_eq!(Ok(expected), SetLogLevelRequest::parse_response(&response));
+        }
+        let response = ControlResponse::from_slice(&[0, 0, 0, 2, 0], true).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::SetLogLevel,
+                actual: DltServiceId::SetTraceStatus,
+            }),
+            SetLogLevelRequest::parse_response(&response)
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let request = SetLogLevelRequest {
+            app_id: *b"APP\0",
+            ctx_id: *b"CTX\0",
+            log_level: ControlLogLevel::Off,
+            com_interface: *b"remo",
+        };
+        assert_eq!(request, request.clone());
+        assert!(format!("{:?}", request).starts_with("SetLogLevelRequest {"));
+    }
+}
@@ -0,0 +1,282 @@
+use super::context_params::{parse_response_status, ContextParams};
+use super::{
+    ControlRequest, ControlResponse, ControlResponseStatus, ControlTraceStatus, DltServiceId,
+};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// "Set trace status" control request (service id 0x02) turning the
+/// tracing of a context on or off.
+///
+/// The parameters have the same layout as the ones of the
+/// [`super::SetLogLevelRequest`]:
+///
+/// ```text
+/// | app id (4 bytes) | context id (4 bytes) | trace status (i8) | com interface (4 bytes) |
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use dlt_parse::control::{ControlRequest, ControlTraceStatus, SetTraceStatusRequest};
+/// use dlt_parse::DltPacketSlice;
+///
+/// let request = SetTraceStatusRequest {
+///     app_id: *b"LOG\0",
+///     ctx_id: *b"TEST",
+///     trace_status: ControlTraceStatus::On,
+///     com_interface: SetTraceStatusRequest::DEFAULT_COM_INTERFACE,
+/// };
+/// let packet_bytes = request.build(true);
+///
+/// let packet = DltPacketSlice::from_slice(&packet_bytes).unwrap();
+/// let control = ControlRequest::from_packet(&packet).unwrap();
+/// assert_eq!(Ok(request), SetTraceStatusRequest::parse(&control));
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SetTraceStatusRequest {
+    /// Application id of the context.
+    pub app_id: [u8; 4],
+    /// Context id of the context.
+    pub ctx_id: [u8; 4],
+    /// New trace status of the context.
+    pub trace_status: ControlTraceStatus,
+    /// Communication interface (reserved, `"remo"` by default).
+    pub com_interface: [u8; 4],
+}
+
+impl SetTraceStatusRequest {
+    /// Service id of the "set trace status" control message.
+    pub const SERVICE_ID: u32 = 0x02;
+
+    /// Communication interface value used by the dlt-daemon & dlt-viewer.
+    pub const DEFAULT_COM_INTERFACE: [u8; 4] = *b"remo";
+
+    /// Length of the parameters (without service id).
+    pub const PARAMS_LEN: usize = ContextParams::LEN;
+
+    /// Decodes the parameters of a "set trace status" request.
+    ///
+    /// Data after the parameters is ignored.
+    pub fn parse(
+        request: &ControlRequest<'_>,
+    ) -> Result<SetTraceStatusRequest, ControlDecodeError> {
+        let params = ContextParams::parse(DltServiceId::SetTraceStatus, request)?;
+        Ok(SetTraceStatusRequest {
+            app_id: params.app_id,
+            ctx_id: params.ctx_id,
+            trace_status: ControlTraceStatus::from_i8(params.value)?,
+            com_interface: params.com_interface,
+        })
+    }
+
+    /// Returns the status of the response to a "set trace status" request.
+    #[inline]
+    pub fn parse_response(
+        response: &ControlResponse<'_>,
+    ) -> Result<ControlResponseStatus, ControlDecodeError> {
+        parse_response_status(DltServiceId::SetTraceStatus, response)
+    }
+
+    /// Adds the control payload (including the service id) to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        ContextParams {
+            app_id: self.app_id,
+            ctx_id: self.ctx_id,
+            value: self.trace_status.to_i8(),
+            com_interface: self.com_interface,
+        }
+        .add_to_msg(DltServiceId::SetTraceStatus, buf, is_big_endian)
+    }
+
+    /// Returns the complete DLT packet of the request (non verbose
+    /// control request with the application & context id
+    /// [`ControlRequest::DEFAULT_APPLICATION_ID`] &
+    /// [`ControlRequest::DEFAULT_CONTEXT_ID`]).
+    #[cfg(feature = "std")]
+    pub fn build(&self, is_big_endian: bool) -> Vec<u8> {
+        let mut payload = ArrayVec::<u8, { 4 + SetTraceStatusRequest::PARAMS_LEN }>::new();
+        // can not fail as the capacity matches the payload length
+        self.add_to_msg(&mut payload, is_big_endian).unwrap();
+        ControlRequest::build_packet(is_big_endian, &payload)
+    }
+}
+
+#[cfg(test)]
+mod set_trace_status_request_tests {
+    use super::*;
+    use crate::error::{Layer, UnexpectedEndOfSliceError};
+    #[cfg(feature = "std")]
+    use crate::{DltControlMessageType, DltMessageType, DltPacketSlice};
+    use alloc::format;
+    #[cfg(feature = "std")]
+    use proptest::prelude::*;
+
+    #[cfg(feature = "std")]
+    fn control_trace_status_any() -> impl Strategy<Value = ControlTraceStatus> {
+        (-1i8..=1).prop_map(|v| ControlTraceStatus::from_i8(v).unwrap())
+    }
+
+    #[cfg(feature = "std")]
+    proptest! {
+        #[test]
+        fn build_parse(
+            app_id in any::<[u8; 4]>(),
+            ctx_id in any::<[u8; 4]>(),
+            trace_status in control_trace_status_any(),
+            com_interface in any::<[u8; 4]>(),
+            is_big_endian in any::<bool>(),
+        ) {
+            let request = SetTraceStatusRequest {
+                app_id,
+                ctx_id,
+                trace_status,
+                com_interface,
+            };
+            let bytes = request.build(is_big_endian);
+            let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+            prop_assert_eq!(is_big_endian, packet.is_big_endian());
+            prop_assert_eq!(
+                Some(DltMessageType::Control(DltControlMessageType::Request)),
+                packet.message_type()
+            );
+
+            let control = ControlRequest::from_packet(&packet).unwrap();
+            prop_assert_eq!(DltServiceId::SetTraceStatus, control.service_id);
+            prop_assert_eq!(Ok(request.clone()), SetTraceStatusRequest::parse(&control));
+
+            // capacity error
+            let mut small = ArrayVec::<u8, 16>::new();
+            prop_assert_eq!(Err(CapacityError::new(())), request.add_to_msg(&mut small, is_big_endian));
+            prop_assert!(small.is_empty());
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let request = SetTraceStatusRequest {
+            app_id: *b"APP\0",
+            ctx_id: *b"CTX\0",
+            trace_status: ControlTraceStatus::Default,
+            com_interface: *b"remo",
+        };
+        let mut payload = ArrayVec::<u8, 32>::new();
+        request.add_to_msg(&mut payload, true).unwrap();
+        assert_eq!(
+            &[0, 0, 0, 2, b'A', b'P', b'P', 0, b'C', b'T', b'X', 0, 0xff, b'r', b'e', b'm', b'o'],
+            &payload[..]
+        );
+        payload.clear();
+        request.add_to_msg(&mut payload, false).unwrap();
+        assert_eq!(
+            &[2, 0, 0, 0, b'A', b'P', b'P', 0, b'C', b'T', b'X', 0, 0xff, b'r', b'e', b'm', b'o'],
+            &payload[..]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        // com interface (reserved field) missing
+        for is_big_endian in [false, true] {
+            let request = ControlRequest {
+                service_id: DltServiceId::SetTraceStatus,
+                params: b"APP1CTX1\x01",
+                is_big_endian,
+                payload_offset: 0,
+            };
+            assert_eq!(
+                Err(ControlDecodeError::UnexpectedEndOfSlice(
+                    UnexpectedEndOfSliceError {
+                        layer: Layer::ControlMessage,
+                        minimum_size: 17,
+                        actual_size: 13,
+                    }
+                )),
+                SetTraceStatusRequest::parse(&request)
+            );
+        }
+
+        // wrong service id
+        let request = ControlRequest {
+            service_id: DltServiceId::SetLogLevel,
+            params: &[0; 13],
+            is_big_endian: true,
+            payload_offset: 0,
+        };
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::SetTraceStatus,
+                actual: DltServiceId::SetLogLevel,
+            }),
+            SetTraceStatusRequest::parse(&request)
+        );
+
+        // invalid trace status
+        let mut params = [0u8; 13];
+        params[8] = 2;
+        let request = ControlRequest {
+            service_id: DltServiceId::SetTraceStatus,
+            params: &params,
+            is_big_endian: true,
+            payload_offset: 0,
+        };
+        assert_eq!(
+            Err(ControlDecodeError::InvalidTraceStatus(2)),
+            SetTraceStatusRequest::parse(&request)
+        );
+    }
+
+    #[test]
+    fn response() {
+        for is_big_endian in [false, true] {
+            let service_id = if is_big_endian {
+                2u32.to_be_bytes()
+            } else {
+                2u32.to_le_bytes()
+            };
+            for (status, expected) in [
+                (0, ControlResponseStatus::Ok),
+                (1, ControlResponseStatus::NotSupported),
+                (2, ControlResponseStatus::Error),
+            ] {
+                let mut payload = [0u8; 5];
+                payload[..4].copy_from_slice(&service_id);
+                payload[4] = status;
+                let response = ControlResponse::from_slice(&payload, is_big_endian).unwrap();
+                assert_eq!(
+                    Ok(expected),
+                    SetTraceStatusRequest::parse_response(&response)
+                );
+            }
+        }
+        let response = ControlResponse::from_slice(&[0, 0, 0, 1, 0], true).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::SetTraceStatus,
+                actual: DltServiceId::SetLogLevel,
+            }),
+            SetTraceStatusRequest::parse_response(&response)
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let request = SetTraceStatusRequest {
+            app_id: *b"APP\0",
+            ctx_id: *b"CTX\0",
+            trace_status: ControlTraceStatus::Off,
+            com_interface: *b"remo",
+        };
+        assert_eq!(request, request.clone());
+        assert!(format!("{:?}", request).starts_with("SetTraceStatusRequest {"));
+    }
+}
@@ -0,0 +1,548 @@
+use super::context_params::{
+    add_service_id, add_status_and_value, check_service_id, parse_status_and_value,
+};
+use super::{
+    ControlRequest, ControlResponse, ControlResponseStatus, ControlTraceStatus, DltServiceId,
+};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Defines a control request without parameters querying an on/off
+/// setting & the response consisting of the status & a boolean byte.
+macro_rules! bool_status_query {
+    (
+        $(#[$req_doc:meta])* $request:ident,
+        $(#[$resp_doc:meta])* $response:ident,
+        $service:ident = $id:literal,
+        $desc:literal
+    ) => {
+        $(#[$req_doc])*
+        ///
+        /// The request has no parameters (the payload only consists of the
+        #[doc = concat!("service id). The answer is decoded via [`", stringify!($response), "`].")]
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// # #[cfg(feature = "std")]
+        /// # {
+        #[doc = concat!("use dlt_parse::control::{ControlRequest, ", stringify!($request), "};")]
+        /// use dlt_parse::DltPacketSlice;
+        ///
+        #[doc = concat!("let packet_bytes = ", stringify!($request), ".build(true);")]
+        ///
+        /// let packet = DltPacketSlice::from_slice(&packet_bytes).unwrap();
+        /// let control = ControlRequest::from_packet(&packet).unwrap();
+        #[doc = concat!("assert_eq!(Ok(", stringify!($request), "), ", stringify!($request), "::parse(&control));")]
+        /// # }
+        /// ```
+        #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+        pub struct $request;
+
+        impl $request {
+            #[doc = concat!("Service id of the \"", $desc, "\" control message.")]
+            pub const SERVICE_ID: u32 = $id;
+
+            #[doc = concat!("Checks that the given request is a \"", $desc, "\" request.")]
+            ///
+            /// Data after the service id is ignored.
+            pub fn parse(request: &ControlRequest<'_>) -> Result<$request, ControlDecodeError> {
+                check_service_id(DltServiceId::$service, request.service_id)?;
+                Ok($request)
+            }
+
+            /// Adds the control payload (the service id) to the given buffer.
+            #[inline]
+            pub fn add_to_msg<const CAP: usize>(
+                &self,
+                buf: &mut ArrayVec<u8, CAP>,
+                is_big_endian: bool,
+            ) -> Result<(), CapacityError> {
+                add_service_id(DltServiceId::$service, buf, is_big_endian)
+            }
+
+            /// Returns the complete DLT packet of the request (non verbose
+            /// control request with the application & context id
+            /// [`ControlRequest::DEFAULT_APPLICATION_ID`] &
+            /// [`ControlRequest::DEFAULT_CONTEXT_ID`]).
+            #[cfg(feature = "std")]
+            #[inline]
+            pub fn build(&self, is_big_endian: bool) -> Vec<u8> {
+                ControlRequest::build_service_packet(DltServiceId::$service, is_big_endian, &[])
+            }
+        }
+
+        $(#[$resp_doc])*
+        ///
+        /// The data of the response consists of a single byte (0 = off,
+        /// 1 = on).
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+        pub struct $response {
+            /// Status of the response.
+            pub status: ControlResponseStatus,
+            /// True if the setting is turned on (`None` if the response only
+            /// contains a status, e.g. if the service is not supported).
+            pub enabled: Option<bool>,
+        }
+
+        impl $response {
+            #[doc = concat!("Service id of the \"", $desc, "\" control message.")]
+            pub const SERVICE_ID: u32 = $id;
+
+            /// Decodes the response.
+            ///
+            /// Responses with a status other than [`ControlResponseStatus::Ok`]
+            /// are allowed to end after the status (`enabled` is then `None`).
+            pub fn parse(response: &ControlResponse<'_>) -> Result<$response, ControlDecodeError> {
+                let (status, value) = parse_status_and_value(DltServiceId::$service, response)?;
+                let enabled = match value {
+                    None => None,
+                    Some(0) => Some(false),
+                    Some(1) => Some(true),
+                    Some(value) => return Err(ControlDecodeError::InvalidBool(value)),
+                };
+                Ok($response { status, enabled })
+            }
+
+            /// Adds the control payload (service id, status & value if
+            /// present) to the given buffer.
+            #[inline]
+            pub fn add_to_msg<const CAP: usize>(
+                &self,
+                buf: &mut ArrayVec<u8, CAP>,
+                is_big_endian: bool,
+            ) -> Result<(), CapacityError> {
+                add_status_and_value(
+                    DltServiceId::$service,
+                    self.status,
+                    self.enabled.map(u8::from),
+                    buf,
+                    is_big_endian,
+                )
+            }
+        }
+    };
+}
+
+bool_status_query!(
+    /// "Get verbose mode status" control request (service id 0x19).
+    GetVerboseModeStatusRequest,
+    /// Response to a "get verbose mode status" control message (service
+    /// id 0x19).
+    GetVerboseModeStatusResponse,
+    GetVerboseModeStatus = 0x19,
+    "get verbose mode status"
+);
+
+bool_status_query!(
+    /// "Get message filtering status" control request (service id 0x1A).
+    GetMessageFilteringStatusRequest,
+    /// Response to a "get message filtering status" control message
+    /// (service id 0x1A).
+    GetMessageFilteringStatusResponse,
+    GetMessageFilteringStatus = 0x1A,
+    "get message filtering status"
+);
+
+bool_status_query!(
+    /// "Get use ECU id" control request (service id 0x1B).
+    GetUseEcuIdRequest,
+    /// Response to a "get use ECU id" control message (service id 0x1B).
+    GetUseEcuIdResponse,
+    GetUseEcuId = 0x1B,
+    "get use ECU id"
+);
+
+bool_status_query!(
+    /// "Get use session id" control request (service id 0x1C).
+    GetUseSessionIdRequest,
+    /// Response to a "get use session id" control message (service id
+    /// 0x1C).
+    GetUseSessionIdResponse,
+    GetUseSessionId = 0x1C,
+    "get use session id"
+);
+
+bool_status_query!(
+    /// "Get use timestamp" control request (service id 0x1D).
+    GetUseTimestampRequest,
+    /// Response to a "get use timestamp" control message (service id
+    /// 0x1D).
+    GetUseTimestampResponse,
+    GetUseTimestamp = 0x1D,
+    "get use timestamp"
+);
+
+bool_status_query!(
+    /// "Get use extended header" control request (service id 0x1E).
+    GetUseExtendedHeaderRequest,
+    /// Response to a "get use extended header" control message (service
+    /// id 0x1E).
+    GetUseExtendedHeaderResponse,
+    GetUseExtendedHeader = 0x1E,
+    "get use extended header"
+);
+
+/// "Get trace status" control request (service id 0x1F) querying the trace
+/// status of a context.
+///
+/// The parameters have the layout:
+///
+/// ```text
+/// | app id (4 bytes) | context id (4 bytes) |
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use dlt_parse::control::{ControlRequest, GetTraceStatusRequest};
+/// use dlt_parse::DltPacketSlice;
+///
+/// let request = GetTraceStatusRequest {
+///     app_id: *b"APP1",
+///     ctx_id: *b"CTX1",
+/// };
+/// let packet_bytes = request.build(false);
+///
+/// let packet = DltPacketSlice::from_slice(&packet_bytes).unwrap();
+/// let control = ControlRequest::from_packet(&packet).unwrap();
+/// assert_eq!(Ok(request), GetTraceStatusRequest::parse(&control));
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GetTraceStatusRequest {
+    /// Application id of the context.
+    pub app_id: [u8; 4],
+    /// Context id of the context.
+    pub ctx_id: [u8; 4],
+}
+
+impl GetTraceStatusRequest {
+    /// Service id of the "get trace status" control message.
+    pub const SERVICE_ID: u32 = 0x1F;
+
+    /// Length of the parameters (without service id).
+    pub const PARAMS_LEN: usize = 8;
+
+    /// Decodes the parameters of a "get trace status" request.
+    ///
+    /// Data after the parameters is ignored.
+    pub fn parse(
+        request: &ControlRequest<'_>,
+    ) -> Result<GetTraceStatusRequest, ControlDecodeError> {
+        check_service_id(DltServiceId::GetTraceStatus, request.service_id)?;
+        let mut reader = request.params_reader();
+        Ok(GetTraceStatusRequest {
+            app_id: reader.read_id()?,
+            ctx_id: reader.read_id()?,
+        })
+    }
+
+    /// Adds the control payload (including the service id) to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        if buf.remaining_capacity() < 4 + GetTraceStatusRequest::PARAMS_LEN {
+            return Err(CapacityError::new(()));
+        }
+        add_service_id(DltServiceId::GetTraceStatus, buf, is_big_endian)?;
+        buf.try_extend_from_slice(&self.app_id)?;
+        buf.try_extend_from_slice(&self.ctx_id)
+    }
+
+    /// Returns the complete DLT packet of the request (non verbose
+    /// control request with the application & context id
+    /// [`ControlRequest::DEFAULT_APPLICATION_ID`] &
+    /// [`ControlRequest::DEFAULT_CONTEXT_ID`]).
+    #[cfg(feature = "std")]
+    pub fn build(&self, is_big_endian: bool) -> Vec<u8> {
+        let mut params = [0u8; GetTraceStatusRequest::PARAMS_LEN];
+        params[..4].copy_from_slice(&self.app_id);
+        params[4..].copy_from_slice(&self.ctx_id);
+        ControlRequest::build_service_packet(DltServiceId::GetTraceStatus, is_big_endian, &params)
+    }
+}
+
+/// Response to a "get trace status" control message (service id 0x1F).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{
+///     ControlResponse, ControlResponseStatus, ControlTraceStatus, GetTraceStatusResponse,
+/// };
+///
+/// // service id, status & trace status (little endian)
+/// let payload = [0x1F, 0, 0, 0, 0, 1];
+/// let response = ControlResponse::from_slice(&payload, false).unwrap();
+/// assert_eq!(
+///     Ok(GetTraceStatusResponse {
+///         status: ControlResponseStatus::Ok,
+///         trace_status: Some(ControlTraceStatus::On),
+///     }),
+///     GetTraceStatusResponse::parse(&response)
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GetTraceStatusResponse {
+    /// Status of the response.
+    pub status: ControlResponseStatus,
+    /// Trace status of the context (`None` if the response only contains
+    /// a status, e.g. if the service is not supported).
+    pub trace_status: Option<ControlTraceStatus>,
+}
+
+impl GetTraceStatusResponse {
+    /// Service id of the "get trace status" control message.
+    pub const SERVICE_ID: u32 = 0x1F;
+
+    /// Decodes the response.
+    ///
+    /// Responses with a status other than [`ControlResponseStatus::Ok`] are
+    /// allowed to end after the status (`trace_status` is then `None`).
+    pub fn parse(
+        response: &ControlResponse<'_>,
+    ) -> Result<GetTraceStatusResponse, ControlDecodeError> {
+        let (status, value) = parse_status_and_value(DltServiceId::GetTraceStatus, response)?;
+        Ok(GetTraceStatusResponse {
+            status,
+            trace_status: value
+                .map(|v| ControlTraceStatus::from_i8(v as i8))
+                .transpose()?,
+        })
+    }
+
+    /// Adds the control payload (service id, status & trace status if
+    /// present) to the given buffer.
+    #[inline]
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        add_status_and_value(
+            DltServiceId::GetTraceStatus,
+            self.status,
+            self.trace_status.map(|v| v.to_i8() as u8),
+            buf,
+            is_big_endian,
+        )
+    }
+}
+
+#[cfg(test)]
+mod status_query_services_tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::{DltControlMessageType, DltMessageType, DltPacketSlice};
+    #[cfg(feature = "std")]
+    use alloc::format;
+
+    macro_rules! bool_query_tests {
+        ($mod_name:ident, $request:ident, $response:ident, $service:ident, $id:literal, $other:ident) => {
+            mod $mod_name {
+                use super::*;
+
+                #[cfg(feature = "std")]
+                #[test]
+                fn request() {
+                    assert_eq!($id, DltServiceId::$service.to_u32());
+                    assert_eq!($id, $request::SERVICE_ID);
+                    assert_eq!($id, $response::SERVICE_ID);
+                    for is_big_endian in [false, true] {
+                        let bytes = $request.build(is_big_endian);
+                        let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+                        assert_eq!(
+                            Some(DltMessageType::Control(DltControlMessageType::Request)),
+                            packet.message_type()
+                        );
+                        let control = ControlRequest::from_packet(&packet).unwrap();
+                        assert_eq!(DltServiceId::$service, control.service_id);
+                        assert_eq!(Ok($request), $request::parse(&control));
+
+                        let mut buf = ArrayVec::<u8, 4>::new();
+                        $request.add_to_msg(&mut buf, is_big_endian).unwrap();
+                        assert_eq!(packet.payload(), &buf[..]);
+                    }
+
+                    let other = ControlRequest {
+                        service_id: DltServiceId::$other,
+                        params: &[],
+                        is_big_endian: true,
+                        payload_offset: 0,
+                    };
+                    assert_eq!(
+                        Err(ControlDecodeError::UnexpectedServiceId {
+                            expected: DltServiceId::$service,
+                            actual: DltServiceId::$other,
+                        }),
+                        $request::parse(&other)
+                    );
+                    assert_eq!(stringify!($request), format!("{:?}", $request));
+                }
+
+                #[test]
+                fn response() {
+                    for is_big_endian in [false, true] {
+                        for (status, enabled) in [
+                            (ControlResponseStatus::Ok, Some(true)),
+                            (ControlResponseStatus::Ok, Some(false)),
+                            (ControlResponseStatus::NotSupported, None),
+                        ] {
+                            let expected = $response { status, enabled };
+                            let mut buf = ArrayVec::<u8, 6>::new();
+                            expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+                            let response =
+                                ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+                            assert_eq!(Ok(expected), $response::parse(&response));
+                        }
+                    }
+
+                    // invalid boolean value
+                    let response =
+                        ControlResponse::from_slice(&[$id, 0, 0, 0, 0, 2], false).unwrap();
+                    assert_eq!(
+                        Err(ControlDecodeError::InvalidBool(2)),
+                        $response::parse(&response)
+                    );
+                }
+            }
+        };
+    }
+
+    bool_query_tests!(
+        get_verbose_mode_status,
+        GetVerboseModeStatusRequest,
+        GetVerboseModeStatusResponse,
+        GetVerboseModeStatus,
+        0x19,
+        GetUseEcuId
+    );
+
+    bool_query_tests!(
+        get_message_filtering_status,
+        GetMessageFilteringStatusRequest,
+        GetMessageFilteringStatusResponse,
+        GetMessageFilteringStatus,
+        0x1A,
+        GetVerboseModeStatus
+    );
+
+    bool_query_tests!(
+        get_use_ecu_id,
+        GetUseEcuIdRequest,
+        GetUseEcuIdResponse,
+        GetUseEcuId,
+        0x1B,
+        UseEcuId
+    );
+
+    bool_query_tests!(
+        get_use_session_id,
+        GetUseSessionIdRequest,
+        GetUseSessionIdResponse,
+        GetUseSessionId,
+        0x1C,
+        GetUseEcuId
+    );
+
+    bool_query_tests!(
+        get_use_timestamp,
+        GetUseTimestampRequest,
+        GetUseTimestampResponse,
+        GetUseTimestamp,
+        0x1D,
+        GetUseSessionId
+    );
+
+    bool_query_tests!(
+        get_use_extended_header,
+        GetUseExtendedHeaderRequest,
+        GetUseExtendedHeaderResponse,
+        GetUseExtendedHeader,
+        0x1E,
+        GetUseTimestamp
+    );
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn get_trace_status_request() {
+        let request = GetTraceStatusRequest {
+            app_id: *b"APP1",
+            ctx_id: *b"CTX1",
+        };
+        for is_big_endian in [false, true] {
+            let bytes = request.build(is_big_endian);
+            let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+            let control = ControlRequest::from_packet(&packet).unwrap();
+            assert_eq!(DltServiceId::GetTraceStatus, control.service_id);
+            assert_eq!(b"APP1CTX1", control.params);
+            assert_eq!(Ok(request.clone()), GetTraceStatusRequest::parse(&control));
+
+            // missing parameter bytes
+            for len in 0..GetTraceStatusRequest::PARAMS_LEN {
+                let short = ControlRequest {
+                    params: &control.params[..len],
+                    ..control.clone()
+                };
+                assert!(matches!(
+                    GetTraceStatusRequest::parse(&short),
+                    Err(ControlDecodeError::UnexpectedEndOfSlice(_))
+                ));
+            }
+        }
+
+        // capacity error
+        let mut small = ArrayVec::<u8, 11>::new();
+        assert_eq!(
+            Err(CapacityError::new(())),
+            request.add_to_msg(&mut small, true)
+        );
+        assert!(small.is_empty());
+        assert!(format!("{:?}", request).starts_with("GetTraceStatusRequest {"));
+    }
+
+    #[test]
+    fn get_trace_status_response() {
+        for is_big_endian in [false, true] {
+            for trace_status in [
+                Some(ControlTraceStatus::Default),
+                Some(ControlTraceStatus::Off),
+                Some(ControlTraceStatus::On),
+            ] {
+                let expected = GetTraceStatusResponse {
+                    status: ControlResponseStatus::Ok,
+                    trace_status,
+                };
+                let mut buf = ArrayVec::<u8, 6>::new();
+                expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+                let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+                assert_eq!(Ok(expected), GetTraceStatusResponse::parse(&response));
+            }
+
+            // status only
+            let expected = GetTraceStatusResponse {
+                status: ControlResponseStatus::Error,
+                trace_status: None,
+            };
+            let mut buf = ArrayVec::<u8, 6>::new();
+            expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+            assert_eq!(5, buf.len());
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+            assert_eq!(Ok(expected), GetTraceStatusResponse::parse(&response));
+        }
+
+        // invalid trace status
+        let response = ControlResponse::from_slice(&[0x1F, 0, 0, 0, 0, 2], false).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::InvalidTraceStatus(2)),
+            GetTraceStatusResponse::parse(&response)
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use super::context_params::{add_service_id, check_service_id};
+use super::{ControlResponse, ControlResponseStatus, DltServiceId};
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+use core::time::Duration;
+
+/// Synchronized time of an ECU as sent in the "sync time stamp" response
+/// (AUTOSAR `StbM_TimeStampType`).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SyncTimeStamp {
+    /// Status of the time base (sync quality, see the `TIME_BASE_*` flags).
+    pub time_base_status: u8,
+    /// Nanoseconds part of the time.
+    pub nanoseconds: u32,
+    /// Lower 32 bits of the seconds since the unix epoch.
+    pub seconds: u32,
+    /// Upper 16 bits of the 48 bit seconds since the unix epoch.
+    pub seconds_hi: u16,
+}
+
+impl SyncTimeStamp {
+    /// Serialized length in bytes (time base status, 3 reserved bytes,
+    /// nanoseconds, seconds & upper seconds).
+    pub const LEN: usize = 14;
+
+    /// Time base status flag set if the time base was not synchronized
+    /// within the expected time (`TIMEOUT`).
+    pub const TIME_BASE_TIMEOUT: u8 = 0b0000_0001;
+
+    /// Time base status flag set if the time base is synchronized via a
+    /// gateway (`SYNC_TO_GATEWAY`).
+    pub const TIME_BASE_SYNC_TO_GATEWAY: u8 = 0b0000_0100;
+
+    /// Time base status flag set once the time base was synchronized with
+    /// the global time master (`GLOBAL_TIME_BASE`).
+    pub const TIME_BASE_GLOBAL_TIME_BASE: u8 = 0b0000_1000;
+
+    /// Time base status flag set if the last synchronization caused a jump
+    /// into the future (`TIMELEAP_FUTURE`).
+    pub const TIME_BASE_TIMELEAP_FUTURE: u8 = 0b0001_0000;
+
+    /// Time base status flag set if the last synchronization caused a jump
+    /// into the past (`TIMELEAP_PAST`).
+    pub const TIME_BASE_TIMELEAP_PAST: u8 = 0b0010_0000;
+
+    /// Returns true if the time base is synchronized with the global time
+    /// master & the synchronization did not time out.
+    #[inline]
+    pub fn is_synchronized(&self) -> bool {
+        0 != self.time_base_status & SyncTimeStamp::TIME_BASE_GLOBAL_TIME_BASE
+            && 0 == self.time_base_status & SyncTimeStamp::TIME_BASE_TIMEOUT
+    }
+
+    /// Returns the 48 bit seconds since the unix epoch.
+    #[inline]
+    pub fn total_seconds(&self) -> u64 {
+        (u64::from(self.seconds_hi) << 32) | u64::from(self.seconds)
+    }
+
+    /// Returns the time since the unix epoch.
+    #[inline]
+    pub fn to_duration(&self) -> Duration {
+        Duration::from_secs(self.total_seconds())
+            + Duration::from_nanos(u64::from(self.nanoseconds))
+    }
+}
+
+/// Response to a "sync time stamp" control message (service id 0x24).
+///
+/// The data after the status is laid out as the time base status (1 byte),
+/// 3 reserved bytes, the nanoseconds (u32), the lower 32 bits of the
+/// seconds (u32) & the upper 16 bits of the seconds (u16). Multi byte
+/// values are encoded in the endianness of the packet. The reserved bytes
+/// are ignored when decoding & written as zeros.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{ControlResponse, SyncTimeStampResponse};
+///
+/// let payload = [
+///     0, 0, 0, 0x24, // service id
+///     0, // status
+///     0b1000, // time base status (GLOBAL_TIME_BASE)
+///     0, 0, 0, // reserved
+///     0, 0, 0, 5, // nanoseconds
+///     0x5f, 0x5e, 0x10, 0x00, // seconds
+///     0, 0, // seconds hi
+/// ];
+/// let response = ControlResponse::from_slice(&payload, true).unwrap();
+/// let time_stamp = SyncTimeStampResponse::parse(&response).unwrap().time_stamp.unwrap();
+/// assert!(time_stamp.is_synchronized());
+/// assert_eq!(1_600_000_000, time_stamp.total_seconds());
+/// assert_eq!(5, time_stamp.nanoseconds);
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SyncTimeStampResponse {
+    /// Status of the response.
+    pub status: ControlResponseStatus,
+    /// Synchronized time of the ECU (`None` if the response only contains
+    /// a status, e.g. if the service is not supported).
+    pub time_stamp: Option<SyncTimeStamp>,
+}
+
+impl SyncTimeStampResponse {
+    /// Service id of the "sync time stamp" control message.
+    pub const SERVICE_ID: u32 = 0x24;
+
+    /// Decodes the response.
+    ///
+    /// Responses with a status other than [`ControlResponseStatus::Ok`] are
+    /// allowed to end after the status (`time_stamp` is then `None`).
+    pub fn parse(
+        response: &ControlResponse<'_>,
+    ) -> Result<SyncTimeStampResponse, ControlDecodeError> {
+        check_service_id(DltServiceId::SyncTimeStamp, response.service_id)?;
+        let time_stamp = if response.data.is_empty() && response.status != ControlResponseStatus::Ok
+        {
+            None
+        } else {
+            let mut reader = response.data_reader();
+            let time_base_status = reader.read_u8()?;
+            // reserved
+            reader.read_raw(3)?;
+            Some(SyncTimeStamp {
+                time_base_status,
+                nanoseconds: reader.read_u32()?,
+                seconds: reader.read_u32()?,
+                seconds_hi: reader.read_u16()?,
+            })
+        };
+        Ok(SyncTimeStampResponse {
+            status: response.status,
+            time_stamp,
+        })
+    }
+
+    /// Adds the control payload (service id, status & time stamp if
+    /// present) to the given buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        let len = if self.time_stamp.is_some() {
+            5 + SyncTimeStamp::LEN
+        } else {
+            5
+        };
+        if buf.remaining_capacity() < len {
+            return Err(CapacityError::new(()));
+        }
+        add_service_id(DltServiceId::SyncTimeStamp, buf, is_big_endian)?;
+        buf.try_extend_from_slice(&[self.status.to_u8()])?;
+        if let Some(t) = &self.time_stamp {
+            buf.try_extend_from_slice(&[t.time_base_status, 0, 0, 0])?;
+            if is_big_endian {
+                buf.try_extend_from_slice(&t.nanoseconds.to_be_bytes())?;
+                buf.try_extend_from_slice(&t.seconds.to_be_bytes())?;
+                buf.try_extend_from_slice(&t.seconds_hi.to_be_bytes())?;
+            } else {
+                buf.try_extend_from_slice(&t.nanoseconds.to_le_bytes())?;
+                buf.try_extend_from_slice(&t.seconds.to_le_bytes())?;
+                buf.try_extend_from_slice(&t.seconds_hi.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod sync_time_stamp_response_tests {
+    use super::*;
+    use crate::error::{Layer, UnexpectedEndOfSliceError};
+    use alloc::format;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn add_to_msg_parse(
+            is_big_endian in any::<bool>(),
+            status in any::<u8>(),
+            time_base_status in any::<u8>(),
+            nanoseconds in any::<u32>(),
+            seconds in any::<u32>(),
+            seconds_hi in any::<u16>(),
+        ) {
+            let expected = SyncTimeStampResponse {
+                status: ControlResponseStatus::from_u8(status),
+                time_stamp: Some(SyncTimeStamp {
+                    time_base_status,
+                    nanoseconds,
+                    seconds,
+                    seconds_hi,
+                }),
+            };
+            let mut buf = ArrayVec::<u8, 19>::new();
+            expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+            prop_assert_eq!(&[0, 0, 0], &buf[6..9]);
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+            prop_assert_eq!(Ok(expected), SyncTimeStampResponse::parse(&response));
+
+            // reserved bytes are ignored
+            buf[6..9].copy_from_slice(&[1, 2, 3]);
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+            prop_assert_eq!(Ok(expected), SyncTimeStampResponse::parse(&response));
+
+            // capacity error
+            let mut small = ArrayVec::<u8, 18>::new();
+            prop_assert_eq!(
+                Err(CapacityError::new(())),
+                expected.add_to_msg(&mut small, is_big_endian)
+            );
+            prop_assert!(small.is_empty());
+        }
+    }
+
+    #[test]
+    fn layout() {
+        let response = SyncTimeStampResponse {
+            status: ControlResponseStatus::Ok,
+            time_stamp: Some(SyncTimeStamp {
+                time_base_status: 0x08,
+                nanoseconds: 0x0102_0304,
+                seconds: 0x0506_0708,
+                seconds_hi: 0x090a,
+            }),
+        };
+        let mut buf = ArrayVec::<u8, 19>::new();
+        response.add_to_msg(&mut buf, true).unwrap();
+        assert_eq!(
+            &[0, 0, 0, 0x24, 0, 0x08, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0xa],
+            &buf[..]
+        );
+        buf.clear();
+        response.add_to_msg(&mut buf, false).unwrap();
+        assert_eq!(
+            &[0x24, 0, 0, 0, 0, 0x08, 0, 0, 0, 4, 3, 2, 1, 8, 7, 6, 5, 0xa, 9],
+            &buf[..]
+        );
+    }
+
+    #[test]
+    fn time_stamp() {
+        let t = SyncTimeStamp {
+            time_base_status: SyncTimeStamp::TIME_BASE_GLOBAL_TIME_BASE,
+            nanoseconds: 250_000_000,
+            seconds: 0x0000_0002,
+            seconds_hi: 0x0001,
+        };
+        assert!(t.is_synchronized());
+        assert_eq!(0x1_0000_0002, t.total_seconds());
+        assert_eq!(
+            Duration::from_secs(0x1_0000_0002) + Duration::from_millis(250),
+            t.to_duration()
+        );
+
+        for time_base_status in [
+            0,
+            SyncTimeStamp::TIME_BASE_TIMEOUT,
+            SyncTimeStamp::TIME_BASE_TIMEOUT | SyncTimeStamp::TIME_BASE_GLOBAL_TIME_BASE,
+        ] {
+            let t = SyncTimeStamp {
+                time_base_status,
+                ..t
+            };
+            assert!(false == t.is_synchronized());
+        }
+    }
+
+    #[test]
+    fn parse_status_only_and_errors() {
+        // status only
+        let response = ControlResponse::from_slice(&[0, 0, 0, 0x24, 1], true).unwrap();
+        assert_eq!(
+            Ok(SyncTimeStampResponse {
+                status: ControlResponseStatus::NotSupported,
+                time_stamp: None,
+            }),
+            SyncTimeStampResponse::parse(&response)
+        );
+
+        // ok status without time stamp
+        let response = ControlResponse::from_slice(&[0, 0, 0, 0x24, 0], true).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 6,
+                    actual_size: 5,
+                }
+            )),
+            SyncTimeStampResponse::parse(&response)
+        );
+
+        // truncated seconds hi
+        let data = [0, 0, 0, 0x24, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let response = ControlResponse::from_slice(&data, true).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::ControlMessage,
+                    minimum_size: 19,
+                    actual_size: 18,
+                }
+            )),
+            SyncTimeStampResponse::parse(&response)
+        );
+
+        // wrong service id
+        let response = ControlResponse::from_slice(&[0, 0, 0, 0x23, 1], true).unwrap();
+        assert_eq!(
+            Err(ControlDecodeError::UnexpectedServiceId {
+                expected: DltServiceId::SyncTimeStamp,
+                actual: DltServiceId::BufferOverflowNotification,
+            }),
+            SyncTimeStampResponse::parse(&response)
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let response = SyncTimeStampResponse {
+            status: ControlResponseStatus::Ok,
+            time_stamp: None,
+        };
+        assert_eq!(response, response.clone());
+        assert_eq!(
+            "SyncTimeStampResponse { status: Ok, time_stamp: None }",
+            format!("{:?}", response)
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use super::SyncTimeStamp;
+use core::time::Duration;
+
+/// Maps the relative timestamps in the DLT headers of an ECU to absolute
+/// time based on a received [`SyncTimeStamp`].
+///
+/// The relative timestamp (time since the ECU startup in 0.1 milliseconds)
+/// of the packet carrying the "sync time stamp" response is used as
+/// reference point. The absolute time of other packets of the same ECU is
+/// then the synchronized time plus the difference of the relative
+/// timestamps. Wrap arounds of the 32 bit timestamps are not handled.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+/// use dlt_parse::control::{SyncTimeStamp, TimeSync};
+///
+/// // ECU reported 1_600_000_000.5 s when its relative timestamp was 10 s
+/// let sync = TimeSync::new(
+///     &SyncTimeStamp {
+///         time_base_status: SyncTimeStamp::TIME_BASE_GLOBAL_TIME_BASE,
+///         nanoseconds: 500_000_000,
+///         seconds: 1_600_000_000,
+///         seconds_hi: 0,
+///     },
+///     100_000,
+/// );
+///
+/// // packet logged at a relative time of 12.5 s
+/// assert_eq!(
+///     Some(Duration::from_secs(1_600_000_003)),
+///     sync.absolute_time(125_000)
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TimeSync {
+    /// Absolute time (since the unix epoch) at the reference timestamp.
+    pub reference_time: Duration,
+    /// Relative timestamp (0.1 milliseconds since the ECU startup) of the
+    /// packet that contained the synchronized time.
+    pub reference_timestamp: u32,
+}
+
+impl TimeSync {
+    /// Creates a time sync from a synchronized time & the relative
+    /// timestamp of the packet it was received in.
+    #[inline]
+    pub fn new(time_stamp: &SyncTimeStamp, timestamp: u32) -> TimeSync {
+        TimeSync {
+            reference_time: time_stamp.to_duration(),
+            reference_timestamp: timestamp,
+        }
+    }
+
+    /// Returns the absolute time of the given relative timestamp in
+    /// nanoseconds since the unix epoch (negative for times before the
+    /// epoch).
+    #[inline]
+    pub fn absolute_nanos(&self, timestamp: u32) -> i128 {
+        let offset = (i128::from(timestamp) - i128::from(self.reference_timestamp)) * 100_000;
+        self.reference_time.as_nanos() as i128 + offset
+    }
+
+    /// Returns the absolute time of the given relative timestamp since
+    /// the unix epoch (`None` if it would be before the epoch).
+    pub fn absolute_time(&self, timestamp: u32) -> Option<Duration> {
+        let nanos = self.absolute_nanos(timestamp);
+        (nanos >= 0).then(|| {
+            let nanos = nanos as u128;
+            Duration::new(
+                (nanos / 1_000_000_000) as u64,
+                (nanos % 1_000_000_000) as u32,
+            )
+        })
+    }
+
+    /// Returns the absolute time of the given relative timestamp as
+    /// system time (`None` if it is not representable).
+    #[cfg(feature = "std")]
+    pub fn system_time(&self, timestamp: u32) -> Option<std::time::SystemTime> {
+        std::time::UNIX_EPOCH.checked_add(self.absolute_time(timestamp)?)
+    }
+}
+
+#[cfg(test)]
+mod time_sync_tests {
+    use super::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    fn time_stamp(seconds: u32, nanoseconds: u32) -> SyncTimeStamp {
+        SyncTimeStamp {
+            time_base_status: SyncTimeStamp::TIME_BASE_GLOBAL_TIME_BASE,
+            nanoseconds,
+            seconds,
+            seconds_hi: 0,
+        }
+    }
+
+    #[test]
+    fn hand_calculated() {
+        // synchronized time 1_600_000_000.123456789 s at 1234.5678 s uptime
+        let sync = TimeSync::new(&time_stamp(1_600_000_000, 123_456_789), 12_345_678);
+        assert_eq!(
+            Duration::new(1_600_000_000, 123_456_789),
+            sync.reference_time
+        );
+        assert_eq!(12_345_678, sync.reference_timestamp);
+
+        // same timestamp
+        assert_eq!(1_600_000_000_123_456_789, sync.absolute_nanos(12_345_678));
+        // 0.1 ms later
+        assert_eq!(1_600_000_000_123_556_789, sync.absolute_nanos(12_345_679));
+        // 1234.5678 s earlier (ECU startup)
+        assert_eq!(1_599_998_765_555_656_789, sync.absolute_nanos(0));
+        assert_eq!(
+            Some(Duration::new(1_599_998_765, 555_656_789)),
+            sync.absolute_time(0)
+        );
+        // ~429496 s later (largest timestamp)
+        assert_eq!(
+            1_600_000_000_123_456_789 + (0xffff_ffffi128 - 12_345_678) * 100_000,
+            sync.absolute_nanos(u32::MAX)
+        );
+        assert_eq!(
+            Some(Duration::new(1_600_428_262, 285_156_789)),
+            sync.absolute_time(u32::MAX)
+        );
+
+        // before the unix epoch
+        let sync = TimeSync::new(&time_stamp(1, 0), 20_000);
+        assert_eq!(-1_000_000_000, sync.absolute_nanos(0));
+        assert_eq!(None, sync.absolute_time(0));
+        assert_eq!(Some(Duration::ZERO), sync.absolute_time(10_000));
+
+        // 48 bit seconds
+        let sync = TimeSync::new(
+            &SyncTimeStamp {
+                time_base_status: 0,
+                nanoseconds: 0,
+                seconds: 0,
+                seconds_hi: 1,
+            },
+            0,
+        );
+        assert_eq!(
+            Some(Duration::from_secs(1 << 32) + Duration::from_millis(1)),
+            sync.absolute_time(10)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn system_time() {
+        let sync = TimeSync::new(&time_stamp(1_600_000_000, 0), 10_000);
+        assert_eq!(
+            Some(std::time::UNIX_EPOCH + Duration::new(1_600_000_001, 500_000_000)),
+            sync.system_time(25_000)
+        );
+        let sync = TimeSync::new(&time_stamp(0, 0), 10_000);
+        assert_eq!(None, sync.system_time(0));
+    }
+
+    proptest! {
+        #[test]
+        fn absolute_time(
+            seconds in any::<u32>(),
+            nanoseconds in 0u32..1_000_000_000,
+            reference_timestamp in any::<u32>(),
+            timestamp in any::<u32>(),
+        ) {
+            let sync = TimeSync::new(&time_stamp(seconds, nanoseconds), reference_timestamp);
+            let expected = i128::from(seconds) * 1_000_000_000
+                + i128::from(nanoseconds)
+                + (i128::from(timestamp) - i128::from(reference_timestamp)) * 100_000;
+            prop_assert_eq!(expected, sync.absolute_nanos(timestamp));
+            match sync.absolute_time(timestamp) {
+                Some(d) => prop_assert_eq!(expected, d.as_nanos() as i128),
+                None => prop_assert!(expected < 0),
+            }
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let sync = TimeSync::new(&time_stamp(1, 2), 3);
+        assert_eq!(sync, sync.clone());
+        assert_eq!(
+            "TimeSync { reference_time: 1.000000002s, reference_timestamp: 3 }",
+            format!("{:?}", sync)
+        );
+    }
+}
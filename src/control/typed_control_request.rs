@@ -0,0 +1,445 @@
+use super::*;
+use crate::error::ControlDecodeError;
+use arrayvec::{ArrayVec, CapacityError};
+
+/// Control request decoded based on its service id.
+///
+/// Requests of standard services without a typed representation are
+/// returned as [`TypedControlRequest::Other`]. Requests of user defined
+/// (vendor specific) & unknown service ids are returned as
+/// [`TypedControlRequest::Unknown`] so they can be decoded by the caller
+/// (see also [`ControlServiceRegistry`]).
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use dlt_parse::control::{SetVerboseModeRequest, TypedControlRequest};
+/// use dlt_parse::{ControlNvPayload, DltPacketSlice, DltTypedPayload};
+///
+/// let packet_bytes = SetVerboseModeRequest { new_status: true }.build(false);
+/// let packet = DltPacketSlice::from_slice(&packet_bytes).unwrap();
+///
+/// if let Some(DltTypedPayload::ControlNv(payload)) = packet.typed_payload() {
+///     let request = payload.control_request(packet.is_big_endian()).unwrap();
+///     match TypedControlRequest::from_request(&request) {
+///         Ok(TypedControlRequest::SetVerboseMode(r)) => assert!(r.new_status),
+///         _ => panic!("unexpected request"),
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TypedControlRequest<'a> {
+    /// "Set log level" request (service id 0x01).
+    SetLogLevel(SetLogLevelRequest),
+    /// "Set trace status" request (service id 0x02).
+    SetTraceStatus(SetTraceStatusRequest),
+    /// "Get log info" request (service id 0x03).
+    GetLogInfo(GetLogInfoRequest),
+    /// "Get default log level" request (service id 0x04).
+    GetDefaultLogLevel(GetDefaultLogLevelRequest),
+    /// "Store configuration" request (service id 0x05).
+    StoreConfiguration(StoreConfigurationRequest),
+    /// "Reset to factory default" request (service id 0x06).
+    ResetToFactoryDefault(ResetToFactoryDefaultRequest),
+    /// "Set verbose mode" request (service id 0x09).
+    SetVerboseMode(SetVerboseModeRequest),
+    /// "Set message filtering" request (service id 0x0A).
+    SetMessageFiltering(SetMessageFilteringRequest),
+    /// "Set timing packets" request (service id 0x0B).
+    SetTimingPackets(SetTimingPacketsRequest),
+    /// "Get local time" request (service id 0x0C).
+    GetLocalTime(GetLocalTimeRequest),
+    /// "Set default log level" request (service id 0x11).
+    SetDefaultLogLevel(SetDefaultLogLevelRequest),
+    /// "Get verbose mode status" request (service id 0x19).
+    GetVerboseModeStatus(GetVerboseModeStatusRequest),
+    /// "Get message filtering status" request (service id 0x1A).
+    GetMessageFilteringStatus(GetMessageFilteringStatusRequest),
+    /// "Get use ECU id" request (service id 0x1B).
+    GetUseEcuId(GetUseEcuIdRequest),
+    /// "Get use session id" request (service id 0x1C).
+    GetUseSessionId(GetUseSessionIdRequest),
+    /// "Get use timestamp" request (service id 0x1D).
+    GetUseTimestamp(GetUseTimestampRequest),
+    /// "Get use extended header" request (service id 0x1E).
+    GetUseExtendedHeader(GetUseExtendedHeaderRequest),
+    /// "Get trace status" request (service id 0x1F).
+    GetTraceStatus(GetTraceStatusRequest),
+    /// "Sync time stamp" request (service id 0x24).
+    SyncTimeStamp(SyncTimeStampRequest),
+    /// Request of a standard service without a typed representation.
+    Other(ControlRequest<'a>),
+    /// Request of a user defined (vendor specific) or unknown service.
+    Unknown {
+        /// Raw service id of the request.
+        service_id: u32,
+        /// Raw parameters of the request (payload after the service id).
+        payload: &'a [u8],
+        /// True if the parameters are encoded in big endian.
+        is_big_endian: bool,
+    },
+}
+
+impl<'a> TypedControlRequest<'a> {
+    /// Decodes the parameters of the request based on its service id.
+    pub fn from_request(
+        request: &ControlRequest<'a>,
+    ) -> Result<TypedControlRequest<'a>, ControlDecodeError> {
+        use DltServiceId as S;
+        use TypedControlRequest::*;
+        Ok(match request.service_id {
+            S::SetLogLevel => SetLogLevel(SetLogLevelRequest::parse(request)?),
+            S::SetTraceStatus => SetTraceStatus(SetTraceStatusRequest::parse(request)?),
+            S::GetLogInfo => GetLogInfo(GetLogInfoRequest::parse(request)?),
+            S::GetDefaultLogLevel => GetDefaultLogLevel(GetDefaultLogLevelRequest::parse(request)?),
+            S::StoreConfiguration => StoreConfiguration(StoreConfigurationRequest::parse(request)?),
+            S::ResetToFactoryDefault => {
+                ResetToFactoryDefault(ResetToFactoryDefaultRequest::parse(request)?)
+            }
+            S::SetVerboseMode => SetVerboseMode(SetVerboseModeRequest::parse(request)?),
+            S::SetMessageFiltering => {
+                SetMessageFiltering(SetMessageFilteringRequest::parse(request)?)
+            }
+            S::SetTimingPackets => SetTimingPackets(SetTimingPacketsRequest::parse(request)?),
+            S::GetLocalTime => GetLocalTime(GetLocalTimeRequest::parse(request)?),
+            S::SetDefaultLogLevel => SetDefaultLogLevel(SetDefaultLogLevelRequest::parse(request)?),
+            S::GetVerboseModeStatus => {
+                GetVerboseModeStatus(GetVerboseModeStatusRequest::parse(request)?)
+            }
+            S::GetMessageFilteringStatus => {
+                GetMessageFilteringStatus(GetMessageFilteringStatusRequest::parse(request)?)
+            }
+            S::GetUseEcuId => GetUseEcuId(GetUseEcuIdRequest::parse(request)?),
+            S::GetUseSessionId => GetUseSessionId(GetUseSessionIdRequest::parse(request)?),
+            S::GetUseTimestamp => GetUseTimestamp(GetUseTimestampRequest::parse(request)?),
+            S::GetUseExtendedHeader => {
+                GetUseExtendedHeader(GetUseExtendedHeaderRequest::parse(request)?)
+            }
+            S::GetTraceStatus => GetTraceStatus(GetTraceStatusRequest::parse(request)?),
+            S::SyncTimeStamp => SyncTimeStamp(SyncTimeStampRequest::parse(request)?),
+            S::UserDefined(service_id) | S::Unknown(service_id) => Unknown {
+                service_id,
+                payload: request.params,
+                is_big_endian: request.is_big_endian,
+            },
+            _ => Other(request.clone()),
+        })
+    }
+
+    /// Returns the service id of the request.
+    pub fn service_id(&self) -> DltServiceId {
+        use TypedControlRequest::*;
+        match self {
+            SetLogLevel(_) => DltServiceId::SetLogLevel,
+            SetTraceStatus(_) => DltServiceId::SetTraceStatus,
+            GetLogInfo(_) => DltServiceId::GetLogInfo,
+            GetDefaultLogLevel(_) => DltServiceId::GetDefaultLogLevel,
+            StoreConfiguration(_) => DltServiceId::StoreConfiguration,
+            ResetToFactoryDefault(_) => DltServiceId::ResetToFactoryDefault,
+            SetVerboseMode(_) => DltServiceId::SetVerboseMode,
+            SetMessageFiltering(_) => DltServiceId::SetMessageFiltering,
+            SetTimingPackets(_) => DltServiceId::SetTimingPackets,
+            GetLocalTime(_) => DltServiceId::GetLocalTime,
+            SetDefaultLogLevel(_) => DltServiceId::SetDefaultLogLevel,
+            GetVerboseModeStatus(_) => DltServiceId::GetVerboseModeStatus,
+            GetMessageFilteringStatus(_) => DltServiceId::GetMessageFilteringStatus,
+            GetUseEcuId(_) => DltServiceId::GetUseEcuId,
+            GetUseSessionId(_) => DltServiceId::GetUseSessionId,
+            GetUseTimestamp(_) => DltServiceId::GetUseTimestamp,
+            GetUseExtendedHeader(_) => DltServiceId::GetUseExtendedHeader,
+            GetTraceStatus(_) => DltServiceId::GetTraceStatus,
+            SyncTimeStamp(_) => DltServiceId::SyncTimeStamp,
+            Other(request) => request.service_id,
+            Unknown { service_id, .. } => DltServiceId::from_u32(*service_id),
+        }
+    }
+
+    /// Adds the control payload (service id & parameters) to the given
+    /// buffer.
+    ///
+    /// The parameters of [`TypedControlRequest::Other`] &
+    /// [`TypedControlRequest::Unknown`] are copied unchanged (they are not
+    /// converted if their endianness differs from `is_big_endian`).
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), CapacityError> {
+        use TypedControlRequest::*;
+        match self {
+            SetLogLevel(r) => r.add_to_msg(buf, is_big_endian),
+            SetTraceStatus(r) => r.add_to_msg(buf, is_big_endian),
+            GetLogInfo(r) => r.add_to_msg(buf, is_big_endian),
+            GetDefaultLogLevel(r) => r.add_to_msg(buf, is_big_endian),
+            StoreConfiguration(r) => r.add_to_msg(buf, is_big_endian),
+            ResetToFactoryDefault(r) => r.add_to_msg(buf, is_big_endian),
+            SetVerboseMode(r) => r.add_to_msg(buf, is_big_endian),
+            SetMessageFiltering(r) => r.add_to_msg(buf, is_big_endian),
+            SetTimingPackets(r) => r.add_to_msg(buf, is_big_endian),
+            GetLocalTime(r) => r.add_to_msg(buf, is_big_endian),
+            SetDefaultLogLevel(r) => r.add_to_msg(buf, is_big_endian),
+            GetVerboseModeStatus(r) => r.add_to_msg(buf, is_big_endian),
+            GetMessageFilteringStatus(r) => r.add_to_msg(buf, is_big_endian),
+            GetUseEcuId(r) => r.add_to_msg(buf, is_big_endian),
+            GetUseSessionId(r) => r.add_to_msg(buf, is_big_endian),
+            GetUseTimestamp(r) => r.add_to_msg(buf, is_big_endian),
+            GetUseExtendedHeader(r) => r.add_to_msg(buf, is_big_endian),
+            GetTraceStatus(r) => r.add_to_msg(buf, is_big_endian),
+            SyncTimeStamp(r) => r.add_to_msg(buf, is_big_endian),
+            Other(r) => add_raw_payload(buf, r.service_id.to_u32(), r.params, is_big_endian),
+            Unknown {
+                service_id,
+                payload,
+                ..
+            } => add_raw_payload(buf, *service_id, payload, is_big_endian),
+        }
+    }
+}
+
+/// Adds the service id followed by the unchanged parameters to the buffer
+/// (nothing is added if they do not fit).
+fn add_raw_payload<const CAP: usize>(
+    buf: &mut ArrayVec<u8, CAP>,
+    service_id: u32,
+    params: &[u8],
+    is_big_endian: bool,
+) -> Result<(), CapacityError> {
+    if buf.remaining_capacity() < 4 + params.len() {
+        return Err(CapacityError::new(()));
+    }
+    if is_big_endian {
+        buf.try_extend_from_slice(&service_id.to_be_bytes())?;
+    } else {
+        buf.try_extend_from_slice(&service_id.to_le_bytes())?;
+    }
+    buf.try_extend_from_slice(params)
+}
+
+#[cfg(test)]
+mod typed_control_request_tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::{DltLogLevel, DltPacketSlice, DltTypedPayload};
+    use alloc::format;
+    #[cfg(feature = "std")]
+    use alloc::{vec, vec::Vec};
+
+    #[cfg(feature = "std")]
+    fn typed(bytes: &[u8]) -> TypedControlRequest<'_> {
+        let packet = DltPacketSlice::from_slice(bytes).unwrap();
+        let request = ControlRequest::from_packet(&packet).unwrap();
+        let result = TypedControlRequest::from_request(&request).unwrap();
+        assert_eq!(request.service_id, result.service_id());
+
+        // same result via the typed payload (offsets relative to the payload)
+        match packet.typed_payload() {
+            Some(DltTypedPayload::ControlNv(payload)) => {
+                let via_payload = payload.control_request(packet.is_big_endian()).unwrap();
+                assert_eq!(
+                    ControlRequest {
+                        payload_offset: 0,
+                        ..request.clone()
+                    },
+                    via_payload
+                );
+                assert_eq!(None, payload.control_response(packet.is_big_endian()));
+            }
+            other => panic!("unexpected payload {:?}", other),
+        }
+        result
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn dispatch() {
+        use TypedControlRequest as T;
+        for is_big_endian in [false, true] {
+            let set_log_level = SetLogLevelRequest {
+                app_id: *b"APP1",
+                ctx_id: *b"CTX1",
+                log_level: DltLogLevel::Warn.into(),
+                com_interface: *b"remo",
+            };
+            let set_trace_status = SetTraceStatusRequest {
+                app_id: *b"APP1",
+                ctx_id: *b"CTX1",
+                trace_status: ControlTraceStatus::On,
+                com_interface: *b"remo",
+            };
+            let get_log_info = GetLogInfoRequest::new(GetLogInfoRequest::OPTIONS_WITH_DESCRIPTIONS)
+                .unwrap()
+                .with_app_id("APP1")
+                .unwrap();
+            let set_default_log_level = SetDefaultLogLevelRequest {
+                log_level: ControlLogLevel::Off,
+                com_interface: *b"remo",
+            };
+            let get_trace_status = GetTraceStatusRequest {
+                app_id: *b"APP1",
+                ctx_id: *b"CTX1",
+            };
+            let cases: Vec<(Vec<u8>, TypedControlRequest<'static>)> = vec![
+                (
+                    set_log_level.build(is_big_endian),
+                    T::SetLogLevel(set_log_level.clone()),
+                ),
+                (
+                    set_trace_status.build(is_big_endian),
+                    T::SetTraceStatus(set_trace_status.clone()),
+                ),
+                (
+                    get_log_info.build(is_big_endian),
+                    T::GetLogInfo(get_log_info.clone()),
+                ),
+                (
+                    GetDefaultLogLevelRequest.build(is_big_endian),
+                    T::GetDefaultLogLevel(GetDefaultLogLevelRequest),
+                ),
+                (
+                    StoreConfigurationRequest.build(is_big_endian),
+                    T::StoreConfiguration(StoreConfigurationRequest),
+                ),
+                (
+                    ResetToFactoryDefaultRequest.build(is_big_endian),
+                    T::ResetToFactoryDefault(ResetToFactoryDefaultRequest),
+                ),
+                (
+                    SetVerboseModeRequest { new_status: true }.build(is_big_endian),
+                    T::SetVerboseMode(SetVerboseModeRequest { new_status: true }),
+                ),
+                (
+                    SetMessageFilteringRequest { new_status: false }.build(is_big_endian),
+                    T::SetMessageFiltering(SetMessageFilteringRequest { new_status: false }),
+                ),
+                (
+                    SetTimingPacketsRequest { new_status: false }.build(is_big_endian),
+                    T::SetTimingPackets(SetTimingPacketsRequest { new_status: false }),
+                ),
+                (
+                    GetLocalTimeRequest.build(is_big_endian),
+                    T::GetLocalTime(GetLocalTimeRequest),
+                ),
+                (
+                    set_default_log_level.build(is_big_endian),
+                    T::SetDefaultLogLevel(set_default_log_level.clone()),
+                ),
+                (
+                    GetVerboseModeStatusRequest.build(is_big_endian),
+                    T::GetVerboseModeStatus(GetVerboseModeStatusRequest),
+                ),
+                (
+                    GetUseTimestampRequest.build(is_big_endian),
+                    T::GetUseTimestamp(GetUseTimestampRequest),
+                ),
+                (
+                    get_trace_status.build(is_big_endian),
+                    T::GetTraceStatus(get_trace_status.clone()),
+                ),
+                (
+                    SyncTimeStampRequest.build(is_big_endian),
+                    T::SyncTimeStamp(SyncTimeStampRequest),
+                ),
+            ];
+            for (bytes, expected) in cases {
+                assert_eq!(expected, typed(&bytes));
+            }
+
+            // service without typed representation
+            let bytes =
+                ControlRequest::build_service_packet(DltServiceId::UseEcuId, is_big_endian, &[]);
+            assert_eq!(
+                T::Other(ControlRequest {
+                    service_id: DltServiceId::UseEcuId,
+                    params: &[],
+                    is_big_endian,
+                    payload_offset: bytes.len() - 4,
+                }),
+                typed(&bytes)
+            );
+
+            // user defined (vendor specific) & unknown services
+            for service_id in [0xF05, 0x30] {
+                let params = [1, 2, 3, 4, 5];
+                let bytes = ControlRequest::build_service_packet(
+                    DltServiceId::from_u32(service_id),
+                    is_big_endian,
+                    &params,
+                );
+                let result = typed(&bytes);
+                assert_eq!(
+                    T::Unknown {
+                        service_id,
+                        payload: &params,
+                        is_big_endian,
+                    },
+                    result
+                );
+                assert_eq!(DltServiceId::from_u32(service_id), result.service_id());
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let request = ControlRequest {
+            service_id: DltServiceId::SetVerboseMode,
+            params: &[2],
+            is_big_endian: true,
+            payload_offset: 0,
+        };
+        assert_eq!(
+            Err(ControlDecodeError::InvalidBool(2)),
+            TypedControlRequest::from_request(&request)
+        );
+    }
+
+    #[test]
+    fn add_to_msg() {
+        let requests = [
+            (
+                TypedControlRequest::SetVerboseMode(SetVerboseModeRequest { new_status: true }),
+                &[0, 0, 0, 0x09, 1][..],
+            ),
+            (
+                TypedControlRequest::Other(ControlRequest {
+                    service_id: DltServiceId::UseEcuId,
+                    params: &[1],
+                    is_big_endian: false,
+                    payload_offset: 0,
+                }),
+                &[0, 0, 0, 0x0D, 1][..],
+            ),
+            (
+                TypedControlRequest::Unknown {
+                    service_id: 0xF01,
+                    payload: &[1],
+                    is_big_endian: false,
+                },
+                &[0, 0, 0x0F, 0x01, 1][..],
+            ),
+        ];
+        for (request, expected) in requests {
+            let mut buf = ArrayVec::<u8, 5>::new();
+            request.add_to_msg(&mut buf, true).unwrap();
+            assert_eq!(expected, &buf[..]);
+
+            // not enough capacity
+            let mut buf = ArrayVec::<u8, 4>::new();
+            assert!(request.add_to_msg(&mut buf, true).is_err());
+            assert!(buf.is_empty());
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let request = TypedControlRequest::StoreConfiguration(StoreConfigurationRequest);
+        assert_eq!(request, request.clone());
+        assert_eq!(
+            "StoreConfiguration(StoreConfigurationRequest)",
+            format!("{:?}", request)
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use super::*;
+use crate::error::ControlDecodeError;
+
+/// Control response decoded based on its service id.
+///
+/// Responses of standard services without a typed representation are
+/// returned as [`TypedControlResponse::Other`]. Responses of user defined
+/// (vendor specific) & unknown service ids are returned as
+/// [`TypedControlResponse::Unknown`] so they can be decoded by the caller
+/// (see also [`ControlServiceRegistry`]).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::control::{ControlResponse, TypedControlResponse};
+///
+/// // "buffer overflow notification" with an overflow counter of 3 (big endian)
+/// let response = ControlResponse::from_slice(&[0, 0, 0, 0x23, 0, 0, 0, 0, 3], true).unwrap();
+/// match TypedControlResponse::from_response(&response) {
+///     Ok(TypedControlResponse::BufferOverflowNotification(n)) => {
+///         assert_eq!(3, n.overflow_counter)
+///     }
+///     _ => panic!("unexpected response"),
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TypedControlResponse<'a> {
+    /// "Get default log level" response (service id 0x04).
+    GetDefaultLogLevel(GetDefaultLogLevelResponse),
+    /// "Get verbose mode status" response (service id 0x19).
+    GetVerboseModeStatus(GetVerboseModeStatusResponse),
+    /// "Get message filtering status" response (service id 0x1A).
+    GetMessageFilteringStatus(GetMessageFilteringStatusResponse),
+    /// "Get use ECU id" response (service id 0x1B).
+    GetUseEcuId(GetUseEcuIdResponse),
+    /// "Get use session id" response (service id 0x1C).
+    GetUseSessionId(GetUseSessionIdResponse),
+    /// "Get use timestamp" response (service id 0x1D).
+    GetUseTimestamp(GetUseTimestampResponse),
+    /// "Get use extended header" response (service id 0x1E).
+    GetUseExtendedHeader(GetUseExtendedHeaderResponse),
+    /// "Get trace status" response (service id 0x1F).
+    GetTraceStatus(GetTraceStatusResponse),
+    /// "Buffer overflow notification" (service id 0x23).
+    BufferOverflowNotification(BufferOverflowNotification),
+    /// "Sync time stamp" response (service id 0x24).
+    SyncTimeStamp(SyncTimeStampResponse),
+    /// Response of a standard service without a typed representation.
+    Other(ControlResponse<'a>),
+    /// Response of a user defined (vendor specific) or unknown service.
+    Unknown {
+        /// Raw service id of the response.
+        service_id: u32,
+        /// Status of the response.
+        status: ControlResponseStatus,
+        /// Raw service specific data (payload after the status byte).
+        payload: &'a [u8],
+        /// True if the data is encoded in big endian.
+        is_big_endian: bool,
+    },
+}
+
+impl<'a> TypedControlResponse<'a> {
+    /// Decodes the data of the response based on its service id.
+    pub fn from_response(
+        response: &ControlResponse<'a>,
+    ) -> Result<TypedControlResponse<'a>, ControlDecodeError> {
+        use DltServiceId as S;
+        use TypedControlResponse::*;
+        Ok(match response.service_id {
+            S::GetDefaultLogLevel => {
+                GetDefaultLogLevel(GetDefaultLogLevelResponse::parse(response)?)
+            }
+            S::GetVerboseModeStatus => {
+                GetVerboseModeStatus(GetVerboseModeStatusResponse::parse(response)?)
+            }
+            S::GetMessageFilteringStatus => {
+                GetMessageFilteringStatus(GetMessageFilteringStatusResponse::parse(response)?)
+            }
+            S::GetUseEcuId => GetUseEcuId(GetUseEcuIdResponse::parse(response)?),
+            S::GetUseSessionId => GetUseSessionId(GetUseSessionIdResponse::parse(response)?),
+            S::GetUseTimestamp => GetUseTimestamp(GetUseTimestampResponse::parse(response)?),
+            S::GetUseExtendedHeader => {
+                GetUseExtendedHeader(GetUseExtendedHeaderResponse::parse(response)?)
+            }
+            S::GetTraceStatus => GetTraceStatus(GetTraceStatusResponse::parse(response)?),
+            S::BufferOverflowNotification => {
+                BufferOverflowNotification(super::BufferOverflowNotification::parse(response)?)
+            }
+            S::SyncTimeStamp => SyncTimeStamp(SyncTimeStampResponse::parse(response)?),
+            S::UserDefined(service_id) | S::Unknown(service_id) => Unknown {
+                service_id,
+                status: response.status,
+                payload: response.data,
+                is_big_endian: response.is_big_endian,
+            },
+            _ => Other(response.clone()),
+        })
+    }
+
+    /// Returns the service id of the response.
+    pub fn service_id(&self) -> DltServiceId {
+        use TypedControlResponse::*;
+        match self {
+            GetDefaultLogLevel(_) => DltServiceId::GetDefaultLogLevel,
+            GetVerboseModeStatus(_) => DltServiceId::GetVerboseModeStatus,
+            GetMessageFilteringStatus(_) => DltServiceId::GetMessageFilteringStatus,
+            GetUseEcuId(_) => DltServiceId::GetUseEcuId,
+            GetUseSessionId(_) => DltServiceId::GetUseSessionId,
+            GetUseTimestamp(_) => DltServiceId::GetUseTimestamp,
+            GetUseExtendedHeader(_) => DltServiceId::GetUseExtendedHeader,
+            GetTraceStatus(_) => DltServiceId::GetTraceStatus,
+            BufferOverflowNotification(_) => DltServiceId::BufferOverflowNotification,
+            SyncTimeStamp(_) => DltServiceId::SyncTimeStamp,
+            Other(response) => response.service_id,
+            Unknown { service_id, .. } => DltServiceId::from_u32(*service_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod typed_control_response_tests {
+    use super::*;
+    use crate::{DltPacketSlice, DltTypedPayload};
+    use alloc::format;
+    use arrayvec::ArrayVec;
+
+    #[test]
+    fn typed_payload() {
+        let bytes = include_bytes!("../../tests/fixtures/buffer_overflow_notification.dlt");
+        let packet = DltPacketSlice::from_slice(bytes).unwrap();
+        let response = match packet.typed_payload() {
+            Some(DltTypedPayload::ControlNv(payload)) => {
+                assert_eq!(None, payload.control_request(packet.is_big_endian()));
+                payload.control_response(packet.is_big_endian()).unwrap()
+            }
+            other => panic!("unexpected payload {:?}", other),
+        };
+        assert_eq!(
+            Ok(ControlResponse {
+                payload_offset: bytes.len() - packet.payload().len(),
+                ..response.clone()
+            }),
+            ControlResponse::from_packet(&packet)
+        );
+        assert_eq!(
+            Ok(TypedControlResponse::BufferOverflowNotification(
+                BufferOverflowNotification {
+                    status: ControlResponseStatus::Ok,
+                    overflow_counter: 42,
+                }
+            )),
+            TypedControlResponse::from_response(&response)
+        );
+    }
+
+    #[test]
+    fn dispatch() {
+        use TypedControlResponse as T;
+        for is_big_endian in [false, true] {
+            // get default log level
+            let expected = GetDefaultLogLevelResponse {
+                status: ControlResponseStatus::Ok,
+                log_level: Some(ControlLogLevel::Off),
+            };
+            let mut buf = ArrayVec::<u8, 6>::new();
+            expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+            let typed = TypedControlResponse::from_response(&response).unwrap();
+            assert_eq!(T::GetDefaultLogLevel(expected), typed);
+            assert_eq!(DltServiceId::GetDefaultLogLevel, typed.service_id());
+
+            // get use ECU id
+            let expected = GetUseEcuIdResponse {
+                status: ControlResponseStatus::Ok,
+                enabled: Some(true),
+            };
+            let mut buf = ArrayVec::<u8, 6>::new();
+            expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+            let typed = TypedControlResponse::from_response(&response).unwrap();
+            assert_eq!(T::GetUseEcuId(expected), typed);
+            assert_eq!(DltServiceId::GetUseEcuId, typed.service_id());
+
+            // get trace status
+            let expected = GetTraceStatusResponse {
+                status: ControlResponseStatus::NotSupported,
+                trace_status: None,
+            };
+            let mut buf = ArrayVec::<u8, 6>::new();
+            expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+            let typed = TypedControlResponse::from_response(&response).unwrap();
+            assert_eq!(T::GetTraceStatus(expected), typed);
+            assert_eq!(DltServiceId::GetTraceStatus, typed.service_id());
+
+            // buffer overflow notification
+            let expected = BufferOverflowNotification {
+                status: ControlResponseStatus::Ok,
+                overflow_counter: 1234,
+            };
+            let mut buf = ArrayVec::<u8, 9>::new();
+            expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+            let typed = TypedControlResponse::from_response(&response).unwrap();
+            assert_eq!(T::BufferOverflowNotification(expected), typed);
+            assert_eq!(DltServiceId::BufferOverflowNotification, typed.service_id());
+
+            // sync time stamp
+            let expected = SyncTimeStampResponse {
+                status: ControlResponseStatus::Ok,
+                time_stamp: Some(SyncTimeStamp {
+                    time_base_status: SyncTimeStamp::TIME_BASE_GLOBAL_TIME_BASE,
+                    nanoseconds: 1,
+                    seconds: 2,
+                    seconds_hi: 3,
+                }),
+            };
+            let mut buf = ArrayVec::<u8, 19>::new();
+            expected.add_to_msg(&mut buf, is_big_endian).unwrap();
+            let response = ControlResponse::from_slice(&buf, is_big_endian).unwrap();
+            let typed = TypedControlResponse::from_response(&response).unwrap();
+            assert_eq!(T::SyncTimeStamp(expected), typed);
+            assert_eq!(DltServiceId::SyncTimeStamp, typed.service_id());
+
+            // service without typed representation
+            let response = ControlResponse {
+                service_id: DltServiceId::UseEcuId,
+                status: ControlResponseStatus::Ok,
+                data: &[],
+                is_big_endian,
+                payload_offset: 0,
+            };
+            let typed = TypedControlResponse::from_response(&response).unwrap();
+            assert_eq!(T::Other(response.clone()), typed);
+            assert_eq!(DltServiceId::UseEcuId, typed.service_id());
+
+            // user defined (vendor specific) & unknown services
+            for service_id in [0xF05, 0x30] {
+                let response = ControlResponse {
+                    service_id: DltServiceId::from_u32(service_id),
+                    status: ControlResponseStatus::Other(8),
+                    data: &[1, 2, 3],
+                    is_big_endian,
+                    payload_offset: 0,
+                };
+                let typed = TypedControlResponse::from_response(&response).unwrap();
+                assert_eq!(
+                    T::Unknown {
+                        service_id,
+                        status: ControlResponseStatus::Other(8),
+                        payload: &[1, 2, 3],
+                        is_big_endian,
+                    },
+                    typed
+                );
+                assert_eq!(DltServiceId::from_u32(service_id), typed.service_id());
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let response = ControlResponse {
+            service_id: DltServiceId::BufferOverflowNotification,
+            status: ControlResponseStatus::Ok,
+            data: &[0, 0, 0],
+            is_big_endian: true,
+            payload_offset: 0,
+        };
+        assert!(TypedControlResponse::from_response(&response).is_err());
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let response =
+            TypedControlResponse::BufferOverflowNotification(BufferOverflowNotification {
+                status: ControlResponseStatus::Ok,
+                overflow_counter: 1,
+            });
+        assert_eq!(response, response.clone());
+        assert!(format!("{:?}", response).starts_with("BufferOverflowNotification("));
+    }
+}
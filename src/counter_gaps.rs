@@ -0,0 +1,168 @@
+use crate::{error::PacketSliceError, SliceIterator};
+use std::collections::HashMap;
+use std::vec::Vec;
+
+/// Gap in the message counter sequence of an ECU (see [`find_counter_gaps`]).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CounterGap {
+    /// Offset of the message after the gap from the start of the slice.
+    pub offset: usize,
+    /// ECU id of the message (`None` if the header contains no ECU id).
+    pub ecu_id: Option<[u8; 4]>,
+    /// Message counter expected based on the previous message of the ECU.
+    pub expected: u8,
+    /// Message counter of the message after the gap.
+    pub actual: u8,
+}
+
+impl CounterGap {
+    /// Number of messages missing between the previous message of the ECU
+    /// & the message after the gap (assuming less then 256 messages were
+    /// dropped).
+    #[inline]
+    pub fn missing(&self) -> u8 {
+        self.actual.wrapping_sub(self.expected)
+    }
+}
+
+/// Checks the message counters of the dlt messages in the given slice &
+/// returns the positions where the sequence of an ECU skips values (e.g.
+/// because messages were dropped).
+///
+/// Each ECU has its own 8 bit counter, so the counters are tracked per
+/// ECU id (messages without an ECU id are tracked together). The counter
+/// wraps from 255 to 0, which is not reported as a gap. The first message
+/// of each ECU only initializes the expected counter.
+///
+/// The slice is expected to only contain dlt messages (without storage
+/// headers). Decoding stops at the first message that can not be decoded
+/// and the error is returned.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{find_counter_gaps, CounterGap, DltHeader};
+///
+/// let packet = |counter: u8| {
+///     let mut header = DltHeader::new_non_verbose(*b"ECU1");
+///     header.message_counter = counter;
+///     header.to_bytes()
+/// };
+/// let capture = [packet(254), packet(255), packet(0), packet(3)].concat();
+///
+/// assert_eq!(
+///     vec![CounterGap {
+///         offset: 3 * 8,
+///         ecu_id: Some(*b"ECU1"),
+///         expected: 1,
+///         actual: 3,
+///     }],
+///     find_counter_gaps(&capture).unwrap()
+/// );
+/// ```
+pub fn find_counter_gaps(slice: &[u8]) -> Result<Vec<CounterGap>, PacketSliceError> {
+    let mut result = Vec::new();
+    let mut next_counters: HashMap<Option<[u8; 4]>, u8> = HashMap::new();
+    let mut offset = 0;
+    for packet in SliceIterator::new(slice) {
+        let packet = packet?;
+        let ecu_id = packet.ecu_id();
+        let actual = packet.header().message_counter;
+        if let Some(expected) = next_counters.insert(ecu_id, actual.wrapping_add(1)) {
+            if expected != actual {
+                result.push(CounterGap {
+                    offset,
+                    ecu_id,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        offset += packet.slice().len();
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod counter_gaps_tests {
+    use super::*;
+    use crate::test_packets;
+    use crate::DltHeader;
+    use alloc::vec;
+    use proptest::prelude::*;
+
+    fn packet(ecu_id: Option<[u8; 4]>, message_counter: u8) -> Vec<u8> {
+        let header = DltHeader {
+            message_counter,
+            ecu_id,
+            ..test_packets::header(true, None)
+        };
+        test_packets::packet(header, &[])
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Ok(Vec::new()), find_counter_gaps(&[]));
+    }
+
+    #[test]
+    fn per_ecu() {
+        let ecu1 = Some(*b"ECU1");
+        let ecu2 = Some(*b"ECU2");
+        let packets = [
+            packet(ecu1, 10),
+            packet(ecu2, 200),
+            packet(None, 0),
+            packet(ecu1, 11),
+            packet(ecu2, 202),
+            packet(None, 1),
+            packet(ecu1, 11),
+            packet(None, 0),
+        ];
+        let offset = |index: usize| packets[..index].iter().map(|p| p.len()).sum::<usize>();
+        let gaps = find_counter_gaps(&packets.concat()).unwrap();
+        assert_eq!(
+            vec![
+                CounterGap {
+                    offset: offset(4),
+                    ecu_id: ecu2,
+                    expected: 201,
+                    actual: 202,
+                },
+                CounterGap {
+                    offset: offset(6),
+                    ecu_id: ecu1,
+                    expected: 12,
+                    actual: 11,
+                },
+                CounterGap {
+                    offset: offset(7),
+                    ecu_id: None,
+                    expected: 2,
+                    actual: 0,
+                },
+            ],
+            gaps
+        );
+        assert_eq!(1, gaps[0].missing());
+        assert_eq!(255, gaps[1].missing());
+        assert_eq!(254, gaps[2].missing());
+    }
+
+    proptest! {
+        #[test]
+        fn wraparound(start in any::<u8>(), len in 0usize..600) {
+            let capture: Vec<u8> = (0..len)
+                .flat_map(|i| packet(Some(*b"ECU1"), start.wrapping_add(i as u8)))
+                .collect();
+            prop_assert_eq!(Ok(Vec::new()), find_counter_gaps(&capture));
+        }
+    }
+
+    #[test]
+    fn error() {
+        let mut capture = packet(None, 0);
+        capture.extend_from_slice(&packet(None, 2));
+        assert!(find_counter_gaps(&capture[..capture.len() - 1]).is_err());
+    }
+}
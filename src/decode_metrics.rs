@@ -0,0 +1,280 @@
+use core::time::Duration;
+use std::time::Instant;
+
+/// Distribution of the time it took to decode messages (requires the
+/// `metrics` feature).
+///
+/// Next to the minimum, maximum & average decode time a histogram with
+/// [`DecodeMetrics::BUCKET_COUNT`] buckets is kept. Bucket `i` counts
+/// the messages that took less than `2^i` microseconds to decode (& not
+/// less than `2^(i-1)` microseconds), the last bucket counts all slower
+/// messages.
+///
+/// [`crate::SliceIterator`] records the time it takes to slice each
+/// message. Other decode steps (e.g. iterating over the verbose values
+/// of a message) can be recorded via [`DecodeMetrics::measure`].
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{DecodeMetrics, SliceIterator};
+///
+/// # let data: [u8; 0] = [];
+/// let mut iter = SliceIterator::new(&data);
+/// let mut verbose_metrics = DecodeMetrics::new();
+/// for packet in &mut iter {
+///     let packet = packet.unwrap();
+///     verbose_metrics.measure(|| {
+///         if let Some(values) = packet.verbose_value_iter() {
+///             values.for_each(drop);
+///         }
+///     });
+/// }
+///
+/// let metrics = iter.metrics();
+/// println!(
+///     "{} messages, min {:?}, max {:?}, avg {:?}",
+///     metrics.count(),
+///     metrics.min(),
+///     metrics.max(),
+///     metrics.average()
+/// );
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DecodeMetrics {
+    count: u64,
+    total: Duration,
+    min: Option<Duration>,
+    max: Option<Duration>,
+    histogram: [u64; DecodeMetrics::BUCKET_COUNT],
+}
+
+impl DecodeMetrics {
+    /// Number of buckets in the histogram.
+    pub const BUCKET_COUNT: usize = 24;
+
+    /// Creates metrics without any recorded decode times.
+    #[inline]
+    pub fn new() -> DecodeMetrics {
+        DecodeMetrics::default()
+    }
+
+    /// Adds the decode time of a message.
+    pub fn record(&mut self, duration: Duration) {
+        self.count += 1;
+        self.total += duration;
+        self.min = Some(self.min.map_or(duration, |min| min.min(duration)));
+        self.max = Some(self.max.map_or(duration, |max| max.max(duration)));
+        self.histogram[DecodeMetrics::bucket_index(duration)] += 1;
+    }
+
+    /// Calls `f`, records the time it took & returns its result.
+    #[inline]
+    pub fn measure<T, F: FnOnce() -> T>(&mut self, f: F) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(start.elapsed());
+        result
+    }
+
+    /// Number of recorded messages.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Sum of all recorded decode times.
+    #[inline]
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Shortest recorded decode time (`None` if nothing was recorded).
+    #[inline]
+    pub fn min(&self) -> Option<Duration> {
+        self.min
+    }
+
+    /// Longest recorded decode time (`None` if nothing was recorded).
+    #[inline]
+    pub fn max(&self) -> Option<Duration> {
+        self.max
+    }
+
+    /// Average decode time (`None` if nothing was recorded).
+    pub fn average(&self) -> Option<Duration> {
+        (self.count > 0)
+            .then(|| Duration::from_nanos((self.total.as_nanos() / u128::from(self.count)) as u64))
+    }
+
+    /// Number of recorded messages per bucket (see
+    /// [`DecodeMetrics::bucket_upper_bound`] for the bucket limits).
+    #[inline]
+    pub fn histogram(&self) -> &[u64; DecodeMetrics::BUCKET_COUNT] {
+        &self.histogram
+    }
+
+    /// Exclusive upper limit of the decode times counted in the bucket
+    /// with the given index (`None` for the last bucket, which has no
+    /// upper limit, & indices outside of the histogram).
+    pub fn bucket_upper_bound(index: usize) -> Option<Duration> {
+        (index + 1 < DecodeMetrics::BUCKET_COUNT).then(|| Duration::from_micros(1 << index))
+    }
+
+    /// Merges the recorded decode times of `other` into `self`.
+    pub fn merge(&mut self, other: &DecodeMetrics) {
+        self.count += other.count;
+        self.total += other.total;
+        self.min = match (self.min, other.min) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.max = match (self.max, other.max) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        for (bucket, other) in self.histogram.iter_mut().zip(other.histogram.iter()) {
+            *bucket += other;
+        }
+    }
+
+    /// Removes all recorded decode times.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = DecodeMetrics::default();
+    }
+
+    /// Index of the histogram bucket for the given decode time.
+    fn bucket_index(duration: Duration) -> usize {
+        let micros = duration.as_micros();
+        // number of bits needed to represent the micro seconds
+        let bits = (u128::BITS - micros.leading_zeros()) as usize;
+        bits.min(DecodeMetrics::BUCKET_COUNT - 1)
+    }
+}
+
+#[cfg(test)]
+mod decode_metrics_tests {
+    use super::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    #[test]
+    fn empty() {
+        let metrics = DecodeMetrics::new();
+        assert_eq!(0, metrics.count());
+        assert_eq!(Duration::ZERO, metrics.total());
+        assert_eq!(None, metrics.min());
+        assert_eq!(None, metrics.max());
+        assert_eq!(None, metrics.average());
+        assert_eq!(&[0; DecodeMetrics::BUCKET_COUNT], metrics.histogram());
+    }
+
+    #[test]
+    fn record() {
+        let mut metrics = DecodeMetrics::new();
+        metrics.record(Duration::from_nanos(500));
+        metrics.record(Duration::from_micros(3));
+        metrics.record(Duration::from_micros(10));
+        metrics.record(Duration::from_secs(100));
+
+        assert_eq!(4, metrics.count());
+        assert_eq!(
+            Duration::from_secs(100) + Duration::from_nanos(13_500),
+            metrics.total()
+        );
+        assert_eq!(Some(Duration::from_nanos(500)), metrics.min());
+        assert_eq!(Some(Duration::from_secs(100)), metrics.max());
+        assert_eq!(
+            Some(Duration::from_nanos(25_000_003_375)),
+            metrics.average()
+        );
+
+        let mut expected = [0; DecodeMetrics::BUCKET_COUNT];
+        // < 1 us
+        expected[0] = 1;
+        // 2..4 us
+        expected[2] = 1;
+        // 8..16 us
+        expected[4] = 1;
+        // no upper limit
+        expected[DecodeMetrics::BUCKET_COUNT - 1] = 1;
+        assert_eq!(&expected, metrics.histogram());
+
+        metrics.clear();
+        assert_eq!(DecodeMetrics::new(), metrics);
+    }
+
+    #[test]
+    fn bucket_upper_bound() {
+        assert_eq!(
+            Some(Duration::from_micros(1)),
+            DecodeMetrics::bucket_upper_bound(0)
+        );
+        assert_eq!(
+            Some(Duration::from_micros(8)),
+            DecodeMetrics::bucket_upper_bound(3)
+        );
+        assert_eq!(
+            Some(Duration::from_micros(
+                1 << (DecodeMetrics::BUCKET_COUNT - 2)
+            )),
+            DecodeMetrics::bucket_upper_bound(DecodeMetrics::BUCKET_COUNT - 2)
+        );
+        assert_eq!(
+            None,
+            DecodeMetrics::bucket_upper_bound(DecodeMetrics::BUCKET_COUNT - 1)
+        );
+        assert_eq!(
+            None,
+            DecodeMetrics::bucket_upper_bound(DecodeMetrics::BUCKET_COUNT)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn bucket_index(nanos in any::<u64>()) {
+            let duration = Duration::from_nanos(nanos);
+            let index = DecodeMetrics::bucket_index(duration);
+            if let Some(upper) = DecodeMetrics::bucket_upper_bound(index) {
+                prop_assert!(duration < upper);
+            }
+            if index > 0 {
+                prop_assert!(duration >= DecodeMetrics::bucket_upper_bound(index - 1).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn measure_merge() {
+        let mut a = DecodeMetrics::new();
+        assert_eq!(3, a.measure(|| 1 + 2));
+        assert_eq!(1, a.count());
+
+        let mut b = DecodeMetrics::new();
+        b.record(Duration::from_secs(1));
+        b.record(Duration::from_secs(2));
+
+        let mut merged = a.clone();
+        merged.merge(&b);
+        assert_eq!(3, merged.count());
+        assert_eq!(a.total() + Duration::from_secs(3), merged.total());
+        assert_eq!(a.min(), merged.min());
+        assert_eq!(Some(Duration::from_secs(2)), merged.max());
+        assert_eq!(3, merged.histogram().iter().sum::<u64>());
+
+        // merging into empty metrics
+        let mut empty = DecodeMetrics::new();
+        empty.merge(&b);
+        assert_eq!(b, empty);
+        b.merge(&DecodeMetrics::new());
+        assert_eq!(empty, b);
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let metrics = DecodeMetrics::new();
+        assert_eq!(metrics, metrics.clone());
+        assert!(format!("{:?}", metrics).starts_with("DecodeMetrics {"));
+    }
+}
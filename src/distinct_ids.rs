@@ -0,0 +1,114 @@
+use crate::{error::PacketSliceError, DltPacketSlice, SliceIterator};
+use std::collections::BTreeSet;
+
+/// Collects all ECU ids present in the dlt messages in the given slice
+/// (messages without an ECU id are skipped).
+///
+/// The slice is expected to only contain dlt messages (without storage
+/// headers). Decoding stops at the first message that can not be decoded
+/// and the error is returned.
+pub fn distinct_ecu_ids(slice: &[u8]) -> Result<BTreeSet<[u8; 4]>, PacketSliceError> {
+    distinct_ids(slice, |packet| packet.ecu_id())
+}
+
+/// Collects all application ids present in the dlt messages in the given
+/// slice (messages without an extended header are skipped).
+///
+/// The slice is expected to only contain dlt messages (without storage
+/// headers). Decoding stops at the first message that can not be decoded
+/// and the error is returned.
+pub fn distinct_app_ids(slice: &[u8]) -> Result<BTreeSet<[u8; 4]>, PacketSliceError> {
+    distinct_ids(slice, |packet| {
+        packet.extended_header().map(|ext| ext.application_id)
+    })
+}
+
+/// Collects all context ids present in the dlt messages in the given
+/// slice (messages without an extended header are skipped).
+///
+/// The slice is expected to only contain dlt messages (without storage
+/// headers). Decoding stops at the first message that can not be decoded
+/// and the error is returned.
+pub fn distinct_context_ids(slice: &[u8]) -> Result<BTreeSet<[u8; 4]>, PacketSliceError> {
+    distinct_ids(slice, |packet| {
+        packet.extended_header().map(|ext| ext.context_id)
+    })
+}
+
+fn distinct_ids<F>(slice: &[u8], get_id: F) -> Result<BTreeSet<[u8; 4]>, PacketSliceError>
+where
+    F: Fn(&DltPacketSlice<'_>) -> Option<[u8; 4]>,
+{
+    let mut result = BTreeSet::new();
+    for packet in SliceIterator::new(slice) {
+        if let Some(id) = get_id(&packet?) {
+            result.insert(id);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use crate::DltHeader;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn distinct(ref packets in prop::collection::vec(dlt_header_with_payload_any(), 0..8)) {
+            let mut buffer = Vec::new();
+            for (header, payload) in packets {
+                buffer.extend_from_slice(&header.to_bytes());
+                buffer.extend_from_slice(payload);
+            }
+
+            let ecu_ids: BTreeSet<[u8; 4]> = packets.iter().filter_map(|p| p.0.ecu_id).collect();
+            let app_ids: BTreeSet<[u8; 4]> = packets
+                .iter()
+                .filter_map(|p| p.0.extended_header.as_ref().map(|e| e.application_id))
+                .collect();
+            let context_ids: BTreeSet<[u8; 4]> = packets
+                .iter()
+                .filter_map(|p| p.0.extended_header.as_ref().map(|e| e.context_id))
+                .collect();
+
+            prop_assert_eq!(Ok(ecu_ids), distinct_ecu_ids(&buffer));
+            prop_assert_eq!(Ok(app_ids), distinct_app_ids(&buffer));
+            prop_assert_eq!(Ok(context_ids), distinct_context_ids(&buffer));
+
+            // error at the end of the slice
+            if false == buffer.is_empty() {
+                let len = buffer.len() - 1;
+                prop_assert!(distinct_ecu_ids(&buffer[..len]).is_err());
+                prop_assert!(distinct_app_ids(&buffer[..len]).is_err());
+                prop_assert!(distinct_context_ids(&buffer[..len]).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn duplicates() {
+        let header = DltHeader {
+            is_big_endian: false,
+            message_counter: 0,
+            length: 4 + 4 + 4,
+            ecu_id: Some(*b"ECU1"),
+            session_id: None,
+            timestamp: None,
+            extended_header: None,
+        };
+        let mut buffer = Vec::new();
+        for _ in 0..3 {
+            buffer.extend_from_slice(&header.to_bytes());
+            buffer.extend_from_slice(&[0, 0, 0, 0]);
+        }
+        assert_eq!(
+            [*b"ECU1"].into_iter().collect::<BTreeSet<_>>(),
+            distinct_ecu_ids(&buffer).unwrap()
+        );
+        assert!(distinct_app_ids(&buffer).unwrap().is_empty());
+    }
+}
@@ -72,10 +72,31 @@ impl DltExtendedHeader {
         //all good
         Ok(())
     }
+
+    ///Returns true if the message info contains values that are reserved
+    ///by the DLT specification.
+    ///
+    ///The message info byte has no unused bits (bit 0 is the verbose flag,
+    ///bits 1-3 the message type (MSTP) & bits 4-7 the message type info
+    ///(MTIN)). Instead the following value ranges are reserved:
+    ///
+    ///* MSTP values 4-7
+    ///* MTIN 0 & 7-15 for log messages
+    ///* MTIN 0 & 6-15 for trace messages
+    ///* MTIN 0 & 4-15 for control messages
+    ///
+    ///Network trace messages have no reserved MTIN values, as 7-15 are
+    ///user defined. This is equivalent to [`DltExtendedHeader::message_type`]
+    ///returning `None`.
+    #[inline]
+    pub fn has_reserved_bits_set(&self) -> bool {
+        self.message_type().is_none()
+    }
 }
 
 /// Tests for `DltExtendedHeader` methods
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::clone_on_copy)]
 mod dlt_extended_header_tests {
 
     use super::*;
@@ -244,8 +265,8 @@ mod dlt_extended_header_tests {
             (Log(Fatal), (0u8..1).chain(7u8..=0xf)),
             //bad trace source (0 & everything above 5)
             (Trace(FunctionIn), (0u8..1).chain(6u8..=0xf)),
-            //bad control message type (0 & everything above 2)
-            (Control(Request), (0u8..1).chain(3u8..=0xf)),
+            //bad control message type (0 & everything above 3)
+            (Control(Request), (0u8..1).chain(4u8..=0xf)),
         ];
 
         for t in bad_values.iter() {
@@ -279,4 +300,55 @@ mod dlt_extended_header_tests {
             }
         }
     }
+
+    proptest! {
+        #[test]
+        fn has_reserved_bits_set(
+            message_type in message_type_any(),
+            verbose in any::<bool>(),
+            message_info in any::<u8>()
+        ) {
+            // valid message types
+            {
+                let mut header = DltExtendedHeader::new_non_verbose(
+                    message_type,
+                    Default::default(),
+                    Default::default(),
+                ).unwrap();
+                header.set_is_verbose(verbose);
+                assert_eq!(false, header.has_reserved_bits_set());
+            }
+            // any value
+            {
+                let header = DltExtendedHeader {
+                    message_info: DltMessageInfo(message_info),
+                    ..Default::default()
+                };
+                assert_eq!(
+                    header.message_type().is_none(),
+                    header.has_reserved_bits_set()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn has_reserved_bits_set_values() {
+        // reserved message type (MSTP)
+        for mstp in 4..=0b111u8 {
+            let header = DltExtendedHeader {
+                message_info: DltMessageInfo((1 << 4) | (mstp << 1)),
+                ..Default::default()
+            };
+            assert!(header.has_reserved_bits_set());
+        }
+        // reserved log level (MTIN 0 & 7)
+        for mtin in [0u8, 7] {
+            let header = DltExtendedHeader {
+                message_info: DltMessageInfo(mtin << 4),
+                ..Default::default()
+            };
+            assert!(header.has_reserved_bits_set());
+        }
+    }
 } // mod dlt_extended_header_tests
@@ -0,0 +1,148 @@
+use super::*;
+
+/// Framing of the DLT packets in a data stream (e.g. used by the
+/// `DltReader` to know what data is expected in front of each packet).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum DltFraming {
+    /// DLT packets are directly concatenated without any additional
+    /// data in between (e.g. the payload of a TCP connection to a
+    /// DLT daemon):
+    ///
+    /// ```text
+    /// | dlt packet | dlt packet | ...
+    /// ```
+    ///
+    /// The length of each packet is taken from the length field of
+    /// the DLT header.
+    Raw,
+
+    /// Each DLT packet is preceded by the 4 byte serial header pattern
+    /// [`crate::SERIAL_HEADER_PATTERN`] ("DLS" followed by 0x01) as
+    /// used when DLT is transmitted via a serial connection:
+    ///
+    /// ```text
+    /// | "DLS" 0x01 | dlt packet | "DLS" 0x01 | dlt packet | ...
+    /// ```
+    SerialHeader,
+
+    /// Each DLT packet is preceded by a 16 byte storage header as used
+    /// in DLT files (see [`crate::storage::StorageHeader`]):
+    ///
+    /// ```text
+    /// | "DLT" 0x01 | seconds (u32 LE) | microseconds (i32 LE) | ecu id (4 bytes) | dlt packet | ...
+    /// ```
+    StorageHeader,
+}
+
+/// Detects the framing of the DLT data at the start of the given slice.
+///
+/// The storage header ("DLT" 0x01) & serial header ("DLS" 0x01) patterns
+/// are checked first. Otherwise the start is checked to be a valid DLT
+/// header (supported version, complete header & a length field
+/// covering at least the header) and [`DltFraming::Raw`] is returned.
+/// `None` is returned if no known framing is detected.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{detect_framing, DltFraming};
+///
+/// assert_eq!(
+///     Some(DltFraming::StorageHeader),
+///     detect_framing(&[0x44, 0x4C, 0x54, 0x01])
+/// );
+/// assert_eq!(
+///     Some(DltFraming::SerialHeader),
+///     detect_framing(&[0x44, 0x4C, 0x53, 0x01])
+/// );
+/// assert_eq!(Some(DltFraming::Raw), detect_framing(&[0x20, 0, 0, 4]));
+/// assert_eq!(None, detect_framing(&[0, 0, 0, 0]));
+/// ```
+pub fn detect_framing(slice: &[u8]) -> Option<DltFraming> {
+    if slice.starts_with(&storage::StorageHeader::PATTERN_AT_START) {
+        Some(DltFraming::StorageHeader)
+    } else if slice.starts_with(&SERIAL_HEADER_PATTERN) {
+        Some(DltFraming::SerialHeader)
+    } else {
+        let header = DltHeader::from_slice(slice).ok()?;
+        if header.length >= header.header_len() {
+            Some(DltFraming::Raw)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod dlt_framing_tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use alloc::{format, vec::Vec};
+    use proptest::prelude::*;
+
+    #[test]
+    fn debug_clone_eq() {
+        assert_eq!(DltFraming::Raw, DltFraming::Raw.clone());
+        assert_eq!("SerialHeader", format!("{:?}", DltFraming::SerialHeader));
+    }
+
+    proptest! {
+        #[test]
+        fn detect(
+            ref header in dlt_header_any(),
+            ref storage_header in storage_header_any(),
+        ) {
+            let mut header = header.clone();
+            header.length = header.header_len();
+            let header_bytes = header.to_bytes();
+
+            // raw
+            prop_assert_eq!(Some(DltFraming::Raw), detect_framing(&header_bytes));
+
+            // storage header
+            {
+                let mut data = Vec::new();
+                data.extend_from_slice(&storage_header.to_bytes());
+                data.extend_from_slice(&header_bytes);
+                prop_assert_eq!(Some(DltFraming::StorageHeader), detect_framing(&data));
+                prop_assert_eq!(Some(DltFraming::StorageHeader), detect_framing(&data[..4]));
+            }
+
+            // serial header
+            {
+                let mut data = Vec::new();
+                data.extend_from_slice(&SERIAL_HEADER_PATTERN);
+                data.extend_from_slice(&header_bytes);
+                prop_assert_eq!(Some(DltFraming::SerialHeader), detect_framing(&data));
+                prop_assert_eq!(Some(DltFraming::SerialHeader), detect_framing(&data[..4]));
+            }
+
+            // incomplete header
+            prop_assert_eq!(None, detect_framing(&header_bytes[..header_bytes.len() - 1]));
+
+            // length field smaller then the header
+            {
+                let mut bytes = header_bytes.clone();
+                let len_be = (header.header_len() - 1).to_be_bytes();
+                bytes[2] = len_be[0];
+                bytes[3] = len_be[1];
+                prop_assert_eq!(None, detect_framing(&bytes));
+            }
+
+            // unsupported version
+            {
+                let mut bytes = header_bytes.clone();
+                bytes[0] = (bytes[0] & 0b0001_1111) | (2 << 5);
+                prop_assert_eq!(None, detect_framing(&bytes));
+            }
+        }
+    }
+
+    #[test]
+    fn detect_unknown() {
+        assert_eq!(None, detect_framing(&[]));
+        assert_eq!(None, detect_framing(&[0x44, 0x4C, 0x54]));
+        assert_eq!(None, detect_framing(&[0x44, 0x4C, 0x53, 0x02]));
+        assert_eq!(None, detect_framing(&[0, 0, 0, 0]));
+    }
+}
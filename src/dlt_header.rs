@@ -32,6 +32,107 @@ impl DltHeader {
     /// Version that will be written into the DLT header version field when writing this header.
     pub const VERSION: u8 = 1;
 
+    /// Creates a header for a verbose log message with the given ECU id,
+    /// application id, context id & log level.
+    ///
+    /// The payload is flagged as little endian, no session id & timestamp
+    /// are set and the number of arguments is 0. The `length` field is set
+    /// to the header length (empty payload) & has to be increased by the
+    /// length of the payload (see also [`crate::normalize_length`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::{DltHeader, DltLogLevel, DltMessageType};
+    ///
+    /// let header = DltHeader::new_verbose_log(*b"ECU1", *b"APP1", *b"CTX1", DltLogLevel::Info);
+    /// let ext = header.extended_header.as_ref().unwrap();
+    /// assert!(ext.is_verbose());
+    /// assert_eq!(Some(DltMessageType::Log(DltLogLevel::Info)), ext.message_type());
+    /// assert_eq!(header.header_len(), header.length);
+    /// ```
+    pub fn new_verbose_log(
+        ecu_id: [u8; 4],
+        application_id: [u8; 4],
+        context_id: [u8; 4],
+        log_level: DltLogLevel,
+    ) -> DltHeader {
+        let mut extended_header =
+            DltExtendedHeader::new_non_verbose_log(log_level, application_id, context_id);
+        extended_header.set_is_verbose(true);
+        DltHeader::with_ecu_id(ecu_id, Some(extended_header))
+    }
+
+    /// Creates a header for a non verbose log message with an extended
+    /// header containing the given application id, context id & log level.
+    ///
+    /// Apart from the verbose flag the header is set up like in
+    /// [`DltHeader::new_verbose_log`].
+    pub fn new_non_verbose_log(
+        ecu_id: [u8; 4],
+        application_id: [u8; 4],
+        context_id: [u8; 4],
+        log_level: DltLogLevel,
+    ) -> DltHeader {
+        DltHeader::with_ecu_id(
+            ecu_id,
+            Some(DltExtendedHeader::new_non_verbose_log(
+                log_level,
+                application_id,
+                context_id,
+            )),
+        )
+    }
+
+    /// Creates a header for a non verbose message without extended header
+    /// (only the ECU id is set).
+    ///
+    /// The message id of non verbose messages is part of the payload. The
+    /// header can be used as template for [`NonVerbosePayload::write`],
+    /// which writes the message id & calculates the `length` field.
+    /// Otherwise the `length` field is set to the header length (empty
+    /// payload) & has to be increased by the length of the payload.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// use dlt_parse::{DltHeader, DltPacketSlice, NonVerbosePayload};
+    ///
+    /// let header = DltHeader::new_non_verbose(*b"ECU1");
+    /// let mut buffer = Vec::new();
+    /// NonVerbosePayload { msg_id: 0x1234, data: &[1, 2] }
+    ///     .write(&header, &mut buffer)
+    ///     .unwrap();
+    ///
+    /// let packet = DltPacketSlice::from_slice(&buffer).unwrap();
+    /// assert_eq!(
+    ///     Some(NonVerbosePayload { msg_id: 0x1234, data: &[1, 2] }),
+    ///     NonVerbosePayload::from_packet(&packet)
+    /// );
+    /// # }
+    /// ```
+    pub fn new_non_verbose(ecu_id: [u8; 4]) -> DltHeader {
+        DltHeader::with_ecu_id(ecu_id, None)
+    }
+
+    /// Little endian header with the given ECU id & extended header and the
+    /// length field set to the header length.
+    fn with_ecu_id(ecu_id: [u8; 4], extended_header: Option<DltExtendedHeader>) -> DltHeader {
+        let mut header = DltHeader {
+            is_big_endian: false,
+            message_counter: 0,
+            length: 0,
+            ecu_id: Some(ecu_id),
+            session_id: None,
+            timestamp: None,
+            extended_header,
+        };
+        header.length = header.header_len();
+        header
+    }
+
     pub fn from_slice(slice: &[u8]) -> Result<DltHeader, error::PacketSliceError> {
         use error::{PacketSliceError::*, *};
 
@@ -428,23 +529,20 @@ impl DltHeader {
         }
 
         //write the extended header if it exists
-        match &self.extended_header {
-            Some(value) => {
-                let bytes: [u8; 10] = [
-                    value.message_info.0,
-                    value.number_of_arguments,
-                    value.application_id[0],
-                    value.application_id[1],
-                    value.application_id[2],
-                    value.application_id[3],
-                    value.context_id[0],
-                    value.context_id[1],
-                    value.context_id[2],
-                    value.context_id[3],
-                ];
-                writer.write_all(&bytes)?;
-            }
-            None => {}
+        if let Some(value) = &self.extended_header {
+            let bytes: [u8; 10] = [
+                value.message_info.0,
+                value.number_of_arguments,
+                value.application_id[0],
+                value.application_id[1],
+                value.application_id[2],
+                value.application_id[3],
+                value.context_id[0],
+                value.context_id[1],
+                value.context_id[2],
+                value.context_id[3],
+            ];
+            writer.write_all(&bytes)?;
         }
         Ok(())
     }
@@ -458,6 +556,36 @@ impl DltHeader {
         }
     }
 
+    ///Returns the timestamp converted to a duration using the given
+    ///interpretation (`None` if no timestamp is present).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use dlt_parse::{DltHeader, TimestampInterpretation};
+    ///
+    /// let header = DltHeader {
+    ///     timestamp: Some(12_345),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     Some(Duration::from_micros(1_234_500)),
+    ///     header.timestamp_duration(TimestampInterpretation::Relative)
+    /// );
+    /// assert_eq!(
+    ///     Some(Duration::from_secs(12_345)),
+    ///     header.timestamp_duration(TimestampInterpretation::Absolute)
+    /// );
+    /// ```
+    #[inline]
+    pub fn timestamp_duration(
+        &self,
+        interpretation: TimestampInterpretation,
+    ) -> Option<core::time::Duration> {
+        self.timestamp.map(|t| interpretation.to_duration(t))
+    }
+
     ///Return the byte/octed size of the serialized header (including extended header)
     #[inline]
     pub fn header_len(&self) -> u16 {
@@ -478,18 +606,31 @@ impl DltHeader {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod dlt_header_tests {
 
     use super::*;
     use crate::proptest_generators::*;
     use proptest::prelude::*;
 
+    proptest! {
+        #[test]
+        fn timestamp_duration(ref dlt_header in dlt_header_any()) {
+            for interpretation in [TimestampInterpretation::Relative, TimestampInterpretation::Absolute] {
+                prop_assert_eq!(
+                    dlt_header.timestamp.map(|t| interpretation.to_duration(t)),
+                    dlt_header.timestamp_duration(interpretation)
+                );
+            }
+        }
+    }
+
     proptest! {
         #[test]
         fn to_bytes_from_slice(
             version in 0..=1u8,
             ref dlt_header in dlt_header_any(),
-            unsupported_version in (0u8..0b111u8).prop_filter(
+            unsupported_version in (0u8..=MAX_VERSION).prop_filter(
                 "version must be unknown",
                 |v| !DltHeader::SUPPORTED_DECODABLE_VERSIONS.iter().any(|&x| v == &x)
             )
@@ -546,6 +687,39 @@ mod dlt_header_tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn version_encoding(
+            version in 0..=MAX_VERSION,
+            ref dlt_header in dlt_header_any(),
+        ) {
+            // the written header type always contains the supported version
+            let mut bytes = dlt_header.to_bytes();
+            prop_assert_eq!(DltHeader::VERSION, (bytes[0] >> 5) & MAX_VERSION);
+
+            // every version (including MAX_VERSION) is read back unmodified
+            // & the flags are not affected by the version bits
+            let flags = bytes[0] & 0b0001_1111;
+            bytes[0] = flags | ((version << 5) & 0b1110_0000);
+            prop_assert_eq!(version, (bytes[0] >> 5) & MAX_VERSION);
+            prop_assert_eq!(flags, bytes[0] & 0b0001_1111);
+
+            let result = DltHeader::from_slice(&bytes);
+            if DltHeader::SUPPORTED_DECODABLE_VERSIONS.contains(&version) {
+                prop_assert_eq!(Ok(dlt_header.clone()), result);
+            } else {
+                prop_assert_eq!(
+                    Err(error::PacketSliceError::UnsupportedDltVersion(
+                        error::UnsupportedDltVersionError {
+                            unsupported_version: version
+                        }
+                    )),
+                    result
+                );
+            }
+        }
+    }
+
     proptest! {
         #[test]
         #[cfg(feature = "std")]
@@ -607,6 +781,56 @@ mod dlt_header_tests {
         assert_eq!(true, header.is_verbose());
     }
 
+    proptest! {
+        #[test]
+        fn constructors(
+            ecu_id in any::<[u8; 4]>(),
+            application_id in any::<[u8; 4]>(),
+            context_id in any::<[u8; 4]>(),
+            log_level in log_level_any(),
+        ) {
+            // verbose & non verbose log
+            for is_verbose in [true, false] {
+                let header = if is_verbose {
+                    DltHeader::new_verbose_log(ecu_id, application_id, context_id, log_level)
+                } else {
+                    DltHeader::new_non_verbose_log(ecu_id, application_id, context_id, log_level)
+                };
+                prop_assert_eq!(4 + 4 + 10, header.length);
+
+                let bytes = header.to_bytes();
+                prop_assert_eq!(
+                    (DltHeader::VERSION << 5) | ECU_ID_FLAG | EXTDENDED_HEADER_FLAG,
+                    bytes[0]
+                );
+                let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+                prop_assert_eq!(false, slice.is_big_endian());
+                prop_assert_eq!(is_verbose, slice.is_verbose());
+                prop_assert_eq!(Some(ecu_id), slice.ecu_id());
+                prop_assert_eq!(None, slice.header().session_id);
+                prop_assert_eq!(None, slice.header().timestamp);
+                prop_assert_eq!(Some(DltMessageType::Log(log_level)), slice.message_type());
+                let ext = slice.extended_header().unwrap();
+                prop_assert_eq!(0, ext.number_of_arguments);
+                prop_assert_eq!(application_id, ext.application_id);
+                prop_assert_eq!(context_id, ext.context_id);
+            }
+
+            // non verbose without extended header
+            {
+                let header = DltHeader::new_non_verbose(ecu_id);
+                prop_assert_eq!(4 + 4, header.length);
+                let bytes = header.to_bytes();
+                prop_assert_eq!((DltHeader::VERSION << 5) | ECU_ID_FLAG, bytes[0]);
+                let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+                prop_assert_eq!(false, slice.is_big_endian());
+                prop_assert_eq!(false, slice.is_verbose());
+                prop_assert_eq!(Some(ecu_id), slice.ecu_id());
+                prop_assert_eq!(None, slice.extended_header());
+            }
+        }
+    }
+
     #[test]
     fn header_len() {
         struct Test {
@@ -0,0 +1,174 @@
+use super::*;
+
+/// Flat `#[repr(C)]` representation of a [`DltHeader`] for passing
+/// headers over a C ABI (e.g. via bindings generated by cbindgen).
+///
+/// The optional parts of a [`DltHeader`] are flattened with the
+/// following rules:
+///
+/// * Every optional field is stored in a fixed size field and paired
+///   with a `has_*` presence boolean.
+/// * Fields that are not present are set to zero when converting from
+///   a [`DltHeader`] and their values are ignored when converting back.
+/// * The extended header is flattened into `message_info`,
+///   `number_of_arguments`, `application_id` & `context_id`, all
+///   guarded by `has_extended_header`.
+/// * `header_type` contains the header type byte as it would be encoded
+///   (including the presence flags & version). When converting back to
+///   a [`DltHeader`] only the MSBF (big endian) flag is taken from it,
+///   presence is determined by the `has_*` booleans.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+pub struct DltHeaderFlat {
+    /// Header type byte (UEH = 0x01, MSBF = 0x02, WEID = 0x04,
+    /// WSID = 0x08, WTMS = 0x10, version in the upper 3 bits).
+    pub header_type: u8,
+    pub message_counter: u8,
+    pub length: u16,
+    pub has_ecu_id: bool,
+    pub ecu_id: [u8; 4],
+    pub has_session_id: bool,
+    pub session_id: u32,
+    pub has_timestamp: bool,
+    pub timestamp: u32,
+    pub has_extended_header: bool,
+    pub message_info: u8,
+    pub number_of_arguments: u8,
+    pub application_id: [u8; 4],
+    pub context_id: [u8; 4],
+}
+
+impl DltHeaderFlat {
+    /// Returns true if the MSBF flag is set in the header type
+    /// (payload is encoded in big endian).
+    #[inline]
+    pub fn is_big_endian(&self) -> bool {
+        0 != self.header_type & BIG_ENDIAN_FLAG
+    }
+}
+
+impl From<&DltHeader> for DltHeaderFlat {
+    fn from(header: &DltHeader) -> DltHeaderFlat {
+        let ext = header.extended_header.as_ref();
+        DltHeaderFlat {
+            header_type: header.to_bytes()[0],
+            message_counter: header.message_counter,
+            length: header.length,
+            has_ecu_id: header.ecu_id.is_some(),
+            ecu_id: header.ecu_id.unwrap_or([0; 4]),
+            has_session_id: header.session_id.is_some(),
+            session_id: header.session_id.unwrap_or(0),
+            has_timestamp: header.timestamp.is_some(),
+            timestamp: header.timestamp.unwrap_or(0),
+            has_extended_header: ext.is_some(),
+            message_info: ext.map(|e| e.message_info.0).unwrap_or(0),
+            number_of_arguments: ext.map(|e| e.number_of_arguments).unwrap_or(0),
+            application_id: ext.map(|e| e.application_id).unwrap_or([0; 4]),
+            context_id: ext.map(|e| e.context_id).unwrap_or([0; 4]),
+        }
+    }
+}
+
+impl From<DltHeader> for DltHeaderFlat {
+    #[inline]
+    fn from(header: DltHeader) -> DltHeaderFlat {
+        DltHeaderFlat::from(&header)
+    }
+}
+
+impl From<&DltHeaderFlat> for DltHeader {
+    fn from(flat: &DltHeaderFlat) -> DltHeader {
+        DltHeader {
+            is_big_endian: flat.is_big_endian(),
+            message_counter: flat.message_counter,
+            length: flat.length,
+            ecu_id: flat.has_ecu_id.then(|| flat.ecu_id),
+            session_id: flat.has_session_id.then(|| flat.session_id),
+            timestamp: flat.has_timestamp.then(|| flat.timestamp),
+            extended_header: flat.has_extended_header.then(|| DltExtendedHeader {
+                message_info: DltMessageInfo(flat.message_info),
+                number_of_arguments: flat.number_of_arguments,
+                application_id: flat.application_id,
+                context_id: flat.context_id,
+            }),
+        }
+    }
+}
+
+impl From<DltHeaderFlat> for DltHeader {
+    #[inline]
+    fn from(flat: DltHeaderFlat) -> DltHeader {
+        DltHeader::from(&flat)
+    }
+}
+
+#[cfg(test)]
+mod dlt_header_flat_tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn round_trip(ref header in dlt_header_any()) {
+            let flat = DltHeaderFlat::from(header);
+
+            // the header type matches the encoded one
+            let bytes = header.to_bytes();
+            prop_assert_eq!(bytes[0], flat.header_type);
+            prop_assert_eq!(header.is_big_endian, flat.is_big_endian());
+            prop_assert_eq!(header.message_counter, flat.message_counter);
+            prop_assert_eq!(header.length, flat.length);
+            prop_assert_eq!(header.ecu_id.is_some(), flat.has_ecu_id);
+            prop_assert_eq!(header.ecu_id.unwrap_or([0; 4]), flat.ecu_id);
+            prop_assert_eq!(header.session_id.is_some(), flat.has_session_id);
+            prop_assert_eq!(header.session_id.unwrap_or(0), flat.session_id);
+            prop_assert_eq!(header.timestamp.is_some(), flat.has_timestamp);
+            prop_assert_eq!(header.timestamp.unwrap_or(0), flat.timestamp);
+            prop_assert_eq!(header.extended_header.is_some(), flat.has_extended_header);
+
+            prop_assert_eq!(header, &DltHeader::from(flat));
+            prop_assert_eq!(flat, DltHeaderFlat::from(header.clone()));
+            prop_assert_eq!(header, &DltHeader::from(&flat));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn absent_fields_ignored(
+            ref header in dlt_header_any(),
+            ecu_id in any::<[u8; 4]>(),
+            session_id in any::<u32>(),
+            timestamp in any::<u32>(),
+            message_info in any::<u8>(),
+        ) {
+            let mut header = header.clone();
+            header.ecu_id = None;
+            header.session_id = None;
+            header.timestamp = None;
+            header.extended_header = None;
+
+            let mut flat = DltHeaderFlat::from(&header);
+            prop_assert_eq!([0; 4], flat.ecu_id);
+            prop_assert_eq!(0, flat.session_id);
+            prop_assert_eq!(0, flat.timestamp);
+            prop_assert_eq!(0, flat.message_info);
+
+            // values of absent fields & presence flags in the header
+            // type are ignored
+            flat.header_type |= 0b0001_1101;
+            flat.ecu_id = ecu_id;
+            flat.session_id = session_id;
+            flat.timestamp = timestamp;
+            flat.message_info = message_info;
+            prop_assert_eq!(header, DltHeader::from(flat));
+        }
+    }
+
+    #[test]
+    fn default() {
+        let flat = DltHeaderFlat::default();
+        assert!(false == flat.is_big_endian());
+        assert_eq!(DltHeader::default(), DltHeader::from(flat));
+    }
+}
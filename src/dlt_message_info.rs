@@ -46,6 +46,7 @@ impl DltMessageInfo {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
     use super::*;
 
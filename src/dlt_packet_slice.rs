@@ -2,49 +2,10 @@ use crate::verbose::VerboseIter;
 
 use super::*;
 
-const SET_LOG_LEVEL: &str = "set_log_level";
-const SET_TRACE_STATUS: &str = "set_trace_status";
-const GET_LOG_INFO: &str = "get_log_info";
-const GET_DEFAULT_LOG_LEVEL: &str = "get_default_log_level";
-const STORE_CONFIGURATION: &str = "store_configuration";
-const RESET_TO_FACTORY_DEFAULT: &str = "reset_to_factory_default";
-const SET_MESSAGE_FILTERING: &str = "set_message_filtering";
-const SET_DEFAULT_LOG_LEVEL: &str = "set_default_log_level";
-const SET_DEFAULT_TRACE_STATUS: &str = "set_default_trace_status";
-const GET_SOFTWARE_VERSION: &str = "get_software_version";
-const GET_DEFAULT_TRACE_STATUS: &str = "get_default_trace_status";
-const GET_LOG_CHANNEL_NAMES: &str = "get_log_channel_names";
-const GET_TRACE_STATUS: &str = "get_trace_status";
-const SET_LOG_CHANNEL_ASSIGNMENT: &str = "set_log_channel_assignment";
-const SET_LOG_CHANNEL_THRESHOLD: &str = "set_log_channel_threshold";
-const GET_LOG_CHANNEL_THRESHOLD: &str = "get_log_channel_threshold";
-const BUFFER_OVERFLOW_NOTIFICATION: &str = "buffer_overflow_notification";
-const SYNC_TIME_STAMP: &str = "sync_time_stamp";
-const CALL_SWC_INJECTIONS: &str = "call_swc_injections";
-
+/// Returns the canonical name of the control service with the given id
+/// (see [`control::DltServiceId::name`]).
 pub fn service_name(service_id: u32) -> Option<&'static str> {
-    match service_id {
-        0x01 => Some(SET_LOG_LEVEL),
-        0x02 => Some(SET_TRACE_STATUS),
-        0x03 => Some(GET_LOG_INFO),
-        0x04 => Some(GET_DEFAULT_LOG_LEVEL),
-        0x05 => Some(STORE_CONFIGURATION),
-        0x06 => Some(RESET_TO_FACTORY_DEFAULT),
-        0x0A => Some(SET_MESSAGE_FILTERING),
-        0x11 => Some(SET_DEFAULT_LOG_LEVEL),
-        0x12 => Some(SET_DEFAULT_TRACE_STATUS),
-        0x13 => Some(GET_SOFTWARE_VERSION),
-        0x15 => Some(GET_DEFAULT_TRACE_STATUS),
-        0x17 => Some(GET_LOG_CHANNEL_NAMES),
-        0x1F => Some(GET_TRACE_STATUS),
-        0x20 => Some(SET_LOG_CHANNEL_ASSIGNMENT),
-        0x21 => Some(SET_LOG_CHANNEL_THRESHOLD),
-        0x22 => Some(GET_LOG_CHANNEL_THRESHOLD),
-        0x23 => Some(BUFFER_OVERFLOW_NOTIFICATION),
-        0x24 => Some(SYNC_TIME_STAMP),
-        0xFFF..=0xFFFFFFFF => Some(CALL_SWC_INJECTIONS),
-        _ => None,
-    }
+    control::DltServiceId::from_u32(service_id).name()
 }
 
 ///A slice containing an dlt header & payload.
@@ -56,7 +17,21 @@ pub struct DltPacketSlice<'a> {
 
 impl<'a> DltPacketSlice<'a> {
     ///Read the dlt header and create a slice containing the dlt header & payload.
-    pub fn from_slice(slice: &'a [u8]) -> Result<DltPacketSlice<'_>, error::PacketSliceError> {
+    #[inline]
+    pub fn from_slice(slice: &'a [u8]) -> Result<DltPacketSlice<'a>, error::PacketSliceError> {
+        DltPacketSlice::from_slice_with_config(slice, &ParseConfig::default())
+    }
+
+    /// Read the dlt header and create a slice containing the dlt header &
+    /// payload with the checks configured in `config` (see [`ParseConfig`]).
+    ///
+    /// If truncated packets are allowed the returned slice can be shorter
+    /// then the length field of the header. [`DltPacketSlice::try_header`]
+    /// returns an error for truncated packets & unknown versions.
+    pub fn from_slice_with_config(
+        slice: &'a [u8],
+        config: &ParseConfig,
+    ) -> Result<DltPacketSlice<'a>, error::PacketSliceError> {
         use error::{PacketSliceError::*, *};
 
         if slice.len() < 4 {
@@ -74,7 +49,7 @@ impl<'a> DltPacketSlice<'a> {
 
         // check version
         let version = (header_type >> 5) & MAX_VERSION;
-        if 0 != version && 1 != version {
+        if false == config.allow_unknown_versions && 0 != version && 1 != version {
             return Err(UnsupportedDltVersion(UnsupportedDltVersionError {
                 unsupported_version: version,
             }));
@@ -87,7 +62,7 @@ impl<'a> DltPacketSlice<'a> {
             unsafe { [*slice.get_unchecked(2), *slice.get_unchecked(3)] },
         ) as usize;
 
-        if slice.len() < length {
+        if slice.len() < length && false == config.allow_truncated_packets {
             return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
                 layer: error::Layer::DltHeader,
                 minimum_size: length,
@@ -135,14 +110,104 @@ impl<'a> DltPacketSlice<'a> {
             }));
         }
 
+        // truncated packets (only allowed via the config) still need
+        // a complete header
+        let length = if slice.len() < length {
+            if slice.len() < header_len {
+                return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                    layer: error::Layer::DltHeader,
+                    minimum_size: header_len,
+                    actual_size: slice.len(),
+                }));
+            }
+            slice.len()
+        } else {
+            length
+        };
+
         //looks ok -> create the DltPacketSlice
-        Ok(DltPacketSlice {
+        let result = DltPacketSlice {
             // SAFETY:
             // Safe as it is checked beforehand that the slice
             // has at least length bytes.
             slice: unsafe { from_raw_parts(slice.as_ptr(), length) },
             header_len,
-        })
+        };
+
+        if false == config.allow_reserved_message_info {
+            if let Some(ext) = result.extended_header() {
+                if ext.has_reserved_bits_set() {
+                    return Err(ReservedMessageInfo(ext.message_info.0));
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Read the dlt header and create a slice containing the dlt header &
+    /// payload. The bytes after the packet are returned as second value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::DltPacketSlice;
+    ///
+    /// let data = [
+    ///     0b0010_0000, 0, 0, 4, // packet (header only)
+    ///     1, 2, // data after the packet
+    /// ];
+    /// let (packet, rest) = DltPacketSlice::from_slice_with_rest(&data).unwrap();
+    /// assert_eq!(&data[..4], packet.slice());
+    /// assert_eq!(&[1, 2], rest);
+    /// ```
+    pub fn from_slice_with_rest(
+        slice: &'a [u8],
+    ) -> Result<(DltPacketSlice<'a>, &'a [u8]), error::PacketSliceError> {
+        let packet = DltPacketSlice::from_slice(slice)?;
+        // SAFETY:
+        // Safe as the packet slice is a prefix of the given slice.
+        let rest = unsafe {
+            from_raw_parts(
+                slice.as_ptr().add(packet.slice.len()),
+                slice.len() - packet.slice.len(),
+            )
+        };
+        Ok((packet, rest))
+    }
+
+    /// Read the dlt header and create a slice containing the dlt header &
+    /// payload, additionally rejecting reserved values in the message info
+    /// of the extended header (see [`DltExtendedHeader::has_reserved_bits_set`]).
+    ///
+    /// [`DltPacketSlice::from_slice`] is lenient & ignores reserved values
+    /// (in that case [`DltPacketSlice::message_type`] returns `None`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::{DltPacketSlice, error::PacketSliceError};
+    ///
+    /// let data = [
+    ///     0b0010_0001, 0, 0, 14, // header with extended header flag
+    ///     0b0000_1000, 0, // message info with a reserved message type
+    ///     b'a', b'p', b'p', b'1', b'c', b't', b'x', b'1',
+    /// ];
+    /// assert!(DltPacketSlice::from_slice(&data).is_ok());
+    /// assert_eq!(
+    ///     Err(PacketSliceError::ReservedMessageInfo(0b0000_1000)),
+    ///     DltPacketSlice::from_slice_strict(&data)
+    /// );
+    /// ```
+    pub fn from_slice_strict(
+        slice: &'a [u8],
+    ) -> Result<DltPacketSlice<'a>, error::PacketSliceError> {
+        let packet = DltPacketSlice::from_slice(slice)?;
+        match packet.extended_header() {
+            Some(ext) if ext.has_reserved_bits_set() => Err(
+                error::PacketSliceError::ReservedMessageInfo(ext.message_info.0),
+            ),
+            _ => Ok(packet),
+        }
     }
 
     ///Returns if an extended header is present.
@@ -177,6 +242,87 @@ impl<'a> DltPacketSlice<'a> {
         }
     }
 
+    ///Returns the ecu id if present (without decoding the complete header).
+    #[inline]
+    pub fn ecu_id(&self) -> Option<[u8; 4]> {
+        // SAFETY:
+        // Safe as it is checked in from_slice that the slice
+        // has at least a length of 4 bytes.
+        if 0 != unsafe { self.slice.get_unchecked(0) } & ECU_ID_FLAG {
+            // SAFETY:
+            // Safe as it is checked in from_slice that the slice
+            // has the length to contain the standard header based
+            // on the flags contained in the standard header.
+            Some(unsafe {
+                [
+                    *self.slice.get_unchecked(4),
+                    *self.slice.get_unchecked(5),
+                    *self.slice.get_unchecked(6),
+                    *self.slice.get_unchecked(7),
+                ]
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the byte offsets of the header fields relative to the
+    /// start of the packet (`None` for fields not present).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::{DltHeader, DltPacketSlice};
+    ///
+    /// let mut header = DltHeader {
+    ///     is_big_endian: true,
+    ///     message_counter: 0,
+    ///     length: 0,
+    ///     ecu_id: Some(*b"ECU1"),
+    ///     session_id: None,
+    ///     timestamp: Some(1234),
+    ///     extended_header: None,
+    /// };
+    /// header.length = header.header_len();
+    /// let bytes = header.to_bytes();
+    ///
+    /// let offsets = DltPacketSlice::from_slice(&bytes).unwrap().field_offsets();
+    /// assert_eq!(Some(4), offsets.ecu_id);
+    /// assert_eq!(None, offsets.session_id);
+    /// assert_eq!(Some(8), offsets.timestamp);
+    /// assert_eq!(12, offsets.payload);
+    /// ```
+    pub fn field_offsets(&self) -> HeaderOffsets {
+        // SAFETY:
+        // Safe as it is checked in from_slice that the slice
+        // has at least a length of 4 bytes.
+        let header_type = unsafe { *self.slice.get_unchecked(0) };
+        let mut offset = 4;
+        let mut next = |flag: u8, len: usize| {
+            if 0 != header_type & flag {
+                let result = offset;
+                offset += len;
+                Some(result)
+            } else {
+                None
+            }
+        };
+        let ecu_id = next(ECU_ID_FLAG, 4);
+        let session_id = next(SESSION_ID_FLAG, 4);
+        let timestamp = next(TIMESTAMP_FLAG, 4);
+        let extended_header = next(EXTDENDED_HEADER_FLAG, 10);
+        HeaderOffsets {
+            header_type: 0,
+            message_counter: 1,
+            length: 2,
+            ecu_id,
+            session_id,
+            timestamp,
+            extended_header,
+            payload: self.header_len,
+        }
+    }
+
     ///Returns the dlt extended header if present
     #[inline]
     pub fn extended_header(&self) -> Option<DltExtendedHeader> {
@@ -252,11 +398,96 @@ impl<'a> DltPacketSlice<'a> {
     }
 
     ///Returns the slice containing the dlt header + payload.
+    ///
+    ///The slice contains the exact bytes the packet was parsed from
+    ///(no normalization of any kind is applied).
     #[inline]
     pub fn slice(&self) -> &'a [u8] {
         self.slice
     }
 
+    ///Returns the exact on-wire bytes of the dlt packet (header + payload).
+    ///
+    ///Same as [`DltPacketSlice::slice`], intended for passing through
+    ///packets unmodified (e.g. when re-writing only some messages of a
+    ///capture).
+    #[inline]
+    pub fn raw_bytes(&self) -> &'a [u8] {
+        self.slice
+    }
+
+    ///Returns a fingerprint of the payload (64 bit FNV-1a hash).
+    ///
+    ///The fingerprint only depends on the payload bytes and is stable
+    ///across program runs & platforms.
+    #[inline]
+    pub fn payload_fingerprint(&self) -> u64 {
+        fnv1a_64(self.payload())
+    }
+
+    ///Returns a rough estimate of the size in bytes the payload would
+    ///have after compression.
+    ///
+    ///The estimate is based on the Shannon entropy of the byte histogram
+    ///of the payload (entropy in bits per byte times the payload length).
+    ///It is only a cheap heuristic & not the result of an actual
+    ///compression: repeating multi byte patterns (which real compressors
+    ///exploit) are not taken into account & compression overhead is
+    ///ignored. A result close to the payload length indicates data that
+    ///will barely compress.
+    ///
+    ///# Example
+    ///
+    ///```
+    ///use dlt_parse::{DltHeader, DltPacketSlice};
+    ///
+    ///let mut header = DltHeader::new_non_verbose(*b"ECU1");
+    ///header.length = header.header_len() + 64;
+    ///let mut buffer = header.to_bytes().to_vec();
+    ///buffer.extend_from_slice(&[0xAA; 64]);
+    ///
+    ///let packet = DltPacketSlice::from_slice(&buffer).unwrap();
+    ///// only a single distinct byte value -> compresses very well
+    ///assert_eq!(0, packet.estimate_compressed_size());
+    ///```
+    #[cfg(feature = "std")]
+    pub fn estimate_compressed_size(&self) -> usize {
+        let payload = self.payload();
+        if payload.is_empty() {
+            return 0;
+        }
+        let mut histogram = [0usize; 256];
+        for b in payload {
+            histogram[usize::from(*b)] += 1;
+        }
+        // entropy * len = len * log2(len) - sum(count * log2(count))
+        let len = payload.len() as f64;
+        let bits = histogram
+            .iter()
+            .filter(|c| **c > 0)
+            .fold(len * len.log2(), |acc, c| {
+                let c = *c as f64;
+                acc - c * c.log2()
+            });
+        ((bits.max(0.0) / 8.0).ceil() as usize).min(payload.len())
+    }
+
+    ///Returns a key identifying the content of the message.
+    ///
+    ///The key is made up of the application id, context id, message info
+    ///and the payload fingerprint. Message counter, session id, ecu id &
+    ///timestamp are intentionally excluded so the key can be used to detect
+    ///duplicate messages in differing captures (e.g. via a `HashSet`).
+    pub fn content_key(&self) -> ContentKey {
+        let ext = self.extended_header();
+        ContentKey {
+            application_id: ext.as_ref().map(|e| e.application_id),
+            context_id: ext.as_ref().map(|e| e.context_id),
+            message_info: ext.as_ref().map(|e| e.message_info),
+            payload_fingerprint: self.payload_fingerprint(),
+        }
+    }
+
     ///Returns a slice containing the payload of the dlt message
     #[inline]
     pub fn payload(&self) -> &'a [u8] {
@@ -326,6 +557,85 @@ impl<'a> DltPacketSlice<'a> {
         }
     }
 
+    /// Returns true if the message carries no content besides its headers.
+    ///
+    /// This is the case if the payload is empty or if the message is a non
+    /// verbose message whose payload consists only of the message id.
+    #[inline]
+    pub fn is_empty_payload(&self) -> bool {
+        let payload_len = self.slice.len() - self.header_len;
+        payload_len == 0 || (false == self.is_verbose() && payload_len == 4)
+    }
+
+    /// Returns true if the message is a keep-alive message as emitted by
+    /// some loggers to signal that the connection is still alive.
+    ///
+    /// A message is treated as keep-alive if it is a control time message
+    /// (sent periodically by the dlt-daemon if timing packets are enabled,
+    /// independent of the payload) or if it is not a verbose message
+    /// and its payload is either empty or exactly 4 bytes long and
+    /// contains only a null message id (`0x00000000`). Non verbose
+    /// messages with a non zero message id but no further data (e.g. the
+    /// "get local time" control request) are not keep-alive messages,
+    /// use [`DltPacketSlice::is_empty_payload`] to detect those.
+    ///
+    /// Only the extended header & at most the first 4 bytes of the payload
+    /// are inspected.
+    #[inline]
+    pub fn is_keepalive(&self) -> bool {
+        (false == self.is_verbose() && matches!(self.payload(), [] | [0, 0, 0, 0]))
+            || self.message_type() == Some(DltMessageType::Control(DltControlMessageType::Time))
+    }
+
+    /// Decodes the non verbose message with the decoders registered in the
+    /// given registry (see [`NonVerboseRegistry::decode`]).
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn decode_with(&self, registry: &NonVerboseRegistry) -> Option<NonVerboseDecoded<'a>> {
+        registry.decode(self)
+    }
+
+    /// Decodes the non verbose message with the format returned by
+    /// `formats` for the message id & returns the formatted text (see
+    /// [`FormatSpec::format_payload`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::{DltHeader, DltPacketSlice, FormatArgType, FormatSpec};
+    ///
+    /// let spec = FormatSpec {
+    ///     template: "temperature {} C".to_string(),
+    ///     arg_types: vec![FormatArgType::I8],
+    /// };
+    ///
+    /// let mut header = DltHeader::new_non_verbose(*b"ECU1");
+    /// header.length += 5;
+    /// let mut data = header.to_bytes().to_vec();
+    /// data.extend_from_slice(&[0x20, 0, 0, 0, 0xfb]); // message id & argument (little endian)
+    ///
+    /// let packet = DltPacketSlice::from_slice(&data).unwrap();
+    /// let text = packet
+    ///     .decode_non_verbose_with_format(&|msg_id| (msg_id == 0x20).then(|| &spec))
+    ///     .unwrap();
+    /// assert_eq!("temperature -5 C", text);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn decode_non_verbose_with_format<'f>(
+        &self,
+        formats: &dyn Fn(u32) -> Option<&'f FormatSpec>,
+    ) -> Result<std::string::String, error::NonVerboseFormatError> {
+        use error::NonVerboseFormatError::*;
+
+        if self.is_verbose() {
+            return Err(VerboseMessage);
+        }
+        let payload = NonVerbosePayload::from_packet(self).ok_or(MissingMessageId)?;
+        formats(payload.msg_id)
+            .ok_or(UnknownMessageId(payload.msg_id))?
+            .format_payload(&payload, self.is_big_endian())
+    }
+
     /// Returns a iterator over the verbose values (if the dlt message is a verbose message).
     pub fn verbose_value_iter(&self) -> Option<VerboseIter<'a>> {
         // verbose messages are required to have an extended header
@@ -358,6 +668,103 @@ impl<'a> DltPacketSlice<'a> {
         }
     }
 
+    /// Returns the raw bytes (type info & data) of the verbose argument at
+    /// the given index.
+    ///
+    /// `None` is returned if the message is not a verbose message, if the
+    /// index is outside of the number of arguments or if an argument before
+    /// or at the index can not be decoded.
+    pub fn verbose_arg_bytes(&self, index: usize) -> Option<&'a [u8]> {
+        let mut iter = self.verbose_value_iter()?;
+        for _ in 0..index {
+            iter.next()?.ok()?;
+        }
+        let start = iter.raw();
+        iter.next()?.ok()?;
+        Some(&start[..start.len() - iter.raw().len()])
+    }
+
+    /// Returns up to `max_len` bytes from the start of the payload
+    /// (including the message id for non verbose messages).
+    #[inline]
+    pub fn payload_preview(&self, max_len: usize) -> &'a [u8] {
+        let payload = self.payload();
+        &payload[..payload.len().min(max_len)]
+    }
+
+    /// Returns the string arguments of a verbose message separated by
+    /// spaces & limited to `max_chars` characters (e.g. for building a
+    /// full-text search index).
+    ///
+    /// Non string arguments are skipped. The preview is cut at the
+    /// character limit even in the middle of an argument & stops at the
+    /// first argument that can not be decoded. `None` is returned if the
+    /// message is not a verbose message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::{build_log_message, DltLogLevel, DltPacketSlice};
+    ///
+    /// let mut buffer = Vec::new();
+    /// build_log_message(
+    ///     *b"APP1",
+    ///     *b"CTX1",
+    ///     DltLogLevel::Info,
+    ///     format_args!("Hello world"),
+    ///     &mut buffer,
+    /// )
+    /// .unwrap();
+    ///
+    /// let packet = DltPacketSlice::from_slice(&buffer).unwrap();
+    /// assert_eq!(Some("Hello".to_string()), packet.verbose_text_preview(5));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn verbose_text_preview(&self, max_chars: usize) -> Option<std::string::String> {
+        use crate::verbose::VerboseValue;
+
+        let mut result = std::string::String::new();
+        let mut remaining = max_chars;
+        let mut is_first = true;
+        for value in self.verbose_value_iter()? {
+            let text = match value {
+                Ok(VerboseValue::Str(v)) => v.value,
+                Ok(_) => continue,
+                Err(_) => break,
+            };
+            if false == is_first {
+                if remaining == 0 {
+                    break;
+                }
+                result.push(' ');
+                remaining -= 1;
+            }
+            is_first = false;
+            for c in text.chars() {
+                if remaining == 0 {
+                    return Some(result);
+                }
+                result.push(c);
+                remaining -= 1;
+            }
+        }
+        Some(result)
+    }
+
+    /// Returns the service id of a non verbose control request or response
+    /// (`None` if the message is not a control request or response or is a
+    /// verbose message or too short to contain a service id).
+    pub fn control_service_id(&self) -> Option<control::DltServiceId> {
+        match self.message_type() {
+            Some(DltMessageType::Control(
+                DltControlMessageType::Request | DltControlMessageType::Response,
+            )) => self
+                .message_id_and_payload()
+                .map(|(id, _)| control::DltServiceId::from_u32(id)),
+            _ => None,
+        }
+    }
+
     /// Returns the verbose or non verbose payload of the given dlt message (if it has one).
     #[inline]
     pub fn typed_payload(&self) -> Option<DltTypedPayload<'a>> {
@@ -373,6 +780,13 @@ impl<'a> DltPacketSlice<'a> {
             // check if the verbose flag is set (aka check that this is a verbose dlt message)
             // SAFETY:
             let message_info = DltMessageInfo(unsafe { *ext_slice.get_unchecked(0) });
+            if let Some(DltMessageType::Control(DltControlMessageType::Time)) =
+                message_info.into_message_type()
+            {
+                return Some(DltTypedPayload::ControlTime(ControlTimePayload {
+                    payload: self.payload(),
+                }));
+            }
             // Safe as the ext_slice is at 10.
             if message_info.is_verbose() {
                 // SAFETY:
@@ -498,7 +912,7 @@ impl<'a> DltPacketSlice<'a> {
                             payload: non_verbose_payload,
                         }));
                     }
-                    None => {
+                    Some(DltMessageType::Control(DltControlMessageType::Time)) | None => {
                         return Some(DltTypedPayload::GenericNv(GenericNvPayload {
                             info: message_info,
                             msg_id: message_id,
@@ -511,8 +925,42 @@ impl<'a> DltPacketSlice<'a> {
         None
     }
 
+    ///Deserialize the dlt header with every field offset checked against
+    ///the length of the packet slice.
+    ///
+    ///Returns an error instead of reading out of bounds if the header
+    ///type flags imply a header larger than the packet. For slices
+    ///created via [`DltPacketSlice::from_slice`] this always succeeds &
+    ///returns the same value as [`DltPacketSlice::header`]. The error
+    ///converts into [`error::ReadError`] via `?`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::DltPacketSlice;
+    ///
+    /// let data = [0b0010_0001, 0, 0, 14, 0x41, 0, b'A', b'P', b'P', b'1', b'C', b'T', b'X', b'1'];
+    /// let packet = DltPacketSlice::from_slice(&data).unwrap();
+    /// assert_eq!(Ok(packet.header()), packet.try_header());
+    /// ```
+    pub fn try_header(&self) -> Result<DltHeader, error::PacketSliceError> {
+        DltHeader::from_slice(self.slice)
+    }
+
     ///Deserialize the dlt header
+    ///
+    ///The header fields are read without bounds checks. This relies on
+    ///every constructor (e.g. [`DltPacketSlice::from_slice`] &
+    ///[`DltPacketSlice::from_slice_with_rest`]) rejecting packets where the
+    ///`length` field is smaller than the header size implied by the
+    ///header type flags or where the given slice is shorter than the
+    ///header. Truncated packets accepted via
+    ///[`ParseConfig::allow_truncated_packets`] still contain the complete
+    ///header. Use [`DltPacketSlice::try_header`] if every field read
+    ///should be checked against the slice length.
     pub fn header(&self) -> DltHeader {
+        debug_assert!(self.header_len <= self.slice.len());
+
         // SAFETY:
         // Safe as it is checked in from_slice that the slice
         // has at least a length of 4 bytes.
@@ -658,33 +1106,26 @@ impl<'a> DltPacketSlice<'a> {
     }
 }
 
+/// Returns the typed payload of a non verbose control response (`None` if
+/// the payload after the service id does not contain the status byte).
 fn determine_dlt_typed_playload_for_non_verbose_response(
     non_verbose_payload: &[u8],
     service_id: u32,
 ) -> Option<DltTypedPayload<'_>> {
-    if non_verbose_payload.len() > 5 {
-        match service_id {
-            0..=2 => {
-                return Some(DltTypedPayload::ControlNv(ControlNvPayload {
-                    msg_type: DltControlMessageType::Response,
-                    payload: non_verbose_payload,
-                    service_id,
-                }))
-            }
-            _ => {
-                return Some(DltTypedPayload::ControlNv(ControlNvPayload {
-                    msg_type: DltControlMessageType::Request,
-                    payload: non_verbose_payload,
-                    service_id,
-                }))
-            }
-        };
+    if non_verbose_payload.is_empty() {
+        None
+    } else {
+        Some(DltTypedPayload::ControlNv(ControlNvPayload {
+            msg_type: DltControlMessageType::Response,
+            payload: non_verbose_payload,
+            service_id,
+        }))
     }
-    None
 }
 
 /// Tests for `DltPacketSlice` methods
 #[cfg(test)]
+#[allow(clippy::field_reassign_with_default)]
 mod tests {
 
     use super::*;
@@ -779,6 +1220,142 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn field_offsets(ref packet in dlt_header_with_payload_any()) {
+            let mut buffer = Vec::with_capacity(usize::from(packet.0.length));
+            buffer.extend_from_slice(&packet.0.to_bytes());
+            buffer.extend_from_slice(&packet.1);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            let offsets = slice.field_offsets();
+
+            assert_eq!(0, offsets.header_type);
+            assert_eq!(1, offsets.message_counter);
+            assert_eq!(2, offsets.length);
+            assert_eq!(usize::from(packet.0.header_len()), offsets.payload);
+            assert_eq!(packet.1.len(), buffer.len() - offsets.payload);
+
+            let read_id = |offset: usize| {
+                let mut id = [0u8; 4];
+                id.copy_from_slice(&buffer[offset..offset + 4]);
+                id
+            };
+            assert_eq!(packet.0.ecu_id, offsets.ecu_id.map(read_id));
+            assert_eq!(packet.0.session_id, offsets.session_id.map(|o| u32::from_be_bytes(read_id(o))));
+            assert_eq!(packet.0.timestamp, offsets.timestamp.map(|o| u32::from_be_bytes(read_id(o))));
+            assert_eq!(
+                packet.0.extended_header.as_ref().map(|e| (e.application_id, e.context_id)),
+                offsets.extended_header.map(|o| (read_id(o + 2), read_id(o + 6)))
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn from_slice_with_rest(
+            ref packet in dlt_header_with_payload_any(),
+            ref trailing in prop::collection::vec(any::<u8>(), 0..10),
+        ) {
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&packet.0.to_bytes());
+            buffer.extend_from_slice(&packet.1[..]);
+            let packet_len = buffer.len();
+            buffer.extend_from_slice(&trailing[..]);
+
+            let (slice, rest) = DltPacketSlice::from_slice_with_rest(&buffer[..]).unwrap();
+            prop_assert_eq!(DltPacketSlice::from_slice(&buffer[..]).unwrap(), slice.clone());
+            prop_assert_eq!(&buffer[..packet_len], slice.slice());
+            prop_assert_eq!(&trailing[..], rest);
+
+            // errors are the same as in from_slice
+            for len in 0..packet_len {
+                prop_assert_eq!(
+                    DltPacketSlice::from_slice(&buffer[..len]).unwrap_err(),
+                    DltPacketSlice::from_slice_with_rest(&buffer[..len]).unwrap_err()
+                );
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn from_slice_strict(
+            ref packet in dlt_header_with_payload_any(),
+            message_info in any::<u8>(),
+        ) {
+            let mut header = packet.0.clone();
+            if let Some(ext) = header.extended_header.as_mut() {
+                ext.message_info = DltMessageInfo(message_info);
+            }
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&header.to_bytes());
+            buffer.extend_from_slice(&packet.1[..]);
+
+            let lenient = DltPacketSlice::from_slice(&buffer[..]).unwrap();
+            match header.extended_header.as_ref() {
+                Some(ext) if ext.has_reserved_bits_set() => {
+                    prop_assert_eq!(
+                        Err(error::PacketSliceError::ReservedMessageInfo(message_info)),
+                        DltPacketSlice::from_slice_strict(&buffer[..])
+                    );
+                }
+                _ => {
+                    prop_assert_eq!(Ok(lenient), DltPacketSlice::from_slice_strict(&buffer[..]));
+                }
+            }
+
+            // length errors are the same as in from_slice
+            for len in 0..buffer.len() {
+                prop_assert_eq!(
+                    DltPacketSlice::from_slice(&buffer[..len]).unwrap_err(),
+                    DltPacketSlice::from_slice_strict(&buffer[..len]).unwrap_err()
+                );
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn raw_bytes(
+            ref packet in dlt_header_with_payload_any(),
+            version in 0..=1u8,
+            ref trailing in prop::collection::vec(any::<u8>(), 0..10),
+        ) {
+            let mut buffer = Vec::with_capacity(
+                packet.1.len() + usize::from(packet.0.header_len()) + trailing.len()
+            );
+            buffer.extend_from_slice(&{
+                let mut bytes = packet.0.to_bytes();
+                bytes[0] = (bytes[0] & 0b0001_1111) | ((version << 5) & 0b1110_0000);
+                bytes
+            });
+            buffer.extend_from_slice(&packet.1[..]);
+            let packet_len = buffer.len();
+            buffer.extend_from_slice(&trailing[..]);
+
+            let slice = DltPacketSlice::from_slice(&buffer[..]).unwrap();
+            prop_assert_eq!(slice.raw_bytes(), &buffer[..packet_len]);
+            prop_assert_eq!(slice.raw_bytes(), slice.slice());
+            // no copy is made
+            prop_assert_eq!(slice.raw_bytes().as_ptr(), buffer.as_ptr());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn ecu_id(ref packet in dlt_header_with_payload_any()) {
+            let mut buffer = Vec::with_capacity(
+                packet.1.len() + usize::from(packet.0.header_len())
+            );
+            buffer.extend_from_slice(&packet.0.to_bytes());
+            buffer.extend_from_slice(&packet.1[..]);
+
+            let slice = DltPacketSlice::from_slice(&buffer[..]).unwrap();
+            prop_assert_eq!(packet.0.ecu_id, slice.ecu_id());
+            prop_assert_eq!(slice.header().ecu_id, slice.ecu_id());
+        }
+    }
+
     #[test]
     fn from_slice_header_len_eof_errors() {
         use error::{PacketSliceError::*, *};
@@ -809,6 +1386,98 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn try_header(ref packet in dlt_header_with_payload_any()) {
+            let mut buffer = Vec::with_capacity(usize::from(packet.0.length));
+            buffer.extend_from_slice(&packet.0.to_bytes());
+            buffer.extend_from_slice(&packet.1);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            prop_assert_eq!(Ok(packet.0.clone()), slice.try_header());
+            prop_assert_eq!(Ok(slice.header()), slice.try_header());
+
+            // slices not fulfilling the from_slice invariants
+            let header_len = usize::from(packet.0.header_len());
+            for len in 0..header_len {
+                let invalid = DltPacketSlice {
+                    slice: &buffer[..len],
+                    header_len,
+                };
+                prop_assert!(invalid.try_header().is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn from_slice_crafted_length() {
+        use error::{PacketSliceError::*, *};
+
+        // all optional header flags set (header size 26 bytes)
+        const ALL_FLAGS: u8 =
+            ECU_ID_FLAG | SESSION_ID_FLAG | TIMESTAMP_FLAG | EXTDENDED_HEADER_FLAG | 0b0010_0000;
+
+        // length field smaller then the header size implied by the flags
+        for length in 0..26u16 {
+            let mut buffer = [0u8; 30];
+            buffer[0] = ALL_FLAGS;
+            buffer[2..4].copy_from_slice(&length.to_be_bytes());
+            let result = DltPacketSlice::from_slice(&buffer);
+            if length < 4 {
+                assert!(result.is_err());
+            } else {
+                assert_eq!(
+                    Err(MessageLengthTooSmall(DltMessageLengthTooSmallError {
+                        required_length: 26,
+                        actual_length: usize::from(length),
+                    })),
+                    result
+                );
+            }
+        }
+
+        // length field larger then the slice (extended header outside of the slice)
+        for slice_len in 4..30 {
+            let mut buffer = [0u8; 30];
+            buffer[0] = ALL_FLAGS;
+            buffer[2..4].copy_from_slice(&30u16.to_be_bytes());
+            assert_eq!(
+                Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                    layer: error::Layer::DltHeader,
+                    minimum_size: 30,
+                    actual_size: slice_len,
+                })),
+                DltPacketSlice::from_slice(&buffer[..slice_len])
+            );
+        }
+
+        // header fills the complete length
+        let mut buffer = [0u8; 30];
+        buffer[0] = ALL_FLAGS;
+        buffer[2..4].copy_from_slice(&26u16.to_be_bytes());
+        let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+        assert_eq!(26, slice.slice().len());
+        assert_eq!(26, slice.header().header_len());
+        assert!(slice.payload().is_empty());
+
+        // the remaining constructors & truncated packets keep the invariant
+        let mut buffer = [0u8; 30];
+        buffer[0] = ALL_FLAGS;
+        buffer[2..4].copy_from_slice(&30u16.to_be_bytes());
+        for slice_len in 4..30 {
+            let data = &buffer[..slice_len];
+            assert!(DltPacketSlice::from_slice_with_rest(data).is_err());
+            assert!(DltPacketSlice::from_slice_strict(data).is_err());
+            let result = DltPacketSlice::from_slice_with_config(data, &ParseConfig::lenient());
+            if slice_len < 26 {
+                assert!(result.is_err());
+            } else {
+                let slice = result.unwrap();
+                assert_eq!(slice_len, slice.slice().len());
+                assert_eq!(26, slice.header().header_len());
+            }
+        }
+    }
+
     proptest! {
         #[test]
         fn from_slice_version_errors(
@@ -856,6 +1525,536 @@ mod tests {
         }
     }
 
+    #[test]
+    fn control_service_id() {
+        use crate::control::DltServiceId;
+
+        let build = |message_type: Option<DltMessageType>, is_verbose: bool, payload: &[u8]| {
+            let mut header: DltHeader = Default::default();
+            header.is_big_endian = true;
+            header.extended_header = message_type.map(|t| {
+                let mut ext = DltExtendedHeader::new_non_verbose(t, [0; 4], [0; 4]).unwrap();
+                ext.set_is_verbose(is_verbose);
+                ext
+            });
+            header.length = header.header_len() + payload.len() as u16;
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&header.to_bytes());
+            buffer.extend_from_slice(payload);
+            buffer
+        };
+
+        for msg_type in [
+            DltControlMessageType::Request,
+            DltControlMessageType::Response,
+        ] {
+            let control = Some(DltMessageType::Control(msg_type));
+            for (payload, expected) in [
+                (&[0, 0, 0, 0x13][..], Some(DltServiceId::GetSoftwareVersion)),
+                (
+                    &[0, 0, 0x0f, 0xff, 1][..],
+                    Some(DltServiceId::CallSwcInjection(0xfff)),
+                ),
+                (&[0, 0, 0, 0x30][..], Some(DltServiceId::Unknown(0x30))),
+                (&[0, 0, 0][..], None),
+            ] {
+                let buffer = build(control, false, payload);
+                let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+                assert_eq!(expected, slice.control_service_id());
+            }
+
+            // verbose control message
+            let buffer = build(control, true, &[0, 0, 0, 0x13]);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(None, slice.control_service_id());
+        }
+
+        // non control messages
+        for message_type in [None, Some(DltMessageType::Log(DltLogLevel::Info))] {
+            let buffer = build(message_type, false, &[0, 0, 0, 0x13]);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(None, slice.control_service_id());
+        }
+    }
+
+    #[test]
+    fn verbose_arg_bytes() {
+        use crate::verbose::{U16Value, U32Value};
+
+        let first = U16Value {
+            variable_info: None,
+            scaling: None,
+            value: 0x1234,
+        };
+        let second = U32Value {
+            variable_info: None,
+            scaling: None,
+            value: 0x5678_9abc,
+        };
+        for is_big_endian in [false, true] {
+            let mut payload = ArrayVec::<u8, 100>::new();
+            first.add_to_msg(&mut payload, is_big_endian).unwrap();
+            let first_len = payload.len();
+            second.add_to_msg(&mut payload, is_big_endian).unwrap();
+
+            let header = {
+                let mut header: DltHeader = Default::default();
+                header.is_big_endian = is_big_endian;
+                header.extended_header = Some({
+                    let mut ext: DltExtendedHeader = Default::default();
+                    ext.set_is_verbose(true);
+                    ext.number_of_arguments = 2;
+                    ext
+                });
+                header.length = header.header_len() + payload.len() as u16;
+                header
+            };
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&header.to_bytes());
+            buffer.extend_from_slice(&payload);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+
+            assert_eq!(Some(&payload[..first_len]), slice.verbose_arg_bytes(0));
+            assert_eq!(Some(&payload[first_len..]), slice.verbose_arg_bytes(1));
+            assert_eq!(None, slice.verbose_arg_bytes(2));
+        }
+
+        // more arguments announced then present
+        {
+            let mut payload = ArrayVec::<u8, 100>::new();
+            first.add_to_msg(&mut payload, false).unwrap();
+            let header = {
+                let mut header: DltHeader = Default::default();
+                header.extended_header = Some({
+                    let mut ext: DltExtendedHeader = Default::default();
+                    ext.set_is_verbose(true);
+                    ext.number_of_arguments = 2;
+                    ext
+                });
+                header.length = header.header_len() + payload.len() as u16;
+                header
+            };
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&header.to_bytes());
+            buffer.extend_from_slice(&payload);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+
+            assert_eq!(Some(&payload[..]), slice.verbose_arg_bytes(0));
+            assert_eq!(None, slice.verbose_arg_bytes(1));
+        }
+
+        // non verbose
+        {
+            let mut header: DltHeader = Default::default();
+            header.length = header.header_len() + 4;
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&header.to_bytes());
+            buffer.extend_from_slice(&[0, 0, 0, 0]);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(None, slice.verbose_arg_bytes(0));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn payload_preview(
+            ref packet in dlt_header_with_payload_any(),
+            max_len in 0usize..1000,
+        ) {
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&packet.0.to_bytes());
+            buffer.extend_from_slice(&packet.1);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            let expected_len = packet.1.len().min(max_len);
+            prop_assert_eq!(&packet.1[..expected_len], slice.payload_preview(max_len));
+        }
+    }
+
+    #[test]
+    fn from_slice_recoverable_with_more_data() {
+        let mut header =
+            DltHeader::new_non_verbose_log(*b"ECU1", *b"APP1", *b"CTX1", DltLogLevel::Info);
+        header.length = header.header_len() + 6;
+        let mut data = header.to_bytes().to_vec();
+        data.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+
+        // truncated valid packet
+        for len in 0..data.len() {
+            let err = DltPacketSlice::from_slice(&data[..len]).unwrap_err();
+            assert!(err.is_recoverable_with_more_data());
+            let needed = err.additional_bytes_needed().unwrap();
+            if len < 4 {
+                // only the minimum header size is known
+                assert_eq!(4 - len, needed);
+            } else {
+                assert_eq!(data.len() - len, needed);
+            }
+        }
+        assert!(DltPacketSlice::from_slice(&data).is_ok());
+
+        // corrupt header (unsupported version)
+        let mut corrupt = data.clone();
+        corrupt[0] |= 0b1110_0000;
+        for len in 4..=corrupt.len() {
+            let err = DltPacketSlice::from_slice(&corrupt[..len]).unwrap_err();
+            assert!(false == err.is_recoverable_with_more_data());
+            assert_eq!(None, err.additional_bytes_needed());
+        }
+
+        // corrupt header (length smaller then the header)
+        let mut corrupt = data.clone();
+        corrupt[3] = 8;
+        let err = DltPacketSlice::from_slice(&corrupt).unwrap_err();
+        assert!(false == err.is_recoverable_with_more_data());
+        assert_eq!(None, err.additional_bytes_needed());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn decode_non_verbose_with_format() {
+        use crate::error::NonVerboseFormatError;
+        use alloc::string::ToString;
+
+        let build = |mut header: DltHeader, payload: &[u8]| {
+            header.length = header.header_len() + payload.len() as u16;
+            let mut buffer = header.to_bytes().to_vec();
+            buffer.extend_from_slice(payload);
+            buffer
+        };
+        let spec = FormatSpec {
+            template: "{} + {}".to_string(),
+            arg_types: vec![FormatArgType::U8, FormatArgType::I16],
+        };
+        let formats = |msg_id| if msg_id == 0x20 { Some(&spec) } else { None };
+
+        for (is_big_endian, payload) in [
+            (false, [0x20, 0, 0, 0, 1, 0xfe, 0xff]),
+            (true, [0, 0, 0, 0x20, 1, 0xff, 0xfe]),
+        ] {
+            let mut header =
+                DltHeader::new_non_verbose_log(*b"ECU1", *b"APP1", *b"CTX1", DltLogLevel::Info);
+            header.is_big_endian = is_big_endian;
+            let buffer = build(header.clone(), &payload);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(
+                Ok("1 + -2".to_string()),
+                slice.decode_non_verbose_with_format(&formats)
+            );
+
+            // argument missing
+            let buffer = build(header.clone(), &payload[..6]);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(
+                Err(NonVerboseFormatError::InvalidArgument {
+                    msg_id: 0x20,
+                    index: 1,
+                    arg_type: FormatArgType::I16,
+                }),
+                slice.decode_non_verbose_with_format(&formats)
+            );
+
+            // unknown message id
+            let buffer = build(header.clone(), &[0x21, 0x21, 0x21, 0x21]);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(
+                Err(NonVerboseFormatError::UnknownMessageId(0x2121_2121)),
+                slice.decode_non_verbose_with_format(&formats)
+            );
+
+            // payload too short for a message id
+            let buffer = build(header, &payload[..3]);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(
+                Err(NonVerboseFormatError::MissingMessageId),
+                slice.decode_non_verbose_with_format(&formats)
+            );
+        }
+
+        // verbose message
+        let buffer = build(
+            DltHeader::new_verbose_log(*b"ECU1", *b"APP1", *b"CTX1", DltLogLevel::Info),
+            &[0x20, 0, 0, 0, 1, 0, 0],
+        );
+        let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+        assert_eq!(
+            Err(NonVerboseFormatError::VerboseMessage),
+            slice.decode_non_verbose_with_format(&formats)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn verbose_text_preview() {
+        use crate::verbose::{StringValue, U16Value, VerboseValue};
+        use alloc::string::ToString;
+
+        let build = |values: &[VerboseValue<'_>], number_of_arguments: u8| {
+            let mut payload = ArrayVec::<u8, 100>::new();
+            for v in values {
+                v.add_to_msg(&mut payload, false).unwrap();
+            }
+            let mut header: DltHeader = Default::default();
+            header.extended_header = Some({
+                let mut ext: DltExtendedHeader = Default::default();
+                ext.set_is_verbose(true);
+                ext.number_of_arguments = number_of_arguments;
+                ext
+            });
+            header.length = header.header_len() + payload.len() as u16;
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&header.to_bytes());
+            buffer.extend_from_slice(&payload);
+            buffer
+        };
+        let str_value = |value| VerboseValue::Str(StringValue { name: None, value });
+
+        let values = [
+            str_value("abc"),
+            VerboseValue::U16(U16Value {
+                variable_info: None,
+                scaling: None,
+                value: 1,
+            }),
+            str_value("äöü"),
+        ];
+        let buffer = build(&values, 3);
+        let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+        for (max_chars, expected) in [
+            (0, ""),
+            (2, "ab"),
+            (3, "abc"),
+            (4, "abc "),
+            (5, "abc ä"),
+            (7, "abc äöü"),
+            (100, "abc äöü"),
+        ] {
+            assert_eq!(
+                Some(expected.to_string()),
+                slice.verbose_text_preview(max_chars)
+            );
+        }
+
+        // stops at the first argument that can not be decoded
+        {
+            let buffer = build(&values[..1], 2);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(Some("abc".to_string()), slice.verbose_text_preview(100));
+        }
+
+        // non verbose
+        {
+            let mut header: DltHeader = Default::default();
+            header.length = header.header_len() + 4;
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&header.to_bytes());
+            buffer.extend_from_slice(&[0, 0, 0, 0]);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(None, slice.verbose_text_preview(100));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn content_key(
+            ref packet in dlt_header_with_payload_any(),
+            message_counter in any::<u8>(),
+            timestamp in any::<u32>(),
+        ) {
+            let serialize = |header: &DltHeader, payload: &[u8]| {
+                let mut buffer = Vec::with_capacity(usize::from(header.header_len()) + payload.len());
+                buffer.extend_from_slice(&header.to_bytes());
+                buffer.extend_from_slice(payload);
+                buffer
+            };
+            let buffer = serialize(&packet.0, &packet.1);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+
+            let key = slice.content_key();
+            prop_assert_eq!(slice.payload_fingerprint(), fnv1a_64(&packet.1));
+            prop_assert_eq!(key.payload_fingerprint, slice.payload_fingerprint());
+            prop_assert_eq!(key.application_id, packet.0.extended_header.as_ref().map(|e| e.application_id));
+            prop_assert_eq!(key.context_id, packet.0.extended_header.as_ref().map(|e| e.context_id));
+            prop_assert_eq!(key.message_info, packet.0.extended_header.as_ref().map(|e| e.message_info));
+
+            // counter & timestamp don't influence the key
+            {
+                let mut header = packet.0.clone();
+                header.message_counter = message_counter;
+                header.timestamp = Some(timestamp);
+                header.length = header.header_len() + packet.1.len() as u16;
+                let other_buffer = serialize(&header, &packet.1);
+                let other = DltPacketSlice::from_slice(&other_buffer).unwrap();
+                prop_assert_eq!(key, other.content_key());
+            }
+
+            // payload changes change the key
+            if false == packet.1.is_empty() {
+                let mut payload = packet.1.clone();
+                payload[0] = payload[0].wrapping_add(1);
+                let other_buffer = serialize(&packet.0, &payload);
+                let other = DltPacketSlice::from_slice(&other_buffer).unwrap();
+                prop_assert_ne!(key, other.content_key());
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn estimate_compressed_size() {
+        let build = |payload: &[u8]| {
+            let mut header = DltHeader::new_non_verbose(*b"ECU1");
+            header.length = header.header_len() + payload.len() as u16;
+            let mut buffer = header.to_bytes().to_vec();
+            buffer.extend_from_slice(payload);
+            buffer
+        };
+        let estimate = |payload: &[u8]| {
+            let buffer = build(payload);
+            DltPacketSlice::from_slice(&buffer)
+                .unwrap()
+                .estimate_compressed_size()
+        };
+
+        // empty & single value payloads
+        assert_eq!(0, estimate(&[]));
+        assert_eq!(0, estimate(&[7; 100]));
+        // two equally distributed values -> 1 bit per byte
+        assert_eq!(8, estimate(&[[0u8, 1]; 32].concat()));
+        // all byte values -> 8 bits per byte (no compression)
+        let all: Vec<u8> = (0..=u8::MAX).collect();
+        assert_eq!(256, estimate(&all));
+        // rounded up to full bytes
+        assert_eq!(1, estimate(&[0, 1, 1]));
+    }
+
+    proptest! {
+        #[cfg(feature = "std")]
+        #[test]
+        fn estimate_compressed_size_bounds(ref packet in dlt_header_with_payload_any()) {
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&packet.0.to_bytes());
+            buffer.extend_from_slice(&packet.1);
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            prop_assert!(slice.estimate_compressed_size() <= packet.1.len());
+        }
+    }
+
+    #[test]
+    fn keepalive_empty_payload() {
+        // (is_verbose, payload, expected is_empty_payload, expected is_keepalive)
+        let tests: [(bool, &[u8], bool, bool); 9] = [
+            (false, &[], true, true),
+            (false, &[0, 0, 0, 0], true, true),
+            (false, &[0, 0, 0, 0xc], true, false),
+            (false, &[0xc, 0, 0, 0], true, false),
+            (false, &[0, 0, 0], false, false),
+            (false, &[0, 0, 0, 0, 0], false, false),
+            (true, &[], true, false),
+            (true, &[0, 0, 0, 0], false, false),
+            (true, &[0, 0, 0, 0, 1], false, false),
+        ];
+        for (is_verbose, payload, expected_empty, expected_keepalive) in tests {
+            for is_big_endian in [false, true] {
+                let mut header = DltHeader {
+                    is_big_endian,
+                    message_counter: 0,
+                    length: 0,
+                    ecu_id: Some(*b"ECU1"),
+                    session_id: None,
+                    timestamp: None,
+                    extended_header: Some({
+                        let mut ext: DltExtendedHeader = Default::default();
+                        ext.set_is_verbose(is_verbose);
+                        ext
+                    }),
+                };
+                header.length = header.header_len() + payload.len() as u16;
+                let mut buffer = ArrayVec::<u8, { DltHeader::MAX_SERIALIZED_SIZE + 5 }>::new();
+                buffer.try_extend_from_slice(&header.to_bytes()).unwrap();
+                buffer.try_extend_from_slice(payload).unwrap();
+
+                let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+                assert_eq!(expected_empty, slice.is_empty_payload());
+                assert_eq!(expected_keepalive, slice.is_keepalive());
+            }
+        }
+
+        // without extended header (always non verbose)
+        let mut header: DltHeader = Default::default();
+        header.extended_header = None;
+        header.length = header.header_len() + 4;
+        let mut buffer = ArrayVec::<u8, { DltHeader::MAX_SERIALIZED_SIZE + 4 }>::new();
+        buffer.try_extend_from_slice(&header.to_bytes()).unwrap();
+        buffer.try_extend_from_slice(&[0, 0, 0, 0]).unwrap();
+        let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+        assert!(slice.is_empty_payload());
+        assert!(slice.is_keepalive());
+    }
+
+    #[test]
+    fn control_time() {
+        let data = include_bytes!("../tests/fixtures/time_messages.dlt");
+
+        // periodic time message (non verbose without payload)
+        let (time, rest) = DltPacketSlice::from_slice_with_rest(data).unwrap();
+        assert_eq!(
+            Some(DltMessageType::Control(DltControlMessageType::Time)),
+            time.message_type()
+        );
+        assert_eq!(
+            Some(DltTypedPayload::ControlTime(ControlTimePayload {
+                payload: &[]
+            })),
+            time.typed_payload()
+        );
+        assert!(time.is_keepalive());
+
+        // "get local time" response
+        let response = DltPacketSlice::from_slice(rest).unwrap();
+        assert!(matches!(
+            response.typed_payload(),
+            Some(DltTypedPayload::ControlNv(_))
+        ));
+        assert!(false == response.is_keepalive());
+
+        // verbose time message with payload
+        for is_verbose in [false, true] {
+            let mut header = DltHeader {
+                is_big_endian: false,
+                message_counter: 0,
+                length: 0,
+                ecu_id: Some(*b"ECU1"),
+                session_id: None,
+                timestamp: Some(1),
+                extended_header: Some(
+                    DltExtendedHeader::new_non_verbose(
+                        DltMessageType::Control(DltControlMessageType::Time),
+                        *b"DA1\0",
+                        *b"DC1\0",
+                    )
+                    .unwrap(),
+                ),
+            };
+            header
+                .extended_header
+                .as_mut()
+                .unwrap()
+                .set_is_verbose(is_verbose);
+            header.length = header.header_len() + 5;
+            let mut buffer = ArrayVec::<u8, { DltHeader::MAX_SERIALIZED_SIZE + 5 }>::new();
+            buffer.try_extend_from_slice(&header.to_bytes()).unwrap();
+            buffer.try_extend_from_slice(&[1, 2, 3, 4, 5]).unwrap();
+            let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+            assert_eq!(
+                Some(DltTypedPayload::ControlTime(ControlTimePayload {
+                    payload: &[1, 2, 3, 4, 5]
+                })),
+                slice.typed_payload()
+            );
+            assert!(slice.is_keepalive());
+        }
+    }
+
     #[test]
     fn payload_methods() {
         //pairs of (header, expected_non_verbose)
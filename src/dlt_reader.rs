@@ -0,0 +1,572 @@
+use std::io::{BufRead, ErrorKind, Read};
+#[cfg(not(test))]
+use std::vec::Vec;
+
+use crate::error::{Layer, ReadError, SerialHeaderStartPatternError, UnexpectedEndOfSliceError};
+use crate::storage::StorageHeader;
+use crate::*;
+
+/// DLT packet read by a [`DltReader`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DltReaderSlice<'a> {
+    /// Storage header preceding the packet (only set if the
+    /// framing is [`DltFraming::StorageHeader`]).
+    pub storage_header: Option<StorageHeader>,
+
+    /// The dlt packet.
+    pub packet: DltPacketSlice<'a>,
+}
+
+/// Reader to parse DLT packets from a [`BufRead`] with one of the
+/// framing conventions defined in [`DltFraming`].
+///
+/// In contrast to [`crate::storage::DltStorageReader`] the reader does
+/// not seek for the next valid packet if corrupted data is encountered.
+/// The first error that can not be resolved by more data (see
+/// [`ReadError::is_recoverable_with_more_data`]) ends the iteration.
+///
+/// If the input ends in the middle of a packet an
+/// [`ReadError::UnexpectedEndOfSlice`] error containing the number of
+/// missing bytes is returned. The partially read packet is kept, so
+/// reading can be continued once more data is available (e.g. when
+/// following a file that is still written).
+///
+/// # Example
+/// ```no_run
+/// # let dlt_file = "dummy.dlt";
+/// use std::{fs::File, io::BufReader};
+/// use dlt_parse::{DltFraming, DltReader};
+///
+/// let dlt_file = File::open(dlt_file).expect("failed to open file");
+/// let mut reader = DltReader::new(BufReader::new(dlt_file), DltFraming::SerialHeader);
+///
+/// while let Some(msg_result) = reader.next_packet() {
+///     let msg = msg_result.expect("failed to parse dlt packet");
+///     println!("{:?}", msg.packet);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DltReader<R: Read + BufRead> {
+    reader: R,
+    framing: DltFraming,
+    /// Bytes of the current frame (framing & packet) read so far.
+    frame: Vec<u8>,
+    /// True if `frame` contains the last returned packet.
+    frame_complete: bool,
+    /// True if the end of the input was reported for the incomplete
+    /// `frame` (no data has been added since).
+    incomplete_reported: bool,
+    read_error: bool,
+    num_read_packets: usize,
+}
+
+impl<R: Read + BufRead> DltReader<R> {
+    /// Creates a new reader expecting the packets to be framed
+    /// as defined by `framing`.
+    pub fn new(reader: R, framing: DltFraming) -> DltReader<R> {
+        DltReader {
+            reader,
+            framing,
+            frame: Vec::with_capacity(u16::MAX as usize),
+            frame_complete: false,
+            incomplete_reported: false,
+            read_error: false,
+            num_read_packets: 0,
+        }
+    }
+
+    /// Returns the framing the reader expects.
+    #[inline]
+    pub fn framing(&self) -> DltFraming {
+        self.framing
+    }
+
+    /// Returns the number of DLT packets read.
+    #[inline]
+    pub fn num_read_packets(&self) -> usize {
+        self.num_read_packets
+    }
+
+    /// Returns the next DLT packet.
+    ///
+    /// Returns `None` if the input ended at a packet boundary, after an
+    /// unrecoverable error or if the input ended in the middle of a
+    /// packet that was already reported as incomplete.
+    pub fn next_packet(&mut self) -> Option<Result<DltReaderSlice<'_>, ReadError>> {
+        if self.read_error {
+            return None;
+        }
+        if self.frame_complete {
+            self.frame.clear();
+            self.frame_complete = false;
+        }
+
+        loop {
+            let err = match self.decode_frame() {
+                Ok(_) => break,
+                Err(err) => err,
+            };
+
+            // corrupt data, more data will not help
+            let needed = match err.additional_bytes_needed() {
+                Some(needed) => needed,
+                None => {
+                    self.read_error = true;
+                    return Some(Err(err));
+                }
+            };
+
+            let slice = match self.reader.fill_buf() {
+                Ok(slice) => slice,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.read_error = true;
+                    return Some(Err(err.into()));
+                }
+            };
+            if slice.is_empty() {
+                // end of input (report incomplete packets only once)
+                if self.frame.is_empty() || self.incomplete_reported {
+                    return None;
+                }
+                self.incomplete_reported = true;
+                return Some(Err(err));
+            }
+            let len = needed.min(slice.len());
+            self.frame.extend_from_slice(&slice[..len]);
+            self.reader.consume(len);
+            self.incomplete_reported = false;
+        }
+
+        self.frame_complete = true;
+        self.num_read_packets += 1;
+        // the frame was already verified in decode_frame
+        let (storage_header, packet) = self.decode_frame().unwrap();
+        Some(Ok(DltReaderSlice {
+            storage_header,
+            packet,
+        }))
+    }
+
+    /// Decodes the framing & the packet from the bytes read so far
+    /// (returns an error recoverable with more data if the frame is
+    /// incomplete).
+    fn decode_frame(&self) -> Result<(Option<StorageHeader>, DltPacketSlice<'_>), ReadError> {
+        let (storage_header, packet) = match self.framing {
+            DltFraming::Raw => (None, &self.frame[..]),
+            DltFraming::SerialHeader => {
+                let len = SERIAL_HEADER_PATTERN.len();
+                Self::check_len(Layer::SerialHeader, len, &self.frame)?;
+                let pattern = [self.frame[0], self.frame[1], self.frame[2], self.frame[3]];
+                if pattern != SERIAL_HEADER_PATTERN {
+                    return Err(SerialHeaderStartPatternError {
+                        actual_pattern: pattern,
+                    }
+                    .into());
+                }
+                (None, &self.frame[len..])
+            }
+            DltFraming::StorageHeader => {
+                let len = StorageHeader::BYTE_LEN;
+                Self::check_len(Layer::StorageHeader, len, &self.frame)?;
+                let mut bytes = [0u8; StorageHeader::BYTE_LEN];
+                bytes.copy_from_slice(&self.frame[..len]);
+                (Some(StorageHeader::from_bytes(bytes)?), &self.frame[len..])
+            }
+        };
+        Ok((storage_header, DltPacketSlice::from_slice(packet)?))
+    }
+
+    /// Returns an unexpected end of slice error if the frame is shorter
+    /// then `len`.
+    fn check_len(layer: Layer, len: usize, frame: &[u8]) -> Result<(), ReadError> {
+        if frame.len() < len {
+            Err(ReadError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer,
+                minimum_size: len,
+                actual_size: frame.len(),
+            }))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod dlt_reader_tests {
+    use super::*;
+    use crate::error::{DltMessageLengthTooSmallError, UnsupportedDltVersionError};
+    use crate::test_packets;
+    use std::collections::VecDeque;
+    use std::format;
+    use std::io::{BufReader, Cursor};
+    use std::vec::Vec;
+
+    /// Reader that returns an error when buffer_fill is called.
+    struct BufferFillErrorReader {}
+
+    impl Read for BufferFillErrorReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Ok(0)
+        }
+    }
+
+    impl BufRead for BufferFillErrorReader {
+        fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, ""))
+        }
+
+        fn consume(&mut self, _amt: usize) {}
+    }
+
+    /// Reader returning the given chunks (an empty chunk signals the end
+    /// of the input once, the following chunks are returned afterwards).
+    struct ChunkReader {
+        chunks: VecDeque<Vec<u8>>,
+    }
+
+    impl Read for ChunkReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let slice = self.fill_buf()?;
+            let len = slice.len().min(buf.len());
+            buf[..len].copy_from_slice(&slice[..len]);
+            self.consume(len);
+            Ok(len)
+        }
+    }
+
+    impl BufRead for ChunkReader {
+        fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+            if self.chunks.front().map(|c| c.is_empty()).unwrap_or(false) {
+                self.chunks.pop_front();
+                return Ok(&[]);
+            }
+            Ok(self.chunks.front().map(|c| &c[..]).unwrap_or(&[]))
+        }
+
+        fn consume(&mut self, amt: usize) {
+            if let Some(chunk) = self.chunks.front_mut() {
+                chunk.drain(..amt);
+                if chunk.is_empty() {
+                    self.chunks.pop_front();
+                }
+            }
+        }
+    }
+
+    const FRAMINGS: [DltFraming; 3] = [
+        DltFraming::Raw,
+        DltFraming::SerialHeader,
+        DltFraming::StorageHeader,
+    ];
+
+    fn packet(message_counter: u8, payload: &[u8]) -> Vec<u8> {
+        let header = DltHeader {
+            message_counter,
+            ..test_packets::header(true, None)
+        };
+        test_packets::packet(header, payload)
+    }
+
+    fn storage_header(seconds: u32) -> StorageHeader {
+        StorageHeader {
+            timestamp_seconds: seconds,
+            timestamp_microseconds: 2,
+            ecu_id: [1, 2, 3, 4],
+        }
+    }
+
+    /// Adds the framing for the given packet to the data.
+    fn frame(framing: DltFraming, seconds: u32, packet: &[u8], data: &mut Vec<u8>) {
+        match framing {
+            DltFraming::Raw => {}
+            DltFraming::SerialHeader => data.extend_from_slice(&SERIAL_HEADER_PATTERN),
+            DltFraming::StorageHeader => {
+                data.extend_from_slice(&storage_header(seconds).to_bytes())
+            }
+        }
+        data.extend_from_slice(packet);
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let r = DltReader::new(BufReader::new(Cursor::new(&[])), DltFraming::Raw);
+        assert!(false == format!("{:?}", r).is_empty());
+    }
+
+    #[test]
+    fn next_packet() {
+        for framing in FRAMINGS {
+            // empty reader
+            {
+                let mut r = DltReader::new(BufReader::new(Cursor::new(&[])), framing);
+                assert_eq!(framing, r.framing());
+                assert!(r.next_packet().is_none());
+                assert_eq!(0, r.num_read_packets());
+            }
+
+            // working packets
+            {
+                let packet0 = packet(1, &[1, 2, 3, 4]);
+                let packet1 = packet(2, &[5, 6, 7, 8, 9, 10]);
+                let mut data = Vec::new();
+                frame(framing, 10, &packet0, &mut data);
+                frame(framing, 11, &packet1, &mut data);
+
+                let expected_header = |seconds| {
+                    if framing == DltFraming::StorageHeader {
+                        Some(storage_header(seconds))
+                    } else {
+                        None
+                    }
+                };
+
+                let mut r = DltReader::new(BufReader::new(Cursor::new(&data[..])), framing);
+                assert_eq!(
+                    r.next_packet().unwrap().unwrap(),
+                    DltReaderSlice {
+                        storage_header: expected_header(10),
+                        packet: DltPacketSlice::from_slice(&packet0).unwrap(),
+                    }
+                );
+                assert_eq!(1, r.num_read_packets());
+                assert_eq!(
+                    r.next_packet().unwrap().unwrap(),
+                    DltReaderSlice {
+                        storage_header: expected_header(11),
+                        packet: DltPacketSlice::from_slice(&packet1).unwrap(),
+                    }
+                );
+                assert_eq!(2, r.num_read_packets());
+                assert!(r.next_packet().is_none());
+            }
+
+            // unexpected end of data in the packet
+            {
+                let packet0 = packet(1, &[1, 2, 3, 4]);
+                let mut data = Vec::new();
+                frame(framing, 10, &packet0, &mut data);
+                data.pop();
+
+                let mut r = DltReader::new(BufReader::new(Cursor::new(&data[..])), framing);
+                let err = r.next_packet().unwrap().unwrap_err();
+                assert_eq!(Some(1), err.additional_bytes_needed());
+                assert_matches!(
+                    err,
+                    ReadError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                        layer: Layer::DltHeader,
+                        minimum_size,
+                        actual_size,
+                    }) if minimum_size == packet0.len() && actual_size == packet0.len() - 1
+                );
+                assert!(r.next_packet().is_none());
+                assert_eq!(0, r.num_read_packets());
+            }
+
+            // unsupported version
+            {
+                let mut packet0 = packet(1, &[1, 2, 3, 4]);
+                packet0[0] = (packet0[0] & 0b0001_1111) | (2 << 5);
+                let mut data = Vec::new();
+                frame(framing, 10, &packet0, &mut data);
+
+                let mut r = DltReader::new(BufReader::new(Cursor::new(&data[..])), framing);
+                assert_matches!(
+                    r.next_packet(),
+                    Some(Err(ReadError::UnsupportedDltVersion(
+                        UnsupportedDltVersionError {
+                            unsupported_version: 2
+                        }
+                    )))
+                );
+                assert!(r.next_packet().is_none());
+            }
+
+            // length smaller then 4
+            {
+                let mut packet0 = packet(1, &[1, 2, 3, 4]);
+                packet0[2] = 0;
+                packet0[3] = 3;
+                let mut data = Vec::new();
+                frame(framing, 10, &packet0, &mut data);
+
+                let mut r = DltReader::new(BufReader::new(Cursor::new(&data[..])), framing);
+                assert_matches!(
+                    r.next_packet(),
+                    Some(Err(ReadError::DltMessageLengthTooSmall(
+                        DltMessageLengthTooSmallError {
+                            required_length: 4,
+                            actual_length: 3,
+                        }
+                    )))
+                );
+                assert!(r.next_packet().is_none());
+            }
+
+            // length smaller then the header length indicated by the flags
+            {
+                let mut packet0 = packet(1, &[1, 2, 3, 4]);
+                packet0[0] |= ECU_ID_FLAG;
+                packet0[3] = 6;
+                packet0.truncate(6);
+                let mut data = Vec::new();
+                frame(framing, 10, &packet0, &mut data);
+
+                let mut r = DltReader::new(BufReader::new(Cursor::new(&data[..])), framing);
+                assert_matches!(
+                    r.next_packet(),
+                    Some(Err(ReadError::DltMessageLengthTooSmall(
+                        DltMessageLengthTooSmallError {
+                            required_length: 8,
+                            actual_length: 6,
+                        }
+                    )))
+                );
+                assert!(r.next_packet().is_none());
+            }
+
+            // fill buf error
+            {
+                let mut r = DltReader::new(BufferFillErrorReader {}, framing);
+                assert_matches!(r.next_packet(), Some(Err(ReadError::IoError(_))));
+                assert!(r.next_packet().is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn next_packet_framing_errors() {
+        let packet0 = packet(1, &[1, 2, 3, 4]);
+
+        // serial header pattern missing
+        {
+            let mut data = Vec::new();
+            data.extend_from_slice(&[0x44, 0x4C, 0x53, 0x02]);
+            data.extend_from_slice(&packet0);
+            let mut r = DltReader::new(
+                BufReader::new(Cursor::new(&data[..])),
+                DltFraming::SerialHeader,
+            );
+            assert_matches!(
+                r.next_packet(),
+                Some(Err(ReadError::SerialHeaderStartPattern(
+                    SerialHeaderStartPatternError {
+                        actual_pattern: [0x44, 0x4C, 0x53, 0x02]
+                    }
+                )))
+            );
+            assert!(r.next_packet().is_none());
+        }
+
+        // storage header pattern missing
+        {
+            let mut data = Vec::new();
+            let mut header = storage_header(10).to_bytes();
+            header[3] = 0;
+            data.extend_from_slice(&header);
+            data.extend_from_slice(&packet0);
+            let mut r = DltReader::new(
+                BufReader::new(Cursor::new(&data[..])),
+                DltFraming::StorageHeader,
+            );
+            assert_matches!(
+                r.next_packet(),
+                Some(Err(ReadError::StorageHeaderStartPattern(_)))
+            );
+            assert!(r.next_packet().is_none());
+        }
+
+        // framing cut off
+        for (framing, layer, len) in [
+            (
+                DltFraming::SerialHeader,
+                Layer::SerialHeader,
+                SERIAL_HEADER_PATTERN.len(),
+            ),
+            (
+                DltFraming::StorageHeader,
+                Layer::StorageHeader,
+                StorageHeader::BYTE_LEN,
+            ),
+        ] {
+            let mut data = Vec::new();
+            frame(framing, 10, &packet0, &mut data);
+            data.truncate(len - 1);
+            let mut r = DltReader::new(BufReader::new(Cursor::new(&data[..])), framing);
+            let err = r.next_packet().unwrap().unwrap_err();
+            assert_eq!(Some(1), err.additional_bytes_needed());
+            assert_eq!(Some(layer), err.layer());
+            assert_matches!(
+                err,
+                ReadError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                    minimum_size,
+                    actual_size,
+                    ..
+                }) if minimum_size == len && actual_size == len - 1
+            );
+            assert!(r.next_packet().is_none());
+        }
+    }
+
+    #[test]
+    fn next_packet_continue_after_end() {
+        for framing in FRAMINGS {
+            let packet0 = packet(1, &[1, 2, 3, 4]);
+            let packet1 = packet(2, &[5, 6, 7, 8, 9, 10]);
+            let mut data = Vec::new();
+            frame(framing, 10, &packet0, &mut data);
+            frame(framing, 11, &packet1, &mut data);
+
+            // end of the input in the middle of the second packet
+            let missing = 3;
+            let cut = data.len() - missing;
+            let mut r = DltReader::new(
+                ChunkReader {
+                    chunks: VecDeque::from(std::vec![
+                        data[..cut].to_vec(),
+                        Vec::new(),
+                        Vec::new(),
+                        data[cut..].to_vec(),
+                    ]),
+                },
+                framing,
+            );
+            assert_eq!(
+                DltPacketSlice::from_slice(&packet0).unwrap(),
+                r.next_packet().unwrap().unwrap().packet
+            );
+
+            // incomplete packet is reported once with the missing bytes
+            let err = r.next_packet().unwrap().unwrap_err();
+            assert!(err.is_recoverable_with_more_data());
+            assert_eq!(Some(missing), err.additional_bytes_needed());
+            assert!(r.next_packet().is_none());
+
+            // reading continues once more data is available
+            assert_eq!(
+                DltPacketSlice::from_slice(&packet1).unwrap(),
+                r.next_packet().unwrap().unwrap().packet
+            );
+            assert_eq!(2, r.num_read_packets());
+            assert!(r.next_packet().is_none());
+        }
+    }
+
+    #[test]
+    fn next_packet_corrupt_header() {
+        for framing in FRAMINGS {
+            // unsupported version with the rest of the packet missing
+            let mut packet0 = packet(1, &[1, 2, 3, 4]);
+            packet0[0] = (packet0[0] & 0b0001_1111) | (2 << 5);
+            let mut data = Vec::new();
+            frame(framing, 10, &packet0, &mut data);
+            data.truncate(data.len() - 2);
+
+            let mut r = DltReader::new(BufReader::new(Cursor::new(&data[..])), framing);
+            let err = r.next_packet().unwrap().unwrap_err();
+            assert!(false == err.is_recoverable_with_more_data());
+            assert_eq!(None, err.additional_bytes_needed());
+            assert!(r.next_packet().is_none());
+        }
+    }
+}
@@ -1,53 +1,197 @@
 use super::*;
 
 /// Allows iterating over the someip message in a udp or tcp payload.
+///
+/// With the `metrics` feature the time it takes to decode each message
+/// is recorded (see [`SliceIterator::metrics`]).
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SliceIterator<'a> {
     slice: &'a [u8],
+    prefix_len: usize,
+    skip_keepalive: bool,
+    config: ParseConfig,
+    #[cfg(feature = "metrics")]
+    metrics: DecodeMetrics,
 }
 
 impl<'a> SliceIterator<'a> {
     #[inline]
     pub fn new(slice: &'a [u8]) -> SliceIterator<'a> {
-        SliceIterator { slice }
+        SliceIterator {
+            slice,
+            prefix_len: 0,
+            skip_keepalive: false,
+            config: ParseConfig::default(),
+            #[cfg(feature = "metrics")]
+            metrics: DecodeMetrics::new(),
+        }
+    }
+
+    /// Sets the number of bytes that are skipped before each message
+    /// (e.g. a fixed size header of a custom capture format).
+    ///
+    /// If the data left in the iterator is not empty but smaller than
+    /// the prefix an [`error::PacketSliceError::UnexpectedEndOfSlice`]
+    /// error is returned (& the iteration ends).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::{DltHeader, SliceIterator};
+    ///
+    /// let mut data = vec![0xff, 0xff];
+    /// data.extend_from_slice(&DltHeader::new_non_verbose(*b"ECU1").to_bytes());
+    /// data.extend_from_slice(&[0xff, 0xff]);
+    /// data.extend_from_slice(&DltHeader::new_non_verbose(*b"ECU2").to_bytes());
+    ///
+    /// let ecu_ids: Vec<_> = SliceIterator::new(&data)
+    ///     .with_prefix_len(2)
+    ///     .map(|p| p.unwrap().ecu_id())
+    ///     .collect();
+    /// assert_eq!(vec![Some(*b"ECU1"), Some(*b"ECU2")], ecu_ids);
+    /// ```
+    #[inline]
+    pub fn with_prefix_len(mut self, prefix_len: usize) -> SliceIterator<'a> {
+        self.prefix_len = prefix_len;
+        self
+    }
+
+    /// Number of bytes skipped before each message.
+    #[inline]
+    pub fn prefix_len(&self) -> usize {
+        self.prefix_len
+    }
+
+    /// Sets if keep-alive messages (see [`DltPacketSlice::is_keepalive`])
+    /// are skipped by the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::SliceIterator;
+    ///
+    /// // control time message followed by a "get local time" response
+    /// let data = include_bytes!("../tests/fixtures/time_messages.dlt");
+    ///
+    /// assert_eq!(2, SliceIterator::new(data).count());
+    /// assert_eq!(1, SliceIterator::new(data).with_skip_keepalive(true).count());
+    /// ```
+    #[inline]
+    pub fn with_skip_keepalive(mut self, skip_keepalive: bool) -> SliceIterator<'a> {
+        self.skip_keepalive = skip_keepalive;
+        self
+    }
+
+    /// True if keep-alive messages are skipped by the iterator.
+    #[inline]
+    pub fn skip_keepalive(&self) -> bool {
+        self.skip_keepalive
+    }
+
+    /// Sets the checks done when decoding the packets (see [`ParseConfig`]).
+    ///
+    /// Truncated packets (if allowed) contain all the data left in the
+    /// iterator & end the iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::{DltHeader, ParseConfig, SliceIterator};
+    ///
+    /// let mut data = DltHeader::new_non_verbose(*b"ECU1").to_bytes().to_vec();
+    /// // second packet with a length field of 12 but only 8 bytes of data
+    /// data.extend_from_slice(&[0x24, 0, 0, 12, b'E', b'C', b'U', b'2']);
+    ///
+    /// assert!(SliceIterator::new(&data).nth(1).unwrap().is_err());
+    ///
+    /// let packet = SliceIterator::new(&data)
+    ///     .with_config(ParseConfig::lenient())
+    ///     .nth(1)
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(Some(*b"ECU2"), packet.ecu_id());
+    /// ```
+    #[inline]
+    pub fn with_config(mut self, config: ParseConfig) -> SliceIterator<'a> {
+        self.config = config;
+        self
+    }
+
+    /// Checks done when decoding the packets.
+    #[inline]
+    pub fn config(&self) -> &ParseConfig {
+        &self.config
     }
 
     /// Returns the slice of data still left in the iterator.
     pub fn slice(&self) -> &'a [u8] {
         self.slice
     }
-}
 
-impl<'a> Iterator for SliceIterator<'a> {
-    type Item = Result<DltPacketSlice<'a>, error::PacketSliceError>;
+    /// Returns the decode times of the messages returned by the iterator
+    /// so far (including messages that could not be decoded).
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub fn metrics(&self) -> &DecodeMetrics {
+        &self.metrics
+    }
 
+    /// Decodes the next packet (including keep-alive messages).
     #[inline]
-    fn next(&mut self) -> Option<Result<DltPacketSlice<'a>, error::PacketSliceError>> {
+    fn next_packet(&mut self) -> Option<Result<DltPacketSlice<'a>, error::PacketSliceError>> {
         if !self.slice.is_empty() {
-            //parse
-            let result = DltPacketSlice::from_slice(self.slice);
+            if self.slice.len() < self.prefix_len {
+                let err = error::PacketSliceError::UnexpectedEndOfSlice(
+                    error::UnexpectedEndOfSliceError {
+                        layer: error::Layer::DltHeader,
+                        minimum_size: self.prefix_len,
+                        actual_size: self.slice.len(),
+                    },
+                );
+                self.slice = &self.slice[self.slice.len()..];
+                return Some(Err(err));
+            }
+            self.slice = &self.slice[self.prefix_len..];
 
-            //move the slice depending on the result
-            match &result {
-                Err(_) => {
-                    //error => move the slice to an len = 0 position so that the iterator ends
-                    let len = self.slice.len();
-                    self.slice = &self.slice[len..];
+            #[cfg(feature = "metrics")]
+            let result = {
+                let (slice, config) = (self.slice, &self.config);
+                self.metrics
+                    .measure(|| DltPacketSlice::from_slice_with_config(slice, config))
+            };
+            #[cfg(not(feature = "metrics"))]
+            let result = DltPacketSlice::from_slice_with_config(self.slice, &self.config);
+            match result {
+                Ok(packet) => {
+                    self.slice = &self.slice[packet.slice().len()..];
+                    Some(Ok(packet))
                 }
-                Ok(ref value) => {
-                    //by the length just taken by the slice
-                    self.slice = &self.slice[value.slice().len()..];
+                Err(err) => {
+                    //error => move the slice to an len = 0 position so that the iterator ends
+                    self.slice = &self.slice[self.slice.len()..];
+                    Some(Err(err))
                 }
             }
-
-            //return parse result
-            Some(result)
         } else {
             None
         }
     }
 }
 
+impl<'a> Iterator for SliceIterator<'a> {
+    type Item = Result<DltPacketSlice<'a>, error::PacketSliceError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Result<DltPacketSlice<'a>, error::PacketSliceError>> {
+        loop {
+            match self.next_packet() {
+                Some(Ok(packet)) if self.skip_keepalive && packet.is_keepalive() => {}
+                result => return result,
+            }
+        }
+    }
+}
+
 /// Tests for `SliceIterator`
 #[cfg(test)]
 mod slice_interator_tests {
@@ -58,15 +202,32 @@ mod slice_interator_tests {
 
     #[test]
     fn clone_eq() {
-        let it = SliceIterator { slice: &[] };
+        let it = SliceIterator::new(&[]);
         assert_eq!(it, it.clone());
     }
 
+    #[cfg(not(feature = "metrics"))]
+    #[test]
+    fn debug() {
+        let it = SliceIterator::new(&[]);
+        assert_eq!(
+            format!(
+                "SliceIterator {{ slice: {:?}, prefix_len: 0, skip_keepalive: false, config: {:?} }}",
+                it.slice, it.config
+            ),
+            format!("{:?}", it)
+        );
+    }
+
+    #[cfg(feature = "metrics")]
     #[test]
     fn debug() {
-        let it = SliceIterator { slice: &[] };
+        let it = SliceIterator::new(&[]);
         assert_eq!(
-            format!("SliceIterator {{ slice: {:?} }}", it.slice),
+            format!(
+                "SliceIterator {{ slice: {:?}, prefix_len: 0, skip_keepalive: false, config: {:?}, metrics: {:?} }}",
+                it.slice, it.config, it.metrics
+            ),
             format!("{:?}", it)
         );
     }
@@ -74,10 +235,28 @@ mod slice_interator_tests {
     #[test]
     fn slice() {
         let buffer: [u8; 4] = [1, 2, 3, 4];
-        let it = SliceIterator { slice: &buffer };
+        let it = SliceIterator::new(&buffer);
         assert_eq!(it.slice(), &buffer);
     }
 
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn metrics() {
+        let mut buffer = Vec::new();
+        for _ in 0..3 {
+            buffer.extend_from_slice(&DltHeader::new_non_verbose(*b"ECU1").to_bytes());
+        }
+        // incomplete packet
+        buffer.extend_from_slice(&[0x35, 0]);
+
+        let mut it = SliceIterator::new(&buffer);
+        assert_eq!(0, it.metrics().count());
+        assert_eq!(3, (&mut it).filter(|p| p.is_ok()).count());
+        assert_eq!(4, it.metrics().count());
+        assert_eq!(4, it.metrics().histogram().iter().sum::<u64>());
+        assert!(it.metrics().min() <= it.metrics().max());
+    }
+
     proptest! {
         #[test]
         fn iterator(ref packets in prop::collection::vec(dlt_header_with_payload_any(), 1..5)) {
@@ -138,4 +317,81 @@ mod slice_interator_tests {
             }
         }
     }
+
+    proptest! {
+        #[test]
+        fn with_prefix_len(
+            ref packets in prop::collection::vec(dlt_header_with_payload_any(), 1..5),
+            prefix_len in 0usize..20,
+        ) {
+            use error::PacketSliceError::*;
+
+            let mut buffer = Vec::new();
+            for packet in packets {
+                buffer.extend((0..prefix_len).map(|i| i as u8));
+                buffer.extend_from_slice(&packet.0.to_bytes());
+                buffer.extend_from_slice(&packet.1);
+            }
+
+            let it = SliceIterator::new(&buffer).with_prefix_len(prefix_len);
+            prop_assert_eq!(prefix_len, it.prefix_len());
+            let decoded: Vec<_> = it.map(|p| p.unwrap()).collect();
+            prop_assert_eq!(packets.len(), decoded.len());
+            for (packet, decoded) in packets.iter().zip(decoded.iter()) {
+                prop_assert_eq!(&packet.0, &decoded.header());
+                prop_assert_eq!(&packet.1[..], decoded.payload());
+            }
+
+            // incomplete prefix at the end
+            for rest_len in 1..prefix_len {
+                let mut data = buffer.clone();
+                data.extend((0..rest_len).map(|i| i as u8));
+                let mut it = SliceIterator::new(&data)
+                    .with_prefix_len(prefix_len)
+                    .skip(packets.len());
+                match it.next() {
+                    Some(Err(UnexpectedEndOfSlice(err))) => {
+                        prop_assert_eq!(prefix_len, err.minimum_size);
+                        prop_assert_eq!(rest_len, err.actual_size);
+                    }
+                    other => prop_assert!(false, "unexpected result {:?}", other),
+                }
+                prop_assert!(it.next().is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn skip_keepalive() {
+        let time_messages = include_bytes!("../tests/fixtures/time_messages.dlt");
+        let overflow = include_bytes!("../tests/fixtures/buffer_overflow_notification.dlt");
+        let mut data = Vec::new();
+        data.extend_from_slice(time_messages);
+        data.extend_from_slice(overflow);
+        data.extend_from_slice(time_messages);
+
+        // keep-alive messages are returned by default
+        let it = SliceIterator::new(&data);
+        assert!(false == it.skip_keepalive());
+        assert_eq!(5, it.count());
+
+        // control time messages are dropped, control responses passed through
+        let it = SliceIterator::new(&data).with_skip_keepalive(true);
+        assert!(it.skip_keepalive());
+        let packets: Vec<_> = it.map(|p| p.unwrap()).collect();
+        assert_eq!(3, packets.len());
+        for packet in packets {
+            assert!(false == packet.is_keepalive());
+            assert_eq!(
+                Some(DltMessageType::Control(DltControlMessageType::Response)),
+                packet.message_type()
+            );
+        }
+
+        // errors are still returned
+        let mut it = SliceIterator::new(&data[..time_messages.len() + 1]).with_skip_keepalive(true);
+        assert!(it.next().unwrap().is_ok());
+        assert!(it.next().unwrap().is_err());
+        assert!(it.next().is_none());
+    }
 } // mod slice_iterator_tests
@@ -24,6 +24,9 @@ pub enum DltTypedPayload<'a> {
     ControlV(ControlVPayload<'a>),
     /// Non verbose control message.
     ControlNv(ControlNvPayload<'a>),
+    /// Control time message (verbose or non verbose) sent periodically
+    /// if timing packets are enabled (see [`crate::DltPacketSlice::is_keepalive`]).
+    ControlTime(ControlTimePayload<'a>),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -78,9 +81,91 @@ pub struct LogNvPayload<'a> {
     pub payload: &'a [u8],
 }
 
+/// Payload of a control time message.
+///
+/// The time itself is the timestamp in the standard header (see
+/// [`crate::control::DltTimeMessage`]), the payload is usually empty.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ControlTimePayload<'a> {
+    pub payload: &'a [u8],
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct ControlNvPayload<'a> {
     pub msg_type: DltControlMessageType,
     pub service_id: u32,
     pub payload: &'a [u8],
 }
+
+impl<'a> ControlNvPayload<'a> {
+    /// Returns the payload as [`crate::control::ControlRequest`] if the
+    /// message is a control request (`None` for responses).
+    ///
+    /// The endianness of the parameters is not part of the payload &
+    /// has to be passed in (see [`crate::DltPacketSlice::is_big_endian`]).
+    /// As the position of the payload in the message is not known, offsets
+    /// are relative to the start of the payload (use
+    /// [`crate::control::ControlRequest::from_packet`] for offsets relative
+    /// to the start of the DLT message).
+    /// The request can then be decoded further via
+    /// [`crate::control::TypedControlRequest::from_request`].
+    pub fn control_request(
+        &self,
+        is_big_endian: bool,
+    ) -> Option<crate::control::ControlRequest<'a>> {
+        if self.msg_type == DltControlMessageType::Request {
+            Some(crate::control::ControlRequest {
+                service_id: crate::control::DltServiceId::from_u32(self.service_id),
+                params: self.payload,
+                is_big_endian,
+                payload_offset: 0,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the payload as [`crate::control::ControlResponse`] if the
+    /// message is a control response (`None` for requests & responses
+    /// without a status byte).
+    ///
+    /// The endianness of the data is not part of the payload & has to be
+    /// passed in (see [`crate::DltPacketSlice::is_big_endian`]). As the
+    /// position of the payload in the message is not known, offsets are
+    /// relative to the start of the payload (use
+    /// [`crate::control::ControlResponse::from_packet`] for offsets
+    /// relative to the start of the DLT message). The
+    /// response can then be decoded further via
+    /// [`crate::control::TypedControlResponse::from_response`].
+    pub fn control_response(
+        &self,
+        is_big_endian: bool,
+    ) -> Option<crate::control::ControlResponse<'a>> {
+        match (self.msg_type, self.payload.split_first()) {
+            (DltControlMessageType::Response, Some((status, data))) => {
+                Some(crate::control::ControlResponse {
+                    service_id: crate::control::DltServiceId::from_u32(self.service_id),
+                    status: crate::control::ControlResponseStatus::from_u8(*status),
+                    data,
+                    is_big_endian,
+                    payload_offset: 0,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Decodes the payload with the handler registered for the service id
+    /// in the given registry & falls back to the typed request or
+    /// response (see [`crate::control::ControlServiceRegistry::decode`]).
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn decode_with<T>(
+        &self,
+        registry: &crate::control::ControlServiceRegistry<T>,
+        is_big_endian: bool,
+    ) -> Result<crate::control::ControlServiceDecoded<'a, T>, crate::error::ControlDecodeError>
+    {
+        registry.decode(self, is_big_endian)
+    }
+}
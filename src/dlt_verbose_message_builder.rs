@@ -0,0 +1,1002 @@
+use crate::error::VerboseEncodeError;
+use crate::message_header_fields::MessageHeaderFields;
+use crate::verbose::{
+    BoolValue, F32Value, F64Value, I16Value, I32Value, I64Value, I8Value, StringValue, U16Value,
+    U32Value, U64Value, U8Value, VariableInfoUnit, VerboseValue,
+};
+use crate::{DltExtendedHeader, DltHeader, DltLogLevel};
+use arrayvec::ArrayVec;
+
+#[cfg(feature = "std")]
+use crate::error::WriteError;
+#[cfg(feature = "std")]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::{io, vec::Vec};
+
+/// Marker appended to formatted log messages that had to be truncated
+/// to fit into a single DLT message (see [`build_log_message`]).
+pub const TRUNCATION_MARKER: &str = "[...]";
+
+/// Appends a complete verbose log message with the formatted arguments
+/// as a single string argument to the given buffer.
+///
+/// The text is formatted directly into `buffer` (no allocations happen
+/// if the buffer has enough capacity, so the same buffer can be reused
+/// for multiple messages). Texts that do not fit into a DLT message are
+/// truncated & end with the [`TRUNCATION_MARKER`].
+///
+/// The message is encoded in little endian without ECU id, session id
+/// or timestamp. Use [`DltVerboseMessageBuilder::build_formatted`] to
+/// control these.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{build_log_message, DltLogLevel, DltPacketSlice};
+/// use dlt_parse::verbose::VerboseValue;
+///
+/// let mut buffer = Vec::new();
+/// build_log_message(*b"APP1", *b"CTX1", DltLogLevel::Info, format_args!("{} + {} = {}", 1, 2, 3), &mut buffer).unwrap();
+///
+/// let packet = DltPacketSlice::from_slice(&buffer).unwrap();
+/// let value = packet.verbose_value_iter().unwrap().next().unwrap().unwrap();
+/// if let VerboseValue::Str(s) = value {
+///     assert_eq!("1 + 2 = 3", s.value);
+/// } else {
+///     panic!("unexpected value");
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn build_log_message(
+    application_id: [u8; 4],
+    context_id: [u8; 4],
+    log_level: DltLogLevel,
+    args: fmt::Arguments<'_>,
+    buffer: &mut Vec<u8>,
+) -> Result<(), VerboseEncodeError> {
+    DltVerboseMessageBuilder::<0>::new(application_id, context_id, log_level)
+        .build_formatted(args, buffer)
+}
+
+/// Generates `add_*_named` methods for integer arguments with a
+/// variable name & unit (without scaling).
+macro_rules! add_named_int {
+    ($(($fn_name:ident, $t:ty, $variant:ident, $value_type:ident)),*) => {
+        $(
+            #[doc = concat!("Adds an argument of type `", stringify!($t), "` with a variable name & unit (VARI flag set).")]
+            ///
+            /// Empty names & units are written with a length of 0 (as
+            /// expected by the decoder). On error no argument is added.
+            pub fn $fn_name(
+                &mut self,
+                name: &str,
+                unit: &str,
+                value: $t,
+            ) -> Result<(), VerboseEncodeError> {
+                self.add_value(&VerboseValue::$variant($value_type {
+                    variable_info: Some(VariableInfoUnit { name, unit }),
+                    scaling: None,
+                    value,
+                }))
+            }
+        )*
+    };
+}
+
+/// Generates `add_*_named` methods for float arguments with a variable
+/// name & unit.
+macro_rules! add_named_float {
+    ($(($fn_name:ident, $t:ty, $variant:ident, $value_type:ident)),*) => {
+        $(
+            #[doc = concat!("Adds an argument of type `", stringify!($t), "` with a variable name & unit (VARI flag set).")]
+            ///
+            /// Empty names & units are written with a length of 0 (as
+            /// expected by the decoder). On error no argument is added.
+            pub fn $fn_name(
+                &mut self,
+                name: &str,
+                unit: &str,
+                value: $t,
+            ) -> Result<(), VerboseEncodeError> {
+                self.add_value(&VerboseValue::$variant($value_type {
+                    variable_info: Some(VariableInfoUnit { name, unit }),
+                    value,
+                }))
+            }
+        )*
+    };
+}
+
+/// Builder for verbose dlt log messages.
+///
+/// The verbose values are encoded into an internal buffer with a
+/// capacity of `CAP` bytes when they are added. The header (including
+/// the length field, the number of arguments, the verbose flag & the
+/// message type) is computed when the message is built.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use dlt_parse::{DltLogLevel, DltPacketSlice, DltVerboseMessageBuilder};
+/// use dlt_parse::verbose::{StringValue, VerboseValue};
+///
+/// let mut builder = DltVerboseMessageBuilder::<256>::new(*b"APP1", *b"CTX1", DltLogLevel::Info)
+///     .with_ecu_id(*b"ECU1");
+/// builder
+///     .add_value(&VerboseValue::Str(StringValue {
+///         name: None,
+///         value: "Hello world",
+///     }))
+///     .unwrap();
+///
+/// let mut buffer = Vec::new();
+/// builder.build(&mut buffer).unwrap();
+///
+/// let packet = DltPacketSlice::from_slice(&buffer).unwrap();
+/// assert!(packet.is_verbose());
+/// assert_eq!(1, packet.verbose_value_iter().unwrap().count());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DltVerboseMessageBuilder<const CAP: usize> {
+    fields: MessageHeaderFields,
+    log_level: DltLogLevel,
+    application_id: [u8; 4],
+    context_id: [u8; 4],
+    number_of_arguments: u8,
+    payload: ArrayVec<u8, CAP>,
+}
+
+impl<const CAP: usize> DltVerboseMessageBuilder<CAP> {
+    /// Creates a builder for a little endian verbose log message without
+    /// any arguments, ecu id, session id or timestamp.
+    pub fn new(
+        application_id: [u8; 4],
+        context_id: [u8; 4],
+        log_level: DltLogLevel,
+    ) -> DltVerboseMessageBuilder<CAP> {
+        DltVerboseMessageBuilder {
+            fields: MessageHeaderFields::default(),
+            log_level,
+            application_id,
+            context_id,
+            number_of_arguments: 0,
+            payload: ArrayVec::new(),
+        }
+    }
+
+    /// Sets if the arguments are encoded in big endian.
+    ///
+    /// # Panics
+    ///
+    /// Has to be set before any values are added, as the already encoded
+    /// arguments are not converted. Panics in debug builds if arguments
+    /// were already added.
+    pub fn with_big_endian(mut self, is_big_endian: bool) -> Self {
+        debug_assert!(
+            self.payload.is_empty(),
+            "with_big_endian called after arguments were added"
+        );
+        self.fields.is_big_endian = is_big_endian;
+        self
+    }
+
+    /// Sets the message counter.
+    pub fn with_message_counter(mut self, message_counter: u8) -> Self {
+        self.fields.message_counter = message_counter;
+        self
+    }
+
+    /// Sets the ecu id.
+    pub fn with_ecu_id(mut self, ecu_id: [u8; 4]) -> Self {
+        self.fields.ecu_id = Some(ecu_id);
+        self
+    }
+
+    /// Sets the session id.
+    pub fn with_session_id(mut self, session_id: u32) -> Self {
+        self.fields.session_id = Some(session_id);
+        self
+    }
+
+    /// Sets the timestamp (in 0.1 milliseconds).
+    pub fn with_timestamp(mut self, timestamp: u32) -> Self {
+        self.fields.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Adds a verbose value as argument to the message.
+    ///
+    /// On error no argument is added & the payload is left unchanged.
+    pub fn add_value(&mut self, value: &VerboseValue<'_>) -> Result<(), VerboseEncodeError> {
+        let number_of_arguments = self
+            .number_of_arguments
+            .checked_add(1)
+            .ok_or(VerboseEncodeError::TooManyArguments)?;
+        value.write(&mut self.payload, self.fields.is_big_endian)?;
+        self.number_of_arguments = number_of_arguments;
+        Ok(())
+    }
+
+    add_named_int! {
+        (add_u8_named, u8, U8, U8Value),
+        (add_u16_named, u16, U16, U16Value),
+        (add_u32_named, u32, U32, U32Value),
+        (add_u64_named, u64, U64, U64Value),
+        (add_i8_named, i8, I8, I8Value),
+        (add_i16_named, i16, I16, I16Value),
+        (add_i32_named, i32, I32, I32Value),
+        (add_i64_named, i64, I64, I64Value)
+    }
+
+    add_named_float! {
+        (add_f32_named, f32, F32, F32Value),
+        (add_f64_named, f64, F64, F64Value)
+    }
+
+    /// Adds a bool argument with a variable name (VARI flag set).
+    ///
+    /// An empty name is written with a length of 0. On error no argument
+    /// is added.
+    pub fn add_bool_named(&mut self, name: &str, value: bool) -> Result<(), VerboseEncodeError> {
+        self.add_value(&VerboseValue::Bool(BoolValue {
+            name: Some(name),
+            value,
+        }))
+    }
+
+    /// Adds an UTF-8 string argument with a variable name (VARI flag set).
+    ///
+    /// An empty name is written with a length of 0. On error no argument
+    /// is added.
+    pub fn add_str_named(&mut self, name: &str, value: &str) -> Result<(), VerboseEncodeError> {
+        self.add_value(&VerboseValue::Str(StringValue {
+            name: Some(name),
+            value,
+        }))
+    }
+
+    /// Number of arguments added so far.
+    #[inline]
+    pub fn number_of_arguments(&self) -> u8 {
+        self.number_of_arguments
+    }
+
+    /// Returns the encoded arguments.
+    #[inline]
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Returns the header of the message (including the length of
+    /// the header & payload).
+    pub fn header(&self) -> Result<DltHeader, VerboseEncodeError> {
+        let mut header = self.header_without_length();
+        let length = usize::from(header.header_len()) + self.payload.len();
+        header.length =
+            u16::try_from(length).map_err(|_| VerboseEncodeError::MessageTooLong(length))?;
+        Ok(header)
+    }
+
+    /// Appends the complete message (header & payload) to the given vector.
+    #[cfg(feature = "std")]
+    pub fn build(&self, buffer: &mut Vec<u8>) -> Result<(), VerboseEncodeError> {
+        let header = self.header()?;
+        buffer.extend_from_slice(&header.to_bytes());
+        buffer.extend_from_slice(&self.payload);
+        Ok(())
+    }
+
+    /// Writes the complete message (header & payload) to the given writer.
+    #[cfg(feature = "std")]
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> Result<(), WriteError> {
+        // write_messages computes & checks the length field itself
+        crate::write_messages(writer, &[(self.header_without_length(), &self.payload)])
+    }
+
+    /// Appends the complete message to the given vector with the formatted
+    /// arguments added as an additional string argument (after the
+    /// already added arguments).
+    ///
+    /// The text is formatted directly into `buffer` & truncated (ending
+    /// with the [`TRUNCATION_MARKER`]) if the message would exceed the
+    /// maximum DLT message length. On error nothing is appended.
+    #[cfg(feature = "std")]
+    pub fn build_formatted(
+        &self,
+        args: fmt::Arguments<'_>,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), VerboseEncodeError> {
+        let start = buffer.len();
+        let result = self.append_formatted(args, buffer);
+        if result.is_err() {
+            buffer.truncate(start);
+        }
+        result
+    }
+
+    #[cfg(feature = "std")]
+    fn append_formatted(
+        &self,
+        args: fmt::Arguments<'_>,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), VerboseEncodeError> {
+        use fmt::Write;
+
+        let mut header = self.header_without_length();
+        if let Some(ext) = header.extended_header.as_mut() {
+            ext.number_of_arguments = self
+                .number_of_arguments
+                .checked_add(1)
+                .ok_or(VerboseEncodeError::TooManyArguments)?;
+        }
+
+        // header, previous arguments & string type info (length is patched later)
+        let start = buffer.len();
+        buffer.extend_from_slice(&header.to_bytes());
+        buffer.extend_from_slice(&self.payload);
+        buffer.extend_from_slice(&[0b0000_0000, 0b1000_0010, 0b0000_0000, 0b0000_0000]);
+        let str_len_offset = buffer.len();
+        buffer.extend_from_slice(&[0, 0]);
+        let text_start = buffer.len();
+
+        // maximum text length (the terminating zero is not part of the text)
+        let max_text_len = (usize::from(u16::MAX) + start)
+            .checked_sub(text_start + 1)
+            .ok_or(VerboseEncodeError::MessageTooLong(text_start + 1 - start))?;
+
+        let mut writer = TruncatingWriter {
+            buffer,
+            end: text_start + max_text_len,
+            truncated: false,
+        };
+        writer
+            .write_fmt(args)
+            .map_err(|_| VerboseEncodeError::Fmt)?;
+        if writer.truncated {
+            let end = char_boundary(
+                &buffer[text_start..],
+                max_text_len.saturating_sub(TRUNCATION_MARKER.len()),
+            );
+            buffer.truncate(text_start + end);
+            buffer.extend_from_slice(TRUNCATION_MARKER.as_bytes());
+        }
+        buffer.push(0);
+
+        // patch lengths (the checks above ensure both fit into an u16)
+        let str_len = (buffer.len() - text_start) as u16;
+        let str_len = if self.fields.is_big_endian {
+            str_len.to_be_bytes()
+        } else {
+            str_len.to_le_bytes()
+        };
+        buffer[str_len_offset..str_len_offset + 2].copy_from_slice(&str_len);
+        let length = (buffer.len() - start) as u16;
+        buffer[start + 2..start + 4].copy_from_slice(&length.to_be_bytes());
+        Ok(())
+    }
+
+    /// Returns the header with the length field set to 0.
+    fn header_without_length(&self) -> DltHeader {
+        let mut extended_header = DltExtendedHeader::new_non_verbose_log(
+            self.log_level,
+            self.application_id,
+            self.context_id,
+        );
+        extended_header.set_is_verbose(true);
+        extended_header.number_of_arguments = self.number_of_arguments;
+        self.fields.header(extended_header)
+    }
+}
+
+/// Writer appending to a vector that stops (& remembers) if the given
+/// end would be exceeded.
+#[cfg(feature = "std")]
+struct TruncatingWriter<'a> {
+    buffer: &'a mut Vec<u8>,
+    end: usize,
+    truncated: bool,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Write for TruncatingWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if false == self.truncated {
+            let remaining = self.end - self.buffer.len();
+            if s.len() <= remaining {
+                self.buffer.extend_from_slice(s.as_bytes());
+            } else {
+                let len = char_boundary(s.as_bytes(), remaining);
+                self.buffer.extend_from_slice(&s.as_bytes()[..len]);
+                self.truncated = true;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the biggest length smaller or equal to `max_len` at which
+/// the given UTF-8 string can be cut.
+#[cfg(feature = "std")]
+fn char_boundary(utf8: &[u8], max_len: usize) -> usize {
+    if max_len >= utf8.len() {
+        return utf8.len();
+    }
+    let mut len = max_len;
+    // continuation bytes have the form 0b10xx_xxxx
+    while len > 0 && utf8[len] & 0b1100_0000 == 0b1000_0000 {
+        len -= 1;
+    }
+    len
+}
+
+#[cfg(all(feature = "std", test))]
+mod tests {
+    use super::*;
+    use crate::verbose::*;
+    use crate::{DltMessageType, DltPacketSlice};
+    use alloc::{string::String, vec::Vec};
+    use proptest::prelude::*;
+
+    /// Argument with owned data that can be converted to a verbose value.
+    #[derive(Clone, Debug)]
+    enum Arg {
+        Bool(bool),
+        U32(u32),
+        I64(i64),
+        F64(f64),
+        Str(Option<String>, String),
+        Raw(Vec<u8>),
+    }
+
+    impl Arg {
+        fn value(&self) -> VerboseValue<'_> {
+            use VerboseValue as V;
+            match self {
+                Arg::Bool(value) => V::Bool(BoolValue {
+                    name: None,
+                    value: *value,
+                }),
+                Arg::U32(value) => V::U32(U32Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: *value,
+                }),
+                Arg::I64(value) => V::I64(I64Value {
+                    variable_info: Some(VariableInfoUnit {
+                        name: "value",
+                        unit: "unit",
+                    }),
+                    scaling: None,
+                    value: *value,
+                }),
+                Arg::F64(value) => V::F64(F64Value {
+                    variable_info: None,
+                    value: *value,
+                }),
+                Arg::Str(name, value) => V::Str(StringValue {
+                    name: name.as_deref(),
+                    value,
+                }),
+                Arg::Raw(data) => V::Raw(RawValue { name: None, data }),
+            }
+        }
+    }
+
+    fn arg_any() -> impl Strategy<Value = Arg> {
+        prop_oneof![
+            any::<bool>().prop_map(Arg::Bool),
+            any::<u32>().prop_map(Arg::U32),
+            any::<i64>().prop_map(Arg::I64),
+            any::<f64>()
+                .prop_filter("NaN is not equal to itself", |v| !v.is_nan())
+                .prop_map(Arg::F64),
+            (proptest::option::of("\\PC{0,10}"), "\\PC{0,30}")
+                .prop_map(|(name, value)| Arg::Str(name, value)),
+            proptest::collection::vec(any::<u8>(), 0..30).prop_map(Arg::Raw),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn build_read(
+            ref args in proptest::collection::vec(arg_any(), 0..10),
+            is_big_endian in any::<bool>(),
+            message_counter in any::<u8>(),
+            ecu_id in any::<Option<[u8; 4]>>(),
+            session_id in any::<Option<u32>>(),
+            timestamp in any::<Option<u32>>(),
+        ) {
+            let mut builder = DltVerboseMessageBuilder::<2048>::new(*b"APP1", *b"CTX1", DltLogLevel::Warn)
+                .with_big_endian(is_big_endian)
+                .with_message_counter(message_counter);
+            if let Some(ecu_id) = ecu_id {
+                builder = builder.with_ecu_id(ecu_id);
+            }
+            if let Some(session_id) = session_id {
+                builder = builder.with_session_id(session_id);
+            }
+            if let Some(timestamp) = timestamp {
+                builder = builder.with_timestamp(timestamp);
+            }
+            for arg in args {
+                builder.add_value(&arg.value()).unwrap();
+            }
+
+            // build
+            let mut buffer = Vec::new();
+            builder.build(&mut buffer).unwrap();
+
+            // write
+            {
+                let mut written = Vec::new();
+                builder.write(&mut written).unwrap();
+                prop_assert_eq!(&buffer, &written);
+            }
+
+            // read back
+            let packet = DltPacketSlice::from_slice(&buffer).unwrap();
+            prop_assert_eq!(buffer.len(), packet.slice().len());
+            prop_assert!(packet.is_verbose());
+            prop_assert_eq!(
+                Some(DltMessageType::Log(DltLogLevel::Warn)),
+                packet.message_type()
+            );
+            let header = packet.header();
+            prop_assert_eq!(is_big_endian, header.is_big_endian);
+            prop_assert_eq!(message_counter, header.message_counter);
+            prop_assert_eq!(ecu_id, header.ecu_id);
+            prop_assert_eq!(session_id, header.session_id);
+            prop_assert_eq!(timestamp, header.timestamp);
+            let ext = header.extended_header.unwrap();
+            prop_assert_eq!(args.len(), usize::from(ext.number_of_arguments));
+            prop_assert_eq!(*b"APP1", ext.application_id);
+            prop_assert_eq!(*b"CTX1", ext.context_id);
+
+            let decoded: Vec<_> = packet
+                .verbose_value_iter()
+                .unwrap()
+                .map(|v| v.unwrap())
+                .collect();
+            let expected: Vec<_> = args.iter().map(|a| a.value()).collect();
+            prop_assert_eq!(expected, decoded);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn add_named_read(
+            ref name in prop_oneof![Just(String::new()), "\\PC{1,10}"],
+            ref unit in prop_oneof![Just(String::new()), "\\PC{1,10}"],
+            ref text in "\\PC{0,10}",
+            is_big_endian in any::<bool>(),
+            v_bool in any::<bool>(),
+            v_u8 in any::<u8>(),
+            v_u16 in any::<u16>(),
+            v_u32 in any::<u32>(),
+            v_u64 in any::<u64>(),
+            v_i8 in any::<i8>(),
+            v_i16 in any::<i16>(),
+            v_i32 in any::<i32>(),
+            v_i64 in any::<i64>(),
+            v_f32 in any::<f32>().prop_filter("NaN is not equal to itself", |v| !v.is_nan()),
+            v_f64 in any::<f64>().prop_filter("NaN is not equal to itself", |v| !v.is_nan()),
+        ) {
+            let mut builder = DltVerboseMessageBuilder::<1024>::new(*b"APP1", *b"CTX1", DltLogLevel::Info)
+                .with_big_endian(is_big_endian);
+            builder.add_u8_named(name, unit, v_u8).unwrap();
+            builder.add_u16_named(name, unit, v_u16).unwrap();
+            builder.add_u32_named(name, unit, v_u32).unwrap();
+            builder.add_u64_named(name, unit, v_u64).unwrap();
+            builder.add_i8_named(name, unit, v_i8).unwrap();
+            builder.add_i16_named(name, unit, v_i16).unwrap();
+            builder.add_i32_named(name, unit, v_i32).unwrap();
+            builder.add_i64_named(name, unit, v_i64).unwrap();
+            builder.add_f32_named(name, unit, v_f32).unwrap();
+            builder.add_f64_named(name, unit, v_f64).unwrap();
+            builder.add_bool_named(name, v_bool).unwrap();
+            builder.add_str_named(name, text).unwrap();
+
+            let mut buffer = Vec::new();
+            builder.build(&mut buffer).unwrap();
+            let packet = DltPacketSlice::from_slice(&buffer).unwrap();
+            let decoded: Vec<_> = packet
+                .verbose_value_iter()
+                .unwrap()
+                .map(|v| v.unwrap())
+                .collect();
+
+            use VerboseValue as V;
+            let variable_info = Some(VariableInfoUnit { name, unit });
+            let expected = [
+                V::U8(U8Value { variable_info: variable_info.clone(), scaling: None, value: v_u8 }),
+                V::U16(U16Value { variable_info: variable_info.clone(), scaling: None, value: v_u16 }),
+                V::U32(U32Value { variable_info: variable_info.clone(), scaling: None, value: v_u32 }),
+                V::U64(U64Value { variable_info: variable_info.clone(), scaling: None, value: v_u64 }),
+                V::I8(I8Value { variable_info: variable_info.clone(), scaling: None, value: v_i8 }),
+                V::I16(I16Value { variable_info: variable_info.clone(), scaling: None, value: v_i16 }),
+                V::I32(I32Value { variable_info: variable_info.clone(), scaling: None, value: v_i32 }),
+                V::I64(I64Value { variable_info: variable_info.clone(), scaling: None, value: v_i64 }),
+                V::F32(F32Value { variable_info: variable_info.clone(), value: v_f32 }),
+                V::F64(F64Value { variable_info, value: v_f64 }),
+                V::Bool(BoolValue { name: Some(name), value: v_bool }),
+                V::Str(StringValue { name: Some(name), value: text }),
+            ];
+            prop_assert_eq!(&expected[..], &decoded[..]);
+            for value in &decoded {
+                prop_assert_eq!(Some(name.as_str()), value.name());
+            }
+        }
+    }
+
+    #[test]
+    fn add_named_empty_name_unit() {
+        let mut builder =
+            DltVerboseMessageBuilder::<64>::new(*b"APP1", *b"CTX1", DltLogLevel::Info);
+        builder.add_u32_named("", "", 0x1234_5678).unwrap();
+        builder.add_u32_named("a", "", 1).unwrap();
+        assert_eq!(
+            &[
+                0x43, 0x08, 0, 0, // type info (UINT, 32 bit, VARI)
+                0, 0, 0, 0, // name & unit length (no terminating zeros)
+                0x78, 0x56, 0x34, 0x12, // value
+                0x43, 0x08, 0, 0, // type info (UINT, 32 bit, VARI)
+                2, 0, 0, 0, // name & unit length
+                b'a', 0, // name
+                1, 0, 0, 0, // value
+            ][..],
+            builder.payload()
+        );
+    }
+
+    #[test]
+    fn add_named_errors() {
+        // capacity errors do not add an argument
+        let mut builder = DltVerboseMessageBuilder::<8>::new(*b"APP1", *b"CTX1", DltLogLevel::Info);
+        assert_eq!(
+            Err(VerboseEncodeError::Capacity(arrayvec::CapacityError::new(
+                ()
+            ))),
+            builder.add_u32_named("name", "unit", 1)
+        );
+        assert_eq!(0, builder.number_of_arguments());
+        assert!(builder.payload().is_empty());
+
+        // name or unit too long (the capacity check happens first)
+        let name = "a".repeat(usize::from(u16::MAX));
+        let mut builder =
+            DltVerboseMessageBuilder::<0x20000>::new(*b"APP1", *b"CTX1", DltLogLevel::Info);
+        assert_eq!(
+            Err(VerboseEncodeError::NameTooLong(name.len())),
+            builder.add_f64_named(&name, "", 1.0)
+        );
+        assert_eq!(
+            Err(VerboseEncodeError::UnitTooLong(name.len())),
+            builder.add_i8_named("", &name, 1)
+        );
+        assert_eq!(0, builder.number_of_arguments());
+        assert!(builder.payload().is_empty());
+
+        // values added afterwards still decode
+        builder.add_u8_named("b", "", 2).unwrap();
+        let mut buffer = Vec::new();
+        builder.build(&mut buffer).unwrap();
+        let packet = DltPacketSlice::from_slice(&buffer).unwrap();
+        let mut iter = packet.verbose_value_iter().unwrap();
+        assert_eq!(
+            Some(Ok(VerboseValue::U8(U8Value {
+                variable_info: Some(VariableInfoUnit {
+                    name: "b",
+                    unit: "",
+                }),
+                scaling: None,
+                value: 2,
+            }))),
+            iter.next()
+        );
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn hello_world() {
+        // reference "Hello world" message (ecu ECU1, app APP1, context
+        // CTX1, log info, verbose with 1 argument)
+        let expected = [
+            0x35, 0x00, 0x00, 0x28, // header type, counter & length
+            b'E', b'C', b'U', b'1', // ecu id
+            0x00, 0x00, 0x00, 0x64, // timestamp
+            0x41, 0x01, b'A', b'P', b'P', b'1', b'C', b'T', b'X', b'1', // extended header
+            0x00, 0x82, 0x00, 0x00, // type info (STRG, SCOD UTF-8)
+            0x0c, 0x00, // string length (including terminating zero)
+            b'H', b'e', b'l', b'l', b'o', b' ', b'w', b'o', b'r', b'l', b'd', 0x00,
+        ];
+
+        let mut builder =
+            DltVerboseMessageBuilder::<64>::new(*b"APP1", *b"CTX1", DltLogLevel::Info)
+                .with_ecu_id(*b"ECU1")
+                .with_timestamp(100);
+        builder
+            .add_value(&VerboseValue::Str(StringValue {
+                name: None,
+                value: "Hello world",
+            }))
+            .unwrap();
+        let mut buffer = Vec::new();
+        builder.build(&mut buffer).unwrap();
+        assert_eq!(&expected[..], &buffer[..]);
+    }
+
+    #[test]
+    fn too_many_arguments() {
+        let mut builder =
+            DltVerboseMessageBuilder::<2048>::new(*b"APP1", *b"CTX1", DltLogLevel::Info);
+        let value = VerboseValue::Bool(BoolValue {
+            name: None,
+            value: true,
+        });
+        for _ in 0..u8::MAX {
+            builder.add_value(&value).unwrap();
+        }
+        assert_eq!(
+            Err(VerboseEncodeError::TooManyArguments),
+            builder.add_value(&value)
+        );
+        assert_eq!(u8::MAX, builder.number_of_arguments());
+        assert_eq!(usize::from(u8::MAX) * 5, builder.payload().len());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn with_big_endian_after_add() {
+        let mut builder =
+            DltVerboseMessageBuilder::<64>::new(*b"APP1", *b"CTX1", DltLogLevel::Info);
+        builder
+            .add_value(&VerboseValue::Bool(BoolValue {
+                name: None,
+                value: true,
+            }))
+            .unwrap();
+        let _ = builder.with_big_endian(true);
+    }
+
+    #[test]
+    fn capacity_error() {
+        let mut builder = DltVerboseMessageBuilder::<4>::new(*b"APP1", *b"CTX1", DltLogLevel::Info);
+        assert_eq!(
+            Err(VerboseEncodeError::Capacity(arrayvec::CapacityError::new(
+                ()
+            ))),
+            builder.add_value(&VerboseValue::Bool(BoolValue {
+                name: None,
+                value: true,
+            }))
+        );
+        assert_eq!(0, builder.number_of_arguments());
+        assert!(builder.payload().is_empty());
+    }
+
+    #[test]
+    fn message_too_long() {
+        let data = [0u8; 0xffff - 4 - 2 - 10];
+        let mut builder =
+            DltVerboseMessageBuilder::<0x10000>::new(*b"APP1", *b"CTX1", DltLogLevel::Info);
+        builder
+            .add_value(&VerboseValue::Raw(RawValue {
+                name: None,
+                data: &data,
+            }))
+            .unwrap();
+        // payload fits but header + payload exceed the length field
+        let length = 4 + 10 + 0xffff - 10;
+        assert_eq!(
+            Err(VerboseEncodeError::MessageTooLong(length)),
+            builder.header().map(|_| ())
+        );
+        assert_eq!(
+            Err(VerboseEncodeError::MessageTooLong(length)),
+            builder.build(&mut Vec::new())
+        );
+        assert_matches!(
+            builder.write(&mut Vec::new()),
+            Err(WriteError::MessageTooLong { index: 0, .. })
+        );
+    }
+
+    /// Decodes the message in the buffer & returns the header & the
+    /// text of the last (string) argument.
+    fn read_log_message(buffer: &[u8]) -> (DltHeader, String) {
+        let packet = DltPacketSlice::from_slice(buffer).unwrap();
+        assert_eq!(buffer.len(), packet.slice().len());
+        let value = packet
+            .verbose_value_iter()
+            .unwrap()
+            .map(|v| v.unwrap())
+            .last()
+            .unwrap();
+        match value {
+            VerboseValue::Str(s) => (packet.header(), String::from(s.value)),
+            _ => panic!("unexpected value {:?}", value),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn build_log_message_read(
+            ref text in "\\PC{0,100}",
+            number in any::<u32>(),
+            is_big_endian in any::<bool>(),
+            message_counter in any::<u8>(),
+            ref prefix in proptest::collection::vec(any::<u8>(), 0..10),
+        ) {
+            // free function
+            {
+                let mut buffer = prefix.clone();
+                build_log_message(
+                    *b"APP1",
+                    *b"CTX1",
+                    DltLogLevel::Debug,
+                    format_args!("{}: {}", text, number),
+                    &mut buffer,
+                )
+                .unwrap();
+                prop_assert_eq!(&prefix[..], &buffer[..prefix.len()]);
+
+                let (header, decoded) = read_log_message(&buffer[prefix.len()..]);
+                prop_assert_eq!(alloc::format!("{}: {}", text, number), decoded);
+                prop_assert_eq!(usize::from(header.length), buffer.len() - prefix.len());
+                prop_assert_eq!(0, header.message_counter);
+                let ext = header.extended_header.unwrap();
+                prop_assert_eq!(1, ext.number_of_arguments);
+                prop_assert_eq!(Some(DltMessageType::Log(DltLogLevel::Debug)), ext.message_type());
+            }
+
+            // builder with previous arguments
+            {
+                let mut builder = DltVerboseMessageBuilder::<64>::new(*b"APP1", *b"CTX1", DltLogLevel::Info)
+                    .with_big_endian(is_big_endian)
+                    .with_message_counter(message_counter)
+                    .with_ecu_id(*b"ECU1");
+                builder.add_value(&VerboseValue::U32(U32Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: number,
+                })).unwrap();
+
+                let mut buffer = Vec::new();
+                builder.build_formatted(format_args!("{}", text), &mut buffer).unwrap();
+
+                let (header, decoded) = read_log_message(&buffer);
+                prop_assert_eq!(text, &decoded);
+                prop_assert_eq!(usize::from(header.length), buffer.len());
+                prop_assert_eq!(message_counter, header.message_counter);
+                prop_assert_eq!(is_big_endian, header.is_big_endian);
+                prop_assert_eq!(Some(*b"ECU1"), header.ecu_id);
+                prop_assert_eq!(2, header.extended_header.unwrap().number_of_arguments);
+                // the builder itself stays unchanged
+                prop_assert_eq!(1, builder.number_of_arguments());
+            }
+        }
+    }
+
+    #[test]
+    fn build_log_message_truncation() {
+        // header (4 + 10), type info (4), string length (2) & zero termination
+        const MAX_TEXT_LEN: usize = 0xffff - 4 - 10 - 4 - 2 - 1;
+
+        let build = |text: &str| {
+            let mut buffer = Vec::new();
+            build_log_message(
+                *b"APP1",
+                *b"CTX1",
+                DltLogLevel::Info,
+                format_args!("{}", text),
+                &mut buffer,
+            )
+            .unwrap();
+            let (header, decoded) = read_log_message(&buffer);
+            assert_eq!(usize::from(header.length), buffer.len());
+            decoded
+        };
+
+        // fits exactly
+        {
+            let text = "a".repeat(MAX_TEXT_LEN);
+            assert_eq!(text, build(&text));
+        }
+
+        // one byte too long
+        {
+            let text = "a".repeat(MAX_TEXT_LEN + 1);
+            let decoded = build(&text);
+            assert_eq!(MAX_TEXT_LEN, decoded.len());
+            assert!(decoded.ends_with(TRUNCATION_MARKER));
+            assert_eq!(
+                &text[..MAX_TEXT_LEN - TRUNCATION_MARKER.len()],
+                &decoded[..MAX_TEXT_LEN - TRUNCATION_MARKER.len()]
+            );
+        }
+
+        // multi byte characters are not split
+        {
+            let text = "\u{1F980}".repeat(MAX_TEXT_LEN / 4 + 1);
+            let decoded = build(&text);
+            assert!(decoded.len() <= MAX_TEXT_LEN);
+            assert!(decoded.ends_with(TRUNCATION_MARKER));
+            let cut = &decoded[..decoded.len() - TRUNCATION_MARKER.len()];
+            assert!(text.starts_with(cut));
+            assert_eq!(0, cut.len() % 4);
+        }
+    }
+
+    #[test]
+    fn build_formatted_errors() {
+        /// Display implementation that always fails.
+        struct Failing;
+
+        impl core::fmt::Display for Failing {
+            fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                Err(core::fmt::Error)
+            }
+        }
+
+        // format error (nothing is appended)
+        {
+            let mut buffer = alloc::vec![1, 2, 3];
+            assert_eq!(
+                Err(VerboseEncodeError::Fmt),
+                build_log_message(
+                    *b"APP1",
+                    *b"CTX1",
+                    DltLogLevel::Info,
+                    format_args!("a{}", Failing),
+                    &mut buffer,
+                )
+            );
+            assert_eq!(&[1, 2, 3], &buffer[..]);
+        }
+
+        // too many arguments
+        {
+            let mut builder =
+                DltVerboseMessageBuilder::<2048>::new(*b"APP1", *b"CTX1", DltLogLevel::Info);
+            let value = VerboseValue::Bool(BoolValue {
+                name: None,
+                value: true,
+            });
+            for _ in 0..u8::MAX {
+                builder.add_value(&value).unwrap();
+            }
+            let mut buffer = Vec::new();
+            assert_eq!(
+                Err(VerboseEncodeError::TooManyArguments),
+                builder.build_formatted(format_args!("a"), &mut buffer)
+            );
+            assert!(buffer.is_empty());
+        }
+
+        // previous arguments already exceed the message length
+        {
+            let data = [0u8; 0xffff - 4 - 2 - 10];
+            let mut builder =
+                DltVerboseMessageBuilder::<0x10000>::new(*b"APP1", *b"CTX1", DltLogLevel::Info);
+            builder
+                .add_value(&VerboseValue::Raw(RawValue {
+                    name: None,
+                    data: &data,
+                }))
+                .unwrap();
+            let mut buffer = Vec::new();
+            assert_eq!(
+                Err(VerboseEncodeError::MessageTooLong(
+                    4 + 10 + 0xffff - 10 + 4 + 2 + 1
+                )),
+                builder.build_formatted(format_args!("a"), &mut buffer)
+            );
+            assert!(buffer.is_empty());
+        }
+    }
+}
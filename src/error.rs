@@ -1,4 +1,5 @@
 use super::*;
+use arrayvec::CapacityError;
 use core::fmt;
 use core::str::Utf8Error;
 
@@ -7,6 +8,7 @@ use alloc::format;
 
 /// Error in which an error occured.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Layer {
     /// Error occured while parsing or writing the DLT header.
     DltHeader,
@@ -14,6 +16,16 @@ pub enum Layer {
     VerboseTypeInfo,
     /// Error occured while parsing or writing a verbose value.
     VerboseValue,
+    /// Error occured while parsing or writing a control message payload.
+    ControlMessage,
+    /// Error occured while parsing a network trace payload.
+    NetworkTrace,
+    /// Error occured while parsing a storage header.
+    StorageHeader,
+    /// Error occured while parsing a serial header.
+    SerialHeader,
+    /// Error occured while parsing a SOME/IP header.
+    SomeIpHeader,
 }
 
 #[cfg(test)]
@@ -33,6 +45,79 @@ mod layer_test {
     }
 }
 
+/// Kind of a verbose value as determined by the type flags in the type
+/// info (used in errors to describe which type a contradiction relates to).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TypeInfoKind {
+    /// Type info with the BOOL flag set.
+    Bool,
+    /// Type info with the SINT flag set.
+    Signed,
+    /// Type info with the UINT flag set.
+    Unsigned,
+    /// Type info with the FLOA flag set.
+    Float,
+    /// Type info with the STRG flag set.
+    String,
+    /// Type info with the RAWD flag set.
+    Raw,
+    /// Type info with the TRAI flag set.
+    TraceInfo,
+    /// Type info with the STRU flag set.
+    Struct,
+}
+
+impl fmt::Display for TypeInfoKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use TypeInfoKind::*;
+        match self {
+            Bool => write!(f, "bool"),
+            Signed => write!(f, "signed integer"),
+            Unsigned => write!(f, "unsigned integer"),
+            Float => write!(f, "float"),
+            String => write!(f, "string"),
+            Raw => write!(f, "raw"),
+            TraceInfo => write!(f, "trace info"),
+            Struct => write!(f, "struct"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod type_info_kind_tests {
+    use super::*;
+
+    #[test]
+    fn clone_eq() {
+        use TypeInfoKind::*;
+        assert_eq!(Bool, Bool.clone());
+    }
+
+    #[test]
+    fn debug() {
+        use TypeInfoKind::*;
+        assert_eq!("TraceInfo", format!("{:?}", TraceInfo));
+    }
+
+    #[test]
+    fn display() {
+        use TypeInfoKind::*;
+        let tests = [
+            (Bool, "bool"),
+            (Signed, "signed integer"),
+            (Unsigned, "unsigned integer"),
+            (Float, "float"),
+            (String, "string"),
+            (Raw, "raw"),
+            (TraceInfo, "trace info"),
+            (Struct, "struct"),
+        ];
+        for (kind, expected) in tests {
+            assert_eq!(expected, format!("{}", kind));
+        }
+    }
+}
+
 /// Error if the length field in a DLT headeris smaller then the header the calculated
 /// header size based on the flags (+ minimum payload size of 4 bytes/octetets)
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -118,6 +203,7 @@ mod dlt_message_length_too_small_error_test {
 
 /// Errors that can occur when slicing a DLT packet.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum PacketSliceError {
     /// An unsupporetd version number has been encountered
     /// while decoding the header.
@@ -129,6 +215,11 @@ pub enum PacketSliceError {
 
     /// Error if a slice did not contain enough data to decode a value.
     UnexpectedEndOfSlice(UnexpectedEndOfSliceError),
+
+    /// Error if the message info in the extended header contains reserved
+    /// values (only returned by the strict decoding functions like
+    /// [`crate::DltPacketSlice::from_slice_strict`]).
+    ReservedMessageInfo(u8),
 }
 
 impl fmt::Display for PacketSliceError {
@@ -138,6 +229,11 @@ impl fmt::Display for PacketSliceError {
             UnsupportedDltVersion(v) => v.fmt(f),
             MessageLengthTooSmall(v) => v.fmt(f),
             UnexpectedEndOfSlice(v) => v.fmt(f),
+            ReservedMessageInfo(v) => write!(
+                f,
+                "DLT Header Error: Message info {:#010b} in the extended header contains reserved values.",
+                v
+            ),
         }
     }
 }
@@ -150,6 +246,42 @@ impl std::error::Error for PacketSliceError {
             UnsupportedDltVersion(v) => Some(v),
             MessageLengthTooSmall(v) => Some(v),
             UnexpectedEndOfSlice(v) => Some(v),
+            ReservedMessageInfo(_) => None,
+        }
+    }
+}
+
+impl PacketSliceError {
+    /// Layer in which the error occured.
+    pub fn layer(&self) -> Layer {
+        use PacketSliceError::*;
+        match self {
+            UnexpectedEndOfSlice(v) => v.layer.clone(),
+            UnsupportedDltVersion(_) | MessageLengthTooSmall(_) | ReservedMessageInfo(_) => {
+                Layer::DltHeader
+            }
+        }
+    }
+
+    /// Returns true if the data only ended before the packet was complete
+    /// (decoding can be retried once more data has arrived) & false if
+    /// the data is corrupt & will never decode (e.g. unsupported version
+    /// or a length smaller then the header).
+    pub fn is_recoverable_with_more_data(&self) -> bool {
+        use PacketSliceError::*;
+        match self {
+            UnexpectedEndOfSlice(_) => true,
+            UnsupportedDltVersion(_) | MessageLengthTooSmall(_) | ReservedMessageInfo(_) => false,
+        }
+    }
+
+    /// Minimum number of additional bytes needed before decoding can be
+    /// retried (`None` if the error is not recoverable with more data,
+    /// see [`PacketSliceError::is_recoverable_with_more_data`]).
+    pub fn additional_bytes_needed(&self) -> Option<usize> {
+        match self {
+            PacketSliceError::UnexpectedEndOfSlice(err) => Some(err.additional_bytes_needed()),
+            _ => None,
         }
     }
 }
@@ -212,6 +344,10 @@ mod packet_slice_error_test {
                 format!("{}", UnexpectedEndOfSlice(inner.clone())),
             );
         }
+        assert_eq!(
+            "DLT Header Error: Message info 0b01111000 in the extended header contains reserved values.",
+            format!("{}", ReservedMessageInfo(0b0111_1000))
+        );
     }
 
     #[cfg(feature = "std")]
@@ -237,6 +373,56 @@ mod packet_slice_error_test {
         })
         .source()
         .is_some());
+        assert!(ReservedMessageInfo(0).source().is_none());
+    }
+
+    #[test]
+    fn layer() {
+        use PacketSliceError::*;
+        assert_eq!(
+            Layer::DltHeader,
+            UnsupportedDltVersion(UnsupportedDltVersionError {
+                unsupported_version: 123,
+            })
+            .layer()
+        );
+        assert_eq!(Layer::DltHeader, ReservedMessageInfo(0).layer());
+        assert_eq!(
+            Layer::StorageHeader,
+            UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                actual_size: 1,
+                layer: Layer::StorageHeader,
+                minimum_size: 3,
+            })
+            .layer()
+        );
+    }
+
+    #[test]
+    fn is_recoverable_with_more_data() {
+        use PacketSliceError::*;
+
+        let v = UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+            actual_size: 1,
+            layer: Layer::DltHeader,
+            minimum_size: 3,
+        });
+        assert!(v.is_recoverable_with_more_data());
+        assert_eq!(Some(2), v.additional_bytes_needed());
+
+        for v in [
+            UnsupportedDltVersion(UnsupportedDltVersionError {
+                unsupported_version: 123,
+            }),
+            MessageLengthTooSmall(DltMessageLengthTooSmallError {
+                required_length: 4,
+                actual_length: 3,
+            }),
+            ReservedMessageInfo(0),
+        ] {
+            assert!(false == v.is_recoverable_with_more_data());
+            assert_eq!(None, v.additional_bytes_needed());
+        }
     }
 }
 
@@ -272,6 +458,14 @@ impl std::error::Error for UnexpectedEndOfSliceError {
     }
 }
 
+impl UnexpectedEndOfSliceError {
+    /// Number of bytes missing to reach the minimum expected size.
+    #[inline]
+    pub fn additional_bytes_needed(&self) -> usize {
+        self.minimum_size.saturating_sub(self.actual_size)
+    }
+}
+
 #[cfg(test)]
 mod unexpected_end_of_slice_error_test {
     use super::*;
@@ -332,6 +526,18 @@ mod unexpected_end_of_slice_error_test {
         .source()
         .is_none());
     }
+
+    #[test]
+    fn additional_bytes_needed() {
+        let v = |minimum_size, actual_size| UnexpectedEndOfSliceError {
+            layer: Layer::DltHeader,
+            minimum_size,
+            actual_size,
+        };
+        assert_eq!(3, v(5, 2).additional_bytes_needed());
+        assert_eq!(0, v(2, 2).additional_bytes_needed());
+        assert_eq!(0, v(2, 3).additional_bytes_needed());
+    }
 }
 
 /// Error that is triggered when an unsupported DLT version is
@@ -414,6 +620,7 @@ mod unsupported_dlt_version_error_test {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum VerboseDecodeError {
     /// Error that occurs if a type info is inconsistent.
     ///
@@ -424,6 +631,56 @@ pub enum VerboseDecodeError {
     /// The encoded type info is given as an argument.
     InvalidTypeInfo([u8; 4]),
 
+    /// Error if more than one of the mutually exclusive base type flags
+    /// (BOOL, SINT, UINT, FLOA, STRG, RAWD, TRAI & STRU) is set in a
+    /// type info.
+    ConflictingTypeFlags {
+        /// Type of the first set base type flag.
+        first: TypeInfoKind,
+        /// Type of the second set base type flag.
+        second: TypeInfoKind,
+        /// Encoded type info.
+        type_info: [u8; 4],
+    },
+
+    /// Error if the type length (TYLE) in a type info is not valid for
+    /// the type.
+    InvalidTypeLength {
+        /// Type of the value.
+        kind: TypeInfoKind,
+        /// Encountered type length.
+        tyle: u8,
+        /// Encoded type info.
+        type_info: [u8; 4],
+    },
+
+    /// Error if the variable info flag (VARI) is set for a type that
+    /// does not support variable infos.
+    VariNotAllowedForType {
+        /// Type of the value.
+        kind: TypeInfoKind,
+        /// Encoded type info.
+        type_info: [u8; 4],
+    },
+
+    /// Error if the fixed point flag (FIXP) is set for a type other then
+    /// a signed or unsigned integer.
+    FixpNotAllowedForType {
+        /// Type of the value.
+        kind: TypeInfoKind,
+        /// Encoded type info.
+        type_info: [u8; 4],
+    },
+
+    /// Error if the string coding (SCOD) of a string or trace info value is
+    /// neither ASCII (0) nor UTF-8 (1).
+    InvalidStringCoding {
+        /// Encountered string coding.
+        scod: u8,
+        /// Encoded type info.
+        type_info: [u8; 4],
+    },
+
     /// Error in case an invalid bool value is encountered (not 0 or 1).
     InvalidBoolValue(u8),
 
@@ -442,8 +699,25 @@ pub enum VerboseDecodeError {
 
     StructDataLengthOverflow,
 
+    /// Error if structs are nested deeper then
+    /// [`crate::verbose::MAX_STRUCT_DEPTH`].
+    StructDepthExceeded,
+
     /// Error when decoding an string (can also occur for variable names or unit names).
     Utf8(Utf8Error),
+
+    /// Error if the data of an UTF-16 encoded string (non standard
+    /// extension, see [`crate::verbose::Utf16StringValue`]) has an odd
+    /// length or contains unpaired surrogates.
+    InvalidUtf16String,
+
+    /// Error if a string or trace info value contains a zero byte before
+    /// its terminating zero (only returned if
+    /// [`crate::ParseConfig::reject_embedded_null`] is set).
+    EmbeddedNull {
+        /// Position of the first zero byte in the string data.
+        position: usize,
+    },
 }
 
 impl fmt::Display for VerboseDecodeError {
@@ -453,6 +727,21 @@ impl fmt::Display for VerboseDecodeError {
             InvalidTypeInfo(value) => write!(
                 f, "DLT Verbose Message Field: Encountered an invalid typeinfo {:?} (contradicting or unknown)", value
             ),
+            ConflictingTypeFlags { first, second, type_info } => write!(
+                f, "DLT Verbose Message Field: Encountered typeinfo {:?} with the conflicting base type flags for {} and {} values set", type_info, first, second
+            ),
+            InvalidTypeLength { kind, tyle, type_info } => write!(
+                f, "DLT Verbose Message Field: Encountered type length (TYLE) {} in typeinfo {:?}, which is not a valid length for a {} value", tyle, type_info, kind
+            ),
+            VariNotAllowedForType { kind, type_info } => write!(
+                f, "DLT Verbose Message Field: The variable info flag (VARI) is set in typeinfo {:?}, but {} values can not have a variable info", type_info, kind
+            ),
+            FixpNotAllowedForType { kind, type_info } => write!(
+                f, "DLT Verbose Message Field: The fixed point flag (FIXP) is set in typeinfo {:?}, but only integer values can be fixed point values (type is {})", type_info, kind
+            ),
+            InvalidStringCoding { scod, type_info } => write!(
+                f, "DLT Verbose Message Field: Encountered unknown string coding (SCOD) {} in typeinfo {:?} (only 0 = ASCII and 1 = UTF-8 are defined)", scod, type_info
+            ),
             InvalidBoolValue(value) => write!(
                 f, "DLT Verbose Message Field: Encountered invalid bool value '{}' (only 0 or 1 are valid)", value
             ),
@@ -466,6 +755,13 @@ impl fmt::Display for VerboseDecodeError {
             Utf8(err) => err.fmt(f),
             ArrayDimensionsOverflow => write!(f, "DLT Verbose Message Field: Array dimension sizes too big. Calculating the overall array size would cause an integer overflow."),
             StructDataLengthOverflow => write!(f, "DLT Verbose Message Field: Struct data length too big. Would cause an integer overflow."),
+            StructDepthExceeded => write!(f, "DLT Verbose Message Field: Structs are nested deeper then the supported maximum of {} levels.", crate::verbose::MAX_STRUCT_DEPTH),
+            InvalidUtf16String => write!(
+                f, "DLT Verbose Message Field: Encountered an invalid UTF-16 string (odd length or unpaired surrogate)"
+            ),
+            EmbeddedNull { position } => write!(
+                f, "DLT Verbose Message Field: Encountered a string containing a zero value at position {} (before the terminating zero)", position
+            ),
         }
     }
 }
@@ -476,6 +772,11 @@ impl std::error::Error for VerboseDecodeError {
         use VerboseDecodeError::*;
         match self {
             InvalidTypeInfo(_) => None,
+            ConflictingTypeFlags { .. } => None,
+            InvalidTypeLength { .. } => None,
+            VariNotAllowedForType { .. } => None,
+            FixpNotAllowedForType { .. } => None,
+            InvalidStringCoding { .. } => None,
             InvalidBoolValue(_) => None,
             UnexpectedEndOfSlice(err) => Some(err),
             VariableNameStringMissingNullTermination => None,
@@ -483,6 +784,9 @@ impl std::error::Error for VerboseDecodeError {
             Utf8(err) => Some(err),
             ArrayDimensionsOverflow => None,
             StructDataLengthOverflow => None,
+            StructDepthExceeded => None,
+            InvalidUtf16String => None,
+            EmbeddedNull { .. } => None,
         }
     }
 }
@@ -493,6 +797,59 @@ impl From<Utf8Error> for VerboseDecodeError {
     }
 }
 
+impl VerboseDecodeError {
+    /// Layer in which the error occured ([`Layer::VerboseTypeInfo`] for
+    /// inconsistent type infos & [`Layer::VerboseValue`] for errors in
+    /// the value data).
+    pub fn layer(&self) -> Layer {
+        use VerboseDecodeError::*;
+        match self {
+            InvalidTypeInfo(_)
+            | ConflictingTypeFlags { .. }
+            | InvalidTypeLength { .. }
+            | VariNotAllowedForType { .. }
+            | FixpNotAllowedForType { .. }
+            | InvalidStringCoding { .. } => Layer::VerboseTypeInfo,
+            UnexpectedEndOfSlice(v) => v.layer.clone(),
+            InvalidBoolValue(_)
+            | VariableNameStringMissingNullTermination
+            | VariableUnitStringMissingNullTermination
+            | ArrayDimensionsOverflow
+            | StructDataLengthOverflow
+            | StructDepthExceeded
+            | Utf8(_)
+            | InvalidUtf16String
+            | EmbeddedNull { .. } => Layer::VerboseValue,
+        }
+    }
+
+    /// Returns true if the data ended before the value was complete
+    /// (e.g. when decoding a payload that arrives in chunks, see
+    /// [`crate::verbose::StreamingVerboseDecoder`]) & false if the data
+    /// is invalid.
+    pub fn is_recoverable_with_more_data(&self) -> bool {
+        use VerboseDecodeError::*;
+        match self {
+            UnexpectedEndOfSlice(_) => true,
+            InvalidTypeInfo(_)
+            | ConflictingTypeFlags { .. }
+            | InvalidTypeLength { .. }
+            | VariNotAllowedForType { .. }
+            | FixpNotAllowedForType { .. }
+            | InvalidStringCoding { .. }
+            | InvalidBoolValue(_)
+            | VariableNameStringMissingNullTermination
+            | VariableUnitStringMissingNullTermination
+            | ArrayDimensionsOverflow
+            | StructDataLengthOverflow
+            | StructDepthExceeded
+            | Utf8(_)
+            | InvalidUtf16String
+            | EmbeddedNull { .. } => false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod verbose_decode_error_tests {
     use super::*;
@@ -520,6 +877,31 @@ mod verbose_decode_error_tests {
             format!("{}", InvalidTypeInfo([1,2,3,4]))
         );
 
+        assert_eq!(
+            "DLT Verbose Message Field: Encountered typeinfo [49, 0, 0, 0] with the conflicting base type flags for bool and signed integer values set",
+            format!("{}", ConflictingTypeFlags { first: TypeInfoKind::Bool, second: TypeInfoKind::Signed, type_info: [0x31, 0, 0, 0] })
+        );
+
+        assert_eq!(
+            "DLT Verbose Message Field: Encountered type length (TYLE) 6 in typeinfo [38, 0, 0, 0], which is not a valid length for a signed integer value",
+            format!("{}", InvalidTypeLength{ kind: TypeInfoKind::Signed, tyle: 6, type_info: [0x26, 0, 0, 0] })
+        );
+
+        assert_eq!(
+            "DLT Verbose Message Field: The variable info flag (VARI) is set in typeinfo [0, 40, 0, 0], but trace info values can not have a variable info",
+            format!("{}", VariNotAllowedForType{ kind: TypeInfoKind::TraceInfo, type_info: [0, 0x28, 0, 0] })
+        );
+
+        assert_eq!(
+            "DLT Verbose Message Field: The fixed point flag (FIXP) is set in typeinfo [131, 16, 0, 0], but only integer values can be fixed point values (type is float)",
+            format!("{}", FixpNotAllowedForType{ kind: TypeInfoKind::Float, type_info: [0x83, 0x10, 0, 0] })
+        );
+
+        assert_eq!(
+            "DLT Verbose Message Field: Encountered unknown string coding (SCOD) 2 in typeinfo [0, 2, 1, 0] (only 0 = ASCII and 1 = UTF-8 are defined)",
+            format!("{}", InvalidStringCoding{ scod: 2, type_info: [0, 2, 1, 0] })
+        );
+
         assert_eq!(
             format!("DLT Verbose Message Field: Encountered invalid bool value '{}' (only 0 or 1 are valid)", 2),
             format!("{}", InvalidBoolValue(2))
@@ -544,11 +926,24 @@ mod verbose_decode_error_tests {
             format!("{}", VariableUnitStringMissingNullTermination)
         );
 
+        assert_eq!(
+            "DLT Verbose Message Field: Structs are nested deeper then the supported maximum of 32 levels.",
+            format!("{}", StructDepthExceeded)
+        );
+
         #[allow(invalid_from_utf8)]
         {
             let v = std::str::from_utf8(&[0, 159, 146, 150]).unwrap_err();
             assert_eq!(format!("{}", v), format!("{}", Utf8(v)));
         }
+        assert_eq!(
+            "DLT Verbose Message Field: Encountered an invalid UTF-16 string (odd length or unpaired surrogate)",
+            format!("{}", InvalidUtf16String)
+        );
+        assert_eq!(
+            "DLT Verbose Message Field: Encountered a string containing a zero value at position 3 (before the terminating zero)",
+            format!("{}", EmbeddedNull { position: 3 })
+        );
     }
 
     #[cfg(feature = "std")]
@@ -558,6 +953,38 @@ mod verbose_decode_error_tests {
         use std::error::Error;
         use VerboseDecodeError::*;
         assert!(InvalidTypeInfo([1, 2, 3, 4]).source().is_none());
+        assert!(ConflictingTypeFlags {
+            first: TypeInfoKind::Bool,
+            second: TypeInfoKind::Signed,
+            type_info: [0x31, 0, 0, 0]
+        }
+        .source()
+        .is_none());
+        assert!(InvalidTypeLength {
+            kind: TypeInfoKind::Bool,
+            tyle: 2,
+            type_info: [0x12, 0, 0, 0]
+        }
+        .source()
+        .is_none());
+        assert!(VariNotAllowedForType {
+            kind: TypeInfoKind::TraceInfo,
+            type_info: [0, 0x28, 0, 0]
+        }
+        .source()
+        .is_none());
+        assert!(FixpNotAllowedForType {
+            kind: TypeInfoKind::Float,
+            type_info: [0x83, 0x10, 0, 0]
+        }
+        .source()
+        .is_none());
+        assert!(InvalidStringCoding {
+            scod: 2,
+            type_info: [0, 2, 1, 0]
+        }
+        .source()
+        .is_none());
         assert!(InvalidBoolValue(2).source().is_none());
         assert!(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
             layer: Layer::DltHeader,
@@ -568,9 +995,59 @@ mod verbose_decode_error_tests {
         .is_some());
         assert!(VariableNameStringMissingNullTermination.source().is_none());
         assert!(VariableUnitStringMissingNullTermination.source().is_none());
+        assert!(StructDepthExceeded.source().is_none());
         assert!(Utf8(std::str::from_utf8(&[0, 159, 146, 150]).unwrap_err())
             .source()
             .is_some());
+        assert!(InvalidUtf16String.source().is_none());
+        assert!(EmbeddedNull { position: 0 }.source().is_none());
+    }
+
+    #[test]
+    fn layer() {
+        use VerboseDecodeError::*;
+        assert_eq!(Layer::VerboseTypeInfo, InvalidTypeInfo([0; 4]).layer());
+        assert_eq!(
+            Layer::VerboseTypeInfo,
+            InvalidStringCoding {
+                scod: 2,
+                type_info: [0, 2, 1, 0]
+            }
+            .layer()
+        );
+        assert_eq!(Layer::VerboseValue, InvalidBoolValue(2).layer());
+        assert_eq!(Layer::VerboseValue, StructDepthExceeded.layer());
+        assert_eq!(Layer::VerboseValue, EmbeddedNull { position: 0 }.layer());
+        assert_eq!(
+            Layer::VerboseTypeInfo,
+            UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer: Layer::VerboseTypeInfo,
+                minimum_size: 4,
+                actual_size: 1,
+            })
+            .layer()
+        );
+    }
+
+    #[test]
+    fn is_recoverable_with_more_data() {
+        use VerboseDecodeError::*;
+        assert!(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+            layer: Layer::VerboseValue,
+            minimum_size: 4,
+            actual_size: 1,
+        })
+        .is_recoverable_with_more_data());
+        for v in [
+            InvalidTypeInfo([0; 4]),
+            InvalidBoolValue(2),
+            VariableNameStringMissingNullTermination,
+            StructDepthExceeded,
+            InvalidUtf16String,
+            EmbeddedNull { position: 0 },
+        ] {
+            assert!(false == v.is_recoverable_with_more_data());
+        }
     }
 
     #[test]
@@ -581,71 +1058,222 @@ mod verbose_decode_error_tests {
     }
 }
 
-/// Error that occurs when another pattern then
-/// [`crate::storage::StorageHeader::PATTERN_AT_START`] is encountered
-/// at the start when parsing a StorageHeader.
+/// Error that can occur when encoding a verbose value.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct StorageHeaderStartPatternError {
-    /// Encountered pattern at the start.
-    pub actual_pattern: [u8; 4],
+#[non_exhaustive]
+pub enum VerboseEncodeError {
+    /// Error if the target buffer has not enough capacity left
+    /// to store the encoded value.
+    Capacity(CapacityError),
+
+    /// Error if a string that should be encoded as ASCII contains
+    /// non ASCII characters.
+    StringNotAscii,
+
+    /// Error if a string value (including the terminating zero) is too
+    /// long to be represented by the u16 length field. The length
+    /// of the string (without the terminating zero) is given as argument.
+    StringTooLong(usize),
+
+    /// Error if a variable name (including the terminating zero) is too
+    /// long to be represented by the u16 length field. The length
+    /// of the name (without the terminating zero) is given as argument.
+    NameTooLong(usize),
+
+    /// Error if raw data is too long to be represented by the u16 length
+    /// field. The length of the data is given as argument.
+    RawDataTooLong(usize),
+
+    /// Error if the product of the array dimensions does not match
+    /// the number of given array elements.
+    ArrayDimensionsMismatch {
+        /// Product of all dimensions (`usize::MAX` if the product overflows).
+        dimensions_product: usize,
+        /// Number of elements that were given.
+        number_of_elements: usize,
+    },
+
+    /// Error if an encoded array would exceed the maximum size of a
+    /// dlt message. The size of the encoded array is given as argument.
+    ArrayTooLong(usize),
+
+    /// Error if more then `u16::MAX` entries are added to a struct.
+    TooManyStructEntries,
+
+    /// Error if structs are nested deeper then
+    /// [`crate::verbose::MAX_STRUCT_DEPTH`].
+    StructDepthExceeded,
+
+    /// Error if the encoded message (header & payload) would exceed the
+    /// maximum dlt message length of `u16::MAX` bytes. The length of the
+    /// message is given as argument.
+    MessageTooLong(usize),
+
+    /// Error if more then `u8::MAX` arguments are added to a verbose message.
+    TooManyArguments,
+
+    /// Error if a variable unit (including the terminating zero) is too
+    /// long to be represented by the u16 length field. The length
+    /// of the unit (without the terminating zero) is given as argument.
+    UnitTooLong(usize),
+
+    /// Error if formatting the arguments of a message failed (e.g. a
+    /// `Display` implementation returned an error).
+    Fmt,
 }
 
-impl fmt::Display for StorageHeaderStartPatternError {
+impl fmt::Display for VerboseEncodeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "Error when parsing DLT storage header. Expected pattern {:?} at start but got {:?}",
-            super::storage::StorageHeader::PATTERN_AT_START,
-            self.actual_pattern
-        )
+        use VerboseEncodeError::*;
+        match self {
+            Capacity(err) => err.fmt(f),
+            StringNotAscii => write!(
+                f, "DLT Verbose Message Field: String contains non ASCII characters and can not be encoded as ASCII"
+            ),
+            StringTooLong(len) => write!(
+                f, "DLT Verbose Message Field: String with length {} is too long (maximum is {} bytes)", len, u16::MAX - 1
+            ),
+            NameTooLong(len) => write!(
+                f, "DLT Verbose Message Field: Variable name with length {} is too long (maximum is {} bytes)", len, u16::MAX - 1
+            ),
+            RawDataTooLong(len) => write!(
+                f, "DLT Verbose Message Field: Raw data with length {} is too long (maximum is {} bytes)", len, u16::MAX
+            ),
+            ArrayDimensionsMismatch { dimensions_product, number_of_elements } => write!(
+                f, "DLT Verbose Message Field: Array dimensions describe {} elements but {} elements were given", dimensions_product, number_of_elements
+            ),
+            ArrayTooLong(len) => write!(
+                f, "DLT Verbose Message Field: Array with an encoded size of {} bytes is too big (maximum is {} bytes)", len, u16::MAX
+            ),
+            TooManyStructEntries => write!(
+                f, "DLT Verbose Message Field: Struct has too many entries (maximum is {})", u16::MAX
+            ),
+            StructDepthExceeded => write!(
+                f, "DLT Verbose Message Field: Structs are nested deeper then the supported maximum of {} levels.", crate::verbose::MAX_STRUCT_DEPTH
+            ),
+            MessageTooLong(len) => write!(
+                f, "DLT Message: Message with a length of {} bytes is too long (maximum is {} bytes)", len, u16::MAX
+            ),
+            TooManyArguments => write!(
+                f, "DLT Message: Verbose message has too many arguments (maximum is {})", u8::MAX
+            ),
+            UnitTooLong(len) => write!(
+                f, "DLT Verbose Message Field: Variable unit with length {} is too long (maximum is {} bytes)", len, u16::MAX - 1
+            ),
+            Fmt => write!(
+                f, "DLT Verbose Message Field: Formatting the message arguments failed."
+            ),
+        }
     }
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for StorageHeaderStartPatternError {
+impl std::error::Error for VerboseEncodeError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+        use VerboseEncodeError::*;
+        match self {
+            Capacity(err) => Some(err),
+            StringNotAscii => None,
+            StringTooLong(_) => None,
+            NameTooLong(_) => None,
+            RawDataTooLong(_) => None,
+            ArrayDimensionsMismatch { .. } => None,
+            ArrayTooLong(_) => None,
+            TooManyStructEntries => None,
+            StructDepthExceeded => None,
+            MessageTooLong(_) => None,
+            TooManyArguments => None,
+            UnitTooLong(_) => None,
+            Fmt => None,
+        }
+    }
+}
+
+impl From<CapacityError> for VerboseEncodeError {
+    fn from(err: CapacityError) -> VerboseEncodeError {
+        VerboseEncodeError::Capacity(err)
     }
 }
 
 #[cfg(test)]
-mod storage_header_start_pattern_error_tests {
+mod verbose_encode_error_tests {
     use super::*;
 
     #[test]
     fn clone_eq() {
-        let v = StorageHeaderStartPatternError {
-            actual_pattern: [1, 2, 3, 4],
-        };
+        use VerboseEncodeError::*;
+        let v = StringTooLong(65535);
         assert_eq!(v, v.clone());
     }
 
     #[test]
     fn debug() {
-        let v = StorageHeaderStartPatternError {
-            actual_pattern: [1, 2, 3, 4],
-        };
-        assert_eq!(
-            format!(
-                "StorageHeaderStartPatternError {{ actual_pattern: {:?} }}",
-                v.actual_pattern
-            ),
-            format!("{:?}", v)
-        );
+        use VerboseEncodeError::*;
+        let v = StringTooLong(65535);
+        assert_eq!(format!("StringTooLong({})", 65535), format!("{:?}", v));
     }
 
     #[test]
     fn display() {
-        let v = StorageHeaderStartPatternError {
-            actual_pattern: [1, 2, 3, 4],
-        };
+        use VerboseEncodeError::*;
+
+        assert_eq!(
+            format!("{}", CapacityError::new(())),
+            format!("{}", Capacity(CapacityError::new(())))
+        );
+        assert_eq!(
+            "DLT Verbose Message Field: String contains non ASCII characters and can not be encoded as ASCII",
+            format!("{}", StringNotAscii)
+        );
+        assert_eq!(
+            "DLT Verbose Message Field: String with length 65535 is too long (maximum is 65534 bytes)",
+            format!("{}", StringTooLong(65535))
+        );
+        assert_eq!(
+            "DLT Verbose Message Field: Variable name with length 65535 is too long (maximum is 65534 bytes)",
+            format!("{}", NameTooLong(65535))
+        );
+        assert_eq!(
+            "DLT Verbose Message Field: Raw data with length 65536 is too long (maximum is 65535 bytes)",
+            format!("{}", RawDataTooLong(65536))
+        );
         assert_eq!(
+            "DLT Verbose Message Field: Array dimensions describe 6 elements but 5 elements were given",
             format!(
-                "Error when parsing DLT storage header. Expected pattern {:?} at start but got {:?}",
-                crate::storage::StorageHeader::PATTERN_AT_START,
-                v.actual_pattern
-            ),
-            format!("{}", v)
+                "{}",
+                ArrayDimensionsMismatch {
+                    dimensions_product: 6,
+                    number_of_elements: 5
+                }
+            )
+        );
+        assert_eq!(
+            "DLT Verbose Message Field: Array with an encoded size of 65536 bytes is too big (maximum is 65535 bytes)",
+            format!("{}", ArrayTooLong(65536))
+        );
+        assert_eq!(
+            "DLT Verbose Message Field: Struct has too many entries (maximum is 65535)",
+            format!("{}", TooManyStructEntries)
+        );
+        assert_eq!(
+            "DLT Verbose Message Field: Structs are nested deeper then the supported maximum of 32 levels.",
+            format!("{}", StructDepthExceeded)
+        );
+        assert_eq!(
+            "DLT Message: Message with a length of 65536 bytes is too long (maximum is 65535 bytes)",
+            format!("{}", MessageTooLong(65536))
+        );
+        assert_eq!(
+            "DLT Message: Verbose message has too many arguments (maximum is 255)",
+            format!("{}", TooManyArguments)
+        );
+        assert_eq!(
+            "DLT Verbose Message Field: Variable unit with length 65535 is too long (maximum is 65534 bytes)",
+            format!("{}", UnitTooLong(65535))
+        );
+        assert_eq!(
+            "DLT Verbose Message Field: Formatting the message arguments failed.",
+            format!("{}", Fmt)
         );
     }
 
@@ -653,296 +1281,2097 @@ mod storage_header_start_pattern_error_tests {
     #[test]
     fn source() {
         use std::error::Error;
-        assert!(StorageHeaderStartPatternError {
-            actual_pattern: [1, 2, 3, 4]
+        use VerboseEncodeError::*;
+        assert!(Capacity(CapacityError::new(())).source().is_some());
+        assert!(StringNotAscii.source().is_none());
+        assert!(StringTooLong(65535).source().is_none());
+        assert!(NameTooLong(65535).source().is_none());
+        assert!(RawDataTooLong(65536).source().is_none());
+        assert!(ArrayDimensionsMismatch {
+            dimensions_product: 6,
+            number_of_elements: 5
         }
         .source()
         .is_none());
+        assert!(ArrayTooLong(65536).source().is_none());
+        assert!(TooManyStructEntries.source().is_none());
+        assert!(StructDepthExceeded.source().is_none());
+        assert!(MessageTooLong(65536).source().is_none());
+        assert!(TooManyArguments.source().is_none());
+        assert!(UnitTooLong(65535).source().is_none());
+        assert!(Fmt.source().is_none());
+    }
+
+    #[test]
+    fn from_capacity_error() {
+        let e: VerboseEncodeError = CapacityError::new(()).into();
+        assert_eq!(e, VerboseEncodeError::Capacity(CapacityError::new(())));
     }
 }
 
-///Errors that can occure on reading a dlt header.
+/// Error that can occur when redacting string arguments of a verbose
+/// payload via [`crate::verbose::redact_strings`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RedactError {
+    /// Error if the verbose payload could not be decoded.
+    Decode(VerboseDecodeError),
+
+    /// Error if the replacement can not be encoded in place of a string
+    /// argument (e.g. non ASCII replacement for an ASCII string).
+    Encode(VerboseEncodeError),
+}
+
 #[cfg(feature = "std")]
-#[derive(Debug)]
-pub enum ReadError {
-    /// Error if the slice is smaller then dlt length field or minimal size.
-    UnexpectedEndOfSlice(UnexpectedEndOfSliceError),
+impl std::error::Error for RedactError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use RedactError::*;
+        match self {
+            Decode(err) => Some(err),
+            Encode(err) => Some(err),
+        }
+    }
+}
 
-    /// An unsupporetd version number has been encountered
-    /// while decoding the header.
+impl fmt::Display for RedactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use RedactError::*;
+        match self {
+            Decode(err) => err.fmt(f),
+            Encode(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<VerboseDecodeError> for RedactError {
+    fn from(err: VerboseDecodeError) -> Self {
+        RedactError::Decode(err)
+    }
+}
+
+impl From<VerboseEncodeError> for RedactError {
+    fn from(err: VerboseEncodeError) -> Self {
+        RedactError::Encode(err)
+    }
+}
+
+/// Tests for `RedactError` methods
+#[cfg(test)]
+mod redact_error_tests {
+    use super::*;
+
+    #[test]
+    fn clone_eq_debug() {
+        let v = RedactError::Encode(VerboseEncodeError::StringNotAscii);
+        assert_eq!(v, v.clone());
+        assert_eq!("Encode(StringNotAscii)", format!("{:?}", v));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            format!("{}", VerboseEncodeError::StringNotAscii),
+            format!(
+                "{}",
+                RedactError::Encode(VerboseEncodeError::StringNotAscii)
+            )
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                VerboseDecodeError::VariableNameStringMissingNullTermination
+            ),
+            format!(
+                "{}",
+                RedactError::Decode(VerboseDecodeError::VariableNameStringMissingNullTermination)
+            )
+        );
+    }
+
+    #[test]
+    fn from() {
+        assert_eq!(
+            RedactError::Encode(VerboseEncodeError::StringNotAscii),
+            VerboseEncodeError::StringNotAscii.into()
+        );
+        assert_eq!(
+            RedactError::Decode(VerboseDecodeError::VariableNameStringMissingNullTermination),
+            VerboseDecodeError::VariableNameStringMissingNullTermination.into()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn source() {
+        use std::error::Error;
+        assert!(RedactError::Encode(VerboseEncodeError::StringNotAscii)
+            .source()
+            .is_some());
+        assert!(
+            RedactError::Decode(VerboseDecodeError::VariableNameStringMissingNullTermination)
+                .source()
+                .is_some()
+        );
+    }
+} // mod redact_error_tests
+
+/// Error that can occur when decoding the payload of a control message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ControlDecodeError {
+    /// Error if not enough data was present in the slice to decode
+    /// the control message payload.
+    UnexpectedEndOfSlice(UnexpectedEndOfSliceError),
+
+    /// Error when decoding a string contained in the control message.
+    Utf8(Utf8Error),
+
+    /// Error if the service id of an injection message is smaller then
+    /// [`crate::control::InjectionMessage::MIN_SERVICE_ID`].
+    InvalidInjectionServiceId(u32),
+
+    /// Error if a packet that is not a control request was passed where
+    /// a control request was expected (contains the message type of the
+    /// packet, `None` if the packet has no extended header).
+    NotAControlRequest(Option<DltMessageType>),
+
+    /// Error if a packet that is not a control response was passed where
+    /// a control response was expected (contains the message type of the
+    /// packet, `None` if the packet has no extended header).
+    NotAControlResponse(Option<DltMessageType>),
+
+    /// Error if a control message with a different service id was
+    /// passed to the decoder of a specific service.
+    UnexpectedServiceId {
+        /// Service id supported by the decoder.
+        expected: crate::control::DltServiceId,
+        /// Service id of the message.
+        actual: crate::control::DltServiceId,
+    },
+
+    /// Error if a log level value in a control message is outside of the
+    /// defined range (-1 = default, 0 = off, 1..=6 log levels).
+    InvalidLogLevel(i8),
+
+    /// Error if a trace status value in a control message is outside of the
+    /// defined range (-1 = default, 0 = off, 1 = on).
+    InvalidTraceStatus(i8),
+
+    /// Error if a boolean value in a control message (e.g. the new status
+    /// of "set message filtering") is neither 0 nor 1.
+    InvalidBool(u8),
+
+    /// Error if a packet passed to the time message decoder is neither a
+    /// control time message nor a response to a "get local time" request.
+    NotATimeMessage(Option<DltMessageType>),
+
+    /// Error if a time message does not contain a timestamp in the header.
+    MissingTimestamp,
+
+    /// Error if the options value of a "get log info" request is outside
+    /// of the defined range (3..=7).
+    InvalidGetLogInfoOptions(u8),
+}
+
+impl fmt::Display for ControlDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ControlDecodeError::*;
+        match self {
+            UnexpectedEndOfSlice(err) => err.fmt(f),
+            Utf8(err) => err.fmt(f),
+            InvalidInjectionServiceId(service_id) => write!(
+                f, "DLT Control Message: Service id 0x{:x} is not an injection service id (injection service ids start at 0xfff).", service_id
+            ),
+            NotAControlRequest(message_type) => write!(
+                f, "DLT Control Message: Expected a control request but got a packet with the message type {:?}.", message_type
+            ),
+            NotAControlResponse(message_type) => write!(
+                f, "DLT Control Message: Expected a control response but got a packet with the message type {:?}.", message_type
+            ),
+            UnexpectedServiceId { expected, actual } => write!(
+                f, "DLT Control Message: Expected a message with the service id {} but got the service id {}.", expected, actual
+            ),
+            InvalidLogLevel(value) => write!(
+                f, "DLT Control Message: Invalid log level {} (allowed are -1 for the default, 0 for off & 1 to 6).", value
+            ),
+            InvalidTraceStatus(value) => write!(
+                f, "DLT Control Message: Invalid trace status {} (allowed are -1 for the default, 0 for off & 1 for on).", value
+            ),
+            InvalidBool(value) => write!(
+                f, "DLT Control Message: Invalid boolean value {} (allowed are 0 & 1).", value
+            ),
+            NotATimeMessage(message_type) => write!(
+                f, "DLT Control Message: Expected a time message or a get local time response but got a packet with the message type {:?}.", message_type
+            ),
+            MissingTimestamp => write!(
+                f, "DLT Control Message: Time message does not contain a timestamp in the header."
+            ),
+            InvalidGetLogInfoOptions(value) => write!(
+                f, "DLT Control Message: Invalid get log info options value {} (expected a value between 3 and 7).", value
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ControlDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use ControlDecodeError::*;
+        match self {
+            UnexpectedEndOfSlice(err) => Some(err),
+            Utf8(err) => Some(err),
+            InvalidInjectionServiceId(_) => None,
+            NotAControlRequest(_) => None,
+            NotAControlResponse(_) => None,
+            UnexpectedServiceId { .. } => None,
+            InvalidLogLevel(_) => None,
+            InvalidTraceStatus(_) => None,
+            InvalidBool(_) => None,
+            NotATimeMessage(_) => None,
+            MissingTimestamp => None,
+            InvalidGetLogInfoOptions(_) => None,
+        }
+    }
+}
+
+impl From<Utf8Error> for ControlDecodeError {
+    fn from(err: Utf8Error) -> ControlDecodeError {
+        ControlDecodeError::Utf8(err)
+    }
+}
+
+impl ControlDecodeError {
+    /// Layer in which the error occured.
+    pub fn layer(&self) -> Layer {
+        match self {
+            ControlDecodeError::UnexpectedEndOfSlice(v) => v.layer.clone(),
+            _ => Layer::ControlMessage,
+        }
+    }
+
+    /// Returns true if the payload ended before the control message was
+    /// complete & false if the payload is invalid.
+    pub fn is_recoverable_with_more_data(&self) -> bool {
+        use ControlDecodeError::*;
+        match self {
+            UnexpectedEndOfSlice(_) => true,
+            Utf8(_)
+            | InvalidInjectionServiceId(_)
+            | NotAControlRequest(_)
+            | NotAControlResponse(_)
+            | UnexpectedServiceId { .. }
+            | InvalidLogLevel(_)
+            | InvalidTraceStatus(_)
+            | InvalidBool(_)
+            | NotATimeMessage(_)
+            | MissingTimestamp
+            | InvalidGetLogInfoOptions(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod control_decode_error_tests {
+    use super::*;
+
+    fn eos() -> UnexpectedEndOfSliceError {
+        UnexpectedEndOfSliceError {
+            layer: Layer::ControlMessage,
+            minimum_size: 5,
+            actual_size: 4,
+        }
+    }
+
+    #[test]
+    fn clone_eq() {
+        use ControlDecodeError::*;
+        let v = UnexpectedEndOfSlice(eos());
+        assert_eq!(v, v.clone());
+    }
+
+    #[test]
+    fn debug() {
+        use ControlDecodeError::*;
+        assert_eq!(
+            format!("UnexpectedEndOfSlice({:?})", eos()),
+            format!("{:?}", UnexpectedEndOfSlice(eos()))
+        );
+    }
+
+    #[test]
+    #[allow(invalid_from_utf8)]
+    fn display() {
+        use ControlDecodeError::*;
+        assert_eq!(
+            format!("{}", eos()),
+            format!("{}", UnexpectedEndOfSlice(eos()))
+        );
+        let v = std::str::from_utf8(&[0, 159, 146, 150]).unwrap_err();
+        assert_eq!(format!("{}", v), format!("{}", Utf8(v)));
+        assert_eq!(
+            "DLT Control Message: Service id 0x12 is not an injection service id (injection service ids start at 0xfff).",
+            format!("{}", InvalidInjectionServiceId(0x12))
+        );
+        assert_eq!(
+            "DLT Control Message: Expected a control request but got a packet with the message type Some(Log(Info)).",
+            format!("{}", NotAControlRequest(Some(DltMessageType::Log(DltLogLevel::Info))))
+        );
+        assert_eq!(
+            "DLT Control Message: Expected a control response but got a packet with the message type Some(Log(Info)).",
+            format!("{}", NotAControlResponse(Some(DltMessageType::Log(DltLogLevel::Info))))
+        );
+        assert_eq!(
+            "DLT Control Message: Expected a message with the service id set_log_level but got the service id get_log_info.",
+            format!("{}", UnexpectedServiceId { expected: crate::control::DltServiceId::SetLogLevel, actual: crate::control::DltServiceId::GetLogInfo })
+        );
+        assert_eq!(
+            "DLT Control Message: Invalid log level 7 (allowed are -1 for the default, 0 for off & 1 to 6).",
+            format!("{}", InvalidLogLevel(7))
+        );
+        assert_eq!(
+            "DLT Control Message: Invalid trace status 2 (allowed are -1 for the default, 0 for off & 1 for on).",
+            format!("{}", InvalidTraceStatus(2))
+        );
+        assert_eq!(
+            "DLT Control Message: Invalid boolean value 2 (allowed are 0 & 1).",
+            format!("{}", InvalidBool(2))
+        );
+        assert_eq!(
+            "DLT Control Message: Expected a time message or a get local time response but got a packet with the message type None.",
+            format!("{}", NotATimeMessage(None))
+        );
+        assert_eq!(
+            "DLT Control Message: Time message does not contain a timestamp in the header.",
+            format!("{}", MissingTimestamp)
+        );
+        assert_eq!(
+            "DLT Control Message: Invalid get log info options value 2 (expected a value between 3 and 7).",
+            format!("{}", InvalidGetLogInfoOptions(2))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[allow(invalid_from_utf8)]
+    fn source() {
+        use std::error::Error;
+        use ControlDecodeError::*;
+        assert!(UnexpectedEndOfSlice(eos()).source().is_some());
+        assert!(Utf8(std::str::from_utf8(&[0, 159, 146, 150]).unwrap_err())
+            .source()
+            .is_some());
+        assert!(InvalidInjectionServiceId(0x12).source().is_none());
+        assert!(
+            NotAControlRequest(Some(DltMessageType::Log(DltLogLevel::Info)))
+                .source()
+                .is_none()
+        );
+        assert!(
+            NotAControlResponse(Some(DltMessageType::Log(DltLogLevel::Info)))
+                .source()
+                .is_none()
+        );
+        assert!(UnexpectedServiceId {
+            expected: crate::control::DltServiceId::SetLogLevel,
+            actual: crate::control::DltServiceId::GetLogInfo
+        }
+        .source()
+        .is_none());
+        assert!(InvalidLogLevel(7).source().is_none());
+        assert!(InvalidTraceStatus(2).source().is_none());
+        assert!(InvalidBool(2).source().is_none());
+        assert!(NotATimeMessage(None).source().is_none());
+        assert!(MissingTimestamp.source().is_none());
+        assert!(InvalidGetLogInfoOptions(2).source().is_none());
+    }
+
+    #[test]
+    fn layer() {
+        use ControlDecodeError::*;
+        assert_eq!(
+            Layer::DltHeader,
+            UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer: Layer::DltHeader,
+                minimum_size: 2,
+                actual_size: 1,
+            })
+            .layer()
+        );
+        assert_eq!(Layer::ControlMessage, MissingTimestamp.layer());
+        assert_eq!(Layer::ControlMessage, InvalidBool(2).layer());
+    }
+
+    #[test]
+    fn is_recoverable_with_more_data() {
+        use ControlDecodeError::*;
+        assert!(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+            layer: Layer::ControlMessage,
+            minimum_size: 2,
+            actual_size: 1,
+        })
+        .is_recoverable_with_more_data());
+        assert!(false == MissingTimestamp.is_recoverable_with_more_data());
+        assert!(false == InvalidBool(2).is_recoverable_with_more_data());
+        assert!(false == NotAControlRequest(None).is_recoverable_with_more_data());
+    }
+
+    #[test]
+    #[allow(invalid_from_utf8)]
+    fn from_utf8_error() {
+        let e: ControlDecodeError = std::str::from_utf8(&[0, 159, 146, 150]).unwrap_err().into();
+        assert_matches!(e, ControlDecodeError::Utf8(_));
+    }
+}
+
+/// Error that occurs when another pattern then
+/// [`crate::storage::StorageHeader::PATTERN_AT_START`] is encountered
+/// at the start when parsing a StorageHeader.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageHeaderStartPatternError {
+    /// Encountered pattern at the start.
+    pub actual_pattern: [u8; 4],
+}
+
+impl fmt::Display for StorageHeaderStartPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Error when parsing DLT storage header. Expected pattern {:?} at start but got {:?}",
+            super::storage::StorageHeader::PATTERN_AT_START,
+            self.actual_pattern
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StorageHeaderStartPatternError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod storage_header_start_pattern_error_tests {
+    use super::*;
+
+    #[test]
+    fn clone_eq() {
+        let v = StorageHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4],
+        };
+        assert_eq!(v, v.clone());
+    }
+
+    #[test]
+    fn debug() {
+        let v = StorageHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4],
+        };
+        assert_eq!(
+            format!(
+                "StorageHeaderStartPatternError {{ actual_pattern: {:?} }}",
+                v.actual_pattern
+            ),
+            format!("{:?}", v)
+        );
+    }
+
+    #[test]
+    fn display() {
+        let v = StorageHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4],
+        };
+        assert_eq!(
+            format!(
+                "Error when parsing DLT storage header. Expected pattern {:?} at start but got {:?}",
+                crate::storage::StorageHeader::PATTERN_AT_START,
+                v.actual_pattern
+            ),
+            format!("{}", v)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn source() {
+        use std::error::Error;
+        assert!(StorageHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4]
+        }
+        .source()
+        .is_none());
+    }
+}
+
+/// Error that occurs when another pattern then
+/// [`crate::SERIAL_HEADER_PATTERN`] is encountered at the
+/// start of a serial header framed DLT packet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerialHeaderStartPatternError {
+    /// Encountered pattern at the start.
+    pub actual_pattern: [u8; 4],
+}
+
+impl fmt::Display for SerialHeaderStartPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Error when parsing DLT serial header. Expected pattern {:?} at start but got {:?}",
+            super::SERIAL_HEADER_PATTERN,
+            self.actual_pattern
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SerialHeaderStartPatternError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod serial_header_start_pattern_error_tests {
+    use super::*;
+
+    #[test]
+    fn clone_eq() {
+        let v = SerialHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4],
+        };
+        assert_eq!(v, v.clone());
+    }
+
+    #[test]
+    fn debug() {
+        let v = SerialHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4],
+        };
+        assert_eq!(
+            format!(
+                "SerialHeaderStartPatternError {{ actual_pattern: {:?} }}",
+                v.actual_pattern
+            ),
+            format!("{:?}", v)
+        );
+    }
+
+    #[test]
+    fn display() {
+        let v = SerialHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4],
+        };
+        assert_eq!(
+            format!(
+                "Error when parsing DLT serial header. Expected pattern {:?} at start but got {:?}",
+                crate::SERIAL_HEADER_PATTERN,
+                v.actual_pattern
+            ),
+            format!("{}", v)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn source() {
+        use std::error::Error;
+        assert!(SerialHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4]
+        }
+        .source()
+        .is_none());
+    }
+}
+
+/// Error that occurs if a FIBEX file is not well formed XML.
+#[cfg(feature = "fibex")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FibexParseError {
+    /// Line (starting at 1) in which the error was detected.
+    pub line: usize,
+    /// Description of the problem.
+    pub reason: &'static str,
+}
+
+#[cfg(feature = "fibex")]
+impl fmt::Display for FibexParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Error when parsing FIBEX file in line {}: {}",
+            self.line, self.reason
+        )
+    }
+}
+
+#[cfg(feature = "fibex")]
+impl std::error::Error for FibexParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+#[cfg(all(test, feature = "fibex"))]
+mod fibex_parse_error_tests {
+    use super::*;
+
+    #[test]
+    fn clone_eq() {
+        let v = FibexParseError {
+            line: 2,
+            reason: "a",
+        };
+        assert_eq!(v, v.clone());
+    }
+
+    #[test]
+    fn debug() {
+        let v = FibexParseError {
+            line: 2,
+            reason: "a",
+        };
+        assert_eq!(
+            "FibexParseError { line: 2, reason: \"a\" }",
+            format!("{:?}", v)
+        );
+    }
+
+    #[test]
+    fn display() {
+        let v = FibexParseError {
+            line: 2,
+            reason: "unexpected end of file",
+        };
+        assert_eq!(
+            "Error when parsing FIBEX file in line 2: unexpected end of file",
+            format!("{}", v)
+        );
+    }
+
+    #[test]
+    fn source() {
+        use std::error::Error;
+        assert!(FibexParseError {
+            line: 2,
+            reason: "a",
+        }
+        .source()
+        .is_none());
+    }
+}
+
+///Errors that can occure on reading a dlt header.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReadError {
+    /// Error if the slice is smaller then dlt length field or minimal size.
+    UnexpectedEndOfSlice(UnexpectedEndOfSliceError),
+
+    /// An unsupporetd version number has been encountered
+    /// while decoding the header.
     UnsupportedDltVersion(UnsupportedDltVersionError),
 
-    /// Error if the dlt length is smaller then the header the calculated header size based on the flags (+ minimum payload size of 4 bytes/octetets)
-    DltMessageLengthTooSmall(DltMessageLengthTooSmallError),
+    /// Error if the dlt length is smaller then the header the calculated header size based on the flags (+ minimum payload size of 4 bytes/octetets)
+    DltMessageLengthTooSmall(DltMessageLengthTooSmallError),
+
+    /// Error if a dlt message is larger then the maximum length that can be
+    /// stored in the length field of the header (contains the length).
+    DltMessageLengthTooLarge(usize),
+
+    /// Error if the message info in the extended header contains reserved
+    /// values (only returned if strict decoding is enabled).
+    ReservedMessageInfo(u8),
+
+    /// Error if a storage header does not start with the correct pattern.
+    StorageHeaderStartPattern(StorageHeaderStartPatternError),
+
+    /// Error if a serial header does not start with the correct pattern.
+    SerialHeaderStartPattern(SerialHeaderStartPatternError),
+
+    /// Standard io error.
+    IoError(io::Error),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use ReadError::*;
+        match self {
+            UnexpectedEndOfSlice(ref err) => Some(err),
+            UnsupportedDltVersion(ref err) => Some(err),
+            DltMessageLengthTooSmall(ref err) => Some(err),
+            DltMessageLengthTooLarge(_) => None,
+            ReservedMessageInfo(_) => None,
+            StorageHeaderStartPattern(ref err) => Some(err),
+            SerialHeaderStartPattern(ref err) => Some(err),
+            IoError(ref err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ReadError::*;
+
+        match self {
+            UnexpectedEndOfSlice(err) => {
+                write!(f, "ReadError: Unexpected end of slice. The given slice only contained {} bytes, which is less then minimum required {} bytes.", err.actual_size, err.minimum_size)
+            }
+            UnsupportedDltVersion(err) => err.fmt(f),
+            DltMessageLengthTooSmall(err) => err.fmt(f),
+            DltMessageLengthTooLarge(length) => write!(
+                f, "DLT Header Error: The message length of {} bytes exceeds the maximum length of {} bytes that can be stored in the dlt header.", length, u16::MAX
+            ),
+            ReservedMessageInfo(v) => PacketSliceError::ReservedMessageInfo(*v).fmt(f),
+            StorageHeaderStartPattern(err) => err.fmt(f),
+            SerialHeaderStartPattern(err) => err.fmt(f),
+            IoError(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<StorageHeaderStartPatternError> for ReadError {
+    fn from(err: StorageHeaderStartPatternError) -> ReadError {
+        ReadError::StorageHeaderStartPattern(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<SerialHeaderStartPatternError> for ReadError {
+    fn from(err: SerialHeaderStartPatternError) -> ReadError {
+        ReadError::SerialHeaderStartPattern(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<PacketSliceError> for ReadError {
+    fn from(err: PacketSliceError) -> ReadError {
+        use PacketSliceError as I;
+        match err {
+            I::UnsupportedDltVersion(err) => ReadError::UnsupportedDltVersion(err),
+            I::MessageLengthTooSmall(err) => ReadError::DltMessageLengthTooSmall(err),
+            I::UnexpectedEndOfSlice(err) => ReadError::UnexpectedEndOfSlice(err),
+            I::ReservedMessageInfo(v) => ReadError::ReservedMessageInfo(v),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> ReadError {
+        ReadError::IoError(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ReadError {
+    /// Layer in which the error occured (`None` for io errors).
+    pub fn layer(&self) -> Option<Layer> {
+        use ReadError::*;
+        match self {
+            UnexpectedEndOfSlice(err) => Some(err.layer.clone()),
+            UnsupportedDltVersion(_)
+            | DltMessageLengthTooSmall(_)
+            | DltMessageLengthTooLarge(_)
+            | ReservedMessageInfo(_) => Some(Layer::DltHeader),
+            StorageHeaderStartPattern(_) => Some(Layer::StorageHeader),
+            SerialHeaderStartPattern(_) => Some(Layer::SerialHeader),
+            IoError(_) => None,
+        }
+    }
+
+    /// Returns true if the input ended before the packet was complete
+    /// (unexpected end of slice or an io error of the kind
+    /// [`io::ErrorKind::UnexpectedEof`]) & false for corrupt data & all
+    /// other io errors.
+    pub fn is_recoverable_with_more_data(&self) -> bool {
+        use ReadError::*;
+        match self {
+            UnexpectedEndOfSlice(_) => true,
+            IoError(err) => err.kind() == io::ErrorKind::UnexpectedEof,
+            UnsupportedDltVersion(_)
+            | DltMessageLengthTooSmall(_)
+            | DltMessageLengthTooLarge(_)
+            | ReservedMessageInfo(_)
+            | StorageHeaderStartPattern(_)
+            | SerialHeaderStartPattern(_) => false,
+        }
+    }
+
+    /// Minimum number of additional bytes needed before reading can be
+    /// continued (`None` if the error is not recoverable with more data
+    /// or the number of missing bytes is unknown, e.g. for io errors).
+    pub fn additional_bytes_needed(&self) -> Option<usize> {
+        match self {
+            ReadError::UnexpectedEndOfSlice(err) => Some(err.additional_bytes_needed()),
+            _ => None,
+        }
+    }
+}
+
+/// Tests for `ReadError` methods
+#[cfg(all(feature = "std", test))]
+mod read_error {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn debug() {
+        use ReadError::*;
+
+        {
+            let c = UnexpectedEndOfSliceError {
+                minimum_size: 1,
+                actual_size: 2,
+                layer: Layer::DltHeader,
+            };
+            assert_eq!(
+                format!("UnexpectedEndOfSlice({:?})", c),
+                format!("{:?}", UnexpectedEndOfSlice(c))
+            );
+        }
+        {
+            let c = UnsupportedDltVersionError {
+                unsupported_version: 123,
+            };
+            assert_eq!(
+                format!("UnsupportedDltVersion({:?})", c),
+                format!("{:?}", UnsupportedDltVersion(c))
+            );
+        }
+        {
+            let c = DltMessageLengthTooSmallError {
+                required_length: 3,
+                actual_length: 4,
+            };
+            assert_eq!(
+                format!("DltMessageLengthTooSmall({:?})", c),
+                format!("{:?}", DltMessageLengthTooSmall(c))
+            );
+        }
+        {
+            let c = std::io::Error::new(std::io::ErrorKind::Other, "oh no!");
+            assert_eq!(format!("IoError({:?})", c), format!("{:?}", IoError(c)));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn display(
+            usize0 in any::<usize>(),
+            usize1 in any::<usize>(),
+        ) {
+
+            use ReadError::*;
+
+            // UnexpectedEndOfSlice
+            assert_eq!(
+                &format!("ReadError: Unexpected end of slice. The given slice only contained {} bytes, which is less then minimum required {} bytes.", usize1, usize0),
+                &format!(
+                    "{}",
+                    UnexpectedEndOfSlice(
+                        UnexpectedEndOfSliceError {
+                            layer: Layer::DltHeader,
+                            minimum_size: usize0,
+                            actual_size: usize1,
+                        }
+                    )
+                )
+            );
+
+            // UnsupportedDltVersionError
+            {
+                let c = UnsupportedDltVersionError{ unsupported_version: 123 };
+                assert_eq!(
+                    &format!("{}", c),
+                    &format!("{}", UnsupportedDltVersion(c))
+                );
+            }
+
+            // DltMessageLengthTooSmall
+            {
+                let c = DltMessageLengthTooSmallError{
+                    required_length: usize0,
+                    actual_length: usize1
+                };
+                assert_eq!(
+                    &format!("{}", c),
+                    &format!("{}", DltMessageLengthTooSmall(c))
+                );
+            }
+
+            // DltMessageLengthTooLarge
+            assert_eq!(
+                &format!("DLT Header Error: The message length of {} bytes exceeds the maximum length of 65535 bytes that can be stored in the dlt header.", usize0),
+                &format!("{}", DltMessageLengthTooLarge(usize0))
+            );
+
+            // ReservedMessageInfo
+            assert_eq!(
+                &format!("{}", PacketSliceError::ReservedMessageInfo(0b1000)),
+                &format!("{}", ReservedMessageInfo(0b1000))
+            );
+
+            // StorageHeaderStartPattern
+            {
+                let c = StorageHeaderStartPatternError{
+                    actual_pattern: [1,2,3,4]
+                };
+                assert_eq!(
+                    &format!("{}", c),
+                    &format!("{}", StorageHeaderStartPattern(c))
+                );
+            }
+
+            // SerialHeaderStartPattern
+            {
+                let c = SerialHeaderStartPatternError{
+                    actual_pattern: [1,2,3,4]
+                };
+                assert_eq!(
+                    &format!("{}", c),
+                    &format!("{}", SerialHeaderStartPattern(c))
+                );
+            }
+
+            //IoError
+            {
+                let custom_error = std::io::Error::new(std::io::ErrorKind::Other, "some error");
+                assert_eq!(
+                    &format!("{}", custom_error),
+                    &format!("{}", IoError(custom_error))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn source() {
+        use std::error::Error;
+        use ReadError::*;
+
+        assert!(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+            layer: Layer::DltHeader,
+            minimum_size: 1,
+            actual_size: 2
+        })
+        .source()
+        .is_some());
+        assert!(UnsupportedDltVersion(UnsupportedDltVersionError {
+            unsupported_version: 123
+        })
+        .source()
+        .is_some());
+        assert!(DltMessageLengthTooSmall(DltMessageLengthTooSmallError {
+            required_length: 3,
+            actual_length: 4
+        })
+        .source()
+        .is_some());
+        assert!(DltMessageLengthTooLarge(65536).source().is_none());
+        assert!(ReservedMessageInfo(0).source().is_none());
+        assert!(StorageHeaderStartPattern(StorageHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4]
+        })
+        .source()
+        .is_some());
+        assert!(SerialHeaderStartPattern(SerialHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4]
+        })
+        .source()
+        .is_some());
+        assert!(
+            IoError(std::io::Error::new(std::io::ErrorKind::Other, "oh no!"))
+                .source()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn from_io_error() {
+        let r: ReadError = std::io::Error::new(std::io::ErrorKind::Other, "oh no!").into();
+        assert_matches!(r, ReadError::IoError(_));
+    }
+
+    #[test]
+    fn from_storage_header_error() {
+        let r: ReadError = StorageHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4],
+        }
+        .into();
+        assert_matches!(r, ReadError::StorageHeaderStartPattern(_));
+    }
+
+    #[test]
+    fn from_serial_header_error() {
+        let r: ReadError = SerialHeaderStartPatternError {
+            actual_pattern: [1, 2, 3, 4],
+        }
+        .into();
+        assert_matches!(r, ReadError::SerialHeaderStartPattern(_));
+    }
+
+    #[test]
+    fn layer() {
+        use ReadError::*;
+        assert_eq!(
+            Some(Layer::VerboseValue),
+            UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer: Layer::VerboseValue,
+                minimum_size: 2,
+                actual_size: 1,
+            })
+            .layer()
+        );
+        assert_eq!(
+            Some(Layer::DltHeader),
+            DltMessageLengthTooLarge(70000).layer()
+        );
+        assert_eq!(
+            Some(Layer::SerialHeader),
+            SerialHeaderStartPattern(SerialHeaderStartPatternError {
+                actual_pattern: [0; 4]
+            })
+            .layer()
+        );
+        assert_eq!(
+            None,
+            IoError(std::io::Error::new(std::io::ErrorKind::Other, "oh no!")).layer()
+        );
+    }
+
+    #[test]
+    fn is_recoverable_with_more_data() {
+        use ReadError::*;
+        let v = UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+            layer: Layer::DltHeader,
+            minimum_size: 2,
+            actual_size: 1,
+        });
+        assert!(v.is_recoverable_with_more_data());
+        assert_eq!(Some(1), v.additional_bytes_needed());
+
+        let v = IoError(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "eof",
+        ));
+        assert!(v.is_recoverable_with_more_data());
+        assert_eq!(None, v.additional_bytes_needed());
+
+        for v in [
+            IoError(std::io::Error::new(std::io::ErrorKind::Other, "oh no!")),
+            DltMessageLengthTooLarge(70000),
+            SerialHeaderStartPattern(SerialHeaderStartPatternError {
+                actual_pattern: [0; 4],
+            }),
+        ] {
+            assert!(false == v.is_recoverable_with_more_data());
+            assert_eq!(None, v.additional_bytes_needed());
+        }
+    }
+
+    #[test]
+    fn from_packet_slice_error() {
+        use PacketSliceError as I;
+
+        // UnsupportedDltVersion
+        {
+            let r: ReadError = I::UnsupportedDltVersion(UnsupportedDltVersionError {
+                unsupported_version: 123,
+            })
+            .into();
+            assert_matches!(r, ReadError::UnsupportedDltVersion(_));
+        }
+
+        // MessageLengthTooSmall
+        {
+            let r: ReadError = I::MessageLengthTooSmall(DltMessageLengthTooSmallError {
+                required_length: 3,
+                actual_length: 4,
+            })
+            .into();
+            assert_matches!(r, ReadError::DltMessageLengthTooSmall(_));
+        }
+
+        // UnexpectedEndOfSlice
+        {
+            let r: ReadError = I::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer: Layer::DltHeader,
+                minimum_size: 1,
+                actual_size: 2,
+            })
+            .into();
+            assert_matches!(r, ReadError::UnexpectedEndOfSlice(_));
+        }
+
+        // ReservedMessageInfo
+        {
+            let r: ReadError = I::ReservedMessageInfo(0b1000).into();
+            assert_matches!(r, ReadError::ReservedMessageInfo(0b1000));
+        }
+    }
+} // mod read_error
 
-    /// Error if a storage header does not start with the correct pattern.
-    StorageHeaderStartPattern(StorageHeaderStartPatternError),
+/// Errors that can occur when writing multiple dlt messages
+/// (see [`crate::write_messages`]).
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WriteError {
+    /// Error if the header & payload of a message are too long to
+    /// be represented in the dlt length field.
+    MessageTooLong {
+        /// Index of the message that could not be written.
+        index: usize,
+        /// Length of the header & payload of the message.
+        length: usize,
+    },
+
+    /// Standard io error that occured while writing a message.
+    Io {
+        /// Index of the message that could not be written.
+        index: usize,
+        err: io::Error,
+    },
+}
+
+#[cfg(feature = "std")]
+impl WriteError {
+    /// Index of the message that could not be written.
+    pub fn index(&self) -> usize {
+        use WriteError::*;
+        match self {
+            MessageTooLong { index, .. } => *index,
+            Io { index, .. } => *index,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use WriteError::*;
+        match self {
+            MessageTooLong { .. } => None,
+            Io { err, .. } => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use WriteError::*;
+
+        match self {
+            MessageTooLong { index, length } => write!(
+                f,
+                "WriteError: Message {} is too long to be written ({} bytes, maximum is {} bytes).",
+                index,
+                length,
+                u16::MAX
+            ),
+            Io { index, err } => {
+                write!(f, "WriteError: Failed to write message {}: {}", index, err)
+            }
+        }
+    }
+}
+
+/// Tests for `WriteError` methods
+#[cfg(all(feature = "std", test))]
+mod write_error {
+    use super::*;
+
+    #[test]
+    fn debug() {
+        use WriteError::*;
+        assert_eq!(
+            "MessageTooLong { index: 1, length: 2 }",
+            format!(
+                "{:?}",
+                MessageTooLong {
+                    index: 1,
+                    length: 2
+                }
+            )
+        );
+        let err = std::io::Error::new(std::io::ErrorKind::Other, "oh no!");
+        assert_eq!(
+            format!("Io {{ index: 3, err: {:?} }}", err),
+            format!("{:?}", Io { index: 3, err })
+        );
+    }
+
+    #[test]
+    fn display() {
+        use WriteError::*;
+        assert_eq!(
+            "WriteError: Message 1 is too long to be written (65536 bytes, maximum is 65535 bytes).",
+            format!(
+                "{}",
+                MessageTooLong {
+                    index: 1,
+                    length: 65536
+                }
+            )
+        );
+        assert_eq!(
+            "WriteError: Failed to write message 3: oh no!",
+            format!(
+                "{}",
+                Io {
+                    index: 3,
+                    err: std::io::Error::new(std::io::ErrorKind::Other, "oh no!")
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn index() {
+        use WriteError::*;
+        assert_eq!(
+            1,
+            MessageTooLong {
+                index: 1,
+                length: 2
+            }
+            .index()
+        );
+        assert_eq!(
+            3,
+            Io {
+                index: 3,
+                err: std::io::Error::new(std::io::ErrorKind::Other, "oh no!")
+            }
+            .index()
+        );
+    }
+
+    #[test]
+    fn source() {
+        use std::error::Error;
+        use WriteError::*;
+        assert!(MessageTooLong {
+            index: 1,
+            length: 2
+        }
+        .source()
+        .is_none());
+        assert!(Io {
+            index: 3,
+            err: std::io::Error::new(std::io::ErrorKind::Other, "oh no!")
+        }
+        .source()
+        .is_some());
+    }
+} // mod write_error
+
+/// Error that can occur when slicing a network trace message via
+/// [`crate::NetworkTraceSlice::from_packet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NetworkTraceError {
+    /// Error if the packet is not a network trace message (contains the
+    /// message type of the packet, `None` if the packet has no extended
+    /// header).
+    NotANetworkTrace(Option<DltMessageType>),
+
+    /// Error if a verbose network trace message does not have exactly two
+    /// arguments (contains the number of arguments of the message).
+    UnexpectedArgumentCount(u8),
+
+    /// Error if an argument of a verbose network trace message is not a
+    /// raw value (contains the index of the argument).
+    ArgumentNotRaw(usize),
+
+    /// Error when decoding an argument of a verbose network trace message.
+    Verbose(VerboseDecodeError),
+
+    /// Error if the payload of a non verbose network trace message is
+    /// too short for the header & payload blocks.
+    UnexpectedEndOfSlice(UnexpectedEndOfSliceError),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NetworkTraceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use NetworkTraceError::*;
+        match self {
+            NotANetworkTrace(_) => None,
+            UnexpectedArgumentCount(_) => None,
+            ArgumentNotRaw(_) => None,
+            Verbose(err) => Some(err),
+            UnexpectedEndOfSlice(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for NetworkTraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use NetworkTraceError::*;
+        match self {
+            NotANetworkTrace(message_type) => write!(
+                f,
+                "DLT Network Trace: Expected a network trace message but got a packet with the message type {:?}.",
+                message_type
+            ),
+            UnexpectedArgumentCount(count) => write!(
+                f,
+                "DLT Network Trace: Expected 2 arguments (header & payload) but the message has {} arguments.",
+                count
+            ),
+            ArgumentNotRaw(index) => write!(
+                f,
+                "DLT Network Trace: Argument {} is not a raw value.",
+                index
+            ),
+            Verbose(err) => err.fmt(f),
+            UnexpectedEndOfSlice(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<VerboseDecodeError> for NetworkTraceError {
+    fn from(err: VerboseDecodeError) -> Self {
+        NetworkTraceError::Verbose(err)
+    }
+}
+
+/// Tests for `NetworkTraceError` methods
+#[cfg(test)]
+mod network_trace_error_tests {
+    use super::*;
+
+    fn end_of_slice() -> UnexpectedEndOfSliceError {
+        UnexpectedEndOfSliceError {
+            layer: Layer::NetworkTrace,
+            minimum_size: 6,
+            actual_size: 2,
+        }
+    }
+
+    #[test]
+    fn clone_eq_debug() {
+        use NetworkTraceError::*;
+        let v = ArgumentNotRaw(1);
+        assert_eq!(v, v.clone());
+        assert_eq!("ArgumentNotRaw(1)", format!("{:?}", v));
+    }
+
+    #[test]
+    fn display() {
+        use NetworkTraceError::*;
+        assert_eq!(
+            format!(
+                "DLT Network Trace: Expected a network trace message but got a packet with the message type {:?}.",
+                Some(DltMessageType::Log(DltLogLevel::Info))
+            ),
+            format!(
+                "{}",
+                NotANetworkTrace(Some(DltMessageType::Log(DltLogLevel::Info)))
+            )
+        );
+        assert_eq!(
+            "DLT Network Trace: Expected 2 arguments (header & payload) but the message has 3 arguments.",
+            format!("{}", UnexpectedArgumentCount(3))
+        );
+        assert_eq!(
+            "DLT Network Trace: Argument 1 is not a raw value.",
+            format!("{}", ArgumentNotRaw(1))
+        );
+        assert_eq!(
+            format!("{}", VerboseDecodeError::InvalidUtf16String),
+            format!("{}", Verbose(VerboseDecodeError::InvalidUtf16String))
+        );
+        assert_eq!(
+            format!("{}", end_of_slice()),
+            format!("{}", UnexpectedEndOfSlice(end_of_slice()))
+        );
+    }
+
+    #[test]
+    fn from() {
+        assert_eq!(
+            NetworkTraceError::Verbose(VerboseDecodeError::InvalidUtf16String),
+            VerboseDecodeError::InvalidUtf16String.into()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn source() {
+        use std::error::Error;
+        use NetworkTraceError::*;
+        assert!(NotANetworkTrace(None).source().is_none());
+        assert!(UnexpectedArgumentCount(1).source().is_none());
+        assert!(ArgumentNotRaw(0).source().is_none());
+        assert!(Verbose(VerboseDecodeError::InvalidUtf16String)
+            .source()
+            .is_some());
+        assert!(UnexpectedEndOfSlice(end_of_slice()).source().is_some());
+    }
+} // mod network_trace_error_tests
+
+/// Error that can occur when decoding a CAN frame from a network trace
+/// message via [`crate::CanFrame`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CanFrameError {
+    /// Error if the network trace message is not a CAN trace (contains the
+    /// network type of the message).
+    NotACanTrace(DltNetworkType),
+
+    /// Error if the header block has a length not supported by the
+    /// selected header format (contains the length of the header block).
+    InvalidHeaderLen(usize),
+
+    /// Error if the number of data bytes is not a valid CAN (FD) frame
+    /// length.
+    InvalidDataLen {
+        /// True if the frame was decoded as a CAN FD frame.
+        fd: bool,
+        /// Number of data bytes.
+        len: usize,
+    },
+
+    /// Error when slicing the network trace message.
+    NetworkTrace(NetworkTraceError),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CanFrameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use CanFrameError::*;
+        match self {
+            NotACanTrace(_) => None,
+            InvalidHeaderLen(_) => None,
+            InvalidDataLen { .. } => None,
+            NetworkTrace(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for CanFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CanFrameError::*;
+        match self {
+            NotACanTrace(net_type) => write!(
+                f,
+                "DLT CAN Frame: Expected a CAN network trace but got a trace with the network type {:?}.",
+                net_type
+            ),
+            InvalidHeaderLen(len) => write!(
+                f,
+                "DLT CAN Frame: Header block with a length of {} bytes is not supported by the header format.",
+                len
+            ),
+            InvalidDataLen { fd, len } => write!(
+                f,
+                "DLT CAN Frame: {} bytes of data are not a valid length for a {} frame.",
+                len,
+                if *fd { "CAN FD" } else { "classic CAN" }
+            ),
+            NetworkTrace(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<NetworkTraceError> for CanFrameError {
+    fn from(err: NetworkTraceError) -> Self {
+        CanFrameError::NetworkTrace(err)
+    }
+}
+
+/// Tests for `CanFrameError` methods
+#[cfg(test)]
+mod can_frame_error_tests {
+    use super::*;
+
+    #[test]
+    fn clone_eq_debug() {
+        use CanFrameError::*;
+        let v = InvalidHeaderLen(3);
+        assert_eq!(v, v.clone());
+        assert_eq!("InvalidHeaderLen(3)", format!("{:?}", v));
+    }
+
+    #[test]
+    fn display() {
+        use CanFrameError::*;
+        assert_eq!(
+            "DLT CAN Frame: Expected a CAN network trace but got a trace with the network type Ethernet.",
+            format!("{}", NotACanTrace(DltNetworkType::Ethernet))
+        );
+        assert_eq!(
+            "DLT CAN Frame: Header block with a length of 3 bytes is not supported by the header format.",
+            format!("{}", InvalidHeaderLen(3))
+        );
+        assert_eq!(
+            "DLT CAN Frame: 9 bytes of data are not a valid length for a classic CAN frame.",
+            format!("{}", InvalidDataLen { fd: false, len: 9 })
+        );
+        assert_eq!(
+            "DLT CAN Frame: 13 bytes of data are not a valid length for a CAN FD frame.",
+            format!("{}", InvalidDataLen { fd: true, len: 13 })
+        );
+        assert_eq!(
+            format!("{}", NetworkTraceError::ArgumentNotRaw(1)),
+            format!("{}", NetworkTrace(NetworkTraceError::ArgumentNotRaw(1)))
+        );
+    }
+
+    #[test]
+    fn from() {
+        assert_eq!(
+            CanFrameError::NetworkTrace(NetworkTraceError::ArgumentNotRaw(1)),
+            NetworkTraceError::ArgumentNotRaw(1).into()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn source() {
+        use std::error::Error;
+        use CanFrameError::*;
+        assert!(NotACanTrace(DltNetworkType::Most).source().is_none());
+        assert!(InvalidHeaderLen(0).source().is_none());
+        assert!(InvalidDataLen { fd: false, len: 9 }.source().is_none());
+        assert!(NetworkTrace(NetworkTraceError::ArgumentNotRaw(1))
+            .source()
+            .is_some());
+    }
+} // mod can_frame_error_tests
+
+/// Error that can occur when decoding a FlexRay frame from a network trace
+/// message via [`crate::FlexRayFrame`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FlexRayFrameError {
+    /// Error if the network trace message is not a FlexRay trace (contains
+    /// the network type of the message).
+    NotAFlexRayTrace(DltNetworkType),
+
+    /// Error when slicing the network trace message.
+    NetworkTrace(NetworkTraceError),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FlexRayFrameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use FlexRayFrameError::*;
+        match self {
+            NotAFlexRayTrace(_) => None,
+            NetworkTrace(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for FlexRayFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FlexRayFrameError::*;
+        match self {
+            NotAFlexRayTrace(net_type) => write!(
+                f,
+                "DLT FlexRay Frame: Expected a FlexRay network trace but got a trace with the network type {:?}.",
+                net_type
+            ),
+            NetworkTrace(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<NetworkTraceError> for FlexRayFrameError {
+    fn from(err: NetworkTraceError) -> Self {
+        FlexRayFrameError::NetworkTrace(err)
+    }
+}
+
+/// Tests for `FlexRayFrameError` methods
+#[cfg(test)]
+mod flex_ray_frame_error_tests {
+    use super::*;
+
+    #[test]
+    fn clone_eq_debug() {
+        use FlexRayFrameError::*;
+        let v = NotAFlexRayTrace(DltNetworkType::Can);
+        assert_eq!(v, v.clone());
+        assert_eq!("NotAFlexRayTrace(Can)", format!("{:?}", v));
+    }
+
+    #[test]
+    fn display() {
+        use FlexRayFrameError::*;
+        assert_eq!(
+            "DLT FlexRay Frame: Expected a FlexRay network trace but got a trace with the network type Can.",
+            format!("{}", NotAFlexRayTrace(DltNetworkType::Can))
+        );
+        assert_eq!(
+            format!("{}", NetworkTraceError::ArgumentNotRaw(1)),
+            format!("{}", NetworkTrace(NetworkTraceError::ArgumentNotRaw(1)))
+        );
+    }
+
+    #[test]
+    fn from() {
+        assert_eq!(
+            FlexRayFrameError::NetworkTrace(NetworkTraceError::ArgumentNotRaw(1)),
+            NetworkTraceError::ArgumentNotRaw(1).into()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn source() {
+        use std::error::Error;
+        use FlexRayFrameError::*;
+        assert!(NotAFlexRayTrace(DltNetworkType::Can).source().is_none());
+        assert!(NetworkTrace(NetworkTraceError::ArgumentNotRaw(1))
+            .source()
+            .is_some());
+    }
+} // mod flex_ray_frame_error_tests
+
+/// Error that can occur when extracting a SOME/IP message from a network
+/// trace message via [`crate::SomeIpTraceSlice`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SomeIpTraceError {
+    /// Error if the network trace message is neither a SOME/IP nor an
+    /// ethernet trace (contains the network type of the message).
+    NotASomeIpTrace(DltNetworkType),
+
+    /// Error if the payload block is too short for the SOME/IP header or
+    /// the length given in the SOME/IP header.
+    UnexpectedEndOfSlice(UnexpectedEndOfSliceError),
+
+    /// Error if the length field of the SOME/IP header is smaller then
+    /// the 8 header bytes it includes (contains the length field).
+    LengthFieldTooSmall(u32),
+
+    /// Error if the message length given by the length field of the
+    /// SOME/IP header can not be represented as an `usize` (contains
+    /// the length field).
+    LengthFieldTooLarge(u32),
 
-    /// Standard io error.
-    IoError(io::Error),
+    /// Error when slicing the network trace message.
+    NetworkTrace(NetworkTraceError),
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for ReadError {
+impl std::error::Error for SomeIpTraceError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        use ReadError::*;
+        use SomeIpTraceError::*;
         match self {
-            UnexpectedEndOfSlice(ref err) => Some(err),
-            UnsupportedDltVersion(ref err) => Some(err),
-            DltMessageLengthTooSmall(ref err) => Some(err),
-            StorageHeaderStartPattern(ref err) => Some(err),
-            IoError(ref err) => Some(err),
+            NotASomeIpTrace(_) => None,
+            UnexpectedEndOfSlice(err) => Some(err),
+            LengthFieldTooSmall(_) => None,
+            LengthFieldTooLarge(_) => None,
+            NetworkTrace(err) => Some(err),
         }
     }
 }
 
-#[cfg(feature = "std")]
-impl fmt::Display for ReadError {
+impl fmt::Display for SomeIpTraceError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use ReadError::*;
-
+        use SomeIpTraceError::*;
         match self {
-            UnexpectedEndOfSlice(err) => {
-                write!(f, "ReadError: Unexpected end of slice. The given slice only contained {} bytes, which is less then minimum required {} bytes.", err.actual_size, err.minimum_size)
-            }
-            UnsupportedDltVersion(err) => err.fmt(f),
-            DltMessageLengthTooSmall(err) => err.fmt(f),
-            StorageHeaderStartPattern(err) => err.fmt(f),
-            IoError(err) => err.fmt(f),
+            NotASomeIpTrace(net_type) => write!(
+                f,
+                "DLT SOME/IP Trace: Expected a SOME/IP or ethernet network trace but got a trace with the network type {:?}.",
+                net_type
+            ),
+            UnexpectedEndOfSlice(err) => err.fmt(f),
+            LengthFieldTooSmall(length) => write!(
+                f,
+                "DLT SOME/IP Trace: Length field {} of the SOME/IP header is smaller then the minimum of 8.",
+                length
+            ),
+            LengthFieldTooLarge(length) => write!(
+                f,
+                "DLT SOME/IP Trace: Length field {} of the SOME/IP header exceeds the maximum supported message length.",
+                length
+            ),
+            NetworkTrace(err) => err.fmt(f),
         }
     }
 }
 
-#[cfg(feature = "std")]
-impl From<StorageHeaderStartPatternError> for ReadError {
-    fn from(err: StorageHeaderStartPatternError) -> ReadError {
-        ReadError::StorageHeaderStartPattern(err)
+impl From<NetworkTraceError> for SomeIpTraceError {
+    fn from(err: NetworkTraceError) -> Self {
+        SomeIpTraceError::NetworkTrace(err)
     }
 }
 
-#[cfg(feature = "std")]
-impl From<PacketSliceError> for ReadError {
-    fn from(err: PacketSliceError) -> ReadError {
-        use PacketSliceError as I;
-        match err {
-            I::UnsupportedDltVersion(err) => ReadError::UnsupportedDltVersion(err),
-            I::MessageLengthTooSmall(err) => ReadError::DltMessageLengthTooSmall(err),
-            I::UnexpectedEndOfSlice(err) => ReadError::UnexpectedEndOfSlice(err),
+/// Tests for `SomeIpTraceError` methods
+#[cfg(test)]
+mod some_ip_trace_error_tests {
+    use super::*;
+
+    fn end_of_slice() -> UnexpectedEndOfSliceError {
+        UnexpectedEndOfSliceError {
+            layer: Layer::SomeIpHeader,
+            minimum_size: 16,
+            actual_size: 2,
         }
     }
+
+    #[test]
+    fn clone_eq_debug() {
+        use SomeIpTraceError::*;
+        let v = NotASomeIpTrace(DltNetworkType::Can);
+        assert_eq!(v, v.clone());
+        assert_eq!("NotASomeIpTrace(Can)", format!("{:?}", v));
+    }
+
+    #[test]
+    fn display() {
+        use SomeIpTraceError::*;
+        assert_eq!(
+            "DLT SOME/IP Trace: Expected a SOME/IP or ethernet network trace but got a trace with the network type Can.",
+            format!("{}", NotASomeIpTrace(DltNetworkType::Can))
+        );
+        assert_eq!(
+            "SomeIpHeader: Unexpected end of slice. The given slice only contained 2 bytes, which is less then minimum required 16 bytes.",
+            format!("{}", UnexpectedEndOfSlice(end_of_slice()))
+        );
+        assert_eq!(
+            "DLT SOME/IP Trace: Length field 7 of the SOME/IP header is smaller then the minimum of 8.",
+            format!("{}", LengthFieldTooSmall(7))
+        );
+        assert_eq!(
+            "DLT SOME/IP Trace: Length field 4294967295 of the SOME/IP header exceeds the maximum supported message length.",
+            format!("{}", LengthFieldTooLarge(u32::MAX))
+        );
+        assert_eq!(
+            format!("{}", NetworkTraceError::ArgumentNotRaw(1)),
+            format!("{}", NetworkTrace(NetworkTraceError::ArgumentNotRaw(1)))
+        );
+    }
+
+    #[test]
+    fn from() {
+        assert_eq!(
+            SomeIpTraceError::NetworkTrace(NetworkTraceError::ArgumentNotRaw(1)),
+            NetworkTraceError::ArgumentNotRaw(1).into()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn source() {
+        use std::error::Error;
+        use SomeIpTraceError::*;
+        assert!(NotASomeIpTrace(DltNetworkType::Can).source().is_none());
+        assert!(UnexpectedEndOfSlice(end_of_slice()).source().is_some());
+        assert!(LengthFieldTooSmall(7).source().is_none());
+        assert!(LengthFieldTooLarge(u32::MAX).source().is_none());
+        assert!(NetworkTrace(NetworkTraceError::ArgumentNotRaw(1))
+            .source()
+            .is_some());
+    }
+} // mod some_ip_trace_error_tests
+
+/// Error that can occur when formatting a non verbose message via
+/// [`crate::DltPacketSlice::decode_non_verbose_with_format`] or
+/// [`crate::FormatSpec::format_payload`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NonVerboseFormatError {
+    /// Error if the message is a verbose message.
+    VerboseMessage,
+
+    /// Error if the payload is too short to contain a message id.
+    MissingMessageId,
+
+    /// Error if no format is known for the message id (contains the
+    /// message id).
+    UnknownMessageId(u32),
+
+    /// Error if an argument could not be decoded (payload too short or
+    /// invalid string).
+    InvalidArgument {
+        /// Message id of the message.
+        msg_id: u32,
+        /// Index of the argument in the format.
+        index: usize,
+        /// Expected type of the argument.
+        arg_type: FormatArgType,
+    },
 }
 
 #[cfg(feature = "std")]
-impl From<io::Error> for ReadError {
-    fn from(err: io::Error) -> ReadError {
-        ReadError::IoError(err)
+impl std::error::Error for NonVerboseFormatError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
     }
 }
 
-/// Tests for `ReadError` methods
-#[cfg(all(feature = "std", test))]
-mod read_error {
+impl fmt::Display for NonVerboseFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use NonVerboseFormatError::*;
+        match self {
+            VerboseMessage => write!(
+                f,
+                "DLT Non Verbose Format: Expected a non verbose message but got a verbose message."
+            ),
+            MissingMessageId => write!(
+                f,
+                "DLT Non Verbose Format: Payload is too short to contain a message id."
+            ),
+            UnknownMessageId(msg_id) => write!(
+                f,
+                "DLT Non Verbose Format: No format known for the message id 0x{:x}.",
+                msg_id
+            ),
+            InvalidArgument {
+                msg_id,
+                index,
+                arg_type,
+            } => write!(
+                f,
+                "DLT Non Verbose Format: Failed to decode argument {} (type {:?}) of the message with the id 0x{:x} (payload too short or invalid string).",
+                index, arg_type, msg_id
+            ),
+        }
+    }
+}
+
+/// Tests for `NonVerboseFormatError` methods
+#[cfg(test)]
+mod non_verbose_format_error_tests {
     use super::*;
-    use proptest::prelude::*;
 
     #[test]
-    fn debug() {
-        use ReadError::*;
-
-        {
-            let c = UnexpectedEndOfSliceError {
-                minimum_size: 1,
-                actual_size: 2,
-                layer: Layer::DltHeader,
-            };
-            assert_eq!(
-                format!("UnexpectedEndOfSlice({:?})", c),
-                format!("{:?}", UnexpectedEndOfSlice(c))
-            );
-        }
-        {
-            let c = UnsupportedDltVersionError {
-                unsupported_version: 123,
-            };
-            assert_eq!(
-                format!("UnsupportedDltVersion({:?})", c),
-                format!("{:?}", UnsupportedDltVersion(c))
-            );
-        }
-        {
-            let c = DltMessageLengthTooSmallError {
-                required_length: 3,
-                actual_length: 4,
-            };
-            assert_eq!(
-                format!("DltMessageLengthTooSmall({:?})", c),
-                format!("{:?}", DltMessageLengthTooSmall(c))
-            );
-        }
-        {
-            let c = std::io::Error::new(std::io::ErrorKind::Other, "oh no!");
-            assert_eq!(format!("IoError({:?})", c), format!("{:?}", IoError(c)));
-        }
+    fn clone_eq_debug() {
+        use NonVerboseFormatError::*;
+        let v = UnknownMessageId(0x12);
+        assert_eq!(v, v.clone());
+        assert_eq!("UnknownMessageId(18)", format!("{:?}", v));
     }
 
-    proptest! {
-        #[test]
-        fn display(
-            usize0 in any::<usize>(),
-            usize1 in any::<usize>(),
-        ) {
+    #[test]
+    fn display() {
+        use NonVerboseFormatError::*;
+        assert_eq!(
+            "DLT Non Verbose Format: Expected a non verbose message but got a verbose message.",
+            format!("{}", VerboseMessage)
+        );
+        assert_eq!(
+            "DLT Non Verbose Format: Payload is too short to contain a message id.",
+            format!("{}", MissingMessageId)
+        );
+        assert_eq!(
+            "DLT Non Verbose Format: No format known for the message id 0x12.",
+            format!("{}", UnknownMessageId(0x12))
+        );
+        assert_eq!(
+            "DLT Non Verbose Format: Failed to decode argument 2 (type U16) of the message with the id 0x12 (payload too short or invalid string).",
+            format!(
+                "{}",
+                InvalidArgument {
+                    msg_id: 0x12,
+                    index: 2,
+                    arg_type: FormatArgType::U16
+                }
+            )
+        );
+    }
 
-            use ReadError::*;
+    #[cfg(feature = "std")]
+    #[test]
+    fn source() {
+        use std::error::Error;
+        use NonVerboseFormatError::*;
+        assert!(VerboseMessage.source().is_none());
+        assert!(MissingMessageId.source().is_none());
+        assert!(UnknownMessageId(1).source().is_none());
+        assert!(InvalidArgument {
+            msg_id: 1,
+            index: 0,
+            arg_type: FormatArgType::Bool
+        }
+        .source()
+        .is_none());
+    }
+} // mod non_verbose_format_error_tests
 
-            // UnexpectedEndOfSlice
-            assert_eq!(
-                &format!("ReadError: Unexpected end of slice. The given slice only contained {} bytes, which is less then minimum required {} bytes.", usize1, usize0),
-                &format!(
-                    "{}",
-                    UnexpectedEndOfSlice(
-                        UnexpectedEndOfSliceError {
-                            layer: Layer::DltHeader,
-                            minimum_size: usize0,
-                            actual_size: usize1,
-                        }
-                    )
-                )
-            );
+/// Errors that can occur while formatting DLT messages via `format_all`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FormatError {
+    /// Error if a message could not be sliced.
+    Packet {
+        /// Index of the message that could not be sliced.
+        index: usize,
+        err: PacketSliceError,
+    },
+
+    /// Error returned by the writer the messages are formatted to.
+    Fmt {
+        /// Index of the message that could not be written.
+        index: usize,
+        err: fmt::Error,
+    },
+}
 
-            // UnsupportedDltVersionError
-            {
-                let c = UnsupportedDltVersionError{ unsupported_version: 123 };
-                assert_eq!(
-                    &format!("{}", c),
-                    &format!("{}", UnsupportedDltVersion(c))
-                );
-            }
+impl FormatError {
+    /// Index of the message that could not be formatted.
+    pub fn index(&self) -> usize {
+        use FormatError::*;
+        match self {
+            Packet { index, .. } => *index,
+            Fmt { index, .. } => *index,
+        }
+    }
+}
 
-            // DltMessageLengthTooSmall
-            {
-                let c = DltMessageLengthTooSmallError{
-                    required_length: usize0,
-                    actual_length: usize1
-                };
-                assert_eq!(
-                    &format!("{}", c),
-                    &format!("{}", DltMessageLengthTooSmall(c))
-                );
-            }
+#[cfg(feature = "std")]
+impl std::error::Error for FormatError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use FormatError::*;
+        match self {
+            Packet { err, .. } => Some(err),
+            Fmt { err, .. } => Some(err),
+        }
+    }
+}
 
-            // StorageHeaderStartPattern
-            {
-                let c = StorageHeaderStartPatternError{
-                    actual_pattern: [1,2,3,4]
-                };
-                assert_eq!(
-                    &format!("{}", c),
-                    &format!("{}", StorageHeaderStartPattern(c))
-                );
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FormatError::*;
+        match self {
+            Packet { index, err } => {
+                write!(f, "FormatError: Failed to slice message {}: {}", index, err)
             }
-
-            //IoError
-            {
-                let custom_error = std::io::Error::new(std::io::ErrorKind::Other, "some error");
-                assert_eq!(
-                    &format!("{}", custom_error),
-                    &format!("{}", IoError(custom_error))
-                );
+            Fmt { index, err } => {
+                write!(f, "FormatError: Failed to write message {}: {}", index, err)
             }
         }
     }
+}
 
-    #[test]
-    fn source() {
-        use std::error::Error;
-        use ReadError::*;
-
-        assert!(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-            layer: Layer::DltHeader,
-            minimum_size: 1,
-            actual_size: 2
-        })
-        .source()
-        .is_some());
-        assert!(UnsupportedDltVersion(UnsupportedDltVersionError {
-            unsupported_version: 123
-        })
-        .source()
-        .is_some());
-        assert!(DltMessageLengthTooSmall(DltMessageLengthTooSmallError {
-            required_length: 3,
-            actual_length: 4
-        })
-        .source()
-        .is_some());
-        assert!(StorageHeaderStartPattern(StorageHeaderStartPatternError {
-            actual_pattern: [1, 2, 3, 4]
-        })
-        .source()
-        .is_some());
-        assert!(
-            IoError(std::io::Error::new(std::io::ErrorKind::Other, "oh no!"))
-                .source()
-                .is_some()
-        );
+/// Tests for `FormatError` methods
+#[cfg(test)]
+mod format_error {
+    use super::*;
+
+    fn packet_err() -> PacketSliceError {
+        PacketSliceError::UnsupportedDltVersion(UnsupportedDltVersionError {
+            unsupported_version: 123,
+        })
     }
 
     #[test]
-    fn from_io_error() {
-        let r: ReadError = std::io::Error::new(std::io::ErrorKind::Other, "oh no!").into();
-        assert_matches!(r, ReadError::IoError(_));
+    fn debug() {
+        use FormatError::*;
+        assert_eq!(
+            format!("Fmt {{ index: 3, err: {:?} }}", fmt::Error),
+            format!(
+                "{:?}",
+                Fmt {
+                    index: 3,
+                    err: fmt::Error
+                }
+            )
+        );
     }
 
     #[test]
-    fn from_storage_header_error() {
-        let r: ReadError = StorageHeaderStartPatternError {
-            actual_pattern: [1, 2, 3, 4],
-        }
-        .into();
-        assert_matches!(r, ReadError::StorageHeaderStartPattern(_));
+    fn clone_eq() {
+        use FormatError::*;
+        let v = Packet {
+            index: 1,
+            err: packet_err(),
+        };
+        assert_eq!(v, v.clone());
     }
 
     #[test]
-    fn from_packet_slice_error() {
-        use PacketSliceError as I;
+    fn display() {
+        use FormatError::*;
+        assert_eq!(
+            format!("FormatError: Failed to slice message 1: {}", packet_err()),
+            format!(
+                "{}",
+                Packet {
+                    index: 1,
+                    err: packet_err()
+                }
+            )
+        );
+        assert_eq!(
+            format!("FormatError: Failed to write message 3: {}", fmt::Error),
+            format!(
+                "{}",
+                Fmt {
+                    index: 3,
+                    err: fmt::Error
+                }
+            )
+        );
+    }
 
-        // UnsupportedDltVersion
-        {
-            let r: ReadError = I::UnsupportedDltVersion(UnsupportedDltVersionError {
-                unsupported_version: 123,
-            })
-            .into();
-            assert_matches!(r, ReadError::UnsupportedDltVersion(_));
-        }
+    #[test]
+    fn index() {
+        use FormatError::*;
+        assert_eq!(
+            1,
+            Packet {
+                index: 1,
+                err: packet_err()
+            }
+            .index()
+        );
+        assert_eq!(
+            3,
+            Fmt {
+                index: 3,
+                err: fmt::Error
+            }
+            .index()
+        );
+    }
 
-        // MessageLengthTooSmall
-        {
-            let r: ReadError = I::MessageLengthTooSmall(DltMessageLengthTooSmallError {
-                required_length: 3,
-                actual_length: 4,
-            })
-            .into();
-            assert_matches!(r, ReadError::DltMessageLengthTooSmall(_));
+    #[cfg(feature = "std")]
+    #[test]
+    fn source() {
+        use std::error::Error;
+        use FormatError::*;
+        assert!(Packet {
+            index: 1,
+            err: packet_err()
         }
-
-        // UnexpectedEndOfSlice
-        {
-            let r: ReadError = I::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::DltHeader,
-                minimum_size: 1,
-                actual_size: 2,
-            })
-            .into();
-            assert_matches!(r, ReadError::UnexpectedEndOfSlice(_));
+        .source()
+        .is_some());
+        assert!(Fmt {
+            index: 3,
+            err: fmt::Error
         }
+        .source()
+        .is_some());
     }
-} // mod read_error
+} // mod format_error
 
 /// Error that can occur when an out of range value is passed to a function.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum RangeError {
     /// Error if the user defined value is outside the range of 7-15
     NetworkTypekUserDefinedOutsideOfRange(u8),
+
+    /// Error if the options of a "get log info" request are outside the
+    /// range of 3-7.
+    GetLogInfoOptionsOutsideOfRange(u8),
+
+    /// Error if an id (e.g. application or context id) is longer than the
+    /// 4 bytes of a DLT id (contains the length in bytes).
+    IdTooLong(usize),
 }
 
 #[cfg(feature = "std")]
@@ -960,6 +3389,12 @@ impl fmt::Display for RangeError {
             NetworkTypekUserDefinedOutsideOfRange(value) => {
                 write!(f, "RangeError: Message type info field user defined value of {} outside of the allowed range of 7-15.", value)
             }
+            GetLogInfoOptionsOutsideOfRange(value) => {
+                write!(f, "RangeError: Get log info options value of {} outside of the allowed range of 3-7.", value)
+            }
+            IdTooLong(len) => {
+                write!(f, "RangeError: Id with a length of {} bytes does not fit into the 4 bytes of a DLT id.", len)
+            }
         }
     }
 }
@@ -996,6 +3431,18 @@ mod range_error_tests {
                 &format!("RangeError: Message type info field user defined value of {} outside of the allowed range of 7-15.", value),
                 &format!("{}", NetworkTypekUserDefinedOutsideOfRange(value))
             );
+
+            // GetLogInfoOptionsOutsideOfRange
+            assert_eq!(
+                &format!("RangeError: Get log info options value of {} outside of the allowed range of 3-7.", value),
+                &format!("{}", GetLogInfoOptionsOutsideOfRange(value))
+            );
+
+            // IdTooLong
+            assert_eq!(
+                &format!("RangeError: Id with a length of {} bytes does not fit into the 4 bytes of a DLT id.", value),
+                &format!("{}", IdTooLong(usize::from(value)))
+            );
         }
     }
 
@@ -1008,5 +3455,466 @@ mod range_error_tests {
         assert!(NetworkTypekUserDefinedOutsideOfRange(123)
             .source()
             .is_none());
+        assert!(GetLogInfoOptionsOutsideOfRange(2).source().is_none());
+        assert!(IdTooLong(5).source().is_none());
     }
 } // mod range_error
+
+/// Error wrapping the errors of the different layers of the crate (DLT
+/// header & packet slicing, verbose & control payload decoding, reading,
+/// writing & io).
+///
+/// The iterators, readers & payload decoders return their layer specific
+/// errors (e.g. [`crate::SliceIterator`] returns a [`PacketSliceError`] &
+/// `DltReader` a [`ReadError`]). `DltError` is not returned by the
+/// crate itself, but all layer specific errors can be converted into it,
+/// so code combining them can use `?` with a single error type without
+/// losing the details of the original error.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{error::{DltError, Layer}, DltPacketSlice};
+///
+/// fn ecu_id(data: &[u8]) -> Result<Option<[u8; 4]>, DltError> {
+///     Ok(DltPacketSlice::from_slice(data)?.ecu_id())
+/// }
+///
+/// let err = ecu_id(&[0x35, 0]).unwrap_err();
+/// assert_eq!(Some(Layer::DltHeader), err.layer());
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DltError {
+    /// Error while decoding a DLT header or slicing a DLT packet.
+    Packet(PacketSliceError),
+
+    /// Error while decoding a verbose value.
+    VerboseDecode(VerboseDecodeError),
+
+    /// Error while encoding a verbose value.
+    VerboseEncode(VerboseEncodeError),
+
+    /// Error while decoding the payload of a control message.
+    Control(ControlDecodeError),
+
+    /// Error while reading DLT messages from a stream.
+    #[cfg(feature = "std")]
+    Read(ReadError),
+
+    /// Error while writing DLT messages.
+    #[cfg(feature = "std")]
+    Write(WriteError),
+
+    /// Standard io error.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+}
+
+impl DltError {
+    /// Layer in which the error occured (`None` for io errors).
+    pub fn layer(&self) -> Option<Layer> {
+        use DltError::*;
+        match self {
+            Packet(err) => Some(err.layer()),
+            VerboseDecode(err) => Some(err.layer()),
+            VerboseEncode(_) => Some(Layer::VerboseValue),
+            Control(err) => Some(err.layer()),
+            #[cfg(feature = "std")]
+            Read(err) => err.layer(),
+            #[cfg(feature = "std")]
+            Write(WriteError::MessageTooLong { .. }) => Some(Layer::DltHeader),
+            #[cfg(feature = "std")]
+            Write(WriteError::Io { .. }) => None,
+            #[cfg(feature = "std")]
+            Io(_) => None,
+        }
+    }
+
+    /// Returns true if the data ended before it could be decoded completely
+    /// & decoding can be retried once more data has arrived (see the
+    /// `is_recoverable_with_more_data` methods of the wrapped errors).
+    pub fn is_recoverable_with_more_data(&self) -> bool {
+        use DltError::*;
+        match self {
+            Packet(err) => err.is_recoverable_with_more_data(),
+            VerboseDecode(err) => err.is_recoverable_with_more_data(),
+            VerboseEncode(_) => false,
+            Control(err) => err.is_recoverable_with_more_data(),
+            #[cfg(feature = "std")]
+            Read(err) => err.is_recoverable_with_more_data(),
+            #[cfg(feature = "std")]
+            Write(_) => false,
+            #[cfg(feature = "std")]
+            Io(err) => err.kind() == io::ErrorKind::UnexpectedEof,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DltError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use DltError::*;
+        match self {
+            Packet(err) => Some(err),
+            VerboseDecode(err) => Some(err),
+            VerboseEncode(err) => Some(err),
+            Control(err) => Some(err),
+            Read(err) => Some(err),
+            Write(err) => Some(err),
+            Io(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for DltError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DltError::*;
+        match self {
+            Packet(err) => err.fmt(f),
+            VerboseDecode(err) => err.fmt(f),
+            VerboseEncode(err) => err.fmt(f),
+            Control(err) => err.fmt(f),
+            #[cfg(feature = "std")]
+            Read(err) => err.fmt(f),
+            #[cfg(feature = "std")]
+            Write(err) => err.fmt(f),
+            #[cfg(feature = "std")]
+            Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<PacketSliceError> for DltError {
+    fn from(err: PacketSliceError) -> DltError {
+        DltError::Packet(err)
+    }
+}
+
+impl From<UnsupportedDltVersionError> for DltError {
+    fn from(err: UnsupportedDltVersionError) -> DltError {
+        DltError::Packet(PacketSliceError::UnsupportedDltVersion(err))
+    }
+}
+
+impl From<DltMessageLengthTooSmallError> for DltError {
+    fn from(err: DltMessageLengthTooSmallError) -> DltError {
+        DltError::Packet(PacketSliceError::MessageLengthTooSmall(err))
+    }
+}
+
+impl From<VerboseDecodeError> for DltError {
+    fn from(err: VerboseDecodeError) -> DltError {
+        DltError::VerboseDecode(err)
+    }
+}
+
+impl From<VerboseEncodeError> for DltError {
+    fn from(err: VerboseEncodeError) -> DltError {
+        DltError::VerboseEncode(err)
+    }
+}
+
+impl From<RedactError> for DltError {
+    fn from(err: RedactError) -> DltError {
+        match err {
+            RedactError::Decode(err) => DltError::VerboseDecode(err),
+            RedactError::Encode(err) => DltError::VerboseEncode(err),
+        }
+    }
+}
+
+impl From<ControlDecodeError> for DltError {
+    fn from(err: ControlDecodeError) -> DltError {
+        DltError::Control(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ReadError> for DltError {
+    fn from(err: ReadError) -> DltError {
+        DltError::Read(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<WriteError> for DltError {
+    fn from(err: WriteError) -> DltError {
+        DltError::Write(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for DltError {
+    fn from(err: io::Error) -> DltError {
+        DltError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod dlt_error_tests {
+    use super::*;
+
+    fn end_of_slice(layer: Layer) -> UnexpectedEndOfSliceError {
+        UnexpectedEndOfSliceError {
+            layer,
+            minimum_size: 2,
+            actual_size: 1,
+        }
+    }
+
+    #[test]
+    fn debug() {
+        let inner = PacketSliceError::ReservedMessageInfo(0xff);
+        assert_eq!(
+            format!("Packet({:?})", inner),
+            format!("{:?}", DltError::Packet(inner))
+        );
+    }
+
+    #[test]
+    fn display() {
+        let inner = PacketSliceError::UnexpectedEndOfSlice(end_of_slice(Layer::DltHeader));
+        assert_eq!(format!("{}", inner), format!("{}", DltError::from(inner)));
+
+        let inner = VerboseDecodeError::InvalidBoolValue(2);
+        assert_eq!(format!("{}", inner), format!("{}", DltError::from(inner)));
+
+        let inner = VerboseEncodeError::StringNotAscii;
+        assert_eq!(format!("{}", inner), format!("{}", DltError::from(inner)));
+
+        let inner = ControlDecodeError::MissingTimestamp;
+        assert_eq!(format!("{}", inner), format!("{}", DltError::from(inner)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display_std() {
+        let inner = ReadError::DltMessageLengthTooLarge(70000);
+        assert_eq!(format!("{}", inner), format!("{}", DltError::from(inner)));
+
+        let inner = WriteError::MessageTooLong {
+            index: 1,
+            length: 70000,
+        };
+        assert_eq!(format!("{}", inner), format!("{}", DltError::from(inner)));
+
+        let inner = std::io::Error::new(std::io::ErrorKind::Other, "oh no!");
+        assert_eq!(format!("{}", inner), format!("{}", DltError::from(inner)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn source() {
+        use std::error::Error;
+
+        assert!(DltError::from(PacketSliceError::ReservedMessageInfo(0xff))
+            .source()
+            .is_some());
+        assert!(DltError::from(VerboseDecodeError::InvalidBoolValue(2))
+            .source()
+            .is_some());
+        assert!(DltError::from(VerboseEncodeError::StringNotAscii)
+            .source()
+            .is_some());
+        assert!(DltError::from(ControlDecodeError::MissingTimestamp)
+            .source()
+            .is_some());
+        assert!(DltError::from(ReadError::DltMessageLengthTooLarge(70000))
+            .source()
+            .is_some());
+        assert!(DltError::from(WriteError::MessageTooLong {
+            index: 1,
+            length: 70000
+        })
+        .source()
+        .is_some());
+        assert!(
+            DltError::from(std::io::Error::new(std::io::ErrorKind::Other, "oh no!"))
+                .source()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn from() {
+        assert_matches!(
+            DltError::from(UnsupportedDltVersionError {
+                unsupported_version: 3
+            }),
+            DltError::Packet(PacketSliceError::UnsupportedDltVersion(
+                UnsupportedDltVersionError {
+                    unsupported_version: 3
+                }
+            ))
+        );
+        assert_matches!(
+            DltError::from(DltMessageLengthTooSmallError {
+                required_length: 4,
+                actual_length: 3,
+            }),
+            DltError::Packet(PacketSliceError::MessageLengthTooSmall(_))
+        );
+        assert_matches!(
+            DltError::from(RedactError::Decode(VerboseDecodeError::InvalidBoolValue(2))),
+            DltError::VerboseDecode(VerboseDecodeError::InvalidBoolValue(2))
+        );
+        assert_matches!(
+            DltError::from(RedactError::Encode(VerboseEncodeError::StringNotAscii)),
+            DltError::VerboseEncode(VerboseEncodeError::StringNotAscii)
+        );
+        assert_matches!(
+            DltError::from(ControlDecodeError::InvalidBool(2)),
+            DltError::Control(ControlDecodeError::InvalidBool(2))
+        );
+    }
+
+    #[test]
+    fn layer() {
+        use Layer::*;
+
+        let tests = [
+            (
+                DltError::from(PacketSliceError::ReservedMessageInfo(0xff)),
+                Some(DltHeader),
+            ),
+            (
+                DltError::from(PacketSliceError::UnexpectedEndOfSlice(end_of_slice(
+                    StorageHeader,
+                ))),
+                Some(StorageHeader),
+            ),
+            (
+                DltError::from(VerboseDecodeError::InvalidTypeInfo([0; 4])),
+                Some(VerboseTypeInfo),
+            ),
+            (
+                DltError::from(VerboseDecodeError::InvalidBoolValue(2)),
+                Some(VerboseValue),
+            ),
+            (
+                DltError::from(VerboseEncodeError::StringNotAscii),
+                Some(VerboseValue),
+            ),
+            (
+                DltError::from(ControlDecodeError::MissingTimestamp),
+                Some(ControlMessage),
+            ),
+        ];
+        for (err, expected) in tests {
+            assert_eq!(expected, err.layer());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn layer_std() {
+        use Layer::*;
+
+        let tests = [
+            (
+                DltError::from(ReadError::StorageHeaderStartPattern(
+                    StorageHeaderStartPatternError {
+                        actual_pattern: [0; 4],
+                    },
+                )),
+                Some(StorageHeader),
+            ),
+            (
+                DltError::from(WriteError::MessageTooLong {
+                    index: 1,
+                    length: 70000,
+                }),
+                Some(DltHeader),
+            ),
+            (
+                DltError::from(WriteError::Io {
+                    index: 1,
+                    err: std::io::Error::new(std::io::ErrorKind::Other, "oh no!"),
+                }),
+                None,
+            ),
+            (
+                DltError::from(std::io::Error::new(std::io::ErrorKind::Other, "oh no!")),
+                None,
+            ),
+        ];
+        for (err, expected) in tests {
+            assert_eq!(expected, err.layer());
+        }
+    }
+
+    #[test]
+    fn is_recoverable_with_more_data() {
+        let tests = [
+            (
+                DltError::from(PacketSliceError::UnexpectedEndOfSlice(end_of_slice(
+                    Layer::DltHeader,
+                ))),
+                true,
+            ),
+            (
+                DltError::from(PacketSliceError::ReservedMessageInfo(0xff)),
+                false,
+            ),
+            (
+                DltError::from(VerboseDecodeError::UnexpectedEndOfSlice(end_of_slice(
+                    Layer::VerboseValue,
+                ))),
+                true,
+            ),
+            (
+                DltError::from(VerboseDecodeError::InvalidBoolValue(2)),
+                false,
+            ),
+            (DltError::from(VerboseEncodeError::StringNotAscii), false),
+            (
+                DltError::from(ControlDecodeError::UnexpectedEndOfSlice(end_of_slice(
+                    Layer::ControlMessage,
+                ))),
+                true,
+            ),
+            (DltError::from(ControlDecodeError::MissingTimestamp), false),
+        ];
+        for (err, expected) in tests {
+            assert_eq!(expected, err.is_recoverable_with_more_data());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn is_recoverable_with_more_data_std() {
+        use std::io::{Error, ErrorKind};
+        let tests = [
+            (
+                DltError::from(ReadError::IoError(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "eof",
+                ))),
+                true,
+            ),
+            (
+                DltError::from(ReadError::DltMessageLengthTooLarge(70000)),
+                false,
+            ),
+            (
+                DltError::from(WriteError::MessageTooLong {
+                    index: 1,
+                    length: 70000,
+                }),
+                false,
+            ),
+            (
+                DltError::from(Error::new(ErrorKind::UnexpectedEof, "eof")),
+                true,
+            ),
+            (
+                DltError::from(Error::new(ErrorKind::Other, "oh no!")),
+                false,
+            ),
+        ];
+        for (err, expected) in tests {
+            assert_eq!(expected, err.is_recoverable_with_more_data());
+        }
+    }
+} // mod dlt_error_tests
@@ -0,0 +1,412 @@
+use super::xml::{self, XmlElement};
+use super::*;
+use crate::error::FibexParseError;
+use crate::NonVerboseRegistry;
+use std::collections::BTreeMap;
+use std::string::ToString;
+use std::vec::Vec;
+
+/// Non verbose message descriptions loaded from a FIBEX file.
+///
+/// Only the subset of FIBEX used to describe DLT non verbose messages
+/// is interpreted:
+///
+/// * `FRAME` elements with the id `ID_<message id>` describe a message.
+///   The `APPLICATION_ID` & `CONTEXT_ID` are taken from the
+///   `MANUFACTURER-EXTENSION` of the frame.
+/// * The `PDU-INSTANCES` of a frame (ordered by `SEQUENCE-NUMBER`)
+///   reference `PDU` elements. A PDU with `SIGNAL-INSTANCES` adds
+///   arguments, a PDU without signals adds its `DESC` as static text.
+/// * The type of an argument is defined by the `CODING-REF` of the
+///   referenced `SIGNAL` (e.g. `S_UINT32`, see [`FibexArgType`]).
+///
+/// Frames that can not be interpreted are skipped & a [`FibexWarning`]
+/// is collected. Only a file that is not well formed XML results in
+/// an error.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::fibex::FibexFile;
+/// use dlt_parse::NonVerboseRegistry;
+///
+/// let fibex = FibexFile::parse(r#"
+///     <fx:FIBEX xmlns:fx="http://www.asam.net/xml/fbx">
+///       <fx:ELEMENTS>
+///         <fx:FRAMES>
+///           <fx:FRAME ID="ID_42">
+///             <fx:MANUFACTURER-EXTENSION>
+///               <APPLICATION_ID>APP1</APPLICATION_ID>
+///               <CONTEXT_ID>CTX1</CONTEXT_ID>
+///             </fx:MANUFACTURER-EXTENSION>
+///             <fx:PDU-INSTANCES>
+///               <fx:PDU-INSTANCE ID="P_42_0">
+///                 <fx:PDU-REF ID-REF="PDU_42_0"/>
+///                 <fx:SEQUENCE-NUMBER>0</fx:SEQUENCE-NUMBER>
+///               </fx:PDU-INSTANCE>
+///               <fx:PDU-INSTANCE ID="P_42_1">
+///                 <fx:PDU-REF ID-REF="PDU_42_1"/>
+///                 <fx:SEQUENCE-NUMBER>1</fx:SEQUENCE-NUMBER>
+///               </fx:PDU-INSTANCE>
+///             </fx:PDU-INSTANCES>
+///           </fx:FRAME>
+///         </fx:FRAMES>
+///         <fx:PDUS>
+///           <fx:PDU ID="PDU_42_0">
+///             <ho:DESC>Speed changed to </ho:DESC>
+///           </fx:PDU>
+///           <fx:PDU ID="PDU_42_1">
+///             <fx:SIGNAL-INSTANCES>
+///               <fx:SIGNAL-INSTANCE ID="S_42_1">
+///                 <fx:SIGNAL-REF ID-REF="S_UINT8"/>
+///               </fx:SIGNAL-INSTANCE>
+///             </fx:SIGNAL-INSTANCES>
+///           </fx:PDU>
+///         </fx:PDUS>
+///         <fx:SIGNALS>
+///           <fx:SIGNAL ID="S_UINT8">
+///             <fx:CODING-REF ID-REF="S_UINT8"/>
+///           </fx:SIGNAL>
+///         </fx:SIGNALS>
+///       </fx:ELEMENTS>
+///     </fx:FIBEX>
+/// "#).unwrap();
+/// assert!(fibex.warnings.is_empty());
+///
+/// let mut registry = NonVerboseRegistry::new();
+/// fibex.register_into(&mut registry);
+///
+/// let decoded = registry
+///     .decode_payload(*b"APP1", *b"CTX1", 42, &[50], false)
+///     .unwrap();
+/// assert_eq!("Speed changed to 50", decoded.to_string());
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FibexFile {
+    /// Successfully loaded message descriptions.
+    pub messages: Vec<FibexMessage>,
+    /// Problems encountered while loading (one per skipped frame).
+    pub warnings: Vec<FibexWarning>,
+}
+
+impl FibexFile {
+    /// Loads the non verbose message descriptions from the content of
+    /// a FIBEX file.
+    pub fn parse(xml: &str) -> Result<FibexFile, FibexParseError> {
+        let root = xml::parse(xml)?;
+
+        let pdus: BTreeMap<&str, &XmlElement> = root
+            .descendants("PDU")
+            .filter_map(|pdu| Some((pdu.attribute("ID")?, pdu)))
+            .collect();
+        let signals: BTreeMap<&str, &XmlElement> = root
+            .descendants("SIGNAL")
+            .filter_map(|signal| Some((signal.attribute("ID")?, signal)))
+            .collect();
+
+        let mut result = FibexFile::default();
+        for frame in root.descendants("FRAME") {
+            match parse_frame(frame, &pdus, &signals) {
+                Ok(msg) => result.messages.push(msg),
+                Err(warning) => result.warnings.push(warning),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Registers all loaded messages as decoders in the given registry.
+    pub fn register_into(&self, registry: &mut NonVerboseRegistry) {
+        for msg in &self.messages {
+            registry.register(msg.application_id, msg.context_id, msg.msg_id, msg.clone());
+        }
+    }
+}
+
+/// Returns the child elements with the given name sorted by their
+/// `SEQUENCE-NUMBER` (missing numbers are treated as 0 & elements
+/// with the same number keep the document order).
+fn sorted_instances<'a>(parent: &'a XmlElement, name: &'a str) -> Vec<&'a XmlElement> {
+    let mut result: Vec<&XmlElement> = parent.descendants(name).collect();
+    result.sort_by_key(|e| {
+        e.child_text("SEQUENCE-NUMBER")
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(0)
+    });
+    result
+}
+
+fn parse_frame(
+    frame: &XmlElement,
+    pdus: &BTreeMap<&str, &XmlElement>,
+    signals: &BTreeMap<&str, &XmlElement>,
+) -> Result<FibexMessage, FibexWarning> {
+    use FibexWarning::*;
+
+    let frame_id = frame.attribute("ID").unwrap_or("").to_string();
+    let msg_id = match frame_id
+        .strip_prefix("ID_")
+        .and_then(|id| id.parse::<u32>().ok())
+    {
+        Some(id) => id,
+        None => return Err(InvalidFrameId { frame_id }),
+    };
+
+    let id = |name: &'static str| frame.descendants(name).next().map(|e| e.text.trim());
+    let (application_id, context_id) = match (id("APPLICATION_ID"), id("CONTEXT_ID")) {
+        (Some(app), Some(ctx)) => (app, ctx),
+        _ => return Err(MissingApplicationOrContextId { frame_id }),
+    };
+    let to_id = |value: &str| {
+        if value.len() > 4 {
+            None
+        } else {
+            let mut result = [0u8; 4];
+            result[..value.len()].copy_from_slice(value.as_bytes());
+            Some(result)
+        }
+    };
+    let application_id = match to_id(application_id) {
+        Some(id) => id,
+        None => {
+            return Err(InvalidApplicationOrContextId {
+                frame_id,
+                value: application_id.to_string(),
+            })
+        }
+    };
+    let context_id = match to_id(context_id) {
+        Some(id) => id,
+        None => {
+            return Err(InvalidApplicationOrContextId {
+                frame_id,
+                value: context_id.to_string(),
+            })
+        }
+    };
+
+    let mut parts = Vec::new();
+    for pdu_instance in sorted_instances(frame, "PDU-INSTANCE") {
+        let pdu_id = pdu_instance
+            .child("PDU-REF")
+            .and_then(|r| r.attribute("ID-REF"))
+            .unwrap_or("");
+        let pdu = match pdus.get(pdu_id) {
+            Some(pdu) => pdu,
+            None => {
+                return Err(MissingPdu {
+                    frame_id,
+                    pdu_id: pdu_id.to_string(),
+                })
+            }
+        };
+
+        let signal_instances = sorted_instances(pdu, "SIGNAL-INSTANCE");
+        if signal_instances.is_empty() {
+            if let Some(desc) = pdu.child("DESC") {
+                parts.push(FibexMessagePart::Text(desc.text.clone()));
+            }
+        }
+        for signal_instance in signal_instances {
+            let signal_id = signal_instance
+                .child("SIGNAL-REF")
+                .and_then(|r| r.attribute("ID-REF"))
+                .unwrap_or("");
+            let signal = match signals.get(signal_id) {
+                Some(signal) => signal,
+                None => {
+                    return Err(MissingSignal {
+                        frame_id,
+                        signal_id: signal_id.to_string(),
+                    })
+                }
+            };
+            let coding = signal
+                .child("CODING-REF")
+                .and_then(|r| r.attribute("ID-REF"))
+                .unwrap_or(signal_id);
+            match FibexArgType::from_coding(coding) {
+                Some(arg_type) => parts.push(FibexMessagePart::Arg(arg_type)),
+                None => {
+                    return Err(UnsupportedCoding {
+                        frame_id,
+                        coding: coding.to_string(),
+                    })
+                }
+            }
+        }
+    }
+
+    Ok(FibexMessage {
+        msg_id,
+        application_id,
+        context_id,
+        parts,
+    })
+}
+
+#[cfg(test)]
+mod fibex_file_tests {
+    use super::*;
+    use crate::test_packets;
+    use crate::*;
+    use alloc::vec;
+    use std::string::String;
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/dlt_non_verbose.fibex.xml");
+
+    fn packet(
+        is_big_endian: bool,
+        app: [u8; 4],
+        ctx: [u8; 4],
+        msg_id: u32,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let ext = DltExtendedHeader::new_non_verbose_log(DltLogLevel::Info, app, ctx);
+        let mut payload = Vec::new();
+        if is_big_endian {
+            payload.extend_from_slice(&msg_id.to_be_bytes());
+        } else {
+            payload.extend_from_slice(&msg_id.to_le_bytes());
+        }
+        payload.extend_from_slice(data);
+        test_packets::packet(test_packets::header(is_big_endian, Some(ext)), &payload)
+    }
+
+    fn s(value: &str) -> String {
+        value.to_string()
+    }
+
+    #[test]
+    fn parse_fixture() {
+        let fibex = FibexFile::parse(FIXTURE).unwrap();
+
+        assert_eq!(
+            vec![
+                FibexMessage {
+                    msg_id: 42,
+                    application_id: *b"APP1",
+                    context_id: *b"CTX1",
+                    parts: vec![
+                        FibexMessagePart::Text(s("Speed changed to ")),
+                        FibexMessagePart::Arg(FibexArgType::I32),
+                        FibexMessagePart::Text(s(" km/h")),
+                    ],
+                },
+                FibexMessage {
+                    msg_id: 43,
+                    application_id: *b"APP1",
+                    context_id: *b"CT2\0",
+                    parts: vec![
+                        FibexMessagePart::Text(s("Door ")),
+                        FibexMessagePart::Arg(FibexArgType::StrUtf8),
+                        FibexMessagePart::Text(s(" open: ")),
+                        FibexMessagePart::Arg(FibexArgType::Bool),
+                        FibexMessagePart::Arg(FibexArgType::U16),
+                    ],
+                },
+            ],
+            fibex.messages
+        );
+        assert_eq!(
+            vec![
+                FibexWarning::InvalidFrameId {
+                    frame_id: s("FRAME_X")
+                },
+                FibexWarning::MissingApplicationOrContextId {
+                    frame_id: s("ID_50")
+                },
+                FibexWarning::InvalidApplicationOrContextId {
+                    frame_id: s("ID_51"),
+                    value: s("TOOLONG")
+                },
+                FibexWarning::MissingPdu {
+                    frame_id: s("ID_52"),
+                    pdu_id: s("PDU_UNKNOWN")
+                },
+                FibexWarning::MissingSignal {
+                    frame_id: s("ID_53"),
+                    signal_id: s("S_UNKNOWN")
+                },
+                FibexWarning::UnsupportedCoding {
+                    frame_id: s("ID_54"),
+                    coding: s("S_FLOA16")
+                },
+            ],
+            fibex.warnings
+        );
+    }
+
+    #[test]
+    fn decode_with_fixture() {
+        let mut registry = NonVerboseRegistry::new();
+        FibexFile::parse(FIXTURE)
+            .unwrap()
+            .register_into(&mut registry);
+        assert_eq!(2, registry.len());
+
+        // speed (little & big endian)
+        for is_big_endian in [false, true] {
+            let value = if is_big_endian {
+                120i32.to_be_bytes()
+            } else {
+                120i32.to_le_bytes()
+            };
+            let buf = packet(is_big_endian, *b"APP1", *b"CTX1", 42, &value);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            match slice.decode_with(&registry) {
+                Some(NonVerboseDecoded::Decoded(msg)) => {
+                    assert_eq!("Speed changed to 120 km/h", msg.to_string())
+                }
+                other => panic!("unexpected result {:?}", other),
+            }
+        }
+
+        // door
+        {
+            let data = [0, 5, b'l', b'e', b'f', b't', 0, 1, 0x01, 0x02];
+            let buf = packet(true, *b"APP1", *b"CT2\0", 43, &data);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            match slice.decode_with(&registry) {
+                Some(NonVerboseDecoded::Decoded(msg)) => {
+                    assert_eq!("Door left open: true258", msg.to_string())
+                }
+                other => panic!("unexpected result {:?}", other),
+            }
+
+            // too short data falls back to raw
+            let buf = packet(true, *b"APP1", *b"CT2\0", 43, &data[..9]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Some(NonVerboseDecoded::Raw(NonVerbosePayload {
+                    msg_id: 43,
+                    data: &data[..9]
+                })),
+                slice.decode_with(&registry)
+            );
+        }
+
+        // skipped frame falls back to raw
+        {
+            let buf = packet(true, *b"APP1", *b"CTX1", 54, &[1, 2]);
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            assert_eq!(
+                Some(NonVerboseDecoded::Raw(NonVerbosePayload {
+                    msg_id: 54,
+                    data: &[1, 2]
+                })),
+                slice.decode_with(&registry)
+            );
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        assert_eq!(
+            Err(FibexParseError {
+                line: 2,
+                reason: "unexpected closing tag"
+            }),
+            FibexFile::parse("<a>\n</b>")
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use super::FibexArgType;
+use crate::{DecodedMessage, NonVerboseDecoder};
+use std::string::String;
+use std::vec::Vec;
+
+/// Part of the text of a non verbose message described in a FIBEX file.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FibexMessagePart {
+    /// Static text (`DESC` of a PDU without signals).
+    Text(String),
+    /// Argument encoded in the payload of the message.
+    Arg(FibexArgType),
+}
+
+/// Description of a non verbose message loaded from a FIBEX file.
+///
+/// Implements [`NonVerboseDecoder`] so it can be registered in a
+/// [`crate::NonVerboseRegistry`] (see [`super::FibexFile::register_into`]).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FibexMessage {
+    /// Message id of the message (from the frame id `ID_<msg id>`).
+    pub msg_id: u32,
+    /// Application id the message is sent with.
+    pub application_id: [u8; 4],
+    /// Context id the message is sent with.
+    pub context_id: [u8; 4],
+    /// Text & arguments of the message in the order they are printed.
+    pub parts: Vec<FibexMessagePart>,
+}
+
+impl FibexMessage {
+    /// Returns the template used in the [`DecodedMessage`] (static text
+    /// with a `{}` placeholder for each argument).
+    pub fn template(&self) -> String {
+        let mut result = String::new();
+        for part in &self.parts {
+            match part {
+                FibexMessagePart::Text(text) => result.push_str(text),
+                FibexMessagePart::Arg(_) => result.push_str("{}"),
+            }
+        }
+        result
+    }
+}
+
+impl NonVerboseDecoder for FibexMessage {
+    /// Decodes the arguments in the order described in the FIBEX file
+    /// (data after the last argument is ignored).
+    fn decode<'a>(
+        &self,
+        _msg_id: u32,
+        data: &'a [u8],
+        is_big_endian: bool,
+    ) -> Option<DecodedMessage<'a>> {
+        let mut values = Vec::new();
+        let mut rest = data;
+        for part in &self.parts {
+            if let FibexMessagePart::Arg(arg_type) = part {
+                let (value, next) = arg_type.decode(rest, is_big_endian)?;
+                values.push(value);
+                rest = next;
+            }
+        }
+        Some(DecodedMessage {
+            template: self.template(),
+            values,
+        })
+    }
+}
+
+#[cfg(test)]
+mod fibex_message_tests {
+    use super::*;
+    use crate::verbose::{StringValue, U16Value, VerboseValue};
+    use alloc::{string::ToString, vec};
+
+    #[test]
+    fn decode() {
+        let msg = FibexMessage {
+            msg_id: 1,
+            application_id: *b"APP1",
+            context_id: *b"CTX1",
+            parts: vec![
+                FibexMessagePart::Text("a=".to_string()),
+                FibexMessagePart::Arg(FibexArgType::U16),
+                FibexMessagePart::Text(" b=".to_string()),
+                FibexMessagePart::Arg(FibexArgType::StrUtf8),
+            ],
+        };
+        assert_eq!("a={} b={}", msg.template());
+
+        let data = [0x12, 0x34, 0, 2, b'x', 0, 0xff];
+        let decoded = msg.decode(1, &data, true).unwrap();
+        assert_eq!(
+            DecodedMessage {
+                template: "a={} b={}".to_string(),
+                values: vec![
+                    VerboseValue::U16(U16Value {
+                        variable_info: None,
+                        scaling: None,
+                        value: 0x1234,
+                    }),
+                    VerboseValue::Str(StringValue {
+                        name: None,
+                        value: "x",
+                    }),
+                ],
+            },
+            decoded
+        );
+        assert_eq!("a=4660 b=x", decoded.to_string());
+
+        // too short
+        assert_eq!(None, msg.decode(1, &data[..5], true));
+        assert_eq!(None, msg.decode(1, &data[..1], true));
+    }
+}
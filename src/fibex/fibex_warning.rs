@@ -0,0 +1,132 @@
+use core::fmt;
+use std::string::String;
+
+/// Problem encountered while loading a FIBEX file that caused a frame
+/// (non verbose message description) to be skipped.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FibexWarning {
+    /// The id of the frame is not in the format `ID_<message id>`.
+    InvalidFrameId { frame_id: String },
+
+    /// The frame has no `APPLICATION_ID` or `CONTEXT_ID`.
+    MissingApplicationOrContextId { frame_id: String },
+
+    /// An application or context id is longer then 4 bytes.
+    InvalidApplicationOrContextId { frame_id: String, value: String },
+
+    /// A PDU referenced by the frame is not defined.
+    MissingPdu { frame_id: String, pdu_id: String },
+
+    /// A signal referenced by a PDU of the frame is not defined.
+    MissingSignal { frame_id: String, signal_id: String },
+
+    /// The coding of a signal used by the frame is not supported.
+    UnsupportedCoding { frame_id: String, coding: String },
+}
+
+impl fmt::Display for FibexWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use FibexWarning::*;
+        match self {
+            InvalidFrameId { frame_id } => write!(
+                f,
+                "Skipped FIBEX frame '{}' as the id is not in the format 'ID_<message id>'.",
+                frame_id
+            ),
+            MissingApplicationOrContextId { frame_id } => write!(
+                f,
+                "Skipped FIBEX frame '{}' as the application or context id is missing.",
+                frame_id
+            ),
+            InvalidApplicationOrContextId { frame_id, value } => write!(
+                f,
+                "Skipped FIBEX frame '{}' as the application or context id '{}' is longer then 4 bytes.",
+                frame_id, value
+            ),
+            MissingPdu { frame_id, pdu_id } => write!(
+                f,
+                "Skipped FIBEX frame '{}' as the referenced PDU '{}' is not defined.",
+                frame_id, pdu_id
+            ),
+            MissingSignal { frame_id, signal_id } => write!(
+                f,
+                "Skipped FIBEX frame '{}' as the referenced signal '{}' is not defined.",
+                frame_id, signal_id
+            ),
+            UnsupportedCoding { frame_id, coding } => write!(
+                f,
+                "Skipped FIBEX frame '{}' as the signal coding '{}' is not supported.",
+                frame_id, coding
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod fibex_warning_tests {
+    use super::*;
+    use alloc::{format, string::ToString};
+
+    #[test]
+    fn clone_eq_debug() {
+        let v = FibexWarning::InvalidFrameId {
+            frame_id: "a".to_string(),
+        };
+        assert_eq!(v, v.clone());
+        assert_eq!("InvalidFrameId { frame_id: \"a\" }", format!("{:?}", v));
+    }
+
+    #[test]
+    fn display() {
+        use FibexWarning::*;
+        let s = |v: &str| v.to_string();
+        assert_eq!(
+            "Skipped FIBEX frame 'a' as the id is not in the format 'ID_<message id>'.",
+            format!("{}", InvalidFrameId { frame_id: s("a") })
+        );
+        assert_eq!(
+            "Skipped FIBEX frame 'a' as the application or context id is missing.",
+            format!("{}", MissingApplicationOrContextId { frame_id: s("a") })
+        );
+        assert_eq!(
+            "Skipped FIBEX frame 'a' as the application or context id 'b' is longer then 4 bytes.",
+            format!(
+                "{}",
+                InvalidApplicationOrContextId {
+                    frame_id: s("a"),
+                    value: s("b")
+                }
+            )
+        );
+        assert_eq!(
+            "Skipped FIBEX frame 'a' as the referenced PDU 'b' is not defined.",
+            format!(
+                "{}",
+                MissingPdu {
+                    frame_id: s("a"),
+                    pdu_id: s("b")
+                }
+            )
+        );
+        assert_eq!(
+            "Skipped FIBEX frame 'a' as the referenced signal 'b' is not defined.",
+            format!(
+                "{}",
+                MissingSignal {
+                    frame_id: s("a"),
+                    signal_id: s("b")
+                }
+            )
+        );
+        assert_eq!(
+            "Skipped FIBEX frame 'a' as the signal coding 'b' is not supported.",
+            format!(
+                "{}",
+                UnsupportedCoding {
+                    frame_id: s("a"),
+                    coding: s("b")
+                }
+            )
+        );
+    }
+}
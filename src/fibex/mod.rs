@@ -0,0 +1,14 @@
+mod xml;
+
+/// Type of an argument of a non verbose message described in a FIBEX file
+/// (the same types are used by [`crate::FormatSpec`]).
+pub use crate::FormatArgType as FibexArgType;
+
+mod fibex_file;
+pub use fibex_file::*;
+
+mod fibex_message;
+pub use fibex_message::*;
+
+mod fibex_warning;
+pub use fibex_warning::*;
@@ -0,0 +1,353 @@
+use crate::error::FibexParseError;
+use std::string::String;
+use std::vec::Vec;
+
+/// Element of a parsed XML document.
+///
+/// Namespace prefixes are removed from element & attribute names as
+/// FIBEX files use varying prefixes for the same namespaces.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct XmlElement {
+    /// Name of the element without namespace prefix.
+    pub name: String,
+    /// Attributes (name without namespace prefix & value).
+    pub attributes: Vec<(String, String)>,
+    /// Child elements.
+    pub children: Vec<XmlElement>,
+    /// Concatenated text content directly contained in the element.
+    pub text: String,
+}
+
+impl XmlElement {
+    /// Returns the value of the attribute with the given name.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the first child element with the given name.
+    pub fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Returns the trimmed text of the first child with the given name.
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|c| c.text.trim())
+    }
+
+    /// Iterates over all elements with the given name in the subtree
+    /// (including the element itself) in document order.
+    pub fn descendants<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> + 'a {
+        let mut stack = Vec::from([self]);
+        core::iter::from_fn(move || {
+            while let Some(e) = stack.pop() {
+                stack.extend(e.children.iter().rev());
+                if e.name == name {
+                    return Some(e);
+                }
+            }
+            None
+        })
+    }
+}
+
+/// Parses a XML document & returns the root element.
+///
+/// Only the subset of XML needed for FIBEX files is supported:
+/// elements, attributes, text, CDATA sections & the predefined
+/// as well as numeric character references. The XML declaration,
+/// processing instructions, comments & DOCTYPE declarations are skipped.
+pub(crate) fn parse(xml: &str) -> Result<XmlElement, FibexParseError> {
+    let mut parser = Parser { xml, pos: 0 };
+    let mut stack: Vec<XmlElement> = Vec::new();
+    let mut root = None;
+
+    loop {
+        let text_start = parser.pos;
+        let text_end = match xml[parser.pos..].find('<') {
+            Some(offset) => parser.pos + offset,
+            None => xml.len(),
+        };
+        let text = &xml[text_start..text_end];
+        parser.pos = text_end;
+        if let Some(current) = stack.last_mut() {
+            decode_text(text, &mut current.text)
+                .map_err(|reason| parser.error_at(text_start, reason))?;
+        } else if false == text.trim().is_empty() {
+            return Err(parser.error_at(text_start, "text outside of the root element"));
+        }
+
+        if parser.pos >= xml.len() {
+            break;
+        }
+
+        let rest = &xml[parser.pos..];
+        if rest.starts_with("<?") {
+            parser.skip_past("?>")?;
+        } else if rest.starts_with("<!--") {
+            parser.skip_past("-->")?;
+        } else if rest.starts_with("<![CDATA[") {
+            let start = parser.pos + "<![CDATA[".len();
+            parser.skip_past("]]>")?;
+            let content = &xml[start..parser.pos - "]]>".len()];
+            match stack.last_mut() {
+                Some(current) => current.text.push_str(content),
+                None => return Err(parser.error_at(start, "CDATA outside of the root element")),
+            }
+        } else if rest.starts_with("<!") {
+            parser.skip_past(">")?;
+        } else if rest.starts_with("</") {
+            let start = parser.pos;
+            parser.pos += 2;
+            let name = parser.read_name()?;
+            parser.skip_whitespace();
+            parser.expect('>')?;
+            let element = match stack.pop() {
+                Some(e) if e.name == local_name(name) => e,
+                _ => return Err(parser.error_at(start, "unexpected closing tag")),
+            };
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => {
+                    if root.is_some() {
+                        return Err(parser.error_at(start, "multiple root elements"));
+                    }
+                    root = Some(element);
+                }
+            }
+        } else {
+            let start = parser.pos;
+            parser.pos += 1;
+            let mut element = XmlElement {
+                name: local_name(parser.read_name()?).into(),
+                ..Default::default()
+            };
+            let self_closing = loop {
+                parser.skip_whitespace();
+                let rest = &xml[parser.pos..];
+                if rest.starts_with("/>") {
+                    parser.pos += 2;
+                    break true;
+                } else if rest.starts_with('>') {
+                    parser.pos += 1;
+                    break false;
+                }
+                let attr_name = parser.read_name()?;
+                parser.skip_whitespace();
+                parser.expect('=')?;
+                parser.skip_whitespace();
+                let value = parser.read_quoted()?;
+                let mut decoded = String::new();
+                decode_text(value, &mut decoded).map_err(|reason| parser.error(reason))?;
+                element
+                    .attributes
+                    .push((local_name(attr_name).into(), decoded));
+            };
+            if self_closing {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => {
+                        if root.is_some() {
+                            return Err(parser.error_at(start, "multiple root elements"));
+                        }
+                        root = Some(element);
+                    }
+                }
+            } else {
+                if stack.is_empty() && root.is_some() {
+                    return Err(parser.error_at(start, "multiple root elements"));
+                }
+                stack.push(element);
+            }
+        }
+    }
+
+    if false == stack.is_empty() {
+        return Err(parser.error("unexpected end of file (unclosed element)"));
+    }
+    root.ok_or_else(|| parser.error("no root element"))
+}
+
+/// Removes the namespace prefix from an element or attribute name.
+fn local_name(name: &str) -> &str {
+    match name.rfind(':') {
+        Some(i) => &name[i + 1..],
+        None => name,
+    }
+}
+
+/// Appends the given text to `out` with the character references resolved.
+fn decode_text(text: &str, out: &mut String) -> Result<(), &'static str> {
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        let end = rest.find(';').ok_or("unterminated character reference")?;
+        let c = match &rest[..end] {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            reference => {
+                let code = if let Some(hex) = reference
+                    .strip_prefix("#x")
+                    .or_else(|| reference.strip_prefix("#X"))
+                {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(dec) = reference.strip_prefix('#') {
+                    dec.parse::<u32>().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32)
+                    .ok_or("unknown character reference")?
+            }
+        };
+        out.push(c);
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(())
+}
+
+struct Parser<'a> {
+    xml: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error_at(&self, pos: usize, reason: &'static str) -> FibexParseError {
+        FibexParseError {
+            line: 1 + self.xml[..pos].matches('\n').count(),
+            reason,
+        }
+    }
+
+    fn error(&self, reason: &'static str) -> FibexParseError {
+        self.error_at(self.pos.min(self.xml.len()), reason)
+    }
+
+    fn skip_past(&mut self, pattern: &str) -> Result<(), FibexParseError> {
+        match self.xml[self.pos..].find(pattern) {
+            Some(offset) => {
+                self.pos += offset + pattern.len();
+                Ok(())
+            }
+            None => Err(self.error("unexpected end of file")),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.xml[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), FibexParseError> {
+        if self.xml[self.pos..].starts_with(c) {
+            self.pos += c.len_utf8();
+            Ok(())
+        } else if self.pos >= self.xml.len() {
+            Err(self.error("unexpected end of file"))
+        } else {
+            Err(self.error("unexpected character"))
+        }
+    }
+
+    fn read_name(&mut self) -> Result<&'a str, FibexParseError> {
+        let rest = &self.xml[self.pos..];
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '>' | '/' | '=' | '<' | '"' | '\''))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(if rest.is_empty() {
+                self.error("unexpected end of file")
+            } else {
+                self.error("expected a name")
+            });
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    fn read_quoted(&mut self) -> Result<&'a str, FibexParseError> {
+        let quote = match self.xml[self.pos..].chars().next() {
+            Some(c) if c == '"' || c == '\'' => c,
+            Some(_) => return Err(self.error("expected a quoted attribute value")),
+            None => return Err(self.error("unexpected end of file")),
+        };
+        let start = self.pos + 1;
+        match self.xml[start..].find(quote) {
+            Some(len) => {
+                self.pos = start + len + 1;
+                Ok(&self.xml[start..start + len])
+            }
+            None => Err(self.error("unexpected end of file")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod xml_tests {
+    use super::*;
+
+    #[test]
+    fn parse_document() {
+        let root = parse(
+            "<?xml version=\"1.0\"?>\n\
+             <!DOCTYPE x>\n\
+             <!-- comment -->\n\
+             <fx:A ho:x='1' y=\"a&amp;b\">\n\
+               t1 &lt;&#x41;&#66;&gt;\n\
+               <B/>\n\
+               <fx:C z=\"2\">c<![CDATA[<d>]]></fx:C>\n\
+               <B>b</B>\n\
+             </fx:A>\n\
+             <!-- trailing -->\n",
+        )
+        .unwrap();
+        assert_eq!("A", root.name);
+        assert_eq!(Some("1"), root.attribute("x"));
+        assert_eq!(Some("a&b"), root.attribute("y"));
+        assert_eq!(None, root.attribute("z"));
+        assert_eq!("t1 <AB>", root.text.trim());
+        assert_eq!(3, root.children.len());
+        assert_eq!(Some(""), root.child_text("B"));
+        assert_eq!(Some("c<d>"), root.child_text("C"));
+        assert_eq!(Some("2"), root.child("C").unwrap().attribute("z"));
+        assert_eq!(None, root.child("D"));
+        assert_eq!(2, root.descendants("B").count());
+        assert_eq!(1, root.descendants("A").count());
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (xml, line, reason) in [
+            ("", 1, "no root element"),
+            ("<a>", 1, "unexpected end of file (unclosed element)"),
+            ("<a>\n</b>", 2, "unexpected closing tag"),
+            ("</a>", 1, "unexpected closing tag"),
+            ("<a></a><b/>", 1, "multiple root elements"),
+            ("<a/><b></b>", 1, "multiple root elements"),
+            ("<a></a>\n<b/>", 2, "multiple root elements"),
+            ("x<a/>", 1, "text outside of the root element"),
+            ("<![CDATA[x]]><a/>", 1, "CDATA outside of the root element"),
+            ("<a>&foo;</a>", 1, "unknown character reference"),
+            ("<a>&amp</a>", 1, "unterminated character reference"),
+            ("<a x=\"&#xffffffff;\"/>", 1, "unknown character reference"),
+            ("<a x=1/>", 1, "expected a quoted attribute value"),
+            ("<a x", 1, "unexpected end of file"),
+            ("<a x=", 1, "unexpected end of file"),
+            ("<a x=\"1", 1, "unexpected end of file"),
+            ("<a x/>", 1, "unexpected character"),
+            ("<", 1, "unexpected end of file"),
+            ("< a/>", 1, "expected a name"),
+            ("<a><!-- x", 1, "unexpected end of file"),
+            ("<a></a", 1, "unexpected end of file"),
+        ] {
+            assert_eq!(Err(FibexParseError { line, reason }), parse(xml), "{}", xml);
+        }
+    }
+}
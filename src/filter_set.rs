@@ -0,0 +1,188 @@
+use crate::{DltPacketSlice, Filter, PacketFilter};
+use std::vec::Vec;
+
+/// List of [`Filter`]s where a packet passes if it matches *any* of them.
+///
+/// Together with the AND semantics of a single [`Filter`] this gives the
+/// "OR of ANDs" structure typically used by log viewers (each filter row
+/// is an AND of its fields, the rows are OR'd). An empty set matches no
+/// packet.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{Filter, FilterSet, SliceIterator};
+///
+/// // show all messages from ECU1 OR ECU2
+/// let filters = FilterSet::from(vec![
+///     Filter { ecu_id: Some(*b"ECU1"), ..Default::default() },
+///     Filter { ecu_id: Some(*b"ECU2"), ..Default::default() },
+/// ]);
+/// # let data: [u8; 0] = [];
+/// for packet in SliceIterator::new(&data).filter_packets(&filters) {
+///     // ...
+/// #   let _ = packet;
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FilterSet {
+    /// Filters of which at least one has to match.
+    pub filters: Vec<Filter>,
+}
+
+impl FilterSet {
+    /// Creates an empty filter set (matching no packets).
+    #[inline]
+    pub fn new() -> FilterSet {
+        FilterSet::default()
+    }
+
+    /// Adds a filter to the set.
+    #[inline]
+    pub fn push(&mut self, filter: Filter) {
+        self.filters.push(filter);
+    }
+
+    /// Number of filters in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.filters.len()
+    }
+
+    /// Returns true if the set contains no filters.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+}
+
+impl From<Vec<Filter>> for FilterSet {
+    #[inline]
+    fn from(filters: Vec<Filter>) -> FilterSet {
+        FilterSet { filters }
+    }
+}
+
+impl FromIterator<Filter> for FilterSet {
+    fn from_iter<I: IntoIterator<Item = Filter>>(iter: I) -> FilterSet {
+        FilterSet {
+            filters: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl PacketFilter for FilterSet {
+    #[inline]
+    fn matches(&self, packet: &DltPacketSlice<'_>) -> bool {
+        self.filters.iter().any(|f| f.matches(packet))
+    }
+}
+
+#[cfg(test)]
+mod filter_set_tests {
+    use super::*;
+    use crate::test_packets;
+    use crate::*;
+    use alloc::format;
+
+    fn packet_bytes(ecu_id: [u8; 4], application_id: [u8; 4]) -> Vec<u8> {
+        let ext =
+            DltExtendedHeader::new_non_verbose_log(DltLogLevel::Info, application_id, *b"CTX1");
+        let header = DltHeader {
+            ecu_id: Some(ecu_id),
+            ..test_packets::header(true, Some(ext))
+        };
+        test_packets::packet(header, &[0, 0, 0, 1])
+    }
+
+    #[test]
+    fn debug_clone_eq_default() {
+        let set = FilterSet::new();
+        assert_eq!(set, set.clone());
+        assert_eq!(FilterSet::default(), set);
+        assert_eq!("FilterSet { filters: [] }", format!("{:?}", set));
+    }
+
+    #[test]
+    fn push_len() {
+        let mut set = FilterSet::new();
+        assert!(set.is_empty());
+        assert_eq!(0, set.len());
+        set.push(Filter::default());
+        assert!(false == set.is_empty());
+        assert_eq!(1, set.len());
+        assert_eq!(set, FilterSet::from(vec![Filter::default()]));
+        assert_eq!(set, [Filter::default()].into_iter().collect());
+    }
+
+    #[test]
+    fn overlapping_filters() {
+        let mut buffer = Vec::new();
+        for (ecu_id, app_id) in [
+            (*b"ECU1", *b"APP1"),
+            (*b"ECU1", *b"APP2"),
+            (*b"ECU2", *b"APP1"),
+            (*b"ECU3", *b"APP1"),
+            (*b"ECU3", *b"APP2"),
+        ] {
+            buffer.extend_from_slice(&packet_bytes(ecu_id, app_id));
+        }
+
+        // (ECU1) OR (ECU3 AND APP1) OR (APP1)
+        // the first & third filter overlap for ECU1/APP1
+        // and the second & third for ECU3/APP1
+        let set: FilterSet = [
+            Filter {
+                ecu_id: Some(*b"ECU1"),
+                ..Default::default()
+            },
+            Filter {
+                ecu_id: Some(*b"ECU3"),
+                application_id: Some(*b"APP1"),
+                ..Default::default()
+            },
+            Filter {
+                application_id: Some(*b"APP1"),
+                ..Default::default()
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        let result: Vec<_> = SliceIterator::new(&buffer)
+            .filter_packets(&set)
+            .map(|p| {
+                let p = p.unwrap();
+                (
+                    p.ecu_id().unwrap(),
+                    p.extended_header().unwrap().application_id,
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (*b"ECU1", *b"APP1"),
+                (*b"ECU1", *b"APP2"),
+                (*b"ECU2", *b"APP1"),
+                (*b"ECU3", *b"APP1"),
+            ],
+            result
+        );
+
+        // empty set matches nothing
+        assert_eq!(
+            0,
+            SliceIterator::new(&buffer)
+                .filter_packets(FilterSet::new())
+                .count()
+        );
+
+        // set with the default filter matches everything
+        assert_eq!(
+            5,
+            SliceIterator::new(&buffer)
+                .filter_packets(FilterSet::from(vec![Filter::default()]))
+                .count()
+        );
+    }
+}
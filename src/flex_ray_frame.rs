@@ -0,0 +1,359 @@
+use crate::error::FlexRayFrameError;
+use crate::*;
+
+/// FlexRay channel(s) on which a frame was transmitted.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum FlexRayChannel {
+    /// Channel A (encoded as 1).
+    A,
+    /// Channel B (encoded as 2).
+    B,
+    /// Channel A & B (encoded as 3).
+    AB,
+}
+
+impl FlexRayChannel {
+    /// Decodes the channel value of a FlexRay trace header (`None` for
+    /// unknown values).
+    pub fn from_u8(value: u8) -> Option<FlexRayChannel> {
+        use FlexRayChannel::*;
+        match value {
+            1 => Some(A),
+            2 => Some(B),
+            3 => Some(AB),
+            _ => None,
+        }
+    }
+
+    /// Returns the value used to encode the channel in FlexRay trace
+    /// headers.
+    pub fn to_u8(self) -> u8 {
+        use FlexRayChannel::*;
+        match self {
+            A => 1,
+            B => 2,
+            AB => 3,
+        }
+    }
+}
+
+/// Header block of a FlexRay network trace message.
+///
+/// The header block has the layout (the slot id & payload length are
+/// encoded in the endianness of the DLT message):
+///
+/// ```text
+/// | slot id (u16) | cycle (u8) | channel (u8) | payload length (u16) |
+/// ```
+///
+/// Bytes after the payload length are ignored.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FlexRayHeader {
+    /// Slot id (1..=2047).
+    pub slot_id: u16,
+    /// Cycle counter (0..=63).
+    pub cycle: u8,
+    /// Channel(s) the frame was transmitted on.
+    pub channel: FlexRayChannel,
+    /// Length of the frame payload in bytes.
+    pub payload_len: u16,
+}
+
+impl FlexRayHeader {
+    /// Serialized length of the header block in bytes.
+    pub const BYTE_LEN: usize = 6;
+
+    /// Maximum value of the slot id.
+    pub const MAX_SLOT_ID: u16 = 2047;
+
+    /// Maximum value of the cycle counter.
+    pub const MAX_CYCLE: u8 = 63;
+
+    /// Decodes the header block (`None` if the block is too short or
+    /// contains values out of range).
+    pub fn from_slice(slice: &[u8], is_big_endian: bool) -> Option<FlexRayHeader> {
+        if slice.len() < FlexRayHeader::BYTE_LEN {
+            return None;
+        }
+        let read_u16 = |bytes: [u8; 2]| {
+            if is_big_endian {
+                u16::from_be_bytes(bytes)
+            } else {
+                u16::from_le_bytes(bytes)
+            }
+        };
+        let slot_id = read_u16([slice[0], slice[1]]);
+        let cycle = slice[2];
+        if 0 == slot_id || slot_id > FlexRayHeader::MAX_SLOT_ID || cycle > FlexRayHeader::MAX_CYCLE
+        {
+            return None;
+        }
+        Some(FlexRayHeader {
+            slot_id,
+            cycle,
+            channel: FlexRayChannel::from_u8(slice[3])?,
+            payload_len: read_u16([slice[4], slice[5]]),
+        })
+    }
+
+    /// Returns the serialized header block.
+    pub fn to_bytes(&self, is_big_endian: bool) -> [u8; 6] {
+        let (slot_id, payload_len) = if is_big_endian {
+            (self.slot_id.to_be_bytes(), self.payload_len.to_be_bytes())
+        } else {
+            (self.slot_id.to_le_bytes(), self.payload_len.to_le_bytes())
+        };
+        [
+            slot_id[0],
+            slot_id[1],
+            self.cycle,
+            self.channel.to_u8(),
+            payload_len[0],
+            payload_len[1],
+        ]
+    }
+}
+
+/// FlexRay frame decoded from a network trace message with the network
+/// type [`DltNetworkType::Flexray`].
+///
+/// Header blocks that can not be decoded (too short, unknown values or a
+/// payload length exceeding the payload block) do not cause an error.
+/// Instead [`FlexRayFrame::header`] is `None` & [`FlexRayFrame::payload`]
+/// contains the complete payload block.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{FlexRayChannel, FlexRayFrame, FlexRayHeader};
+/// # use dlt_parse::{DltExtendedHeader, DltHeader, DltMessageType, DltNetworkType, DltPacketSlice};
+/// # let header = FlexRayHeader {
+/// #     slot_id: 42,
+/// #     cycle: 3,
+/// #     channel: FlexRayChannel::A,
+/// #     payload_len: 2,
+/// # };
+/// # let mut payload = vec![0, 0, 0, 1, 0, 6];
+/// # payload.extend_from_slice(&header.to_bytes(true));
+/// # payload.extend_from_slice(&[0, 2, 0xab, 0xcd]);
+/// # let mut dlt_header = DltHeader {
+/// #     is_big_endian: true,
+/// #     message_counter: 0,
+/// #     length: 0,
+/// #     ecu_id: None,
+/// #     session_id: None,
+/// #     timestamp: None,
+/// #     extended_header: Some(DltExtendedHeader::new_non_verbose(
+/// #         DltMessageType::NetworkTrace(DltNetworkType::Flexray),
+/// #         *b"NET\0",
+/// #         *b"TRC\0",
+/// #     ).unwrap()),
+/// # };
+/// # dlt_header.length = dlt_header.header_len() + payload.len() as u16;
+/// # let mut bytes = dlt_header.to_bytes().to_vec();
+/// # bytes.extend_from_slice(&payload);
+/// let packet = DltPacketSlice::from_slice(&bytes).unwrap();
+/// let frame = FlexRayFrame::from_packet(&packet).unwrap();
+/// let header = frame.header.unwrap();
+/// assert_eq!(42, header.slot_id);
+/// assert_eq!(3, header.cycle);
+/// assert_eq!(FlexRayChannel::A, header.channel);
+/// assert_eq!(&[0xab, 0xcd], frame.payload);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FlexRayFrame<'a> {
+    /// Decoded header (`None` if the header block could not be decoded).
+    pub header: Option<FlexRayHeader>,
+    /// Raw header block of the network trace message.
+    pub raw_header: &'a [u8],
+    /// Payload of the frame (the complete payload block if the header
+    /// could not be decoded).
+    pub payload: &'a [u8],
+}
+
+impl<'a> FlexRayFrame<'a> {
+    /// Decodes the FlexRay frame of a network trace packet.
+    pub fn from_packet(packet: &DltPacketSlice<'a>) -> Result<FlexRayFrame<'a>, FlexRayFrameError> {
+        FlexRayFrame::from_trace(&NetworkTraceSlice::from_packet(packet)?)
+    }
+
+    /// Decodes the FlexRay frame of a network trace.
+    pub fn from_trace(
+        trace: &NetworkTraceSlice<'a>,
+    ) -> Result<FlexRayFrame<'a>, FlexRayFrameError> {
+        if DltNetworkType::Flexray != trace.net_type() {
+            return Err(FlexRayFrameError::NotAFlexRayTrace(trace.net_type()));
+        }
+        let raw_header = trace.header();
+        let payload = trace.payload();
+        let header = FlexRayHeader::from_slice(raw_header, trace.is_big_endian())
+            .filter(|h| usize::from(h.payload_len) <= payload.len());
+        Ok(FlexRayFrame {
+            payload: header
+                .as_ref()
+                .map_or(payload, |h| &payload[..usize::from(h.payload_len)]),
+            header,
+            raw_header,
+        })
+    }
+}
+
+#[cfg(test)]
+mod flex_ray_frame_tests {
+    use super::*;
+    use crate::test_packets;
+    use crate::verbose::RawValue;
+    use alloc::vec::Vec;
+    use arrayvec::ArrayVec;
+    use proptest::prelude::*;
+
+    /// Builds a verbose FlexRay network trace packet with the given header
+    /// & payload blocks.
+    fn packet(
+        net_type: DltNetworkType,
+        header_block: &[u8],
+        payload_block: &[u8],
+        is_big_endian: bool,
+    ) -> Vec<u8> {
+        let mut payload = ArrayVec::<u8, 1024>::new();
+        for data in [header_block, payload_block] {
+            RawValue { name: None, data }
+                .add_to_msg(&mut payload, is_big_endian)
+                .unwrap();
+        }
+        let mut ext = DltExtendedHeader::new_non_verbose(
+            DltMessageType::NetworkTrace(net_type),
+            *b"NET\0",
+            *b"TRC\0",
+        )
+        .unwrap();
+        ext.set_is_verbose(true);
+        ext.number_of_arguments = 2;
+        let header = DltHeader {
+            ecu_id: Some(*b"ECU1"),
+            ..test_packets::header(is_big_endian, Some(ext))
+        };
+        test_packets::packet(header, &payload)
+    }
+
+    fn channel_any() -> impl Strategy<Value = FlexRayChannel> {
+        use FlexRayChannel::*;
+        prop_oneof![Just(A), Just(B), Just(AB)]
+    }
+
+    proptest! {
+        #[test]
+        fn from_packet(
+            slot_id in 1..=FlexRayHeader::MAX_SLOT_ID,
+            cycle in 0..=FlexRayHeader::MAX_CYCLE,
+            channel in channel_any(),
+            ref frame_payload in proptest::collection::vec(any::<u8>(), 0..64),
+            ref padding in proptest::collection::vec(any::<u8>(), 0..4),
+            is_big_endian in any::<bool>(),
+        ) {
+            let header = FlexRayHeader {
+                slot_id,
+                cycle,
+                channel,
+                payload_len: frame_payload.len() as u16,
+            };
+            let header_block = header.to_bytes(is_big_endian);
+            let mut payload_block = frame_payload.clone();
+            payload_block.extend_from_slice(padding);
+
+            let bytes = packet(DltNetworkType::Flexray, &header_block, &payload_block, is_big_endian);
+            let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+            prop_assert_eq!(
+                Ok(FlexRayFrame {
+                    header: Some(header.clone()),
+                    raw_header: &header_block[..],
+                    payload: &frame_payload[..],
+                }),
+                FlexRayFrame::from_packet(&slice)
+            );
+
+            // truncated header block
+            for len in 0..FlexRayHeader::BYTE_LEN {
+                let bytes = packet(DltNetworkType::Flexray, &header_block[..len], &payload_block, is_big_endian);
+                let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+                prop_assert_eq!(
+                    Ok(FlexRayFrame {
+                        header: None,
+                        raw_header: &header_block[..len],
+                        payload: &payload_block[..],
+                    }),
+                    FlexRayFrame::from_packet(&slice)
+                );
+            }
+
+            // payload length exceeding the payload block
+            {
+                let mut long = header.clone();
+                long.payload_len = payload_block.len() as u16 + 1;
+                let long_block = long.to_bytes(is_big_endian);
+                let bytes = packet(DltNetworkType::Flexray, &long_block, &payload_block, is_big_endian);
+                let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+                let frame = FlexRayFrame::from_packet(&slice).unwrap();
+                prop_assert_eq!(None, frame.header);
+                prop_assert_eq!(&payload_block[..], frame.payload);
+            }
+        }
+    }
+
+    #[test]
+    fn header_from_slice() {
+        let valid = [0, 1, 63, 3, 0, 0];
+        assert_eq!(
+            Some(FlexRayHeader {
+                slot_id: 1,
+                cycle: 63,
+                channel: FlexRayChannel::AB,
+                payload_len: 0,
+            }),
+            FlexRayHeader::from_slice(&valid, true)
+        );
+        // additional bytes are ignored
+        assert!(FlexRayHeader::from_slice(&[0, 1, 63, 3, 0, 0, 0xff], true).is_some());
+        // slot id 0 & above 2047
+        assert_eq!(None, FlexRayHeader::from_slice(&[0, 0, 0, 1, 0, 0], true));
+        assert_eq!(
+            None,
+            FlexRayHeader::from_slice(&[0x08, 0, 0, 1, 0, 0], true)
+        );
+        // cycle above 63
+        assert_eq!(None, FlexRayHeader::from_slice(&[0, 1, 64, 1, 0, 0], true));
+        // unknown channels
+        for channel in [0, 4, 0xff] {
+            assert_eq!(
+                None,
+                FlexRayHeader::from_slice(&[0, 1, 0, channel, 0, 0], true)
+            );
+        }
+    }
+
+    #[test]
+    fn channel() {
+        use FlexRayChannel::*;
+        for channel in [A, B, AB] {
+            assert_eq!(Some(channel), FlexRayChannel::from_u8(channel.to_u8()));
+        }
+        assert_eq!(None, FlexRayChannel::from_u8(0));
+    }
+
+    #[test]
+    fn errors() {
+        let bytes = packet(DltNetworkType::Can, &[], &[], false);
+        let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+        assert_eq!(
+            Err(FlexRayFrameError::NotAFlexRayTrace(DltNetworkType::Can)),
+            FlexRayFrame::from_packet(&slice)
+        );
+
+        let bytes = include_bytes!("../tests/fixtures/non_verbose_message.dlt");
+        let slice = DltPacketSlice::from_slice(bytes).unwrap();
+        assert!(matches!(
+            FlexRayFrame::from_packet(&slice),
+            Err(FlexRayFrameError::NetworkTrace(_))
+        ));
+    }
+}
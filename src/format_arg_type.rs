@@ -0,0 +1,387 @@
+use crate::verbose::*;
+
+/// Type of an argument of a non verbose message (e.g. described in a
+/// FIBEX file or a [`crate::FormatSpec`]).
+///
+/// Arguments are encoded without type info in the endianness of the
+/// message. Strings & raw data are prefixed by a u16 length. The variants
+/// are named after the FIBEX coding ids (see [`FormatArgType::from_coding`]).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum FormatArgType {
+    /// `S_BOOL` (1 byte).
+    Bool,
+    /// `S_SINT8`
+    I8,
+    /// `S_SINT16`
+    I16,
+    /// `S_SINT32`
+    I32,
+    /// `S_SINT64`
+    I64,
+    /// `S_UINT8`
+    U8,
+    /// `S_UINT16`
+    U16,
+    /// `S_UINT32`
+    U32,
+    /// `S_UINT64`
+    U64,
+    /// `S_FLOA32`
+    F32,
+    /// `S_FLOA64`
+    F64,
+    /// `S_STRG_ASCII` (u16 length followed by the zero terminated string).
+    StrAscii,
+    /// `S_STRG_UTF8` (u16 length followed by the zero terminated string).
+    StrUtf8,
+    /// `S_RAWD` or `S_RAW` (u16 length followed by the data).
+    Raw,
+}
+
+impl FormatArgType {
+    /// Returns the argument type for the given FIBEX coding id (e.g.
+    /// `S_UINT32`) or `None` if the coding is not supported.
+    pub fn from_coding(coding: &str) -> Option<FormatArgType> {
+        use FormatArgType::*;
+        Some(match coding {
+            "S_BOOL" => Bool,
+            "S_SINT8" => I8,
+            "S_SINT16" => I16,
+            "S_SINT32" => I32,
+            "S_SINT64" => I64,
+            "S_UINT8" => U8,
+            "S_UINT16" => U16,
+            "S_UINT32" => U32,
+            "S_UINT64" => U64,
+            "S_FLOA32" => F32,
+            "S_FLOA64" => F64,
+            "S_STRG_ASCII" => StrAscii,
+            "S_STRG_UTF8" => StrUtf8,
+            "S_RAWD" | "S_RAW" => Raw,
+            _ => return None,
+        })
+    }
+
+    /// Decodes an argument of this type from the start of `data` & returns
+    /// the value together with the remaining data.
+    ///
+    /// `None` is returned if `data` is too short or a string is not
+    /// valid (ASCII strings are required to only contain ASCII characters).
+    pub fn decode<'a>(
+        self,
+        data: &'a [u8],
+        is_big_endian: bool,
+    ) -> Option<(VerboseValue<'a>, &'a [u8])> {
+        use FormatArgType::*;
+
+        macro_rules! fixed {
+            ($t:ty, $n:literal) => {{
+                let bytes: [u8; $n] = data.get(..$n)?.try_into().ok()?;
+                let value = if is_big_endian {
+                    <$t>::from_be_bytes(bytes)
+                } else {
+                    <$t>::from_le_bytes(bytes)
+                };
+                (value, &data[$n..])
+            }};
+        }
+        macro_rules! int {
+            ($variant:ident, $value:ident, $t:ty, $n:literal) => {{
+                let (value, rest) = fixed!($t, $n);
+                (
+                    VerboseValue::$variant($value {
+                        variable_info: None,
+                        scaling: None,
+                        value,
+                    }),
+                    rest,
+                )
+            }};
+        }
+
+        Some(match self {
+            Bool => {
+                let (value, rest) = fixed!(u8, 1);
+                (
+                    VerboseValue::Bool(BoolValue {
+                        name: None,
+                        value: value != 0,
+                    }),
+                    rest,
+                )
+            }
+            I8 => int!(I8, I8Value, i8, 1),
+            I16 => int!(I16, I16Value, i16, 2),
+            I32 => int!(I32, I32Value, i32, 4),
+            I64 => int!(I64, I64Value, i64, 8),
+            U8 => int!(U8, U8Value, u8, 1),
+            U16 => int!(U16, U16Value, u16, 2),
+            U32 => int!(U32, U32Value, u32, 4),
+            U64 => int!(U64, U64Value, u64, 8),
+            F32 => {
+                let (value, rest) = fixed!(f32, 4);
+                (
+                    VerboseValue::F32(F32Value {
+                        variable_info: None,
+                        value,
+                    }),
+                    rest,
+                )
+            }
+            F64 => {
+                let (value, rest) = fixed!(f64, 8);
+                (
+                    VerboseValue::F64(F64Value {
+                        variable_info: None,
+                        value,
+                    }),
+                    rest,
+                )
+            }
+            StrAscii | StrUtf8 | Raw => {
+                let (len, rest) = fixed!(u16, 2);
+                let len = usize::from(len);
+                let content = rest.get(..len)?;
+                let rest = &rest[len..];
+                if self == Raw {
+                    (
+                        VerboseValue::Raw(RawValue {
+                            name: None,
+                            data: content,
+                        }),
+                        rest,
+                    )
+                } else {
+                    // remove the zero termination
+                    let end = content
+                        .iter()
+                        .position(|b| *b == 0)
+                        .unwrap_or(content.len());
+                    let content = &content[..end];
+                    if self == StrAscii && false == content.is_ascii() {
+                        return None;
+                    }
+                    (
+                        VerboseValue::Str(StringValue {
+                            name: None,
+                            value: core::str::from_utf8(content).ok()?,
+                        }),
+                        rest,
+                    )
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod format_arg_type_tests {
+    use super::*;
+
+    #[test]
+    fn from_coding() {
+        use FormatArgType::*;
+        for (coding, expected) in [
+            ("S_BOOL", Some(Bool)),
+            ("S_SINT8", Some(I8)),
+            ("S_SINT16", Some(I16)),
+            ("S_SINT32", Some(I32)),
+            ("S_SINT64", Some(I64)),
+            ("S_UINT8", Some(U8)),
+            ("S_UINT16", Some(U16)),
+            ("S_UINT32", Some(U32)),
+            ("S_UINT64", Some(U64)),
+            ("S_FLOA32", Some(F32)),
+            ("S_FLOA64", Some(F64)),
+            ("S_STRG_ASCII", Some(StrAscii)),
+            ("S_STRG_UTF8", Some(StrUtf8)),
+            ("S_RAWD", Some(Raw)),
+            ("S_RAW", Some(Raw)),
+            ("S_FLOA16", None),
+            ("", None),
+        ] {
+            assert_eq!(expected, FormatArgType::from_coding(coding));
+        }
+    }
+
+    #[test]
+    fn decode() {
+        use FormatArgType::*;
+
+        // fixed size values
+        for is_big_endian in [false, true] {
+            let data = if is_big_endian {
+                [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0]
+            } else {
+                [0xf0, 0xde, 0xbc, 0x9a, 0x78, 0x56, 0x34, 0x12]
+            };
+            // decodes the lowest len bytes of the u64 value in data
+            let v = |ty: FormatArgType, len: usize| {
+                let bytes = if is_big_endian {
+                    &data[8 - len..]
+                } else {
+                    &data[..len]
+                };
+                let (value, rest) = ty.decode(bytes, is_big_endian).unwrap();
+                assert!(rest.is_empty());
+                assert_eq!(None, ty.decode(&bytes[..len - 1], is_big_endian));
+                value
+            };
+            let expected_u64 = 0x1234_5678_9abc_def0u64;
+            assert_eq!(
+                VerboseValue::U8(U8Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: expected_u64 as u8
+                }),
+                v(U8, 1)
+            );
+            assert_eq!(
+                VerboseValue::I8(I8Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: expected_u64 as i8
+                }),
+                v(I8, 1)
+            );
+            assert_eq!(
+                VerboseValue::U16(U16Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: expected_u64 as u16
+                }),
+                v(U16, 2)
+            );
+            assert_eq!(
+                VerboseValue::I16(I16Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: expected_u64 as i16
+                }),
+                v(I16, 2)
+            );
+            assert_eq!(
+                VerboseValue::U32(U32Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: expected_u64 as u32
+                }),
+                v(U32, 4)
+            );
+            assert_eq!(
+                VerboseValue::I32(I32Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: expected_u64 as i32
+                }),
+                v(I32, 4)
+            );
+            assert_eq!(
+                VerboseValue::U64(U64Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: expected_u64
+                }),
+                v(U64, 8)
+            );
+            assert_eq!(
+                VerboseValue::I64(I64Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: expected_u64 as i64
+                }),
+                v(I64, 8)
+            );
+            assert_eq!(
+                VerboseValue::F32(F32Value {
+                    variable_info: None,
+                    value: f32::from_bits(expected_u64 as u32)
+                }),
+                v(F32, 4)
+            );
+            assert_eq!(
+                VerboseValue::F64(F64Value {
+                    variable_info: None,
+                    value: f64::from_bits(expected_u64)
+                }),
+                v(F64, 8)
+            );
+        }
+
+        // bool
+        assert_eq!(
+            Some((
+                VerboseValue::Bool(BoolValue {
+                    name: None,
+                    value: false
+                }),
+                &[1u8][..]
+            )),
+            Bool.decode(&[0, 1], false)
+        );
+        assert_eq!(
+            Some((
+                VerboseValue::Bool(BoolValue {
+                    name: None,
+                    value: true
+                }),
+                &[][..]
+            )),
+            Bool.decode(&[2], true)
+        );
+        assert_eq!(None, Bool.decode(&[], true));
+
+        // strings & raw
+        for is_big_endian in [false, true] {
+            let len: [u8; 2] = if is_big_endian { [0, 4] } else { [4, 0] };
+            let mut data = [0u8; 7];
+            data[..2].copy_from_slice(&len);
+            data[2..6].copy_from_slice(b"ab\0\0");
+            data[6] = 0xff;
+
+            for ty in [StrAscii, StrUtf8] {
+                assert_eq!(
+                    Some((
+                        VerboseValue::Str(StringValue {
+                            name: None,
+                            value: "ab"
+                        }),
+                        &[0xffu8][..]
+                    )),
+                    ty.decode(&data, is_big_endian)
+                );
+                assert_eq!(None, ty.decode(&data[..5], is_big_endian));
+                assert_eq!(None, ty.decode(&data[..1], is_big_endian));
+            }
+            assert_eq!(
+                Some((
+                    VerboseValue::Raw(RawValue {
+                        name: None,
+                        data: b"ab\0\0"
+                    }),
+                    &[0xffu8][..]
+                )),
+                Raw.decode(&data, is_big_endian)
+            );
+            assert_eq!(None, Raw.decode(&data[..5], is_big_endian));
+
+            // non ascii & invalid utf8
+            let mut data = [0u8; 6];
+            data[..2].copy_from_slice(&len);
+            data[2..6].copy_from_slice("ä\0\0".as_bytes());
+            assert_eq!(None, StrAscii.decode(&data, is_big_endian));
+            assert_eq!(
+                Some((
+                    VerboseValue::Str(StringValue {
+                        name: None,
+                        value: "ä"
+                    }),
+                    &[][..]
+                )),
+                StrUtf8.decode(&data, is_big_endian)
+            );
+            data[2] = 0xff;
+            assert_eq!(None, StrUtf8.decode(&data, is_big_endian));
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use crate::error::NonVerboseFormatError;
+use crate::non_verbose_registry::write_template;
+use crate::verbose::VerboseValue;
+use crate::{DecodedMessage, FormatArgType, NonVerboseDecoder, NonVerbosePayload};
+use std::string::String;
+use std::vec::Vec;
+
+/// Format of a non verbose message (text template & the types of the
+/// arguments following the message id).
+///
+/// Each `{}` in the template is replaced by the next argument. Placeholders
+/// without a matching argument are written unchanged & data after the
+/// last argument is ignored.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{FormatArgType, FormatSpec, NonVerbosePayload};
+///
+/// let spec = FormatSpec {
+///     template: "speed {} km/h, gear {}".to_string(),
+///     arg_types: vec![FormatArgType::U16, FormatArgType::I8],
+/// };
+///
+/// // message id 0x10 followed by the arguments (big endian)
+/// let payload = NonVerbosePayload::from_slice(&[0, 0, 0, 0x10, 0, 120, 0xff], true).unwrap();
+/// assert_eq!(
+///     "speed 120 km/h, gear -1",
+///     spec.format_payload(&payload, true).unwrap()
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FormatSpec {
+    /// Text of the message with a `{}` placeholder for each argument.
+    pub template: String,
+    /// Types of the arguments in the order they are encoded.
+    pub arg_types: Vec<FormatArgType>,
+}
+
+impl FormatSpec {
+    /// Decodes the arguments from the data following the message id.
+    pub fn decode_args<'a>(
+        &self,
+        payload: &NonVerbosePayload<'a>,
+        is_big_endian: bool,
+    ) -> Result<Vec<VerboseValue<'a>>, NonVerboseFormatError> {
+        let mut values = Vec::with_capacity(self.arg_types.len());
+        let mut rest = payload.data;
+        for (index, arg_type) in self.arg_types.iter().enumerate() {
+            let (value, next) = arg_type.decode(rest, is_big_endian).ok_or(
+                NonVerboseFormatError::InvalidArgument {
+                    msg_id: payload.msg_id,
+                    index,
+                    arg_type: *arg_type,
+                },
+            )?;
+            values.push(value);
+            rest = next;
+        }
+        Ok(values)
+    }
+
+    /// Decodes the arguments & returns the template with the placeholders
+    /// replaced by the argument values.
+    pub fn format_payload(
+        &self,
+        payload: &NonVerbosePayload<'_>,
+        is_big_endian: bool,
+    ) -> Result<String, NonVerboseFormatError> {
+        let values = self.decode_args(payload, is_big_endian)?;
+        let mut result = String::with_capacity(self.template.len());
+        // writing to a string can not fail
+        write_template(&mut result, &self.template, &values).unwrap();
+        Ok(result)
+    }
+}
+
+impl NonVerboseDecoder for FormatSpec {
+    fn decode<'a>(
+        &self,
+        msg_id: u32,
+        data: &'a [u8],
+        is_big_endian: bool,
+    ) -> Option<DecodedMessage<'a>> {
+        let values = self
+            .decode_args(&NonVerbosePayload { msg_id, data }, is_big_endian)
+            .ok()?;
+        Some(DecodedMessage {
+            template: self.template.clone(),
+            values,
+        })
+    }
+}
+
+#[cfg(test)]
+mod format_spec_tests {
+    use super::*;
+    use crate::NonVerboseRegistry;
+    use alloc::{string::ToString, vec};
+
+    fn spec() -> FormatSpec {
+        FormatSpec {
+            template: "{} {}: {}".to_string(),
+            arg_types: vec![
+                FormatArgType::Bool,
+                FormatArgType::StrUtf8,
+                FormatArgType::U32,
+            ],
+        }
+    }
+
+    #[test]
+    fn format_payload() {
+        for is_big_endian in [false, true] {
+            let mut data = vec![1];
+            if is_big_endian {
+                data.extend_from_slice(&3u16.to_be_bytes());
+                data.extend_from_slice(b"ab\0");
+                data.extend_from_slice(&1234u32.to_be_bytes());
+            } else {
+                data.extend_from_slice(&3u16.to_le_bytes());
+                data.extend_from_slice(b"ab\0");
+                data.extend_from_slice(&1234u32.to_le_bytes());
+            }
+            let payload = NonVerbosePayload {
+                msg_id: 7,
+                data: &data,
+            };
+            assert_eq!(
+                Ok("true ab: 1234".to_string()),
+                spec().format_payload(&payload, is_big_endian)
+            );
+            assert_eq!(
+                3,
+                spec().decode_args(&payload, is_big_endian).unwrap().len()
+            );
+
+            // data after the last argument is ignored
+            let mut longer = data.clone();
+            longer.push(0xff);
+            assert_eq!(
+                Ok("true ab: 1234".to_string()),
+                spec().format_payload(
+                    &NonVerbosePayload {
+                        msg_id: 7,
+                        data: &longer,
+                    },
+                    is_big_endian
+                )
+            );
+
+            // missing or invalid arguments
+            for (len, index, arg_type) in [
+                (0, 0, FormatArgType::Bool),
+                (2, 1, FormatArgType::StrUtf8),
+                (data.len() - 1, 2, FormatArgType::U32),
+            ] {
+                assert_eq!(
+                    Err(NonVerboseFormatError::InvalidArgument {
+                        msg_id: 7,
+                        index,
+                        arg_type,
+                    }),
+                    spec().format_payload(
+                        &NonVerbosePayload {
+                            msg_id: 7,
+                            data: &data[..len],
+                        },
+                        is_big_endian
+                    )
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn placeholders() {
+        // more placeholders then arguments
+        let spec = FormatSpec {
+            template: "{} of {}".to_string(),
+            arg_types: vec![FormatArgType::U8],
+        };
+        let payload = NonVerbosePayload {
+            msg_id: 1,
+            data: &[3],
+        };
+        assert_eq!(
+            Ok("3 of {}".to_string()),
+            spec.format_payload(&payload, true)
+        );
+
+        // no arguments
+        let spec = FormatSpec {
+            template: "startup".to_string(),
+            arg_types: vec![],
+        };
+        assert_eq!(
+            Ok("startup".to_string()),
+            spec.format_payload(&payload, true)
+        );
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = NonVerboseRegistry::new();
+        registry.register(*b"APP\0", *b"CTX\0", 7, spec());
+        let decoded = registry
+            .decode_payload(
+                *b"APP\0",
+                *b"CTX\0",
+                7,
+                &[0, 2, 0, b'a', 0, 1, 0, 0, 0],
+                false,
+            )
+            .unwrap();
+        assert_eq!("false a: 1", decoded.to_string());
+        assert_eq!(
+            None,
+            registry.decode_payload(*b"APP\0", *b"CTX\0", 7, &[0], false)
+        );
+    }
+}
@@ -0,0 +1,48 @@
+/// Byte offsets of the header fields of a dlt packet relative to the
+/// start of the packet (see [`crate::DltPacketSlice::field_offsets`]).
+///
+/// Fields that are not present in the packet are `None`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct HeaderOffsets {
+    /// Offset of the header type byte (always 0).
+    pub header_type: usize,
+    /// Offset of the message counter (always 1).
+    pub message_counter: usize,
+    /// Offset of the length field (always 2, 2 bytes).
+    pub length: usize,
+    /// Offset of the ecu id (4 bytes).
+    pub ecu_id: Option<usize>,
+    /// Offset of the session id (4 bytes).
+    pub session_id: Option<usize>,
+    /// Offset of the timestamp (4 bytes).
+    pub timestamp: Option<usize>,
+    /// Offset of the extended header (10 bytes).
+    pub extended_header: Option<usize>,
+    /// Offset of the payload (directly after the headers).
+    pub payload: usize,
+}
+
+#[cfg(test)]
+mod header_offsets_tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq() {
+        let offsets = HeaderOffsets {
+            header_type: 0,
+            message_counter: 1,
+            length: 2,
+            ecu_id: None,
+            session_id: None,
+            timestamp: None,
+            extended_header: None,
+            payload: 4,
+        };
+        assert_eq!(offsets, offsets.clone());
+        assert_eq!(
+            "HeaderOffsets { header_type: 0, message_counter: 1, length: 2, ecu_id: None, session_id: None, timestamp: None, extended_header: None, payload: 4 }",
+            format!("{:?}", offsets)
+        );
+    }
+}
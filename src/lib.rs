@@ -17,6 +17,22 @@
 //! dlt_parse = { version = "0.9.2", features = ["serde"] }
 //! ```
 //!
+//! The `f128_approx` feature adds `verbose::RawF128::to_f64_approx` to convert 128 bit
+//! floating point values to an approximate `f64`.
+//!
+//! The `fibex` feature adds the `fibex` module to load non verbose message descriptions
+//! from FIBEX files into a `NonVerboseRegistry`.
+//!
+//! The `metrics` feature adds `DecodeMetrics` & makes `SliceIterator` record the
+//! distribution of the time it takes to decode each message. Without the feature
+//! no timing code is compiled in.
+//!
+//! The `someip` feature adds `SomeIpTraceSlice::header` to decode the header of SOME/IP
+//! messages contained in network traces via the `someip_parse` crate.
+//!
+//! The `alloc` feature (enabled by `std`) adds `verbose::Utf16StringValue` to decode
+//! UTF-16 encoded strings (a non standard extension) into an owned `String`.
+//!
 //! If you want to use the crate in `no_std` mode you will have to disable the default features:
 //!
 //! ```toml
@@ -130,6 +146,7 @@
 //!                     NetworkV(_) => println!("verbose network message received"),
 //!                     NetworkNv(_) => println!("non verbose network message received"),
 //!                     ControlV(_) => println!("verbose control message received"),
+//!                     ControlTime(_) => println!("control time message received"),
 //!                }
 //!            } else {
 //!                println!("non verbose message with incomplete message id");
@@ -165,7 +182,7 @@
 #![allow(clippy::bool_comparison)]
 #![no_std]
 
-#[cfg(test)]
+#[cfg(any(feature = "alloc", test))]
 extern crate alloc;
 #[cfg(test)]
 extern crate proptest;
@@ -175,12 +192,44 @@ extern crate std;
 #[macro_use]
 extern crate assert_matches;
 
+mod can_frame;
+pub use can_frame::*;
+
+#[cfg(feature = "std")]
+mod capture_diff;
+#[cfg(feature = "std")]
+pub use capture_diff::*;
+
+mod content_key;
+pub use content_key::*;
+
+#[cfg(feature = "std")]
+mod counter_gaps;
+#[cfg(feature = "std")]
+pub use counter_gaps::*;
+
+#[cfg(feature = "std")]
+mod distinct_ids;
+#[cfg(feature = "std")]
+pub use distinct_ids::*;
+
+#[cfg(feature = "metrics")]
+mod decode_metrics;
+#[cfg(feature = "metrics")]
+pub use decode_metrics::*;
+
 mod dlt_extended_header;
 pub use dlt_extended_header::*;
 
 mod dlt_header;
 pub use dlt_header::*;
 
+mod dlt_header_flat;
+pub use dlt_header_flat::*;
+
+mod dlt_verbose_message_builder;
+pub use dlt_verbose_message_builder::*;
+
 mod dlt_typed_payload;
 pub use dlt_typed_payload::*;
 
@@ -193,6 +242,104 @@ pub use dlt_packet_slice::*;
 mod dlt_slice_iterator;
 pub use dlt_slice_iterator::*;
 
+mod dlt_framing;
+pub use dlt_framing::*;
+
+#[cfg(feature = "std")]
+mod dlt_reader;
+#[cfg(feature = "std")]
+pub use dlt_reader::*;
+
+#[cfg(feature = "std")]
+mod filter_set;
+#[cfg(feature = "std")]
+pub use filter_set::*;
+
+mod flex_ray_frame;
+pub use flex_ray_frame::*;
+
+mod format_arg_type;
+pub use format_arg_type::*;
+
+#[cfg(feature = "std")]
+mod format_spec;
+#[cfg(feature = "std")]
+pub use format_spec::*;
+
+mod header_offsets;
+pub use header_offsets::*;
+
+#[cfg(feature = "std")]
+mod message_arena;
+#[cfg(feature = "std")]
+pub use message_arena::*;
+
+mod message_formatter;
+pub use message_formatter::*;
+
+mod message_header_fields;
+
+mod network_trace_slice;
+pub use network_trace_slice::*;
+
+#[cfg(feature = "std")]
+mod non_verbose_message_builder;
+#[cfg(feature = "std")]
+pub use non_verbose_message_builder::*;
+
+mod non_verbose_payload;
+pub use non_verbose_payload::*;
+
+#[cfg(feature = "std")]
+mod normalize_length;
+#[cfg(feature = "std")]
+pub use normalize_length::*;
+
+#[cfg(feature = "std")]
+mod non_verbose_registry;
+#[cfg(feature = "std")]
+pub use non_verbose_registry::*;
+
+mod packet_filter;
+pub use packet_filter::*;
+
+mod parse_config;
+pub use parse_config::*;
+
+#[cfg(feature = "std")]
+mod ring_dlt_reader;
+#[cfg(feature = "std")]
+pub use ring_dlt_reader::*;
+
+mod some_ip_trace_slice;
+pub use some_ip_trace_slice::*;
+
+#[cfg(feature = "std")]
+mod split_by_ecu;
+#[cfg(feature = "std")]
+pub use split_by_ecu::*;
+
+#[cfg(feature = "std")]
+mod statistics_collector;
+#[cfg(feature = "std")]
+pub use statistics_collector::*;
+
+mod timestamp_interpretation;
+pub use timestamp_interpretation::*;
+
+mod timestamp_tracker;
+pub use timestamp_tracker::*;
+
+#[cfg(feature = "std")]
+mod truncate_payload;
+#[cfg(feature = "std")]
+pub use truncate_payload::*;
+
+#[cfg(feature = "std")]
+mod write_messages;
+#[cfg(feature = "std")]
+pub use write_messages::*;
+
 /// Errors that can be returned by functions in dlt_parse.
 pub mod error;
 
@@ -202,6 +349,14 @@ pub mod verbose;
 /// Module for decoding .dlt files or other formats that use the DLT storage header.
 pub mod storage;
 
+/// Module containing decoders & encoders for the payloads of DLT control messages.
+pub mod control;
+
+/// Module for loading non verbose message descriptions from FIBEX files
+/// (requires the `fibex` feature).
+#[cfg(feature = "fibex")]
+pub mod fibex;
+
 #[cfg(test)]
 use alloc::{format, vec, vec::Vec};
 use arrayvec::ArrayVec;
@@ -210,10 +365,16 @@ use core::slice::from_raw_parts;
 use std::io;
 #[cfg(test)]
 mod proptest_generators;
+#[cfg(test)]
+mod test_packets;
 
 /// Maximum value that can be encoded in the DLT header version field (has only 3 bits).
 const MAX_VERSION: u8 = 0b111;
 
+/// Pattern at the start of DLT packets transmitted via a serial
+/// connection ("DLS" followed by 0x01).
+pub const SERIAL_HEADER_PATTERN: [u8; 4] = [0x44, 0x4C, 0x53, 0x01];
+
 const EXTDENDED_HEADER_FLAG: u8 = 0b1;
 const BIG_ENDIAN_FLAG: u8 = 0b10;
 const ECU_ID_FLAG: u8 = 0b100;
@@ -287,6 +448,9 @@ pub enum DltControlMessageType {
     Request = 0x1,
     ///Respond control message.
     Response = 0x2,
+    ///Time control message (sent periodically if timing packets are
+    ///enabled, the time is contained in the timestamp of the header).
+    Time = 0x3,
 }
 
 ///Message type info field (contains the the information of the message type & message type info field)
@@ -354,6 +518,7 @@ impl DltMessageType {
                 match (value & MSIN_MASK) >> 4 {
                     0x1 => Some(Control(Request)),
                     0x2 => Some(Control(Response)),
+                    0x3 => Some(Control(Time)),
                     //undefined values
                     _ => None,
                 }
@@ -403,6 +568,7 @@ impl DltMessageType {
 }
 
 #[cfg(test)]
+#[allow(clippy::clone_on_copy)]
 mod tests {
     use super::*;
 
@@ -538,7 +704,8 @@ mod tests {
 
         #[test]
         fn clone_eq() {
-            const VALUES: [(DltControlMessageType, u8); 2] = [(Request, 1), (Response, 2)];
+            const VALUES: [(DltControlMessageType, u8); 3] =
+                [(Request, 1), (Response, 2), (Time, 3)];
 
             for v0 in &VALUES {
                 // identity property
@@ -553,8 +720,8 @@ mod tests {
 
         #[test]
         fn debug() {
-            const VALUES: [(DltControlMessageType, &str); 2] =
-                [(Request, "Request"), (Response, "Response")];
+            const VALUES: [(DltControlMessageType, &str); 3] =
+                [(Request, "Request"), (Response, "Response"), (Time, "Time")];
             for v in &VALUES {
                 assert_eq!(v.1, format!("{:?}", v.0));
             }
@@ -570,7 +737,7 @@ mod tests {
         use DltNetworkType::*;
         use DltTraceType::*;
 
-        const VALUES: [(DltMessageType, u8); 28] = [
+        const VALUES: [(DltMessageType, u8); 29] = [
             (Log(Fatal), 0b0001_0000),
             (Log(Error), 0b0010_0000),
             (Log(Warn), 0b0011_0000),
@@ -599,6 +766,7 @@ mod tests {
             (NetworkTrace(UserDefined(0xF)), 0b1111_0100),
             (Control(Request), 0b0001_0110),
             (Control(Response), 0b0010_0110),
+            (Control(Time), 0b0011_0110),
         ];
 
         #[test]
@@ -660,7 +828,7 @@ mod tests {
             // invalid control
             assert!(DltMessageType::from_byte(0b0000_0110).is_none());
             assert!(DltMessageType::from_byte(0b0000_0111).is_none());
-            for i in 3..=0b1111 {
+            for i in 4..=0b1111 {
                 assert!(DltMessageType::from_byte((i << 4) | 0b0110).is_none());
                 // with verbose
                 assert!(DltMessageType::from_byte((i << 4) | 0b0111).is_none());
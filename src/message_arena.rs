@@ -0,0 +1,271 @@
+use crate::{error::PacketSliceError, DltPacketSlice, SliceIterator};
+use core::ops::Range;
+use std::vec::Vec;
+
+/// Owned collection of dlt packets stored in a single backing buffer.
+///
+/// All packets are copied into one `Vec<u8>` & only the byte range of
+/// each packet is stored additionally. Collecting a complete capture
+/// therefore only needs two (amortized) allocations instead of one
+/// allocation per message.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{DltHeader, MessageArena};
+///
+/// let mut header = DltHeader {
+///     is_big_endian: true,
+///     message_counter: 0,
+///     length: 0,
+///     ecu_id: Some(*b"ECU1"),
+///     session_id: None,
+///     timestamp: None,
+///     extended_header: None,
+/// };
+/// header.length = header.header_len() + 4;
+/// let mut data = Vec::new();
+/// for _ in 0..3 {
+///     data.extend_from_slice(&header.to_bytes());
+///     data.extend_from_slice(&[1, 2, 3, 4]);
+/// }
+///
+/// let arena = MessageArena::from_slice(&data).unwrap();
+/// assert_eq!(3, arena.len());
+/// assert_eq!(Some(*b"ECU1"), arena.get(1).unwrap().ecu_id());
+/// for packet in arena.iter() {
+///     assert_eq!(&[1, 2, 3, 4], packet.payload());
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct MessageArena {
+    data: Vec<u8>,
+    ranges: Vec<Range<usize>>,
+}
+
+impl MessageArena {
+    /// Creates an empty arena.
+    #[inline]
+    pub fn new() -> MessageArena {
+        MessageArena::default()
+    }
+
+    /// Creates an empty arena with space for `bytes` bytes of packet
+    /// data & `messages` packets.
+    #[inline]
+    pub fn with_capacity(bytes: usize, messages: usize) -> MessageArena {
+        MessageArena {
+            data: Vec::with_capacity(bytes),
+            ranges: Vec::with_capacity(messages),
+        }
+    }
+
+    /// Collects all dlt packets in the given slice.
+    ///
+    /// The slice is expected to only contain dlt messages (without storage
+    /// headers). Decoding stops at the first message that can not be
+    /// decoded and the error is returned.
+    pub fn from_slice(slice: &[u8]) -> Result<MessageArena, PacketSliceError> {
+        let mut result = MessageArena::with_capacity(slice.len(), 0);
+        for packet in SliceIterator::new(slice) {
+            result.push(&packet?);
+        }
+        Ok(result)
+    }
+
+    /// Copies the packet into the arena & returns its index.
+    pub fn push(&mut self, packet: &DltPacketSlice<'_>) -> usize {
+        let start = self.data.len();
+        self.data.extend_from_slice(packet.slice());
+        self.ranges.push(start..self.data.len());
+        self.ranges.len() - 1
+    }
+
+    /// Decodes the dlt packet at the start of the given slice, copies it
+    /// into the arena & returns its index (data after the packet is
+    /// ignored).
+    pub fn push_slice(&mut self, slice: &[u8]) -> Result<usize, PacketSliceError> {
+        Ok(self.push(&DltPacketSlice::from_slice(slice)?))
+    }
+
+    /// Number of packets in the arena.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns true if the arena contains no packets.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the packet with the given index.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<DltPacketSlice<'_>> {
+        self.ranges
+            .get(index)
+            .and_then(|range| self.packet(range.clone()))
+    }
+
+    /// Returns the byte range of the packet with the given index in the
+    /// backing buffer (see [`MessageArena::data`]).
+    #[inline]
+    pub fn range(&self, index: usize) -> Option<Range<usize>> {
+        self.ranges.get(index).cloned()
+    }
+
+    /// Backing buffer containing all packets in the order they were added.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns an iterator over all packets in the arena.
+    #[inline]
+    pub fn iter(&self) -> MessageArenaIter<'_> {
+        MessageArenaIter {
+            arena: self,
+            ranges: self.ranges.iter(),
+        }
+    }
+
+    /// Removes all packets (keeping the allocated memory).
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.ranges.clear();
+    }
+
+    fn packet(&self, range: Range<usize>) -> Option<DltPacketSlice<'_>> {
+        // the packets were validated when they were added, so decoding
+        // them again can not fail
+        DltPacketSlice::from_slice(self.data.get(range)?).ok()
+    }
+}
+
+impl<'a> IntoIterator for &'a MessageArena {
+    type Item = DltPacketSlice<'a>;
+    type IntoIter = MessageArenaIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> MessageArenaIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the packets of a [`MessageArena`].
+#[derive(Clone, Debug)]
+pub struct MessageArenaIter<'a> {
+    arena: &'a MessageArena,
+    ranges: core::slice::Iter<'a, Range<usize>>,
+}
+
+impl<'a> Iterator for MessageArenaIter<'a> {
+    type Item = DltPacketSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<DltPacketSlice<'a>> {
+        let range = self.ranges.next()?;
+        self.arena.packet(range.clone())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ranges.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for MessageArenaIter<'a> {}
+
+#[cfg(test)]
+mod message_arena_tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn from_slice_get_iter(ref packets in prop::collection::vec(dlt_header_with_payload_any(), 0..8)) {
+            let mut buffer = Vec::new();
+            for (header, payload) in packets {
+                buffer.extend_from_slice(&header.to_bytes());
+                buffer.extend_from_slice(payload);
+            }
+
+            let arena = MessageArena::from_slice(&buffer).unwrap();
+            prop_assert_eq!(packets.len(), arena.len());
+            prop_assert_eq!(packets.is_empty(), arena.is_empty());
+            prop_assert_eq!(&buffer[..], arena.data());
+            prop_assert_eq!(packets.len(), arena.iter().len());
+
+            let expected: Vec<_> = SliceIterator::new(&buffer).map(|p| p.unwrap()).collect();
+            let actual: Vec<_> = arena.iter().collect();
+            prop_assert_eq!(&expected, &actual);
+            let actual: Vec<_> = (&arena).into_iter().collect();
+            prop_assert_eq!(&expected, &actual);
+
+            let mut start = 0;
+            for (index, (header, payload)) in packets.iter().enumerate() {
+                let packet = arena.get(index).unwrap();
+                prop_assert_eq!(header, &packet.header());
+                prop_assert_eq!(&payload[..], packet.payload());
+                let end = start + usize::from(header.length);
+                prop_assert_eq!(Some(start..end), arena.range(index));
+                start = end;
+            }
+            prop_assert_eq!(None, arena.get(packets.len()));
+            prop_assert_eq!(None, arena.range(packets.len()));
+
+            // push one by one
+            let mut pushed = MessageArena::new();
+            for (index, packet) in expected.iter().enumerate() {
+                prop_assert_eq!(index, pushed.push(packet));
+            }
+            prop_assert_eq!(&arena, &pushed);
+
+            // push_slice
+            let mut pushed = MessageArena::with_capacity(buffer.len(), packets.len());
+            let mut rest = &buffer[..];
+            while !rest.is_empty() {
+                let index = pushed.push_slice(rest).unwrap();
+                rest = &rest[pushed.range(index).unwrap().len()..];
+            }
+            prop_assert_eq!(&arena, &pushed);
+
+            pushed.clear();
+            prop_assert!(pushed.is_empty());
+            prop_assert!(pushed.data().is_empty());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn errors(ref packet in dlt_header_with_payload_any()) {
+            let mut buffer = Vec::new();
+            buffer.extend_from_slice(&packet.0.to_bytes());
+            buffer.extend_from_slice(&packet.1);
+
+            let truncated = &buffer[..buffer.len() - 1];
+            let expected = DltPacketSlice::from_slice(truncated).unwrap_err();
+            prop_assert_eq!(Err(expected.clone()), MessageArena::from_slice(truncated));
+
+            let mut arena = MessageArena::new();
+            prop_assert_eq!(Err(expected), arena.push_slice(truncated));
+            prop_assert!(arena.is_empty());
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq_default() {
+        let arena = MessageArena::new();
+        assert_eq!(arena, arena.clone());
+        assert_eq!(MessageArena::default(), arena);
+        assert_eq!(
+            "MessageArena { data: [], ranges: [] }",
+            format!("{:?}", arena)
+        );
+        assert!(format!("{:?}", arena.iter()).starts_with("MessageArenaIter {"));
+    }
+}
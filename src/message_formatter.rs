@@ -0,0 +1,434 @@
+use crate::error::FormatError;
+use crate::verbose::VerboseValue;
+use crate::*;
+use core::fmt;
+
+/// Formats DLT messages to text (e.g. a line per message).
+///
+/// Implement this trait to define custom output formats (CSV,
+/// JSON-lines, ...) & pass it to [`format_all`]. [`DltViewerFormatter`]
+/// is the default implementation.
+pub trait MessageFormatter {
+    /// Writes the given message to `out`.
+    ///
+    /// `index` is the position of the message in the formatted data.
+    fn format(
+        &mut self,
+        index: usize,
+        packet: &DltPacketSlice<'_>,
+        out: &mut dyn fmt::Write,
+    ) -> fmt::Result;
+}
+
+/// Formats the DLT packets in the given slice with the given formatter.
+///
+/// Formatting stops at the first message that can not be sliced or
+/// written, the index of that message is contained in the returned error.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{format_all, DltViewerFormatter};
+///
+/// let data = [
+///     0b0011_0010, 0, 0, 13, // header (big endian & timestamp present)
+///     0, 0, 0x30, 0x39, // timestamp
+///     0, 0, 0, 1, // message id
+///     0xab, // payload
+/// ];
+///
+/// let mut out = String::new();
+/// format_all(&data, &mut DltViewerFormatter, &mut out).unwrap();
+/// assert_eq!("0 1.2345 0 - - - - - - 0 [1] ab\n", out);
+/// ```
+pub fn format_all<F, W>(slice: &[u8], formatter: &mut F, writer: &mut W) -> Result<(), FormatError>
+where
+    F: MessageFormatter + ?Sized,
+    W: fmt::Write,
+{
+    for (index, packet) in SliceIterator::new(slice).enumerate() {
+        let packet = packet.map_err(|err| FormatError::Packet { index, err })?;
+        formatter
+            .format(index, &packet, writer)
+            .map_err(|err| FormatError::Fmt { index, err })?;
+    }
+    Ok(())
+}
+
+/// Formats messages to lines similar to the text export of the DLT Viewer.
+///
+/// Each message is written as a single line with the space separated
+/// columns index, timestamp (in seconds), message counter, ECU id,
+/// application id, context id, message type, message type info, mode,
+/// number of arguments & the payload. Columns that are not present in
+/// the message are written as `-`.
+///
+/// The arguments of verbose messages are decoded & written separated
+/// by spaces. Non verbose payloads are written as hex bytes prefixed
+/// with the message id.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DltViewerFormatter;
+
+impl MessageFormatter for DltViewerFormatter {
+    fn format(
+        &mut self,
+        index: usize,
+        packet: &DltPacketSlice<'_>,
+        out: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        let header = packet.header();
+
+        write!(out, "{} ", index)?;
+        match header.timestamp {
+            Some(ts) => write!(out, "{}.{:04} ", ts / 10_000, ts % 10_000)?,
+            None => out.write_str("- ")?,
+        }
+        write!(out, "{} ", header.message_counter)?;
+        write_id(out, header.ecu_id)?;
+
+        if let Some(ext) = &header.extended_header {
+            write_id(out, Some(ext.application_id))?;
+            write_id(out, Some(ext.context_id))?;
+            let (message_type, message_type_info) = message_type_names(ext.message_type());
+            write!(
+                out,
+                "{} {} {} {}",
+                message_type,
+                message_type_info,
+                if ext.is_verbose() {
+                    "verbose"
+                } else {
+                    "non-verbose"
+                },
+                ext.number_of_arguments
+            )?;
+        } else {
+            out.write_str("- - - - - 0")?;
+        }
+
+        if let Some(iter) = packet.verbose_value_iter() {
+            for value in iter {
+                out.write_char(' ')?;
+                match value {
+                    Ok(value) => write_verbose_value(out, &value)?,
+                    Err(err) => {
+                        write!(out, "<{}>", err)?;
+                        break;
+                    }
+                }
+            }
+        } else if let Some((message_id, payload)) = packet.message_id_and_payload() {
+            write!(out, " [{}]", message_id)?;
+            write_hex(out, payload)?;
+        } else {
+            write_hex(out, packet.payload())?;
+        }
+
+        out.write_char('\n')
+    }
+}
+
+/// Writes an id (ECU, application or context id) followed by a space.
+fn write_id(out: &mut dyn fmt::Write, id: Option<[u8; 4]>) -> fmt::Result {
+    match id {
+        Some(id) => {
+            for b in id.iter().take_while(|b| **b != 0) {
+                if b.is_ascii_graphic() {
+                    out.write_char(char::from(*b))?;
+                } else {
+                    out.write_char('?')?;
+                }
+            }
+            out.write_char(' ')
+        }
+        None => out.write_str("- "),
+    }
+}
+
+/// Writes the given bytes as space separated hex values (including
+/// a leading space).
+fn write_hex(out: &mut dyn fmt::Write, data: &[u8]) -> fmt::Result {
+    for b in data {
+        write!(out, " {:02x}", b)?;
+    }
+    Ok(())
+}
+
+/// Returns the names used by the DLT Viewer for the message type &
+/// message type info.
+fn message_type_names(message_type: Option<DltMessageType>) -> (&'static str, &'static str) {
+    use DltMessageType::*;
+    match message_type {
+        Some(Log(level)) => (
+            "log",
+            match level {
+                DltLogLevel::Fatal => "fatal",
+                DltLogLevel::Error => "error",
+                DltLogLevel::Warn => "warn",
+                DltLogLevel::Info => "info",
+                DltLogLevel::Debug => "debug",
+                DltLogLevel::Verbose => "verbose",
+            },
+        ),
+        Some(Trace(trace_type)) => (
+            "app_trace",
+            match trace_type {
+                DltTraceType::Variable => "variable",
+                DltTraceType::FunctionIn => "func_in",
+                DltTraceType::FunctionOut => "func_out",
+                DltTraceType::State => "state",
+                DltTraceType::Vfb => "vfb",
+            },
+        ),
+        Some(NetworkTrace(network_type)) => (
+            "nw_trace",
+            match network_type {
+                DltNetworkType::Ipc => "ipc",
+                DltNetworkType::Can => "can",
+                DltNetworkType::Flexray => "flexray",
+                DltNetworkType::Most => "most",
+                DltNetworkType::Ethernet => "ethernet",
+                DltNetworkType::SomeIp => "someip",
+                DltNetworkType::UserDefined(_) => "user_defined",
+            },
+        ),
+        Some(Control(control_type)) => (
+            "control",
+            match control_type {
+                DltControlMessageType::Request => "request",
+                DltControlMessageType::Response => "response",
+                DltControlMessageType::Time => "time",
+            },
+        ),
+        None => ("-", "-"),
+    }
+}
+
+/// Writes the elements of an array separated by commas.
+fn write_array<T, I>(out: &mut dyn fmt::Write, iter: I) -> fmt::Result
+where
+    T: fmt::Display,
+    I: Iterator<Item = T>,
+{
+    out.write_char('[')?;
+    for (i, value) in iter.enumerate() {
+        if i > 0 {
+            out.write_str(", ")?;
+        }
+        write!(out, "{}", value)?;
+    }
+    out.write_char(']')
+}
+
+/// Writes the value of a verbose argument (names, units & scalings are
+/// not written).
+pub(crate) fn write_verbose_value(
+    out: &mut dyn fmt::Write,
+    value: &VerboseValue<'_>,
+) -> fmt::Result {
+    use VerboseValue::*;
+    match value {
+        Bool(v) => write!(out, "{}", v.value),
+        Str(v) => out.write_str(v.value),
+        TraceInfo(v) => out.write_str(v.value),
+        I8(v) => write!(out, "{}", v.value),
+        I16(v) => write!(out, "{}", v.value),
+        I32(v) => write!(out, "{}", v.value),
+        I64(v) => write!(out, "{}", v.value),
+        I128(v) => write!(out, "{}", v.value),
+        U8(v) => write!(out, "{}", v.value),
+        U16(v) => write!(out, "{}", v.value),
+        U32(v) => write!(out, "{}", v.value),
+        U64(v) => write!(out, "{}", v.value),
+        U128(v) => write!(out, "{}", v.value),
+        F16(v) => write!(out, "{}", v.value.to_f32()),
+        F32(v) => write!(out, "{}", v.value),
+        F64(v) => write!(out, "{}", v.value),
+        F128(v) => write!(out, "0x{:032x}", v.value.to_bits()),
+        ArrBool(v) => write_array(out, v.iter()),
+        ArrI8(v) => write_array(out, v.iter()),
+        ArrI16(v) => write_array(out, v.iter()),
+        ArrI32(v) => write_array(out, v.iter()),
+        ArrI64(v) => write_array(out, v.iter()),
+        ArrI128(v) => write_array(out, v.iter()),
+        ArrU8(v) => write_array(out, v.iter()),
+        ArrU16(v) => write_array(out, v.iter()),
+        ArrU32(v) => write_array(out, v.iter()),
+        ArrU64(v) => write_array(out, v.iter()),
+        ArrU128(v) => write_array(out, v.iter()),
+        ArrF16(v) => write_array(out, v.iter().map(|f| f.to_f32())),
+        ArrF32(v) => write_array(out, v.iter()),
+        ArrF64(v) => write_array(out, v.iter()),
+        ArrF128(v) => {
+            out.write_char('[')?;
+            for (i, f) in v.iter().enumerate() {
+                if i > 0 {
+                    out.write_str(", ")?;
+                }
+                write!(out, "0x{:032x}", f.to_bits())?;
+            }
+            out.write_char(']')
+        }
+        Struct(v) => {
+            out.write_char('{')?;
+            for (i, entry) in v.entries().enumerate() {
+                if i > 0 {
+                    out.write_str(", ")?;
+                }
+                match entry {
+                    Ok(entry) => write_verbose_value(out, &entry)?,
+                    Err(err) => {
+                        write!(out, "<{}>", err)?;
+                        break;
+                    }
+                }
+            }
+            out.write_char('}')
+        }
+        Raw(v) => {
+            for (i, b) in v.data.iter().enumerate() {
+                if i > 0 {
+                    out.write_char(' ')?;
+                }
+                write!(out, "{:02x}", b)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::verbose::{StringValue, U16Value, VerboseValue};
+    use alloc::string::String;
+
+    /// Formatter writing the index & length of each message.
+    struct LenFormatter;
+
+    impl MessageFormatter for LenFormatter {
+        fn format(
+            &mut self,
+            index: usize,
+            packet: &DltPacketSlice<'_>,
+            out: &mut dyn fmt::Write,
+        ) -> fmt::Result {
+            writeln!(out, "{},{}", index, packet.slice().len())
+        }
+    }
+
+    /// Writer that fails after the given number of writes.
+    struct FailingWriter(usize);
+
+    impl fmt::Write for FailingWriter {
+        fn write_str(&mut self, _: &str) -> fmt::Result {
+            if self.0 == 0 {
+                Err(fmt::Error)
+            } else {
+                self.0 -= 1;
+                Ok(())
+            }
+        }
+    }
+
+    fn verbose_message() -> Vec<u8> {
+        let mut builder =
+            DltVerboseMessageBuilder::<256>::new(*b"APP\0", *b"CTX1", DltLogLevel::Warn)
+                .with_message_counter(5)
+                .with_ecu_id(*b"ECU1")
+                .with_timestamp(123_456);
+        builder
+            .add_value(&VerboseValue::Str(StringValue {
+                name: None,
+                value: "hello",
+            }))
+            .unwrap();
+        builder
+            .add_value(&VerboseValue::U16(U16Value {
+                variable_info: None,
+                scaling: None,
+                value: 1234,
+            }))
+            .unwrap();
+        let mut buffer = Vec::new();
+        builder.build(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn dlt_viewer_formatter() {
+        // verbose
+        {
+            let mut out = String::new();
+            format_all(&verbose_message(), &mut DltViewerFormatter, &mut out).unwrap();
+            assert_eq!(
+                "0 12.3456 5 ECU1 APP CTX1 log warn verbose 2 hello 1234\n",
+                out
+            );
+        }
+        // non verbose
+        {
+            let header = DltHeader {
+                is_big_endian: true,
+                message_counter: 1,
+                length: 0,
+                ecu_id: None,
+                session_id: None,
+                timestamp: None,
+                extended_header: Some(DltExtendedHeader::new_non_verbose_log(
+                    DltLogLevel::Info,
+                    *b"APP1",
+                    *b"CTX1",
+                )),
+            };
+            let mut data = Vec::new();
+            write_messages(&mut data, &[(header, &[0, 0, 0, 7, 0xab, 0xcd])]).unwrap();
+            let mut out = String::new();
+            format_all(&data, &mut DltViewerFormatter, &mut out).unwrap();
+            assert_eq!("0 - 1 - APP1 CTX1 log info non-verbose 0 [7] ab cd\n", out);
+        }
+    }
+
+    #[test]
+    fn custom_formatter() {
+        let msg = verbose_message();
+        let mut data = msg.clone();
+        data.extend_from_slice(&msg);
+
+        let mut out = String::new();
+        format_all(&data, &mut LenFormatter, &mut out).unwrap();
+        assert_eq!(alloc::format!("0,{}\n1,{}\n", msg.len(), msg.len()), out);
+
+        // trait objects
+        let formatter: &mut dyn MessageFormatter = &mut LenFormatter;
+        let mut out = String::new();
+        format_all(&data, formatter, &mut out).unwrap();
+        assert_eq!(alloc::format!("0,{}\n1,{}\n", msg.len(), msg.len()), out);
+    }
+
+    #[test]
+    fn errors() {
+        let msg = verbose_message();
+
+        // slice error
+        {
+            let mut data = msg.clone();
+            data.extend_from_slice(&msg[..msg.len() - 1]);
+            let mut out = String::new();
+            assert_matches!(
+                format_all(&data, &mut LenFormatter, &mut out),
+                Err(FormatError::Packet { index: 1, .. })
+            );
+            assert_eq!(alloc::format!("0,{}\n", msg.len()), out);
+        }
+
+        // writer error
+        assert_eq!(
+            Err(FormatError::Fmt {
+                index: 0,
+                err: fmt::Error
+            }),
+            format_all(&msg, &mut DltViewerFormatter, &mut FailingWriter(2))
+        );
+    }
+}
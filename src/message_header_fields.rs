@@ -0,0 +1,28 @@
+use crate::{DltExtendedHeader, DltHeader};
+
+/// Encoding & optional standard header fields shared by the message
+/// builders.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct MessageHeaderFields {
+    pub is_big_endian: bool,
+    pub message_counter: u8,
+    pub ecu_id: Option<[u8; 4]>,
+    pub session_id: Option<u32>,
+    pub timestamp: Option<u32>,
+}
+
+impl MessageHeaderFields {
+    /// Returns a header with the fields & the given extended header (the
+    /// length field is set to 0).
+    pub fn header(&self, extended_header: DltExtendedHeader) -> DltHeader {
+        DltHeader {
+            is_big_endian: self.is_big_endian,
+            message_counter: self.message_counter,
+            length: 0,
+            ecu_id: self.ecu_id,
+            session_id: self.session_id,
+            timestamp: self.timestamp,
+            extended_header: Some(extended_header),
+        }
+    }
+}
@@ -0,0 +1,418 @@
+use crate::error::{Layer, NetworkTraceError, UnexpectedEndOfSliceError};
+use crate::verbose::VerboseValue;
+use crate::*;
+
+/// Network trace message (message type "network trace") split into the
+/// bus specific header & the traced data.
+///
+/// Verbose network trace messages contain two raw arguments, the first
+/// one containing the header & the second one the payload. In non
+/// verbose messages both blocks follow the message id, each prefixed
+/// with its length (encoded in the endianness of the message):
+///
+/// ```text
+/// | message id (u32) | header len (u16) | header | payload len (u16) | payload |
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{DltNetworkType, DltPacketSlice, NetworkTraceSlice};
+///
+/// let bytes = include_bytes!("../tests/fixtures/network_trace_verbose.dlt");
+/// let packet = DltPacketSlice::from_slice(bytes).unwrap();
+///
+/// let trace = NetworkTraceSlice::from_packet(&packet).unwrap();
+/// assert_eq!(DltNetworkType::Can, trace.net_type());
+/// assert_eq!(&[0x00, 0x00, 0x01, 0x23], trace.header());
+/// assert_eq!(&[0xde, 0xad, 0xbe, 0xef], trace.payload());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct NetworkTraceSlice<'a> {
+    net_type: DltNetworkType,
+    is_big_endian: bool,
+    msg_id: Option<u32>,
+    header: &'a [u8],
+    payload: &'a [u8],
+}
+
+impl<'a> NetworkTraceSlice<'a> {
+    /// Splits the payload of the given network trace packet into the
+    /// header & payload blocks.
+    ///
+    /// Returns [`NetworkTraceError::NotANetworkTrace`] if the packet is
+    /// not a network trace message (packets without an extended header
+    /// are not considered to be network trace messages).
+    pub fn from_packet(
+        packet: &DltPacketSlice<'a>,
+    ) -> Result<NetworkTraceSlice<'a>, NetworkTraceError> {
+        let net_type = match packet.message_type() {
+            Some(DltMessageType::NetworkTrace(net_type)) => net_type,
+            other => return Err(NetworkTraceError::NotANetworkTrace(other)),
+        };
+
+        if packet.is_verbose() {
+            // the extended header is present as the message type is known
+            let number_of_arguments = packet
+                .extended_header()
+                .map_or(0, |ext| ext.number_of_arguments);
+            if 2 != number_of_arguments {
+                return Err(NetworkTraceError::UnexpectedArgumentCount(
+                    number_of_arguments,
+                ));
+            }
+            let is_big_endian = packet.is_big_endian();
+            let (header, rest) = raw_argument(0, packet.payload(), is_big_endian)?;
+            let (payload, _) = raw_argument(1, rest, is_big_endian)?;
+            Ok(NetworkTraceSlice {
+                net_type,
+                is_big_endian,
+                msg_id: None,
+                header,
+                payload,
+            })
+        } else {
+            let mut reader = BlockReader {
+                rest: packet.payload(),
+                offset: 0,
+                is_big_endian: packet.is_big_endian(),
+            };
+            let msg_id = reader.read(4)?;
+            let msg_id = [msg_id[0], msg_id[1], msg_id[2], msg_id[3]];
+            let msg_id = if reader.is_big_endian {
+                u32::from_be_bytes(msg_id)
+            } else {
+                u32::from_le_bytes(msg_id)
+            };
+            let header = reader.read_block()?;
+            let payload = reader.read_block()?;
+            Ok(NetworkTraceSlice {
+                net_type,
+                is_big_endian: reader.is_big_endian,
+                msg_id: Some(msg_id),
+                header,
+                payload,
+            })
+        }
+    }
+
+    /// Type of the traced network (bus).
+    #[inline]
+    pub fn net_type(&self) -> DltNetworkType {
+        self.net_type
+    }
+
+    /// True if the message (& with that the lengths & ids in the header
+    /// block) is encoded in big endian.
+    #[inline]
+    pub fn is_big_endian(&self) -> bool {
+        self.is_big_endian
+    }
+
+    /// Message id of non verbose messages (`None` for verbose messages).
+    #[inline]
+    pub fn msg_id(&self) -> Option<u32> {
+        self.msg_id
+    }
+
+    /// Bus specific header of the traced message.
+    #[inline]
+    pub fn header(&self) -> &'a [u8] {
+        self.header
+    }
+
+    /// Data of the traced message.
+    #[inline]
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+}
+
+/// Decodes the verbose argument at the start of `slice` & returns its data
+/// if it is a raw value.
+fn raw_argument(
+    index: usize,
+    slice: &[u8],
+    is_big_endian: bool,
+) -> Result<(&[u8], &[u8]), NetworkTraceError> {
+    match VerboseValue::from_slice(slice, is_big_endian)? {
+        (VerboseValue::Raw(raw), rest) => Ok((raw.data, rest)),
+        _ => Err(NetworkTraceError::ArgumentNotRaw(index)),
+    }
+}
+
+/// Reads the length prefixed blocks of non verbose network trace messages.
+struct BlockReader<'a> {
+    rest: &'a [u8],
+    offset: usize,
+    is_big_endian: bool,
+}
+
+impl<'a> BlockReader<'a> {
+    fn read(&mut self, len: usize) -> Result<&'a [u8], NetworkTraceError> {
+        if self.rest.len() < len {
+            return Err(NetworkTraceError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::NetworkTrace,
+                    minimum_size: self.offset + len,
+                    actual_size: self.offset + self.rest.len(),
+                },
+            ));
+        }
+        let (result, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        self.offset += len;
+        Ok(result)
+    }
+
+    fn read_block(&mut self) -> Result<&'a [u8], NetworkTraceError> {
+        let len = self.read(2)?;
+        let len = if self.is_big_endian {
+            u16::from_be_bytes([len[0], len[1]])
+        } else {
+            u16::from_le_bytes([len[0], len[1]])
+        };
+        self.read(usize::from(len))
+    }
+}
+
+#[cfg(test)]
+mod network_trace_slice_tests {
+    use super::*;
+    use crate::test_packets::packet;
+    use crate::verbose::{RawValue, U32Value};
+    use alloc::{format, vec::Vec};
+    use arrayvec::ArrayVec;
+    use proptest::prelude::*;
+
+    /// Verbose CAN trace (big endian, header `00 00 01 23`, payload
+    /// `de ad be ef`).
+    const VERBOSE: &[u8] = include_bytes!("../tests/fixtures/network_trace_verbose.dlt");
+
+    /// Non verbose ethernet trace (little endian, message id 0x10, header
+    /// `01 02`, payload `0a 0b 0c`).
+    const NON_VERBOSE: &[u8] = include_bytes!("../tests/fixtures/network_trace_non_verbose.dlt");
+
+    fn header(net_type: DltNetworkType, is_verbose: bool, is_big_endian: bool) -> DltHeader {
+        let mut ext = DltExtendedHeader::new_non_verbose(
+            DltMessageType::NetworkTrace(net_type),
+            *b"NET\0",
+            *b"TRC\0",
+        )
+        .unwrap();
+        if is_verbose {
+            ext.set_is_verbose(true);
+            ext.number_of_arguments = 2;
+        }
+        DltHeader {
+            is_big_endian,
+            message_counter: 0,
+            length: 0,
+            ecu_id: Some(*b"ECU1"),
+            session_id: None,
+            timestamp: None,
+            extended_header: Some(ext),
+        }
+    }
+
+    fn verbose_payload(header: &[u8], payload: &[u8], is_big_endian: bool) -> Vec<u8> {
+        let mut buf = ArrayVec::<u8, 1024>::new();
+        RawValue {
+            name: None,
+            data: header,
+        }
+        .add_to_msg(&mut buf, is_big_endian)
+        .unwrap();
+        RawValue {
+            name: None,
+            data: payload,
+        }
+        .add_to_msg(&mut buf, is_big_endian)
+        .unwrap();
+        buf.to_vec()
+    }
+
+    fn non_verbose_payload(
+        msg_id: u32,
+        header: &[u8],
+        payload: &[u8],
+        is_big_endian: bool,
+    ) -> Vec<u8> {
+        let mut result = Vec::new();
+        if is_big_endian {
+            result.extend_from_slice(&msg_id.to_be_bytes());
+            result.extend_from_slice(&(header.len() as u16).to_be_bytes());
+            result.extend_from_slice(header);
+            result.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        } else {
+            result.extend_from_slice(&msg_id.to_le_bytes());
+            result.extend_from_slice(&(header.len() as u16).to_le_bytes());
+            result.extend_from_slice(header);
+            result.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+        }
+        result.extend_from_slice(payload);
+        result
+    }
+
+    fn net_type_any() -> impl Strategy<Value = DltNetworkType> {
+        use DltNetworkType::*;
+        prop_oneof![
+            Just(Ipc),
+            Just(Can),
+            Just(Flexray),
+            Just(Most),
+            Just(Ethernet),
+            Just(SomeIp),
+            (7u8..=15).prop_map(UserDefined),
+        ]
+    }
+
+    #[test]
+    fn fixtures() {
+        let verbose = DltPacketSlice::from_slice(VERBOSE).unwrap();
+        assert!(verbose.is_verbose());
+        assert_eq!(
+            Ok(NetworkTraceSlice {
+                net_type: DltNetworkType::Can,
+                is_big_endian: true,
+                msg_id: None,
+                header: &[0x00, 0x00, 0x01, 0x23],
+                payload: &[0xde, 0xad, 0xbe, 0xef],
+            }),
+            NetworkTraceSlice::from_packet(&verbose)
+        );
+
+        let non_verbose = DltPacketSlice::from_slice(NON_VERBOSE).unwrap();
+        assert!(false == non_verbose.is_verbose());
+        let trace = NetworkTraceSlice::from_packet(&non_verbose).unwrap();
+        assert_eq!(DltNetworkType::Ethernet, trace.net_type());
+        assert!(false == trace.is_big_endian());
+        assert_eq!(Some(0x10), trace.msg_id());
+        assert_eq!(&[0x01, 0x02], trace.header());
+        assert_eq!(&[0x0a, 0x0b, 0x0c], trace.payload());
+    }
+
+    proptest! {
+        #[test]
+        fn from_packet(
+            net_type in net_type_any(),
+            msg_id in any::<u32>(),
+            ref header_data in proptest::collection::vec(any::<u8>(), 0..20),
+            ref payload_data in proptest::collection::vec(any::<u8>(), 0..50),
+            is_big_endian in any::<bool>(),
+        ) {
+            // verbose
+            let bytes = packet(
+                header(net_type, true, is_big_endian),
+                &verbose_payload(header_data, payload_data, is_big_endian),
+            );
+            let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+            let trace = NetworkTraceSlice::from_packet(&slice).unwrap();
+            prop_assert_eq!(net_type, trace.net_type());
+            prop_assert_eq!(is_big_endian, trace.is_big_endian());
+            prop_assert_eq!(None, trace.msg_id());
+            prop_assert_eq!(&header_data[..], trace.header());
+            prop_assert_eq!(&payload_data[..], trace.payload());
+
+            // non verbose
+            let nv_payload = non_verbose_payload(msg_id, header_data, payload_data, is_big_endian);
+            let bytes = packet(header(net_type, false, is_big_endian), &nv_payload);
+            let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+            let trace = NetworkTraceSlice::from_packet(&slice).unwrap();
+            prop_assert_eq!(net_type, trace.net_type());
+            prop_assert_eq!(is_big_endian, trace.is_big_endian());
+            prop_assert_eq!(Some(msg_id), trace.msg_id());
+            prop_assert_eq!(&header_data[..], trace.header());
+            prop_assert_eq!(&payload_data[..], trace.payload());
+
+            // truncated non verbose payload
+            for len in 0..nv_payload.len() {
+                let bytes = packet(header(net_type, false, is_big_endian), &nv_payload[..len]);
+                let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+                let is_end_of_slice = matches!(
+                    NetworkTraceSlice::from_packet(&slice),
+                    Err(NetworkTraceError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                        layer: Layer::NetworkTrace,
+                        actual_size,
+                        ..
+                    })) if actual_size == len
+                );
+                prop_assert!(is_end_of_slice);
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        // log message
+        let bytes = include_bytes!("../tests/fixtures/non_verbose_message.dlt");
+        let slice = DltPacketSlice::from_slice(bytes).unwrap();
+        assert_eq!(
+            Err(NetworkTraceError::NotANetworkTrace(Some(
+                DltMessageType::Log(DltLogLevel::Info)
+            ))),
+            NetworkTraceSlice::from_packet(&slice)
+        );
+
+        // no extended header
+        let bytes = DltHeader::new_non_verbose(*b"ECU1").to_bytes();
+        let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+        assert_eq!(
+            Err(NetworkTraceError::NotANetworkTrace(None)),
+            NetworkTraceSlice::from_packet(&slice)
+        );
+
+        // wrong number of arguments
+        let mut h = header(DltNetworkType::Can, true, false);
+        h.extended_header.as_mut().unwrap().number_of_arguments = 1;
+        let bytes = packet(h, &verbose_payload(&[1], &[2], false));
+        let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+        assert_eq!(
+            Err(NetworkTraceError::UnexpectedArgumentCount(1)),
+            NetworkTraceSlice::from_packet(&slice)
+        );
+
+        // argument that is not a raw value
+        for index in 0..2 {
+            let mut buf = ArrayVec::<u8, 64>::new();
+            let raw = RawValue {
+                name: None,
+                data: &[1, 2],
+            };
+            let other = U32Value {
+                variable_info: None,
+                scaling: None,
+                value: 1,
+            };
+            if 0 == index {
+                other.add_to_msg(&mut buf, false).unwrap();
+                raw.add_to_msg(&mut buf, false).unwrap();
+            } else {
+                raw.add_to_msg(&mut buf, false).unwrap();
+                other.add_to_msg(&mut buf, false).unwrap();
+            }
+            let bytes = packet(header(DltNetworkType::Can, true, false), &buf);
+            let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+            assert_eq!(
+                Err(NetworkTraceError::ArgumentNotRaw(index)),
+                NetworkTraceSlice::from_packet(&slice)
+            );
+        }
+
+        // verbose decode error
+        let bytes = packet(header(DltNetworkType::Can, true, false), &[0, 0]);
+        let slice = DltPacketSlice::from_slice(&bytes).unwrap();
+        assert!(matches!(
+            NetworkTraceSlice::from_packet(&slice),
+            Err(NetworkTraceError::Verbose(_))
+        ));
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let slice = DltPacketSlice::from_slice(VERBOSE).unwrap();
+        let trace = NetworkTraceSlice::from_packet(&slice).unwrap();
+        assert_eq!(trace, trace.clone());
+        assert!(format!("{:?}", trace).starts_with("NetworkTraceSlice {"));
+    }
+}
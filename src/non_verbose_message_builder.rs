@@ -0,0 +1,273 @@
+use crate::error::WriteError;
+use crate::message_header_fields::MessageHeaderFields;
+use crate::{DltExtendedHeader, DltHeader, DltLogLevel, NonVerbosePayload};
+use std::{io, vec::Vec};
+
+/// Builder for non verbose dlt log messages (message id followed by the
+/// raw payload data).
+///
+/// The message is written with an extended header (containing the
+/// application id, context id & log level) that marks the message as
+/// non verbose & without arguments. The message id is encoded in the
+/// endianness of the message.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{DltPacketSlice, NonVerboseMessageBuilder};
+///
+/// let builder = NonVerboseMessageBuilder::new(*b"APP1", *b"CTX1", 0x1234, &[1, 2, 3])
+///     .with_ecu_id(*b"ECU1")
+///     .with_timestamp(1234);
+///
+/// let mut buffer = Vec::new();
+/// builder.build(&mut buffer).unwrap();
+///
+/// let packet = DltPacketSlice::from_slice(&buffer).unwrap();
+/// assert!(false == packet.is_verbose());
+/// assert_eq!(Some(0x1234), packet.message_id());
+/// assert_eq!(Some(&[1u8, 2, 3][..]), packet.non_verbose_payload());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct NonVerboseMessageBuilder<'a> {
+    fields: MessageHeaderFields,
+    log_level: DltLogLevel,
+    application_id: [u8; 4],
+    context_id: [u8; 4],
+    payload: NonVerbosePayload<'a>,
+}
+
+impl<'a> NonVerboseMessageBuilder<'a> {
+    /// Creates a builder for a little endian non verbose info log message
+    /// without ecu id, session id or timestamp.
+    pub fn new(
+        application_id: [u8; 4],
+        context_id: [u8; 4],
+        msg_id: u32,
+        data: &'a [u8],
+    ) -> NonVerboseMessageBuilder<'a> {
+        NonVerboseMessageBuilder {
+            fields: MessageHeaderFields::default(),
+            log_level: DltLogLevel::Info,
+            application_id,
+            context_id,
+            payload: NonVerbosePayload { msg_id, data },
+        }
+    }
+
+    /// Sets if the message id is encoded in big endian (the data is
+    /// written unchanged).
+    pub fn with_big_endian(mut self, is_big_endian: bool) -> Self {
+        self.fields.is_big_endian = is_big_endian;
+        self
+    }
+
+    /// Sets the message counter.
+    pub fn with_message_counter(mut self, message_counter: u8) -> Self {
+        self.fields.message_counter = message_counter;
+        self
+    }
+
+    /// Sets the ecu id.
+    pub fn with_ecu_id(mut self, ecu_id: [u8; 4]) -> Self {
+        self.fields.ecu_id = Some(ecu_id);
+        self
+    }
+
+    /// Sets the session id.
+    pub fn with_session_id(mut self, session_id: u32) -> Self {
+        self.fields.session_id = Some(session_id);
+        self
+    }
+
+    /// Sets the timestamp (in 0.1 milliseconds).
+    pub fn with_timestamp(mut self, timestamp: u32) -> Self {
+        self.fields.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the log level (info by default).
+    pub fn with_log_level(mut self, log_level: DltLogLevel) -> Self {
+        self.log_level = log_level;
+        self
+    }
+
+    /// Returns the message id & data of the message.
+    #[inline]
+    pub fn payload(&self) -> &NonVerbosePayload<'a> {
+        &self.payload
+    }
+
+    /// Returns the header of the message (including the length of the
+    /// header & payload).
+    ///
+    /// Returns [`WriteError::MessageTooLong`] if the length of the message
+    /// can not be represented in the length field.
+    pub fn header(&self) -> Result<DltHeader, WriteError> {
+        let mut header = self.header_without_length();
+        let length = usize::from(header.header_len()) + self.payload.serialized_len();
+        header.length =
+            u16::try_from(length).map_err(|_| WriteError::MessageTooLong { index: 0, length })?;
+        Ok(header)
+    }
+
+    /// Appends the complete message (header, message id & data) to the
+    /// given vector. On error nothing is appended.
+    pub fn build(&self, buffer: &mut Vec<u8>) -> Result<(), WriteError> {
+        let header = self.header()?;
+        buffer.reserve(usize::from(header.length));
+        self.payload.write(&header, buffer)
+    }
+
+    /// Writes the complete message (header, message id & data) to the
+    /// given writer.
+    ///
+    /// As only one message is written the index in a returned error is
+    /// always 0.
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> Result<(), WriteError> {
+        // NonVerbosePayload::write computes & checks the length field itself
+        self.payload.write(&self.header_without_length(), writer)
+    }
+
+    /// Returns the header with the length field set to 0.
+    fn header_without_length(&self) -> DltHeader {
+        self.fields.header(DltExtendedHeader::new_non_verbose_log(
+            self.log_level,
+            self.application_id,
+            self.context_id,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod non_verbose_message_builder_tests {
+    use super::*;
+    use crate::{DltMessageType, DltPacketSlice};
+    use alloc::{format, vec};
+    use proptest::prelude::*;
+
+    /// Non verbose info message with ecu id & timestamp (little endian).
+    const FIXTURE: &[u8] = include_bytes!("../tests/fixtures/non_verbose_message.dlt");
+
+    #[test]
+    fn fixture() {
+        let builder = NonVerboseMessageBuilder::new(*b"APP1", *b"CTX1", 0x1234, &[1, 2, 3])
+            .with_ecu_id(*b"ECU1")
+            .with_timestamp(1234);
+        let mut buffer = Vec::new();
+        builder.build(&mut buffer).unwrap();
+        assert_eq!(FIXTURE, &buffer[..]);
+
+        let mut written = Vec::new();
+        builder.write(&mut written).unwrap();
+        assert_eq!(FIXTURE, &written[..]);
+
+        // non verbose flag cleared & no arguments
+        let packet = DltPacketSlice::from_slice(FIXTURE).unwrap();
+        assert!(false == packet.is_verbose());
+        let ext = packet.extended_header().unwrap();
+        assert_eq!(0, ext.number_of_arguments);
+        assert_eq!(
+            Some(DltMessageType::Log(DltLogLevel::Info)),
+            ext.message_type()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn build_round_trip(
+            application_id in any::<[u8; 4]>(),
+            context_id in any::<[u8; 4]>(),
+            msg_id in any::<u32>(),
+            ref data in proptest::collection::vec(any::<u8>(), 0..100),
+            is_big_endian in any::<bool>(),
+            message_counter in any::<u8>(),
+            ecu_id in any::<Option<[u8; 4]>>(),
+            session_id in any::<Option<u32>>(),
+            timestamp in any::<Option<u32>>(),
+        ) {
+            let mut builder = NonVerboseMessageBuilder::new(application_id, context_id, msg_id, data)
+                .with_big_endian(is_big_endian)
+                .with_message_counter(message_counter)
+                .with_log_level(DltLogLevel::Warn);
+            if let Some(ecu_id) = ecu_id {
+                builder = builder.with_ecu_id(ecu_id);
+            }
+            if let Some(session_id) = session_id {
+                builder = builder.with_session_id(session_id);
+            }
+            if let Some(timestamp) = timestamp {
+                builder = builder.with_timestamp(timestamp);
+            }
+            prop_assert_eq!(msg_id, builder.payload().msg_id);
+
+            let mut buffer = vec![0xff];
+            builder.build(&mut buffer).unwrap();
+
+            let packet = DltPacketSlice::from_slice(&buffer[1..]).unwrap();
+            prop_assert_eq!(builder.header().unwrap(), packet.header());
+            prop_assert_eq!(buffer.len() - 1, packet.slice().len());
+            prop_assert_eq!(false, packet.is_verbose());
+            prop_assert_eq!(is_big_endian, packet.is_big_endian());
+            prop_assert_eq!(ecu_id, packet.ecu_id());
+            prop_assert_eq!(Some(msg_id), packet.message_id());
+            prop_assert_eq!(Some(&data[..]), packet.non_verbose_payload());
+            prop_assert_eq!(
+                Some(NonVerbosePayload { msg_id, data }),
+                NonVerbosePayload::from_packet(&packet)
+            );
+            let ext = packet.extended_header().unwrap();
+            prop_assert_eq!(0, ext.number_of_arguments);
+            prop_assert_eq!(application_id, ext.application_id);
+            prop_assert_eq!(context_id, ext.context_id);
+            prop_assert_eq!(Some(DltMessageType::Log(DltLogLevel::Warn)), ext.message_type());
+        }
+    }
+
+    #[test]
+    fn too_long() {
+        // largest payload that fits
+        let header_len = 4 + 10;
+        let data = vec![0u8; usize::from(u16::MAX) - header_len - 4];
+        let builder = NonVerboseMessageBuilder::new(*b"APP1", *b"CTX1", 1, &data);
+        assert_eq!(u16::MAX, builder.header().unwrap().length);
+        let mut buffer = Vec::new();
+        builder.build(&mut buffer).unwrap();
+        assert_eq!(usize::from(u16::MAX), buffer.len());
+
+        // one byte too many
+        let data = vec![0u8; usize::from(u16::MAX) - header_len - 3];
+        let builder = NonVerboseMessageBuilder::new(*b"APP1", *b"CTX1", 1, &data);
+        let expected_length = usize::from(u16::MAX) + 1;
+        match builder.header() {
+            Err(WriteError::MessageTooLong { index, length }) => {
+                assert_eq!(0, index);
+                assert_eq!(expected_length, length);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        let mut buffer = vec![1, 2];
+        match builder.build(&mut buffer) {
+            Err(WriteError::MessageTooLong { length, .. }) => {
+                assert_eq!(expected_length, length)
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(vec![1, 2], buffer);
+        let mut written = Vec::new();
+        match builder.write(&mut written) {
+            Err(WriteError::MessageTooLong { length, .. }) => {
+                assert_eq!(expected_length, length)
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(written.is_empty());
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let builder = NonVerboseMessageBuilder::new(*b"APP1", *b"CTX1", 1, &[]);
+        assert_eq!(builder, builder.clone());
+        assert!(format!("{:?}", builder).starts_with("NonVerboseMessageBuilder {"));
+    }
+}
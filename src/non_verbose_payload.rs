@@ -0,0 +1,294 @@
+use super::*;
+use core::fmt;
+
+/// Payload of a non verbose DLT message (message id & the data
+/// following the message id).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::NonVerbosePayload;
+///
+/// let payload = NonVerbosePayload::from_slice(&[0, 0, 0, 0x12, 1, 2], true).unwrap();
+/// assert_eq!(0x12, payload.msg_id);
+/// assert_eq!(&[1, 2], payload.data);
+/// assert_eq!("[0x12] 01 02", format!("{}", payload));
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct NonVerbosePayload<'a> {
+    /// Message id identifying the message (e.g. in a FIBEX file).
+    pub msg_id: u32,
+    /// Data following the message id.
+    pub data: &'a [u8],
+}
+
+impl<'a> NonVerbosePayload<'a> {
+    /// Maximum number of data bytes written by the `Display` implementation
+    /// (further bytes are indicated by " ...").
+    pub const MAX_DISPLAY_BYTES: usize = 32;
+
+    /// Decodes the message id from the start of the given payload
+    /// (`None` is returned if the payload is shorter then 4 bytes).
+    pub fn from_slice(payload: &'a [u8], is_big_endian: bool) -> Option<NonVerbosePayload<'a>> {
+        if payload.len() < 4 {
+            return None;
+        }
+        let id_bytes = [payload[0], payload[1], payload[2], payload[3]];
+        Some(NonVerbosePayload {
+            msg_id: if is_big_endian {
+                u32::from_be_bytes(id_bytes)
+            } else {
+                u32::from_le_bytes(id_bytes)
+            },
+            data: &payload[4..],
+        })
+    }
+
+    /// Returns the non verbose payload of the packet (`None` if the packet
+    /// is a verbose message or the payload is too short for a message id).
+    pub fn from_packet(packet: &DltPacketSlice<'a>) -> Option<NonVerbosePayload<'a>> {
+        packet
+            .message_id_and_payload()
+            .map(|(msg_id, data)| NonVerbosePayload { msg_id, data })
+    }
+
+    /// Number of bytes of the payload when encoded (message id & data).
+    #[inline]
+    pub fn serialized_len(&self) -> usize {
+        4 + self.data.len()
+    }
+
+    /// Writes a complete DLT packet containing the payload.
+    ///
+    /// The header is based on `header_template`. The `length` field is
+    /// calculated & the verbose flag in the extended header (if present)
+    /// is cleared together with the number of arguments. The message id is
+    /// encoded with the endianness defined by `is_big_endian` of the
+    /// template. As only one message is written the index in a returned
+    /// error is always 0.
+    #[cfg(feature = "std")]
+    pub fn write<W: std::io::Write>(
+        &self,
+        header_template: &DltHeader,
+        writer: &mut W,
+    ) -> Result<(), error::WriteError> {
+        let mut header = header_template.clone();
+        if let Some(ext) = header.extended_header.as_mut() {
+            ext.set_is_verbose(false);
+            ext.number_of_arguments = 0;
+        }
+        let length = usize::from(header.header_len()) + self.serialized_len();
+        header.length = u16::try_from(length)
+            .map_err(|_| error::WriteError::MessageTooLong { index: 0, length })?;
+
+        let id_bytes = if header.is_big_endian {
+            self.msg_id.to_be_bytes()
+        } else {
+            self.msg_id.to_le_bytes()
+        };
+        header
+            .write(writer)
+            .and_then(|_| writer.write_all(&id_bytes))
+            .and_then(|_| writer.write_all(self.data))
+            .map_err(|err| error::WriteError::Io { index: 0, err })
+    }
+}
+
+impl<'a> fmt::Display for NonVerbosePayload<'a> {
+    /// Writes the message id in hex followed by the first
+    /// [`NonVerbosePayload::MAX_DISPLAY_BYTES`] data bytes in hex.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[0x{:x}]", self.msg_id)?;
+        for b in self.data.iter().take(NonVerbosePayload::MAX_DISPLAY_BYTES) {
+            write!(f, " {:02x}", b)?;
+        }
+        if self.data.len() > NonVerbosePayload::MAX_DISPLAY_BYTES {
+            write!(f, " ...")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod non_verbose_payload_tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::proptest_generators::*;
+    use alloc::{format, string::String, vec::Vec};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn from_slice(
+            msg_id in any::<u32>(),
+            ref data in proptest::collection::vec(any::<u8>(), 0..10),
+        ) {
+            for is_big_endian in [false, true] {
+                let mut payload = Vec::with_capacity(4 + data.len());
+                if is_big_endian {
+                    payload.extend_from_slice(&msg_id.to_be_bytes());
+                } else {
+                    payload.extend_from_slice(&msg_id.to_le_bytes());
+                }
+                payload.extend_from_slice(data);
+
+                let actual = NonVerbosePayload::from_slice(&payload, is_big_endian).unwrap();
+                prop_assert_eq!(msg_id, actual.msg_id);
+                prop_assert_eq!(&data[..], actual.data);
+                prop_assert_eq!(payload.len(), actual.serialized_len());
+
+                // too short for a message id
+                for len in 0..4 {
+                    prop_assert_eq!(None, NonVerbosePayload::from_slice(&payload[..len], is_big_endian));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    proptest! {
+        #[test]
+        fn write_from_packet(
+            ref header in dlt_header_any(),
+            msg_id in any::<u32>(),
+            ref data in proptest::collection::vec(any::<u8>(), 0..10),
+        ) {
+            let value = NonVerbosePayload { msg_id, data };
+
+            let mut buf = Vec::new();
+            value.write(header, &mut buf).unwrap();
+
+            let packet = DltPacketSlice::from_slice(&buf).unwrap();
+            prop_assert_eq!(buf.len(), packet.slice().len());
+            prop_assert_eq!(header.is_big_endian, packet.is_big_endian());
+            prop_assert!(false == packet.is_verbose());
+            prop_assert_eq!(Some(value), NonVerbosePayload::from_packet(&packet));
+            prop_assert_eq!(
+                Some(value),
+                NonVerbosePayload::from_slice(packet.payload(), header.is_big_endian)
+            );
+
+            // header fields are taken from the template
+            let mut expected = header.clone();
+            expected.length = buf.len() as u16;
+            if let Some(ext) = expected.extended_header.as_mut() {
+                ext.set_is_verbose(false);
+                ext.number_of_arguments = 0;
+            }
+            prop_assert_eq!(expected, packet.header());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_errors() {
+        let header = DltHeader::default();
+
+        // too long
+        {
+            let data = [0u8; 0xffff];
+            let mut buf = Vec::new();
+            assert_matches!(
+                NonVerbosePayload { msg_id: 1, data: &data }.write(&header, &mut buf),
+                Err(error::WriteError::MessageTooLong { index: 0, length }) if length == 4 + 4 + 0xffff
+            );
+            assert!(buf.is_empty());
+        }
+
+        // io error
+        {
+            let mut buf = [0u8; 4];
+            let mut writer = &mut buf[..];
+            assert_matches!(
+                NonVerbosePayload {
+                    msg_id: 1,
+                    data: &[]
+                }
+                .write(&header, &mut writer),
+                Err(error::WriteError::Io { index: 0, .. })
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_packet_verbose() {
+        let mut header = DltHeader {
+            is_big_endian: false,
+            message_counter: 0,
+            length: 0,
+            ecu_id: None,
+            session_id: None,
+            timestamp: None,
+            extended_header: Some(DltExtendedHeader::new_non_verbose_log(
+                DltLogLevel::Info,
+                [0; 4],
+                [0; 4],
+            )),
+        };
+        header
+            .extended_header
+            .as_mut()
+            .unwrap()
+            .set_is_verbose(true);
+        header.length = header.header_len() + 4;
+        let mut buf = Vec::new();
+        header.write(&mut buf).unwrap();
+        buf.extend_from_slice(&[1, 2, 3, 4]);
+
+        let packet = DltPacketSlice::from_slice(&buf).unwrap();
+        assert_eq!(None, NonVerbosePayload::from_packet(&packet));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "[0x0]",
+            format!(
+                "{}",
+                NonVerbosePayload {
+                    msg_id: 0,
+                    data: &[]
+                }
+            )
+        );
+        assert_eq!(
+            "[0xabc] 00 0f ff",
+            format!(
+                "{}",
+                NonVerbosePayload {
+                    msg_id: 0xabc,
+                    data: &[0, 0xf, 0xff]
+                }
+            )
+        );
+
+        // bounded output
+        let data = [0xaau8; NonVerbosePayload::MAX_DISPLAY_BYTES + 1];
+        let mut expected = String::from("[0x1]");
+        for _ in 0..NonVerbosePayload::MAX_DISPLAY_BYTES {
+            expected.push_str(" aa");
+        }
+        expected.push_str(" ...");
+        assert_eq!(
+            expected,
+            format!(
+                "{}",
+                NonVerbosePayload {
+                    msg_id: 1,
+                    data: &data
+                }
+            )
+        );
+        assert_eq!(
+            expected[..expected.len() - 4],
+            format!(
+                "{}",
+                NonVerbosePayload {
+                    msg_id: 1,
+                    data: &data[..NonVerbosePayload::MAX_DISPLAY_BYTES]
+                }
+            )
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use crate::message_formatter::write_verbose_value;
+use crate::verbose::VerboseValue;
+use crate::{DltPacketSlice, NonVerbosePayload};
+use core::fmt;
+use std::boxed::Box;
+use std::collections::BTreeMap;
+use std::string::String;
+use std::vec::Vec;
+
+/// Non verbose message decoded by a [`NonVerboseDecoder`].
+///
+/// The arguments are represented as verbose values (pointing into the
+/// payload data) & the text of the message is described by a template
+/// in which each `{}` is replaced by the next argument (e.g. taken from
+/// the message description in a FIBEX file).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedMessage<'a> {
+    /// Template describing the message text (each `{}` is a placeholder
+    /// for the next value in `values`).
+    pub template: String,
+    /// Decoded arguments of the message.
+    pub values: Vec<VerboseValue<'a>>,
+}
+
+impl<'a> fmt::Display for DecodedMessage<'a> {
+    /// Writes the template with the placeholders replaced by the values.
+    ///
+    /// Placeholders without a matching value are written unchanged &
+    /// values without a placeholder are ignored.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_template(f, &self.template, &self.values)
+    }
+}
+
+/// Writes the template with each `{}` replaced by the next value
+/// (placeholders without a matching value are written unchanged &
+/// values without a placeholder are ignored).
+pub(crate) fn write_template(
+    out: &mut dyn fmt::Write,
+    template: &str,
+    values: &[VerboseValue<'_>],
+) -> fmt::Result {
+    let mut values = values.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        out.write_str(first)?;
+    }
+    for part in parts {
+        match values.next() {
+            Some(value) => write_verbose_value(out, value)?,
+            None => out.write_str("{}")?,
+        }
+        out.write_str(part)?;
+    }
+    Ok(())
+}
+
+/// Decoder for the payload of non verbose messages with a known layout.
+pub trait NonVerboseDecoder {
+    /// Decodes the data following the message id of a non verbose
+    /// message. `None` is returned if the data does not match the
+    /// expected layout.
+    fn decode<'a>(
+        &self,
+        msg_id: u32,
+        data: &'a [u8],
+        is_big_endian: bool,
+    ) -> Option<DecodedMessage<'a>>;
+}
+
+/// Result of decoding a non verbose message via a [`NonVerboseRegistry`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NonVerboseDecoded<'a> {
+    /// Message was decoded by a registered decoder.
+    Decoded(DecodedMessage<'a>),
+    /// No decoder is registered for the message (or the registered
+    /// decoder failed) and only the raw payload is available.
+    Raw(NonVerbosePayload<'a>),
+}
+
+/// Registry of [`NonVerboseDecoder`]s keyed by application id, context id
+/// & message id.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{DecodedMessage, NonVerboseDecoder, NonVerboseRegistry};
+/// use dlt_parse::verbose::{U8Value, VerboseValue};
+///
+/// struct SpeedDecoder;
+///
+/// impl NonVerboseDecoder for SpeedDecoder {
+///     fn decode<'a>(&self, _: u32, data: &'a [u8], _: bool) -> Option<DecodedMessage<'a>> {
+///         Some(DecodedMessage {
+///             template: "speed {} km/h".to_string(),
+///             values: vec![VerboseValue::U8(U8Value {
+///                 variable_info: None,
+///                 scaling: None,
+///                 value: *data.first()?,
+///             })],
+///         })
+///     }
+/// }
+///
+/// let mut registry = NonVerboseRegistry::new();
+/// registry.register(*b"APP\0", *b"CTX\0", 0x10, SpeedDecoder);
+///
+/// let decoded = registry
+///     .decode_payload(*b"APP\0", *b"CTX\0", 0x10, &[42], true)
+///     .unwrap();
+/// assert_eq!("speed 42 km/h", decoded.to_string());
+/// ```
+#[derive(Default)]
+pub struct NonVerboseRegistry {
+    decoders: BTreeMap<DecoderKey, Box<dyn NonVerboseDecoder>>,
+}
+
+/// Application id, context id & message id a decoder is registered for.
+type DecoderKey = ([u8; 4], [u8; 4], u32);
+
+impl NonVerboseRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> NonVerboseRegistry {
+        NonVerboseRegistry {
+            decoders: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a decoder for the given application id, context id &
+    /// message id. A previously registered decoder for the same key is
+    /// replaced & returned.
+    pub fn register<D: NonVerboseDecoder + 'static>(
+        &mut self,
+        application_id: [u8; 4],
+        context_id: [u8; 4],
+        msg_id: u32,
+        decoder: D,
+    ) -> Option<Box<dyn NonVerboseDecoder>> {
+        self.decoders
+            .insert((application_id, context_id, msg_id), Box::new(decoder))
+    }
+
+    /// Number of registered decoders.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.decoders.len()
+    }
+
+    /// Returns true if no decoder is registered.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.decoders.is_empty()
+    }
+
+    /// Decodes the data of a non verbose message with the decoder
+    /// registered for the given key (`None` if no decoder is registered
+    /// or the decoder failed).
+    pub fn decode_payload<'a>(
+        &self,
+        application_id: [u8; 4],
+        context_id: [u8; 4],
+        msg_id: u32,
+        data: &'a [u8],
+        is_big_endian: bool,
+    ) -> Option<DecodedMessage<'a>> {
+        self.decoders
+            .get(&(application_id, context_id, msg_id))
+            .and_then(|decoder| decoder.decode(msg_id, data, is_big_endian))
+    }
+
+    /// Decodes the given non verbose packet with the matching registered
+    /// decoder & falls back to the raw payload if no decoder matches.
+    ///
+    /// Packets without an extended header are always returned as raw
+    /// payload (as the application & context id are unknown). `None` is
+    /// returned for verbose packets & packets too short for a message id.
+    pub fn decode<'a>(&self, packet: &DltPacketSlice<'a>) -> Option<NonVerboseDecoded<'a>> {
+        let payload = NonVerbosePayload::from_packet(packet)?;
+        let decoded = packet.extended_header().and_then(|ext| {
+            self.decode_payload(
+                ext.application_id,
+                ext.context_id,
+                payload.msg_id,
+                payload.data,
+                packet.is_big_endian(),
+            )
+        });
+        Some(match decoded {
+            Some(d) => NonVerboseDecoded::Decoded(d),
+            None => NonVerboseDecoded::Raw(payload),
+        })
+    }
+}
+
+impl fmt::Debug for NonVerboseRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NonVerboseRegistry")
+            .field("keys", &self.decoders.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod non_verbose_registry_tests {
+    use super::*;
+    use crate::test_packets;
+    use crate::verbose::{U16Value, U8Value};
+    use crate::*;
+    use alloc::{format, string::ToString, vec};
+
+    /// Decodes a single u16 argument.
+    struct U16Decoder;
+
+    impl NonVerboseDecoder for U16Decoder {
+        fn decode<'a>(
+            &self,
+            _msg_id: u32,
+            data: &'a [u8],
+            is_big_endian: bool,
+        ) -> Option<DecodedMessage<'a>> {
+            if data.len() != 2 {
+                return None;
+            }
+            let bytes = [data[0], data[1]];
+            Some(DecodedMessage {
+                template: "value={}".to_string(),
+                values: vec![VerboseValue::U16(U16Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: if is_big_endian {
+                        u16::from_be_bytes(bytes)
+                    } else {
+                        u16::from_le_bytes(bytes)
+                    },
+                })],
+            })
+        }
+    }
+
+    const APP: [u8; 4] = *b"APP1";
+    const CTX: [u8; 4] = *b"CTX1";
+
+    fn packet(
+        is_big_endian: bool,
+        with_ext: bool,
+        is_verbose: bool,
+        msg_id: u32,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let mut ext =
+            with_ext.then(|| DltExtendedHeader::new_non_verbose_log(DltLogLevel::Info, APP, CTX));
+        if let Some(ext) = ext.as_mut() {
+            ext.set_is_verbose(is_verbose);
+        }
+        let mut payload = Vec::new();
+        if is_big_endian {
+            payload.extend_from_slice(&msg_id.to_be_bytes());
+        } else {
+            payload.extend_from_slice(&msg_id.to_le_bytes());
+        }
+        payload.extend_from_slice(data);
+        test_packets::packet(test_packets::header(is_big_endian, ext), &payload)
+    }
+
+    #[test]
+    fn dispatch_and_fallback() {
+        let mut registry = NonVerboseRegistry::new();
+        assert!(registry.is_empty());
+        assert!(registry.register(APP, CTX, 1, U16Decoder).is_none());
+        assert!(registry.register(APP, CTX, 1, U16Decoder).is_some());
+        assert_eq!(1, registry.len());
+        assert!(format!("{:?}", registry).contains("NonVerboseRegistry"));
+
+        for is_big_endian in [false, true] {
+            // registered id
+            {
+                let buf = packet(is_big_endian, true, false, 1, &[0x12, 0x34]);
+                let slice = DltPacketSlice::from_slice(&buf).unwrap();
+                let expected_value = if is_big_endian { 0x1234 } else { 0x3412 };
+                let decoded = registry.decode(&slice).unwrap();
+                assert_eq!(
+                    NonVerboseDecoded::Decoded(DecodedMessage {
+                        template: "value={}".to_string(),
+                        values: vec![VerboseValue::U16(U16Value {
+                            variable_info: None,
+                            scaling: None,
+                            value: expected_value,
+                        })],
+                    }),
+                    decoded
+                );
+                assert_eq!(Some(decoded), slice.decode_with(&registry));
+            }
+
+            // unknown message id, app id & context id
+            {
+                let buf = packet(is_big_endian, true, false, 2, &[0x12, 0x34]);
+                let slice = DltPacketSlice::from_slice(&buf).unwrap();
+                assert_eq!(
+                    Some(NonVerboseDecoded::Raw(NonVerbosePayload {
+                        msg_id: 2,
+                        data: &[0x12, 0x34]
+                    })),
+                    registry.decode(&slice)
+                );
+                assert_eq!(
+                    None,
+                    registry.decode_payload(*b"APP2", CTX, 1, &[0x12, 0x34], is_big_endian)
+                );
+                assert_eq!(
+                    None,
+                    registry.decode_payload(APP, *b"CTX2", 1, &[0x12, 0x34], is_big_endian)
+                );
+            }
+
+            // decoder fails
+            {
+                let buf = packet(is_big_endian, true, false, 1, &[0x12]);
+                let slice = DltPacketSlice::from_slice(&buf).unwrap();
+                assert_eq!(
+                    Some(NonVerboseDecoded::Raw(NonVerbosePayload {
+                        msg_id: 1,
+                        data: &[0x12]
+                    })),
+                    slice.decode_with(&registry)
+                );
+            }
+
+            // no extended header
+            {
+                let buf = packet(is_big_endian, false, false, 1, &[0x12, 0x34]);
+                let slice = DltPacketSlice::from_slice(&buf).unwrap();
+                assert_eq!(
+                    Some(NonVerboseDecoded::Raw(NonVerbosePayload {
+                        msg_id: 1,
+                        data: &[0x12, 0x34]
+                    })),
+                    slice.decode_with(&registry)
+                );
+            }
+
+            // verbose message
+            {
+                let buf = packet(is_big_endian, true, true, 1, &[0x12, 0x34]);
+                let slice = DltPacketSlice::from_slice(&buf).unwrap();
+                assert_eq!(None, slice.decode_with(&registry));
+            }
+        }
+    }
+
+    #[test]
+    fn display() {
+        let value = |value| {
+            VerboseValue::U8(U8Value {
+                variable_info: None,
+                scaling: None,
+                value,
+            })
+        };
+        let msg = |template: &str, values| DecodedMessage {
+            template: template.to_string(),
+            values,
+        };
+        assert_eq!("", msg("", vec![]).to_string());
+        assert_eq!("abc", msg("abc", vec![value(1)]).to_string());
+        assert_eq!(
+            "a=1, b=2",
+            msg("a={}, b={}", vec![value(1), value(2)]).to_string()
+        );
+        assert_eq!("1 {}", msg("{} {}", vec![value(1)]).to_string());
+        assert_eq!("12", msg("{}{}", vec![value(1), value(2)]).to_string());
+    }
+}
@@ -0,0 +1,182 @@
+use crate::error::{ReadError, UnexpectedEndOfSliceError, UnsupportedDltVersionError};
+use crate::*;
+
+/// Sets the length field in the dlt header at the start of `buf` to the
+/// length of `buf`.
+///
+/// Useful if payload was appended to a serialized header (e.g. when
+/// generating log messages) and the length field has to be fixed up
+/// afterwards. The whole buffer is treated as one packet, the current
+/// value of the length field is ignored.
+///
+/// An error is returned (& `buf` is left unchanged) if the buffer is
+/// smaller than the header described by the header type flags, the dlt
+/// version is not supported or the buffer is larger than the maximum
+/// length of a dlt packet (`u16::MAX`).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{normalize_length, DltHeader, DltPacketSlice};
+///
+/// let mut header = DltHeader {
+///     is_big_endian: true,
+///     message_counter: 0,
+///     length: 0,
+///     ecu_id: Some(*b"ECU1"),
+///     session_id: None,
+///     timestamp: None,
+///     extended_header: None,
+/// };
+/// let mut buffer = Vec::new();
+/// header.write(&mut buffer).unwrap();
+/// buffer.extend_from_slice(&[1, 2, 3, 4]);
+///
+/// normalize_length(&mut buffer).unwrap();
+/// let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+/// assert_eq!(&[1, 2, 3, 4], slice.payload());
+/// ```
+pub fn normalize_length(buf: &mut [u8]) -> Result<(), ReadError> {
+    if buf.len() < 4 {
+        return Err(ReadError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+            layer: error::Layer::DltHeader,
+            minimum_size: 4,
+            actual_size: buf.len(),
+        }));
+    }
+
+    let header_type = buf[0];
+    let version = (header_type >> 5) & MAX_VERSION;
+    if 0 != version && 1 != version {
+        return Err(ReadError::UnsupportedDltVersion(
+            UnsupportedDltVersionError {
+                unsupported_version: version,
+            },
+        ));
+    }
+
+    let header_len = [
+        (ECU_ID_FLAG, 4),
+        (SESSION_ID_FLAG, 4),
+        (TIMESTAMP_FLAG, 4),
+        (EXTDENDED_HEADER_FLAG, 10),
+    ]
+    .iter()
+    .filter(|(flag, _)| 0 != header_type & flag)
+    .fold(4, |len, (_, field_len)| len + field_len);
+
+    if buf.len() < header_len {
+        return Err(ReadError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+            layer: error::Layer::DltHeader,
+            minimum_size: header_len,
+            actual_size: buf.len(),
+        }));
+    }
+
+    let length = u16::try_from(buf.len())
+        .map_err(|_| ReadError::DltMessageLengthTooLarge(buf.len()))?
+        .to_be_bytes();
+    buf[2] = length[0];
+    buf[3] = length[1];
+    Ok(())
+}
+
+#[cfg(test)]
+mod normalize_length_tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use crate::test_packets::packet;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn append_and_normalize(
+            ref header in dlt_header_any(),
+            ref payload in proptest::collection::vec(any::<u8>(), 0..100),
+            ref appended in proptest::collection::vec(any::<u8>(), 0..100),
+        ) {
+            let mut buf = packet(header.clone(), payload);
+
+            // already correct length stays unchanged
+            let expected = buf.clone();
+            normalize_length(&mut buf).unwrap();
+            prop_assert_eq!(&expected, &buf);
+
+            // append payload
+            buf.extend_from_slice(appended);
+            normalize_length(&mut buf).unwrap();
+
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            prop_assert_eq!(buf.len(), slice.slice().len());
+            let mut expected_payload = payload.clone();
+            expected_payload.extend_from_slice(appended);
+            prop_assert_eq!(&expected_payload[..], slice.payload());
+
+            let mut expected_header = header.clone();
+            expected_header.length = buf.len() as u16;
+            prop_assert_eq!(expected_header, slice.header());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn header_too_small(ref header in dlt_header_any()) {
+            let mut buf = Vec::new();
+            header.write(&mut buf).unwrap();
+
+            for len in 0..buf.len() {
+                let mut data = buf[..len].to_vec();
+                let err = normalize_length(&mut data).unwrap_err();
+                let minimum_size = if len < 4 {
+                    4
+                } else {
+                    usize::from(header.header_len())
+                };
+                match err {
+                    ReadError::UnexpectedEndOfSlice(err) => {
+                        prop_assert_eq!(
+                            UnexpectedEndOfSliceError {
+                                layer: error::Layer::DltHeader,
+                                minimum_size,
+                                actual_size: len,
+                            },
+                            err
+                        );
+                    }
+                    err => prop_assert!(false, "unexpected error {:?}", err),
+                }
+                // buffer left unchanged
+                prop_assert_eq!(&buf[..len], &data[..]);
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        // unsupported version
+        let mut buf = [0b0100_0000, 0, 0, 0];
+        match normalize_length(&mut buf) {
+            Err(ReadError::UnsupportedDltVersion(err)) => {
+                assert_eq!(2, err.unsupported_version)
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!([0b0100_0000, 0, 0, 0], buf);
+
+        // too large
+        let mut buf = vec![0b0010_0000u8; usize::from(u16::MAX) + 1];
+        match normalize_length(&mut buf) {
+            Err(ReadError::DltMessageLengthTooLarge(len)) => {
+                assert_eq!(usize::from(u16::MAX) + 1, len)
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(&[0b0010_0000u8; 4], &buf[..4]);
+
+        // maximum length
+        buf.pop();
+        normalize_length(&mut buf).unwrap();
+        assert_eq!(&[0xff, 0xff], &buf[2..4]);
+    }
+}
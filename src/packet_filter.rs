@@ -0,0 +1,530 @@
+use crate::{error, DltMessageType, DltPacketSlice, SliceIterator};
+
+/// Decides if a dlt packet should be kept (e.g. when displaying or
+/// exporting messages).
+pub trait PacketFilter {
+    /// Returns true if the packet matches the filter.
+    fn matches(&self, packet: &DltPacketSlice<'_>) -> bool;
+}
+
+impl<T: PacketFilter + ?Sized> PacketFilter for &T {
+    #[inline]
+    fn matches(&self, packet: &DltPacketSlice<'_>) -> bool {
+        (**self).matches(packet)
+    }
+}
+
+/// Filter matching dlt packets based on their header fields.
+///
+/// All fields that are set must match (AND semantics). Fields set to
+/// `None` match any packet, so the default filter matches all packets.
+/// Application id, context id & message type can only match packets with
+/// an extended header.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{Filter, PacketFilter, SliceIterator};
+///
+/// let filter = Filter {
+///     ecu_id: Some(*b"ECU1"),
+///     application_id: Some(*b"APP1"),
+///     ..Default::default()
+/// };
+/// # let data: [u8; 0] = [];
+/// for packet in SliceIterator::new(&data).filter_packets(&filter) {
+///     assert!(filter.matches(&packet.unwrap()));
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Filter {
+    /// ECU id the packet must have.
+    pub ecu_id: Option<[u8; 4]>,
+    /// Application id the packet must have.
+    pub application_id: Option<[u8; 4]>,
+    /// Context id the packet must have.
+    pub context_id: Option<[u8; 4]>,
+    /// Message type the packet must have.
+    pub message_type: Option<DltMessageType>,
+    /// Value the verbose flag of the packet must have.
+    pub verbose: Option<bool>,
+}
+
+impl Filter {
+    /// Creates a builder for a filter with the default wildcard ids
+    /// (see [`FilterWildcards`]).
+    #[inline]
+    pub fn builder() -> FilterBuilder {
+        FilterBuilder::new()
+    }
+}
+
+/// Sentinel application & context ids that are treated as "match any"
+/// by the [`FilterBuilder`].
+///
+/// By default the dlt-daemon conventions all `0xFF` bytes (`u32::MAX`) &
+/// all spaces (`"    "`) are wildcards. All zero ids ("id not set") are
+/// not treated as wildcards by default, as messages can legitimately
+/// contain all zero ids & filtering for them is useful.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FilterWildcards {
+    /// Treat `[0xff, 0xff, 0xff, 0xff]` as wildcard.
+    pub all_ones: bool,
+    /// Treat `*b"    "` as wildcard.
+    pub all_spaces: bool,
+    /// Treat `[0, 0, 0, 0]` as wildcard.
+    pub all_zeros: bool,
+}
+
+impl FilterWildcards {
+    /// No id is treated as a wildcard.
+    pub const NONE: FilterWildcards = FilterWildcards {
+        all_ones: false,
+        all_spaces: false,
+        all_zeros: false,
+    };
+
+    /// Returns true if the given id is a wildcard.
+    #[inline]
+    pub fn is_wildcard(&self, id: [u8; 4]) -> bool {
+        (self.all_ones && id == [0xff; 4])
+            || (self.all_spaces && id == *b"    ")
+            || (self.all_zeros && id == [0; 4])
+    }
+}
+
+impl Default for FilterWildcards {
+    fn default() -> Self {
+        FilterWildcards {
+            all_ones: true,
+            all_spaces: true,
+            all_zeros: false,
+        }
+    }
+}
+
+/// Builder for [`Filter`] that maps wildcard application & context ids
+/// to "match any".
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::Filter;
+///
+/// // any context within the application "APP1"
+/// let filter = Filter::builder()
+///     .application_id(*b"APP1")
+///     .context_id([0xff; 4])
+///     .build();
+/// assert_eq!(Some(*b"APP1"), filter.application_id);
+/// assert_eq!(None, filter.context_id);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FilterBuilder {
+    filter: Filter,
+    wildcards: FilterWildcards,
+}
+
+impl FilterBuilder {
+    /// Creates a builder for a filter matching all packets that uses
+    /// the default wildcards.
+    #[inline]
+    pub fn new() -> FilterBuilder {
+        FilterBuilder::default()
+    }
+
+    /// Sets the ids treated as wildcards (only affects ids set after
+    /// this call).
+    pub fn wildcards(mut self, wildcards: FilterWildcards) -> Self {
+        self.wildcards = wildcards;
+        self
+    }
+
+    /// Sets the ecu id the packets must have.
+    pub fn ecu_id(mut self, ecu_id: [u8; 4]) -> Self {
+        self.filter.ecu_id = Some(ecu_id);
+        self
+    }
+
+    /// Sets the application id the packets must have (a wildcard id
+    /// matches any application id).
+    pub fn application_id(mut self, application_id: [u8; 4]) -> Self {
+        self.filter.application_id =
+            (false == self.wildcards.is_wildcard(application_id)).then(|| application_id);
+        self
+    }
+
+    /// Sets the context id the packets must have (a wildcard id matches
+    /// any context id).
+    pub fn context_id(mut self, context_id: [u8; 4]) -> Self {
+        self.filter.context_id =
+            (false == self.wildcards.is_wildcard(context_id)).then(|| context_id);
+        self
+    }
+
+    /// Sets the message type the packets must have.
+    pub fn message_type(mut self, message_type: DltMessageType) -> Self {
+        self.filter.message_type = Some(message_type);
+        self
+    }
+
+    /// Sets the value the verbose flag of the packets must have.
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.filter.verbose = Some(verbose);
+        self
+    }
+
+    /// Returns the filter.
+    #[inline]
+    pub fn build(self) -> Filter {
+        self.filter
+    }
+}
+
+impl PacketFilter for Filter {
+    fn matches(&self, packet: &DltPacketSlice<'_>) -> bool {
+        if self.ecu_id.is_some() && self.ecu_id != packet.ecu_id() {
+            return false;
+        }
+        if let Some(verbose) = self.verbose {
+            if verbose != packet.is_verbose() {
+                return false;
+            }
+        }
+        if self.message_type.is_some() && self.message_type != packet.message_type() {
+            return false;
+        }
+        if self.application_id.is_none() && self.context_id.is_none() {
+            return true;
+        }
+        match packet.extended_header() {
+            Some(ext) => {
+                self.application_id
+                    .map_or(true, |id| id == ext.application_id)
+                    && self.context_id.map_or(true, |id| id == ext.context_id)
+            }
+            None => false,
+        }
+    }
+}
+
+/// Iterator over the dlt packets in a slice that match a filter
+/// (see [`SliceIterator::filter_packets`]).
+///
+/// Errors are always passed through so decoding problems are not
+/// silently hidden by the filter.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FilteredSliceIterator<'a, F: PacketFilter> {
+    iter: SliceIterator<'a>,
+    filter: F,
+}
+
+impl<'a, F: PacketFilter> FilteredSliceIterator<'a, F> {
+    /// Creates an iterator only returning the packets in `slice`
+    /// matching `filter`.
+    #[inline]
+    pub fn new(slice: &'a [u8], filter: F) -> FilteredSliceIterator<'a, F> {
+        FilteredSliceIterator {
+            iter: SliceIterator::new(slice),
+            filter,
+        }
+    }
+
+    /// Returns the filter used by the iterator.
+    #[inline]
+    pub fn packet_filter(&self) -> &F {
+        &self.filter
+    }
+
+    /// Returns the slice of data still left in the iterator.
+    #[inline]
+    pub fn slice(&self) -> &'a [u8] {
+        self.iter.slice()
+    }
+}
+
+impl<'a, F: PacketFilter> Iterator for FilteredSliceIterator<'a, F> {
+    type Item = Result<DltPacketSlice<'a>, error::PacketSliceError>;
+
+    fn next(&mut self) -> Option<Result<DltPacketSlice<'a>, error::PacketSliceError>> {
+        for result in self.iter.by_ref() {
+            match result {
+                Ok(packet) => {
+                    if self.filter.matches(&packet) {
+                        return Some(Ok(packet));
+                    }
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        None
+    }
+}
+
+impl<'a> SliceIterator<'a> {
+    /// Converts the iterator into an iterator only returning the packets
+    /// matching the given filter (errors are still returned).
+    #[inline]
+    pub fn filter_packets<F: PacketFilter>(self, filter: F) -> FilteredSliceIterator<'a, F> {
+        FilteredSliceIterator { iter: self, filter }
+    }
+}
+
+#[cfg(test)]
+mod packet_filter_tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use crate::test_packets;
+    use crate::*;
+    use alloc::{format, vec::Vec};
+    use proptest::prelude::*;
+
+    /// Serializes a packet with the given ids & message type.
+    fn packet_bytes(
+        ecu_id: Option<[u8; 4]>,
+        ids: Option<([u8; 4], [u8; 4])>,
+        message_type: DltMessageType,
+    ) -> Vec<u8> {
+        let ext = ids
+            .map(|(app, ctx)| DltExtendedHeader::new_non_verbose(message_type, app, ctx).unwrap());
+        let header = DltHeader {
+            ecu_id,
+            ..test_packets::header(true, ext)
+        };
+        test_packets::packet(header, &[0, 0, 0, 1])
+    }
+
+    #[test]
+    fn debug_clone_eq_default() {
+        let filter = Filter::default();
+        assert_eq!(filter, filter.clone());
+        assert_eq!(
+            "Filter { ecu_id: None, application_id: None, context_id: None, message_type: None, verbose: None }",
+            format!("{:?}", filter)
+        );
+
+        let iter = SliceIterator::new(&[]).filter_packets(Filter::default());
+        assert_eq!(iter, iter.clone());
+        assert_eq!(&Filter::default(), iter.packet_filter());
+        assert!(iter.slice().is_empty());
+        assert_eq!(
+            format!(
+                "FilteredSliceIterator {{ iter: {:?}, filter: {:?} }}",
+                SliceIterator::new(&[]),
+                Filter::default()
+            ),
+            format!("{:?}", iter)
+        );
+    }
+
+    #[test]
+    fn matches() {
+        let log = DltMessageType::Log(DltLogLevel::Info);
+        let trace = DltMessageType::Trace(DltTraceType::State);
+        let full = packet_bytes(Some(*b"ECU1"), Some((*b"APP1", *b"CTX1")), log);
+        let full = DltPacketSlice::from_slice(&full).unwrap();
+        let no_ext = packet_bytes(Some(*b"ECU1"), None, log);
+        let no_ext = DltPacketSlice::from_slice(&no_ext).unwrap();
+        let no_ecu = packet_bytes(None, Some((*b"APP1", *b"CTX1")), trace);
+        let no_ecu = DltPacketSlice::from_slice(&no_ecu).unwrap();
+
+        for (filter, expected) in [
+            (Filter::default(), [true, true, true]),
+            (
+                Filter {
+                    ecu_id: Some(*b"ECU1"),
+                    ..Default::default()
+                },
+                [true, true, false],
+            ),
+            (
+                Filter {
+                    ecu_id: Some(*b"ECU2"),
+                    ..Default::default()
+                },
+                [false, false, false],
+            ),
+            (
+                Filter {
+                    application_id: Some(*b"APP1"),
+                    ..Default::default()
+                },
+                [true, false, true],
+            ),
+            (
+                Filter {
+                    context_id: Some(*b"CTX1"),
+                    ..Default::default()
+                },
+                [true, false, true],
+            ),
+            (
+                Filter {
+                    ecu_id: Some(*b"ECU1"),
+                    context_id: Some(*b"CTX2"),
+                    ..Default::default()
+                },
+                [false, false, false],
+            ),
+            (
+                Filter {
+                    message_type: Some(log),
+                    ..Default::default()
+                },
+                [true, false, false],
+            ),
+            (
+                Filter {
+                    verbose: Some(false),
+                    ..Default::default()
+                },
+                [true, true, true],
+            ),
+            (
+                Filter {
+                    verbose: Some(true),
+                    ..Default::default()
+                },
+                [false, false, false],
+            ),
+        ] {
+            assert_eq!(expected[0], filter.matches(&full), "{:?}", filter);
+            assert_eq!(expected[1], filter.matches(&no_ext), "{:?}", filter);
+            assert_eq!(expected[2], filter.matches(&no_ecu), "{:?}", filter);
+            assert_eq!(expected[0], filter.matches(&full), "{:?}", filter);
+        }
+    }
+
+    #[test]
+    fn wildcards() {
+        let default = FilterWildcards::default();
+        assert!(default.is_wildcard([0xff; 4]));
+        assert!(default.is_wildcard(*b"    "));
+        assert!(false == default.is_wildcard([0; 4]));
+        assert!(false == default.is_wildcard(*b"APP1"));
+        assert!(false == default.is_wildcard([0xff, 0xff, 0xff, 0]));
+
+        for id in [[0xff; 4], *b"    ", [0; 4], *b"APP1"] {
+            assert!(false == FilterWildcards::NONE.is_wildcard(id));
+        }
+
+        let zeros = FilterWildcards {
+            all_ones: false,
+            all_spaces: false,
+            all_zeros: true,
+        };
+        assert!(zeros.is_wildcard([0; 4]));
+        assert!(false == zeros.is_wildcard([0xff; 4]));
+        assert_eq!(zeros, zeros.clone());
+    }
+
+    #[test]
+    fn builder() {
+        let log = DltMessageType::Log(DltLogLevel::Warn);
+
+        // exact ids
+        assert_eq!(
+            Filter {
+                ecu_id: Some(*b"ECU1"),
+                application_id: Some(*b"APP1"),
+                context_id: Some(*b"CTX1"),
+                message_type: Some(log),
+                verbose: Some(true),
+            },
+            Filter::builder()
+                .ecu_id(*b"ECU1")
+                .application_id(*b"APP1")
+                .context_id(*b"CTX1")
+                .message_type(log)
+                .verbose(true)
+                .build()
+        );
+        assert_eq!(Filter::default(), FilterBuilder::new().build());
+
+        // wildcard & exact combinations
+        for (app, ctx, expected) in [
+            ([0xff; 4], *b"CTX1", (None, Some(*b"CTX1"))),
+            (*b"APP1", [0xff; 4], (Some(*b"APP1"), None)),
+            (*b"    ", *b"    ", (None, None)),
+            ([0; 4], [0; 4], (Some([0; 4]), Some([0; 4]))),
+        ] {
+            let filter = Filter::builder()
+                .application_id(app)
+                .context_id(ctx)
+                .build();
+            assert_eq!(expected, (filter.application_id, filter.context_id));
+        }
+
+        // configured wildcards
+        let filter = Filter::builder()
+            .wildcards(FilterWildcards::NONE)
+            .application_id([0xff; 4])
+            .build();
+        assert_eq!(Some([0xff; 4]), filter.application_id);
+        let filter = Filter::builder()
+            .wildcards(FilterWildcards {
+                all_zeros: true,
+                ..Default::default()
+            })
+            .application_id([0; 4])
+            .context_id(*b"CTX1")
+            .build();
+        assert_eq!(None, filter.application_id);
+        assert_eq!(Some(*b"CTX1"), filter.context_id);
+
+        // any context within an application
+        let ctx1 = packet_bytes(None, Some((*b"APP1", *b"CTX1")), log);
+        let ctx2 = packet_bytes(None, Some((*b"APP1", *b"CTX2")), log);
+        let other = packet_bytes(None, Some((*b"APP2", *b"CTX1")), log);
+        let filter = Filter::builder()
+            .application_id(*b"APP1")
+            .context_id([0xff; 4])
+            .build();
+        assert!(filter.matches(&DltPacketSlice::from_slice(&ctx1).unwrap()));
+        assert!(filter.matches(&DltPacketSlice::from_slice(&ctx2).unwrap()));
+        assert!(false == filter.matches(&DltPacketSlice::from_slice(&other).unwrap()));
+
+        let builder = Filter::builder();
+        assert_eq!(builder, builder.clone());
+        assert!(format!("{:?}", builder).starts_with("FilterBuilder {"));
+    }
+
+    proptest! {
+        #[test]
+        fn filter_packets(
+            ref packets in prop::collection::vec(dlt_header_with_payload_any(), 0..8),
+            ecu_id in any::<[u8; 4]>(),
+        ) {
+            let mut buffer = Vec::new();
+            for (header, payload) in packets {
+                buffer.extend_from_slice(&header.to_bytes());
+                buffer.extend_from_slice(payload);
+            }
+            let filter = Filter {
+                ecu_id: Some(ecu_id),
+                ..Default::default()
+            };
+            let expected: Vec<_> = SliceIterator::new(&buffer)
+                .filter(|p| p.as_ref().map(|p| p.ecu_id() == Some(ecu_id)).unwrap_or(true))
+                .collect();
+            let actual: Vec<_> = SliceIterator::new(&buffer).filter_packets(&filter).collect();
+            prop_assert_eq!(&expected, &actual);
+            prop_assert_eq!(
+                expected,
+                FilteredSliceIterator::new(&buffer, filter).collect::<Vec<_>>()
+            );
+
+            // errors are passed through
+            if false == buffer.is_empty() {
+                let len = buffer.len() - 1;
+                let last = SliceIterator::new(&buffer[..len])
+                    .filter_packets(Filter {
+                        ecu_id: Some(ecu_id),
+                        ..Default::default()
+                    })
+                    .last();
+                prop_assert!(matches!(last, Some(Err(_))));
+            }
+        }
+    }
+}
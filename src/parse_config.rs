@@ -0,0 +1,378 @@
+/// Configuration of the checks done when decoding DLT packets & verbose
+/// values.
+///
+/// The spec & the data sent by real ECUs do not always match. Each toggle
+/// allows one specific deviation from the spec instead of returning an
+/// error. The configuration can be passed to
+/// [`crate::DltPacketSlice::from_slice_with_config`],
+/// [`crate::SliceIterator::with_config`],
+/// [`crate::verbose::VerboseValue::from_slice_with_config`] &
+/// [`crate::verbose::VerboseIter::with_config`].
+///
+/// [`ParseConfig::default`] matches the behavior of the functions without
+/// a configuration (e.g. [`crate::DltPacketSlice::from_slice`]).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{DltPacketSlice, ParseConfig};
+///
+/// // packet with a length field of 16 but only 14 bytes of data
+/// let data = [
+///     0b0010_0001, 0, 0, 16, // header with extended header flag
+///     0b0100_0000, 0, // message info (log info)
+///     b'A', b'P', b'P', b'1', b'C', b'T', b'X', b'1',
+/// ];
+/// assert!(DltPacketSlice::from_slice_with_config(&data, &ParseConfig::strict()).is_err());
+///
+/// let packet = DltPacketSlice::from_slice_with_config(&data, &ParseConfig::lenient()).unwrap();
+/// assert_eq!(&data[..], packet.slice());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ParseConfig {
+    /// Accept packets with a DLT version other than 0 & 1 (the remaining
+    /// header is decoded as version 1).
+    pub allow_unknown_versions: bool,
+
+    /// Accept packets where the length field is bigger than the available
+    /// data (the packet then contains all the remaining data). The header
+    /// still has to be complete.
+    pub allow_truncated_packets: bool,
+
+    /// Accept reserved values in the message info of the extended header
+    /// (see [`crate::DltExtendedHeader::has_reserved_bits_set`]).
+    pub allow_reserved_message_info: bool,
+
+    /// Accept variable names & units where the last byte is not a
+    /// terminating zero (the last byte is then part of the string).
+    ///
+    /// Additionally the last byte of string & trace info values is kept
+    /// if it is not zero (otherwise it is always skipped as terminator).
+    pub allow_missing_string_termination: bool,
+
+    /// Only accept 0 & 1 as bool values (otherwise all non zero values
+    /// are decoded as `true`).
+    pub strict_bool: bool,
+
+    /// Reject string & trace info values containing a zero byte before
+    /// the terminating zero with [`crate::error::VerboseDecodeError::EmbeddedNull`]
+    /// (e.g. for values that get passed to C APIs).
+    pub reject_embedded_null: bool,
+}
+
+impl ParseConfig {
+    /// Configuration rejecting all deviations from the spec.
+    pub const fn strict() -> ParseConfig {
+        ParseConfig {
+            allow_unknown_versions: false,
+            allow_truncated_packets: false,
+            allow_reserved_message_info: false,
+            allow_missing_string_termination: false,
+            strict_bool: true,
+            reject_embedded_null: true,
+        }
+    }
+
+    /// Configuration accepting all supported deviations from the spec.
+    pub const fn lenient() -> ParseConfig {
+        ParseConfig {
+            allow_unknown_versions: true,
+            allow_truncated_packets: true,
+            allow_reserved_message_info: true,
+            allow_missing_string_termination: true,
+            strict_bool: false,
+            reject_embedded_null: false,
+        }
+    }
+}
+
+impl Default for ParseConfig {
+    /// Returns the configuration used by the functions without a
+    /// configuration (strict except for reserved message info values &
+    /// embedded zeros in strings).
+    fn default() -> ParseConfig {
+        ParseConfig {
+            allow_reserved_message_info: true,
+            reject_embedded_null: false,
+            ..ParseConfig::strict()
+        }
+    }
+}
+
+#[cfg(test)]
+mod parse_config_tests {
+    use super::*;
+    use crate::verbose::{VerboseIter, VerboseValue};
+    use crate::{error::PacketSliceError, DltPacketSlice, SliceIterator};
+
+    /// Packet with an extended header & the given message info.
+    fn packet(header_type: u8, message_info: u8) -> [u8; 14] {
+        [
+            header_type | 0b1,
+            0,
+            0,
+            14,
+            message_info,
+            0,
+            b'A',
+            b'P',
+            b'P',
+            b'1',
+            b'C',
+            b'T',
+            b'X',
+            b'1',
+        ]
+    }
+
+    /// Log info message info.
+    const LOG_INFO: u8 = 0b0100_0000;
+
+    #[test]
+    fn presets() {
+        let strict = ParseConfig::strict();
+        assert!(false == strict.allow_unknown_versions);
+        assert!(false == strict.allow_truncated_packets);
+        assert!(false == strict.allow_reserved_message_info);
+        assert!(false == strict.allow_missing_string_termination);
+        assert!(strict.strict_bool);
+        assert!(strict.reject_embedded_null);
+
+        let lenient = ParseConfig::lenient();
+        assert!(lenient.allow_unknown_versions);
+        assert!(lenient.allow_truncated_packets);
+        assert!(lenient.allow_reserved_message_info);
+        assert!(lenient.allow_missing_string_termination);
+        assert!(false == lenient.strict_bool);
+        assert!(false == lenient.reject_embedded_null);
+
+        assert_eq!(
+            ParseConfig {
+                allow_reserved_message_info: true,
+                reject_embedded_null: false,
+                ..ParseConfig::strict()
+            },
+            ParseConfig::default()
+        );
+    }
+
+    #[test]
+    fn unknown_versions() {
+        let data = packet(0b0100_0000, LOG_INFO);
+        let allowed = ParseConfig {
+            allow_unknown_versions: true,
+            ..ParseConfig::strict()
+        };
+        for config in [ParseConfig::strict(), ParseConfig::default()] {
+            let result = DltPacketSlice::from_slice_with_config(&data, &config);
+            let is_version_err = matches!(result, Err(PacketSliceError::UnsupportedDltVersion(_)));
+            assert!(is_version_err);
+        }
+        for config in [allowed, ParseConfig::lenient()] {
+            let packet = DltPacketSlice::from_slice_with_config(&data, &config).unwrap();
+            assert_eq!(&data[..], packet.slice());
+        }
+    }
+
+    #[test]
+    fn truncated_packets() {
+        let mut data = packet(0b0010_0000, LOG_INFO);
+        // length field 2 bytes bigger then the data
+        data[3] = 16;
+        let allowed = ParseConfig {
+            allow_truncated_packets: true,
+            ..ParseConfig::strict()
+        };
+        for config in [ParseConfig::strict(), ParseConfig::default()] {
+            assert!(DltPacketSlice::from_slice_with_config(&data, &config).is_err());
+        }
+        for config in [allowed, ParseConfig::lenient()] {
+            let packet = DltPacketSlice::from_slice_with_config(&data, &config).unwrap();
+            assert_eq!(&data[..], packet.slice());
+            assert_eq!(&[] as &[u8], packet.payload());
+
+            // header still has to be complete
+            for len in 4..data.len() {
+                use crate::error::{Layer, UnexpectedEndOfSliceError};
+                assert_eq!(
+                    Err(PacketSliceError::UnexpectedEndOfSlice(
+                        UnexpectedEndOfSliceError {
+                            layer: Layer::DltHeader,
+                            minimum_size: data.len(),
+                            actual_size: len,
+                        }
+                    )),
+                    DltPacketSlice::from_slice_with_config(&data[..len], &config)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reserved_message_info() {
+        // reserved message type (MSTP 4)
+        let data = packet(0b0010_0000, 4 << 1);
+        let allowed = ParseConfig {
+            allow_reserved_message_info: true,
+            ..ParseConfig::strict()
+        };
+        assert_eq!(
+            Err(PacketSliceError::ReservedMessageInfo(4 << 1)),
+            DltPacketSlice::from_slice_with_config(&data, &ParseConfig::strict())
+        );
+        for config in [allowed, ParseConfig::default(), ParseConfig::lenient()] {
+            let packet = DltPacketSlice::from_slice_with_config(&data, &config).unwrap();
+            assert_eq!(&data[..], packet.slice());
+        }
+    }
+
+    #[test]
+    fn missing_string_termination() {
+        let allowed = ParseConfig {
+            allow_missing_string_termination: true,
+            ..ParseConfig::strict()
+        };
+
+        // bool with a variable name without terminating zero
+        let data = [0x11, 0x08, 0, 0, 2, 0, b'a', b'b', 1];
+        for config in [ParseConfig::strict(), ParseConfig::default()] {
+            assert!(VerboseValue::from_slice_with_config(&data, false, &config).is_err());
+        }
+        for config in [allowed, ParseConfig::lenient()] {
+            let (value, rest) =
+                VerboseValue::from_slice_with_config(&data, false, &config).unwrap();
+            let is_expected =
+                matches!(value, VerboseValue::Bool(b) if b.value && b.name == Some("ab"));
+            assert!(is_expected);
+            assert!(rest.is_empty());
+        }
+
+        // u8 with a unit without terminating zero
+        let data = [0x41, 0x08, 0, 0, 1, 0, 2, 0, 0, b'm', b's', 7];
+        for config in [ParseConfig::strict(), ParseConfig::default()] {
+            assert!(VerboseValue::from_slice_with_config(&data, false, &config).is_err());
+        }
+        for config in [allowed, ParseConfig::lenient()] {
+            let (value, _) = VerboseValue::from_slice_with_config(&data, false, &config).unwrap();
+            let is_expected = matches!(value, VerboseValue::U8(v) if v.value == 7 && v.variable_info.as_ref().map(|i| i.unit) == Some("ms"));
+            assert!(is_expected);
+        }
+
+        // string value without terminating zero
+        let data = [0, 0x02, 0, 0, 3, 0, b'a', b'b', b'c'];
+        for config in [ParseConfig::strict(), ParseConfig::default()] {
+            let (value, _) = VerboseValue::from_slice_with_config(&data, false, &config).unwrap();
+            let is_expected = matches!(value, VerboseValue::Str(s) if s.value == "ab");
+            assert!(is_expected);
+        }
+        for config in [allowed, ParseConfig::lenient()] {
+            let (value, _) = VerboseValue::from_slice_with_config(&data, false, &config).unwrap();
+            let is_expected = matches!(value, VerboseValue::Str(s) if s.value == "abc");
+            assert!(is_expected);
+        }
+
+        // terminated strings are identical in all configs
+        let data = [0, 0x02, 0, 0, 3, 0, b'a', b'b', 0];
+        for config in [ParseConfig::strict(), ParseConfig::lenient()] {
+            let (value, _) = VerboseValue::from_slice_with_config(&data, false, &config).unwrap();
+            let is_expected = matches!(value, VerboseValue::Str(s) if s.value == "ab");
+            assert!(is_expected);
+        }
+    }
+
+    #[test]
+    fn strict_bool() {
+        let data = [0x11, 0, 0, 0, 2];
+        let relaxed = ParseConfig {
+            strict_bool: false,
+            ..ParseConfig::strict()
+        };
+        for config in [ParseConfig::strict(), ParseConfig::default()] {
+            assert!(VerboseValue::from_slice_with_config(&data, false, &config).is_err());
+        }
+        for config in [relaxed, ParseConfig::lenient()] {
+            let (value, _) = VerboseValue::from_slice_with_config(&data, false, &config).unwrap();
+            let is_true = matches!(value, VerboseValue::Bool(b) if b.value);
+            assert!(is_true);
+        }
+    }
+
+    #[test]
+    fn embedded_null() {
+        use crate::error::VerboseDecodeError;
+
+        let rejected = ParseConfig {
+            reject_embedded_null: true,
+            ..ParseConfig::lenient()
+        };
+
+        // string & trace info value with a zero in the middle
+        for type_info_1 in [0x02, 0x20] {
+            let data = [0, type_info_1, 0, 0, 4, 0, b'a', 0, b'b', 0];
+            for config in [ParseConfig::default(), ParseConfig::lenient()] {
+                let (value, rest) =
+                    VerboseValue::from_slice_with_config(&data, false, &config).unwrap();
+                let is_expected = match value {
+                    VerboseValue::Str(s) => s.value == "a\0b",
+                    VerboseValue::TraceInfo(t) => t.value == "a\0b",
+                    _ => false,
+                };
+                assert!(is_expected);
+                assert!(rest.is_empty());
+            }
+            for config in [rejected, ParseConfig::strict()] {
+                assert_eq!(
+                    Err(VerboseDecodeError::EmbeddedNull { position: 1 }),
+                    VerboseValue::from_slice_with_config(&data, false, &config)
+                );
+            }
+
+            // only the terminating zero
+            let data = [0, type_info_1, 0, 0, 3, 0, b'a', b'b', 0];
+            assert!(VerboseValue::from_slice_with_config(&data, false, &rejected).is_ok());
+
+            // zero only byte
+            let data = [0, type_info_1, 0, 0, 2, 0, 0, 0];
+            assert_eq!(
+                Err(VerboseDecodeError::EmbeddedNull { position: 0 }),
+                VerboseValue::from_slice_with_config(&data, false, &rejected)
+            );
+
+            // unterminated string with a zero in the middle
+            let data = [0, type_info_1, 0, 0, 3, 0, b'a', 0, b'b'];
+            assert_eq!(
+                Err(VerboseDecodeError::EmbeddedNull { position: 1 }),
+                VerboseValue::from_slice_with_config(&data, false, &rejected)
+            );
+        }
+    }
+
+    #[test]
+    fn slice_iterator() {
+        let data = packet(0b0100_0000, LOG_INFO);
+
+        let mut iter = SliceIterator::new(&data);
+        assert_eq!(&ParseConfig::default(), iter.config());
+        assert!(iter.next().unwrap().is_err());
+
+        let mut iter = SliceIterator::new(&data).with_config(ParseConfig::lenient());
+        assert_eq!(&ParseConfig::lenient(), iter.config());
+        assert_eq!(&data[..], iter.next().unwrap().unwrap().slice());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn verbose_iter() {
+        let data = [0x11, 0, 0, 0, 2];
+
+        let mut iter = VerboseIter::new(false, 1, &data);
+        assert_eq!(&ParseConfig::default(), iter.config());
+        assert!(iter.next().unwrap().is_err());
+
+        let mut iter = VerboseIter::new(false, 1, &data).with_config(ParseConfig::lenient());
+        assert_eq!(&ParseConfig::lenient(), iter.config());
+        let is_true = matches!(iter.next(), Some(Ok(VerboseValue::Bool(b))) if b.value);
+        assert!(is_true);
+        assert!(iter.next().is_none());
+    }
+}
@@ -13,9 +13,9 @@ prop_compose! {
     {
         DltExtendedHeader {
             message_info: DltMessageInfo(message_info),
-            number_of_arguments: number_of_arguments,
-            application_id: application_id,
-            context_id: context_id
+            number_of_arguments,
+            application_id,
+            context_id
         }
     }
 }
@@ -135,5 +135,6 @@ pub fn message_type_any() -> impl Strategy<Value = DltMessageType> {
         Just(NetworkTrace(UserDefined(0xF))),
         Just(Control(Request)),
         Just(Control(Response)),
+        Just(Control(Time)),
     ]
 }
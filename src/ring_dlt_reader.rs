@@ -0,0 +1,374 @@
+use crate::{error::PacketSliceError, DltPacketSlice};
+use core::ops::Range;
+use std::collections::VecDeque;
+use std::vec;
+use std::vec::Vec;
+
+/// Ring buffer keeping the most recent dlt packets in a fixed amount of
+/// memory (e.g. for live tailing a log stream).
+///
+/// The packets are copied into a backing buffer of `byte_capacity` bytes
+/// that is allocated once on creation. Each packet is stored contiguously:
+/// if a packet does not fit between the end of the newest packet & the
+/// end of the buffer, it is written to the start of the buffer instead
+/// (the unused bytes at the end are skipped).
+///
+/// # Eviction policy
+///
+/// When a packet is pushed the oldest packets are dropped until
+///
+/// * less than `max_messages` packets are retained &
+/// * enough contiguous space is free for the new packet.
+///
+/// Packets are always dropped in the order they were pushed, so the
+/// retained packets are always the most recent ones. Packets larger than
+/// `byte_capacity` are not stored at all (& do not evict any packets).
+/// As dlt packets are at most `u16::MAX` bytes long, a byte capacity of
+/// at least `u16::MAX` guarantees that every packet can be stored.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{DltHeader, RingDltReader};
+///
+/// let mut reader = RingDltReader::new(2, 1024);
+/// for counter in 0..5 {
+///     let mut header = DltHeader::new_non_verbose(*b"ECU1");
+///     header.message_counter = counter;
+///     reader.push_slice(&header.to_bytes()).unwrap();
+/// }
+///
+/// // only the last two messages are retained
+/// let counters: Vec<_> = reader.iter().map(|p| p.header().message_counter).collect();
+/// assert_eq!(vec![3, 4], counters);
+/// assert_eq!(3, reader.evicted());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RingDltReader {
+    data: Vec<u8>,
+    ranges: VecDeque<Range<usize>>,
+    max_messages: usize,
+    write_pos: usize,
+    evicted: u64,
+}
+
+impl RingDltReader {
+    /// Creates a ring buffer retaining at most `max_messages` packets with a
+    /// backing buffer of `byte_capacity` bytes.
+    pub fn new(max_messages: usize, byte_capacity: usize) -> RingDltReader {
+        RingDltReader {
+            data: vec![0; byte_capacity],
+            ranges: VecDeque::with_capacity(max_messages.min(byte_capacity / 4)),
+            max_messages,
+            write_pos: 0,
+            evicted: 0,
+        }
+    }
+
+    /// Copies the packet into the ring buffer (dropping the oldest packets
+    /// if needed, see the eviction policy of [`RingDltReader`]).
+    ///
+    /// Returns false if the packet was not stored because it is larger
+    /// than the byte capacity or the maximum number of messages is zero.
+    pub fn push(&mut self, packet: &DltPacketSlice<'_>) -> bool {
+        let slice = packet.slice();
+        let len = slice.len();
+        if len > self.data.len() || 0 == self.max_messages {
+            return false;
+        }
+
+        while self.ranges.len() >= self.max_messages {
+            self.evict_oldest();
+        }
+
+        let start = loop {
+            let head = match self.ranges.front() {
+                Some(oldest) => oldest.start,
+                None => {
+                    // restart at the beginning if the buffer is empty
+                    self.write_pos = 0;
+                    break 0;
+                }
+            };
+            if head >= self.write_pos {
+                // free: write_pos..head
+                if self.write_pos + len <= head {
+                    break self.write_pos;
+                }
+            } else {
+                // free: write_pos..end & 0..head
+                if self.write_pos + len <= self.data.len() {
+                    break self.write_pos;
+                }
+                if len <= head {
+                    break 0;
+                }
+            }
+            self.evict_oldest();
+        };
+
+        self.data[start..start + len].copy_from_slice(slice);
+        self.ranges.push_back(start..start + len);
+        self.write_pos = start + len;
+        true
+    }
+
+    /// Decodes the dlt packet at the start of the given slice & copies it
+    /// into the ring buffer (data after the packet is ignored).
+    ///
+    /// Returns false if the packet was not stored (see [`RingDltReader::push`]).
+    pub fn push_slice(&mut self, slice: &[u8]) -> Result<bool, PacketSliceError> {
+        Ok(self.push(&DltPacketSlice::from_slice(slice)?))
+    }
+
+    /// Number of retained packets.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns true if no packets are retained.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Maximum number of retained packets.
+    #[inline]
+    pub fn max_messages(&self) -> usize {
+        self.max_messages
+    }
+
+    /// Size of the backing buffer in bytes.
+    #[inline]
+    pub fn byte_capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Number of packets dropped so far to make room for newer packets.
+    #[inline]
+    pub fn evicted(&self) -> u64 {
+        self.evicted
+    }
+
+    /// Returns the retained packet with the given index (0 is the oldest
+    /// retained packet).
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<DltPacketSlice<'_>> {
+        self.ranges
+            .get(index)
+            .and_then(|range| self.packet(range.clone()))
+    }
+
+    /// Returns an iterator over the retained packets from the oldest to
+    /// the newest.
+    #[inline]
+    pub fn iter(&self) -> RingDltReaderIter<'_> {
+        RingDltReaderIter {
+            reader: self,
+            ranges: self.ranges.iter(),
+        }
+    }
+
+    /// Removes all packets (the evicted counter & the backing buffer are
+    /// kept).
+    #[inline]
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+        self.write_pos = 0;
+    }
+
+    fn evict_oldest(&mut self) {
+        if self.ranges.pop_front().is_some() {
+            self.evicted += 1;
+        }
+    }
+
+    fn packet(&self, range: Range<usize>) -> Option<DltPacketSlice<'_>> {
+        // the packets were validated when they were added, so decoding
+        // them again can not fail
+        DltPacketSlice::from_slice(self.data.get(range)?).ok()
+    }
+}
+
+impl<'a> IntoIterator for &'a RingDltReader {
+    type Item = DltPacketSlice<'a>;
+    type IntoIter = RingDltReaderIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> RingDltReaderIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the packets retained in a [`RingDltReader`] (from the
+/// oldest to the newest).
+#[derive(Clone, Debug)]
+pub struct RingDltReaderIter<'a> {
+    reader: &'a RingDltReader,
+    ranges: std::collections::vec_deque::Iter<'a, Range<usize>>,
+}
+
+impl<'a> Iterator for RingDltReaderIter<'a> {
+    type Item = DltPacketSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<DltPacketSlice<'a>> {
+        let range = self.ranges.next()?;
+        self.reader.packet(range.clone())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ranges.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for RingDltReaderIter<'a> {}
+
+#[cfg(test)]
+mod ring_dlt_reader_tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use crate::test_packets;
+    use crate::DltHeader;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    /// Returns a non verbose packet with the given counter & payload length.
+    fn packet(counter: u8, payload_len: usize) -> Vec<u8> {
+        let mut header = DltHeader::new_non_verbose(*b"ECU1");
+        header.message_counter = counter;
+        test_packets::packet(header, &vec![counter; payload_len])
+    }
+
+    fn counters(reader: &RingDltReader) -> Vec<u8> {
+        reader.iter().map(|p| p.header().message_counter).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn push_iter(
+            ref packets in prop::collection::vec(dlt_header_with_payload_any(), 0..20),
+            max_messages in 0usize..6,
+            byte_capacity in 0usize..400,
+        ) {
+            let mut reader = RingDltReader::new(max_messages, byte_capacity);
+            let mut stored: Vec<Vec<u8>> = Vec::new();
+            for (header, payload) in packets {
+                let mut bytes = header.to_bytes().to_vec();
+                bytes.extend_from_slice(payload);
+
+                let expected_stored = bytes.len() <= byte_capacity && max_messages > 0;
+                prop_assert_eq!(expected_stored, reader.push_slice(&bytes).unwrap());
+                if expected_stored {
+                    stored.push(bytes.clone());
+                }
+
+                // the retained packets are the most recent stored packets
+                prop_assert!(reader.len() <= max_messages);
+                let retained: Vec<Vec<u8>> = reader.iter().map(|p| p.slice().to_vec()).collect();
+                prop_assert_eq!(&stored[stored.len() - retained.len()..], &retained[..]);
+                prop_assert_eq!(
+                    (stored.len() - retained.len()) as u64,
+                    reader.evicted()
+                );
+                prop_assert!(retained.iter().map(|r| r.len()).sum::<usize>() <= byte_capacity);
+                if expected_stored {
+                    prop_assert_eq!(Some(&bytes), retained.last());
+                }
+
+                // get & iter are consistent
+                prop_assert_eq!(retained.len(), reader.iter().len());
+                for (index, r) in retained.iter().enumerate() {
+                    prop_assert_eq!(&r[..], reader.get(index).unwrap().slice());
+                }
+                prop_assert!(reader.get(retained.len()).is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn evict_by_count() {
+        let mut reader = RingDltReader::new(3, 1024);
+        assert!(reader.is_empty());
+        for counter in 0..5 {
+            assert_eq!(Ok(true), reader.push_slice(&packet(counter, 4)));
+        }
+        assert_eq!(vec![2, 3, 4], counters(&reader));
+        assert_eq!(3, reader.len());
+        assert_eq!(2, reader.evicted());
+        assert_eq!(3, reader.max_messages());
+        assert_eq!(1024, reader.byte_capacity());
+    }
+
+    #[test]
+    fn evict_by_bytes() {
+        // packets of 20 bytes (8 header & 12 payload)
+        let mut reader = RingDltReader::new(100, 50);
+        for counter in 0..2 {
+            assert_eq!(Ok(true), reader.push_slice(&packet(counter, 12)));
+        }
+        assert_eq!(vec![0, 1], counters(&reader));
+
+        // does not fit at the end (40..60) -> wraps to the start & evicts
+        // the first packet
+        assert_eq!(Ok(true), reader.push_slice(&packet(2, 12)));
+        assert_eq!(vec![1, 2], counters(&reader));
+
+        // no free space between the wrapped packet (0..20) & the oldest
+        // packet (20..40) -> packet 1 is evicted
+        assert_eq!(Ok(true), reader.push_slice(&packet(3, 12)));
+        assert_eq!(vec![2, 3], counters(&reader));
+
+        // bigger packet evicts multiple packets
+        assert_eq!(Ok(true), reader.push_slice(&packet(4, 42)));
+        assert_eq!(vec![4], counters(&reader));
+        assert_eq!(4, reader.evicted());
+
+        // packet bigger than the capacity is not stored & evicts nothing
+        assert_eq!(Ok(false), reader.push_slice(&packet(5, 43)));
+        assert_eq!(vec![4], counters(&reader));
+        assert_eq!(4, reader.evicted());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut reader = RingDltReader::new(0, 1024);
+        assert_eq!(Ok(false), reader.push_slice(&packet(0, 4)));
+        assert!(reader.is_empty());
+
+        let mut reader = RingDltReader::new(10, 0);
+        assert_eq!(Ok(false), reader.push_slice(&packet(0, 4)));
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn push_slice_error() {
+        let mut reader = RingDltReader::new(10, 1024);
+        assert!(reader.push_slice(&[1, 2]).is_err());
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn clear_into_iter_debug() {
+        let mut reader = RingDltReader::new(2, 1024);
+        for counter in 0..3 {
+            reader.push_slice(&packet(counter, 4)).unwrap();
+        }
+        let collected: Vec<_> = (&reader)
+            .into_iter()
+            .map(|p| p.header().message_counter)
+            .collect();
+        assert_eq!(vec![1, 2], collected);
+        assert_eq!(reader, reader.clone());
+        assert!(format!("{:?}", reader.iter()).starts_with("RingDltReaderIter {"));
+
+        reader.clear();
+        assert!(reader.is_empty());
+        assert_eq!(1, reader.evicted());
+        assert!(reader.iter().next().is_none());
+        reader.push_slice(&packet(7, 4)).unwrap();
+        assert_eq!(vec![7], counters(&reader));
+    }
+}
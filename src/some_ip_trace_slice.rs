@@ -0,0 +1,321 @@
+use crate::error::{Layer, SomeIpTraceError, UnexpectedEndOfSliceError};
+use crate::*;
+
+/// Serialized length of the SOME/IP header in bytes.
+const HEADER_LEN: usize = 16;
+
+/// Number of SOME/IP header bytes included in the length field.
+const LEN_OFFSET: u32 = 8;
+
+/// SOME/IP message contained in the payload block of a network trace
+/// message with the network type [`DltNetworkType::SomeIp`] or
+/// [`DltNetworkType::Ethernet`].
+///
+/// The SOME/IP message is expected to start at the beginning of the
+/// payload block. Bytes after the length given in the SOME/IP header
+/// (e.g. further SOME/IP messages in the same datagram) are not part of
+/// the slice.
+///
+/// The header can be decoded via `SomeIpTraceSlice::header` if the
+/// `someip` feature is enabled (uses the `someip_parse` crate).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{DltPacketSlice, SomeIpTraceSlice};
+///
+/// let bytes = include_bytes!("../tests/fixtures/someip_trace_request.dlt");
+/// let packet = DltPacketSlice::from_slice(bytes).unwrap();
+///
+/// let someip = SomeIpTraceSlice::from_packet(&packet).unwrap();
+/// assert_eq!(&[0xde, 0xad, 0xbe, 0xef], someip.payload());
+///
+/// # #[cfg(feature = "someip")]
+/// # {
+/// let header = someip.header().unwrap();
+/// assert_eq!(0x1234, header.service_id());
+/// assert_eq!(0x0001, header.event_or_method_id());
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SomeIpTraceSlice<'a> {
+    slice: &'a [u8],
+}
+
+impl<'a> SomeIpTraceSlice<'a> {
+    /// Extracts the SOME/IP message from a network trace packet.
+    pub fn from_packet(
+        packet: &DltPacketSlice<'a>,
+    ) -> Result<SomeIpTraceSlice<'a>, SomeIpTraceError> {
+        SomeIpTraceSlice::from_trace(&NetworkTraceSlice::from_packet(packet)?)
+    }
+
+    /// Extracts the SOME/IP message from the payload block of a network
+    /// trace.
+    pub fn from_trace(
+        trace: &NetworkTraceSlice<'a>,
+    ) -> Result<SomeIpTraceSlice<'a>, SomeIpTraceError> {
+        use SomeIpTraceError::*;
+
+        match trace.net_type() {
+            DltNetworkType::SomeIp | DltNetworkType::Ethernet => {}
+            other => return Err(NotASomeIpTrace(other)),
+        }
+
+        let payload = trace.payload();
+        if payload.len() < HEADER_LEN {
+            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer: Layer::SomeIpHeader,
+                minimum_size: HEADER_LEN,
+                actual_size: payload.len(),
+            }));
+        }
+
+        let length = u32::from_be_bytes([payload[4], payload[5], payload[6], payload[7]]);
+        // the length field includes the last 8 bytes of the header
+        if length < LEN_OFFSET {
+            return Err(LengthFieldTooSmall(length));
+        }
+        let total_len = usize::try_from(length)
+            .ok()
+            .and_then(|v| v.checked_add(LEN_OFFSET as usize))
+            .ok_or(LengthFieldTooLarge(length))?;
+        if payload.len() < total_len {
+            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer: Layer::SomeIpHeader,
+                minimum_size: total_len,
+                actual_size: payload.len(),
+            }));
+        }
+
+        Ok(SomeIpTraceSlice {
+            slice: &payload[..total_len],
+        })
+    }
+
+    /// Bytes of the SOME/IP message (header & payload).
+    #[inline]
+    pub fn slice(&self) -> &'a [u8] {
+        self.slice
+    }
+
+    /// Decodes the SOME/IP header via the `someip_parse` crate (requires
+    /// the `someip` feature).
+    ///
+    /// Returns an error if the header contains values not supported by
+    /// `someip_parse` (e.g. an unknown protocol version or message type).
+    #[cfg(feature = "someip")]
+    pub fn header(
+        &self,
+    ) -> Result<someip_parse::SomeipHeader, someip_parse::err::SomeipSliceError> {
+        someip_parse::SomeipMsgSlice::from_slice(self.slice).map(|msg| msg.to_header())
+    }
+
+    /// Payload of the SOME/IP message (after the header).
+    #[inline]
+    pub fn payload(&self) -> &'a [u8] {
+        &self.slice[HEADER_LEN..]
+    }
+}
+
+#[cfg(test)]
+mod some_ip_trace_slice_tests {
+    use super::*;
+    use crate::test_packets;
+    use alloc::{vec, vec::Vec};
+    use proptest::prelude::*;
+
+    /// Verbose SOME/IP trace (big endian, header block with the ip
+    /// addresses & ports, request to service 0x1234 method 0x0001 with
+    /// the payload `de ad be ef`).
+    const REQUEST: &[u8] = include_bytes!("../tests/fixtures/someip_trace_request.dlt");
+
+    /// Builds a non verbose network trace packet with the given payload
+    /// block.
+    fn packet(net_type: DltNetworkType, data: &[u8]) -> Vec<u8> {
+        let mut payload = vec![0, 0, 0, 1, 0, 0];
+        payload.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        payload.extend_from_slice(data);
+
+        let ext = DltExtendedHeader::new_non_verbose(
+            DltMessageType::NetworkTrace(net_type),
+            *b"NET\0",
+            *b"TRC\0",
+        )
+        .unwrap();
+        let header = DltHeader {
+            ecu_id: Some(*b"ECU1"),
+            ..test_packets::header(true, Some(ext))
+        };
+        test_packets::packet(header, &payload)
+    }
+
+    #[test]
+    fn fixture() {
+        let packet = DltPacketSlice::from_slice(REQUEST).unwrap();
+        let trace = NetworkTraceSlice::from_packet(&packet).unwrap();
+        assert_eq!(DltNetworkType::SomeIp, trace.net_type());
+
+        let someip = SomeIpTraceSlice::from_trace(&trace).unwrap();
+        assert_eq!(&[0xde, 0xad, 0xbe, 0xef], someip.payload());
+        assert_eq!(trace.payload(), someip.slice());
+        assert_eq!(Ok(someip), SomeIpTraceSlice::from_packet(&packet));
+    }
+
+    #[cfg(feature = "someip")]
+    #[test]
+    fn header() {
+        use someip_parse::{MessageType, SomeipHeader};
+
+        let packet_slice = DltPacketSlice::from_slice(REQUEST).unwrap();
+        let someip = SomeIpTraceSlice::from_packet(&packet_slice).unwrap();
+        assert_eq!(
+            Ok(SomeipHeader {
+                message_id: 0x1234_0001,
+                length: 12,
+                request_id: 0x0001_0002,
+                interface_version: 1,
+                message_type: MessageType::Request,
+                return_code: 0x00,
+                tp_header: None,
+            }),
+            someip.header()
+        );
+
+        // unsupported protocol version
+        let mut data = someip.slice().to_vec();
+        data[12] = 2;
+        let bytes = packet(DltNetworkType::SomeIp, &data);
+        let packet_slice = DltPacketSlice::from_slice(&bytes).unwrap();
+        let someip = SomeIpTraceSlice::from_packet(&packet_slice).unwrap();
+        assert!(someip.header().is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn from_trace(
+            service_id in any::<u16>(),
+            method_id in any::<u16>(),
+            client_id in any::<u16>(),
+            session_id in any::<u16>(),
+            protocol_version in any::<u8>(),
+            interface_version in any::<u8>(),
+            message_type in any::<u8>(),
+            return_code in any::<u8>(),
+            ref payload in proptest::collection::vec(any::<u8>(), 0..40),
+            ref trailing in proptest::collection::vec(any::<u8>(), 0..8),
+            is_ethernet in any::<bool>(),
+        ) {
+            let mut data = Vec::new();
+            data.extend_from_slice(&service_id.to_be_bytes());
+            data.extend_from_slice(&method_id.to_be_bytes());
+            data.extend_from_slice(&(LEN_OFFSET + payload.len() as u32).to_be_bytes());
+            data.extend_from_slice(&client_id.to_be_bytes());
+            data.extend_from_slice(&session_id.to_be_bytes());
+            data.extend_from_slice(&[
+                protocol_version,
+                interface_version,
+                message_type,
+                return_code,
+            ]);
+            data.extend_from_slice(payload);
+            let message_len = data.len();
+            data.extend_from_slice(trailing);
+
+            let net_type = if is_ethernet {
+                DltNetworkType::Ethernet
+            } else {
+                DltNetworkType::SomeIp
+            };
+            let bytes = packet(net_type, &data);
+            let packet_slice = DltPacketSlice::from_slice(&bytes).unwrap();
+            let someip = SomeIpTraceSlice::from_packet(&packet_slice).unwrap();
+            #[cfg(feature = "someip")]
+            prop_assert_eq!(
+                someip_parse::SomeipMsgSlice::from_slice(&data[..message_len])
+                    .map(|msg| msg.to_header()),
+                someip.header()
+            );
+            prop_assert_eq!(&payload[..], someip.payload());
+            prop_assert_eq!(&data[..message_len], someip.slice());
+
+            // too short for the header or the length in the header
+            for len in 0..message_len {
+                let bytes = packet(net_type, &data[..len]);
+                let packet_slice = DltPacketSlice::from_slice(&bytes).unwrap();
+                prop_assert_eq!(
+                    Err(SomeIpTraceError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                        layer: Layer::SomeIpHeader,
+                        minimum_size: if len < HEADER_LEN {
+                            HEADER_LEN
+                        } else {
+                            message_len
+                        },
+                        actual_size: len,
+                    })),
+                    SomeIpTraceSlice::from_packet(&packet_slice)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        // other network type
+        let bytes = packet(DltNetworkType::Can, &[0; 16]);
+        let packet_slice = DltPacketSlice::from_slice(&bytes).unwrap();
+        assert_eq!(
+            Err(SomeIpTraceError::NotASomeIpTrace(DltNetworkType::Can)),
+            SomeIpTraceSlice::from_packet(&packet_slice)
+        );
+
+        // length field smaller then the part of the header it includes
+        for length in [0, LEN_OFFSET - 1] {
+            let mut data = [0; 20];
+            data[4..8].copy_from_slice(&length.to_be_bytes());
+            let bytes = packet(DltNetworkType::SomeIp, &data);
+            let packet_slice = DltPacketSlice::from_slice(&bytes).unwrap();
+            assert_eq!(
+                Err(SomeIpTraceError::LengthFieldTooSmall(length)),
+                SomeIpTraceSlice::from_packet(&packet_slice)
+            );
+        }
+
+        // length field only covering the header
+        let mut data = [0; 20];
+        data[4..8].copy_from_slice(&LEN_OFFSET.to_be_bytes());
+        let bytes = packet(DltNetworkType::SomeIp, &data);
+        let packet_slice = DltPacketSlice::from_slice(&bytes).unwrap();
+        let someip = SomeIpTraceSlice::from_packet(&packet_slice).unwrap();
+        assert_eq!(HEADER_LEN, someip.slice().len());
+        assert!(someip.payload().is_empty());
+
+        // length field larger then the payload block
+        let mut data = [0; 20];
+        data[4..8].copy_from_slice(&u32::MAX.to_be_bytes());
+        let bytes = packet(DltNetworkType::SomeIp, &data);
+        let packet_slice = DltPacketSlice::from_slice(&bytes).unwrap();
+        assert_eq!(
+            Err(match (u32::MAX as usize).checked_add(LEN_OFFSET as usize) {
+                Some(total_len) => {
+                    SomeIpTraceError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                        layer: Layer::SomeIpHeader,
+                        minimum_size: total_len,
+                        actual_size: 20,
+                    })
+                }
+                // length does not fit into an usize on 32 bit targets
+                None => SomeIpTraceError::LengthFieldTooLarge(u32::MAX),
+            }),
+            SomeIpTraceSlice::from_packet(&packet_slice)
+        );
+
+        // not a network trace
+        let bytes = include_bytes!("../tests/fixtures/non_verbose_message.dlt");
+        let packet_slice = DltPacketSlice::from_slice(bytes).unwrap();
+        assert!(matches!(
+            SomeIpTraceSlice::from_packet(&packet_slice),
+            Err(SomeIpTraceError::NetworkTrace(_))
+        ));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{error::PacketSliceError, SliceIterator};
+use std::collections::HashMap;
+use std::vec::Vec;
+
+/// Splits the dlt messages in the given slice into one buffer per ECU id.
+///
+/// The ECU ids are used as keys in the form of big endian `u32` values
+/// (e.g. `u32::from_be_bytes(*b"ECU1")`). Messages without an ECU id are
+/// put into the buffer with the key `0` (shared with messages that
+/// contain the ECU id `[0, 0, 0, 0]`). The order of the messages within a
+/// buffer is the same as in the slice.
+///
+/// The bytes of every message are copied, so the buffers together
+/// allocate about as much memory as the given slice (one growing `Vec`
+/// per ECU id, allocated when the first message of the ECU is found).
+///
+/// The slice is expected to only contain dlt messages (without storage
+/// headers). Decoding stops at the first message that can not be decoded
+/// and the error is returned.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{split_by_ecu, DltHeader};
+///
+/// let ecu1 = DltHeader::new_non_verbose(*b"ECU1").to_bytes();
+/// let ecu2 = DltHeader::new_non_verbose(*b"ECU2").to_bytes();
+/// let mut capture = Vec::new();
+/// capture.extend_from_slice(&ecu1);
+/// capture.extend_from_slice(&ecu2);
+/// capture.extend_from_slice(&ecu1);
+///
+/// let buffers = split_by_ecu(&capture).unwrap();
+/// assert_eq!(2, buffers.len());
+/// assert_eq!(2 * ecu1.len(), buffers[&u32::from_be_bytes(*b"ECU1")].len());
+/// assert_eq!(&ecu2[..], &buffers[&u32::from_be_bytes(*b"ECU2")][..]);
+/// ```
+pub fn split_by_ecu(slice: &[u8]) -> Result<HashMap<u32, Vec<u8>>, PacketSliceError> {
+    let mut result: HashMap<u32, Vec<u8>> = HashMap::new();
+    for packet in SliceIterator::new(slice) {
+        let packet = packet?;
+        let key = packet.ecu_id().map_or(0, u32::from_be_bytes);
+        result
+            .entry(key)
+            .or_default()
+            .extend_from_slice(packet.slice());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod split_by_ecu_tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn split(ref packets in prop::collection::vec(dlt_header_with_payload_any(), 0..8)) {
+            let mut buffer = Vec::new();
+            let mut expected: HashMap<u32, Vec<u8>> = HashMap::new();
+            for (header, payload) in packets {
+                let start = buffer.len();
+                buffer.extend_from_slice(&header.to_bytes());
+                buffer.extend_from_slice(payload);
+                expected
+                    .entry(header.ecu_id.map_or(0, u32::from_be_bytes))
+                    .or_default()
+                    .extend_from_slice(&buffer[start..]);
+            }
+            prop_assert_eq!(Ok(expected), split_by_ecu(&buffer));
+
+            // error at the end of the slice
+            if false == buffer.is_empty() {
+                let is_err = split_by_ecu(&buffer[..buffer.len() - 1]).is_err();
+                prop_assert!(is_err);
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Ok(HashMap::new()), split_by_ecu(&[]));
+    }
+}
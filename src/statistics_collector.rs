@@ -0,0 +1,247 @@
+use crate::control::{BufferOverflowNotification, ControlResponse, DltServiceId};
+use crate::{error::PacketSliceError, DltPacketSlice, SliceIterator};
+use std::collections::{btree_map, BTreeMap};
+
+/// Statistics of the messages received from a single ECU.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct EcuStatistics {
+    /// Number of messages received.
+    pub messages: u64,
+    /// Number of "buffer overflow notification" control messages received.
+    pub buffer_overflow_notifications: u64,
+    /// Number of messages the ECU reported as dropped (sum of the overflow
+    /// counters of all received "buffer overflow notifications").
+    pub dropped_messages: u64,
+}
+
+/// Collects message statistics per ECU id.
+///
+/// Next to counting the messages, the collector decodes
+/// [`BufferOverflowNotification`]s so the number of messages an ECU
+/// dropped before they could be sent is accumulated. Messages without an
+/// ECU id are collected under the key `None`.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::StatisticsCollector;
+///
+/// let mut stats = StatisticsCollector::new();
+/// # let data: [u8; 0] = [];
+/// stats.add_slice(&data).unwrap();
+/// for (ecu_id, ecu_stats) in stats.iter() {
+///     println!("{:?}: {} messages, {} dropped", ecu_id, ecu_stats.messages, ecu_stats.dropped_messages);
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StatisticsCollector {
+    ecus: BTreeMap<Option<[u8; 4]>, EcuStatistics>,
+}
+
+impl StatisticsCollector {
+    /// Creates a collector without any statistics.
+    #[inline]
+    pub fn new() -> StatisticsCollector {
+        StatisticsCollector::default()
+    }
+
+    /// Adds the given packet to the statistics of its ECU.
+    ///
+    /// "Buffer overflow notifications" that can not be decoded are only
+    /// counted as messages.
+    pub fn add(&mut self, packet: &DltPacketSlice<'_>) {
+        let stats = self.ecus.entry(packet.ecu_id()).or_default();
+        stats.messages += 1;
+        if packet.control_service_id() == Some(DltServiceId::BufferOverflowNotification) {
+            let notification = ControlResponse::from_packet(packet)
+                .and_then(|response| BufferOverflowNotification::parse(&response));
+            if let Ok(notification) = notification {
+                stats.buffer_overflow_notifications += 1;
+                stats.dropped_messages += u64::from(notification.overflow_counter);
+            }
+        }
+    }
+
+    /// Adds all dlt messages in the given slice (without storage headers).
+    ///
+    /// Decoding stops at the first message that can not be decoded and the
+    /// error is returned (messages before it stay in the statistics).
+    pub fn add_slice(&mut self, slice: &[u8]) -> Result<(), PacketSliceError> {
+        for packet in SliceIterator::new(slice) {
+            self.add(&packet?);
+        }
+        Ok(())
+    }
+
+    /// Returns the statistics of the given ECU (`None` if no message
+    /// of the ECU was added).
+    #[inline]
+    pub fn ecu(&self, ecu_id: Option<[u8; 4]>) -> Option<&EcuStatistics> {
+        self.ecus.get(&ecu_id)
+    }
+
+    /// Returns an iterator over the statistics of all ECUs (ordered by ECU id).
+    #[inline]
+    pub fn iter(&self) -> btree_map::Iter<'_, Option<[u8; 4]>, EcuStatistics> {
+        self.ecus.iter()
+    }
+
+    /// Returns the statistics summed up over all ECUs.
+    pub fn total(&self) -> EcuStatistics {
+        self.ecus
+            .values()
+            .fold(EcuStatistics::default(), |acc, s| EcuStatistics {
+                messages: acc.messages + s.messages,
+                buffer_overflow_notifications: acc.buffer_overflow_notifications
+                    + s.buffer_overflow_notifications,
+                dropped_messages: acc.dropped_messages + s.dropped_messages,
+            })
+    }
+
+    /// Removes all statistics.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.ecus.clear();
+    }
+}
+
+#[cfg(test)]
+mod statistics_collector_tests {
+    use super::*;
+    use crate::control::ControlResponseStatus;
+    use crate::test_packets;
+    use crate::*;
+    use alloc::format;
+    use alloc::vec::Vec;
+    use arrayvec::ArrayVec;
+
+    const FIXTURE: &[u8] = include_bytes!("../tests/fixtures/buffer_overflow_notification.dlt");
+
+    fn packet(ecu_id: Option<[u8; 4]>, message_type: DltMessageType, payload: &[u8]) -> Vec<u8> {
+        let ext = DltExtendedHeader::new_non_verbose(message_type, *b"APP\0", *b"CTX\0").unwrap();
+        let header = DltHeader {
+            ecu_id,
+            ..test_packets::header(true, Some(ext))
+        };
+        test_packets::packet(header, payload)
+    }
+
+    fn notification(ecu_id: Option<[u8; 4]>, overflow_counter: u32) -> Vec<u8> {
+        let mut payload = ArrayVec::<u8, { BufferOverflowNotification::PAYLOAD_LEN }>::new();
+        BufferOverflowNotification {
+            status: ControlResponseStatus::Ok,
+            overflow_counter,
+        }
+        .add_to_msg(&mut payload, true)
+        .unwrap();
+        packet(
+            ecu_id,
+            DltMessageType::Control(DltControlMessageType::Response),
+            &payload,
+        )
+    }
+
+    #[test]
+    fn fixture() {
+        let mut stats = StatisticsCollector::new();
+        stats.add_slice(FIXTURE).unwrap();
+        assert_eq!(
+            Some(&EcuStatistics {
+                messages: 1,
+                buffer_overflow_notifications: 1,
+                dropped_messages: 42,
+            }),
+            stats.ecu(Some(*b"ECU1"))
+        );
+        assert_eq!(None, stats.ecu(None));
+    }
+
+    #[test]
+    fn per_ecu() {
+        let log = DltMessageType::Log(DltLogLevel::Info);
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&packet(Some(*b"ECU1"), log, &[0, 0, 0, 1]));
+        buffer.extend_from_slice(&notification(Some(*b"ECU1"), 10));
+        buffer.extend_from_slice(&packet(Some(*b"ECU2"), log, &[0, 0, 0, 1]));
+        buffer.extend_from_slice(&notification(Some(*b"ECU1"), 5));
+        buffer.extend_from_slice(&notification(Some(*b"ECU2"), 7));
+        buffer.extend_from_slice(&notification(None, 1));
+        // truncated notification (only counted as message)
+        buffer.extend_from_slice(&packet(
+            Some(*b"ECU2"),
+            DltMessageType::Control(DltControlMessageType::Response),
+            &[0, 0, 0, 0x23, 0, 0, 0],
+        ));
+        // request with the same service id (not a notification)
+        buffer.extend_from_slice(&packet(
+            Some(*b"ECU2"),
+            DltMessageType::Control(DltControlMessageType::Request),
+            &[0, 0, 0, 0x23, 0, 0, 0, 0, 1],
+        ));
+
+        let mut stats = StatisticsCollector::new();
+        stats.add_slice(&buffer).unwrap();
+
+        let ecu1 = EcuStatistics {
+            messages: 3,
+            buffer_overflow_notifications: 2,
+            dropped_messages: 15,
+        };
+        let ecu2 = EcuStatistics {
+            messages: 4,
+            buffer_overflow_notifications: 1,
+            dropped_messages: 7,
+        };
+        let no_ecu = EcuStatistics {
+            messages: 1,
+            buffer_overflow_notifications: 1,
+            dropped_messages: 1,
+        };
+        assert_eq!(Some(&ecu1), stats.ecu(Some(*b"ECU1")));
+        assert_eq!(Some(&ecu2), stats.ecu(Some(*b"ECU2")));
+        assert_eq!(Some(&no_ecu), stats.ecu(None));
+        assert_eq!(None, stats.ecu(Some(*b"ECU3")));
+        assert_eq!(
+            vec![
+                (None, no_ecu),
+                (Some(*b"ECU1"), ecu1),
+                (Some(*b"ECU2"), ecu2)
+            ],
+            stats.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            EcuStatistics {
+                messages: 8,
+                buffer_overflow_notifications: 4,
+                dropped_messages: 23,
+            },
+            stats.total()
+        );
+
+        stats.clear();
+        assert_eq!(StatisticsCollector::new(), stats);
+        assert_eq!(EcuStatistics::default(), stats.total());
+    }
+
+    #[test]
+    fn add_slice_error() {
+        let mut buffer = notification(Some(*b"ECU1"), 3);
+        buffer.extend_from_slice(&[0x35, 0]);
+        let mut stats = StatisticsCollector::new();
+        assert!(stats.add_slice(&buffer).is_err());
+        assert_eq!(3, stats.ecu(Some(*b"ECU1")).unwrap().dropped_messages);
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let stats = StatisticsCollector::new();
+        assert_eq!(stats, stats.clone());
+        assert_eq!("StatisticsCollector { ecus: {} }", format!("{:?}", stats));
+        let ecu = EcuStatistics::default();
+        assert_eq!(ecu, ecu.clone());
+        assert_eq!(
+            "EcuStatistics { messages: 0, buffer_overflow_notifications: 0, dropped_messages: 0 }",
+            format!("{:?}", ecu)
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{error::PacketSliceError, storage::StorageHeader, SliceIterator};
+use core::time::Duration;
+use std::vec::Vec;
+
+/// Converts a buffer of concatenated dlt packets (without storage headers,
+/// e.g. the payload of a network capture) into the content of a .dlt file
+/// by prefixing each packet with a storage header.
+///
+/// The first packet gets `base_time` as timestamp and every following
+/// packet a timestamp `time_increment` later than the previous one (pass
+/// [`Duration::ZERO`] to give all packets the same timestamp). Timestamps
+/// after the end of the storage header range (`u32::MAX` seconds) are
+/// clamped to the last representable value. All storage headers get
+/// `ecu_id` set as ECU id.
+///
+/// Decoding stops at the first packet that can not be decoded and the
+/// error is returned.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+/// use dlt_parse::{storage::{add_storage_headers, StorageHeader}, DltHeader};
+///
+/// let mut header = DltHeader {
+///     is_big_endian: true,
+///     message_counter: 0,
+///     length: 0,
+///     ecu_id: None,
+///     session_id: None,
+///     timestamp: None,
+///     extended_header: None,
+/// };
+/// header.length = header.header_len() + 4;
+/// let mut capture = Vec::new();
+/// header.write(&mut capture).unwrap();
+/// capture.extend_from_slice(&[1, 2, 3, 4]);
+///
+/// let dlt_file = add_storage_headers(
+///     &capture,
+///     Duration::from_secs(1_600_000_000),
+///     Duration::from_millis(1),
+///     *b"ECU1",
+/// ).unwrap();
+/// assert_eq!(StorageHeader::BYTE_LEN + capture.len(), dlt_file.len());
+/// ```
+pub fn add_storage_headers(
+    slice: &[u8],
+    base_time: Duration,
+    time_increment: Duration,
+    ecu_id: [u8; 4],
+) -> Result<Vec<u8>, PacketSliceError> {
+    // the storage headers only support up to u32::MAX seconds
+    let max_time = Duration::new(u64::from(u32::MAX), 999_999_000);
+
+    let mut result = Vec::with_capacity(slice.len());
+    let mut time = Some(base_time);
+    for packet in SliceIterator::new(slice) {
+        let packet = packet?;
+        let t = time.map_or(max_time, |t| t.min(max_time));
+        let header = StorageHeader {
+            timestamp_seconds: t.as_secs() as u32,
+            timestamp_microseconds: t.subsec_micros(),
+            ecu_id,
+        };
+        result.extend_from_slice(&header.to_bytes());
+        result.extend_from_slice(packet.slice());
+        time = time.and_then(|t| t.checked_add(time_increment));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod add_storage_headers_tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use crate::DltPacketSlice;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn add_headers(
+            ref packets in prop::collection::vec(dlt_header_with_payload_any(), 0..8),
+            base_secs in 0u64..=u64::from(u32::MAX) - 8,
+            base_micros in 0u32..1_000_000,
+            increment_micros in 0u64..2_000_000,
+            ecu_id in any::<[u8; 4]>(),
+        ) {
+            let mut buffer = Vec::new();
+            for (header, payload) in packets {
+                buffer.extend_from_slice(&header.to_bytes());
+                buffer.extend_from_slice(payload);
+            }
+            let base_time = Duration::new(base_secs, base_micros * 1000);
+            let increment = Duration::from_micros(increment_micros);
+
+            let result = add_storage_headers(&buffer, base_time, increment, ecu_id).unwrap();
+            prop_assert_eq!(buffer.len() + packets.len() * StorageHeader::BYTE_LEN, result.len());
+
+            let mut rest = &result[..];
+            let mut expected_time = base_time;
+            for packet in SliceIterator::new(&buffer) {
+                let packet = packet.unwrap();
+                let mut header_bytes = [0u8; 16];
+                header_bytes.copy_from_slice(&rest[..16]);
+                prop_assert_eq!(
+                    Ok(StorageHeader {
+                        timestamp_seconds: expected_time.as_secs() as u32,
+                        timestamp_microseconds: expected_time.subsec_micros(),
+                        ecu_id,
+                    }),
+                    StorageHeader::from_bytes(header_bytes)
+                );
+                let (stored, next) = DltPacketSlice::from_slice_with_rest(&rest[16..]).unwrap();
+                prop_assert_eq!(packet, stored);
+                expected_time += increment;
+                rest = next;
+            }
+            prop_assert!(rest.is_empty());
+
+            // error
+            if false == buffer.is_empty() {
+                let len = buffer.len() - 1;
+                prop_assert!(add_storage_headers(&buffer[..len], base_time, increment, ecu_id).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn clamped_time() {
+        let mut header = crate::DltHeader {
+            is_big_endian: true,
+            message_counter: 0,
+            length: 0,
+            ecu_id: None,
+            session_id: None,
+            timestamp: None,
+            extended_header: None,
+        };
+        header.length = header.header_len();
+        let mut buffer = Vec::new();
+        for _ in 0..3 {
+            buffer.extend_from_slice(&header.to_bytes());
+        }
+
+        for (base_time, increment) in [
+            (
+                Duration::new(u64::from(u32::MAX), 999_999_999),
+                Duration::ZERO,
+            ),
+            (
+                Duration::from_secs(u64::from(u32::MAX)),
+                Duration::from_secs(1),
+            ),
+            (Duration::MAX, Duration::MAX),
+        ] {
+            let result = add_storage_headers(&buffer, base_time, increment, *b"ECU1").unwrap();
+            let header_len = usize::from(header.length);
+            for i in 0..3 {
+                let start = i * (StorageHeader::BYTE_LEN + header_len);
+                let mut bytes = [0u8; 16];
+                bytes.copy_from_slice(&result[start..start + 16]);
+                let storage_header = StorageHeader::from_bytes(bytes).unwrap();
+                if i > 0 || base_time > Duration::from_secs(u64::from(u32::MAX)) {
+                    assert_eq!(u32::MAX, storage_header.timestamp_seconds);
+                    assert_eq!(999_999, storage_header.timestamp_microseconds);
+                }
+            }
+        }
+    }
+}
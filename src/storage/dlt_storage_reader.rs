@@ -184,7 +184,7 @@ impl<R: Read + BufRead> DltStorageReader<R> {
                 packet,
             }))
         } else {
-            loop {
+            'seek: loop {
                 // seek the next storage header pattern
                 let mut pattern_elements_found = 0;
                 let mut storage_pattern_error = false;
@@ -255,50 +255,21 @@ impl<R: Read + BufRead> DltStorageReader<R> {
                     }
                 }
 
-                // check version
-                let version = (header_start[0] >> 5) & MAX_VERSION;
-                if 0 != version && 1 != version {
-                    continue;
-                }
-
-                // calculate the minimum size based on the header flags
-                let header_len = if 0 != header_start[0] & ECU_ID_FLAG {
-                    4 + 4
-                } else {
-                    4
-                };
-
-                let header_len = if 0 != header_start[0] & SESSION_ID_FLAG {
-                    header_len + 4
-                } else {
-                    header_len
-                };
-
-                let header_len = if 0 != header_start[0] & TIMESTAMP_FLAG {
-                    header_len + 4
-                } else {
-                    header_len
-                };
-
-                let header_len = if 0 != header_start[0] & EXTDENDED_HEADER_FLAG {
-                    header_len + 10
-                } else {
-                    header_len
-                };
-
-                // check length to be at least 4
-                let length = u16::from_be_bytes([header_start[2], header_start[3]]) as usize;
-                if length < header_len {
-                    continue;
-                }
-
-                // read the complete packet
+                // read the packet until it is complete (or seek the next
+                // storage header if the data turns out to be corrupt)
                 self.last_packet.clear();
-                self.last_packet.reserve(length);
                 self.last_packet.extend_from_slice(&header_start);
-                if length > 4 {
-                    self.last_packet.resize(length, 0);
-                    if let Err(err) = self.reader.read_exact(&mut self.last_packet[4..]) {
+                loop {
+                    let needed = match DltPacketSlice::from_slice(&self.last_packet) {
+                        Ok(_) => break,
+                        Err(err) => match err.additional_bytes_needed() {
+                            Some(needed) => needed,
+                            None => continue 'seek,
+                        },
+                    };
+                    let start = self.last_packet.len();
+                    self.last_packet.resize(start + needed, 0);
+                    if let Err(err) = self.reader.read_exact(&mut self.last_packet[start..]) {
                         self.read_error = true;
                         if err.kind() == ErrorKind::UnexpectedEof {
                             return None;
@@ -307,14 +278,8 @@ impl<R: Read + BufRead> DltStorageReader<R> {
                         }
                     }
                 }
-
-                let packet = match DltPacketSlice::from_slice(&self.last_packet) {
-                    Ok(packet) => packet,
-                    Err(err) => {
-                        self.read_error = true;
-                        return Some(Err(err.into()));
-                    }
-                };
+                // the packet was already verified in the loop above
+                let packet = DltPacketSlice::from_slice(&self.last_packet).unwrap();
 
                 // packet successfully read
                 self.num_read_packets += 1;
@@ -330,6 +295,7 @@ impl<R: Read + BufRead> DltStorageReader<R> {
 
 #[cfg(test)]
 #[cfg(feature = "std")]
+#[allow(clippy::assign_op_pattern, clippy::len_zero, clippy::unused_io_amount)]
 mod dlt_storage_reader_tests {
     use super::*;
     use std::format;
@@ -471,6 +437,53 @@ mod dlt_storage_reader_tests {
             assert!(reader.next_packet().is_none());
         }
 
+        // reader with a corrupt packet (seeking)
+        {
+            let storage_header = StorageHeader {
+                timestamp_seconds: 1,
+                timestamp_microseconds: 2,
+                ecu_id: [0, 0, 0, 0],
+            };
+            let packet = {
+                let mut packet = Vec::new();
+                let mut header = DltHeader {
+                    is_big_endian: true,
+                    message_counter: 1,
+                    length: 0, // set afterwords
+                    ecu_id: None,
+                    session_id: None,
+                    timestamp: None,
+                    extended_header: None,
+                };
+                header.length = header.header_len() + 4;
+                header.write(&mut packet).unwrap();
+                packet.extend_from_slice(&[1, 2, 3, 4]);
+                packet
+            };
+            let mut corrupt = packet.clone();
+            // unsupported version
+            corrupt[0] |= 0b1110_0000;
+
+            let mut v = Vec::new();
+            v.extend_from_slice(&storage_header.to_bytes());
+            v.extend_from_slice(&corrupt);
+            v.extend_from_slice(&storage_header.to_bytes());
+            v.extend_from_slice(&packet);
+
+            // the corrupt packet is skipped & the reader resyncs
+            let mut reader = DltStorageReader::new(BufReader::new(Cursor::new(&v[..])));
+            assert_eq!(
+                reader.next_packet().unwrap().unwrap(),
+                StorageSlice {
+                    storage_header,
+                    packet: DltPacketSlice::from_slice(&packet).unwrap()
+                }
+            );
+            assert_eq!(1, reader.num_read_packets());
+            assert_eq!(1, reader.num_pattern_seeks());
+            assert!(reader.next_packet().is_none());
+        }
+
         // reader with working packets (strict)
         {
             // build two packets
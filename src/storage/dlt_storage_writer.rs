@@ -74,6 +74,7 @@ impl<W: Write> DltStorageWriter<W> {
 
 #[cfg(feature = "std")]
 #[cfg(test)]
+#[allow(clippy::len_zero)]
 mod dlt_storage_writer_tests {
     use super::*;
     use crate::DltHeader;
@@ -0,0 +1,207 @@
+use crate::{
+    error::{Layer, ReadError, UnexpectedEndOfSliceError},
+    storage::StorageHeader,
+    DltPacketSlice,
+};
+use std::vec::Vec;
+
+/// Pattern/Magic Number at the start of an index generated by
+/// [`index_to_bytes`].
+pub const INDEX_PATTERN_AT_START: [u8; 4] = *b"DLTI";
+
+/// Version of the index format generated by [`index_to_bytes`].
+pub const INDEX_VERSION: u8 = 1;
+
+/// Length of the header at the start of an index generated by
+/// [`index_to_bytes`].
+pub const INDEX_HEADER_LEN: usize = 16;
+
+/// Generates an index containing the offsets of all messages in the
+/// content of a .dlt file (dlt messages with storage headers), allowing
+/// loaders to seek to any message in O(1).
+///
+/// The index starts with a 16 byte header followed by one offset per
+/// message (all values are encoded in little endian):
+///
+/// ```text
+/// | pattern "DLTI" (4 bytes) | version (u8) | offset size (u8) | reserved (2 bytes, zero) |
+/// | message count (u64) |
+/// | offset of message 0 | offset of message 1 | ... |
+/// ```
+///
+/// * `version` is [`INDEX_VERSION`].
+/// * `offset size` is the size of each offset in bytes: `4` (`u32`
+///   offsets) if all messages start before 4 GiB, otherwise `8` (`u64`
+///   offsets).
+/// * Each offset points to the start of the storage header of a message
+///   (relative to the start of the given slice), in the order the
+///   messages are stored.
+///
+/// Decoding stops at the first message that can not be decoded & the
+/// error is returned.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+/// use dlt_parse::{storage::{add_storage_headers, index_to_bytes}, DltHeader};
+///
+/// let packet = DltHeader::new_non_verbose(*b"ECU1").to_bytes();
+/// let mut capture = Vec::new();
+/// capture.extend_from_slice(&packet);
+/// capture.extend_from_slice(&packet);
+/// let dlt_file = add_storage_headers(&capture, Duration::ZERO, Duration::ZERO, *b"ECU1").unwrap();
+///
+/// let index = index_to_bytes(&dlt_file).unwrap();
+/// assert_eq!(&b"DLTI"[..], &index[..4]);
+/// // two u32 offsets
+/// assert_eq!(4, index[5]);
+/// assert_eq!(2, u64::from_le_bytes(index[8..16].try_into().unwrap()));
+/// assert_eq!(0, u32::from_le_bytes(index[16..20].try_into().unwrap()));
+/// assert_eq!(
+///     (16 + packet.len()) as u32,
+///     u32::from_le_bytes(index[20..24].try_into().unwrap())
+/// );
+/// ```
+pub fn index_to_bytes(slice: &[u8]) -> Result<Vec<u8>, ReadError> {
+    let mut offsets = Vec::new();
+    let mut rest = slice;
+    while false == rest.is_empty() {
+        if rest.len() < StorageHeader::BYTE_LEN {
+            return Err(ReadError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer: Layer::StorageHeader,
+                minimum_size: StorageHeader::BYTE_LEN,
+                actual_size: rest.len(),
+            }));
+        }
+        let mut header = [0u8; StorageHeader::BYTE_LEN];
+        header.copy_from_slice(&rest[..StorageHeader::BYTE_LEN]);
+        StorageHeader::from_bytes(header)?;
+
+        offsets.push((slice.len() - rest.len()) as u64);
+        let (_, next) = DltPacketSlice::from_slice_with_rest(&rest[StorageHeader::BYTE_LEN..])?;
+        rest = next;
+    }
+
+    let offset_len: usize = if offsets.last().map_or(true, |v| *v <= u64::from(u32::MAX)) {
+        4
+    } else {
+        8
+    };
+
+    let mut result = Vec::with_capacity(INDEX_HEADER_LEN + offsets.len() * offset_len);
+    result.extend_from_slice(&INDEX_PATTERN_AT_START);
+    result.extend_from_slice(&[INDEX_VERSION, offset_len as u8, 0, 0]);
+    result.extend_from_slice(&(offsets.len() as u64).to_le_bytes());
+    for offset in offsets {
+        if 4 == offset_len {
+            result.extend_from_slice(&(offset as u32).to_le_bytes());
+        } else {
+            result.extend_from_slice(&offset.to_le_bytes());
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod index_to_bytes_tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use proptest::prelude::*;
+
+    /// Decodes the offsets of an index with `u32` offsets.
+    fn decode(index: &[u8]) -> Vec<u64> {
+        assert_eq!(&INDEX_PATTERN_AT_START, &index[..4]);
+        assert_eq!(&[INDEX_VERSION, 4, 0, 0], &index[4..8]);
+        let mut count = [0u8; 8];
+        count.copy_from_slice(&index[8..16]);
+        let count = u64::from_le_bytes(count) as usize;
+        assert_eq!(INDEX_HEADER_LEN + count * 4, index.len());
+        index[INDEX_HEADER_LEN..]
+            .chunks_exact(4)
+            .map(|c| u64::from(u32::from_le_bytes([c[0], c[1], c[2], c[3]])))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn index(
+            ref packets in prop::collection::vec(dlt_header_with_payload_any(), 0..8),
+            ecu_id in any::<[u8; 4]>(),
+        ) {
+            let mut buffer = Vec::new();
+            let mut expected = Vec::new();
+            for (header, payload) in packets {
+                expected.push(buffer.len() as u64);
+                buffer.extend_from_slice(
+                    &StorageHeader {
+                        timestamp_seconds: 1,
+                        timestamp_microseconds: 2,
+                        ecu_id,
+                    }
+                    .to_bytes(),
+                );
+                buffer.extend_from_slice(&header.to_bytes());
+                buffer.extend_from_slice(payload);
+            }
+
+            let index = index_to_bytes(&buffer).unwrap();
+            prop_assert_eq!(expected, decode(&index));
+
+            // the offsets point to the storage headers
+            for offset in decode(&index) {
+                let offset = offset as usize;
+                prop_assert_eq!(
+                    &StorageHeader::PATTERN_AT_START[..],
+                    &buffer[offset..offset + 4]
+                );
+            }
+
+            // truncated storage header
+            if false == buffer.is_empty() {
+                let last = *decode(&index).last().unwrap() as usize;
+                for len in last + 1..last + StorageHeader::BYTE_LEN {
+                    let is_end_of_slice = matches!(
+                        index_to_bytes(&buffer[..len]),
+                        Err(ReadError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                            layer: Layer::StorageHeader,
+                            ..
+                        }))
+                    );
+                    prop_assert!(is_end_of_slice);
+                }
+            }
+
+            // truncated packet
+            if false == buffer.is_empty() {
+                let is_err = index_to_bytes(&buffer[..buffer.len() - 1]).is_err();
+                prop_assert!(is_err);
+            }
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let index = index_to_bytes(&[]).unwrap();
+        assert_eq!(&INDEX_PATTERN_AT_START, &index[..4]);
+        assert_eq!(&[INDEX_VERSION, 4, 0, 0], &index[4..8]);
+        assert_eq!(&[0u8; 8], &index[8..]);
+    }
+
+    #[test]
+    fn bad_storage_pattern() {
+        let mut buffer = StorageHeader {
+            timestamp_seconds: 0,
+            timestamp_microseconds: 0,
+            ecu_id: *b"ECU1",
+        }
+        .to_bytes()
+        .to_vec();
+        buffer[0] = 0;
+        buffer.extend_from_slice(&crate::DltHeader::new_non_verbose(*b"ECU1").to_bytes());
+        assert!(matches!(
+            index_to_bytes(&buffer),
+            Err(ReadError::StorageHeaderStartPattern(_))
+        ));
+    }
+}
@@ -1,3 +1,8 @@
+#[cfg(feature = "std")]
+mod add_storage_headers;
+#[cfg(feature = "std")]
+pub use add_storage_headers::*;
+
 #[cfg(feature = "std")]
 mod dlt_storage_reader;
 #[cfg(feature = "std")]
@@ -8,6 +13,11 @@ mod dlt_storage_writer;
 #[cfg(feature = "std")]
 pub use dlt_storage_writer::*;
 
+#[cfg(feature = "std")]
+mod index_to_bytes;
+#[cfg(feature = "std")]
+pub use index_to_bytes::*;
+
 mod storage_header;
 pub use storage_header::*;
 
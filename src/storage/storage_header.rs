@@ -84,6 +84,7 @@ impl StorageHeader {
 }
 
 #[cfg(test)]
+#[allow(clippy::clone_on_copy)]
 mod storage_header_tests {
 
     use super::*;
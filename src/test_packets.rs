@@ -0,0 +1,25 @@
+use crate::*;
+use alloc::vec::Vec;
+
+/// Returns a header without ecu id, session id & timestamp (the length
+/// is set when the packet is built via [`packet`]).
+pub fn header(is_big_endian: bool, extended_header: Option<DltExtendedHeader>) -> DltHeader {
+    DltHeader {
+        is_big_endian,
+        message_counter: 0,
+        length: 0,
+        ecu_id: None,
+        session_id: None,
+        timestamp: None,
+        extended_header,
+    }
+}
+
+/// Serializes the header with the length set to match the payload
+/// followed by the payload.
+pub fn packet(mut header: DltHeader, payload: &[u8]) -> Vec<u8> {
+    header.length = header.header_len() + payload.len() as u16;
+    let mut result = header.to_bytes().to_vec();
+    result.extend_from_slice(payload);
+    result
+}
@@ -0,0 +1,143 @@
+use core::time::Duration;
+
+/// Interpretation of the 32 bit timestamp in the DLT header.
+///
+/// The DLT specification defines the timestamp as the time since the
+/// ECU startup in 0.1 milliseconds (wrapping roughly every 119 hours).
+/// Some ECUs instead write the absolute unix time in seconds into the
+/// field.
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+/// use dlt_parse::TimestampInterpretation;
+///
+/// let relative = TimestampInterpretation::guess_interpretation(12_345);
+/// assert_eq!(TimestampInterpretation::Relative, relative);
+/// assert_eq!(Duration::from_micros(1_234_500), relative.to_duration(12_345));
+///
+/// // 2020-09-13T12:26:40Z
+/// let absolute = TimestampInterpretation::guess_interpretation(1_600_000_000);
+/// assert_eq!(TimestampInterpretation::Absolute, absolute);
+/// assert_eq!(Duration::from_secs(1_600_000_000), absolute.to_duration(1_600_000_000));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TimestampInterpretation {
+    /// Time since the ECU startup in 0.1 milliseconds (as defined by the
+    /// DLT specification).
+    Relative,
+    /// Seconds since the unix epoch (1970-01-01T00:00:00Z).
+    Absolute,
+}
+
+impl TimestampInterpretation {
+    /// Smallest timestamp value [`TimestampInterpretation::guess_interpretation`]
+    /// treats as absolute time (2010-01-01T00:00:00Z in seconds since the
+    /// unix epoch, roughly 35 hours of uptime as relative timestamp).
+    pub const MIN_ABSOLUTE: u32 = 1_262_304_000;
+
+    /// Guesses the interpretation of a timestamp based on its magnitude.
+    ///
+    /// Values of at least [`TimestampInterpretation::MIN_ABSOLUTE`] are
+    /// treated as absolute time, smaller values as relative time. As both
+    /// ranges overlap the guess is wrong for ECUs with an uptime of more
+    /// than ~35 hours (or clocks set before 2010). If possible the guess
+    /// should be made once per ECU & not per message.
+    #[inline]
+    pub fn guess_interpretation(timestamp: u32) -> TimestampInterpretation {
+        if timestamp >= TimestampInterpretation::MIN_ABSOLUTE {
+            TimestampInterpretation::Absolute
+        } else {
+            TimestampInterpretation::Relative
+        }
+    }
+
+    /// Converts the timestamp to a duration (since the ECU startup for
+    /// relative & since the unix epoch for absolute timestamps).
+    #[inline]
+    pub fn to_duration(self, timestamp: u32) -> Duration {
+        match self {
+            TimestampInterpretation::Relative => Duration::from_micros(u64::from(timestamp) * 100),
+            TimestampInterpretation::Absolute => Duration::from_secs(u64::from(timestamp)),
+        }
+    }
+}
+
+impl Default for TimestampInterpretation {
+    /// Returns [`TimestampInterpretation::Relative`] (as defined by the
+    /// DLT specification).
+    #[inline]
+    fn default() -> Self {
+        TimestampInterpretation::Relative
+    }
+}
+
+#[cfg(test)]
+mod timestamp_interpretation_tests {
+    use super::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn to_duration(timestamp in any::<u32>()) {
+            prop_assert_eq!(
+                Duration::from_micros(u64::from(timestamp) * 100),
+                TimestampInterpretation::Relative.to_duration(timestamp)
+            );
+            prop_assert_eq!(
+                Duration::from_secs(u64::from(timestamp)),
+                TimestampInterpretation::Absolute.to_duration(timestamp)
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn guess_interpretation(timestamp in any::<u32>()) {
+            let expected = if timestamp < TimestampInterpretation::MIN_ABSOLUTE {
+                TimestampInterpretation::Relative
+            } else {
+                TimestampInterpretation::Absolute
+            };
+            prop_assert_eq!(expected, TimestampInterpretation::guess_interpretation(timestamp));
+        }
+    }
+
+    #[test]
+    fn guess_boundaries() {
+        use TimestampInterpretation::*;
+        assert_eq!(Relative, TimestampInterpretation::guess_interpretation(0));
+        assert_eq!(
+            Relative,
+            TimestampInterpretation::guess_interpretation(
+                TimestampInterpretation::MIN_ABSOLUTE - 1
+            )
+        );
+        assert_eq!(
+            Absolute,
+            TimestampInterpretation::guess_interpretation(TimestampInterpretation::MIN_ABSOLUTE)
+        );
+        assert_eq!(
+            Absolute,
+            TimestampInterpretation::guess_interpretation(u32::MAX)
+        );
+        // maximum relative value is ~119 hours
+        assert_eq!(
+            Duration::from_micros(429_496_729_500),
+            Relative.to_duration(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn debug_clone_eq_default() {
+        let value = TimestampInterpretation::Absolute;
+        assert_eq!(value, value.clone());
+        assert_eq!("Absolute", format!("{:?}", value));
+        assert_eq!(
+            TimestampInterpretation::Relative,
+            TimestampInterpretation::default()
+        );
+    }
+}
@@ -0,0 +1,337 @@
+/// Extends the 32 bit DLT header timestamps (0.1 milliseconds since
+/// the ECU startup) to monotonic 64 bit timestamps by detecting
+/// rollovers (the 32 bit timestamp wraps roughly every 119 hours).
+///
+/// Timestamps have to be passed in the order the messages were
+/// received. Timestamps up to [`TimestampTracker::reorder_window`]
+/// behind the newest timestamp are treated as out of order arrivals
+/// and do not trigger a wrap detection.
+///
+/// Larger backward jumps are only treated as a wrap if the timestamp
+/// went from close to `u32::MAX` to close to 0 (at most
+/// [`TimestampTracker::wrap_window`] across the wrap). Any other backward
+/// jump (e.g. caused by a restart of the ECU) is treated as a reset.
+/// After a reset the tracking continues from the new timestamp offset by
+/// the newest extended timestamp before the reset, so the extended
+/// timestamps never decrease (except for out of order arrivals).
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::TimestampTracker;
+///
+/// let mut tracker = TimestampTracker::new();
+/// assert_eq!(u64::from(u32::MAX) - 1, tracker.update(u32::MAX - 1));
+/// // wrap
+/// assert_eq!(u64::from(u32::MAX) + 3, tracker.update(2));
+/// // out of order message from before the wrap
+/// assert_eq!(u64::from(u32::MAX), tracker.update(u32::MAX));
+///
+/// assert_eq!(Some(u64::from(u32::MAX) + 3), tracker.extended_timestamp());
+/// assert_eq!(1, tracker.wraps());
+///
+/// // restart of the ECU
+/// assert_eq!(u64::from(u32::MAX) + 50_000_001, tracker.update(50_000_000));
+/// assert_eq!(u64::from(u32::MAX) + 50_000_021, tracker.update(20));
+/// assert_eq!(0, tracker.wraps());
+/// assert_eq!(1, tracker.resets());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TimestampTracker {
+    /// Maximum distance (in 0.1 milliseconds) a timestamp can be behind
+    /// the newest timestamp to be treated as an out of order arrival.
+    reorder_window: u32,
+
+    /// Maximum distance (in 0.1 milliseconds) between the newest timestamp
+    /// & a timestamp after a wrap of the 32 bit value.
+    wrap_window: u32,
+
+    /// Newest extended timestamp since the start or the last reset.
+    newest: Option<u64>,
+
+    /// Sum of the newest extended timestamps before each reset (added
+    /// to the returned timestamps so they do not decrease on a reset).
+    offset: u64,
+
+    /// Number of detected timestamp resets.
+    resets: u64,
+}
+
+impl TimestampTracker {
+    /// Default reorder window (10 seconds in 0.1 milliseconds).
+    pub const DEFAULT_REORDER_WINDOW: u32 = 10 * 10_000;
+
+    /// Default wrap window (10 minutes in 0.1 milliseconds).
+    pub const DEFAULT_WRAP_WINDOW: u32 = 10 * 60 * 10_000;
+
+    /// Creates a tracker with the default reorder window.
+    #[inline]
+    pub fn new() -> TimestampTracker {
+        TimestampTracker::with_reorder_window(TimestampTracker::DEFAULT_REORDER_WINDOW)
+    }
+
+    /// Creates a tracker with the given reorder window (in 0.1 milliseconds)
+    /// & the default wrap window.
+    #[inline]
+    pub fn with_reorder_window(reorder_window: u32) -> TimestampTracker {
+        TimestampTracker::with_windows(reorder_window, TimestampTracker::DEFAULT_WRAP_WINDOW)
+    }
+
+    /// Creates a tracker with the given reorder & wrap window (both in
+    /// 0.1 milliseconds).
+    #[inline]
+    pub fn with_windows(reorder_window: u32, wrap_window: u32) -> TimestampTracker {
+        TimestampTracker {
+            reorder_window,
+            wrap_window,
+            newest: None,
+            offset: 0,
+            resets: 0,
+        }
+    }
+
+    /// Maximum distance (in 0.1 milliseconds) a timestamp can be behind
+    /// the newest timestamp to be treated as an out of order arrival.
+    #[inline]
+    pub fn reorder_window(&self) -> u32 {
+        self.reorder_window
+    }
+
+    /// Maximum distance (in 0.1 milliseconds) between the newest timestamp
+    /// & a timestamp after a wrap of the 32 bit value. Larger backward
+    /// jumps are treated as resets.
+    #[inline]
+    pub fn wrap_window(&self) -> u32 {
+        self.wrap_window
+    }
+
+    /// Feeds the next timestamp into the tracker & returns the extended
+    /// 64 bit timestamp of it.
+    pub fn update(&mut self, timestamp: u32) -> u64 {
+        let extended = self.update_since_reset(timestamp);
+        self.offset + extended
+    }
+
+    /// Updates the tracker & returns the extended timestamp without the
+    /// offset of previous resets.
+    fn update_since_reset(&mut self, timestamp: u32) -> u64 {
+        let newest = match self.newest {
+            Some(newest) => newest,
+            None => {
+                self.newest = Some(u64::from(timestamp));
+                return u64::from(timestamp);
+            }
+        };
+
+        // lower 32 bits of the newest timestamp
+        let last = newest as u32;
+
+        // out of order arrival (or the same timestamp)
+        let behind = last.wrapping_sub(timestamp);
+        if behind <= self.reorder_window && u64::from(behind) <= newest {
+            return newest - u64::from(behind);
+        }
+
+        // backward jump that is not a wrap from close to u32::MAX to
+        // close to 0 (e.g. restart of the ECU)
+        let forward = timestamp.wrapping_sub(last);
+        if timestamp < last && forward > self.wrap_window {
+            self.resets += 1;
+            self.offset += newest;
+            self.newest = Some(u64::from(timestamp));
+            return u64::from(timestamp);
+        }
+
+        // moving forward (wraps are handled by the wrapping sub)
+        let extended = newest + u64::from(forward);
+        self.newest = Some(extended);
+        extended
+    }
+
+    /// Returns the newest extended timestamp (`None` if no timestamp
+    /// has been passed to the tracker yet).
+    #[inline]
+    pub fn extended_timestamp(&self) -> Option<u64> {
+        self.newest.map(|v| self.offset + v)
+    }
+
+    /// Returns the number of detected rollovers of the 32 bit timestamp
+    /// since the start or the last reset.
+    #[inline]
+    pub fn wraps(&self) -> u64 {
+        self.newest.map(|v| v >> 32).unwrap_or(0)
+    }
+
+    /// Returns the number of detected timestamp resets (backward jumps
+    /// that are neither out of order arrivals nor wraps).
+    #[inline]
+    pub fn resets(&self) -> u64 {
+        self.resets
+    }
+}
+
+impl Default for TimestampTracker {
+    #[inline]
+    fn default() -> Self {
+        TimestampTracker::new()
+    }
+}
+
+#[cfg(test)]
+mod timestamp_tracker_tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn new_default() {
+        let tracker = TimestampTracker::new();
+        assert_eq!(
+            TimestampTracker::DEFAULT_REORDER_WINDOW,
+            tracker.reorder_window()
+        );
+        assert_eq!(None, tracker.extended_timestamp());
+        assert_eq!(0, tracker.wraps());
+        assert_eq!(0, tracker.resets());
+        assert_eq!(tracker, TimestampTracker::default());
+        assert_eq!(TimestampTracker::DEFAULT_WRAP_WINDOW, tracker.wrap_window());
+
+        let tracker = TimestampTracker::with_reorder_window(5);
+        assert_eq!(5, tracker.reorder_window());
+        assert_eq!(TimestampTracker::DEFAULT_WRAP_WINDOW, tracker.wrap_window());
+
+        let tracker = TimestampTracker::with_windows(5, 6);
+        assert_eq!(5, tracker.reorder_window());
+        assert_eq!(6, tracker.wrap_window());
+    }
+
+    #[test]
+    fn update() {
+        let mut tracker = TimestampTracker::with_reorder_window(10);
+        assert_eq!(100, tracker.update(100));
+        assert_eq!(100, tracker.update(100));
+        assert_eq!(120, tracker.update(120));
+
+        // out of order within the window
+        assert_eq!(110, tracker.update(110));
+        assert_eq!(Some(120), tracker.extended_timestamp());
+
+        // going back further then the window is treated as reset
+        assert_eq!(120 + 109, tracker.update(109));
+        assert_eq!(0, tracker.wraps());
+        assert_eq!(1, tracker.resets());
+        assert_eq!(120 + 100, tracker.update(100));
+        assert_eq!(Some(120 + 109), tracker.extended_timestamp());
+
+        // wrap with an out of order arrival from before the wrap
+        let mut tracker = TimestampTracker::with_reorder_window(10);
+        assert_eq!(0xffff_fff0, tracker.update(0xffff_fff0));
+        assert_eq!(1 << 32 | 5, tracker.update(5));
+        assert_eq!(0xffff_fffc, tracker.update(0xffff_fffc));
+        assert_eq!(Some(1 << 32 | 5), tracker.extended_timestamp());
+        assert_eq!(1, tracker.wraps());
+
+        // wrap at the edge of the wrap window
+        let mut tracker = TimestampTracker::with_windows(10, 20);
+        assert_eq!(0xffff_fff0, tracker.update(0xffff_fff0));
+        assert_eq!(1 << 32 | 4, tracker.update(4));
+        assert_eq!(1, tracker.wraps());
+        assert_eq!(0, tracker.resets());
+
+        let mut tracker = TimestampTracker::with_windows(10, 20);
+        assert_eq!(0xffff_fff0, tracker.update(0xffff_fff0));
+        assert_eq!(0xffff_fff0 + 5, tracker.update(5));
+        assert_eq!(0, tracker.wraps());
+        assert_eq!(1, tracker.resets());
+
+        // no negative values for out of order arrivals directly at the start
+        let mut tracker = TimestampTracker::with_reorder_window(10);
+        assert_eq!(5, tracker.update(5));
+        assert_eq!(0xffff_fffe, tracker.update(0xffff_fffe));
+        assert_eq!(0, tracker.wraps());
+    }
+
+    #[test]
+    fn restart() {
+        // ECU restart after 3 hours (after a previous wrap)
+        let mut tracker = TimestampTracker::new();
+        let before = 3 * 60 * 60 * 10_000;
+        assert_eq!(0xffff_ff00, tracker.update(0xffff_ff00));
+        assert_eq!(1 << 32 | 100, tracker.update(100));
+        assert_eq!(1 << 32 | before, tracker.update(before as u32));
+        assert_eq!(1, tracker.wraps());
+
+        let offset = 1 << 32 | before;
+        assert_eq!(offset + 50, tracker.update(50));
+        assert_eq!(Some(offset + 50), tracker.extended_timestamp());
+        assert_eq!(0, tracker.wraps());
+        assert_eq!(1, tracker.resets());
+
+        // tracking continues after the restart
+        assert_eq!(offset + 1_000, tracker.update(1_000));
+        assert_eq!(offset + 900, tracker.update(900));
+        assert_eq!(Some(offset + 1_000), tracker.extended_timestamp());
+
+        // second restart
+        assert_eq!(offset + 500_000, tracker.update(500_000));
+        assert_eq!(offset + 500_000 + 10, tracker.update(10));
+        assert_eq!(2, tracker.resets());
+
+        // restart shortly before the 32 bit timestamp would have wrapped
+        let mut tracker = TimestampTracker::new();
+        let late = u32::MAX - TimestampTracker::DEFAULT_WRAP_WINDOW;
+        assert_eq!(u64::from(late), tracker.update(late));
+        assert_eq!(u64::from(late) + 10, tracker.update(10));
+        assert_eq!(0, tracker.wraps());
+        assert_eq!(1, tracker.resets());
+    }
+
+    proptest! {
+        #[test]
+        fn monotonic_over_wraps(
+            start in any::<u32>(),
+            steps in proptest::collection::vec(0u32..0x4000_0000, 0..40),
+            window in 0u32..1000,
+        ) {
+            let mut tracker = TimestampTracker::with_windows(window, 0x4000_0000);
+            let mut expected = u64::from(start);
+            prop_assert_eq!(expected, tracker.update(start));
+            for step in steps {
+                expected += u64::from(step);
+                prop_assert_eq!(expected, tracker.update(expected as u32));
+                prop_assert_eq!(Some(expected), tracker.extended_timestamp());
+                prop_assert_eq!(expected >> 32, tracker.wraps());
+
+                // out of order arrivals do not change the newest timestamp
+                let late = expected.saturating_sub(u64::from(window));
+                prop_assert_eq!(late, tracker.update(late as u32));
+                prop_assert_eq!(Some(expected), tracker.extended_timestamp());
+            }
+        }
+
+        #[test]
+        fn monotonic_over_resets(
+            timestamps in proptest::collection::vec(any::<u32>(), 0..40),
+            window in 0u32..1000,
+        ) {
+            // without a reorder window the extended timestamps never decrease
+            let mut tracker = TimestampTracker::with_reorder_window(0);
+            let mut last = 0;
+            for timestamp in &timestamps {
+                let extended = tracker.update(*timestamp);
+                prop_assert!(extended >= last);
+                prop_assert_eq!(Some(extended), tracker.extended_timestamp());
+                last = extended;
+            }
+
+            // with a reorder window the newest timestamp never decreases
+            let mut tracker = TimestampTracker::with_reorder_window(window);
+            let mut newest = 0;
+            for timestamp in &timestamps {
+                tracker.update(*timestamp);
+                let extended = tracker.extended_timestamp().unwrap();
+                prop_assert!(extended >= newest);
+                newest = extended;
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use crate::error::PacketSliceError;
+use crate::DltPacketSlice;
+use std::vec::Vec;
+
+/// Result of [`truncate_payload`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PayloadTruncation {
+    /// The payload already was at most `max_payload` bytes long and
+    /// the packet was left unchanged.
+    Unchanged,
+
+    /// The payload of a non verbose message (or a message without
+    /// extended header) was truncated.
+    Truncated,
+
+    /// The payload of a verbose message was truncated.
+    ///
+    /// The arguments of verbose messages are not aligned to the new
+    /// payload length, so the last argument is most likely cut off and
+    /// decoding the payload will fail.
+    TruncatedVerbose,
+}
+
+/// Shortens the payload of the DLT packet at the start of `buf` to
+/// at most `max_payload` bytes & updates the length field in the header.
+///
+/// The header is kept intact and data following the packet in `buf`
+/// is preserved. For non verbose messages the message id is counted as
+/// part of the payload (same as in [`DltPacketSlice::payload`]).
+///
+/// Truncating a verbose message will most likely cut through an argument,
+/// this is signaled by the returned [`PayloadTruncation::TruncatedVerbose`].
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{truncate_payload, DltHeader, DltPacketSlice, PayloadTruncation};
+///
+/// let mut header = DltHeader {
+///     is_big_endian: true,
+///     message_counter: 0,
+///     length: 0,
+///     ecu_id: None,
+///     session_id: None,
+///     timestamp: None,
+///     extended_header: None,
+/// };
+/// header.length = header.header_len() + 6;
+///
+/// let mut buffer = Vec::new();
+/// header.write(&mut buffer).unwrap();
+/// buffer.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+///
+/// assert_eq!(Ok(PayloadTruncation::Truncated), truncate_payload(&mut buffer, 4));
+/// let slice = DltPacketSlice::from_slice(&buffer).unwrap();
+/// assert_eq!(&[1, 2, 3, 4], slice.payload());
+/// ```
+pub fn truncate_payload(
+    buf: &mut Vec<u8>,
+    max_payload: usize,
+) -> Result<PayloadTruncation, PacketSliceError> {
+    let (header_len, payload_len, is_verbose) = {
+        let slice = DltPacketSlice::from_slice(buf)?;
+        (
+            slice.slice().len() - slice.payload().len(),
+            slice.payload().len(),
+            slice.is_verbose(),
+        )
+    };
+
+    if payload_len <= max_payload {
+        return Ok(PayloadTruncation::Unchanged);
+    }
+
+    // remove the payload bytes & update the length field
+    let new_len = header_len + max_payload;
+    buf.drain(new_len..header_len + payload_len);
+    // the length can not grow so it is guaranteed to fit into an u16
+    let len_be = (new_len as u16).to_be_bytes();
+    buf[2] = len_be[0];
+    buf[3] = len_be[1];
+
+    Ok(if is_verbose {
+        PayloadTruncation::TruncatedVerbose
+    } else {
+        PayloadTruncation::Truncated
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Layer;
+    use crate::error::UnexpectedEndOfSliceError;
+    use crate::proptest_generators::*;
+    use crate::test_packets::packet;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    #[test]
+    fn debug_clone_eq() {
+        let v = PayloadTruncation::TruncatedVerbose;
+        assert_eq!(v, v.clone());
+        assert_eq!("TruncatedVerbose", format!("{:?}", v));
+    }
+
+    proptest! {
+        #[test]
+        fn truncate(
+            ref header in dlt_header_any(),
+            ref payload in proptest::collection::vec(any::<u8>(), 0..100),
+            ref trailing in proptest::collection::vec(any::<u8>(), 0..10),
+            max_payload in 0usize..120,
+        ) {
+            let mut buf = packet(header.clone(), payload);
+            buf.extend_from_slice(trailing);
+
+            let result = truncate_payload(&mut buf, max_payload);
+
+            let slice = DltPacketSlice::from_slice(&buf).unwrap();
+            if payload.len() <= max_payload {
+                prop_assert_eq!(Ok(PayloadTruncation::Unchanged), result);
+                prop_assert_eq!(&payload[..], slice.payload());
+            } else {
+                let is_verbose = header
+                    .extended_header
+                    .as_ref()
+                    .map(|e| e.is_verbose())
+                    .unwrap_or(false);
+                if is_verbose {
+                    prop_assert_eq!(Ok(PayloadTruncation::TruncatedVerbose), result);
+                } else {
+                    prop_assert_eq!(Ok(PayloadTruncation::Truncated), result);
+                }
+                prop_assert_eq!(&payload[..max_payload], slice.payload());
+            }
+
+            // header & trailing data are kept
+            let mut expected_header = header.clone();
+            expected_header.length = slice.slice().len() as u16;
+            prop_assert_eq!(expected_header, slice.header());
+            prop_assert_eq!(&trailing[..], &buf[slice.slice().len()..]);
+        }
+    }
+
+    #[test]
+    fn truncate_error() {
+        let mut buf = Vec::from([0, 0, 0]);
+        assert_eq!(
+            Err(PacketSliceError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::DltHeader,
+                    minimum_size: 4,
+                    actual_size: 3,
+                }
+            )),
+            truncate_payload(&mut buf, 0)
+        );
+        assert_eq!(&[0, 0, 0], &buf[..]);
+    }
+}
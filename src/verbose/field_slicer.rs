@@ -9,12 +9,28 @@ pub(crate) struct FieldSlicer<'a> {
 
     /// Offset since the parsing has started.
     offset: usize,
+
+    /// True if variable names & units without a terminating zero are
+    /// accepted.
+    allow_missing_termination: bool,
 }
 
 impl<'a> FieldSlicer<'a> {
     #[inline]
-    pub fn new(data: &[u8], offset: usize) -> FieldSlicer {
-        FieldSlicer { rest: data, offset }
+    pub fn new(data: &[u8], offset: usize) -> FieldSlicer<'_> {
+        FieldSlicer {
+            rest: data,
+            offset,
+            allow_missing_termination: false,
+        }
+    }
+
+    /// Sets if variable names & units without a terminating zero are
+    /// accepted (the last byte is then part of the string).
+    #[inline]
+    pub fn with_allow_missing_termination(mut self, allow: bool) -> FieldSlicer<'a> {
+        self.allow_missing_termination = allow;
+        self
     }
 
     #[inline]
@@ -22,170 +38,56 @@ impl<'a> FieldSlicer<'a> {
         self.rest
     }
 
-    pub fn read_u8(&mut self) -> Result<u8, VerboseDecodeError> {
-        use VerboseDecodeError::*;
-
-        // check length
-        if self.rest.is_empty() {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseValue,
-                minimum_size: self.offset + 1,
+    /// Returns the next `len` bytes without moving the slice (or an
+    /// unexpected end of slice error if less bytes are left).
+    #[inline]
+    fn peek(&self, len: usize, layer: Layer) -> Result<&'a [u8], VerboseDecodeError> {
+        self.rest.get(..len).ok_or_else(|| {
+            VerboseDecodeError::UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer,
+                minimum_size: self.offset + len,
                 actual_size: self.offset + self.rest.len(),
-            }));
-        }
-
-        // SAFETY: Length of at least 1 verified in the previous if.
-        let result = unsafe { *self.rest.get_unchecked(0) };
+            })
+        })
+    }
 
-        // move slice
-        // SAFETY: Length of at least 1 verified in the previous if.
-        self.rest =
-            unsafe { core::slice::from_raw_parts(self.rest.as_ptr().add(1), self.rest.len() - 1) };
-        self.offset += 1;
+    /// Moves the slice & offset by `len` bytes (`len` has to be checked
+    /// beforehand via [`FieldSlicer::peek`]).
+    #[inline]
+    fn advance(&mut self, len: usize) {
+        self.rest = &self.rest[len..];
+        self.offset += len;
+    }
 
+    #[inline]
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], VerboseDecodeError> {
+        let mut result = [0u8; N];
+        result.copy_from_slice(self.read_raw(N)?);
         Ok(result)
     }
 
+    pub fn read_u8(&mut self) -> Result<u8, VerboseDecodeError> {
+        self.read_array::<1>().map(|bytes| bytes[0])
+    }
+
     pub fn read_i8(&mut self) -> Result<i8, VerboseDecodeError> {
         Ok(i8::from_ne_bytes([self.read_u8()?]))
     }
 
     pub fn read_2bytes(&mut self) -> Result<[u8; 2], VerboseDecodeError> {
-        use VerboseDecodeError::*;
-
-        // check length
-        if self.rest.len() < 2 {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseValue,
-                minimum_size: self.offset + 2,
-                actual_size: self.offset + self.rest.len(),
-            }));
-        }
-
-        // read value
-        // SAFETY: Length of at least 2 verified in the previous if.
-        let result = unsafe { [*self.rest.get_unchecked(0), *self.rest.get_unchecked(1)] };
-
-        // move slice
-        // SAFETY: Length of at least 2 verified in the previous if.
-        self.rest =
-            unsafe { core::slice::from_raw_parts(self.rest.as_ptr().add(2), self.rest.len() - 2) };
-        self.offset += 2;
-
-        Ok(result)
+        self.read_array()
     }
 
     pub fn read_4bytes(&mut self) -> Result<[u8; 4], VerboseDecodeError> {
-        use VerboseDecodeError::*;
-
-        // check length
-        if self.rest.len() < 4 {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseValue,
-                minimum_size: self.offset + 4,
-                actual_size: self.offset + self.rest.len(),
-            }));
-        }
-
-        // read value
-        // SAFETY: Length of at least 4 verified in the previous if.
-        let result = unsafe {
-            [
-                *self.rest.get_unchecked(0),
-                *self.rest.get_unchecked(1),
-                *self.rest.get_unchecked(2),
-                *self.rest.get_unchecked(3),
-            ]
-        };
-
-        // move slice
-        // SAFETY: Length of at least 4 verified in the previous if.
-        self.rest =
-            unsafe { core::slice::from_raw_parts(self.rest.as_ptr().add(4), self.rest.len() - 4) };
-        self.offset += 4;
-
-        Ok(result)
+        self.read_array()
     }
 
     pub fn read_8bytes(&mut self) -> Result<[u8; 8], VerboseDecodeError> {
-        use VerboseDecodeError::*;
-
-        // check length
-        if self.rest.len() < 8 {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseValue,
-                minimum_size: self.offset + 8,
-                actual_size: self.offset + self.rest.len(),
-            }));
-        }
-
-        // read value
-        // SAFETY: Length of at least 8 verified in the previous if.
-        let result = unsafe {
-            [
-                *self.rest.get_unchecked(0),
-                *self.rest.get_unchecked(1),
-                *self.rest.get_unchecked(2),
-                *self.rest.get_unchecked(3),
-                *self.rest.get_unchecked(4),
-                *self.rest.get_unchecked(5),
-                *self.rest.get_unchecked(6),
-                *self.rest.get_unchecked(7),
-            ]
-        };
-
-        // move slice
-        // SAFETY: Length of at least 8 verified in the previous if.
-        self.rest =
-            unsafe { core::slice::from_raw_parts(self.rest.as_ptr().add(8), self.rest.len() - 8) };
-        self.offset += 8;
-
-        Ok(result)
+        self.read_array()
     }
 
     pub fn read_16bytes(&mut self) -> Result<[u8; 16], VerboseDecodeError> {
-        use VerboseDecodeError::*;
-
-        // check length
-        if self.rest.len() < 16 {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseValue,
-                minimum_size: self.offset + 16,
-                actual_size: self.offset + self.rest.len(),
-            }));
-        }
-
-        // read value
-        // SAFETY: Length of at least 16 verified in the previous if.
-        let result = unsafe {
-            [
-                *self.rest.get_unchecked(0),
-                *self.rest.get_unchecked(1),
-                *self.rest.get_unchecked(2),
-                *self.rest.get_unchecked(3),
-                *self.rest.get_unchecked(4),
-                *self.rest.get_unchecked(5),
-                *self.rest.get_unchecked(6),
-                *self.rest.get_unchecked(7),
-                *self.rest.get_unchecked(8),
-                *self.rest.get_unchecked(9),
-                *self.rest.get_unchecked(10),
-                *self.rest.get_unchecked(11),
-                *self.rest.get_unchecked(12),
-                *self.rest.get_unchecked(13),
-                *self.rest.get_unchecked(14),
-                *self.rest.get_unchecked(15),
-            ]
-        };
-
-        // move slice
-        // SAFETY: Length of at least 8 verified in the previous if.
-        self.rest = unsafe {
-            core::slice::from_raw_parts(self.rest.as_ptr().add(16), self.rest.len() - 16)
-        };
-        self.offset += 16;
-
-        Ok(result)
+        self.read_array()
     }
 
     pub fn read_u16(&mut self, is_big_endian: bool) -> Result<u16, VerboseDecodeError> {
@@ -297,72 +199,22 @@ impl<'a> FieldSlicer<'a> {
     }
 
     pub fn read_var_name(&mut self, is_big_endian: bool) -> Result<&'a str, VerboseDecodeError> {
-        use VerboseDecodeError::*;
-
-        // check length
-        if self.rest.len() < 2 {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseValue,
-                minimum_size: self.offset + 2,
-                actual_size: self.offset + self.rest.len(),
-            }));
-        }
-
-        // read lengths
+        // read length
         let name_length = {
-            // SAFETY: Length of at least 2 verified in the previous if.
-            let bytes = unsafe { [*self.rest.get_unchecked(0), *self.rest.get_unchecked(1)] };
-            if is_big_endian {
-                u16::from_be_bytes(bytes) as usize
-            } else {
-                u16::from_le_bytes(bytes) as usize
-            }
+            let bytes = self.peek(2, Layer::VerboseValue)?;
+            usize::from(to_u16([bytes[0], bytes[1]], is_big_endian))
         };
 
-        // check length of slice
-        let total_size = 2 + name_length;
-        if self.rest.len() < total_size {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseValue,
-                minimum_size: self.offset + total_size,
-                actual_size: self.offset + self.rest.len(),
-            }));
-        }
-
         // read name
-        let name = if name_length > 0 {
-            // SAFETY: Length of at least 2 + name_length verified in the previous if.
-            //         Additionally name_length is guranteed to be at least 1.
-            let name_raw = unsafe {
-                core::slice::from_raw_parts(
-                    self.rest.as_ptr().add(2),
-                    // substract 1 to skip the zero termination
-                    name_length - 1,
-                )
-            };
-            // SAFETY: Length of at least 2 + name_length verified in the previous if.
-            //         Additionally name_length is guranteed to be at least 1.
-            let last = unsafe { *self.rest.as_ptr().add(2 + name_length - 1) };
-
-            // check for zero termination
-            if last != 0 {
-                return Err(VariableNameStringMissingNullTermination);
-            }
-
-            core::str::from_utf8(name_raw)?
-        } else {
-            ""
-        };
+        let total_size = 2 + name_length;
+        let data = self.peek(total_size, Layer::VerboseValue)?;
+        let name = self.to_str(
+            &data[2..],
+            VerboseDecodeError::VariableNameStringMissingNullTermination,
+        )?;
 
         // move slice
-        // SAFETY: Length of at least total_size verfied in previous if.
-        self.rest = unsafe {
-            core::slice::from_raw_parts(
-                self.rest.as_ptr().add(total_size),
-                self.rest.len() - total_size,
-            )
-        };
-        self.offset += total_size;
+        self.advance(total_size);
 
         Ok(name)
     }
@@ -373,130 +225,62 @@ impl<'a> FieldSlicer<'a> {
     ) -> Result<(&'a str, &'a str), VerboseDecodeError> {
         use VerboseDecodeError::*;
 
-        // check length
-        if self.rest.len() < 4 {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseValue,
-                minimum_size: self.offset + 4,
-                actual_size: self.offset + self.rest.len(),
-            }));
-        }
-
         // read lengths
-        let name_length = {
-            // SAFETY: Length of at least 4 verified in the previous if.
-            let bytes = unsafe { [*self.rest.get_unchecked(0), *self.rest.get_unchecked(1)] };
-            if is_big_endian {
-                u16::from_be_bytes(bytes) as usize
-            } else {
-                u16::from_le_bytes(bytes) as usize
-            }
-        };
-        let unit_length = {
-            // SAFETY: Length of at least 4 verified in the previous if.
-            let bytes = unsafe { [*self.rest.get_unchecked(2), *self.rest.get_unchecked(3)] };
-            if is_big_endian {
-                u16::from_be_bytes(bytes) as usize
-            } else {
-                u16::from_le_bytes(bytes) as usize
-            }
+        let (name_length, unit_length) = {
+            let bytes = self.peek(4, Layer::VerboseValue)?;
+            (
+                usize::from(to_u16([bytes[0], bytes[1]], is_big_endian)),
+                usize::from(to_u16([bytes[2], bytes[3]], is_big_endian)),
+            )
         };
 
-        // check length of slice
-        let total_size = 4 + name_length + unit_length;
-        if self.rest.len() < total_size {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseValue,
-                minimum_size: self.offset + total_size,
-                actual_size: self.offset + self.rest.len(),
-            }));
-        }
-
-        // read name
-        let name = if name_length > 0 {
-            // SAFETY: Length of at least 4 + name_length verified in the previous if.
-            //         Additionally name_length is guranteed to be at least 1.
-            let name_raw = unsafe {
-                core::slice::from_raw_parts(
-                    self.rest.as_ptr().add(4),
-                    // substract 1 to skip the zero termination
-                    name_length - 1,
-                )
-            };
-            // SAFETY: Length of at least 4 + name_length verified in the previous if.
-            //         Additionally name_length is guranteed to be at least 1.
-            let last = unsafe { *self.rest.as_ptr().add(4 + name_length - 1) };
-
-            // check for zero termination
-            if last != 0 {
-                return Err(VariableNameStringMissingNullTermination);
-            }
-
-            core::str::from_utf8(name_raw)?
-        } else {
-            ""
+        // check length of slice (checked as the sum can overflow
+        // on 16 bit targets)
+        let total_size = match 4usize
+            .checked_add(name_length)
+            .and_then(|v| v.checked_add(unit_length))
+        {
+            Some(total_size) if total_size <= self.rest.len() => total_size,
+            total_size => {
+                return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                    layer: Layer::VerboseValue,
+                    minimum_size: self.offset.saturating_add(total_size.unwrap_or(usize::MAX)),
+                    actual_size: self.offset + self.rest.len(),
+                }));
+            }
         };
 
-        // read unit
-        let unit = if unit_length > 0 {
-            // SAFETY: Length of at least 4 + name_length + unit_length verified in the previous if.
-            //         Additionally unit_length is guranteed to be at least 1.
-            let unit_raw = unsafe {
-                core::slice::from_raw_parts(
-                    self.rest.as_ptr().add(4 + name_length),
-                    // substract 1 to skip the zero termination
-                    unit_length - 1,
-                )
-            };
-            // SAFETY: Length of at least 4 + name_length + unit_length verified in the previous if.
-            //         Additionally unit_length is guranteed to be at least 1.
-            let last = unsafe { *self.rest.as_ptr().add(4 + name_length + unit_length - 1) };
-
-            // check for zero termination
-            if last != 0 {
-                return Err(VariableUnitStringMissingNullTermination);
-            }
-
-            core::str::from_utf8(unit_raw)?
-        } else {
-            ""
-        };
+        // read name & unit
+        let (name_raw, unit_raw) = self.rest[4..total_size].split_at(name_length);
+        let name = self.to_str(name_raw, VariableNameStringMissingNullTermination)?;
+        let unit = self.to_str(unit_raw, VariableUnitStringMissingNullTermination)?;
 
         // move slice
-        // SAFETY: Length of at least total_size verfied in previous if.
-        self.rest = unsafe {
-            core::slice::from_raw_parts(
-                self.rest.as_ptr().add(total_size),
-                self.rest.len() - total_size,
-            )
-        };
-        self.offset += total_size;
+        self.advance(total_size);
 
         // done
         Ok((name, unit))
     }
 
-    pub fn read_raw(&mut self, len: usize) -> Result<&'a [u8], VerboseDecodeError> {
-        use VerboseDecodeError::*;
-
-        // check that the string length is present
-        if self.rest.len() < len {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseValue,
-                minimum_size: self.offset + len,
-                actual_size: self.offset + self.rest.len(),
-            }));
+    /// Decodes a variable name or unit (`data` including the terminating
+    /// zero). `missing_termination` is returned if the last byte is not
+    /// zero & missing terminations are not allowed.
+    fn to_str(
+        &self,
+        data: &'a [u8],
+        missing_termination: VerboseDecodeError,
+    ) -> Result<&'a str, VerboseDecodeError> {
+        match data.split_last() {
+            None => Ok(""),
+            Some((0, value)) => Ok(core::str::from_utf8(value)?),
+            Some(_) if self.allow_missing_termination => Ok(core::str::from_utf8(data)?),
+            Some(_) => Err(missing_termination),
         }
+    }
 
-        // SAFETY: Slice length checked above to be at least len
-        let result = unsafe { core::slice::from_raw_parts(self.rest.as_ptr(), len) };
-
-        // move rest & offset
-        self.rest = unsafe {
-            core::slice::from_raw_parts(self.rest.as_ptr().add(len), self.rest.len() - len)
-        };
-        self.offset += len;
-
+    pub fn read_raw(&mut self, len: usize) -> Result<&'a [u8], VerboseDecodeError> {
+        let result = self.peek(len, Layer::VerboseValue)?;
+        self.advance(len);
         Ok(result)
     }
 
@@ -551,44 +335,43 @@ impl<'a> FieldSlicer<'a> {
         &mut self,
         is_big_endian: bool,
     ) -> Result<ArrayDimensions<'a>, VerboseDecodeError> {
-        use VerboseDecodeError::*;
-
         // first read the number of dimensions
         let num_dims = self.read_u16(is_big_endian)?;
 
-        // check if enough data is present for the dimensions
+        // read the dimensions
         let len = usize::from(num_dims) * 2;
-        if self.rest.len() < len {
-            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                layer: Layer::VerboseTypeInfo,
-                minimum_size: self.offset + len,
-                actual_size: self.offset + self.rest.len(),
-            }));
-        }
+        let dimensions = self.peek(len, Layer::VerboseTypeInfo)?;
+        self.advance(len);
 
-        // safe array dimensions slice
-        let result = ArrayDimensions {
+        Ok(ArrayDimensions {
             is_big_endian,
-            dimensions: unsafe { core::slice::from_raw_parts(self.rest.as_ptr(), len) },
-        };
-
-        // move rest and offset
-        self.rest = unsafe {
-            core::slice::from_raw_parts(self.rest.as_ptr().add(len), self.rest.len() - len)
-        };
-        self.offset += len;
+            dimensions,
+        })
+    }
+}
 
-        Ok(result)
+/// Converts the given bytes to an u16 with the given endianness.
+#[inline]
+fn to_u16(bytes: [u8; 2], is_big_endian: bool) -> u16 {
+    if is_big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
     }
 }
 
 #[cfg(test)]
+#[allow(
+    clippy::identity_op,
+    clippy::len_zero,
+    clippy::needless_borrow,
+    clippy::unnecessary_cast
+)]
 mod test_field_slicer {
     use super::*;
     use alloc::vec::Vec;
     use proptest::collection::vec;
     use proptest::prelude::*;
-    use std::format;
 
     proptest! {
         #[test]
@@ -771,10 +554,7 @@ mod test_field_slicer {
             // ok
             {
                 let data = [value, 123, 234];
-                let mut slicer = FieldSlicer{
-                    rest: &data[..slice_len],
-                    offset,
-                };
+                let mut slicer = FieldSlicer::new(&data[..slice_len], offset);
                 prop_assert_eq!(
                     slicer.read_u8(),
                     Ok(value)
@@ -784,10 +564,7 @@ mod test_field_slicer {
             }
             // length error
             {
-                let mut slicer = FieldSlicer{
-                    rest: &[],
-                    offset,
-                };
+                let mut slicer = FieldSlicer::new(&[], offset);
                 prop_assert_eq!(
                     slicer.read_u8(),
                     Err(VerboseDecodeError::UnexpectedEndOfSlice(
@@ -812,10 +589,7 @@ mod test_field_slicer {
             // ok
             {
                 let data = [value as u8, i8::MIN as u8, i8::MAX as u8];
-                let mut slicer = FieldSlicer{
-                    rest: &data[..slice_len],
-                    offset,
-                };
+                let mut slicer = FieldSlicer::new(&data[..slice_len], offset);
                 prop_assert_eq!(
                     slicer.read_i8(),
                     Ok(value)
@@ -825,10 +599,7 @@ mod test_field_slicer {
             }
             // length error
             {
-                let mut slicer = FieldSlicer{
-                    rest: &[],
-                    offset,
-                };
+                let mut slicer = FieldSlicer::new(&[], offset);
                 prop_assert_eq!(
                     slicer.read_i8(),
                     Err(VerboseDecodeError::UnexpectedEndOfSlice(
@@ -1743,6 +1514,47 @@ mod test_field_slicer {
         }
     }
 
+    #[test]
+    fn read_var_name_and_unit_max_len() {
+        use VerboseDecodeError::*;
+
+        // maximum lengths with missing data
+        {
+            let data = [0xff, 0xff, 0xff, 0xff];
+            let mut slicer = FieldSlicer::new(&data, 10);
+            assert_eq!(
+                slicer.read_var_name_and_unit(false),
+                Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                    layer: Layer::VerboseValue,
+                    minimum_size: 10 + 4 + 0xffff + 0xffff,
+                    actual_size: 10 + 4,
+                }))
+            );
+            assert_eq!(slicer.offset, 10);
+            assert_eq!(slicer.rest, &data[..]);
+        }
+
+        // maximum lengths with all data present
+        {
+            let name = "a".repeat(0xfffe);
+            let unit = "b".repeat(0xfffe);
+            let mut buffer = Vec::with_capacity(4 + 0xffff + 0xffff);
+            buffer.extend_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+            buffer.extend_from_slice(name.as_bytes());
+            buffer.push(0);
+            buffer.extend_from_slice(unit.as_bytes());
+            buffer.push(0);
+
+            let mut slicer = FieldSlicer::new(&buffer, 0);
+            assert_eq!(
+                slicer.read_var_name_and_unit(true),
+                Ok((name.as_str(), unit.as_str()))
+            );
+            assert_eq!(slicer.offset, buffer.len());
+            assert!(slicer.rest.is_empty());
+        }
+    }
+
     proptest! {
         #[test]
         fn read_raw(
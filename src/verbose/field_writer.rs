@@ -0,0 +1,679 @@
+use crate::error::VerboseEncodeError;
+use arrayvec::ArrayVec;
+
+use super::{ArrayDimensions, RawF128, RawF16, Scaling};
+
+/// Writer for the fields of verbose arguments (e.g. type info, variable
+/// name & unit or scaling) to a DLT message buffer.
+///
+/// Can be used to write arguments field by field, e.g. by tools that
+/// rewrite messages. Each single write method either writes the complete
+/// field or returns an error without modifying the buffer. Arguments
+/// consisting of multiple fields can still be partially written if a
+/// later field fails (e.g. a name that is too long after the type info
+/// was written). Use [`FieldWriter::reset`] to remove the partially
+/// written argument in that case.
+///
+/// # Example
+///
+/// ```
+/// use arrayvec::ArrayVec;
+/// use dlt_parse::verbose::FieldWriter;
+///
+/// let mut buf = ArrayVec::<u8, 32>::new();
+/// let mut writer = FieldWriter::new(&mut buf);
+/// // UINT 8 bit with variable info (VARI)
+/// writer.write_type_info([0x41, 0, 0, 0], true, false).unwrap();
+/// writer.write_var_name_and_unit("speed", "km/h", false).unwrap();
+/// writer.write_u8(42).unwrap();
+/// assert_eq!(4 + 4 + 6 + 5 + 1, writer.offset());
+///
+/// // names that do not fit into the u16 length field are rejected
+/// let name = [b'a'; 0x1_0000];
+/// let name = core::str::from_utf8(&name).unwrap();
+/// assert!(writer.write_var_name(name, false).is_err());
+///
+/// // remove everything written by the writer
+/// writer.reset();
+/// assert!(buf.is_empty());
+/// ```
+pub struct FieldWriter<'a, const CAP: usize> {
+    /// Buffer the fields are written to.
+    buf: &'a mut ArrayVec<u8, CAP>,
+
+    /// Length of the buffer when the writing started.
+    start: usize,
+}
+
+impl<'a, const CAP: usize> FieldWriter<'a, CAP> {
+    const VARIABLE_INFO_FLAG_1: u8 = 0b0000_1000;
+    const FIXED_POINT_FLAG_1: u8 = 0b0001_0000;
+
+    /// Creates a writer appending the fields to the given buffer.
+    #[inline]
+    pub fn new(buf: &'a mut ArrayVec<u8, CAP>) -> FieldWriter<'a, CAP> {
+        let start = buf.len();
+        FieldWriter { buf, start }
+    }
+
+    /// Number of bytes written since the writer was created.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.buf.len() - self.start
+    }
+
+    /// Removes all bytes written since the writer was created.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.buf.truncate(self.start);
+    }
+
+    /// Writes the given bytes as they are.
+    pub fn write_raw(&mut self, data: &[u8]) -> Result<(), VerboseEncodeError> {
+        self.buf.try_extend_from_slice(data)?;
+        Ok(())
+    }
+
+    /// Writes the type info with the variable info (VARI) & fixed
+    /// point (FIXP) flags set if requested.
+    pub fn write_type_info(
+        &mut self,
+        mut type_info: [u8; 4],
+        has_variable_info: bool,
+        has_scaling: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        if has_variable_info {
+            type_info[1] |= Self::VARIABLE_INFO_FLAG_1;
+        }
+        if has_scaling {
+            type_info[1] |= Self::FIXED_POINT_FLAG_1;
+        }
+        self.write_raw(&type_info)
+    }
+
+    /// Writes an u8 value.
+    #[inline]
+    pub fn write_u8(&mut self, value: u8) -> Result<(), VerboseEncodeError> {
+        self.write_raw(&[value])
+    }
+
+    /// Writes an i8 value.
+    #[inline]
+    pub fn write_i8(&mut self, value: i8) -> Result<(), VerboseEncodeError> {
+        self.write_raw(&value.to_ne_bytes())
+    }
+
+    /// Writes an u16 value with the given endianness.
+    pub fn write_u16(&mut self, value: u16, is_big_endian: bool) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes an i16 value with the given endianness.
+    pub fn write_i16(&mut self, value: i16, is_big_endian: bool) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes an u32 value with the given endianness.
+    pub fn write_u32(&mut self, value: u32, is_big_endian: bool) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes an i32 value with the given endianness.
+    pub fn write_i32(&mut self, value: i32, is_big_endian: bool) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes an u64 value with the given endianness.
+    pub fn write_u64(&mut self, value: u64, is_big_endian: bool) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes an i64 value with the given endianness.
+    pub fn write_i64(&mut self, value: i64, is_big_endian: bool) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes an u128 value with the given endianness.
+    pub fn write_u128(
+        &mut self,
+        value: u128,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes an i128 value with the given endianness.
+    pub fn write_i128(
+        &mut self,
+        value: i128,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes a 16 bit float value with the given endianness.
+    pub fn write_f16(
+        &mut self,
+        value: RawF16,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes a f32 value with the given endianness.
+    pub fn write_f32(&mut self, value: f32, is_big_endian: bool) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes a f64 value with the given endianness.
+    pub fn write_f64(&mut self, value: f64, is_big_endian: bool) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes a 128 bit float value with the given endianness.
+    pub fn write_f128(
+        &mut self,
+        value: RawF128,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        if is_big_endian {
+            self.write_raw(&value.to_be_bytes())
+        } else {
+            self.write_raw(&value.to_le_bytes())
+        }
+    }
+
+    /// Writes a zero terminated name including its length (e.g. the
+    /// name of a variable info).
+    ///
+    /// Empty names are written as length 0 without a terminating zero.
+    pub fn write_var_name(
+        &mut self,
+        name: &str,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        let name_len = var_name_len(name).ok_or(VerboseEncodeError::NameTooLong(name.len()))?;
+        if self.buf.remaining_capacity() < 2 + usize::from(name_len) {
+            return Err(VerboseEncodeError::Capacity(arrayvec::CapacityError::new(
+                (),
+            )));
+        }
+        self.write_u16(name_len, is_big_endian)?;
+        self.write_var_string(name)
+    }
+
+    /// Writes the lengths & the zero terminated strings of a variable
+    /// info (VARI) name & unit.
+    ///
+    /// Empty names & units are written as length 0 without a terminating
+    /// zero.
+    pub fn write_var_name_and_unit(
+        &mut self,
+        name: &str,
+        unit: &str,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        let name_len = var_name_len(name).ok_or(VerboseEncodeError::NameTooLong(name.len()))?;
+        let unit_len = var_name_len(unit).ok_or(VerboseEncodeError::UnitTooLong(unit.len()))?;
+        if self.buf.remaining_capacity() < 4 + usize::from(name_len) + usize::from(unit_len) {
+            return Err(VerboseEncodeError::Capacity(arrayvec::CapacityError::new(
+                (),
+            )));
+        }
+        self.write_u16(name_len, is_big_endian)?;
+        self.write_u16(unit_len, is_big_endian)?;
+        self.write_var_string(name)?;
+        self.write_var_string(unit)
+    }
+
+    /// Writes the string & the terminating zero (nothing is written for
+    /// empty strings).
+    fn write_var_string(&mut self, value: &str) -> Result<(), VerboseEncodeError> {
+        if false == value.is_empty() {
+            self.write_raw(value.as_bytes())?;
+            self.write_u8(0)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the quantization & offset if a scaling is present.
+    pub fn write_i32_scaling(
+        &mut self,
+        scaling: &Option<Scaling<i32>>,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        if let Some(scaling) = scaling {
+            self.write_f32(scaling.quantization, is_big_endian)?;
+            self.write_i32(scaling.offset, is_big_endian)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the quantization & offset if a scaling is present.
+    pub fn write_i64_scaling(
+        &mut self,
+        scaling: &Option<Scaling<i64>>,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        if let Some(scaling) = scaling {
+            self.write_f32(scaling.quantization, is_big_endian)?;
+            self.write_i64(scaling.offset, is_big_endian)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the quantization & offset if a scaling is present.
+    pub fn write_i128_scaling(
+        &mut self,
+        scaling: &Option<Scaling<i128>>,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        if let Some(scaling) = scaling {
+            self.write_f32(scaling.quantization, is_big_endian)?;
+            self.write_i128(scaling.offset, is_big_endian)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the number of dimensions followed by the dimensions
+    /// (the dimensions are copied as they are).
+    pub fn write_array_dimensions(
+        &mut self,
+        dimensions: &ArrayDimensions<'_>,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        self.write_u16((dimensions.dimensions.len() / 2) as u16, is_big_endian)?;
+        self.write_raw(dimensions.dimensions)
+    }
+}
+
+/// Returns the encoded length of a variable name or unit (including
+/// the terminating zero, 0 for empty strings).
+#[inline]
+pub(crate) fn var_str_len(value: &str) -> usize {
+    if value.is_empty() {
+        0
+    } else {
+        value.len() + 1
+    }
+}
+
+/// Returns the value of the length field of a variable name or unit
+/// or `None` if the string is too long for the u16 length field.
+#[inline]
+pub(crate) fn var_name_len(value: &str) -> Option<u16> {
+    u16::try_from(var_str_len(value)).ok()
+}
+
+#[cfg(test)]
+mod test_field_writer {
+    use super::*;
+    use crate::verbose::FieldSlicer;
+    use arrayvec::CapacityError;
+    use proptest::prelude::*;
+
+    macro_rules! round_trip {
+        ($value:expr, $write:ident, $read:ident, $len:expr) => {
+            for is_big_endian in [false, true] {
+                // ok
+                {
+                    let mut buf = ArrayVec::<u8, 32>::new();
+                    buf.push(0xff);
+                    let mut writer = FieldWriter::new(&mut buf);
+                    writer.$write($value, is_big_endian).unwrap();
+                    assert_eq!($len, writer.offset());
+                    assert_eq!(1 + $len, buf.len());
+
+                    let mut slicer = FieldSlicer::new(&buf[1..], 0);
+                    assert_eq!($value, slicer.$read(is_big_endian).unwrap());
+                    assert!(slicer.rest().is_empty());
+                }
+                // buffer too small (buffer is not modified)
+                {
+                    let mut buf = ArrayVec::<u8, 32>::new();
+                    buf.extend(core::iter::repeat(0xff).take(32 - $len + 1));
+                    let mut writer = FieldWriter::new(&mut buf);
+                    assert_eq!(
+                        Err(VerboseEncodeError::Capacity(CapacityError::new(()))),
+                        writer.$write($value, is_big_endian)
+                    );
+                    assert_eq!(0, writer.offset());
+                    assert_eq!(32 - $len + 1, buf.len());
+                }
+            }
+        };
+    }
+
+    proptest! {
+        #[test]
+        fn write_primitives(
+            v_u8 in any::<u8>(),
+            v_i8 in any::<i8>(),
+            v_u16 in any::<u16>(),
+            v_i16 in any::<i16>(),
+            v_u32 in any::<u32>(),
+            v_i32 in any::<i32>(),
+            v_u64 in any::<u64>(),
+            v_i64 in any::<i64>(),
+            v_u128 in any::<u128>(),
+            v_i128 in any::<i128>(),
+            v_f16 in any::<u16>(),
+            v_f32 in any::<f32>().prop_filter("NaN", |v| !v.is_nan()),
+            v_f64 in any::<f64>().prop_filter("NaN", |v| !v.is_nan()),
+            v_f128 in any::<u128>(),
+        ) {
+            // u8 & i8 (no endianness)
+            {
+                let mut buf = ArrayVec::<u8, 2>::new();
+                let mut writer = FieldWriter::new(&mut buf);
+                writer.write_u8(v_u8).unwrap();
+                writer.write_i8(v_i8).unwrap();
+                prop_assert_eq!(
+                    Err(VerboseEncodeError::Capacity(CapacityError::new(()))),
+                    writer.write_u8(v_u8)
+                );
+                let mut slicer = FieldSlicer::new(&buf, 0);
+                prop_assert_eq!(v_u8, slicer.read_u8().unwrap());
+                prop_assert_eq!(v_i8, slicer.read_i8().unwrap());
+            }
+            round_trip!(v_u16, write_u16, read_u16, 2);
+            round_trip!(v_i16, write_i16, read_i16, 2);
+            round_trip!(v_u32, write_u32, read_u32, 4);
+            round_trip!(v_i32, write_i32, read_i32, 4);
+            round_trip!(v_u64, write_u64, read_u64, 8);
+            round_trip!(v_i64, write_i64, read_i64, 8);
+            round_trip!(v_u128, write_u128, read_u128, 16);
+            round_trip!(v_i128, write_i128, read_i128, 16);
+            round_trip!(RawF16::from_bits(v_f16), write_f16, read_f16, 2);
+            round_trip!(v_f32, write_f32, read_f32, 4);
+            round_trip!(v_f64, write_f64, read_f64, 8);
+            round_trip!(RawF128::from_bits(v_f128), write_f128, read_f128, 16);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn write_var_name_and_unit(
+            ref name in "\\PC{0,10}",
+            ref unit in "\\PC{0,10}",
+            is_big_endian in any::<bool>(),
+        ) {
+            // name
+            {
+                let mut buf = ArrayVec::<u8, 64>::new();
+                let mut writer = FieldWriter::new(&mut buf);
+                writer.write_var_name(name, is_big_endian).unwrap();
+                prop_assert_eq!(2 + var_str_len(name), writer.offset());
+                let mut slicer = FieldSlicer::new(&buf, 0);
+                prop_assert_eq!(Ok(&name[..]), slicer.read_var_name(is_big_endian));
+                prop_assert!(slicer.rest().is_empty());
+            }
+            // name & unit
+            {
+                let mut buf = ArrayVec::<u8, 128>::new();
+                let mut writer = FieldWriter::new(&mut buf);
+                writer.write_var_name_and_unit(name, unit, is_big_endian).unwrap();
+                prop_assert_eq!(4 + var_str_len(name) + var_str_len(unit), writer.offset());
+                let mut slicer = FieldSlicer::new(&buf, 0);
+                prop_assert_eq!(
+                    Ok((&name[..], &unit[..])),
+                    slicer.read_var_name_and_unit(is_big_endian)
+                );
+                prop_assert!(slicer.rest().is_empty());
+            }
+            // buffer too small (buffer is not modified)
+            {
+                let mut buf = ArrayVec::<u8, 1>::new();
+                let mut writer = FieldWriter::new(&mut buf);
+                prop_assert_eq!(
+                    Err(VerboseEncodeError::Capacity(CapacityError::new(()))),
+                    writer.write_var_name(name, is_big_endian)
+                );
+                prop_assert_eq!(
+                    Err(VerboseEncodeError::Capacity(CapacityError::new(()))),
+                    writer.write_var_name_and_unit(name, unit, is_big_endian)
+                );
+                prop_assert!(buf.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn write_var_name_and_unit_layout() {
+        // (string, expected length field, expected string bytes)
+        let cases: [(&str, u16, &[u8]); 3] = [
+            ("", 0, &[]),
+            ("abc", 4, b"abc\0"),
+            (
+                "\u{00e4}\u{1F980}",
+                7,
+                &[0xc3, 0xa4, 0xf0, 0x9f, 0xa6, 0x80, 0],
+            ),
+        ];
+        for is_big_endian in [false, true] {
+            let to_bytes = |v: u16| {
+                if is_big_endian {
+                    v.to_be_bytes()
+                } else {
+                    v.to_le_bytes()
+                }
+            };
+            for (name, name_len, name_bytes) in cases {
+                // name
+                {
+                    let mut buf = ArrayVec::<u8, 32>::new();
+                    FieldWriter::new(&mut buf)
+                        .write_var_name(name, is_big_endian)
+                        .unwrap();
+                    let mut expected = alloc::vec::Vec::new();
+                    expected.extend_from_slice(&to_bytes(name_len));
+                    expected.extend_from_slice(name_bytes);
+                    assert_eq!(&expected[..], &buf[..]);
+
+                    let mut slicer = FieldSlicer::new(&buf, 0);
+                    assert_eq!(Ok(name), slicer.read_var_name(is_big_endian));
+                    assert!(slicer.rest().is_empty());
+                }
+                for (unit, unit_len, unit_bytes) in cases {
+                    let mut buf = ArrayVec::<u8, 32>::new();
+                    FieldWriter::new(&mut buf)
+                        .write_var_name_and_unit(name, unit, is_big_endian)
+                        .unwrap();
+                    let mut expected = alloc::vec::Vec::new();
+                    expected.extend_from_slice(&to_bytes(name_len));
+                    expected.extend_from_slice(&to_bytes(unit_len));
+                    expected.extend_from_slice(name_bytes);
+                    expected.extend_from_slice(unit_bytes);
+                    assert_eq!(&expected[..], &buf[..]);
+
+                    let mut slicer = FieldSlicer::new(&buf, 0);
+                    assert_eq!(
+                        Ok((name, unit)),
+                        slicer.read_var_name_and_unit(is_big_endian)
+                    );
+                    assert!(slicer.rest().is_empty());
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn write_scaling(
+            quantization in any::<f32>().prop_filter("NaN", |v| !v.is_nan()),
+            offset in any::<i32>(),
+            is_big_endian in any::<bool>(),
+        ) {
+            let type_info = [0, 0b0001_0000, 0, 0];
+            let mut buf = ArrayVec::<u8, 64>::new();
+            let mut writer = FieldWriter::new(&mut buf);
+            writer.write_i32_scaling(&None, is_big_endian).unwrap();
+            prop_assert_eq!(0, writer.offset());
+            writer.write_i32_scaling(&Some(Scaling { quantization, offset }), is_big_endian).unwrap();
+            writer.write_i64_scaling(&Some(Scaling { quantization, offset: offset.into() }), is_big_endian).unwrap();
+            writer.write_i128_scaling(&Some(Scaling { quantization, offset: offset.into() }), is_big_endian).unwrap();
+            prop_assert_eq!(8 + 12 + 20, writer.offset());
+
+            let mut slicer = FieldSlicer::new(&buf, 0);
+            prop_assert_eq!(
+                Ok(Some(Scaling { quantization, offset })),
+                slicer.read_i32_scaling(is_big_endian, type_info)
+            );
+            prop_assert_eq!(
+                Ok(Some(Scaling { quantization, offset: i64::from(offset) })),
+                slicer.read_i64_scaling(is_big_endian, type_info)
+            );
+            prop_assert_eq!(
+                Ok(Some(Scaling { quantization, offset: i128::from(offset) })),
+                slicer.read_i128_scaling(is_big_endian, type_info)
+            );
+        }
+    }
+
+    #[test]
+    fn write_type_info() {
+        let mut buf = ArrayVec::<u8, 16>::new();
+        let mut writer = FieldWriter::new(&mut buf);
+        writer
+            .write_type_info([0b0010_0011, 0, 0, 0], false, false)
+            .unwrap();
+        writer
+            .write_type_info([0b0010_0011, 0, 0, 0], true, false)
+            .unwrap();
+        writer
+            .write_type_info([0b0010_0011, 0, 0, 0], false, true)
+            .unwrap();
+        writer
+            .write_type_info([0b0010_0011, 0b0000_0001, 0, 0], true, true)
+            .unwrap();
+        assert_eq!(
+            &[
+                0b0010_0011,
+                0b0000_0000,
+                0,
+                0, //
+                0b0010_0011,
+                0b0000_1000,
+                0,
+                0, //
+                0b0010_0011,
+                0b0001_0000,
+                0,
+                0, //
+                0b0010_0011,
+                0b0001_1001,
+                0,
+                0, //
+            ],
+            &buf[..]
+        );
+    }
+
+    #[test]
+    fn write_too_long_names() {
+        let long = "a".repeat(usize::from(u16::MAX));
+        let mut buf = ArrayVec::<u8, 16>::new();
+        let mut writer = FieldWriter::new(&mut buf);
+        assert_eq!(
+            Err(VerboseEncodeError::NameTooLong(long.len())),
+            writer.write_var_name(&long, false)
+        );
+        assert_eq!(
+            Err(VerboseEncodeError::NameTooLong(long.len())),
+            writer.write_var_name_and_unit(&long, "", false)
+        );
+        assert_eq!(
+            Err(VerboseEncodeError::UnitTooLong(long.len())),
+            writer.write_var_name_and_unit("", &long, false)
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn write_array_dimensions() {
+        for is_big_endian in [false, true] {
+            let dims = if is_big_endian {
+                [0, 2, 0, 3]
+            } else {
+                [2, 0, 3, 0]
+            };
+            let dimensions = ArrayDimensions {
+                is_big_endian,
+                dimensions: &dims,
+            };
+            let mut buf = ArrayVec::<u8, 16>::new();
+            let mut writer = FieldWriter::new(&mut buf);
+            writer
+                .write_array_dimensions(&dimensions, is_big_endian)
+                .unwrap();
+            assert_eq!(6, writer.offset());
+
+            let mut slicer = FieldSlicer::new(&buf, 0);
+            let read = slicer.read_array_dimesions(is_big_endian).unwrap();
+            assert_eq!(
+                alloc::vec![2, 3],
+                read.iter().collect::<alloc::vec::Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn reset() {
+        let mut buf = ArrayVec::<u8, 16>::new();
+        buf.push(0xff);
+        let mut writer = FieldWriter::new(&mut buf);
+        writer
+            .write_type_info([0x41, 0, 0, 0], true, false)
+            .unwrap();
+        writer.write_u8(1).unwrap();
+        assert_eq!(5, writer.offset());
+        writer.reset();
+        assert_eq!(0, writer.offset());
+        writer.write_u8(2).unwrap();
+        assert_eq!(&[0xff, 2], &buf[..]);
+    }
+}
@@ -1,9 +1,22 @@
 mod field_slicer;
 use field_slicer::*;
 
+mod field_writer;
+pub use field_writer::*;
+
 mod values;
 pub use values::*;
 
+#[cfg(feature = "std")]
+mod redact_strings;
+#[cfg(feature = "std")]
+pub use redact_strings::*;
+
+#[cfg(feature = "std")]
+mod streaming_verbose_decoder;
+#[cfg(feature = "std")]
+pub use streaming_verbose_decoder::*;
+
 mod verbose_iter;
 pub use verbose_iter::*;
 
@@ -13,21 +26,56 @@ pub use pre_checked_verbose_iter::*;
 mod verbose_value;
 pub use verbose_value::*;
 
+#[cfg(feature = "alloc")]
+mod utf16_string_value;
+#[cfg(feature = "alloc")]
+pub use utf16_string_value::*;
+
 use super::*;
 use core::str;
 
+/// Maximum nesting depth of verbose structs (structs containing structs)
+/// supported when decoding & encoding verbose values.
+pub const MAX_STRUCT_DEPTH: usize = 32;
+
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Scaling<T: Sized> {
-    quantization: f32,
-    offset: T,
+    /// Factor the raw value gets multiplied with (FIXP quantization).
+    pub quantization: f32,
+    /// Offset added after the quantization (FIXP offset).
+    pub offset: T,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VariableInfoUnit<'a> {
-    name: &'a str,
-    unit: &'a str,
+    /// Name of the variable (VARI).
+    pub name: &'a str,
+    /// Unit of the variable (VARI).
+    pub unit: &'a str,
+}
+
+/// String coding (SCOD) used when encoding strings.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum StringCoding {
+    /// ASCII (only characters in the ASCII range are allowed).
+    Ascii,
+    /// UTF-8
+    Utf8,
+}
+
+impl StringCoding {
+    /// Bits that have to be set in the second type info byte to
+    /// signal the string coding.
+    #[inline]
+    pub(crate) const fn type_info_flag_1(self) -> u8 {
+        match self {
+            StringCoding::Ascii => 0b0000_0000,
+            StringCoding::Utf8 => 0b1000_0000,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -46,6 +94,36 @@ impl<'a> ArrayDimensions<'a> {
             rest: self.dimensions,
         }
     }
+
+    /// Number of elements described by the dimensions (product of
+    /// all dimensions). Returns `None` if the calculation overflows.
+    ///
+    /// An array without any dimensions contains no elements.
+    pub fn element_count(&self) -> Option<usize> {
+        element_count(self.iter())
+    }
+}
+
+/// Returns the dimension at index `i` for arrays used in tests, chosen so
+/// that the product of all `dim_count` dimensions equals `1 + 2 + .. + dim_count`
+/// (the number of elements generated by the array tests).
+#[cfg(test)]
+pub(crate) fn test_dimension(i: u16, dim_count: u16) -> u16 {
+    if i + 1 == dim_count {
+        dim_count * (dim_count + 1) / 2
+    } else {
+        1
+    }
+}
+
+/// Calculates the number of elements of an array with the given dimensions.
+pub(crate) fn element_count(mut dimensions: impl Iterator<Item = u16>) -> Option<usize> {
+    match dimensions.next() {
+        Some(first) => {
+            dimensions.try_fold(usize::from(first), |acc, d| acc.checked_mul(usize::from(d)))
+        }
+        None => Some(0),
+    }
 }
 
 impl<'a> IntoIterator for &'a ArrayDimensions<'a> {
@@ -0,0 +1,225 @@
+use super::VerboseValue;
+use crate::error::{RedactError, VerboseEncodeError};
+use std::vec::Vec;
+
+/// Replaces the value of the string arguments of a verbose payload for
+/// which `predicate` returns true with `replacement` & returns the
+/// rewritten payload.
+///
+/// `predicate` is called for each string argument with the index of the
+/// argument (counting all arguments, not only strings) & the variable name
+/// of the argument (if present).
+///
+/// The type info & variable name of redacted arguments are kept, only
+/// the length field & the value are replaced. All other arguments (as
+/// well as any data after the last argument) are copied byte for byte. As
+/// the length of the payload can change the caller is responsible for
+/// updating the length in the DLT header when re-emitting the message.
+///
+/// Returns an error if the payload can not be decoded or if the
+/// replacement can not be encoded (e.g. it contains non ASCII characters
+/// & a redacted string uses the ASCII string coding).
+///
+/// # Example
+///
+/// ```
+/// use arrayvec::ArrayVec;
+/// use dlt_parse::verbose::{redact_strings, StringValue, U32Value, VerboseIter, VerboseValue};
+///
+/// let mut payload = ArrayVec::<u8, 100>::new();
+/// StringValue { name: Some("user"), value: "jane.doe" }
+///     .add_to_msg(&mut payload, false)
+///     .unwrap();
+/// U32Value { variable_info: None, scaling: None, value: 42 }
+///     .add_to_msg(&mut payload, false)
+///     .unwrap();
+///
+/// let redacted = redact_strings(&payload, false, 2, "***", |_, name| {
+///     name == Some("user")
+/// })
+/// .unwrap();
+///
+/// let mut iter = VerboseIter::new(false, 2, &redacted);
+/// assert_eq!(
+///     Some(Ok(VerboseValue::Str(StringValue { name: Some("user"), value: "***" }))),
+///     iter.next()
+/// );
+/// assert_eq!(
+///     Some(Ok(VerboseValue::U32(U32Value { variable_info: None, scaling: None, value: 42 }))),
+///     iter.next()
+/// );
+/// ```
+pub fn redact_strings<F>(
+    payload: &[u8],
+    is_big_endian: bool,
+    number_of_arguments: u16,
+    replacement: &str,
+    mut predicate: F,
+) -> Result<Vec<u8>, RedactError>
+where
+    F: FnMut(usize, Option<&str>) -> bool,
+{
+    // lengths include the terminating zero
+    if replacement.len() >= usize::from(u16::MAX) {
+        return Err(VerboseEncodeError::StringTooLong(replacement.len()).into());
+    }
+
+    let mut result = Vec::with_capacity(payload.len());
+    let mut rest = payload;
+    for index in 0..usize::from(number_of_arguments) {
+        let (value, next) = VerboseValue::from_slice(rest, is_big_endian)?;
+        let raw = &rest[..rest.len() - next.len()];
+        rest = next;
+
+        let value = match value {
+            VerboseValue::Str(value) if predicate(index, value.name) => value,
+            _ => {
+                result.extend_from_slice(raw);
+                continue;
+            }
+        };
+
+        // string coding 0 (ASCII) is signaled by the highest bit of the
+        // second type info byte not being set
+        if 0 == raw[1] & 0b1000_0000 && false == replacement.is_ascii() {
+            return Err(VerboseEncodeError::StringNotAscii.into());
+        }
+
+        // the raw value is encoded as
+        // | type info (4) | len (2) | name (optional) | value (len) |
+        let len = usize::from(if is_big_endian {
+            u16::from_be_bytes([raw[4], raw[5]])
+        } else {
+            u16::from_le_bytes([raw[4], raw[5]])
+        });
+        debug_assert_eq!(value.value.len() + usize::from(len > 0), len);
+
+        let new_len = replacement.len() as u16 + 1;
+        result.extend_from_slice(&raw[..4]);
+        if is_big_endian {
+            result.extend_from_slice(&new_len.to_be_bytes());
+        } else {
+            result.extend_from_slice(&new_len.to_le_bytes());
+        }
+        result.extend_from_slice(&raw[6..raw.len() - len]);
+        result.extend_from_slice(replacement.as_bytes());
+        result.push(0);
+    }
+    result.extend_from_slice(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod redact_strings_tests {
+    use super::*;
+    use crate::error::VerboseDecodeError;
+    use crate::verbose::{StringCoding, StringValue, U16Value, VerboseIter};
+    use arrayvec::ArrayVec;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn redact(
+            ref first in "\\pc{0,20}",
+            ref second in "\\pc{0,20}",
+            ref name in "[a-z]{1,8}",
+            number in any::<u16>(),
+            ref replacement in "[ -~]{0,10}",
+            ref trailing in proptest::collection::vec(any::<u8>(), 0..4),
+            is_big_endian in any::<bool>(),
+        ) {
+            let first_value = StringValue { name: None, value: first };
+            let number_value = U16Value { variable_info: None, scaling: None, value: number };
+            let second_value = StringValue { name: Some(name), value: second };
+
+            let mut payload = ArrayVec::<u8, 200>::new();
+            first_value.add_to_msg(&mut payload, is_big_endian).unwrap();
+            number_value.add_to_msg(&mut payload, is_big_endian).unwrap();
+            let second_start = payload.len();
+            second_value.add_to_msg(&mut payload, is_big_endian).unwrap();
+            payload.try_extend_from_slice(trailing).unwrap();
+
+            // redact the named string only
+            let result = redact_strings(&payload, is_big_endian, 3, replacement, |index, n| {
+                assert!(index == 0 || index == 2);
+                n == Some(name.as_str())
+            })
+            .unwrap();
+
+            // the prefix is copied byte by byte
+            prop_assert_eq!(&payload[..second_start], &result[..second_start]);
+            let mut iter = VerboseIter::new(is_big_endian, 3, &result);
+            prop_assert_eq!(Some(Ok(VerboseValue::Str(first_value.clone()))), iter.next());
+            prop_assert_eq!(Some(Ok(VerboseValue::U16(number_value))), iter.next());
+            prop_assert_eq!(
+                Some(Ok(VerboseValue::Str(StringValue { name: Some(name), value: replacement }))),
+                iter.next()
+            );
+            prop_assert_eq!(None, iter.next());
+            prop_assert_eq!(&trailing[..], iter.raw());
+
+            // nothing redacted
+            let result = redact_strings(&payload, is_big_endian, 3, replacement, |_, _| false)
+                .unwrap();
+            prop_assert_eq!(&payload[..], &result[..]);
+
+            // truncated payload
+            let is_decode_err = matches!(
+                redact_strings(&payload[..second_start + 4], is_big_endian, 3, replacement, |_, _| true),
+                Err(RedactError::Decode(_))
+            );
+            prop_assert!(is_decode_err);
+        }
+    }
+
+    #[test]
+    fn empty_string() {
+        // strings with a length field of zero (no terminating zero)
+        let payload = [0, 0b1000_0010, 0, 0, 0, 0];
+        let result = redact_strings(&payload, false, 1, "x", |_, _| true).unwrap();
+        assert_eq!(&[0, 0b1000_0010, 0, 0, 2, 0, b'x', 0], &result[..]);
+    }
+
+    #[test]
+    fn errors() {
+        // non ASCII replacement for an ASCII string
+        let mut payload = ArrayVec::<u8, 100>::new();
+        StringValue {
+            name: None,
+            value: "abc",
+        }
+        .add_to_msg_with_coding(&mut payload, true, StringCoding::Ascii)
+        .unwrap();
+        assert_eq!(
+            Err(RedactError::Encode(VerboseEncodeError::StringNotAscii)),
+            redact_strings(&payload, true, 1, "ä", |_, _| true)
+        );
+        let result = redact_strings(&payload, true, 1, "***", |_, _| true).unwrap();
+        // ASCII coding is kept
+        assert_eq!(&payload[..4], &result[..4]);
+        assert_eq!(
+            Some(Ok(VerboseValue::Str(StringValue {
+                name: None,
+                value: "***"
+            }))),
+            VerboseIter::new(true, 1, &result).next()
+        );
+
+        // replacement too long
+        let long = "a".repeat(usize::from(u16::MAX));
+        assert_eq!(
+            Err(RedactError::Encode(VerboseEncodeError::StringTooLong(
+                long.len()
+            ))),
+            redact_strings(&payload, true, 1, &long, |_, _| true)
+        );
+
+        // missing data
+        assert!(matches!(
+            redact_strings(&[], true, 1, "***", |_, _| true),
+            Err(RedactError::Decode(
+                VerboseDecodeError::UnexpectedEndOfSlice(_)
+            ))
+        ));
+    }
+}
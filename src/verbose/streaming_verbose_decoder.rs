@@ -0,0 +1,510 @@
+use super::{FieldSlicer, VerboseValue};
+use crate::error::{Layer, UnexpectedEndOfSliceError, VerboseDecodeError};
+use std::vec::Vec;
+
+/// Event emitted by the [`StreamingVerboseDecoder`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum VerboseStreamEvent<'a> {
+    /// Completely decoded argument (all arguments except raw values).
+    Value(VerboseValue<'a>),
+    /// Start of a raw argument (followed by `RawData` events containing
+    /// a total of `len` bytes & a `RawEnd` event).
+    RawStart {
+        /// Name of the raw value (if present).
+        name: Option<&'a str>,
+        /// Total length of the raw data.
+        len: usize,
+    },
+    /// Chunk of the data of the current raw argument.
+    RawData(&'a [u8]),
+    /// End of the current raw argument.
+    RawEnd,
+}
+
+/// Push based decoder for verbose arguments that can be fed with the
+/// payload in chunks of arbitrary size.
+///
+/// Arguments are emitted as soon as they are complete. The data of raw
+/// arguments is passed through in chunks as it arrives (see
+/// [`VerboseStreamEvent::RawData`]) so large raw arguments don't have to
+/// be buffered. Incomplete arguments of all other types are buffered
+/// until the remaining bytes are pushed.
+///
+/// After an error was returned the state of the decoder is reset and
+/// the bytes of the failed argument are discarded.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::verbose::{StreamingVerboseDecoder, VerboseStreamEvent};
+///
+/// // raw argument with 4 bytes of data (big endian)
+/// let payload = [0, 4, 0, 0, 0, 4, 1, 2, 3, 4];
+///
+/// let mut decoder = StreamingVerboseDecoder::new(true);
+/// let mut data = Vec::new();
+/// for chunk in payload.chunks(3) {
+///     decoder.push(chunk, |event| {
+///         if let VerboseStreamEvent::RawData(d) = event {
+///             data.extend_from_slice(d);
+///         }
+///     }).unwrap();
+/// }
+/// decoder.finish().unwrap();
+/// assert_eq!(&[1, 2, 3, 4], &data[..]);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StreamingVerboseDecoder {
+    is_big_endian: bool,
+    /// Bytes of an incomplete argument.
+    buffer: Vec<u8>,
+    /// Total length & already received bytes of the raw argument
+    /// that is currently passed through.
+    raw: Option<(usize, usize)>,
+    /// Number of arguments that were started.
+    number_of_arguments: usize,
+}
+
+impl StreamingVerboseDecoder {
+    /// Creates a decoder for arguments in the given endianness.
+    pub fn new(is_big_endian: bool) -> StreamingVerboseDecoder {
+        StreamingVerboseDecoder {
+            is_big_endian,
+            buffer: Vec::new(),
+            raw: None,
+            number_of_arguments: 0,
+        }
+    }
+
+    /// Returns true if the arguments are decoded as big endian.
+    #[inline]
+    pub fn is_big_endian(&self) -> bool {
+        self.is_big_endian
+    }
+
+    /// Number of arguments that have been emitted so far (raw arguments
+    /// are counted as soon as their `RawStart` event is emitted).
+    #[inline]
+    pub fn number_of_arguments(&self) -> usize {
+        self.number_of_arguments
+    }
+
+    /// Number of bytes of an incomplete argument currently buffered.
+    #[inline]
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns true if no argument is partially decoded (all pushed
+    /// data resulted in complete arguments).
+    #[inline]
+    pub fn is_idle(&self) -> bool {
+        self.buffer.is_empty() && self.raw.is_none()
+    }
+
+    /// Decodes the given chunk of payload data & calls `on_event` for all
+    /// events that become available.
+    pub fn push<F>(&mut self, data: &[u8], mut on_event: F) -> Result<(), VerboseDecodeError>
+    where
+        F: FnMut(VerboseStreamEvent<'_>),
+    {
+        if self.buffer.is_empty() {
+            match self.process(data, &mut on_event) {
+                Ok(consumed) => {
+                    self.buffer.extend_from_slice(&data[consumed..]);
+                    Ok(())
+                }
+                Err(err) => {
+                    self.reset();
+                    Err(err)
+                }
+            }
+        } else {
+            let mut buffer = core::mem::take(&mut self.buffer);
+            buffer.extend_from_slice(data);
+            match self.process(&buffer, &mut on_event) {
+                Ok(consumed) => {
+                    buffer.drain(..consumed);
+                    self.buffer = buffer;
+                    Ok(())
+                }
+                Err(err) => {
+                    self.reset();
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    /// Checks that the pushed data ended with a complete argument.
+    ///
+    /// Returns an unexpected end of slice error if an argument is only
+    /// partially decoded. The decoder is reset afterwards and can be used
+    /// for the next payload.
+    pub fn finish(&mut self) -> Result<(), VerboseDecodeError> {
+        let result = if let Some((len, received)) = self.raw {
+            Err(VerboseDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::VerboseValue,
+                    minimum_size: len,
+                    actual_size: received,
+                },
+            ))
+        } else if self.buffer.is_empty() {
+            Ok(())
+        } else {
+            VerboseValue::from_slice(&self.buffer, self.is_big_endian).map(|_| ())
+        };
+        self.reset();
+        self.number_of_arguments = 0;
+        result
+    }
+
+    /// Discards all partially decoded data.
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.raw = None;
+    }
+
+    /// Emits all events contained in `data` & returns the number of
+    /// bytes consumed (the rest is an incomplete argument).
+    fn process<F>(&mut self, data: &[u8], on_event: &mut F) -> Result<usize, VerboseDecodeError>
+    where
+        F: FnMut(VerboseStreamEvent<'_>),
+    {
+        let mut rest = data;
+        loop {
+            if let Some((len, received)) = self.raw {
+                let n = (len - received).min(rest.len());
+                if n > 0 {
+                    on_event(VerboseStreamEvent::RawData(&rest[..n]));
+                    rest = &rest[n..];
+                }
+                if received + n < len {
+                    self.raw = Some((len, received + n));
+                    return Ok(data.len() - rest.len());
+                }
+                self.raw = None;
+                on_event(VerboseStreamEvent::RawEnd);
+            }
+            if rest.is_empty() {
+                return Ok(data.len());
+            }
+            match self.next_argument(rest, on_event)? {
+                Some(consumed) => rest = &rest[consumed..],
+                None => return Ok(data.len() - rest.len()),
+            }
+        }
+    }
+
+    /// Decodes the start of the next argument in `data` & returns the
+    /// number of bytes consumed (`None` if more data is needed).
+    fn next_argument<F>(
+        &mut self,
+        data: &[u8],
+        on_event: &mut F,
+    ) -> Result<Option<usize>, VerboseDecodeError>
+    where
+        F: FnMut(VerboseStreamEvent<'_>),
+    {
+        const RAW_FLAG_1: u8 = 0b0000_0100;
+        const VARINFO_FLAG_1: u8 = 0b0000_1000;
+        // flags that would turn the value into a different type or
+        // contradict the raw type (same as used when decoding raw values)
+        const RAW_CONTRADICTING_MASK_0: u8 = 0b1111_0000;
+        const RAW_CONTRADICTING_MASK_1: u8 = 0b0111_0011;
+
+        if data.len() < 4 {
+            return Ok(None);
+        }
+
+        let is_raw = 0 != data[1] & RAW_FLAG_1
+            && 0 == data[0] & RAW_CONTRADICTING_MASK_0
+            && 0 == data[1] & RAW_CONTRADICTING_MASK_1;
+
+        if is_raw {
+            // only decode the raw header, the data is passed through
+            let mut slicer = FieldSlicer::new(&data[4..], 4);
+            let header = slicer.read_u16(self.is_big_endian).and_then(|len| {
+                if 0 != data[1] & VARINFO_FLAG_1 {
+                    Ok((len, Some(slicer.read_var_name(self.is_big_endian)?)))
+                } else {
+                    Ok((len, None))
+                }
+            });
+            match header {
+                Ok((len, name)) => {
+                    let len = usize::from(len);
+                    self.number_of_arguments += 1;
+                    on_event(VerboseStreamEvent::RawStart { name, len });
+                    self.raw = Some((len, 0));
+                    Ok(Some(data.len() - slicer.rest().len()))
+                }
+                Err(err) if err.is_recoverable_with_more_data() => Ok(None),
+                Err(err) => Err(err),
+            }
+        } else {
+            match VerboseValue::from_slice(data, self.is_big_endian) {
+                Ok((value, rest)) => {
+                    self.number_of_arguments += 1;
+                    on_event(VerboseStreamEvent::Value(value));
+                    Ok(Some(data.len() - rest.len()))
+                }
+                Err(err) if err.is_recoverable_with_more_data() => Ok(None),
+                Err(err) => Err(err),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod streaming_verbose_decoder_tests {
+    use super::*;
+    use crate::verbose::{RawValue, StringValue, U16Value, U32Value, VariableInfoUnit};
+    use alloc::{format, string::String};
+    use arrayvec::ArrayVec;
+    use proptest::prelude::*;
+
+    /// Owned representation of the events (raw data chunks merged).
+    #[derive(Debug, PartialEq)]
+    enum Owned {
+        Value(String),
+        Raw(Option<String>, Vec<u8>),
+    }
+
+    fn collect(events: &mut Vec<Owned>, open_raw: &mut bool, event: VerboseStreamEvent<'_>) {
+        use VerboseStreamEvent::*;
+        match event {
+            Value(v) => {
+                assert!(false == *open_raw);
+                events.push(Owned::Value(format!("{:?}", v)));
+            }
+            RawStart { name, len } => {
+                assert!(false == *open_raw);
+                *open_raw = true;
+                events.push(Owned::Raw(name.map(String::from), Vec::with_capacity(len)));
+            }
+            RawData(d) => {
+                assert!(*open_raw);
+                assert!(false == d.is_empty());
+                match events.last_mut() {
+                    Some(Owned::Raw(_, data)) => data.extend_from_slice(d),
+                    _ => panic!("raw data without raw start"),
+                }
+            }
+            RawEnd => {
+                assert!(*open_raw);
+                *open_raw = false;
+            }
+        }
+    }
+
+    fn values() -> [VerboseValue<'static>; 5] {
+        [
+            VerboseValue::U32(U32Value {
+                variable_info: None,
+                scaling: None,
+                value: 0x1234_5678,
+            }),
+            VerboseValue::Raw(RawValue {
+                name: Some("raw"),
+                data: &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+            }),
+            VerboseValue::Str(StringValue {
+                name: None,
+                value: "hello streaming",
+            }),
+            VerboseValue::Raw(RawValue {
+                name: None,
+                data: &[],
+            }),
+            VerboseValue::U16(U16Value {
+                variable_info: Some(VariableInfoUnit {
+                    name: "speed",
+                    unit: "km/h",
+                }),
+                scaling: None,
+                value: 120,
+            }),
+        ]
+    }
+
+    fn expected() -> Vec<Owned> {
+        values()
+            .iter()
+            .map(|v| match v {
+                VerboseValue::Raw(r) => Owned::Raw(r.name.map(String::from), r.data.to_vec()),
+                v => Owned::Value(format!("{:?}", v)),
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn chunked(
+            is_big_endian in any::<bool>(),
+            chunk_len in 1usize..20,
+        ) {
+            let mut payload = ArrayVec::<u8, 512>::new();
+            for v in values() {
+                v.add_to_msg(&mut payload, is_big_endian).unwrap();
+            }
+
+            let mut decoder = StreamingVerboseDecoder::new(is_big_endian);
+            prop_assert_eq!(is_big_endian, decoder.is_big_endian());
+            let mut events = Vec::new();
+            let mut open_raw = false;
+            for chunk in payload.chunks(chunk_len) {
+                decoder
+                    .push(chunk, |e| collect(&mut events, &mut open_raw, e))
+                    .unwrap();
+                // raw data & complete arguments are never buffered
+                prop_assert!(decoder.buffered_len() <= payload.len());
+            }
+            prop_assert!(decoder.is_idle());
+            prop_assert_eq!(0, decoder.buffered_len());
+            prop_assert_eq!(5, decoder.number_of_arguments());
+            prop_assert_eq!(Ok(()), decoder.finish());
+            prop_assert_eq!(0, decoder.number_of_arguments());
+            prop_assert!(false == open_raw);
+            prop_assert_eq!(expected(), events);
+        }
+
+        #[test]
+        fn truncated(
+            is_big_endian in any::<bool>(),
+            len in 0usize..200,
+        ) {
+            let mut payload = ArrayVec::<u8, 512>::new();
+            for v in values() {
+                v.add_to_msg(&mut payload, is_big_endian).unwrap();
+            }
+            let len = len.min(payload.len());
+
+            let mut decoder = StreamingVerboseDecoder::new(is_big_endian);
+            let mut events = Vec::new();
+            let mut open_raw = false;
+            decoder
+                .push(&payload[..len], |e| collect(&mut events, &mut open_raw, e))
+                .unwrap();
+            // only payloads ending at an argument boundary are complete
+            let mut boundaries = Vec::from([0]);
+            for v in values() {
+                boundaries.push(boundaries.last().unwrap() + v.serialized_len());
+            }
+            let result = decoder.finish();
+            if boundaries.contains(&len) {
+                prop_assert_eq!(Ok(()), result);
+                prop_assert!(false == open_raw);
+            } else {
+                prop_assert!(result.is_err());
+            }
+            // finish resets the decoder
+            prop_assert!(decoder.is_idle());
+            prop_assert_eq!(0, decoder.number_of_arguments());
+        }
+    }
+
+    #[test]
+    fn raw_data_not_buffered() {
+        let data = [0xAAu8; 1000];
+        let mut payload = ArrayVec::<u8, 1100>::new();
+        VerboseValue::Raw(RawValue {
+            name: None,
+            data: &data,
+        })
+        .add_to_msg(&mut payload, false)
+        .unwrap();
+
+        let mut decoder = StreamingVerboseDecoder::new(false);
+        let mut received = 0;
+        for chunk in payload.chunks(100) {
+            decoder
+                .push(chunk, |e| {
+                    if let VerboseStreamEvent::RawData(d) = e {
+                        received += d.len();
+                    }
+                })
+                .unwrap();
+            assert_eq!(0, decoder.buffered_len());
+        }
+        assert_eq!(1000, received);
+        assert!(decoder.is_idle());
+    }
+
+    #[test]
+    fn unfinished() {
+        let mut payload = ArrayVec::<u8, 64>::new();
+        VerboseValue::Raw(RawValue {
+            name: None,
+            data: &[1, 2, 3, 4],
+        })
+        .add_to_msg(&mut payload, true)
+        .unwrap();
+
+        // raw data missing
+        let mut decoder = StreamingVerboseDecoder::new(true);
+        decoder.push(&payload[..payload.len() - 1], |_| {}).unwrap();
+        assert!(false == decoder.is_idle());
+        assert_eq!(
+            Err(VerboseDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::VerboseValue,
+                    minimum_size: 4,
+                    actual_size: 3,
+                }
+            )),
+            decoder.finish()
+        );
+        assert!(decoder.is_idle());
+
+        // type info missing
+        decoder.push(&payload[..2], |_| {}).unwrap();
+        assert_eq!(2, decoder.buffered_len());
+        assert_eq!(
+            Err(VerboseDecodeError::UnexpectedEndOfSlice(
+                UnexpectedEndOfSliceError {
+                    layer: Layer::VerboseTypeInfo,
+                    minimum_size: 4,
+                    actual_size: 2,
+                }
+            )),
+            decoder.finish()
+        );
+        assert!(decoder.is_idle());
+    }
+
+    #[test]
+    fn decode_error() {
+        // bool with contradicting signed flag
+        let mut decoder = StreamingVerboseDecoder::new(true);
+        let mut called = false;
+        assert!(decoder
+            .push(&[0b0011_0001, 0, 0, 0, 1], |_| called = true)
+            .is_err());
+        assert!(false == called);
+        assert!(decoder.is_idle());
+
+        // error in the raw header (invalid name)
+        let mut decoder = StreamingVerboseDecoder::new(true);
+        assert!(decoder
+            .push(&[0, 0b0000_1100, 0, 0, 0, 0, 0, 2, 0xff, 0], |_| called =
+                true)
+            .is_err());
+        assert!(false == called);
+        assert!(decoder.is_idle());
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let decoder = StreamingVerboseDecoder::new(false);
+        assert_eq!(decoder, decoder.clone());
+        assert_eq!(
+            "StreamingVerboseDecoder { is_big_endian: false, buffer: [], raw: None, number_of_arguments: 0 }",
+            format!("{:?}", decoder)
+        );
+        let event = VerboseStreamEvent::RawEnd;
+        assert_eq!(event, event.clone());
+        assert_eq!("RawEnd", format!("{:?}", event));
+    }
+}
@@ -0,0 +1,269 @@
+use crate::error::{Layer, TypeInfoKind, UnexpectedEndOfSliceError, VerboseDecodeError};
+use alloc::string::String;
+
+use super::verbose_value::{check_type_flags, check_type_len, type_info_kind};
+use super::FieldSlicer;
+
+/// Verbose string argument with UTF-16 encoded data, decoded into an
+/// owned `String` (requires the `alloc` feature).
+///
+/// This is an extension beyond the DLT specification, which only defines
+/// ASCII & UTF-8 as string codings (SCOD). Some loggers still send UTF-16
+/// encoded strings, either signaled via the (reserved) string coding
+/// [`Utf16StringValue::SCOD`] or without any indication in the type info.
+/// [`super::VerboseValue::from_slice`] rejects the first case with
+/// [`VerboseDecodeError::InvalidStringCoding`], in which case the argument
+/// can be decoded via [`Utf16StringValue::from_slice`] instead.
+///
+/// The string data is decoded in the endianness of the message & a
+/// terminating zero character is removed. The variable name is, as
+/// for all other arguments, expected to be ASCII/UTF-8.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::verbose::Utf16StringValue;
+///
+/// let data = [
+///     // type info (string with the non standard SCOD 2)
+///     0b0000_0000, 0b0000_0010, 0b0000_0001, 0b0000_0000,
+///     // length of the string data (including the terminating zero)
+///     6, 0,
+///     // "hi" + terminating zero in UTF-16 (little endian)
+///     b'h', 0, b'i', 0, 0, 0,
+/// ];
+/// let (value, rest) = Utf16StringValue::from_slice(&data, false, false).unwrap();
+/// assert_eq!("hi", value.value);
+/// assert_eq!(None, value.name);
+/// assert!(rest.is_empty());
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Utf16StringValue<'a> {
+    pub name: Option<&'a str>,
+    pub value: String,
+}
+
+impl<'a> Utf16StringValue<'a> {
+    /// Non standard string coding (SCOD) value signaling UTF-16 encoded
+    /// string data.
+    pub const SCOD: u8 = 2;
+
+    /// Decodes a verbose string argument with UTF-16 encoded data &
+    /// returns it together with the remaining slice.
+    ///
+    /// If `force_utf16` is `false` the string coding in the type info
+    /// has to be [`Utf16StringValue::SCOD`] (otherwise
+    /// [`VerboseDecodeError::InvalidStringCoding`] is returned). If it
+    /// is `true` the data of any string argument is decoded as UTF-16,
+    /// independent of the signaled string coding.
+    pub fn from_slice(
+        slice: &'a [u8],
+        is_big_endian: bool,
+        force_utf16: bool,
+    ) -> Result<(Utf16StringValue<'a>, &'a [u8]), VerboseDecodeError> {
+        use VerboseDecodeError::*;
+
+        const VARINFO_FLAG_1: u8 = 0b0000_1000;
+
+        if slice.len() < 4 {
+            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer: Layer::VerboseTypeInfo,
+                minimum_size: 4,
+                actual_size: slice.len(),
+            }));
+        }
+        let type_info = [slice[0], slice[1], slice[2], slice[3]];
+
+        if TypeInfoKind::String != type_info_kind(type_info)? {
+            return Err(InvalidTypeInfo(type_info));
+        }
+        check_type_flags(type_info, TypeInfoKind::String, 0b1111_1111, 0b0111_0101)?;
+        check_type_len(type_info, TypeInfoKind::String, 0..=0)?;
+
+        let scod = (type_info[1] >> 7) | ((type_info[2] & 0b0000_0011) << 1);
+        if false == force_utf16 && Utf16StringValue::SCOD != scod {
+            return Err(InvalidStringCoding { scod, type_info });
+        }
+
+        let mut slicer = FieldSlicer::new(&slice[4..], 4);
+        let len = usize::from(slicer.read_u16(is_big_endian)?);
+        let name = if 0 != type_info[1] & VARINFO_FLAG_1 {
+            Some(slicer.read_var_name(is_big_endian)?)
+        } else {
+            None
+        };
+        let data = slicer.read_raw(len).map_err(|_| {
+            UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                layer: Layer::VerboseValue,
+                minimum_size: len,
+                actual_size: slicer.rest().len(),
+            })
+        })?;
+        let value = decode_utf16(data, is_big_endian)?;
+
+        Ok((Utf16StringValue { name, value }, slicer.rest()))
+    }
+}
+
+/// Decodes UTF-16 data in the given endianness into a `String`
+/// (a terminating zero character is removed).
+///
+/// Returns [`VerboseDecodeError::InvalidUtf16String`] if the data has an
+/// odd length or contains unpaired surrogates.
+pub fn decode_utf16(data: &[u8], is_big_endian: bool) -> Result<String, VerboseDecodeError> {
+    if 0 != data.len() % 2 {
+        return Err(VerboseDecodeError::InvalidUtf16String);
+    }
+    let data = match data {
+        [rest @ .., 0, 0] => rest,
+        data => data,
+    };
+    char::decode_utf16(data.chunks_exact(2).map(|c| {
+        if is_big_endian {
+            u16::from_be_bytes([c[0], c[1]])
+        } else {
+            u16::from_le_bytes([c[0], c[1]])
+        }
+    }))
+    .collect::<Result<String, _>>()
+    .map_err(|_| VerboseDecodeError::InvalidUtf16String)
+}
+
+#[cfg(test)]
+mod utf16_string_value_tests {
+    use super::*;
+    use crate::verbose::VerboseValue;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    /// Serializes a string argument with UTF-16 encoded data.
+    fn encode(
+        scod: u8,
+        name: Option<&str>,
+        value: &str,
+        is_big_endian: bool,
+        terminated: bool,
+    ) -> Vec<u8> {
+        let to_bytes = |v: u16| {
+            if is_big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+        let mut units: Vec<u16> = value.encode_utf16().collect();
+        if terminated {
+            units.push(0);
+        }
+
+        let mut result = Vec::new();
+        result.extend_from_slice(&[
+            0,
+            0b0000_0010 | ((scod & 1) << 7) | if name.is_some() { 0b0000_1000 } else { 0 },
+            (scod >> 1) & 0b11,
+            0,
+        ]);
+        result.extend_from_slice(&to_bytes(units.len() as u16 * 2));
+        if let Some(name) = name {
+            result.extend_from_slice(&to_bytes(name.len() as u16 + 1));
+            result.extend_from_slice(name.as_bytes());
+            result.push(0);
+        }
+        for unit in units {
+            result.extend_from_slice(&to_bytes(unit));
+        }
+        result
+    }
+
+    proptest! {
+        #[test]
+        fn from_slice(
+            ref value in "\\PC{0,40}",
+            ref name in proptest::option::of("[a-z]{1,10}"),
+            is_big_endian in any::<bool>(),
+            terminated in any::<bool>(),
+            ref rest in proptest::collection::vec(any::<u8>(), 0..4),
+        ) {
+            let mut data = encode(Utf16StringValue::SCOD, name.as_deref(), value, is_big_endian, terminated);
+            data.extend_from_slice(rest);
+
+            let (decoded, decoded_rest) = Utf16StringValue::from_slice(&data, is_big_endian, false).unwrap();
+            prop_assert_eq!(&decoded.value, value);
+            prop_assert_eq!(decoded.name, name.as_deref());
+            prop_assert_eq!(decoded_rest, &rest[..]);
+
+            // the standard decoder rejects the non standard coding
+            let is_invalid_coding = matches!(
+                VerboseValue::from_slice(&data, is_big_endian),
+                Err(VerboseDecodeError::InvalidStringCoding { scod: 2, .. })
+            );
+            prop_assert!(is_invalid_coding);
+
+            // standard codings are only decoded as UTF-16 if forced
+            for scod in [0, 1] {
+                let data = encode(scod, name.as_deref(), value, is_big_endian, terminated);
+                let is_invalid_coding = matches!(
+                    Utf16StringValue::from_slice(&data, is_big_endian, false),
+                    Err(VerboseDecodeError::InvalidStringCoding { .. })
+                );
+                prop_assert!(is_invalid_coding);
+                let (forced, _) = Utf16StringValue::from_slice(&data, is_big_endian, true).unwrap();
+                prop_assert_eq!(&forced.value, value);
+            }
+
+            // missing data
+            for len in 0..data.len() - rest.len() {
+                let is_end_of_slice = matches!(
+                    Utf16StringValue::from_slice(&data[..len], is_big_endian, false),
+                    Err(VerboseDecodeError::UnexpectedEndOfSlice(_))
+                );
+                prop_assert!(is_end_of_slice);
+            }
+        }
+    }
+
+    #[test]
+    fn from_slice_errors() {
+        // other type
+        assert_eq!(
+            Err(VerboseDecodeError::InvalidTypeInfo([0x43, 0, 0, 0])),
+            Utf16StringValue::from_slice(&[0x43, 0, 0, 0, 0, 0], false, true)
+        );
+        // fixed point flag set
+        assert!(matches!(
+            Utf16StringValue::from_slice(&[0, 0b0001_0010, 0b1, 0, 0, 0], false, false),
+            Err(VerboseDecodeError::FixpNotAllowedForType { .. })
+        ));
+        // odd length
+        assert_eq!(
+            Err(VerboseDecodeError::InvalidUtf16String),
+            Utf16StringValue::from_slice(&[0, 0b0000_0010, 0b1, 0, 1, 0, b'a'], false, false)
+        );
+    }
+
+    #[test]
+    fn decode_utf16_test() {
+        assert_eq!(Ok(String::new()), decode_utf16(&[], false));
+        assert_eq!(Ok(String::new()), decode_utf16(&[0, 0], true));
+        // only one terminating zero is removed
+        assert_eq!(Ok("\0".into()), decode_utf16(&[0, 0, 0, 0], true));
+        assert_eq!(Ok("a".into()), decode_utf16(&[0, b'a'], true));
+        assert_eq!(Ok("a".into()), decode_utf16(&[b'a', 0], false));
+        // surrogate pair
+        assert_eq!(
+            Ok("\u{1F600}".into()),
+            decode_utf16(&[0xD8, 0x3D, 0xDE, 0x00], true)
+        );
+        // unpaired surrogate
+        assert_eq!(
+            Err(VerboseDecodeError::InvalidUtf16String),
+            decode_utf16(&[0xD8, 0x3D, 0, b'a'], true)
+        );
+        // odd length
+        assert_eq!(
+            Err(VerboseDecodeError::InvalidUtf16String),
+            decode_utf16(&[0, b'a', 0], true)
+        );
+    }
+}
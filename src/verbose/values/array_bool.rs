@@ -1,6 +1,7 @@
-use crate::verbose::{ArrayDimensions, VariableInfoUnit};
+use crate::error::VerboseEncodeError;
+use crate::verbose::{ArrayDimensions, FieldWriter, VariableInfoUnit};
 
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
 
@@ -65,53 +66,18 @@ impl<'a> ArrayBool<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b0001_0001, 0b0000_0001, 0b0000_0000, 0b0000_0000],
+            self.variable_info.is_some(),
+            false,
+        )?;
+        writer.write_array_dimensions(&self.dimensions, is_big_endian)?;
         if let Some(var_info) = &self.variable_info {
-            let type_info: [u8; 4] = [0b0001_0001, 0b0000_1001, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-
-            let (name_len, unit_len, number_of_dimensions) = if is_big_endian {
-                (
-                    (var_info.name.len() as u16 + 1).to_be_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_be_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes(),
-                )
-            } else {
-                (
-                    (var_info.name.len() as u16 + 1).to_le_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_le_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes(),
-                )
-            };
-
-            buf.try_extend_from_slice(&number_of_dimensions)?;
-            buf.try_extend_from_slice(self.dimensions.dimensions)?;
-            buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-            buf.try_extend_from_slice(var_info.name.as_bytes())?;
-            if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                // Safe as capacity is checked earlier
-                unsafe { buf.push_unchecked(0) };
-                let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                unsafe { buf.push_unchecked(0) };
-                buf.try_extend_from_slice(self.data)?;
-                Ok(())
-            } else {
-                Err(CapacityError::new(()))
-            }
-        } else {
-            let number_of_dimensions = if is_big_endian {
-                (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes()
-            } else {
-                (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes()
-            };
-            let type_info: [u8; 4] = [0b0001_0001, 0b0000_0001, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&number_of_dimensions)?;
-            buf.try_extend_from_slice(self.dimensions.dimensions)?;
-            buf.try_extend_from_slice(self.data)?;
-
-            Ok(())
+            writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
         }
+        writer.write_raw(self.data)
     }
 }
 
@@ -182,21 +148,23 @@ impl<'a> IntoIterator for &'a ArrayBool<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::explicit_counter_loop, clippy::unnecessary_cast)]
 mod test {
     use super::*;
     #[cfg(feature = "serde")]
     use crate::alloc::string::ToString;
     use crate::error::UnexpectedEndOfSliceError;
     use crate::error::VerboseDecodeError::UnexpectedEndOfSlice;
+    use crate::verbose::test_dimension;
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::ArrBool;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
 
     proptest! {
         #[test]
-        fn write_read(ref name in "\\pc{0,20}", ref unit in "\\pc{0,20}", dim_count in 0u16..5) {
+        fn write_read(ref name in "\\pc{1,20}", ref unit in "\\pc{1,20}", dim_count in 0u16..5) {
             const BUFFER_SIZE: usize = 400;
 
             // test big endian with name
@@ -210,7 +178,7 @@ mod test {
             let mut content = Vec::with_capacity(dim_count as usize);
 
             for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                 for x in 0..=i {
                     content.push(u8::from(x % 2 == 0));       // Sample booleans
                 }
@@ -219,7 +187,7 @@ mod test {
             let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
             let arr_bool = ArrayBool {variable_info, dimensions: arr_dim, data: &content };
-            arr_bool.add_to_msg(&mut msg_buff, is_big_endian)?;
+            arr_bool.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
             let len_name_be = (name.len() as u16 + 1).to_be_bytes();
             let len_unit_be = (unit.len() as u16 + 1).to_be_bytes();
@@ -255,7 +223,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i + 1).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i {
                         content.push(u8::from(x % 2 == 0));       // Sample booleans
                     }
@@ -264,7 +232,7 @@ mod test {
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                 let arr_bool = ArrayBool {variable_info, dimensions: arr_dim, data: &content };
-                arr_bool.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_bool.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name_le = (name.len() as u16 + 1).to_le_bytes();
                 let len_unit_le = (unit.len() as u16 + 1).to_le_bytes();
@@ -300,7 +268,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i {
                         content.push(u8::from(x % 2 == 0));       // Sample booleans
                     }
@@ -309,7 +277,7 @@ mod test {
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                 let arr_bool = ArrayBool {variable_info, dimensions: arr_dim, data: &content };
-                arr_bool.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_bool.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let mut content_buff = Vec::new();
 
@@ -337,7 +305,7 @@ mod test {
             let mut content = Vec::with_capacity(dim_count as usize);
 
             for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i + 1).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                 for x in 0..=i {
                     content.push(u8::from(x % 2 == 0));       // Sample booleans
                 }
@@ -346,7 +314,7 @@ mod test {
             let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
             let arr_bool = ArrayBool {variable_info, dimensions: arr_dim, data: &content };
-            arr_bool.add_to_msg(&mut msg_buff, is_big_endian)?;
+            arr_bool.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
             let mut content_buff = Vec::new();
 
@@ -376,7 +344,7 @@ mod test {
 
 
                 for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..i {
                         content.push(u8::from(x % 2 == 0));       // Sample booleans
                     }
@@ -386,7 +354,7 @@ mod test {
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                 let arr_bool = ArrayBool {variable_info, dimensions: arr_dim, data: &content };
-                arr_bool.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_bool.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -408,7 +376,7 @@ mod test {
 
                 for i in 0..dim_count {
 
-                    dimensions.extend_from_slice(&(i + 1).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..i {
                         content.push(u8::from(x % 2 == 0));       // Sample booleans
                     }
@@ -418,7 +386,7 @@ mod test {
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                 let arr_bool = ArrayBool {variable_info, dimensions: arr_dim, data: &content };
-                arr_bool.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_bool.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -439,7 +407,7 @@ mod test {
 
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
 
                     for x in 0..i {
                         content.push(u8::from(x % 2 == 0));       // Sample booleans
@@ -450,7 +418,7 @@ mod test {
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                 let arr_bool = ArrayBool {variable_info, dimensions: arr_dim, data: &content };
-                arr_bool.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_bool.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -472,7 +440,7 @@ mod test {
 
                 for i in 0..dim_count {
 
-                        dimensions.extend_from_slice(&(i + 1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
 
                     for x in 0..i {
                         content.push(u8::from(x % 2 == 0));       // Sample booleans
@@ -483,7 +451,7 @@ mod test {
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                 let arr_bool = ArrayBool {variable_info, dimensions: arr_dim, data: &content };
-                arr_bool.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_bool.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -517,7 +485,7 @@ mod test {
                 let arr_bool = ArrayBool {variable_info, dimensions: arr_dim, data: &content };
                 let err = arr_bool.add_to_msg(&mut msg_buff, is_big_endian);
 
-                prop_assert_eq!(err, Err(CapacityError::new(())));
+                prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 }
 
@@ -547,7 +515,7 @@ mod test {
             let mut content = Vec::with_capacity(dim_count as usize);
 
             for i in 0..dim_count {
-                dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                 for x in 0..=i {
                     content.push((x as u8) % 2);
                 }
@@ -0,0 +1,340 @@
+use crate::error::VerboseEncodeError;
+use crate::verbose::{element_count, var_str_len, FieldWriter, RawF128, RawF16, VariableInfoUnit};
+use arrayvec::ArrayVec;
+
+/// Primitive types that can be encoded as elements of a verbose array
+/// (see [`add_array_to_msg`]).
+pub trait ArrayElement: Copy + sealed::Sealed {
+    /// First byte of the type info (type flag & type length) of the element type.
+    const TYPE_INFO_0: u8;
+
+    /// Size of an encoded element in bytes.
+    const SIZE: usize;
+
+    /// Adds the encoded element to the given buffer.
+    fn add_to_msg<const CAP: usize>(
+        self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl sealed::Sealed for bool {}
+
+impl ArrayElement for bool {
+    const TYPE_INFO_0: u8 = 0b0001_0001;
+    const SIZE: usize = 1;
+
+    #[inline]
+    fn add_to_msg<const CAP: usize>(
+        self,
+        buf: &mut ArrayVec<u8, CAP>,
+        _is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        FieldWriter::new(buf).write_u8(u8::from(self))
+    }
+}
+
+macro_rules! impl_single_byte_array_element {
+    ($t:ty, $type_info_0:expr, $write:ident) => {
+        impl sealed::Sealed for $t {}
+
+        impl ArrayElement for $t {
+            const TYPE_INFO_0: u8 = $type_info_0;
+            const SIZE: usize = 1;
+
+            #[inline]
+            fn add_to_msg<const CAP: usize>(
+                self,
+                buf: &mut ArrayVec<u8, CAP>,
+                _is_big_endian: bool,
+            ) -> Result<(), VerboseEncodeError> {
+                FieldWriter::new(buf).$write(self)
+            }
+        }
+    };
+}
+
+impl_single_byte_array_element!(u8, 0b0100_0001, write_u8);
+impl_single_byte_array_element!(i8, 0b0010_0001, write_i8);
+
+macro_rules! impl_array_element {
+    ($t:ty, $type_info_0:expr, $write:ident) => {
+        impl sealed::Sealed for $t {}
+
+        impl ArrayElement for $t {
+            const TYPE_INFO_0: u8 = $type_info_0;
+            const SIZE: usize = core::mem::size_of::<$t>();
+
+            #[inline]
+            fn add_to_msg<const CAP: usize>(
+                self,
+                buf: &mut ArrayVec<u8, CAP>,
+                is_big_endian: bool,
+            ) -> Result<(), VerboseEncodeError> {
+                FieldWriter::new(buf).$write(self, is_big_endian)
+            }
+        }
+    };
+}
+
+impl_array_element!(u16, 0b0100_0010, write_u16);
+impl_array_element!(u32, 0b0100_0011, write_u32);
+impl_array_element!(u64, 0b0100_0100, write_u64);
+impl_array_element!(u128, 0b0100_0101, write_u128);
+impl_array_element!(i16, 0b0010_0010, write_i16);
+impl_array_element!(i32, 0b0010_0011, write_i32);
+impl_array_element!(i64, 0b0010_0100, write_i64);
+impl_array_element!(i128, 0b0010_0101, write_i128);
+impl_array_element!(RawF16, 0b1000_0010, write_f16);
+impl_array_element!(f32, 0b1000_0011, write_f32);
+impl_array_element!(f64, 0b1000_0100, write_f64);
+impl_array_element!(RawF128, 0b1000_0101, write_f128);
+
+/// Adds a verbose array with the given dimensions & elements to the
+/// given dlt message buffer.
+///
+/// The elements are expected in the order they are encoded in (the
+/// product of all dimensions has to match the number of elements, an
+/// array without dimensions has no elements).
+///
+/// # Example
+///
+/// ```
+/// use arrayvec::ArrayVec;
+/// use dlt_parse::verbose::{add_array_to_msg, VerboseValue};
+///
+/// let mut buf = ArrayVec::<u8, 100>::new();
+/// // 2x3 matrix
+/// add_array_to_msg(&mut buf, true, None, &[2, 3], [1i16, 2, 3, 4, 5, 6]).unwrap();
+///
+/// let (value, _) = VerboseValue::from_slice(&buf, true).unwrap();
+/// if let VerboseValue::ArrI16(arr) = value {
+///     assert_eq!(vec![2, 3], arr.dimensions.iter().collect::<Vec<_>>());
+///     assert_eq!(vec![1, 2, 3, 4, 5, 6], arr.iter().collect::<Vec<_>>());
+/// } else {
+///     panic!("unexpected value");
+/// }
+/// ```
+pub fn add_array_to_msg<T, I, const CAP: usize>(
+    buf: &mut ArrayVec<u8, CAP>,
+    is_big_endian: bool,
+    variable_info: Option<&VariableInfoUnit<'_>>,
+    dimensions: &[u16],
+    elements: I,
+) -> Result<(), VerboseEncodeError>
+where
+    T: ArrayElement,
+    I: IntoIterator<Item = T>,
+    I::IntoIter: ExactSizeIterator,
+{
+    use VerboseEncodeError::*;
+
+    let elements = elements.into_iter();
+
+    // check the dimensions match the number of elements
+    let dimensions_product = element_count(dimensions.iter().copied()).unwrap_or(usize::MAX);
+    if dimensions_product != elements.len() {
+        return Err(ArrayDimensionsMismatch {
+            dimensions_product,
+            number_of_elements: elements.len(),
+        });
+    }
+
+    // check the array would fit into a dlt message
+    let var_info_len = variable_info
+        .map(|v| 4 + var_str_len(v.name) + var_str_len(v.unit))
+        .unwrap_or(0);
+    let total_len = elements
+        .len()
+        .checked_mul(T::SIZE)
+        .and_then(|v| v.checked_add(4 + 2 + dimensions.len() * 2 + var_info_len))
+        .unwrap_or(usize::MAX);
+    if total_len > usize::from(u16::MAX) {
+        return Err(ArrayTooLong(total_len));
+    }
+
+    let mut writer = FieldWriter::new(buf);
+    writer.write_type_info(
+        [T::TYPE_INFO_0, 0b0000_0001, 0b0000_0000, 0b0000_0000],
+        variable_info.is_some(),
+        false,
+    )?;
+
+    // dimensions (length checked via total_len)
+    writer.write_u16(dimensions.len() as u16, is_big_endian)?;
+    for d in dimensions {
+        writer.write_u16(*d, is_big_endian)?;
+    }
+
+    // name & unit (lengths checked via total_len)
+    if let Some(var_info) = variable_info {
+        writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
+    }
+
+    for element in elements {
+        element.add_to_msg(buf, is_big_endian)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::verbose::VerboseValue;
+    use alloc::{vec, vec::Vec};
+    use arrayvec::CapacityError;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn write_read_1d(
+            ref values in prop::collection::vec(any::<i16>(), 0..40),
+            ref name in "\\PC{0,10}",
+            ref unit in "\\PC{0,10}",
+            is_big_endian in any::<bool>(),
+        ) {
+            let var_info = VariableInfoUnit { name, unit };
+            for variable_info in [None, Some(&var_info)] {
+                let mut buf = ArrayVec::<u8, 1000>::new();
+                add_array_to_msg(
+                    &mut buf,
+                    is_big_endian,
+                    variable_info,
+                    &[values.len() as u16],
+                    values.iter().copied(),
+                ).unwrap();
+
+                let (value, rest) = VerboseValue::from_slice(&buf, is_big_endian).unwrap();
+                prop_assert!(rest.is_empty());
+                match value {
+                    VerboseValue::ArrI16(arr) => {
+                        prop_assert_eq!(variable_info.cloned(), arr.variable_info.clone());
+                        prop_assert_eq!(vec![values.len() as u16], arr.dimensions.iter().collect::<Vec<_>>());
+                        prop_assert_eq!(values, &arr.iter().collect::<Vec<_>>());
+                    }
+                    other => prop_assert!(false, "unexpected value {:?}", other),
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn write_read_2d(
+            ref values in prop::collection::vec(any::<f32>(), 6),
+            is_big_endian in any::<bool>(),
+        ) {
+            let mut buf = ArrayVec::<u8, 1000>::new();
+            add_array_to_msg(&mut buf, is_big_endian, None, &[2, 3], values.iter().copied()).unwrap();
+            prop_assert_eq!(4 + 2 + 2*2 + 6*4, buf.len());
+
+            let (value, rest) = VerboseValue::from_slice(&buf, is_big_endian).unwrap();
+            prop_assert!(rest.is_empty());
+            match value {
+                VerboseValue::ArrF32(arr) => {
+                    prop_assert_eq!(vec![2, 3], arr.dimensions.iter().collect::<Vec<_>>());
+                    prop_assert_eq!(
+                        values.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+                        arr.iter().map(|v| v.to_bits()).collect::<Vec<_>>()
+                    );
+                }
+                other => prop_assert!(false, "unexpected value {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn write_read_types() {
+        macro_rules! check {
+            ($variant:ident, $values:expr) => {
+                for is_big_endian in [false, true] {
+                    let values = $values;
+                    let mut buf = ArrayVec::<u8, 1000>::new();
+                    add_array_to_msg(
+                        &mut buf,
+                        is_big_endian,
+                        None,
+                        &[values.len() as u16],
+                        values,
+                    )
+                    .unwrap();
+                    let (value, rest) = VerboseValue::from_slice(&buf, is_big_endian).unwrap();
+                    assert!(rest.is_empty());
+                    match value {
+                        VerboseValue::$variant(arr) => {
+                            assert_eq!(&values[..], &arr.iter().collect::<Vec<_>>()[..])
+                        }
+                        other => panic!("unexpected value {:?}", other),
+                    }
+                }
+            };
+        }
+        check!(ArrBool, [true, false, true]);
+        check!(ArrU8, [0u8, 1, u8::MAX]);
+        check!(ArrU16, [0u16, 1, u16::MAX]);
+        check!(ArrU32, [0u32, 1, u32::MAX]);
+        check!(ArrU64, [0u64, 1, u64::MAX]);
+        check!(ArrU128, [0u128, 1, u128::MAX]);
+        check!(ArrI8, [i8::MIN, -1, i8::MAX]);
+        check!(ArrI16, [i16::MIN, -1, i16::MAX]);
+        check!(ArrI32, [i32::MIN, -1, i32::MAX]);
+        check!(ArrI64, [i64::MIN, -1, i64::MAX]);
+        check!(ArrI128, [i128::MIN, -1, i128::MAX]);
+        check!(
+            ArrF16,
+            [RawF16::from_bits(0x3c00), RawF16::from_bits(0x7c01)]
+        );
+        check!(ArrF64, [0.5f64, -1.0, f64::INFINITY]);
+        check!(
+            ArrF128,
+            [RawF128::from_bits(1), RawF128::from_bits(u128::MAX)]
+        );
+    }
+
+    #[test]
+    fn dimension_mismatch() {
+        let mut buf = ArrayVec::<u8, 100>::new();
+        assert_eq!(
+            Err(VerboseEncodeError::ArrayDimensionsMismatch {
+                dimensions_product: 6,
+                number_of_elements: 5,
+            }),
+            add_array_to_msg(&mut buf, false, None, &[2, 3], [1u8, 2, 3, 4, 5])
+        );
+        // overflowing dimensions
+        let dims = [u16::MAX; 8];
+        assert_eq!(
+            Err(VerboseEncodeError::ArrayDimensionsMismatch {
+                dimensions_product: usize::MAX,
+                number_of_elements: 0,
+            }),
+            add_array_to_msg(&mut buf, false, None, &dims, [0u8; 0])
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn too_long() {
+        let mut buf = ArrayVec::<u8, 100>::new();
+        let values = [0u32; 0x4000];
+        assert_eq!(
+            Err(VerboseEncodeError::ArrayTooLong(4 + 2 + 2 + 0x4000 * 4)),
+            add_array_to_msg(&mut buf, false, None, &[0x4000], values)
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn capacity_error() {
+        let mut buf = ArrayVec::<u8, 10>::new();
+        assert_eq!(
+            Err(VerboseEncodeError::Capacity(CapacityError::new(()))),
+            add_array_to_msg(&mut buf, false, None, &[4], [0u32; 4])
+        );
+    }
+}
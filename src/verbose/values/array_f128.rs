@@ -1,8 +1,9 @@
-use crate::verbose::{ArrayDimensions, RawF128, VariableInfoUnit};
+use crate::error::VerboseEncodeError;
+use crate::verbose::{ArrayDimensions, FieldWriter, RawF128, VariableInfoUnit};
 
 #[cfg(feature = "serde")]
 use super::ArrayItDimension;
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
 
@@ -33,51 +34,18 @@ impl<'a> ArrayF128<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b1000_0101, 0b0000_0001, 0b0000_0000, 0b0000_0000],
+            self.variable_info.is_some(),
+            false,
+        )?;
+        writer.write_array_dimensions(&self.dimensions, is_big_endian)?;
         if let Some(var_info) = &self.variable_info {
-            let (name_len, unit_len, number_of_dimensions) = if is_big_endian {
-                (
-                    (var_info.name.len() as u16 + 1).to_be_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_be_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes(),
-                )
-            } else {
-                (
-                    (var_info.name.len() as u16 + 1).to_le_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_le_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes(),
-                )
-            };
-
-            let type_info: [u8; 4] = [0b1000_0101, 0b0000_1001, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-
-            buf.try_extend_from_slice(&number_of_dimensions)?;
-            buf.try_extend_from_slice(self.dimensions.dimensions)?;
-            buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-            buf.try_extend_from_slice(var_info.name.as_bytes())?;
-            if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                // Safe as capacity is checked earlier
-                unsafe { buf.push_unchecked(0) };
-                let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                unsafe { buf.push_unchecked(0) };
-            } else {
-                return Err(CapacityError::new(()));
-            }
-            buf.try_extend_from_slice(self.data)?;
-        } else {
-            let number_of_dimensions = if is_big_endian {
-                (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes()
-            } else {
-                (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes()
-            };
-            let type_info: [u8; 4] = [0b1000_0101, 0b0000_0001, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&number_of_dimensions)?;
-            buf.try_extend_from_slice(self.dimensions.dimensions)?;
-            buf.try_extend_from_slice(self.data)?;
+            writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
         }
-        Ok(())
+        writer.write_raw(self.data)
     }
 }
 
@@ -268,15 +236,17 @@ impl<'a> IntoIterator for &'a ArrayF128<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::iter_nth_zero, clippy::neg_multiply, clippy::unnecessary_cast)]
 mod test {
     use super::*;
     use crate::error::UnexpectedEndOfSliceError;
     use crate::error::VerboseDecodeError::UnexpectedEndOfSlice;
+    use crate::verbose::test_dimension;
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::ArrF128;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
     use std::mem::size_of;
 
     type TestType<'a> = ArrayF128<'a>;
@@ -284,7 +254,7 @@ mod test {
 
     proptest! {
         #[test]
-        fn write_read(ref name in "\\pc{0,20}", ref unit in "\\pc{0,20}", dim_count in 0u16..5) {
+        fn write_read(ref name in "\\pc{1,20}", ref unit in "\\pc{1,20}", dim_count in 0u16..5) {
             const TYPE_INFO_RAW: [u8; 4] = [0b1000_0101, 0b0000_0001, 0b0000_0000, 0b0000_0000];
             const VAR_INFO_FLAG: u8 = 0b0000_1000;
 
@@ -301,7 +271,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -315,7 +285,7 @@ mod test {
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_be_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_be_bytes();
@@ -351,7 +321,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_le_bytes());
@@ -364,7 +334,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_le_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_le_bytes();
@@ -400,7 +370,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -413,7 +383,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                 let mut content_buff = Vec::new();
 
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -440,7 +410,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_le_bytes());
@@ -453,7 +423,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let mut content_buff = Vec::new();
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -482,7 +452,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -496,7 +466,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
 
                 // Now wrap back
@@ -517,7 +487,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -531,7 +501,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -551,7 +521,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -565,7 +535,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -585,7 +555,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -599,7 +569,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -636,7 +606,7 @@ mod test {
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
                 let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-                prop_assert_eq!(err, Err(CapacityError::new(())));
+                prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 }
 
@@ -671,7 +641,7 @@ mod test {
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
                 let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-                prop_assert_eq!(err, Err(CapacityError::new(())));
+                prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 }
         }
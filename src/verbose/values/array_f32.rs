@@ -1,8 +1,9 @@
-use crate::verbose::{ArrayDimensions, VariableInfoUnit};
+use crate::error::VerboseEncodeError;
+use crate::verbose::{ArrayDimensions, FieldWriter, VariableInfoUnit};
 
 #[cfg(feature = "serde")]
 use super::ArrayItDimension;
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
 
@@ -29,51 +30,18 @@ impl<'a> ArrayF32<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b1000_0011, 0b0000_0001, 0b0000_0000, 0b0000_0000],
+            self.variable_info.is_some(),
+            false,
+        )?;
+        writer.write_array_dimensions(&self.dimensions, is_big_endian)?;
         if let Some(var_info) = &self.variable_info {
-            let (name_len, unit_len, number_of_dimensions) = if is_big_endian {
-                (
-                    (var_info.name.len() as u16 + 1).to_be_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_be_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes(),
-                )
-            } else {
-                (
-                    (var_info.name.len() as u16 + 1).to_le_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_le_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes(),
-                )
-            };
-
-            let type_info: [u8; 4] = [0b1000_0011, 0b0000_1001, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-
-            buf.try_extend_from_slice(&number_of_dimensions)?;
-            buf.try_extend_from_slice(self.dimensions.dimensions)?;
-            buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-            buf.try_extend_from_slice(var_info.name.as_bytes())?;
-            if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                // Safe as capacity is checked earlier
-                unsafe { buf.push_unchecked(0) };
-                let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                unsafe { buf.push_unchecked(0) };
-            } else {
-                return Err(CapacityError::new(()));
-            }
-            buf.try_extend_from_slice(self.data)?;
-        } else {
-            let number_of_dimensions = if is_big_endian {
-                (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes()
-            } else {
-                (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes()
-            };
-            let type_info: [u8; 4] = [0b1000_0011, 0b0000_0001, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&number_of_dimensions)?;
-            buf.try_extend_from_slice(self.dimensions.dimensions)?;
-            buf.try_extend_from_slice(self.data)?;
+            writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
         }
-        Ok(())
+        writer.write_raw(self.data)
     }
 }
 
@@ -146,15 +114,17 @@ impl<'a> Serialize for ArrayF32Iterator<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::neg_multiply, clippy::unnecessary_cast)]
 mod test {
     use super::*;
     use crate::error::UnexpectedEndOfSliceError;
     use crate::error::VerboseDecodeError::UnexpectedEndOfSlice;
+    use crate::verbose::test_dimension;
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::ArrF32;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
     use std::mem::size_of;
 
     type TestType<'a> = ArrayF32<'a>;
@@ -162,7 +132,7 @@ mod test {
 
     proptest! {
         #[test]
-        fn write_read(ref name in "\\pc{0,20}", ref unit in "\\pc{0,20}", dim_count in 0u16..5) {
+        fn write_read(ref name in "\\pc{1,20}", ref unit in "\\pc{1,20}", dim_count in 0u16..5) {
             const TYPE_INFO_RAW: [u8; 4] = [0b1000_0011, 0b0000_0001, 0b0000_0000, 0b0000_0000];
             const VAR_INFO_FLAG: u8 = 0b0000_1000;
 
@@ -179,7 +149,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as i16 {
                         if x % 2 == 1 {
                             content.extend_from_slice(&(InternalTypes::from(x)).to_be_bytes());
@@ -193,7 +163,7 @@ mod test {
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_be_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_be_bytes();
@@ -229,7 +199,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as i16 {
                         if x % 2 == 1 {
                             content.extend_from_slice(&(InternalTypes::from(x)).to_le_bytes());
@@ -242,7 +212,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_le_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_le_bytes();
@@ -278,7 +248,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as i16 {
                         if x % 2 == 1 {
                             content.extend_from_slice(&(InternalTypes::from(x)).to_be_bytes());
@@ -291,7 +261,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                 let mut content_buff = Vec::new();
 
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -318,7 +288,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as i16 {
                         if x % 2 == 1 {
                             content.extend_from_slice(&(InternalTypes::from(x)).to_le_bytes());
@@ -331,7 +301,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let mut content_buff = Vec::new();
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -360,7 +330,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     for x in 0..(i-1) as i16 {
                         if x % 2 == 1 {
@@ -374,7 +344,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
 
                 // Now wrap back
@@ -395,7 +365,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as i16 {
                         if x % 2 == 1 {
@@ -409,7 +379,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -429,7 +399,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     for x in 0..(i-1) as i16 {
                         if x % 2 == 1 {
@@ -443,7 +413,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -463,7 +433,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as i16 {
                         if x % 2 == 1 {
@@ -477,7 +447,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -514,7 +484,7 @@ mod test {
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
                 let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-                prop_assert_eq!(err, Err(CapacityError::new(())));
+                prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 }
 
@@ -549,7 +519,7 @@ mod test {
                 let arr = TestType {is_big_endian, variable_info,dimensions:arr_dim,data: &content };
                 let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-                prop_assert_eq!(err, Err(CapacityError::new(())));
+                prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 }
         }
@@ -1,8 +1,9 @@
-use crate::verbose::{ArrayDimensions, Scaling, VariableInfoUnit};
+use crate::error::VerboseEncodeError;
+use crate::verbose::{ArrayDimensions, FieldWriter, Scaling, VariableInfoUnit};
 
 #[cfg(feature = "serde")]
 use super::ArrayItDimension;
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
 
@@ -30,108 +31,19 @@ impl<'a> ArrayI128<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b0010_0101, 0b0000_0001, 0b0000_0000, 0b0000_0000],
+            self.variable_info.is_some(),
+            self.scaling.is_some(),
+        )?;
+        writer.write_array_dimensions(&self.dimensions, is_big_endian)?;
         if let Some(var_info) = &self.variable_info {
-            let (name_len, unit_len, number_of_dimensions) = if is_big_endian {
-                (
-                    (var_info.name.len() as u16 + 1).to_be_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_be_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes(),
-                )
-            } else {
-                (
-                    (var_info.name.len() as u16 + 1).to_le_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_le_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes(),
-                )
-            };
-
-            if let Some(scaler) = &self.scaling {
-                let type_info = [0b0010_0101, 0b0001_1001, 0b0000_0000, 0b0000_0000];
-                let quantization;
-                let offset;
-
-                if is_big_endian {
-                    quantization = scaler.quantization.to_be_bytes();
-                    offset = scaler.offset.to_be_bytes();
-                } else {
-                    quantization = scaler.quantization.to_le_bytes();
-                    offset = scaler.offset.to_le_bytes();
-                }
-
-                buf.try_extend_from_slice(&type_info)?;
-
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-                buf.try_extend_from_slice(var_info.name.as_bytes())?;
-                if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                    // Safe as capacity is checked earlier
-                    unsafe { buf.push_unchecked(0) };
-                    let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                    unsafe { buf.push_unchecked(0) };
-                } else {
-                    return Err(CapacityError::new(()));
-                }
-                buf.try_extend_from_slice(&quantization)?;
-                buf.try_extend_from_slice(&offset)?;
-                buf.try_extend_from_slice(self.data)?;
-                Ok(())
-            } else {
-                let type_info: [u8; 4] = [0b0010_0101, 0b0000_1001, 0b0000_0000, 0b0000_0000];
-                buf.try_extend_from_slice(&type_info)?;
-
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-                buf.try_extend_from_slice(var_info.name.as_bytes())?;
-                if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                    // Safe as capacity is checked earlier
-                    unsafe { buf.push_unchecked(0) };
-                    let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                    unsafe { buf.push_unchecked(0) };
-                } else {
-                    return Err(CapacityError::new(()));
-                }
-                buf.try_extend_from_slice(self.data)?;
-                Ok(())
-            }
-        } else {
-            let number_of_dimensions = if is_big_endian {
-                (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes()
-            } else {
-                (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes()
-            };
-
-            if let Some(scaler) = &self.scaling {
-                let type_info: [u8; 4] = [0b0010_0101, 0b0001_0001, 0b0000_0000, 0b0000_0000];
-
-                let quantization;
-                let offset;
-
-                if is_big_endian {
-                    quantization = scaler.quantization.to_be_bytes();
-                    offset = scaler.offset.to_be_bytes();
-                } else {
-                    quantization = scaler.quantization.to_le_bytes();
-                    offset = scaler.offset.to_le_bytes();
-                }
-
-                buf.try_extend_from_slice(&type_info)?;
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&quantization)?;
-                buf.try_extend_from_slice(&offset)?;
-                buf.try_extend_from_slice(self.data)?;
-            } else {
-                let type_info: [u8; 4] = [0b0010_0101, 0b0000_0001, 0b0000_0000, 0b0000_0000];
-                buf.try_extend_from_slice(&type_info)?;
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(self.data)?;
-            }
-            Ok(())
+            writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
         }
+        writer.write_i128_scaling(&self.scaling, is_big_endian)?;
+        writer.write_raw(self.data)
     }
 }
 
@@ -239,15 +151,17 @@ impl<'a> Serialize for ArrayI128Iterator<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::neg_multiply, clippy::unnecessary_cast)]
 mod test {
     use super::*;
     use crate::error::UnexpectedEndOfSliceError;
     use crate::error::VerboseDecodeError::UnexpectedEndOfSlice;
+    use crate::verbose::test_dimension;
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::ArrI128;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
     use std::mem::size_of;
 
     type TestType<'a> = ArrayI128<'a>;
@@ -255,7 +169,7 @@ mod test {
 
     proptest! {
         #[test]
-        fn write_read(ref name in "\\pc{0,20}", ref unit in "\\pc{0,20}", quantization in any::<f32>(), offset in any::<i128>(), dim_count in 0u16..5) {
+        fn write_read(ref name in "\\pc{1,20}", ref unit in "\\pc{1,20}", quantization in any::<f32>(), offset in any::<i128>(), dim_count in 0u16..5) {
             const TYPE_INFO_RAW: [u8; 4] = [0b0010_0101, 0b0000_0001, 0b0000_0000, 0b0000_0000];
             const VAR_INFO_FLAG: u8 = 0b0000_1000;
             const FIXED_POINT_FLAG: u8 = 0b0001_0000;
@@ -280,7 +194,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i + 1).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -301,7 +215,7 @@ mod test {
                         data: &content,
                         scaling,
                     };
-                arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_be_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_be_bytes();
@@ -352,7 +266,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i + 1).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_le_bytes());
@@ -373,7 +287,7 @@ mod test {
                         data: &content,
                         scaling,
                     };
-                arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_le_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_le_bytes();
@@ -418,7 +332,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -432,7 +346,7 @@ mod test {
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_be_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_be_bytes();
@@ -469,7 +383,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_le_bytes());
@@ -482,7 +396,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr_u = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_le_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_le_bytes();
@@ -519,7 +433,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -532,7 +446,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                 let mut content_buff = Vec::new();
 
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -560,7 +474,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_le_bytes());
@@ -573,7 +487,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let mut content_buff = Vec::new();
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -601,7 +515,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -614,7 +528,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                 let mut content_buff = Vec::new();
 
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1] | FIXED_POINT_FLAG, TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -644,7 +558,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_le_bytes());
@@ -657,7 +571,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                 let mut content_buff = Vec::new();
 
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1] | FIXED_POINT_FLAG, TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -688,7 +602,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 // for i in 1u16..=dim_count {
-                //     dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                //     dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                 //     for x in 0..(i-1) as InternalTypes {
                 //         content.extend_from_slice(&x.to_be_bytes());
@@ -696,7 +610,7 @@ mod test {
                 // }
 
                 for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                 for x in 0..i as InternalTypes {
                     if x % 2 == 1 {
                         content.extend_from_slice(&x.to_be_bytes());
@@ -709,7 +623,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
                 prop_assert_eq!(parsed_back, Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError { layer: crate::error::Layer::VerboseValue, minimum_size: msg_buff.len() + size_of::<InternalTypes>() * dim_count as usize, actual_size: msg_buff.len() })));
@@ -729,7 +643,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -743,7 +657,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -763,7 +677,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -777,7 +691,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
 
                 // Now wrap back
@@ -799,7 +713,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -813,7 +727,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -833,7 +747,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -847,7 +761,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -868,7 +782,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -882,7 +796,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -902,7 +816,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -916,7 +830,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -937,7 +851,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -951,7 +865,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -990,7 +904,7 @@ mod test {
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
                 let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-                prop_assert_eq!(err, Err(CapacityError::new(())));
+                prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 }
 
@@ -1026,7 +940,7 @@ mod test {
                 let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
                 let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-                prop_assert_eq!(err, Err(CapacityError::new(())));
+                prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 }
         }
@@ -1058,7 +972,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 0 {
                             content.extend_from_slice(&x.to_be_bytes());
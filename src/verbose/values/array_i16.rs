@@ -1,8 +1,9 @@
-use crate::verbose::{ArrayDimensions, Scaling, VariableInfoUnit};
+use crate::error::VerboseEncodeError;
+use crate::verbose::{ArrayDimensions, FieldWriter, Scaling, VariableInfoUnit};
 
 #[cfg(feature = "serde")]
 use super::ArrayItDimension;
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
 
@@ -30,108 +31,19 @@ impl<'a> ArrayI16<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b0010_0010, 0b0000_0001, 0b0000_0000, 0b0000_0000],
+            self.variable_info.is_some(),
+            self.scaling.is_some(),
+        )?;
+        writer.write_array_dimensions(&self.dimensions, is_big_endian)?;
         if let Some(var_info) = &self.variable_info {
-            let (name_len, unit_len, number_of_dimensions) = if is_big_endian {
-                (
-                    (var_info.name.len() as u16 + 1).to_be_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_be_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes(),
-                )
-            } else {
-                (
-                    (var_info.name.len() as u16 + 1).to_le_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_le_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes(),
-                )
-            };
-
-            if let Some(scaler) = &self.scaling {
-                let type_info = [0b0010_0010, 0b0001_1001, 0b0000_0000, 0b0000_0000];
-                let quantization;
-                let offset;
-
-                if is_big_endian {
-                    quantization = scaler.quantization.to_be_bytes();
-                    offset = scaler.offset.to_be_bytes();
-                } else {
-                    quantization = scaler.quantization.to_le_bytes();
-                    offset = scaler.offset.to_le_bytes();
-                }
-
-                buf.try_extend_from_slice(&type_info)?;
-
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-                buf.try_extend_from_slice(var_info.name.as_bytes())?;
-                if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                    // Safe as capacity is checked earlier
-                    unsafe { buf.push_unchecked(0) };
-                    let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                    unsafe { buf.push_unchecked(0) };
-                } else {
-                    return Err(CapacityError::new(()));
-                }
-                buf.try_extend_from_slice(&quantization)?;
-                buf.try_extend_from_slice(&offset)?;
-                buf.try_extend_from_slice(self.data)?;
-                Ok(())
-            } else {
-                let type_info: [u8; 4] = [0b0010_0010, 0b0000_1001, 0b0000_0000, 0b0000_0000];
-                buf.try_extend_from_slice(&type_info)?;
-
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-                buf.try_extend_from_slice(var_info.name.as_bytes())?;
-                if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                    // Safe as capacity is checked earlier
-                    unsafe { buf.push_unchecked(0) };
-                    let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                    unsafe { buf.push_unchecked(0) };
-                } else {
-                    return Err(CapacityError::new(()));
-                }
-                buf.try_extend_from_slice(self.data)?;
-                Ok(())
-            }
-        } else {
-            let number_of_dimensions = if is_big_endian {
-                (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes()
-            } else {
-                (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes()
-            };
-
-            if let Some(scaler) = &self.scaling {
-                let type_info: [u8; 4] = [0b0010_0010, 0b0001_0001, 0b0000_0000, 0b0000_0000];
-
-                let quantization;
-                let offset;
-
-                if is_big_endian {
-                    quantization = scaler.quantization.to_be_bytes();
-                    offset = scaler.offset.to_be_bytes();
-                } else {
-                    quantization = scaler.quantization.to_le_bytes();
-                    offset = scaler.offset.to_le_bytes();
-                }
-
-                buf.try_extend_from_slice(&type_info)?;
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&quantization)?;
-                buf.try_extend_from_slice(&offset)?;
-                buf.try_extend_from_slice(self.data)?;
-            } else {
-                let type_info: [u8; 4] = [0b0010_0010, 0b0000_0001, 0b0000_0000, 0b0000_0000];
-                buf.try_extend_from_slice(&type_info)?;
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(self.data)?;
-            }
-            Ok(())
+            writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
         }
+        writer.write_i32_scaling(&self.scaling, is_big_endian)?;
+        writer.write_raw(self.data)
     }
 }
 
@@ -205,15 +117,17 @@ impl<'a> Serialize for ArrayI16Iterator<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::neg_multiply, clippy::unnecessary_cast)]
 mod test {
     use super::*;
     use crate::error::UnexpectedEndOfSliceError;
     use crate::error::VerboseDecodeError::UnexpectedEndOfSlice;
+    use crate::verbose::test_dimension;
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::ArrI16;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
     use std::mem::size_of;
 
     type TestType<'a> = ArrayI16<'a>;
@@ -221,7 +135,7 @@ mod test {
 
     proptest! {
         #[test]
-        fn write_read(ref name in "\\pc{0,20}", ref unit in "\\pc{0,20}", quantization in any::<f32>(), offset in any::<i32>(), dim_count in 0u16..5) {
+        fn write_read(ref name in "\\pc{1,20}", ref unit in "\\pc{1,20}", quantization in any::<f32>(), offset in any::<i32>(), dim_count in 0u16..5) {
             const TYPE_INFO_RAW: [u8; 4] = [0b0010_0010, 0b0000_0001, 0b0000_0000, 0b0000_0000];
             const VAR_INFO_FLAG: u8 = 0b0000_1000;
             const FIXED_POINT_FLAG: u8 = 0b0001_0000;
@@ -246,7 +160,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i + 1).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -262,7 +176,7 @@ mod test {
                     dimensions: &dimensions,
                 };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_be_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_be_bytes();
@@ -313,7 +227,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i + 1).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_le_bytes());
@@ -329,7 +243,7 @@ mod test {
                     dimensions: &dimensions,
                 };
                 let arr_u = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_le_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_le_bytes();
@@ -374,7 +288,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -388,7 +302,7 @@ mod test {
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_be_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_be_bytes();
@@ -425,7 +339,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_le_bytes());
@@ -438,7 +352,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr_u = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let len_name = (name.len() as u16 + 1).to_le_bytes();
                 let len_unit = (unit.len() as u16 + 1).to_le_bytes();
@@ -475,7 +389,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -488,7 +402,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                 let mut content_buff = Vec::new();
 
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -516,7 +430,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_le_bytes());
@@ -529,7 +443,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 let mut content_buff = Vec::new();
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -557,7 +471,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -570,7 +484,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                 let mut content_buff = Vec::new();
 
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1] | FIXED_POINT_FLAG, TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -600,7 +514,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_le_bytes());
@@ -613,7 +527,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                 let mut content_buff = Vec::new();
 
                 content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1] | FIXED_POINT_FLAG, TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -644,7 +558,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 // for i in 1u16..=dim_count {
-                //     dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                //     dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                 //     for x in 0..(i-1) as InternalTypes {
                 //         content.extend_from_slice(&x.to_be_bytes());
@@ -652,7 +566,7 @@ mod test {
                 // }
 
                 for i in 0..dim_count {
-                    dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                 for x in 0..i as InternalTypes {
                     if x % 2 == 1 {
                         content.extend_from_slice(&x.to_be_bytes());
@@ -665,7 +579,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
                 prop_assert_eq!(parsed_back, Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError { layer: crate::error::Layer::VerboseValue, minimum_size: msg_buff.len() + size_of::<InternalTypes>() * dim_count as usize, actual_size: msg_buff.len() })));
@@ -685,7 +599,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -699,7 +613,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -719,7 +633,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -733,7 +647,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
 
                 // Now wrap back
@@ -755,7 +669,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -769,7 +683,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -789,7 +703,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -803,7 +717,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -824,7 +738,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -838,7 +752,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -858,7 +772,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -872,7 +786,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -893,7 +807,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 1u16..=dim_count {
-                    dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                    dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                     for x in 0..(i-1) as InternalTypes {
                         if x % 2 == 1 {
@@ -907,7 +821,7 @@ mod test {
 
                 let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
-                arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                 // Now wrap back
                 let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -946,7 +860,7 @@ mod test {
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
                 let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-                prop_assert_eq!(err, Err(CapacityError::new(())));
+                prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 }
 
@@ -982,7 +896,7 @@ mod test {
                 let arr = TestType {is_big_endian, variable_info, scaling,dimensions:arr_dim,data: &content };
                 let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-                prop_assert_eq!(err, Err(CapacityError::new(())));
+                prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 }
         }
@@ -1014,7 +928,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 0 {
                             content.extend_from_slice(&x.to_be_bytes());
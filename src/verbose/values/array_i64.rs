@@ -1,8 +1,9 @@
-use crate::verbose::{ArrayDimensions, Scaling, VariableInfoUnit};
+use crate::error::VerboseEncodeError;
+use crate::verbose::{ArrayDimensions, FieldWriter, Scaling, VariableInfoUnit};
 
 #[cfg(feature = "serde")]
 use super::ArrayItDimension;
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
 
@@ -30,108 +31,19 @@ impl<'a> ArrayI64<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b0010_0100, 0b0000_0001, 0b0000_0000, 0b0000_0000],
+            self.variable_info.is_some(),
+            self.scaling.is_some(),
+        )?;
+        writer.write_array_dimensions(&self.dimensions, is_big_endian)?;
         if let Some(var_info) = &self.variable_info {
-            let (name_len, unit_len, number_of_dimensions) = if is_big_endian {
-                (
-                    (var_info.name.len() as u16 + 1).to_be_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_be_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes(),
-                )
-            } else {
-                (
-                    (var_info.name.len() as u16 + 1).to_le_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_le_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes(),
-                )
-            };
-
-            if let Some(scaler) = &self.scaling {
-                let type_info = [0b0010_0100, 0b0001_1001, 0b0000_0000, 0b0000_0000];
-                let quantization;
-                let offset;
-
-                if is_big_endian {
-                    quantization = scaler.quantization.to_be_bytes();
-                    offset = scaler.offset.to_be_bytes();
-                } else {
-                    quantization = scaler.quantization.to_le_bytes();
-                    offset = scaler.offset.to_le_bytes();
-                }
-
-                buf.try_extend_from_slice(&type_info)?;
-
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-                buf.try_extend_from_slice(var_info.name.as_bytes())?;
-                if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                    // Safe as capacity is checked earlier
-                    unsafe { buf.push_unchecked(0) };
-                    let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                    unsafe { buf.push_unchecked(0) };
-                } else {
-                    return Err(CapacityError::new(()));
-                }
-                buf.try_extend_from_slice(&quantization)?;
-                buf.try_extend_from_slice(&offset)?;
-                buf.try_extend_from_slice(self.data)?;
-                Ok(())
-            } else {
-                let type_info: [u8; 4] = [0b0010_0100, 0b0000_1001, 0b0000_0000, 0b0000_0000];
-                buf.try_extend_from_slice(&type_info)?;
-
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-                buf.try_extend_from_slice(var_info.name.as_bytes())?;
-                if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                    // Safe as capacity is checked earlier
-                    unsafe { buf.push_unchecked(0) };
-                    let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                    unsafe { buf.push_unchecked(0) };
-                } else {
-                    return Err(CapacityError::new(()));
-                }
-                buf.try_extend_from_slice(self.data)?;
-                Ok(())
-            }
-        } else {
-            let number_of_dimensions = if is_big_endian {
-                (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes()
-            } else {
-                (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes()
-            };
-
-            if let Some(scaler) = &self.scaling {
-                let type_info: [u8; 4] = [0b0010_0100, 0b0001_0001, 0b0000_0000, 0b0000_0000];
-
-                let quantization;
-                let offset;
-
-                if is_big_endian {
-                    quantization = scaler.quantization.to_be_bytes();
-                    offset = scaler.offset.to_be_bytes();
-                } else {
-                    quantization = scaler.quantization.to_le_bytes();
-                    offset = scaler.offset.to_le_bytes();
-                }
-
-                buf.try_extend_from_slice(&type_info)?;
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&quantization)?;
-                buf.try_extend_from_slice(&offset)?;
-                buf.try_extend_from_slice(self.data)?;
-            } else {
-                let type_info: [u8; 4] = [0b0010_0100, 0b0000_0001, 0b0000_0000, 0b0000_0000];
-                buf.try_extend_from_slice(&type_info)?;
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(self.data)?;
-            }
-            Ok(())
+            writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
         }
+        writer.write_i64_scaling(&self.scaling, is_big_endian)?;
+        writer.write_raw(self.data)
     }
 }
 
@@ -223,15 +135,17 @@ impl<'a> Serialize for ArrayI64Iterator<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::neg_multiply, clippy::unnecessary_cast)]
 mod test {
     use super::*;
     use crate::error::UnexpectedEndOfSliceError;
     use crate::error::VerboseDecodeError::UnexpectedEndOfSlice;
+    use crate::verbose::test_dimension;
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::ArrI64;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
     use std::mem::size_of;
 
     type TestType<'a> = ArrayI64<'a>;
@@ -239,7 +153,7 @@ mod test {
 
     proptest! {
             #[test]
-            fn write_read(ref name in "\\pc{0,20}", ref unit in "\\pc{0,20}", quantization in any::<f32>(), offset in any::<i64>(), dim_count in 0u16..5) {
+            fn write_read(ref name in "\\pc{1,20}", ref unit in "\\pc{1,20}", quantization in any::<f32>(), offset in any::<i64>(), dim_count in 0u16..5) {
                 const TYPE_INFO_RAW: [u8; 4] = [0b0010_0100, 0b0000_0001, 0b0000_0000, 0b0000_0000];
                 const VAR_INFO_FLAG: u8 = 0b0000_1000;
                 const FIXED_POINT_FLAG: u8 = 0b0001_0000;
@@ -264,7 +178,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i + 1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                         for x in 0..=i as InternalTypes {
                             if x % 2 == 1 {
                                 content.extend_from_slice(&x.to_be_bytes());
@@ -285,7 +199,7 @@ mod test {
                         data: &content,
                         scaling,
                     };
-                    arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     let len_name = (name.len() as u16 + 1).to_be_bytes();
                     let len_unit = (unit.len() as u16 + 1).to_be_bytes();
@@ -336,7 +250,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i + 1).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                         for x in 0..=i as InternalTypes {
                             if x % 2 == 1 {
                                 content.extend_from_slice(&x.to_le_bytes());
@@ -357,7 +271,7 @@ mod test {
                         data: &content,
                         scaling,
                     };
-                    arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     let len_name = (name.len() as u16 + 1).to_le_bytes();
                     let len_unit = (unit.len() as u16 + 1).to_le_bytes();
@@ -402,7 +316,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 0..dim_count {
-                            dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                            dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                         for x in 0..=i as InternalTypes {
                             if x % 2 == 1 {
                                 content.extend_from_slice(&x.to_be_bytes());
@@ -416,7 +330,7 @@ mod test {
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     let len_name = (name.len() as u16 + 1).to_be_bytes();
                     let len_unit = (unit.len() as u16 + 1).to_be_bytes();
@@ -453,7 +367,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 0..dim_count {
-                            dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                            dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                         for x in 0..=i as InternalTypes {
                             if x % 2 == 1 {
                                 content.extend_from_slice(&x.to_le_bytes());
@@ -466,7 +380,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr_u = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     let len_name = (name.len() as u16 + 1).to_le_bytes();
                     let len_unit = (unit.len() as u16 + 1).to_le_bytes();
@@ -503,7 +417,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 0..dim_count {
-                            dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                            dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                         for x in 0..=i as InternalTypes {
                             if x % 2 == 1 {
                                 content.extend_from_slice(&x.to_be_bytes());
@@ -516,7 +430,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                     let mut content_buff = Vec::new();
 
                     content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -544,7 +458,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 0..dim_count {
-                            dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                            dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                         for x in 0..=i as InternalTypes {
                             if x % 2 == 1 {
                                 content.extend_from_slice(&x.to_le_bytes());
@@ -557,7 +471,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     let mut content_buff = Vec::new();
                     content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -585,7 +499,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 0..dim_count {
-                            dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                            dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                         for x in 0..=i as InternalTypes {
                             if x % 2 == 1 {
                                 content.extend_from_slice(&x.to_be_bytes());
@@ -598,7 +512,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                     let mut content_buff = Vec::new();
 
                     content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1] | FIXED_POINT_FLAG, TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -628,7 +542,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 0..dim_count {
-                            dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                            dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                         for x in 0..=i as InternalTypes {
                             if x % 2 == 1 {
                                 content.extend_from_slice(&x.to_le_bytes());
@@ -641,7 +555,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                     let mut content_buff = Vec::new();
 
                     content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1] | FIXED_POINT_FLAG, TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -672,7 +586,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     // for i in 1u16..=dim_count {
-                    //     dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                    //     dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                     //     for x in 0..(i-1) as InternalTypes {
                     //         content.extend_from_slice(&x.to_be_bytes());
@@ -680,7 +594,7 @@ mod test {
                     // }
 
                     for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..i as InternalTypes {
                         if x % 2 == 1 {
                             content.extend_from_slice(&x.to_be_bytes());
@@ -693,7 +607,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
                     // Now wrap back
                     let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
                     prop_assert_eq!(parsed_back, Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError { layer: crate::error::Layer::VerboseValue, minimum_size: msg_buff.len() + size_of::<InternalTypes>() * dim_count as usize, actual_size: msg_buff.len() })));
@@ -713,7 +627,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 1u16..=dim_count {
-                        dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                         for x in 0..(i-1) as InternalTypes {
                             if x % 2 == 1 {
@@ -727,7 +641,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     // Now wrap back
                     let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -747,7 +661,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 1u16..=dim_count {
-                        dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                         for x in 0..(i-1) as InternalTypes {
                             if x % 2 == 1 {
@@ -761,7 +675,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
 
                     // Now wrap back
@@ -783,7 +697,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 1u16..=dim_count {
-                        dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                         for x in 0..(i-1) as InternalTypes {
                             if x % 2 == 1 {
@@ -797,7 +711,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     // Now wrap back
                     let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -817,7 +731,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 1u16..=dim_count {
-                        dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                         for x in 0..(i-1) as InternalTypes {
                             if x % 2 == 1 {
@@ -831,7 +745,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     // Now wrap back
                     let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -852,7 +766,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 1u16..=dim_count {
-                        dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                         for x in 0..(i-1) as InternalTypes {
                             if x % 2 == 1 {
@@ -866,7 +780,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     // Now wrap back
                     let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -886,7 +800,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 1u16..=dim_count {
-                        dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                         for x in 0..(i-1) as InternalTypes {
                             if x % 2 == 1 {
@@ -900,7 +814,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     // Now wrap back
                     let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -921,7 +835,7 @@ mod test {
                     let mut content = Vec::with_capacity(dim_count as usize);
 
                     for i in 1u16..=dim_count {
-                        dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                         for x in 0..(i-1) as InternalTypes {
                             if x % 2 == 1 {
@@ -935,7 +849,7 @@ mod test {
 
                     let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-                    arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+                    arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
                     // Now wrap back
                     let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -974,7 +888,7 @@ mod test {
                     let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
                     let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-                    prop_assert_eq!(err, Err(CapacityError::new(())));
+                    prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                     }
 
@@ -1010,7 +924,7 @@ mod test {
         let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
         let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-        prop_assert_eq!(err, Err(CapacityError::new(())));
+        prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
         }
             }
@@ -1042,7 +956,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                         if x % 2 == 0 {
                             content.extend_from_slice(&x.to_be_bytes());
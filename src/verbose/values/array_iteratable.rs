@@ -6,9 +6,7 @@ use super::{
 };
 use super::{RawF128, RawF16};
 #[cfg(feature = "serde")]
-use crate::verbose::std::mem::size_of;
-#[cfg(feature = "serde")]
-use serde::ser::{Serialize, SerializeSeq, Serializer};
+use serde::ser::{Error, Serialize, SerializeSeq, Serializer};
 
 #[cfg(feature = "serde")]
 #[derive(Clone, Debug)]
@@ -30,14 +28,16 @@ impl<'a, T: ArrayIteratable + Sized> Serialize for ArrayItDimension<'a, T> {
             2 => T::serialize_elements(self.is_big_endian, self.data, serializer),
             _ => {
                 // calculate memory step size
-                let mut stepsize: usize = 1;
+                let mut stepsize: usize = T::ELEMENT_SIZE;
                 for i in (2..self.dimensions.len()).step_by(2) {
                     let bytes = [self.dimensions[i], self.dimensions[i + 1]];
-                    stepsize *= usize::from(if self.is_big_endian {
-                        u16::from_be_bytes(bytes)
-                    } else {
-                        u16::from_le_bytes(bytes)
-                    });
+                    stepsize = stepsize
+                        .checked_mul(usize::from(if self.is_big_endian {
+                            u16::from_be_bytes(bytes)
+                        } else {
+                            u16::from_le_bytes(bytes)
+                        }))
+                        .ok_or_else(|| S::Error::custom("array dimensions too large"))?;
                 }
 
                 // determine own dim size & the subdimensions
@@ -55,13 +55,20 @@ impl<'a, T: ArrayIteratable + Sized> Serialize for ArrayItDimension<'a, T> {
                 let mut seq = serializer.serialize_seq(Some(dim_count))?;
                 for i in 0..dim_count {
                     // serialize subdimensions
-                    let block_start = i * stepsize * size_of::<T>();
-                    let block_end = (i + 1) * stepsize * size_of::<T>();
+                    // can not overflow as the previous blocks were in range
+                    let block_start = i * stepsize;
+                    let data = self
+                        .data
+                        .get(block_start..)
+                        .and_then(|rest| rest.get(..stepsize))
+                        .ok_or_else(|| {
+                            S::Error::custom("array data shorter than described by the dimensions")
+                        })?;
 
                     let subit = ArrayItDimension::<'a, T> {
                         is_big_endian: self.is_big_endian,
                         dimensions: sub_dimensions,
-                        data: &self.data[block_start..block_end],
+                        data,
                         phantom: Default::default(),
                     };
                     seq.serialize_element(&subit)?;
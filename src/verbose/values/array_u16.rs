@@ -1,8 +1,9 @@
-use crate::verbose::{ArrayDimensions, Scaling, VariableInfoUnit};
+use crate::error::VerboseEncodeError;
+use crate::verbose::{ArrayDimensions, FieldWriter, Scaling, VariableInfoUnit};
 
 #[cfg(feature = "serde")]
 use super::ArrayItDimension;
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 #[cfg(feature = "serde")]
 use serde::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
 
@@ -30,108 +31,19 @@ impl<'a> ArrayU16<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b0100_0010, 0b0000_0001, 0b0000_0000, 0b0000_0000],
+            self.variable_info.is_some(),
+            self.scaling.is_some(),
+        )?;
+        writer.write_array_dimensions(&self.dimensions, is_big_endian)?;
         if let Some(var_info) = &self.variable_info {
-            let (name_len, unit_len, number_of_dimensions) = if is_big_endian {
-                (
-                    (var_info.name.len() as u16 + 1).to_be_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_be_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes(),
-                )
-            } else {
-                (
-                    (var_info.name.len() as u16 + 1).to_le_bytes(),
-                    (var_info.unit.len() as u16 + 1).to_le_bytes(),
-                    (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes(),
-                )
-            };
-
-            if let Some(scaler) = &self.scaling {
-                let type_info = [0b0100_0010, 0b0001_1001, 0b0000_0000, 0b0000_0000];
-                let quantization;
-                let offset: [u8; 4];
-
-                if is_big_endian {
-                    quantization = scaler.quantization.to_be_bytes();
-                    offset = scaler.offset.to_be_bytes();
-                } else {
-                    quantization = scaler.quantization.to_le_bytes();
-                    offset = scaler.offset.to_le_bytes();
-                }
-
-                buf.try_extend_from_slice(&type_info)?;
-
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-                buf.try_extend_from_slice(var_info.name.as_bytes())?;
-                if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                    // Safe as capacity is checked earlier
-                    unsafe { buf.push_unchecked(0) };
-                    let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                    unsafe { buf.push_unchecked(0) };
-                } else {
-                    return Err(CapacityError::new(()));
-                }
-                buf.try_extend_from_slice(&quantization)?;
-                buf.try_extend_from_slice(&offset)?;
-                buf.try_extend_from_slice(self.data)?;
-                Ok(())
-            } else {
-                let type_info: [u8; 4] = [0b0100_0010, 0b0000_1001, 0b0000_0000, 0b0000_0000];
-                buf.try_extend_from_slice(&type_info)?;
-
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-                buf.try_extend_from_slice(var_info.name.as_bytes())?;
-                if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                    // Safe as capacity is checked earlier
-                    unsafe { buf.push_unchecked(0) };
-                    let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                    unsafe { buf.push_unchecked(0) };
-                } else {
-                    return Err(CapacityError::new(()));
-                }
-                buf.try_extend_from_slice(self.data)?;
-                Ok(())
-            }
-        } else {
-            let number_of_dimensions = if is_big_endian {
-                (self.dimensions.dimensions.len() as u16 / 2).to_be_bytes()
-            } else {
-                (self.dimensions.dimensions.len() as u16 / 2).to_le_bytes()
-            };
-
-            if let Some(scaler) = &self.scaling {
-                let type_info: [u8; 4] = [0b0100_0010, 0b0001_0001, 0b0000_0000, 0b0000_0000];
-
-                let quantization;
-                let offset: [u8; 4];
-
-                if is_big_endian {
-                    quantization = scaler.quantization.to_be_bytes();
-                    offset = scaler.offset.to_be_bytes();
-                } else {
-                    quantization = scaler.quantization.to_le_bytes();
-                    offset = scaler.offset.to_le_bytes();
-                }
-
-                buf.try_extend_from_slice(&type_info)?;
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(&quantization)?;
-                buf.try_extend_from_slice(&offset)?;
-                buf.try_extend_from_slice(self.data)?;
-            } else {
-                let type_info: [u8; 4] = [0b0100_0010, 0b0000_0001, 0b0000_0000, 0b0000_0000];
-                buf.try_extend_from_slice(&type_info)?;
-                buf.try_extend_from_slice(&number_of_dimensions)?;
-                buf.try_extend_from_slice(self.dimensions.dimensions)?;
-                buf.try_extend_from_slice(self.data)?;
-            }
-            Ok(())
+            writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
         }
+        writer.write_i32_scaling(&self.scaling, is_big_endian)?;
+        writer.write_raw(self.data)
     }
 }
 
@@ -205,15 +117,17 @@ impl<'a> Serialize for ArrayU16Iterator<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::unnecessary_cast)]
 mod test {
     use super::*;
     use crate::error::UnexpectedEndOfSliceError;
     use crate::error::VerboseDecodeError::UnexpectedEndOfSlice;
+    use crate::verbose::test_dimension;
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::ArrU16;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
     use std::mem::size_of;
 
     type TestType<'a> = ArrayU16<'a>;
@@ -221,7 +135,7 @@ mod test {
 
     proptest! {
         #[test]
-        fn write_read(ref name in "\\pc{0,20}", ref unit in "\\pc{0,20}", quantization in any::<f32>(), offset in any::<i32>(), dim_count in 0u16..5) {
+        fn write_read(ref name in "\\pc{1,20}", ref unit in "\\pc{1,20}", quantization in any::<f32>(), offset in any::<i32>(), dim_count in 0u16..5) {
             const TYPE_INFO_RAW: [u8; 4] = [0b0100_0010, 0b0000_0001, 0b0000_0000, 0b0000_0000];
             const VAR_INFO_FLAG: u8 = 0b0000_1000;
             const FIXED_POINT_FLAG: u8 = 0b0001_0000;
@@ -246,7 +160,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 0..dim_count {
-                 dimensions.extend_from_slice(&(i + 1).to_be_bytes());
+                 dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                  for x in 0..=i as InternalTypes {
                      content.extend_from_slice(&x.to_be_bytes()); // Sample U16s
                  }
@@ -262,7 +176,7 @@ mod test {
                         data: &content,
                         scaling,
                     };
-             arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              let len_name = (name.len() as u16 + 1).to_be_bytes();
              let len_unit = (unit.len() as u16 + 1).to_be_bytes();
@@ -313,7 +227,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 0..dim_count {
-                 dimensions.extend_from_slice(&(i + 1).to_le_bytes());
+                 dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                  for x in 0..=i as InternalTypes {
                      content.extend_from_slice(&x.to_be_bytes()); // Sample U16s
                  }
@@ -329,7 +243,7 @@ mod test {
                         data: &content,
                         scaling,
                     };
-             arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              let len_name = (name.len() as u16 + 1).to_le_bytes();
              let len_unit = (unit.len() as u16 + 1).to_le_bytes();
@@ -374,7 +288,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 0..dim_count {
-                     dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                     dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                  for x in 0..=i as InternalTypes {
                      content.extend_from_slice(&x.to_be_bytes());       // Sample U16s
                  }
@@ -383,7 +297,7 @@ mod test {
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
 
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              let len_name = (name.len() as u16 + 1).to_be_bytes();
              let len_unit = (unit.len() as u16 + 1).to_be_bytes();
@@ -420,7 +334,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 0..dim_count {
-                     dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                     dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                  for x in 0..=i as InternalTypes {
                      content.extend_from_slice(&x.to_be_bytes());       // Sample U16s
                  }
@@ -428,7 +342,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr_u = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr_u.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr_u.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              let len_name = (name.len() as u16 + 1).to_le_bytes();
              let len_unit = (unit.len() as u16 + 1).to_le_bytes();
@@ -465,7 +379,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 0..dim_count {
-                     dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                     dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                  for x in 0..=i as InternalTypes {
                      content.extend_from_slice(&x.to_be_bytes());       // Sample U16s
                  }
@@ -473,7 +387,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
              let mut content_buff = Vec::new();
 
              content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -501,7 +415,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 0..dim_count {
-                     dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                     dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                  for x in 0..=i as InternalTypes {
                      content.extend_from_slice(&x.to_be_bytes());       // Sample U16s
                  }
@@ -509,7 +423,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              let mut content_buff = Vec::new();
              content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1], TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -537,7 +451,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 0..dim_count {
-                     dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                     dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                  for x in 0..=i as InternalTypes {
                      content.extend_from_slice(&x.to_be_bytes());       // Sample U16s
                  }
@@ -545,7 +459,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
              let mut content_buff = Vec::new();
 
              content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1] | FIXED_POINT_FLAG, TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -575,7 +489,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 0..dim_count {
-                     dimensions.extend_from_slice(&(i+1).to_le_bytes());
+                     dimensions.extend_from_slice(&test_dimension(i, dim_count).to_le_bytes());
                  for x in 0..=i as InternalTypes {
                      content.extend_from_slice(&x.to_le_bytes());       // Sample U16s
                  }
@@ -583,7 +497,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
              let mut content_buff = Vec::new();
 
              content_buff.extend_from_slice(&[TYPE_INFO_RAW[0], TYPE_INFO_RAW[1] | FIXED_POINT_FLAG, TYPE_INFO_RAW[2], TYPE_INFO_RAW[3]]);
@@ -614,7 +528,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              // for i in 1u16..=dim_count {
-             //     dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+             //     dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
              //     for x in 0..(i-1) as InternalTypes {
              //         content.extend_from_slice(&x.to_be_bytes());
@@ -622,7 +536,7 @@ mod test {
              // }
 
              for i in 0..dim_count {
-                 dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                 dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
              for x in 0..i as InternalTypes {
                  content.extend_from_slice(&x.to_be_bytes());       // Sample U16s
              }
@@ -630,7 +544,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
              // Now wrap back
              let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
              prop_assert_eq!(parsed_back, Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError { layer: crate::error::Layer::VerboseValue, minimum_size: msg_buff.len() + size_of::<InternalTypes>() * dim_count as usize, actual_size: msg_buff.len() })));
@@ -650,7 +564,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 1u16..=dim_count {
-                 dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                 dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                  for x in 0..(i-1) as InternalTypes {
                      content.extend_from_slice(&x.to_le_bytes());
@@ -659,7 +573,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              // Now wrap back
              let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -679,7 +593,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 1u16..=dim_count {
-                 dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                 dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                  for x in 0..(i-1) as InternalTypes {
                      content.extend_from_slice(&x.to_be_bytes());
@@ -688,7 +602,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
 
              // Now wrap back
@@ -710,7 +624,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 1u16..=dim_count {
-                 dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                 dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                  for x in 0..(i-1) as InternalTypes {
                      content.extend_from_slice(&x.to_le_bytes());
@@ -719,7 +633,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              // Now wrap back
              let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -739,7 +653,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 1u16..=dim_count {
-                 dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                 dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                  for x in 0..(i-1) as InternalTypes {
                      content.extend_from_slice(&x.to_be_bytes());
@@ -748,7 +662,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              // Now wrap back
              let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -769,7 +683,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 1u16..=dim_count {
-                 dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                 dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                  for x in 0..(i-1) as InternalTypes {
                      content.extend_from_slice(&x.to_le_bytes());
@@ -778,7 +692,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              // Now wrap back
              let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -798,7 +712,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 1u16..=dim_count {
-                 dimensions.extend_from_slice(&(i as u16).to_be_bytes());
+                 dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_be_bytes());
 
                  for x in 0..(i-1) as InternalTypes {
                      content.extend_from_slice(&x.to_be_bytes());
@@ -807,7 +721,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              // Now wrap back
              let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -828,7 +742,7 @@ mod test {
              let mut content = Vec::with_capacity(dim_count as usize);
 
              for i in 1u16..=dim_count {
-                 dimensions.extend_from_slice(&(i as u16).to_le_bytes());
+                 dimensions.extend_from_slice(&test_dimension(i - 1, dim_count).to_le_bytes());
 
                  for x in 0..(i-1) as InternalTypes {
                      content.extend_from_slice(&x.to_le_bytes());
@@ -837,7 +751,7 @@ mod test {
 
              let arr_dim = ArrayDimensions { is_big_endian, dimensions: &dimensions };
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
-             arr.add_to_msg(&mut msg_buff, is_big_endian)?;
+             arr.add_to_msg(&mut msg_buff, is_big_endian).unwrap();
 
              // Now wrap back
              let parsed_back = VerboseValue::from_slice(&msg_buff, is_big_endian);
@@ -871,7 +785,7 @@ mod test {
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
              let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-             prop_assert_eq!(err, Err(CapacityError::new(())));
+             prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
              }
 
@@ -902,7 +816,7 @@ mod test {
              let arr = TestType { is_big_endian, variable_info, dimensions:arr_dim,data: &content, scaling };
              let err = arr.add_to_msg(&mut msg_buff, is_big_endian);
 
-             prop_assert_eq!(err, Err(CapacityError::new(())));
+             prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
              }
         }
@@ -934,7 +848,7 @@ mod test {
                 let mut content = Vec::with_capacity(dim_count as usize);
 
                 for i in 0..dim_count {
-                        dimensions.extend_from_slice(&(i+1).to_be_bytes());
+                        dimensions.extend_from_slice(&test_dimension(i, dim_count).to_be_bytes());
                     for x in 0..=i as InternalTypes {
                             content.extend_from_slice(&x.to_be_bytes());
                     }
@@ -1187,5 +1101,22 @@ mod test {
             let convert_content = "{\"variable_info\":null,\"scaling\":null,\"data\":[[[[[0,1,2,3,4],[5,6,7,8,9],[10,11,12,13,14],[15,16,17,18,19]],[[20,21,22,23,24],[25,26,27,28,29],[30,31,32,33,34],[35,36,37,38,39]],[[40,41,42,43,44],[45,46,47,48,49],[50,51,52,53,54],[55,56,57,58,59]]],[[[60,61,62,63,64],[65,66,67,68,69],[70,71,72,73,74],[75,76,77,78,79]],[[80,81,82,83,84],[85,86,87,88,89],[90,91,92,93,94],[95,96,97,98,99]],[[100,101,102,103,104],[105,106,107,108,109],[110,111,112,113,114],[115,116,117,118,119]]]]]}".to_string();
             assert_eq!(convert_content, serde_json::to_string(&arr).unwrap());
         }
+
+        // dimensions describing more data then present
+        {
+            let dimensions = [0, 2, 0, 3];
+            let content = [0u8; 5 * 2];
+            let arr = TestType {
+                variable_info: None,
+                dimensions: ArrayDimensions {
+                    is_big_endian: true,
+                    dimensions: &dimensions,
+                },
+                data: &content,
+                scaling: None,
+                is_big_endian: true,
+            };
+            assert!(serde_json::to_string(&arr).is_err());
+        }
     }
 }
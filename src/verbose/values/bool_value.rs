@@ -1,4 +1,7 @@
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
+
+use crate::error::VerboseEncodeError;
+use crate::verbose::FieldWriter;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
@@ -13,39 +16,17 @@ impl<'a> BoolValue<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
-        if self.name.is_some() {
-            let name = unsafe { self.name.unwrap_unchecked() };
-            let type_info: [u8; 4] = [0b0001_0001, 0b0000_1000, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-
-            let name_len = if is_big_endian {
-                (name.len() as u16 + 1).to_be_bytes()
-            } else {
-                (name.len() as u16 + 1).to_le_bytes()
-            };
-            buf.try_extend_from_slice(&[name_len[0], name_len[1]])?;
-            buf.try_extend_from_slice(name.as_bytes())?;
-            if buf.remaining_capacity() > 1 {
-                // Safe as capacity is checked earlier
-                unsafe { buf.push_unchecked(0) };
-                unsafe { buf.push_unchecked(u8::from(self.value)) }
-                Ok(())
-            } else {
-                Err(CapacityError::new(()))
-            }
-        } else {
-            let type_info: [u8; 4] = [0b0001_0001, 0b0000_0000, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-
-            if buf.remaining_capacity() > 0 {
-                // Safe as capacity is checked earlier
-                unsafe { buf.push_unchecked(u8::from(self.value)) }
-                Ok(())
-            } else {
-                Err(CapacityError::new(()))
-            }
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b0001_0001, 0b0000_0000, 0b0000_0000, 0b0000_0000],
+            self.name.is_some(),
+            false,
+        )?;
+        if let Some(name) = self.name {
+            writer.write_var_name(name, is_big_endian)?;
         }
+        writer.write_u8(u8::from(self.value))
     }
 }
 
@@ -55,8 +36,8 @@ mod test {
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::Bool;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
 
     proptest! {
         #[test]
@@ -163,10 +144,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, 7> = ArrayVec::new();
-                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 7> = ArrayVec::new();
-                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -176,10 +157,10 @@ mod test {
                 let is_big_endian = false;
 
                 let mut msg_buff: ArrayVec<u8, 7> = ArrayVec::new();
-                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -189,10 +170,10 @@ mod test {
 
                 let bool_val = BoolValue { name: None, value };
                 let is_big_endian = true;
-                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
             // Capacity error big endian without name
@@ -201,10 +182,10 @@ mod test {
 
                 let bool_val = BoolValue { name: None, value };
                 let is_big_endian = false;
-                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(bool_val.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -1,5 +1,6 @@
-use crate::verbose::VariableInfoUnit;
-use arrayvec::{ArrayVec, CapacityError};
+use crate::error::VerboseEncodeError;
+use crate::verbose::{FieldWriter, VariableInfoUnit};
+use arrayvec::ArrayVec;
 
 use super::RawF16;
 
@@ -17,39 +18,17 @@ impl<'a> F16Value<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b1000_0010, 0b0000_0000, 0b0000_0000, 0b0000_0000],
+            self.variable_info.is_some(),
+            false,
+        )?;
         if let Some(var_info) = &self.variable_info {
-            let type_info = [0b1000_0010, 0b0000_1000, 0b0000_0000, 0b0000_0000];
-            let name_len;
-            let unit_len;
-            if is_big_endian {
-                name_len = (var_info.name.len() as u16 + 1).to_be_bytes();
-                unit_len = (var_info.unit.len() as u16 + 1).to_be_bytes();
-            } else {
-                name_len = (var_info.name.len() as u16 + 1).to_le_bytes();
-                unit_len = (var_info.unit.len() as u16 + 1).to_le_bytes();
-            };
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-            buf.try_extend_from_slice(var_info.name.as_bytes())?;
-            if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                // Safe as capacity is checked earlier
-                unsafe { buf.push_unchecked(0) };
-                buf.try_extend_from_slice(var_info.unit.as_bytes())?;
-                unsafe { buf.push_unchecked(0) };
-            } else {
-                return Err(CapacityError::new(()));
-            }
-        } else {
-            let type_info = [0b1000_0010, 0b0000_0000, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-        }
-
-        if is_big_endian {
-            buf.try_extend_from_slice(&self.value.to_be_bytes())
-        } else {
-            buf.try_extend_from_slice(&self.value.to_le_bytes())
+            writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
         }
+        writer.write_f16(self.value, is_big_endian)
     }
 }
 
@@ -59,12 +38,12 @@ mod test {
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::F16;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
 
     proptest! {
         #[test]
-        fn write_read(value in any::<u16>(), ref name in "\\pc{0,20}", ref unit in "\\pc{0,20}") {
+        fn write_read(value in any::<u16>(), ref name in "\\pc{1,20}", ref unit in "\\pc{1,20}") {
             const MAX_SYMBOL_LENGTH_NAME: usize = 20;
             const MAX_SYMBOL_LENGTH_UNIT: usize = 20;
             const BYTES_NEEDED: usize = 6;
@@ -188,10 +167,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -204,10 +183,10 @@ mod test {
                 let is_big_endian = false;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -220,10 +199,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -236,10 +215,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f16_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
         }
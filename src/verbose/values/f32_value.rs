@@ -1,6 +1,7 @@
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 
-use crate::verbose::VariableInfoUnit;
+use crate::error::VerboseEncodeError;
+use crate::verbose::{FieldWriter, VariableInfoUnit};
 
 /// Verbose 32 bit float number.
 #[derive(Debug, PartialEq, Clone)]
@@ -16,39 +17,17 @@ impl<'a> F32Value<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b1000_0011, 0b0000_0000, 0b0000_0000, 0b0000_0000],
+            self.variable_info.is_some(),
+            false,
+        )?;
         if let Some(var_info) = &self.variable_info {
-            let type_info = [0b1000_0011, 0b0000_1000, 0b0000_0000, 0b0000_0000];
-            let name_len;
-            let unit_len;
-            if is_big_endian {
-                name_len = (var_info.name.len() as u16 + 1).to_be_bytes();
-                unit_len = (var_info.unit.len() as u16 + 1).to_be_bytes();
-            } else {
-                name_len = (var_info.name.len() as u16 + 1).to_le_bytes();
-                unit_len = (var_info.unit.len() as u16 + 1).to_le_bytes();
-            };
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-            buf.try_extend_from_slice(var_info.name.as_bytes())?;
-            if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                // Safe as capacity is checked earlier
-                unsafe { buf.push_unchecked(0) };
-                buf.try_extend_from_slice(var_info.unit.as_bytes())?;
-                unsafe { buf.push_unchecked(0) };
-            } else {
-                return Err(CapacityError::new(()));
-            }
-        } else {
-            let type_info = [0b1000_0011, 0b0000_0000, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-        }
-
-        if is_big_endian {
-            buf.try_extend_from_slice(&self.value.to_be_bytes())
-        } else {
-            buf.try_extend_from_slice(&self.value.to_le_bytes())
+            writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
         }
+        writer.write_f32(self.value, is_big_endian)
     }
 }
 
@@ -58,12 +37,12 @@ mod test {
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::F32;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
 
     proptest! {
         #[test]
-        fn write_read(value in any::<f32>(), ref name in "\\pc{0,20}", ref unit in "\\pc{0,20}") {
+        fn write_read(value in any::<f32>(), ref name in "\\pc{1,20}", ref unit in "\\pc{1,20}") {
             const MAX_SYMBOL_LENGTH_NAME: usize = 20;
             const MAX_SYMBOL_LENGTH_UNIT: usize = 20;
             const BYTES_NEEDED: usize = 8;
@@ -188,10 +167,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -204,10 +183,10 @@ mod test {
                 let is_big_endian = false;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -220,10 +199,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -236,13 +215,94 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(f32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
         }
     }
+
+    #[test]
+    fn write_read_special_values() {
+        // nan (quiet, signaling, negative), infinities & negative zero
+        let bit_values: [u32; 7] = [
+            0x7fc0_0000,
+            0xffc0_0000,
+            0x7f80_0001,
+            0x7fbf_ffff,
+            0x7f80_0000,
+            0xff80_0000,
+            0x8000_0000,
+        ];
+        for bits in bit_values {
+            for is_big_endian in [false, true] {
+                let value = f32::from_bits(bits);
+                let bytes = if is_big_endian {
+                    bits.to_be_bytes()
+                } else {
+                    bits.to_le_bytes()
+                };
+
+                // without name
+                {
+                    let mut buf = ArrayVec::<u8, 100>::new();
+                    F32Value {
+                        variable_info: None,
+                        value,
+                    }
+                    .add_to_msg(&mut buf, is_big_endian)
+                    .unwrap();
+                    assert_eq!(&buf[4..], &bytes[..]);
+
+                    let (parsed, rest) = VerboseValue::from_slice(&buf, is_big_endian).unwrap();
+                    assert!(rest.is_empty());
+                    match parsed {
+                        F32(v) => {
+                            assert_eq!(None, v.variable_info);
+                            assert_eq!(bits, v.value.to_bits());
+                        }
+                        other => panic!("unexpected value {:?}", other),
+                    }
+                }
+
+                // with name & unit
+                {
+                    let mut buf = ArrayVec::<u8, 100>::new();
+                    F32Value {
+                        variable_info: Some(VariableInfoUnit {
+                            name: "speed",
+                            unit: "km/h",
+                        }),
+                        value,
+                    }
+                    .add_to_msg(&mut buf, is_big_endian)
+                    .unwrap();
+                    assert_eq!(
+                        &buf[..4],
+                        &[0b1000_0011, 0b0000_1000, 0b0000_0000, 0b0000_0000]
+                    );
+                    assert_eq!(&buf[4 + 4 + 6 + 5..], &bytes[..]);
+
+                    let (parsed, rest) = VerboseValue::from_slice(&buf, is_big_endian).unwrap();
+                    assert!(rest.is_empty());
+                    match parsed {
+                        F32(v) => {
+                            assert_eq!(
+                                Some(VariableInfoUnit {
+                                    name: "speed",
+                                    unit: "km/h",
+                                }),
+                                v.variable_info
+                            );
+                            assert_eq!(bits, v.value.to_bits());
+                        }
+                        other => panic!("unexpected value {:?}", other),
+                    }
+                }
+            }
+        }
+    }
 }
@@ -1,6 +1,7 @@
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 
-use crate::verbose::{Scaling, VariableInfoUnit};
+use crate::error::VerboseEncodeError;
+use crate::verbose::{FieldWriter, Scaling, VariableInfoUnit};
 
 /// Verbose 32 bit signed integer.
 #[derive(Debug, PartialEq, Clone)]
@@ -17,87 +18,18 @@ impl<'a> I32Value<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b0010_0011, 0b0000_0000, 0b0000_0000, 0b0000_0000],
+            self.variable_info.is_some(),
+            self.scaling.is_some(),
+        )?;
         if let Some(var_info) = &self.variable_info {
-            let name_len;
-            let unit_len;
-            if is_big_endian {
-                name_len = (var_info.name.len() as u16 + 1).to_be_bytes();
-                unit_len = (var_info.unit.len() as u16 + 1).to_be_bytes();
-            } else {
-                name_len = (var_info.name.len() as u16 + 1).to_le_bytes();
-                unit_len = (var_info.unit.len() as u16 + 1).to_le_bytes();
-            };
-
-            if let Some(scaler) = &self.scaling {
-                let type_info = [0b0010_0011, 0b0001_1000, 0b0000_0000, 0b0000_0000];
-                let quantization;
-                let offset: [u8; 4];
-
-                if is_big_endian {
-                    quantization = scaler.quantization.to_be_bytes();
-                    offset = scaler.offset.to_be_bytes();
-                } else {
-                    quantization = scaler.quantization.to_le_bytes();
-                    offset = scaler.offset.to_le_bytes();
-                }
-                buf.try_extend_from_slice(&type_info)?;
-                buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-                buf.try_extend_from_slice(var_info.name.as_bytes())?;
-                if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                    // Safe as capacity is checked earlier
-                    unsafe { buf.push_unchecked(0) };
-                    let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                    unsafe { buf.push_unchecked(0) };
-                } else {
-                    return Err(CapacityError::new(()));
-                }
-
-                buf.try_extend_from_slice(&quantization)?;
-                buf.try_extend_from_slice(&offset)?;
-            } else {
-                let type_info = [0b0010_0011, 0b0000_1000, 0b0000_0000, 0b0000_0000];
-
-                buf.try_extend_from_slice(&type_info)?;
-                buf.try_extend_from_slice(&[name_len[0], name_len[1], unit_len[0], unit_len[1]])?;
-                buf.try_extend_from_slice(var_info.name.as_bytes())?;
-                if buf.remaining_capacity() > var_info.unit.len() + 2 {
-                    // Safe as capacity is checked earlier
-                    unsafe { buf.push_unchecked(0) };
-                    let _ = buf.try_extend_from_slice(var_info.unit.as_bytes());
-                    unsafe { buf.push_unchecked(0) };
-                } else {
-                    return Err(CapacityError::new(()));
-                }
-            }
-        }
-        // No name & unit
-        else if let Some(scaler) = &self.scaling {
-            let type_info: [u8; 4] = [0b0010_0011, 0b0001_0000, 0b0000_0000, 0b0000_0000];
-
-            let quantization;
-            let offset: [u8; 4];
-            if is_big_endian {
-                quantization = scaler.quantization.to_be_bytes();
-                offset = scaler.offset.to_be_bytes();
-            } else {
-                quantization = scaler.quantization.to_le_bytes();
-                offset = scaler.offset.to_le_bytes();
-            }
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&quantization)?;
-            buf.try_extend_from_slice(&offset)?;
-        } else {
-            let type_info: [u8; 4] = [0b0010_0011, 0b0000_0000, 0b0000_0000, 0b0000_0000];
-            buf.try_extend_from_slice(&type_info)?;
-        }
-
-        // value
-        if is_big_endian {
-            buf.try_extend_from_slice(&self.value.to_be_bytes())
-        } else {
-            buf.try_extend_from_slice(&self.value.to_le_bytes())
+            writer.write_var_name_and_unit(var_info.name, var_info.unit, is_big_endian)?;
         }
+        writer.write_i32_scaling(&self.scaling, is_big_endian)?;
+        writer.write_i32(self.value, is_big_endian)
     }
 }
 
@@ -107,12 +39,13 @@ mod test {
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::I32;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
     use std::format;
 
     proptest! {
         #[test]
-        fn write_read(value in any::<i32>(), ref name in "\\pc{0,20}", ref unit in "\\pc{0,20}", quantization in any::<f32>(), offset in any::<i32>()) {
+        fn write_read(value in any::<i32>(), ref name in "\\pc{1,20}", ref unit in "\\pc{1,20}", quantization in any::<f32>(), offset in any::<i32>()) {
             const MAX_SYMBOL_LENGTH_NAME: usize = 20;
             const MAX_SYMBOL_LENGTH_UNIT: usize = 20;
             const FIXED_POINT_LENGTH: usize = 8;
@@ -405,10 +338,10 @@ mod test {
             let is_big_endian = true;
 
             let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
         }
 
@@ -422,10 +355,10 @@ mod test {
             let is_big_endian = false;
 
             let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
         }
 
@@ -439,10 +372,10 @@ mod test {
             let is_big_endian = true;
 
             let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
         }
 
@@ -456,10 +389,10 @@ mod test {
             let is_big_endian = false;
 
             let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
         }
 
@@ -473,10 +406,10 @@ mod test {
             let is_big_endian = true;
 
             let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
         }
 
@@ -490,10 +423,10 @@ mod test {
             let is_big_endian = false;
 
             let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
         }
 
@@ -507,10 +440,10 @@ mod test {
             let is_big_endian = true;
 
             let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
         }
 
@@ -524,10 +457,10 @@ mod test {
             let is_big_endian = false;
 
             let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+            prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
         }
 
@@ -541,10 +474,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -558,10 +491,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -575,10 +508,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -592,10 +525,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -610,10 +543,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -627,10 +560,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(i32_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -693,4 +626,34 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn dlt_daemon_reference() {
+        // message as captured from a dlt-daemon for
+        // `DLT_LOG(ctx, DLT_LOG_INFO, DLT_INT32(-5))` on a
+        // little endian host
+        let captured = [
+            0x3d, 0x00, 0x00, 0x22, // header type, counter & length
+            b'E', b'C', b'U', b'1', // ecu id
+            0x00, 0x00, 0x04, 0xd2, // session id
+            0x00, 0x01, 0xe2, 0x40, // timestamp
+            0x41, 0x01, b'L', b'O', b'G', 0x00, b'T', b'E', b'S', b'T', // extended header
+            0x23, 0x00, 0x00, 0x00, // type info (SINT, TYLE 32 bit)
+            0xfb, 0xff, 0xff, 0xff, // value
+        ];
+        let packet = crate::DltPacketSlice::from_slice(&captured).unwrap();
+
+        let value = I32Value {
+            variable_info: None,
+            scaling: None,
+            value: -5,
+        };
+        let mut buf = ArrayVec::<u8, 8>::new();
+        value.add_to_msg(&mut buf, false).unwrap();
+        assert_eq!(packet.payload(), &buf[..]);
+
+        let mut iter = packet.verbose_value_iter().unwrap();
+        assert_eq!(Some(Ok(I32(value))), iter.next());
+        assert_eq!(None, iter.next());
+    }
 }
@@ -94,6 +94,9 @@ pub use array_i64::*;
 mod array_i128;
 pub use array_i128::*;
 
+mod array_encoder;
+pub use array_encoder::*;
+
 #[cfg(feature = "serde")]
 mod array_iteratable;
 #[cfg(feature = "serde")]
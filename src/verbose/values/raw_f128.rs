@@ -2,6 +2,16 @@
 ///
 /// This is needed as Rust does not support (and most systems)
 /// don't support 128 bit floating point values.
+///
+/// The value is stored as the IEEE 754 binary128 bit pattern (sign bit
+/// in the most significant bit), independent of the endianness of the
+/// message it was decoded from. [`RawF128::to_be_bytes`] returns the
+/// bytes in the order they appear in a big endian message and
+/// [`RawF128::to_le_bytes`] in the order they appear in a little endian
+/// message, so values can be re-encoded losslessly.
+///
+/// With the `f128_approx` feature enabled the value can be converted
+/// to an approximate `f64` via `RawF128::to_f64_approx`.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct RawF128(u128);
 
@@ -59,6 +69,51 @@ impl RawF128 {
     pub const fn to_bits(self) -> u128 {
         self.0
     }
+
+    /// Converts the value to the nearest smaller (in magnitude) `f64`.
+    ///
+    /// The mantissa is truncated from 112 to 52 bits. Values too large
+    /// for a `f64` are converted to infinity and values too small to
+    /// zero. Infinity & NaN are preserved (NaN payloads are not).
+    #[cfg(feature = "f128_approx")]
+    pub fn to_f64_approx(self) -> f64 {
+        const MANTISSA_MASK: u128 = (1 << 112) - 1;
+
+        let sign = ((self.0 >> 127) as u64) << 63;
+        let exponent = ((self.0 >> 112) & 0x7fff) as i32;
+        let mantissa = self.0 & MANTISSA_MASK;
+
+        let bits = if exponent == 0x7fff {
+            if mantissa == 0 {
+                // infinity
+                sign | 0x7ff0_0000_0000_0000
+            } else {
+                // nan
+                sign | 0x7ff8_0000_0000_0000
+            }
+        } else if exponent == 0 {
+            // zero & subnormals (always below the smallest f64)
+            sign
+        } else {
+            let unbiased = exponent - 16383;
+            if unbiased > 1023 {
+                // too big, convert to infinity
+                sign | 0x7ff0_0000_0000_0000
+            } else if unbiased >= -1022 {
+                // normal f64
+                sign | (((unbiased + 1023) as u64) << 52) | ((mantissa >> 60) as u64)
+            } else {
+                // subnormal f64 (including the implicit leading bit)
+                let shift = 60 + (-1022 - unbiased) as u32;
+                if shift >= 128 {
+                    sign
+                } else {
+                    sign | (((1 << 112) | mantissa) >> shift) as u64
+                }
+            }
+        };
+        f64::from_bits(bits)
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -169,6 +224,65 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "f128_approx")]
+    #[test]
+    fn to_f64_approx() {
+        // 1.0
+        assert_eq!(1.0, RawF128(0x3fff << 112).to_f64_approx());
+        // -2.5
+        assert_eq!(-2.5, RawF128(0xc000 << 112 | 0x4 << 108).to_f64_approx());
+        // zeros & f128 subnormals
+        assert_eq!(0.0f64.to_bits(), RawF128(0).to_f64_approx().to_bits());
+        assert_eq!(
+            (-0.0f64).to_bits(),
+            RawF128(1 << 127).to_f64_approx().to_bits()
+        );
+        assert_eq!(0.0, RawF128(1).to_f64_approx());
+        // infinity & nan
+        assert_eq!(f64::INFINITY, RawF128(0x7fff << 112).to_f64_approx());
+        assert_eq!(f64::NEG_INFINITY, RawF128(0xffff << 112).to_f64_approx());
+        assert!(RawF128(0x7fff << 112 | 1).to_f64_approx().is_nan());
+        // too big
+        assert_eq!(
+            f64::INFINITY,
+            RawF128((16383 + 1024) << 112).to_f64_approx()
+        );
+        assert_eq!(
+            f64::MAX,
+            RawF128(((16383 + 1023) << 112) | ((1 << 112) - 1)).to_f64_approx()
+        );
+        // f64 subnormals
+        assert_eq!(
+            f64::from_bits(1 << 51),
+            RawF128((16383 - 1023) << 112).to_f64_approx()
+        );
+        assert_eq!(
+            f64::from_bits(1),
+            RawF128((16383 - 1074) << 112).to_f64_approx()
+        );
+        // too small
+        assert_eq!(0.0, RawF128((16383 - 1075) << 112).to_f64_approx());
+        assert_eq!(0.0, RawF128(1 << 112).to_f64_approx());
+    }
+
+    #[cfg(feature = "f128_approx")]
+    proptest! {
+        #[test]
+        fn to_f64_approx_exact(value in any::<f64>().prop_filter("normal", |v| v.is_normal())) {
+            // f64 values that can be represented exactly
+            let bits = value.to_bits();
+            let sign = u128::from(bits >> 63) << 127;
+            let exponent = u128::from((bits >> 52) & 0x7ff) + 16383 - 1023;
+            let mantissa = u128::from(bits & ((1 << 52) - 1)) << 60;
+            let raw = RawF128(sign | (exponent << 112) | mantissa);
+            prop_assert_eq!(value, raw.to_f64_approx());
+
+            // lower mantissa bits get truncated
+            let raw = RawF128(raw.0 | ((1 << 60) - 1));
+            prop_assert_eq!(value, raw.to_f64_approx());
+        }
+    }
+
     #[cfg(feature = "serde")]
     proptest! {
         #[test]
@@ -36,7 +36,10 @@ impl RawF16 {
             // max has to be handled specially (as it represents infinity or NaN)
             0b0111_1111_1000_0000_0000_0000_0000_0000
         } else if masked_expo == 0 {
-            0
+            // zero & subnormal numbers (fraction * 2^-24), these can be
+            // represented exactly as normal f32 numbers
+            let abs = f32::from(raw_u16 & RawF16::FRAC_MASK) * (1.0 / 16_777_216.0);
+            return f32::from_bits(sign | abs.to_bits());
         } else {
             // to get the to the exponent substract 0b01111
             let decoded_expo = i32::from((raw_u16 & RawF16::EXPO_MASK) >> 10) - 0b01111;
@@ -112,6 +115,18 @@ impl From<RawF16> for f32 {
     }
 }
 
+/// Decodes a 16 bit IEEE 754 half precision floating point value (FLOA
+/// with TYLE=1) and converts it to a f32 (including subnormals, infinity
+/// and NaN).
+#[inline]
+pub fn half_to_f32(bytes: [u8; 2], is_big_endian: bool) -> f32 {
+    if is_big_endian {
+        RawF16::from_be_bytes(bytes).to_f32()
+    } else {
+        RawF16::from_le_bytes(bytes).to_f32()
+    }
+}
+
 #[cfg(feature = "serde")]
 impl serde::Serialize for RawF16 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -123,6 +138,7 @@ impl serde::Serialize for RawF16 {
 }
 
 #[cfg(test)]
+#[allow(clippy::unusual_byte_groupings)]
 mod tests {
     use super::*;
     use proptest::prelude::*;
@@ -168,6 +184,80 @@ mod tests {
         // largest normal number
         assert_eq!(65504.0, RawF16::from_bits(0b0111_1011_1111_1111).to_f32());
         assert_eq!(-65504.0, RawF16::from_bits(0b1111_1011_1111_1111).to_f32());
+
+        // negative zero
+        let neg_zero = RawF16::from_bits(0b1000_0000_0000_0000).to_f32();
+        assert_eq!(0.0, neg_zero);
+        assert!(neg_zero.is_sign_negative());
+
+        // smallest & largest subnormal numbers
+        assert_eq!(
+            5.960_464_5e-8,
+            RawF16::from_bits(0b0000_0000_0000_0001).to_f32()
+        );
+        assert_eq!(
+            -5.960_464_5e-8,
+            RawF16::from_bits(0b1000_0000_0000_0001).to_f32()
+        );
+        assert_eq!(
+            6.097_555e-5,
+            RawF16::from_bits(0b0000_0011_1111_1111).to_f32()
+        );
+
+        // smallest normal number
+        assert_eq!(
+            6.103_515_6e-5,
+            RawF16::from_bits(0b0000_0100_0000_0000).to_f32()
+        );
+    }
+
+    #[test]
+    fn half_to_f32_vectors() {
+        // (bits, expected value)
+        let vectors: [(u16, f32); 9] = [
+            (0x0000, 0.0),
+            (0x0001, 5.960_464_5e-8),
+            (0x03ff, 6.097_555e-5),
+            (0x0400, 6.103_515_6e-5),
+            (0x3555, 0.333_251_95),
+            (0x3c00, 1.0),
+            (0xc000, -2.0),
+            (0x7bff, 65504.0),
+            (0x7c00, f32::INFINITY),
+        ];
+        for (bits, expected) in vectors {
+            assert_eq!(expected, half_to_f32(bits.to_be_bytes(), true));
+            assert_eq!(expected, half_to_f32(bits.to_le_bytes(), false));
+        }
+        assert_eq!(
+            f32::NEG_INFINITY,
+            half_to_f32(0xfc00u16.to_be_bytes(), true)
+        );
+        assert!(half_to_f32(0x7e00u16.to_be_bytes(), true).is_nan());
+        assert!(half_to_f32(0xfc01u16.to_le_bytes(), false).is_nan());
+    }
+
+    proptest! {
+        #[test]
+        fn to_f32_finite_values(value in any::<u16>()) {
+            // reference: value = (-1)^sign * 2^(expo - 15) * (1 + frac/1024)
+            //            or for subnormals (-1)^sign * 2^-14 * (frac/1024)
+            let v = RawF16::from_bits(value);
+            let expo = i32::from((value >> 10) & 0x1f);
+            let frac = f64::from(value & 0x3ff);
+            let sign = if 0 != value & 0x8000 { -1.0 } else { 1.0 };
+            // exact power of two (powi does not guarantee exact results,
+            // e.g. Miri adds errors to it)
+            let pow2 = |e: i32| f64::from_bits(((1023 + e) as u64) << 52);
+            if expo != 0x1f {
+                let expected = if expo == 0 {
+                    sign * (frac / 1024.0) * pow2(-14)
+                } else {
+                    sign * (1.0 + frac / 1024.0) * pow2(expo - 15)
+                };
+                prop_assert_eq!(expected, f64::from(v.to_f32()));
+            }
+        }
     }
 
     proptest! {
@@ -1,4 +1,7 @@
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
+
+use crate::error::VerboseEncodeError;
+use crate::verbose::FieldWriter;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
@@ -13,43 +16,28 @@ impl<'a> RawValue<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        if self.data.len() > usize::from(u16::MAX) {
+            return Err(VerboseEncodeError::RawDataTooLong(self.data.len()));
+        }
         if let Some(name) = self.name {
-            let type_info = [0b0000_0000, 0b0000_1100, 0b0000_0000, 0b0000_0000];
-            let (data_len, name_len) = if is_big_endian {
-                (
-                    (self.data.len() as u16).to_be_bytes(),
-                    (name.len() as u16 + 1).to_be_bytes(),
-                )
-            } else {
-                (
-                    (self.data.len() as u16).to_le_bytes(),
-                    (name.len() as u16 + 1).to_le_bytes(),
-                )
-            };
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&[data_len[0], data_len[1], name_len[0], name_len[1]])?;
-            buf.try_extend_from_slice(name.as_bytes())?;
-            if buf.remaining_capacity() > 0 {
-                // Safe as capacity is checked earlier
-                unsafe { buf.push_unchecked(0) };
-            } else {
-                return Err(CapacityError::new(()));
+            // name length includes the terminating zero
+            if name.len() >= usize::from(u16::MAX) {
+                return Err(VerboseEncodeError::NameTooLong(name.len()));
             }
-        } else {
-            let type_info = [0b0000_0000, 0b0000_0100, 0b0000_0000, 0b0000_0000];
-            let data_len = if is_big_endian {
-                (self.data.len() as u16).to_be_bytes()
-            } else {
-                (self.data.len() as u16).to_le_bytes()
-            };
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&[data_len[0], data_len[1]])?;
         }
 
-        buf.try_extend_from_slice(self.data)?;
-
-        Ok(())
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b0000_0000, 0b0000_0100, 0b0000_0000, 0b0000_0000],
+            self.name.is_some(),
+            false,
+        )?;
+        writer.write_u16(self.data.len() as u16, is_big_endian)?;
+        if let Some(name) = self.name {
+            writer.write_var_name(name, is_big_endian)?;
+        }
+        writer.write_raw(self.data)
     }
 }
 
@@ -59,12 +47,12 @@ mod test {
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::Raw;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
 
     proptest! {
         #[test]
-        fn write_read(ref data in "\\pc{0,80}", ref name in "\\pc{0,20}") {
+        fn write_read(ref data in "\\pc{0,80}", ref name in "\\pc{1,20}") {
             const MAX_SYMBOL_LENGTH_NAME: usize = 20;
             const MAX_SYMBOL_LENGTH_VALUE: usize = 80;
             const BYTES_NEEDED: usize = 6;
@@ -179,10 +167,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -194,10 +182,10 @@ mod test {
                 let is_big_endian = false;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -209,10 +197,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -224,14 +212,88 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(raw_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
+
+            }
+
 
+        }
+    }
+
+    #[test]
+    fn write_read_blobs() {
+        let blob: Vec<u8> = (0..1024u32).map(|v| v as u8).collect();
+        for data in [&[][..], &blob[..]] {
+            for name in [None, Some(""), Some("blob")] {
+                for is_big_endian in [false, true] {
+                    let raw_value = RawValue { name, data };
+                    let mut buf = ArrayVec::<u8, 2048>::new();
+                    raw_value.add_to_msg(&mut buf, is_big_endian).unwrap();
+
+                    // empty names are encoded without a terminating zero
+                    let header_len = 4
+                        + 2
+                        + name
+                            .map(|n| 2 + crate::verbose::var_str_len(n))
+                            .unwrap_or(0);
+                    assert_eq!(header_len + data.len(), buf.len());
+                    assert_eq!(&buf[header_len..], data);
+                    assert_eq!(
+                        VerboseValue::from_slice(&buf, is_big_endian),
+                        Ok((Raw(raw_value), &[] as &[u8]))
+                    );
+                }
             }
+        }
+    }
+
+    #[test]
+    fn too_long() {
+        let data = [0u8; 0x1_0000];
+
+        // data too long
+        {
+            let mut buf = ArrayVec::<u8, 100>::new();
+            assert_eq!(
+                Err(VerboseEncodeError::RawDataTooLong(data.len())),
+                RawValue {
+                    name: None,
+                    data: &data
+                }
+                .add_to_msg(&mut buf, false)
+            );
+            assert!(buf.is_empty());
+        }
 
+        // name too long
+        {
+            let name = "a".repeat(usize::from(u16::MAX));
+            let mut buf = ArrayVec::<u8, 100>::new();
+            assert_eq!(
+                Err(VerboseEncodeError::NameTooLong(name.len())),
+                RawValue {
+                    name: Some(&name),
+                    data: &[]
+                }
+                .add_to_msg(&mut buf, true)
+            );
+            assert!(buf.is_empty());
+        }
 
+        // data exceeding the remaining capacity
+        {
+            let mut buf = ArrayVec::<u8, 100>::new();
+            assert_eq!(
+                Err(VerboseEncodeError::Capacity(CapacityError::new(()))),
+                RawValue {
+                    name: None,
+                    data: &data[..95]
+                }
+                .add_to_msg(&mut buf, true)
+            );
         }
     }
 }
@@ -1,4 +1,7 @@
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
+
+use crate::error::VerboseEncodeError;
+use crate::verbose::{FieldWriter, StringCoding};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
@@ -8,69 +11,72 @@ pub struct StringValue<'a> {
 }
 
 impl<'a> StringValue<'a> {
-    /// Adds the verbose value to the given dlt mesage buffer.
+    /// Adds the verbose value to the given dlt mesage buffer (encoded as UTF-8).
     pub fn add_to_msg<const CAP: usize>(
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        self.add_to_msg_with_coding(buf, is_big_endian, StringCoding::Utf8)
+    }
+
+    /// Adds the verbose value to the given dlt mesage buffer using the
+    /// given string coding.
+    ///
+    /// If [`StringCoding::Ascii`] is requested the value is verified to
+    /// only contain ASCII characters.
+    pub fn add_to_msg_with_coding<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+        coding: StringCoding,
+    ) -> Result<(), VerboseEncodeError> {
+        if StringCoding::Ascii == coding && false == self.value.is_ascii() {
+            return Err(VerboseEncodeError::StringNotAscii);
+        }
+        // lengths include the terminating zero
+        if self.value.len() >= usize::from(u16::MAX) {
+            return Err(VerboseEncodeError::StringTooLong(self.value.len()));
+        }
         if let Some(name) = self.name {
-            let type_info = [0b0000_0000, 0b0000_1010, 0b0000_0000, 0b0000_0000];
-            let (value_len, name_len) = if is_big_endian {
-                (
-                    (self.value.len() as u16 + 1).to_be_bytes(),
-                    (name.len() as u16 + 1).to_be_bytes(),
-                )
-            } else {
-                (
-                    (self.value.len() as u16 + 1).to_le_bytes(),
-                    (name.len() as u16 + 1).to_le_bytes(),
-                )
-            };
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&[value_len[0], value_len[1], name_len[0], name_len[1]])?;
-            buf.try_extend_from_slice(name.as_bytes())?;
-            if buf.remaining_capacity() > 0 {
-                // Safe as capacity is checked earlier
-                unsafe { buf.push_unchecked(0) };
-            } else {
-                return Err(CapacityError::new(()));
+            if name.len() >= usize::from(u16::MAX) {
+                return Err(VerboseEncodeError::NameTooLong(name.len()));
             }
-        } else {
-            let type_info = [0b0000_0000, 0b0000_0010, 0b0000_0000, 0b0000_0000];
-            let value_len = if is_big_endian {
-                (self.value.len() as u16 + 1).to_be_bytes()
-            } else {
-                (self.value.len() as u16 + 1).to_le_bytes()
-            };
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&[value_len[0], value_len[1]])?;
         }
 
-        buf.try_extend_from_slice(self.value.as_bytes())?;
-        if buf.remaining_capacity() > 0 {
-            // Safe as capacity is checked earlier
-            unsafe { buf.push_unchecked(0) };
-        } else {
-            return Err(CapacityError::new(()));
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [
+                0b0000_0000,
+                0b0000_0010 | coding.type_info_flag_1(),
+                0b0000_0000,
+                0b0000_0000,
+            ],
+            self.name.is_some(),
+            false,
+        )?;
+        writer.write_u16(self.value.len() as u16 + 1, is_big_endian)?;
+        if let Some(name) = self.name {
+            writer.write_var_name(name, is_big_endian)?;
         }
-
-        Ok(())
+        writer.write_raw(self.value.as_bytes())?;
+        writer.write_u8(0)
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrow)]
 mod test {
     use super::*;
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::Str;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
 
     proptest! {
         #[test]
-        fn write_read(ref value in "\\pc{0,80}", ref name in "\\pc{0,20}") {
+        fn write_read(ref value in "\\pc{0,80}", ref name in "\\pc{1,20}") {
             const MAX_SYMBOL_LENGTH_NAME: usize = 20;
             const MAX_SYMBOL_LENGTH_VALUE: usize = 80;
             const BYTES_NEEDED: usize = 7;
@@ -93,7 +99,7 @@ mod test {
 
                 prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Ok(()));
 
-                content_buff.extend_from_slice(&[0b0000_0000, 0b0000_1010, 0b0000_0000, 0b0000_0000, len_value_be[0], len_value_be[1], len_name_be[0], len_name_be[1]]);
+                content_buff.extend_from_slice(&[0b0000_0000, 0b1000_1010, 0b0000_0000, 0b0000_0000, len_value_be[0], len_value_be[1], len_name_be[0], len_name_be[1]]);
                 content_buff.extend_from_slice(name.as_bytes());
                 content_buff.push(0);
                 content_buff.extend_from_slice(&value.as_bytes());
@@ -120,7 +126,7 @@ mod test {
 
                 prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Ok(()));
 
-                content_buff.extend_from_slice(&[0b0000_0000, 0b0000_1010, 0b0000_0000, 0b0000_0000, len_value_le[0], len_value_le[1], len_name_le[0], len_name_le[1]]);
+                content_buff.extend_from_slice(&[0b0000_0000, 0b1000_1010, 0b0000_0000, 0b0000_0000, len_value_le[0], len_value_le[1], len_name_le[0], len_name_le[1]]);
                 content_buff.extend_from_slice(name.as_bytes());
                 content_buff.push(0);
                 content_buff.extend_from_slice(&value.as_bytes());
@@ -146,7 +152,7 @@ mod test {
 
                 prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Ok(()));
 
-                content_buff.extend_from_slice(&[0b0000_0000, 0b0000_0010, 0b0000_0000, 0b0000_0000, len_value_be[0], len_value_be[1]]);
+                content_buff.extend_from_slice(&[0b0000_0000, 0b1000_0010, 0b0000_0000, 0b0000_0000, len_value_be[0], len_value_be[1]]);
                 content_buff.extend_from_slice(&value.as_bytes());
                 content_buff.push(0);
                 prop_assert_eq!(&msg_buff[..slice_len], &content_buff[..]);
@@ -170,7 +176,7 @@ mod test {
 
                 prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Ok(()));
 
-                content_buff.extend_from_slice(&[0b0000_0000, 0b0000_0010, 0b0000_0000, 0b0000_0000, len_value_le[0], len_value_le[1]]);
+                content_buff.extend_from_slice(&[0b0000_0000, 0b1000_0010, 0b0000_0000, 0b0000_0000, len_value_le[0], len_value_le[1]]);
                 content_buff.extend_from_slice(&value.as_bytes());
                 content_buff.push(0);
                 prop_assert_eq!(&msg_buff[..slice_len], &content_buff[..]);
@@ -189,10 +195,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -204,10 +210,10 @@ mod test {
                 let is_big_endian = false;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -219,10 +225,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -234,14 +240,113 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(string_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
 
         }
     }
+
+    #[test]
+    fn write_read_coding() {
+        let values = ["", "abc", "\u{00e4}\u{00f6}\u{00fc} \u{1F980}"];
+        for value in values {
+            for name in [None, Some(""), Some("n\u{00e4}me")] {
+                for is_big_endian in [false, true] {
+                    let string_value = StringValue { name, value };
+
+                    // utf8
+                    {
+                        let mut buf = ArrayVec::<u8, 100>::new();
+                        string_value
+                            .add_to_msg_with_coding(&mut buf, is_big_endian, StringCoding::Utf8)
+                            .unwrap();
+                        assert_eq!(0b1000_0000, buf[1] & 0b1000_0000);
+                        assert_eq!(0, buf[2]);
+                        assert_eq!(
+                            VerboseValue::from_slice(&buf, is_big_endian),
+                            Ok((Str(string_value.clone()), &[] as &[u8]))
+                        );
+
+                        let mut default_buf = ArrayVec::<u8, 100>::new();
+                        string_value
+                            .add_to_msg(&mut default_buf, is_big_endian)
+                            .unwrap();
+                        assert_eq!(buf, default_buf);
+                    }
+
+                    // ascii
+                    {
+                        let mut buf = ArrayVec::<u8, 100>::new();
+                        let result = string_value.add_to_msg_with_coding(
+                            &mut buf,
+                            is_big_endian,
+                            StringCoding::Ascii,
+                        );
+                        if value.is_ascii() {
+                            assert_eq!(Ok(()), result);
+                            assert_eq!(0, buf[1] & 0b1000_0000);
+                            assert_eq!(
+                                VerboseValue::from_slice(&buf, is_big_endian),
+                                Ok((Str(string_value.clone()), &[] as &[u8]))
+                            );
+                        } else {
+                            assert_eq!(Err(VerboseEncodeError::StringNotAscii), result);
+                            assert!(buf.is_empty());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn too_long() {
+        let max = "a".repeat(usize::from(u16::MAX) - 1);
+        let too_long = "a".repeat(usize::from(u16::MAX));
+
+        // value too long
+        {
+            let mut buf = ArrayVec::<u8, { 0x1_0000 + 100 }>::new();
+            assert_eq!(
+                Err(VerboseEncodeError::StringTooLong(too_long.len())),
+                StringValue {
+                    name: None,
+                    value: &too_long
+                }
+                .add_to_msg(&mut buf, false)
+            );
+            assert!(buf.is_empty());
+
+            // maximum length is still ok
+            let string_value = StringValue {
+                name: None,
+                value: &max,
+            };
+            string_value.add_to_msg(&mut buf, true).unwrap();
+            assert_eq!(&buf[4..6], &u16::MAX.to_be_bytes());
+            assert_eq!(
+                VerboseValue::from_slice(&buf, true),
+                Ok((Str(string_value), &[] as &[u8]))
+            );
+        }
+
+        // name too long
+        {
+            let mut buf = ArrayVec::<u8, 100>::new();
+            assert_eq!(
+                Err(VerboseEncodeError::NameTooLong(too_long.len())),
+                StringValue {
+                    name: Some(&too_long),
+                    value: ""
+                }
+                .add_to_msg(&mut buf, false)
+            );
+            assert!(buf.is_empty());
+        }
+    }
 }
@@ -1,6 +1,7 @@
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 
-use crate::verbose::VerboseIter;
+use crate::error::VerboseEncodeError;
+use crate::verbose::{FieldWriter, VerboseIter, VerboseValue, MAX_STRUCT_DEPTH};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct StructValue<'a> {
@@ -16,44 +17,18 @@ impl<'a> StructValue<'a> {
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
+    ) -> Result<(), VerboseEncodeError> {
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [0b0000_0000, 0b0100_0000, 0b0000_0000, 0b0000_0000],
+            self.name.is_some(),
+            false,
+        )?;
+        writer.write_u16(self.number_of_entries, is_big_endian)?;
         if let Some(name) = self.name {
-            let type_info = [0b0000_0000, 0b0100_1000, 0b0000_0000, 0b0000_0000];
-            let (number_of_entries, name_len) = if is_big_endian {
-                (
-                    self.number_of_entries.to_be_bytes(),
-                    (name.len() as u16 + 1).to_be_bytes(),
-                )
-            } else {
-                (
-                    self.number_of_entries.to_le_bytes(),
-                    (name.len() as u16 + 1).to_le_bytes(),
-                )
-            };
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&number_of_entries)?;
-            buf.try_extend_from_slice(&[name_len[0], name_len[1]])?;
-            buf.try_extend_from_slice(name.as_bytes())?;
-            if buf.remaining_capacity() > 0 {
-                // Safe as capacity is checked earlier
-                unsafe { buf.push_unchecked(0) };
-            } else {
-                return Err(CapacityError::new(()));
-            }
-        } else {
-            let type_info = [0b0000_0000, 0b0100_0000, 0b0000_0000, 0b0000_0000];
-            let number_of_entries = if is_big_endian {
-                self.number_of_entries.to_be_bytes()
-            } else {
-                self.number_of_entries.to_le_bytes()
-            };
-            buf.try_extend_from_slice(&type_info)?;
-            buf.try_extend_from_slice(&[number_of_entries[0], number_of_entries[1]])?;
+            writer.write_var_name(name, is_big_endian)?;
         }
-
-        buf.try_extend_from_slice(self.entries_data)?;
-
-        Ok(())
+        writer.write_raw(self.entries_data)
     }
 
     /// Returns an iterator over the entries/fields of the struct
@@ -73,6 +48,157 @@ impl<'a> StructValue<'a> {
     }
 }
 
+/// Adds a struct verbose value to the given dlt message buffer.
+///
+/// The entries of the struct are added via the `add_entries` closure
+/// (nested structs can be added via [`StructWriter::add_struct`]). The number
+/// of entries is counted while writing & patched into the struct header
+/// afterwards. If an error occurs the buffer is reset to the length it had
+/// before the call.
+///
+/// # Example
+///
+/// ```
+/// use arrayvec::ArrayVec;
+/// use dlt_parse::verbose::{add_struct_to_msg, I32Value, StringValue, VerboseValue};
+///
+/// let mut buf = ArrayVec::<u8, 64>::new();
+/// add_struct_to_msg(&mut buf, true, Some("point"), |s| {
+///     s.add_value(&VerboseValue::I32(I32Value {
+///         variable_info: None,
+///         scaling: None,
+///         value: 1,
+///     }))?;
+///     s.add_struct(None, |inner| {
+///         inner.add_value(&VerboseValue::Str(StringValue {
+///             name: None,
+///             value: "a",
+///         }))
+///     })
+/// })
+/// .unwrap();
+/// ```
+pub fn add_struct_to_msg<const CAP: usize, F>(
+    buf: &mut ArrayVec<u8, CAP>,
+    is_big_endian: bool,
+    name: Option<&str>,
+    add_entries: F,
+) -> Result<(), VerboseEncodeError>
+where
+    F: FnOnce(&mut StructWriter<'_, CAP>) -> Result<(), VerboseEncodeError>,
+{
+    let start = buf.len();
+    let result = add_struct_with_depth(buf, is_big_endian, name, 0, add_entries);
+    if result.is_err() {
+        buf.truncate(start);
+    }
+    result
+}
+
+fn add_struct_with_depth<const CAP: usize, F>(
+    buf: &mut ArrayVec<u8, CAP>,
+    is_big_endian: bool,
+    name: Option<&str>,
+    depth: usize,
+    add_entries: F,
+) -> Result<(), VerboseEncodeError>
+where
+    F: FnOnce(&mut StructWriter<'_, CAP>) -> Result<(), VerboseEncodeError>,
+{
+    if depth >= MAX_STRUCT_DEPTH {
+        return Err(VerboseEncodeError::StructDepthExceeded);
+    }
+
+    // header (entry count is patched once all entries are written)
+    let start = buf.len();
+    let mut writer = FieldWriter::new(buf);
+    writer.write_type_info(
+        [0b0000_0000, 0b0100_0000, 0b0000_0000, 0b0000_0000],
+        name.is_some(),
+        false,
+    )?;
+    let count_offset = start + writer.offset();
+    writer.write_u16(0, is_big_endian)?;
+    if let Some(name) = name {
+        writer.write_var_name(name, is_big_endian)?;
+    }
+
+    // entries
+    let mut writer = StructWriter {
+        buf,
+        is_big_endian,
+        depth,
+        number_of_entries: 0,
+    };
+    add_entries(&mut writer)?;
+    let number_of_entries = writer.number_of_entries;
+
+    let number_of_entries = if is_big_endian {
+        number_of_entries.to_be_bytes()
+    } else {
+        number_of_entries.to_le_bytes()
+    };
+    buf[count_offset..count_offset + 2].copy_from_slice(&number_of_entries);
+
+    Ok(())
+}
+
+/// Writer for the entries of a struct verbose value (see [`add_struct_to_msg`]).
+pub struct StructWriter<'b, const CAP: usize> {
+    buf: &'b mut ArrayVec<u8, CAP>,
+    is_big_endian: bool,
+    depth: usize,
+    number_of_entries: u16,
+}
+
+impl<'b, const CAP: usize> StructWriter<'b, CAP> {
+    /// Adds an already constructed verbose value as entry to the struct.
+    pub fn add_value(&mut self, value: &VerboseValue<'_>) -> Result<(), VerboseEncodeError> {
+        let number_of_entries = self.next_number_of_entries()?;
+        value.add_to_msg(self.buf, self.is_big_endian)?;
+        self.number_of_entries = number_of_entries;
+        Ok(())
+    }
+
+    /// Adds multiple already constructed verbose values as entries to the struct.
+    pub fn add_values(&mut self, values: &[VerboseValue<'_>]) -> Result<(), VerboseEncodeError> {
+        values.iter().try_for_each(|value| self.add_value(value))
+    }
+
+    /// Adds a nested struct as entry to the struct.
+    pub fn add_struct<F>(
+        &mut self,
+        name: Option<&str>,
+        add_entries: F,
+    ) -> Result<(), VerboseEncodeError>
+    where
+        F: FnOnce(&mut StructWriter<'_, CAP>) -> Result<(), VerboseEncodeError>,
+    {
+        let number_of_entries = self.next_number_of_entries()?;
+        add_struct_with_depth(
+            self.buf,
+            self.is_big_endian,
+            name,
+            self.depth + 1,
+            add_entries,
+        )?;
+        self.number_of_entries = number_of_entries;
+        Ok(())
+    }
+
+    /// Number of entries written so far.
+    #[inline]
+    pub fn number_of_entries(&self) -> u16 {
+        self.number_of_entries
+    }
+
+    fn next_number_of_entries(&self) -> Result<u16, VerboseEncodeError> {
+        self.number_of_entries
+            .checked_add(1)
+            .ok_or(VerboseEncodeError::TooManyStructEntries)
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'a> serde::Serialize for StructValue<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -91,16 +217,17 @@ impl<'a> serde::Serialize for StructValue<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::len_zero)]
 mod test {
     use super::*;
     use crate::verbose::VerboseValue::*;
     use crate::verbose::*;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
 
     proptest! {
             #[test]
-            fn write_read(ref data_str in "\\pc{0,80}", ref name in "\\pc{0,20}") {
+            fn write_read(ref data_str in "\\pc{0,80}", ref name in "\\pc{1,20}") {
                 const STRUCT_INIT_LEN_WITHOUT_NAME: usize = 6;
                 const STRUCT_INIT_LEN_WITH_NAME: usize = STRUCT_INIT_LEN_WITHOUT_NAME + 3;
 
@@ -906,11 +1033,11 @@ mod test {
 
             let mut zero_buff: ArrayVec<u8, 0> = ArrayVec::new();
             let err = struct_value.add_to_msg(&mut zero_buff, is_big_endian);
-            prop_assert_eq!(err, Err(CapacityError::new(())));
+            prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             let mut off_by_one_buff: ArrayVec<u8, BUFFER_SIZE> = ArrayVec::new();
             let err = struct_value.add_to_msg(&mut off_by_one_buff, is_big_endian);
-            prop_assert_eq!(err, Err(CapacityError::new(())));
+            prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
 
         }
@@ -937,11 +1064,11 @@ mod test {
 
             let mut zero_buff: ArrayVec<u8, 0> = ArrayVec::new();
             let err = struct_value.add_to_msg(&mut zero_buff, is_big_endian);
-            prop_assert_eq!(err, Err(CapacityError::new(())));
+            prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             let mut off_by_one_buff: ArrayVec<u8, BUFFER_SIZE> = ArrayVec::new();
             let err = struct_value.add_to_msg(&mut off_by_one_buff, is_big_endian);
-            prop_assert_eq!(err, Err(CapacityError::new(())));
+            prop_assert_eq!(err, Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
 
         }
@@ -949,4 +1076,166 @@ mod test {
 
             }
         }
+
+    #[test]
+    fn add_struct_to_msg_round_trip() {
+        for is_big_endian in [false, true] {
+            let mut buf = ArrayVec::<u8, 128>::new();
+            add_struct_to_msg(&mut buf, is_big_endian, Some("outer"), |s| {
+                s.add_values(&[
+                    I32(I32Value {
+                        variable_info: None,
+                        scaling: None,
+                        value: -123,
+                    }),
+                    Str(StringValue {
+                        name: None,
+                        value: "hello",
+                    }),
+                ])?;
+                s.add_struct(Some("inner"), |inner| {
+                    inner.add_value(&U8(U8Value {
+                        variable_info: None,
+                        scaling: None,
+                        value: 7,
+                    }))
+                })
+            })
+            .unwrap();
+
+            // outer header: type info (4) + entry count (2) + name len (2) + "outer\0" (6)
+            // i32 entry: type info (4) + value (4)
+            // string entry: type info (4) + len (2) + "hello\0" (6)
+            // inner header: type info (4) + entry count (2) + name len (2) + "inner\0" (6)
+            // u8 entry: type info (4) + value (1)
+            assert_eq!(14 + 8 + 12 + 14 + 5, buf.len());
+
+            let (value, rest) = VerboseValue::from_slice(&buf, is_big_endian).unwrap();
+            assert!(rest.is_empty());
+            let outer = match value {
+                Struct(outer) => outer,
+                value => panic!("expected struct, got {:?}", value),
+            };
+            assert_eq!(Some("outer"), outer.name);
+            assert_eq!(3, outer.number_of_entries);
+
+            let mut entries = outer.entries();
+            assert_eq!(
+                Some(Ok(I32(I32Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: -123,
+                }))),
+                entries.next()
+            );
+            assert_eq!(
+                Some(Ok(Str(StringValue {
+                    name: None,
+                    value: "hello",
+                }))),
+                entries.next()
+            );
+            let inner = match entries.next() {
+                Some(Ok(Struct(inner))) => inner,
+                other => panic!("expected nested struct, got {:?}", other),
+            };
+            assert!(entries.next().is_none());
+            assert_eq!(Some("inner"), inner.name);
+            assert_eq!(
+                vec![U8(U8Value {
+                    variable_info: None,
+                    scaling: None,
+                    value: 7,
+                })],
+                inner.entries().map(|e| e.unwrap()).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    fn nest<const CAP: usize>(
+        writer: &mut StructWriter<'_, CAP>,
+        levels: usize,
+    ) -> Result<(), VerboseEncodeError> {
+        if levels == 0 {
+            Ok(())
+        } else {
+            writer.add_struct(None, |w| nest(w, levels - 1))
+        }
+    }
+
+    #[test]
+    fn add_struct_to_msg_depth_limit() {
+        // maximum depth
+        {
+            let mut buf = ArrayVec::<u8, 512>::new();
+            add_struct_to_msg(&mut buf, false, None, |w| nest(w, MAX_STRUCT_DEPTH - 1)).unwrap();
+            assert_eq!(MAX_STRUCT_DEPTH * 6, buf.len());
+            let (_, rest) = VerboseValue::from_slice(&buf, false).unwrap();
+            assert!(rest.is_empty());
+        }
+        // one level too deep (buffer gets reset)
+        {
+            let mut buf = ArrayVec::<u8, 512>::new();
+            buf.push(0xff);
+            assert_eq!(
+                Err(VerboseEncodeError::StructDepthExceeded),
+                add_struct_to_msg(&mut buf, false, None, |w| nest(w, MAX_STRUCT_DEPTH))
+            );
+            assert_eq!(&[0xff], &buf[..]);
+        }
+        // decoder shares the same limit
+        {
+            let mut data = Vec::new();
+            for i in 0..=MAX_STRUCT_DEPTH {
+                let count = if i == MAX_STRUCT_DEPTH { 0 } else { 1 };
+                data.extend_from_slice(&[0, 0b0100_0000, 0, 0, count, 0]);
+            }
+            assert_eq!(
+                Err(crate::error::VerboseDecodeError::StructDepthExceeded),
+                VerboseValue::from_slice(&data, false)
+            );
+        }
+    }
+
+    #[test]
+    fn add_struct_to_msg_errors() {
+        // too many entries
+        {
+            let mut buf = ArrayVec::<u8, 32>::new();
+            let mut writer = StructWriter {
+                buf: &mut buf,
+                is_big_endian: false,
+                depth: 0,
+                number_of_entries: u16::MAX,
+            };
+            assert_eq!(
+                Err(VerboseEncodeError::TooManyStructEntries),
+                writer.add_value(&Bool(BoolValue {
+                    name: None,
+                    value: true,
+                }))
+            );
+            assert_eq!(
+                Err(VerboseEncodeError::TooManyStructEntries),
+                writer.add_struct(None, |_| Ok(()))
+            );
+            assert_eq!(u16::MAX, writer.number_of_entries());
+            assert!(buf.is_empty());
+        }
+        // capacity error (buffer gets reset)
+        {
+            let mut buf = ArrayVec::<u8, 8>::new();
+            assert_eq!(
+                Err(VerboseEncodeError::Capacity(CapacityError::new(()))),
+                add_struct_to_msg(&mut buf, false, None, |w| {
+                    w.add_value(&U32(U32Value {
+                        variable_info: None,
+                        scaling: None,
+                        value: 1,
+                    }))
+                })
+            );
+            assert!(buf.is_empty());
+        }
+    }
 }
@@ -1,5 +1,13 @@
-use arrayvec::{ArrayVec, CapacityError};
+use arrayvec::ArrayVec;
 
+use crate::error::VerboseEncodeError;
+use crate::verbose::{FieldWriter, StringCoding};
+
+/// Trace info verbose value (TRAI), e.g. the name of a traced function.
+///
+/// Trace infos can not contain a variable info (VARI) or fixed
+/// point values (FIXP). Messages containing such combinations are
+/// rejected by the decoder.
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TraceInfoValue<'a> {
@@ -7,41 +15,60 @@ pub struct TraceInfoValue<'a> {
 }
 
 impl<'a> TraceInfoValue<'a> {
-    /// Adds the verbose value to the given dlt mesage buffer.
+    /// Adds the verbose value to the given dlt mesage buffer (encoded as UTF-8).
     pub fn add_to_msg<const CAP: usize>(
         &self,
         buf: &mut ArrayVec<u8, CAP>,
         is_big_endian: bool,
-    ) -> Result<(), CapacityError> {
-        let type_info = [0b0000_0000, 0b0010_0000, 0b0000_0000, 0b0000_0000];
-        let value_len = if is_big_endian {
-            (self.value.len() as u16 + 1).to_be_bytes()
-        } else {
-            (self.value.len() as u16 + 1).to_le_bytes()
-        };
-        buf.try_extend_from_slice(&type_info)?;
-        buf.try_extend_from_slice(&[value_len[0], value_len[1]])?;
-
-        buf.try_extend_from_slice(self.value.as_bytes())?;
-        if buf.remaining_capacity() > 0 {
-            // Safe as capacity is checked earlier
-            unsafe { buf.push_unchecked(0) };
-        } else {
-            return Err(CapacityError::new(()));
+    ) -> Result<(), VerboseEncodeError> {
+        self.add_to_msg_with_coding(buf, is_big_endian, StringCoding::Utf8)
+    }
+
+    /// Adds the verbose value to the given dlt mesage buffer using the
+    /// given string coding.
+    ///
+    /// If [`StringCoding::Ascii`] is requested the value is verified to
+    /// only contain ASCII characters.
+    pub fn add_to_msg_with_coding<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+        coding: StringCoding,
+    ) -> Result<(), VerboseEncodeError> {
+        if StringCoding::Ascii == coding && false == self.value.is_ascii() {
+            return Err(VerboseEncodeError::StringNotAscii);
+        }
+        // length includes the terminating zero
+        if self.value.len() >= usize::from(u16::MAX) {
+            return Err(VerboseEncodeError::StringTooLong(self.value.len()));
         }
 
-        Ok(())
+        let mut writer = FieldWriter::new(buf);
+        writer.write_type_info(
+            [
+                0b0000_0000,
+                0b0010_0000 | coding.type_info_flag_1(),
+                0b0000_0000,
+                0b0000_0000,
+            ],
+            false,
+            false,
+        )?;
+        writer.write_u16(self.value.len() as u16 + 1, is_big_endian)?;
+        writer.write_raw(self.value.as_bytes())?;
+        writer.write_u8(0)
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrow)]
 mod test {
     use super::*;
     use crate::verbose::VerboseValue;
     use crate::verbose::VerboseValue::TraceInfo;
     use alloc::vec::Vec;
+    use arrayvec::CapacityError;
     use proptest::prelude::*;
-    use std::format;
 
     proptest! {
         #[test]
@@ -66,7 +93,7 @@ mod test {
 
                 prop_assert_eq!(trace_value.add_to_msg(&mut msg_buff, is_big_endian), Ok(()));
 
-                content_buff.extend_from_slice(&[0b0000_0000, 0b0010_0000, 0b0000_0000, 0b0000_0000, len_value_be[0], len_value_be[1]]);
+                content_buff.extend_from_slice(&[0b0000_0000, 0b1010_0000, 0b0000_0000, 0b0000_0000, len_value_be[0], len_value_be[1]]);
                 content_buff.extend_from_slice(&value.as_bytes());
                 content_buff.push(0);
                 prop_assert_eq!(&msg_buff[..slice_len], &content_buff[..]);
@@ -90,7 +117,7 @@ mod test {
 
                 prop_assert_eq!(trace_value.add_to_msg(&mut msg_buff, is_big_endian), Ok(()));
 
-                content_buff.extend_from_slice(&[0b0000_0000, 0b0010_0000, 0b0000_0000, 0b0000_0000, len_value_le[0], len_value_le[1]]);
+                content_buff.extend_from_slice(&[0b0000_0000, 0b1010_0000, 0b0000_0000, 0b0000_0000, len_value_le[0], len_value_le[1]]);
                 content_buff.extend_from_slice(&value.as_bytes());
                 content_buff.push(0);
                 prop_assert_eq!(&msg_buff[..slice_len], &content_buff[..]);
@@ -109,10 +136,10 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(trace_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(trace_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(trace_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(trace_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
@@ -124,14 +151,111 @@ mod test {
                 let is_big_endian = true;
 
                 let mut msg_buff: ArrayVec<u8, SLICE_LEN> = ArrayVec::new();
-                prop_assert_eq!(trace_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(trace_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
                 let mut msg_buff: ArrayVec<u8, 0> = ArrayVec::new();
-                prop_assert_eq!(trace_value.add_to_msg(&mut msg_buff, is_big_endian), Err(CapacityError::new(())));
+                prop_assert_eq!(trace_value.add_to_msg(&mut msg_buff, is_big_endian), Err(VerboseEncodeError::Capacity(CapacityError::new(()))));
 
             }
 
 
         }
     }
+
+    proptest! {
+        #[test]
+        fn write_read_coding(ref value in "[ -~]{0,40}", is_big_endian in any::<bool>()) {
+            for (coding, flag) in [(StringCoding::Ascii, 0b0000_0000), (StringCoding::Utf8, 0b1000_0000)] {
+                let trace_value = TraceInfoValue { value };
+                let mut buf = ArrayVec::<u8, 64>::new();
+                trace_value.add_to_msg_with_coding(&mut buf, is_big_endian, coding).unwrap();
+                prop_assert_eq!(0b0010_0000 | flag, buf[1]);
+                prop_assert_eq!(
+                    VerboseValue::from_slice(&buf, is_big_endian),
+                    Ok((TraceInfo(trace_value), &[] as &[u8]))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn encode_errors() {
+        // non ascii
+        {
+            let mut buf = ArrayVec::<u8, 64>::new();
+            assert_eq!(
+                Err(VerboseEncodeError::StringNotAscii),
+                TraceInfoValue { value: "ä" }.add_to_msg_with_coding(
+                    &mut buf,
+                    false,
+                    StringCoding::Ascii
+                )
+            );
+            assert!(buf.is_empty());
+        }
+        // too long
+        {
+            let value = "a".repeat(usize::from(u16::MAX));
+            let mut buf = ArrayVec::<u8, 64>::new();
+            assert_eq!(
+                Err(VerboseEncodeError::StringTooLong(value.len())),
+                TraceInfoValue { value: &value }.add_to_msg(&mut buf, false)
+            );
+            assert!(buf.is_empty());
+        }
+    }
+
+    #[test]
+    fn decode_rejects_vari_fixp() {
+        use crate::error::{TypeInfoKind, VerboseDecodeError::*};
+
+        // trace info with VARI (0x08) set
+        let type_info = [0, 0b1010_1000, 0, 0];
+        let data = [type_info[0], type_info[1], 0, 0, 2, 0, b'a', 0];
+        assert_eq!(
+            Err(VariNotAllowedForType {
+                kind: TypeInfoKind::TraceInfo,
+                type_info
+            }),
+            VerboseValue::from_slice(&data, false)
+        );
+
+        // trace info with FIXP (0x10) set
+        let type_info = [0, 0b1011_0000, 0, 0];
+        let data = [type_info[0], type_info[1], 0, 0, 2, 0, b'a', 0];
+        assert_eq!(
+            Err(FixpNotAllowedForType {
+                kind: TypeInfoKind::TraceInfo,
+                type_info
+            }),
+            VerboseValue::from_slice(&data, false)
+        );
+    }
+
+    #[test]
+    fn dlt_daemon_reference() {
+        // verbose little endian trace message in the layout written by
+        // dlt-daemon's libdlt (DLT_TYPE_INFO_TRAI | DLT_SCOD_UTF8) for a
+        // trace info argument "main::run"
+        let reference = [
+            0x3d, 0x00, 0x00, 0x2a, // header type, counter & length
+            b'E', b'C', b'U', b'1', // ecu id
+            0x00, 0x00, 0x04, 0xd2, // session id
+            0x00, 0x01, 0xe2, 0x40, // timestamp
+            0x41, 0x01, b'T', b'R', b'C', 0x00, b'F', b'U', b'N', b'C', // extended header
+            0x00, 0xa0, 0x00, 0x00, // type info (TRAI, SCOD UTF-8)
+            0x0a, 0x00, // length (including terminating zero)
+            b'm', b'a', b'i', b'n', b':', b':', b'r', b'u', b'n', 0x00, // value
+        ];
+        let packet = crate::DltPacketSlice::from_slice(&reference).unwrap();
+
+        let value = TraceInfoValue { value: "main::run" };
+        let mut buf = ArrayVec::<u8, 16>::new();
+        value.add_to_msg(&mut buf, false).unwrap();
+        assert_eq!(packet.payload(), &buf[..]);
+
+        let mut iter = packet.verbose_value_iter().unwrap();
+        assert_eq!(Some(Ok(TraceInfo(value))), iter.next());
+        assert_eq!(None, iter.next());
+    }
 }
@@ -1,5 +1,6 @@
 use super::VerboseValue;
 use crate::error::VerboseDecodeError;
+use crate::ParseConfig;
 
 /// Iterator over verbose values.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -7,6 +8,7 @@ pub struct VerboseIter<'a> {
     is_big_endian: bool,
     number_of_arguments: u16,
     rest: &'a [u8],
+    config: ParseConfig,
 }
 
 impl<'a> VerboseIter<'a> {
@@ -21,9 +23,23 @@ impl<'a> VerboseIter<'a> {
             is_big_endian,
             number_of_arguments,
             rest: payload,
+            config: ParseConfig::default(),
         }
     }
 
+    /// Sets the checks done when decoding the values (see [`ParseConfig`]).
+    #[inline]
+    pub fn with_config(mut self, config: ParseConfig) -> VerboseIter<'a> {
+        self.config = config;
+        self
+    }
+
+    /// Checks done when decoding the values.
+    #[inline]
+    pub fn config(&self) -> &ParseConfig {
+        &self.config
+    }
+
     /// Returns if the values encoded in the big endian format.
     #[inline]
     pub fn is_big_endian(&self) -> bool {
@@ -41,6 +57,13 @@ impl<'a> VerboseIter<'a> {
     pub fn raw(&self) -> &'a [u8] {
         self.rest
     }
+
+    /// Returns an iterator over the values with a variable name together
+    /// with their name (values without a name are skipped).
+    #[inline]
+    pub fn named(self) -> NamedVerboseIter<'a> {
+        NamedVerboseIter { iter: self }
+    }
 }
 
 impl<'a> core::iter::Iterator for VerboseIter<'a> {
@@ -50,7 +73,8 @@ impl<'a> core::iter::Iterator for VerboseIter<'a> {
         if self.number_of_arguments == 0 {
             None
         } else {
-            match VerboseValue::from_slice(self.rest, self.is_big_endian) {
+            match VerboseValue::from_slice_with_config(self.rest, self.is_big_endian, &self.config)
+            {
                 Ok((value, rest)) => {
                     self.rest = rest;
                     self.number_of_arguments -= 1;
@@ -67,10 +91,42 @@ impl<'a> core::iter::Iterator for VerboseIter<'a> {
     }
 }
 
+/// Iterator over the verbose values that have a variable name
+/// (see [`VerboseIter::named`]).
+///
+/// Values with duplicate names are all returned in the order they
+/// appear in the message. Decoding errors are passed through and
+/// end the iteration.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct NamedVerboseIter<'a> {
+    iter: VerboseIter<'a>,
+}
+
+impl<'a> core::iter::Iterator for NamedVerboseIter<'a> {
+    type Item = Result<(&'a str, VerboseValue<'a>), VerboseDecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for value in self.iter.by_ref() {
+            match value {
+                Ok(value) => {
+                    if let Some(name) = value.name() {
+                        return Some(Ok((name, value)));
+                    }
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        None
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::VerboseIter;
-    use crate::verbose::{U16Value, U32Value, VerboseValue};
+    use crate::verbose::{
+        I128Value, I16Value, I32Value, I64Value, I8Value, Scaling, U128Value, U16Value, U32Value,
+        U64Value, U8Value, VariableInfoUnit, VerboseValue,
+    };
     use arrayvec::ArrayVec;
 
     #[test]
@@ -172,4 +228,201 @@ mod test {
             assert_eq!(None, iter.next());
         }
     }
+
+    #[test]
+    fn next_all_unsigned_widths() {
+        for is_big_endian in [false, true] {
+            let var_info = Some(VariableInfoUnit {
+                name: "speed",
+                unit: "km/h",
+            });
+            let u8_value = U8Value {
+                variable_info: None,
+                scaling: None,
+                value: 0xab,
+            };
+            let u16_value = U16Value {
+                variable_info: var_info.clone(),
+                scaling: None,
+                value: 0x1234,
+            };
+            let u32_value = U32Value {
+                variable_info: None,
+                scaling: Some(Scaling {
+                    quantization: 0.5,
+                    offset: -12,
+                }),
+                value: 0x1234_5678,
+            };
+            let u64_value = U64Value {
+                variable_info: var_info.clone(),
+                scaling: Some(Scaling {
+                    quantization: 1.5,
+                    offset: -1234_5678_9012,
+                }),
+                value: 0x1234_5678_9abc_def0,
+            };
+            let u128_value = U128Value {
+                variable_info: var_info,
+                scaling: Some(Scaling {
+                    quantization: 2.0,
+                    offset: i128::MIN,
+                }),
+                value: u128::MAX - 1,
+            };
+
+            let mut data = ArrayVec::<u8, 1000>::new();
+            u8_value.add_to_msg(&mut data, is_big_endian).unwrap();
+            u16_value.add_to_msg(&mut data, is_big_endian).unwrap();
+            u32_value.add_to_msg(&mut data, is_big_endian).unwrap();
+            u64_value.add_to_msg(&mut data, is_big_endian).unwrap();
+            u128_value.add_to_msg(&mut data, is_big_endian).unwrap();
+
+            // type info + var info (4 + "speed\0" + "km/h\0") + scaling + value
+            assert_eq!(
+                (4 + 1)
+                    + (4 + 4 + 6 + 5 + 2)
+                    + (4 + 4 + 4 + 4)
+                    + (4 + 4 + 6 + 5 + 4 + 8 + 8)
+                    + (4 + 4 + 6 + 5 + 4 + 16 + 16),
+                data.len()
+            );
+
+            let mut iter = VerboseIter::new(is_big_endian, 5, &data);
+            assert_eq!(Some(Ok(VerboseValue::U8(u8_value))), iter.next());
+            assert_eq!(Some(Ok(VerboseValue::U16(u16_value))), iter.next());
+            assert_eq!(Some(Ok(VerboseValue::U32(u32_value))), iter.next());
+            assert_eq!(Some(Ok(VerboseValue::U64(u64_value))), iter.next());
+            assert_eq!(Some(Ok(VerboseValue::U128(u128_value))), iter.next());
+            assert_eq!(None, iter.next());
+            assert!(iter.raw().is_empty());
+        }
+    }
+
+    #[test]
+    fn next_all_signed_widths() {
+        for is_big_endian in [false, true] {
+            let var_info = Some(VariableInfoUnit {
+                name: "temp",
+                unit: "C",
+            });
+            let i8_value = I8Value {
+                variable_info: None,
+                scaling: None,
+                value: i8::MIN,
+            };
+            let i16_value = I16Value {
+                variable_info: var_info.clone(),
+                scaling: None,
+                value: -1,
+            };
+            let i32_value = I32Value {
+                variable_info: None,
+                scaling: Some(Scaling {
+                    quantization: 0.25,
+                    offset: i32::MIN,
+                }),
+                value: i32::MAX,
+            };
+            let i64_value = I64Value {
+                variable_info: var_info.clone(),
+                scaling: Some(Scaling {
+                    quantization: -1.5,
+                    offset: i64::MAX,
+                }),
+                value: i64::MIN,
+            };
+            let i128_value = I128Value {
+                variable_info: var_info,
+                scaling: Some(Scaling {
+                    quantization: 3.0,
+                    offset: -1,
+                }),
+                value: i128::MIN,
+            };
+
+            let mut data = ArrayVec::<u8, 1000>::new();
+            i8_value.add_to_msg(&mut data, is_big_endian).unwrap();
+            i16_value.add_to_msg(&mut data, is_big_endian).unwrap();
+            i32_value.add_to_msg(&mut data, is_big_endian).unwrap();
+            i64_value.add_to_msg(&mut data, is_big_endian).unwrap();
+            i128_value.add_to_msg(&mut data, is_big_endian).unwrap();
+
+            // two's complement of the minimum values
+            let i64_min = if is_big_endian {
+                [0x80, 0, 0, 0, 0, 0, 0, 0]
+            } else {
+                [0, 0, 0, 0, 0, 0, 0, 0x80]
+            };
+            let i64_start = (4 + 1) + (4 + 4 + 5 + 2 + 2) + (4 + 4 + 4 + 4);
+            let i64_value_start = i64_start + 4 + 4 + 5 + 2 + 4 + 8;
+            assert_eq!(&i64_min[..], &data[i64_value_start..i64_value_start + 8]);
+
+            let mut iter = VerboseIter::new(is_big_endian, 5, &data);
+            assert_eq!(Some(Ok(VerboseValue::I8(i8_value))), iter.next());
+            assert_eq!(Some(Ok(VerboseValue::I16(i16_value))), iter.next());
+            assert_eq!(Some(Ok(VerboseValue::I32(i32_value))), iter.next());
+            assert_eq!(Some(Ok(VerboseValue::I64(i64_value))), iter.next());
+            assert_eq!(Some(Ok(VerboseValue::I128(i128_value))), iter.next());
+            assert_eq!(None, iter.next());
+            assert!(iter.raw().is_empty());
+        }
+    }
+
+    #[test]
+    fn named() {
+        let var_info = |name| Some(VariableInfoUnit { name, unit: "unit" });
+        let values = [
+            U16Value {
+                variable_info: var_info("a"),
+                scaling: None,
+                value: 1,
+            },
+            U16Value {
+                variable_info: None,
+                scaling: None,
+                value: 2,
+            },
+            U16Value {
+                variable_info: var_info("b"),
+                scaling: None,
+                value: 3,
+            },
+            U16Value {
+                variable_info: var_info("a"),
+                scaling: None,
+                value: 4,
+            },
+        ];
+        let mut data = ArrayVec::<u8, 1000>::new();
+        for value in &values {
+            value.add_to_msg(&mut data, false).unwrap();
+        }
+
+        // all named values (including duplicates) in order
+        {
+            let mut iter = VerboseIter::new(false, 4, &data).named();
+            assert_eq!(
+                Some(Ok(("a", VerboseValue::U16(values[0].clone())))),
+                iter.next()
+            );
+            assert_eq!(
+                Some(Ok(("b", VerboseValue::U16(values[2].clone())))),
+                iter.next()
+            );
+            assert_eq!(
+                Some(Ok(("a", VerboseValue::U16(values[3].clone())))),
+                iter.next()
+            );
+            assert_eq!(None, iter.next());
+        }
+
+        // error is passed through
+        {
+            let mut iter = VerboseIter::new(false, 5, &data).named();
+            assert_eq!(3, iter.by_ref().take(3).count());
+            assert!(iter.next().unwrap().is_err());
+            assert_eq!(None, iter.next());
+        }
+    }
 }
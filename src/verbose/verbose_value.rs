@@ -1,4 +1,5 @@
-use crate::error::VerboseDecodeError;
+use crate::error::{TypeInfoKind, VerboseDecodeError, VerboseEncodeError};
+use arrayvec::{ArrayVec, CapacityError};
 
 use super::*;
 
@@ -47,6 +48,43 @@ impl<'a> VerboseValue<'a> {
     pub fn from_slice(
         slice: &'a [u8],
         is_big_endian: bool,
+    ) -> Result<(VerboseValue<'a>, &'a [u8]), error::VerboseDecodeError> {
+        VerboseValue::from_slice_with_depth(slice, is_big_endian, &ParseConfig::default(), 0)
+    }
+
+    /// Decodes a verbose value with the checks configured in `config`
+    /// (see [`ParseConfig`]).
+    ///
+    /// The entries of struct values are validated with the given config
+    /// but decoded with the default config when iterated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dlt_parse::{verbose::VerboseValue, ParseConfig};
+    ///
+    /// // bool with the value 2
+    /// let data = [0x11, 0, 0, 0, 2];
+    /// assert!(VerboseValue::from_slice_with_config(&data, false, &ParseConfig::strict()).is_err());
+    ///
+    /// let (value, _) =
+    ///     VerboseValue::from_slice_with_config(&data, false, &ParseConfig::lenient()).unwrap();
+    /// assert!(matches!(value, VerboseValue::Bool(b) if b.value));
+    /// ```
+    pub fn from_slice_with_config(
+        slice: &'a [u8],
+        is_big_endian: bool,
+        config: &ParseConfig,
+    ) -> Result<(VerboseValue<'a>, &'a [u8]), error::VerboseDecodeError> {
+        VerboseValue::from_slice_with_depth(slice, is_big_endian, config, 0)
+    }
+
+    /// Decodes a verbose value that is nested in `depth` structs.
+    fn from_slice_with_depth(
+        slice: &'a [u8],
+        is_big_endian: bool,
+        config: &ParseConfig,
+        depth: usize,
     ) -> Result<(VerboseValue<'a>, &'a [u8]), error::VerboseDecodeError> {
         use error::{UnexpectedEndOfSliceError, VerboseDecodeError::*};
         use VerboseValue::*;
@@ -73,25 +111,32 @@ impl<'a> VerboseValue<'a> {
         // determine the type
 
         const TYPE_LEN_MASK_0: u8 = 0b0000_1111;
-        const BOOL_FLAG_0: u8 = 0b0001_0000;
-        const SIGNED_FLAG_0: u8 = 0b0010_0000;
-        const UNSIGNED_FLAG_0: u8 = 0b0100_0000;
-        const FLOAT_FLAG_0: u8 = 0b1000_0000;
-
         const ARRAY_FLAG_1: u8 = 0b0000_0001;
-        const STRING_FLAG_1: u8 = 0b0000_0010;
-        const RAW_FLAG_1: u8 = 0b0000_0100;
         const VARINFO_FLAG_1: u8 = 0b0000_1000;
-        const TRACE_INFO_FLAG_1: u8 = 0b0010_0000;
-        const STRUCT_FLAG_1: u8 = 0b0100_0000;
+
+        let kind = type_info_kind(type_info)?;
 
         let mut slicer = FieldSlicer::new(
             // SAFETY: Length of at least 4 verified in the if at the beginning.
             unsafe { slice::from_raw_parts(slice.as_ptr().add(4), slice.len() - 4) },
             4,
-        );
+        )
+        .with_allow_missing_termination(config.allow_missing_string_termination);
 
         if 0 != type_info[1] & ARRAY_FLAG_1 {
+            // only bool & numeric values can be arrays
+            if false
+                == matches!(
+                    kind,
+                    TypeInfoKind::Bool
+                        | TypeInfoKind::Signed
+                        | TypeInfoKind::Unsigned
+                        | TypeInfoKind::Float
+                )
+            {
+                return Err(InvalidTypeInfo(type_info));
+            }
+
             let type_len: usize = usize::from(type_info[0] & TYPE_LEN_MASK_0);
 
             // read array dimensions
@@ -106,645 +151,862 @@ impl<'a> VerboseValue<'a> {
 
             let variable_info = name_and_unit.map(|(name, unit)| VariableInfoUnit { name, unit });
 
-            if 0 != type_info[0] & BOOL_FLAG_0 {
-                const CONTRADICTING_MASK_0: u8 = 0b1110_0000;
-                const CONTRADICTING_MASK_1: u8 = 0b1111_0110;
-                if
-                // check type length (must be 1 for bool)
-                (1 != type_info[0] & TYPE_LEN_MASK_0) ||
-                    // check none of the other type flags other then varinfo
-                    // flag is set
-                    (0 != type_info[0] & CONTRADICTING_MASK_0) ||
-                    (0 != type_info[1] & CONTRADICTING_MASK_1)
-                {
-                    return Err(InvalidTypeInfo(type_info));
+            match kind {
+                TypeInfoKind::Bool => {
+                    const CONTRADICTING_MASK_0: u8 = 0b1110_0000;
+                    const CONTRADICTING_MASK_1: u8 = 0b1111_0110;
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::Bool,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+                    // check type length (must be 1 for bool)
+                    check_type_len(type_info, TypeInfoKind::Bool, 1..=1)?;
+
+                    // determine data size of array
+                    let data_len = dimensions
+                        .element_count()
+                        .ok_or(VerboseDecodeError::ArrayDimensionsOverflow)?;
+
+                    // take the data area of the bool array
+                    Ok((
+                        ArrBool(ArrayBool {
+                            dimensions,
+                            variable_info,
+                            data: slicer.read_raw(data_len)?,
+                        }),
+                        slicer.rest(),
+                    ))
                 }
-
-                // determine data size of array
-                let mut data_len = 0;
-                for dim in &dimensions {
-                    if let Some(sum) = (usize::from(dim)).checked_add(data_len) {
-                        data_len = sum;
-                    } else {
-                        return Err(VerboseDecodeError::ArrayDimensionsOverflow);
+                TypeInfoKind::Signed => {
+                    const CONTRADICTING_MASK_0: u8 = 0b1101_0000;
+                    const CONTRADICTING_MASK_1: u8 = 0b1110_0110;
+
+                    // check that no contradicting type info is present
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::Signed,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+
+                    check_type_len(type_info, TypeInfoKind::Signed, 1..=5)?;
+
+                    let real_type_len = 0b0000_0001 << (type_len - 1);
+
+                    // determine data size of array
+                    let data_len = dimensions
+                        .element_count()
+                        .and_then(|count| count.checked_mul(real_type_len))
+                        .ok_or(VerboseDecodeError::ArrayDimensionsOverflow)?;
+
+                    match type_len {
+                        1 => Ok((
+                            ArrI8(ArrayI8 {
+                                dimensions,
+                                variable_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        2 => Ok((
+                            ArrI16(ArrayI16 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        3 => Ok((
+                            ArrI32(ArrayI32 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        4 => Ok((
+                            ArrI64(ArrayI64 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                scaling: slicer.read_i64_scaling(is_big_endian, type_info)?,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        5 => Ok((
+                            ArrI128(ArrayI128 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                scaling: slicer.read_i128_scaling(is_big_endian, type_info)?,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        _ => unreachable!(),
                     }
                 }
-
-                // take the data area of the bool array
-                Ok((
-                    ArrBool(ArrayBool {
-                        dimensions,
-                        variable_info,
-                        data: slicer.read_raw(data_len)?,
-                    }),
-                    slicer.rest(),
-                ))
-            } else if 0 != type_info[0] & SIGNED_FLAG_0 {
-                const CONTRADICTING_MASK_0: u8 = 0b1101_0000;
-                const CONTRADICTING_MASK_1: u8 = 0b1110_0110;
-
-                // check that no contradicting type info is present
-                if (0 != type_info[0] & CONTRADICTING_MASK_0)
-                    || (0 != type_info[1] & CONTRADICTING_MASK_1)
-                {
-                    return Err(InvalidTypeInfo(type_info));
+                TypeInfoKind::Unsigned => {
+                    const CONTRADICTING_MASK_0: u8 = 0b1011_0000;
+                    const CONTRADICTING_MASK_1: u8 = 0b1110_0110;
+
+                    // check that no contradicting type info is present
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::Unsigned,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+
+                    let type_len = type_info[0] & TYPE_LEN_MASK_0;
+                    check_type_len(type_info, TypeInfoKind::Unsigned, 1..=5)?;
+
+                    let real_type_len = 0b0000_0001 << (type_len - 1);
+
+                    // determine data size of array
+                    let data_len = dimensions
+                        .element_count()
+                        .and_then(|count| count.checked_mul(real_type_len))
+                        .ok_or(VerboseDecodeError::ArrayDimensionsOverflow)?;
+
+                    match type_len {
+                        1 => Ok((
+                            ArrU8(ArrayU8 {
+                                dimensions,
+                                variable_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        2 => Ok((
+                            ArrU16(ArrayU16 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        3 => Ok((
+                            ArrU32(ArrayU32 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        4 => Ok((
+                            ArrU64(ArrayU64 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                scaling: slicer.read_i64_scaling(is_big_endian, type_info)?,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        5 => Ok((
+                            ArrU128(ArrayU128 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                scaling: slicer.read_i128_scaling(is_big_endian, type_info)?,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        _ => unreachable!(),
+                    }
                 }
-
-                match type_len {
-                    1..=5 => {}
-                    _ => return Err(InvalidTypeInfo(type_info)), //Look
+                TypeInfoKind::Float => {
+                    const CONTRADICTING_MASK_0: u8 = 0b0111_0000;
+                    const CONTRADICTING_MASK_1: u8 = 0b1111_0110;
+
+                    // check that no contradicting type info is present
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::Float,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+
+                    let type_len = type_info[0] & TYPE_LEN_MASK_0;
+                    check_type_len(type_info, TypeInfoKind::Float, 2..=5)?;
+
+                    let real_type_len = 0b0000_0001 << (type_len - 1);
+
+                    // determine data size of array
+                    let data_len = dimensions
+                        .element_count()
+                        .and_then(|count| count.checked_mul(real_type_len))
+                        .ok_or(VerboseDecodeError::ArrayDimensionsOverflow)?;
+
+                    match type_len {
+                        2 => Ok((
+                            ArrF16(ArrayF16 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        3 => Ok((
+                            ArrF32(ArrayF32 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        4 => Ok((
+                            ArrF64(ArrayF64 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        5 => Ok((
+                            ArrF128(ArrayF128 {
+                                is_big_endian,
+                                dimensions,
+                                variable_info,
+                                data: slicer.read_raw(data_len)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        _ => unreachable!(),
+                    }
                 }
-
-                let real_type_len = 0b0000_0001 << (type_len - 1);
-
-                // determine data size of array
-                let mut data_len = 0;
-                for dim in &dimensions {
-                    if let Some(sum) = (usize::from(dim) * real_type_len).checked_add(data_len) {
-                        data_len = sum;
+                // checked above
+                _ => unreachable!(),
+            }
+        } else {
+            match kind {
+                TypeInfoKind::Bool => {
+                    const CONTRADICTING_MASK_0: u8 = 0b1110_0000;
+                    const CONTRADICTING_MASK_1: u8 = 0b1111_0111;
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::Bool,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+                    // check type length (must be 1 for bool)
+                    check_type_len(type_info, TypeInfoKind::Bool, 1..=1)?;
+
+                    // check for varinfo
+                    let name = if 0 != type_info[1] & VARINFO_FLAG_1 {
+                        Some(slicer.read_var_name(is_big_endian)?)
+                    } else {
+                        None
+                    };
+
+                    // verify no conflicting information is present
+                    let value_u8 = slicer.read_u8()?;
+
+                    let value = match value_u8 {
+                        0 => false,
+                        1 => true,
+                        value if config.strict_bool => return Err(InvalidBoolValue(value)),
+                        _ => true,
+                    };
+                    Ok((Bool(BoolValue { name, value }), slicer.rest()))
+                }
+                TypeInfoKind::Signed => {
+                    const CONTRADICTING_MASK_0: u8 = 0b1101_0000;
+                    const CONTRADICTING_MASK_1: u8 = 0b1110_0111;
+
+                    // check that no contradicting type info is present
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::Signed,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+
+                    let type_len = type_info[0] & TYPE_LEN_MASK_0;
+                    check_type_len(type_info, TypeInfoKind::Signed, 1..=5)?;
+
+                    // check for varinfo
+                    let name_and_unit = if 0 != type_info[1] & VARINFO_FLAG_1 {
+                        Some(slicer.read_var_name_and_unit(is_big_endian)?)
                     } else {
-                        return Err(VerboseDecodeError::ArrayDimensionsOverflow);
+                        None
+                    };
+
+                    let var_info =
+                        name_and_unit.map(|(name, unit)| VariableInfoUnit { name, unit });
+
+                    match type_len {
+                        1 => Ok((
+                            I8(I8Value {
+                                variable_info: var_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                value: slicer.read_i8()?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        2 => Ok((
+                            I16(I16Value {
+                                variable_info: var_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                value: slicer.read_i16(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        3 => Ok((
+                            I32(I32Value {
+                                variable_info: var_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                value: slicer.read_i32(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        4 => Ok((
+                            I64(I64Value {
+                                variable_info: var_info,
+                                scaling: slicer.read_i64_scaling(is_big_endian, type_info)?,
+                                value: slicer.read_i64(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        5 => Ok((
+                            I128(I128Value {
+                                variable_info: var_info,
+                                scaling: slicer.read_i128_scaling(is_big_endian, type_info)?,
+                                value: slicer.read_i128(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        _ => unreachable!(),
                     }
                 }
-
-                match type_len {
-                    1 => Ok((
-                        ArrI8(ArrayI8 {
-                            dimensions,
-                            variable_info,
-                            scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    2 => Ok((
-                        ArrI16(ArrayI16 {
-                            is_big_endian,
-                            dimensions,
-                            variable_info,
-                            scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    3 => Ok((
-                        ArrI32(ArrayI32 {
-                            is_big_endian,
-                            dimensions,
-                            variable_info,
-                            scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    4 => Ok((
-                        ArrI64(ArrayI64 {
-                            is_big_endian,
-                            dimensions,
-                            variable_info,
-                            scaling: slicer.read_i64_scaling(is_big_endian, type_info)?,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    5 => Ok((
-                        ArrI128(ArrayI128 {
-                            is_big_endian,
-                            dimensions,
-                            variable_info,
-                            scaling: slicer.read_i128_scaling(is_big_endian, type_info)?,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    _ => unreachable!(),
+                TypeInfoKind::Unsigned => {
+                    // verify no conflicting information is present
+                    const CONTRADICTING_MASK_0: u8 = 0b1011_0000;
+                    const CONTRADICTING_MASK_1: u8 = 0b1110_0111;
+
+                    // check that no contradicting type info is present
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::Unsigned,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+
+                    let type_len = type_info[0] & TYPE_LEN_MASK_0;
+                    check_type_len(type_info, TypeInfoKind::Unsigned, 1..=5)?;
+
+                    // check for varinfo
+                    let name_and_unit = if 0 != type_info[1] & VARINFO_FLAG_1 {
+                        Some(slicer.read_var_name_and_unit(is_big_endian)?)
+                    } else {
+                        None
+                    };
+
+                    let var_info =
+                        name_and_unit.map(|(name, unit)| VariableInfoUnit { name, unit });
+
+                    match type_len {
+                        1 => Ok((
+                            U8(U8Value {
+                                variable_info: var_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                value: slicer.read_u8()?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        2 => Ok((
+                            U16(U16Value {
+                                variable_info: var_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                value: slicer.read_u16(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        3 => Ok((
+                            U32(U32Value {
+                                variable_info: var_info,
+                                scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
+                                value: slicer.read_u32(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        4 => Ok((
+                            U64(U64Value {
+                                variable_info: var_info,
+                                scaling: slicer.read_i64_scaling(is_big_endian, type_info)?,
+                                value: slicer.read_u64(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        5 => Ok((
+                            U128(U128Value {
+                                variable_info: var_info,
+                                scaling: slicer.read_i128_scaling(is_big_endian, type_info)?,
+                                value: slicer.read_u128(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        _ => unreachable!(),
+                    }
                 }
-            } else if 0 != type_info[0] & UNSIGNED_FLAG_0 {
-                const CONTRADICTING_MASK_0: u8 = 0b1011_0000;
-                const CONTRADICTING_MASK_1: u8 = 0b1110_0110;
-
-                // check that no contradicting type info is present
-                if (0 != type_info[0] & CONTRADICTING_MASK_0)
-                    || (0 != type_info[1] & CONTRADICTING_MASK_1)
-                {
-                    return Err(InvalidTypeInfo(type_info));
+                TypeInfoKind::Float => {
+                    // verify no conflicting information is present
+
+                    const CONTRADICTING_MASK_0: u8 = 0b0111_0000;
+                    const CONTRADICTING_MASK_1: u8 = 0b1111_0111;
+
+                    // check that no contradicting type info is present
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::Float,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+
+                    let type_len = type_info[0] & TYPE_LEN_MASK_0;
+                    check_type_len(type_info, TypeInfoKind::Float, 2..=5)?;
+
+                    // check for varinfo
+                    let name_and_unit = if 0 != type_info[1] & VARINFO_FLAG_1 {
+                        Some(slicer.read_var_name_and_unit(is_big_endian)?)
+                    } else {
+                        None
+                    };
+
+                    let variable_info =
+                        name_and_unit.map(|(name, unit)| VariableInfoUnit { name, unit });
+
+                    match type_len {
+                        2 => Ok((
+                            F16(F16Value {
+                                variable_info,
+                                value: slicer.read_f16(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        3 => Ok((
+                            F32(F32Value {
+                                variable_info,
+                                value: slicer.read_f32(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        4 => Ok((
+                            F64(F64Value {
+                                variable_info,
+                                value: slicer.read_f64(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        5 => Ok((
+                            F128(F128Value {
+                                variable_info,
+                                value: slicer.read_f128(is_big_endian)?,
+                            }),
+                            slicer.rest(),
+                        )),
+                        _ => unreachable!(),
+                    }
                 }
+                TypeInfoKind::String => {
+                    const CONTRADICTING_MASK_0: u8 = 0b1111_1111;
+                    const CONTRADICTING_MASK_1: u8 = 0b0111_0101;
 
-                let type_len = type_info[0] & TYPE_LEN_MASK_0;
-                match type_len {
-                    1..=5 => {}
-                    _ => return Err(InvalidTypeInfo(type_info)),
-                }
+                    // check none of the other type flags other then varinfo
+                    // flag is set
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::String,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+                    check_type_len(type_info, TypeInfoKind::String, 0..=0)?;
+
+                    // check the string coding (0 = ASCII, 1 = UTF-8)
+                    let scod = (type_info[1] >> 7) | ((type_info[2] & 0b0000_0011) << 1);
+                    if scod > 1 {
+                        return Err(InvalidStringCoding { scod, type_info });
+                    }
 
-                let real_type_len = 0b0000_0001 << (type_len - 1);
+                    let len = usize::from(slicer.read_u16(is_big_endian)?);
 
-                // determine data size of array
-                let mut data_len = 0;
-                for dim in &dimensions {
-                    if let Some(sum) = (usize::from(dim) * real_type_len).checked_add(data_len) {
-                        data_len = sum;
+                    let name = if 0 != type_info[1] & VARINFO_FLAG_1 {
+                        Some(slicer.read_var_name(is_big_endian)?)
                     } else {
-                        return Err(VerboseDecodeError::ArrayDimensionsOverflow);
-                    }
+                        None
+                    };
+                    let value = match slicer.read_raw(len) {
+                        Ok(valid_parse) => {
+                            let data = strip_termination(
+                                valid_parse,
+                                config.allow_missing_string_termination,
+                            );
+                            check_embedded_null(data, config)?;
+                            core::str::from_utf8(data)?
+                        }
+                        Err(_) => {
+                            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                                layer: error::Layer::VerboseValue,
+                                minimum_size: len,
+                                actual_size: slicer.rest().len(),
+                            }))
+                        }
+                    };
+
+                    Ok((Str(StringValue { name, value }), slicer.rest()))
                 }
+                TypeInfoKind::Raw => {
+                    // verify no conflicting information is present+
+                    const CONTRADICTING_MASK_0: u8 = 0b1111_0000;
+                    const CONTRADICTING_MASK_1: u8 = 0b0111_0011;
 
-                match type_len {
-                    1 => Ok((
-                        ArrU8(ArrayU8 {
-                            dimensions,
-                            variable_info,
-                            scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    2 => Ok((
-                        ArrU16(ArrayU16 {
-                            is_big_endian,
-                            dimensions,
-                            variable_info,
-                            scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    3 => Ok((
-                        ArrU32(ArrayU32 {
-                            is_big_endian,
-                            dimensions,
-                            variable_info,
-                            scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    4 => Ok((
-                        ArrU64(ArrayU64 {
-                            is_big_endian,
-                            dimensions,
-                            variable_info,
-                            scaling: slicer.read_i64_scaling(is_big_endian, type_info)?,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    5 => Ok((
-                        ArrU128(ArrayU128 {
-                            is_big_endian,
-                            dimensions,
-                            variable_info,
-                            scaling: slicer.read_i128_scaling(is_big_endian, type_info)?,
-                            data: slicer.read_raw(data_len)?,
+                    // check none of the other type flags other then varinfo
+                    // flag is set
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::Raw,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+
+                    // read len of raw data
+                    let len = usize::from(slicer.read_u16(is_big_endian)?);
+
+                    // check for varinfo
+                    let name = if 0 != type_info[1] & VARINFO_FLAG_1 {
+                        Some(slicer.read_var_name(is_big_endian)?)
+                    } else {
+                        None
+                    };
+
+                    Ok((
+                        Raw(RawValue {
+                            name,
+                            data: slicer.read_raw(len)?,
                         }),
                         slicer.rest(),
-                    )),
-                    _ => unreachable!(),
-                }
-            } else if 0 != type_info[0] & FLOAT_FLAG_0 {
-                const CONTRADICTING_MASK_0: u8 = 0b0111_0000;
-                const CONTRADICTING_MASK_1: u8 = 0b1111_0110;
-
-                // check that no contradicting type info is present
-                if (0 != type_info[0] & CONTRADICTING_MASK_0)
-                    || (0 != type_info[1] & CONTRADICTING_MASK_1)
-                {
-                    return Err(InvalidTypeInfo(type_info));
+                    ))
                 }
+                TypeInfoKind::TraceInfo => {
+                    // verify no conflicting information is present
+
+                    const CONTRADICTING_MASK_0: u8 = 0b1111_1111;
+                    const CONTRADICTING_MASK_1: u8 = 0b0101_1111;
+
+                    // check that no contradicting type info is present
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::TraceInfo,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+                    check_type_len(type_info, TypeInfoKind::TraceInfo, 0..=0)?;
+
+                    // check the string coding (0 = ASCII, 1 = UTF-8) so values
+                    // with an unknown coding are not misread
+                    let scod = (type_info[1] >> 7) | ((type_info[2] & 0b0000_0011) << 1);
+                    if scod > 1 {
+                        return Err(InvalidStringCoding { scod, type_info });
+                    }
 
-                let type_len = type_info[0] & TYPE_LEN_MASK_0;
-                match type_len {
-                    2..=5 => {}
-                    _ => return Err(InvalidTypeInfo(type_info)),
+                    // read len of trace data string
+                    let len = usize::from(slicer.read_u16(is_big_endian)?);
+
+                    let parse: Result<&str, str::Utf8Error> = match slicer.read_raw(len) {
+                        Ok(valid_parse) => {
+                            let data = strip_termination(
+                                valid_parse,
+                                config.allow_missing_string_termination,
+                            );
+                            check_embedded_null(data, config)?;
+                            str::from_utf8(data)
+                        }
+                        Err(_) => {
+                            return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
+                                layer: error::Layer::VerboseValue,
+                                minimum_size: len,
+                                actual_size: slicer.rest().len(),
+                            }))
+                        }
+                    };
+                    Ok((TraceInfo(TraceInfoValue { value: parse? }), slicer.rest()))
                 }
+                TypeInfoKind::Struct => {
+                    // verify no conflicting information is present
+                    const CONTRADICTING_MASK_0: u8 = 0b1111_1111;
+                    const CONTRADICTING_MASK_1: u8 = 0b1011_0111;
+
+                    // check that no contradicting type info is present
+                    check_type_flags(
+                        type_info,
+                        TypeInfoKind::Struct,
+                        CONTRADICTING_MASK_0,
+                        CONTRADICTING_MASK_1,
+                    )?;
+                    check_type_len(type_info, TypeInfoKind::Struct, 0..=0)?;
+
+                    // limit the nesting to prevent unbounded recursion
+                    if depth >= MAX_STRUCT_DEPTH {
+                        return Err(StructDepthExceeded);
+                    }
 
-                let real_type_len = 0b0000_0001 << (type_len - 1);
+                    // read number of struct entries
+                    let number_of_entries = slicer.read_u16(is_big_endian)?;
 
-                // determine data size of array
-                let mut data_len = 0;
-                for dim in &dimensions {
-                    if let Some(sum) = (usize::from(dim) * real_type_len).checked_add(data_len) {
-                        data_len = sum;
+                    let name = if 0 != type_info[1] & VARINFO_FLAG_1 {
+                        Some(slicer.read_var_name(is_big_endian)?)
                     } else {
-                        return Err(VerboseDecodeError::ArrayDimensionsOverflow);
-                    }
-                }
+                        None
+                    };
 
-                match type_len {
-                    2 => Ok((
-                        ArrF16(ArrayF16 {
-                            is_big_endian,
-                            dimensions,
-                            variable_info,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    3 => Ok((
-                        ArrF32(ArrayF32 {
-                            is_big_endian,
-                            dimensions,
-                            variable_info,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    4 => Ok((
-                        ArrF64(ArrayF64 {
+                    let mut rest = slicer.rest();
+
+                    // the recursion is limited via MAX_STRUCT_DEPTH
+                    for _ in 0..number_of_entries {
+                        (_, rest) = VerboseValue::from_slice_with_depth(
+                            rest,
                             is_big_endian,
-                            dimensions,
-                            variable_info,
-                            data: slicer.read_raw(data_len)?,
-                        }),
-                        slicer.rest(),
-                    )),
-                    5 => Ok((
-                        ArrF128(ArrayF128 {
+                            config,
+                            depth + 1,
+                        )?;
+                    }
+                    let slice_begin = slicer.rest().as_ptr();
+                    // Rust allocations are ensured to always be smaller than isize::MAX, hence the distance can't result overflow
+                    // This operation is therefore safe
+                    let data_len = unsafe { rest.as_ptr().offset_from(slice_begin) as usize };
+
+                    Ok((
+                        Struct(StructValue {
                             is_big_endian,
-                            dimensions,
-                            variable_info,
-                            data: slicer.read_raw(data_len)?,
+                            number_of_entries,
+                            name,
+                            entries_data: slicer.read_raw(data_len)?,
                         }),
                         slicer.rest(),
-                    )),
-                    _ => unreachable!(),
+                    ))
                 }
-            } else {
-                Err(VerboseDecodeError::InvalidTypeInfo(type_info))
-            }
-        } else if 0 != type_info[0] & BOOL_FLAG_0 {
-            const CONTRADICTING_MASK_0: u8 = 0b1110_0000;
-            const CONTRADICTING_MASK_1: u8 = 0b1111_0111;
-            if
-            // check type length (must be 1 for bool)
-            (1 != type_info[0] & TYPE_LEN_MASK_0) ||
-                // check none of the other type flags other then varinfo
-                // flag is set
-                (0 != type_info[0] & CONTRADICTING_MASK_0) ||
-                (0 != type_info[1] & CONTRADICTING_MASK_1)
-            {
-                return Err(InvalidTypeInfo(type_info));
-            }
-
-            // check for varinfo
-            let name = if 0 != type_info[1] & VARINFO_FLAG_1 {
-                Some(slicer.read_var_name(is_big_endian)?)
-            } else {
-                None
-            };
-
-            // verify no conflicting information is present
-            let value_u8 = slicer.read_u8()?;
-
-            let value = match value_u8 {
-                0 => false,
-                1 => true,
-                value => return Err(InvalidBoolValue(value)),
-            };
-            Ok((Bool(BoolValue { name, value }), slicer.rest()))
-        } else if 0 != type_info[0] & SIGNED_FLAG_0 {
-            const CONTRADICTING_MASK_0: u8 = 0b1101_0000;
-            const CONTRADICTING_MASK_1: u8 = 0b1110_0111;
-
-            // check that no contradicting type info is present
-            if (0 != type_info[0] & CONTRADICTING_MASK_0)
-                || (0 != type_info[1] & CONTRADICTING_MASK_1)
-            {
-                return Err(InvalidTypeInfo(type_info));
             }
+        }
+    }
 
-            let type_len = type_info[0] & TYPE_LEN_MASK_0;
-            match type_len {
-                1..=5 => {}
-                _ => return Err(InvalidTypeInfo(type_info)),
-            }
-
-            // check for varinfo
-            let name_and_unit = if 0 != type_info[1] & VARINFO_FLAG_1 {
-                Some(slicer.read_var_name_and_unit(is_big_endian)?)
-            } else {
-                None
-            };
-
-            let var_info = name_and_unit.map(|(name, unit)| VariableInfoUnit { name, unit });
-
-            match type_len {
-                1 => Ok((
-                    I8(I8Value {
-                        variable_info: var_info,
-                        scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                        value: slicer.read_i8()?,
-                    }),
-                    slicer.rest(),
-                )),
-                2 => Ok((
-                    I16(I16Value {
-                        variable_info: var_info,
-                        scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                        value: slicer.read_i16(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                3 => Ok((
-                    I32(I32Value {
-                        variable_info: var_info,
-                        scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                        value: slicer.read_i32(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                4 => Ok((
-                    I64(I64Value {
-                        variable_info: var_info,
-                        scaling: slicer.read_i64_scaling(is_big_endian, type_info)?,
-                        value: slicer.read_i64(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                5 => Ok((
-                    I128(I128Value {
-                        variable_info: var_info,
-                        scaling: slicer.read_i128_scaling(is_big_endian, type_info)?,
-                        value: slicer.read_i128(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                _ => unreachable!(),
-            }
-        } else if 0 != type_info[0] & UNSIGNED_FLAG_0 {
-            // verify no conflicting information is present
-            const CONTRADICTING_MASK_0: u8 = 0b1011_0000;
-            const CONTRADICTING_MASK_1: u8 = 0b1110_0111;
-
-            // check that no contradicting type info is present
-            if (0 != type_info[0] & CONTRADICTING_MASK_0)
-                || (0 != type_info[1] & CONTRADICTING_MASK_1)
-            {
-                return Err(InvalidTypeInfo(type_info));
-            }
-
-            let type_len = type_info[0] & TYPE_LEN_MASK_0;
-            match type_len {
-                1..=5 => {}
-                _ => return Err(InvalidTypeInfo(type_info)),
-            }
-
-            // check for varinfo
-            let name_and_unit = if 0 != type_info[1] & VARINFO_FLAG_1 {
-                Some(slicer.read_var_name_and_unit(is_big_endian)?)
-            } else {
-                None
-            };
-
-            let var_info = name_and_unit.map(|(name, unit)| VariableInfoUnit { name, unit });
+    /// Adds the verbose value to the given dlt mesage buffer.
+    pub fn add_to_msg<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<(), VerboseEncodeError> {
+        use VerboseValue::*;
 
-            match type_len {
-                1 => Ok((
-                    U8(U8Value {
-                        variable_info: var_info,
-                        scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                        value: slicer.read_u8()?,
-                    }),
-                    slicer.rest(),
-                )),
-                2 => Ok((
-                    U16(U16Value {
-                        variable_info: var_info,
-                        scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                        value: slicer.read_u16(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                3 => Ok((
-                    U32(U32Value {
-                        variable_info: var_info,
-                        scaling: slicer.read_i32_scaling(is_big_endian, type_info)?,
-                        value: slicer.read_u32(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                4 => Ok((
-                    U64(U64Value {
-                        variable_info: var_info,
-                        scaling: slicer.read_i64_scaling(is_big_endian, type_info)?,
-                        value: slicer.read_u64(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                5 => Ok((
-                    U128(U128Value {
-                        variable_info: var_info,
-                        scaling: slicer.read_i128_scaling(is_big_endian, type_info)?,
-                        value: slicer.read_u128(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                _ => unreachable!(),
-            }
-        } else if 0 != type_info[0] & FLOAT_FLAG_0 {
-            // verify no conflicting information is present
+        match self {
+            Bool(v) => v.add_to_msg(buf, is_big_endian)?,
+            Str(v) => v.add_to_msg(buf, is_big_endian)?,
+            TraceInfo(v) => v.add_to_msg(buf, is_big_endian)?,
+            I8(v) => v.add_to_msg(buf, is_big_endian)?,
+            I16(v) => v.add_to_msg(buf, is_big_endian)?,
+            I32(v) => v.add_to_msg(buf, is_big_endian)?,
+            I64(v) => v.add_to_msg(buf, is_big_endian)?,
+            I128(v) => v.add_to_msg(buf, is_big_endian)?,
+            U8(v) => v.add_to_msg(buf, is_big_endian)?,
+            U16(v) => v.add_to_msg(buf, is_big_endian)?,
+            U32(v) => v.add_to_msg(buf, is_big_endian)?,
+            U64(v) => v.add_to_msg(buf, is_big_endian)?,
+            U128(v) => v.add_to_msg(buf, is_big_endian)?,
+            F16(v) => v.add_to_msg(buf, is_big_endian)?,
+            F32(v) => v.add_to_msg(buf, is_big_endian)?,
+            F64(v) => v.add_to_msg(buf, is_big_endian)?,
+            F128(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrBool(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrI8(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrI16(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrI32(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrI64(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrI128(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrU8(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrU16(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrU32(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrU64(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrU128(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrF16(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrF32(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrF64(v) => v.add_to_msg(buf, is_big_endian)?,
+            ArrF128(v) => v.add_to_msg(buf, is_big_endian)?,
+            Struct(v) => v.add_to_msg(buf, is_big_endian)?,
+            Raw(v) => v.add_to_msg(buf, is_big_endian)?,
+        }
+        Ok(())
+    }
 
-            const CONTRADICTING_MASK_0: u8 = 0b0111_0000;
-            const CONTRADICTING_MASK_1: u8 = 0b1111_0111;
+    /// Writes the verbose value to the given dlt message buffer and
+    /// returns the number of bytes written.
+    ///
+    /// In contrast to [`VerboseValue::add_to_msg`] the buffer is left
+    /// untouched on all errors. The required space is checked via
+    /// [`VerboseValue::serialized_len`] before anything is written & the
+    /// already written fields are removed again if a later field can not
+    /// be encoded (e.g. a name that is too long).
+    pub fn write<const CAP: usize>(
+        &self,
+        buf: &mut ArrayVec<u8, CAP>,
+        is_big_endian: bool,
+    ) -> Result<usize, VerboseEncodeError> {
+        let len = self.serialized_len();
+        if buf.remaining_capacity() < len {
+            return Err(VerboseEncodeError::Capacity(CapacityError::new(())));
+        }
+        let start = buf.len();
+        if let Err(err) = self.add_to_msg(buf, is_big_endian) {
+            buf.truncate(start);
+            return Err(err);
+        }
+        Ok(len)
+    }
 
-            // check that no contradicting type info is present
-            if (0 != type_info[0] & CONTRADICTING_MASK_0)
-                || (0 != type_info[1] & CONTRADICTING_MASK_1)
-            {
-                return Err(InvalidTypeInfo(type_info));
-            }
+    /// Returns the number of bytes the value occupies when written
+    /// via [`VerboseValue::write`] or [`VerboseValue::add_to_msg`].
+    pub fn serialized_len(&self) -> usize {
+        use core::mem::size_of;
+        use VerboseValue::*;
 
-            let type_len = type_info[0] & TYPE_LEN_MASK_0;
-            match type_len {
-                2..=5 => {}
-                _ => return Err(InvalidTypeInfo(type_info)),
-            }
+        /// Length of the type info.
+        const TYPE_INFO: usize = 4;
 
-            // check for varinfo
-            let name_and_unit = if 0 != type_info[1] & VARINFO_FLAG_1 {
-                Some(slicer.read_var_name_and_unit(is_big_endian)?)
-            } else {
-                None
-            };
+        /// Length of an optional name (length field & zero terminated string).
+        fn name_len(name: Option<&str>) -> usize {
+            name.map(|n| 2 + var_str_len(n)).unwrap_or(0)
+        }
 
-            let variable_info = name_and_unit.map(|(name, unit)| VariableInfoUnit { name, unit });
+        /// Length of the optional variable info (name & unit).
+        fn var_info_len(v: &Option<VariableInfoUnit<'_>>) -> usize {
+            v.as_ref()
+                .map(|v| 2 + 2 + var_str_len(v.name) + var_str_len(v.unit))
+                .unwrap_or(0)
+        }
 
-            match type_len {
-                2 => Ok((
-                    F16(F16Value {
-                        variable_info,
-                        value: slicer.read_f16(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                3 => Ok((
-                    F32(F32Value {
-                        variable_info,
-                        value: slicer.read_f32(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                4 => Ok((
-                    F64(F64Value {
-                        variable_info,
-                        value: slicer.read_f64(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                5 => Ok((
-                    F128(F128Value {
-                        variable_info,
-                        value: slicer.read_f128(is_big_endian)?,
-                    }),
-                    slicer.rest(),
-                )),
-                _ => unreachable!(),
-            }
-        } else if 0 != type_info[1] & STRING_FLAG_1 {
-            const CONTRADICTING_MASK_0: u8 = 0b1111_1111;
-            const CONTRADICTING_MASK_1: u8 = 0b0111_0101;
-
-            if
-            // check none of the other type flags other then varinfo
-            // flag is set
-            (0 != type_info[0] & CONTRADICTING_MASK_0)
-                || (0 != type_info[1] & CONTRADICTING_MASK_1)
-            {
-                return Err(InvalidTypeInfo(type_info));
-            }
+        /// Length of the optional scaling (quantization & offset).
+        fn scaling_len<T>(s: &Option<Scaling<T>>) -> usize {
+            s.as_ref().map(|_| 4 + size_of::<T>()).unwrap_or(0)
+        }
 
-            let len = usize::from(slicer.read_u16(is_big_endian)?);
+        /// Length of the number of dimensions & the dimensions.
+        fn dimensions_len(d: &ArrayDimensions<'_>) -> usize {
+            2 + d.dimensions.len()
+        }
 
-            let name = if 0 != type_info[1] & VARINFO_FLAG_1 {
-                Some(slicer.read_var_name(is_big_endian)?)
-            } else {
-                None
-            };
-            let value = match slicer.read_raw(len) {
-                Ok(valid_parse) => {
-                    if len > 0 {
-                        core::str::from_utf8(&valid_parse[..valid_parse.len() - 1])?
-                    } else {
-                        ""
-                    }
+        TYPE_INFO
+            + match self {
+                Bool(v) => name_len(v.name) + 1,
+                Str(v) => name_len(v.name) + 2 + v.value.len() + 1,
+                TraceInfo(v) => 2 + v.value.len() + 1,
+                I8(v) => var_info_len(&v.variable_info) + scaling_len(&v.scaling) + 1,
+                I16(v) => var_info_len(&v.variable_info) + scaling_len(&v.scaling) + 2,
+                I32(v) => var_info_len(&v.variable_info) + scaling_len(&v.scaling) + 4,
+                I64(v) => var_info_len(&v.variable_info) + scaling_len(&v.scaling) + 8,
+                I128(v) => var_info_len(&v.variable_info) + scaling_len(&v.scaling) + 16,
+                U8(v) => var_info_len(&v.variable_info) + scaling_len(&v.scaling) + 1,
+                U16(v) => var_info_len(&v.variable_info) + scaling_len(&v.scaling) + 2,
+                U32(v) => var_info_len(&v.variable_info) + scaling_len(&v.scaling) + 4,
+                U64(v) => var_info_len(&v.variable_info) + scaling_len(&v.scaling) + 8,
+                U128(v) => var_info_len(&v.variable_info) + scaling_len(&v.scaling) + 16,
+                F16(v) => var_info_len(&v.variable_info) + 2,
+                F32(v) => var_info_len(&v.variable_info) + 4,
+                F64(v) => var_info_len(&v.variable_info) + 8,
+                F128(v) => var_info_len(&v.variable_info) + 16,
+                ArrI8(v) => {
+                    dimensions_len(&v.dimensions)
+                        + var_info_len(&v.variable_info)
+                        + scaling_len(&v.scaling)
+                        + v.data.len()
                 }
-                Err(_) => {
-                    return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                        layer: error::Layer::VerboseValue,
-                        minimum_size: len,
-                        actual_size: slicer.rest().len(),
-                    }))
+                ArrI16(v) => {
+                    dimensions_len(&v.dimensions)
+                        + var_info_len(&v.variable_info)
+                        + scaling_len(&v.scaling)
+                        + v.data.len()
                 }
-            };
-
-            Ok((Str(StringValue { name, value }), slicer.rest()))
-        } else if 0 != type_info[1] & RAW_FLAG_1 {
-            // verify no conflicting information is present+
-            const CONTRADICTING_MASK_0: u8 = 0b1111_0000;
-            const CONTRADICTING_MASK_1: u8 = 0b0111_0011;
-            if
-            // check none of the other type flags other then varinfo
-            // flag is set
-            (0 != type_info[0] & CONTRADICTING_MASK_0)
-                || (0 != type_info[1] & CONTRADICTING_MASK_1)
-            {
-                return Err(InvalidTypeInfo(type_info));
-            }
-
-            // read len of raw data
-            let len = usize::from(slicer.read_u16(is_big_endian)?);
-
-            // check for varinfo
-            let name = if 0 != type_info[1] & VARINFO_FLAG_1 {
-                Some(slicer.read_var_name(is_big_endian)?)
-            } else {
-                None
-            };
-
-            Ok((
-                Raw(RawValue {
-                    name,
-                    data: slicer.read_raw(len)?,
-                }),
-                slicer.rest(),
-            ))
-        } else if 0 != type_info[1] & TRACE_INFO_FLAG_1 {
-            // verify no conflicting information is present
-
-            const CONTRADICTING_MASK_0: u8 = 0b1111_1111;
-            const CONTRADICTING_MASK_1: u8 = 0b0101_1111;
-
-            // check that no contradicting type info is present
-            if (0 != type_info[0] & CONTRADICTING_MASK_0)
-                || (0 != type_info[1] & CONTRADICTING_MASK_1)
-            {
-                return Err(InvalidTypeInfo(type_info));
-            }
-
-            // read len of trace data string
-            let len = usize::from(slicer.read_u16(is_big_endian)?);
-
-            let parse: Result<&str, str::Utf8Error> = match slicer.read_raw(len) {
-                Ok(valid_parse) => {
-                    if len > 0 {
-                        str::from_utf8(&valid_parse[..valid_parse.len() - 1])
-                    } else {
-                        Ok("")
-                    }
+                ArrI32(v) => {
+                    dimensions_len(&v.dimensions)
+                        + var_info_len(&v.variable_info)
+                        + scaling_len(&v.scaling)
+                        + v.data.len()
                 }
-                Err(_) => {
-                    return Err(UnexpectedEndOfSlice(UnexpectedEndOfSliceError {
-                        layer: error::Layer::VerboseValue,
-                        minimum_size: len,
-                        actual_size: slicer.rest().len(),
-                    }))
+                ArrI64(v) => {
+                    dimensions_len(&v.dimensions)
+                        + var_info_len(&v.variable_info)
+                        + scaling_len(&v.scaling)
+                        + v.data.len()
                 }
-            };
-            Ok((TraceInfo(TraceInfoValue { value: parse? }), slicer.rest()))
-        } else if 0 != type_info[1] & STRUCT_FLAG_1 {
-            // verify no conflicting information is present
-            const CONTRADICTING_MASK_0: u8 = 0b1111_1111;
-            const CONTRADICTING_MASK_1: u8 = 0b1011_0111;
-
-            // check that no contradicting type info is present
-            if (0 != type_info[0] & CONTRADICTING_MASK_0)
-                || (0 != type_info[1] & CONTRADICTING_MASK_1)
-            {
-                return Err(InvalidTypeInfo(type_info));
-            }
-
-            // read number of struct entries
-            let number_of_entries = slicer.read_u16(is_big_endian)?;
-
-            let name = if 0 != type_info[1] & VARINFO_FLAG_1 {
-                Some(slicer.read_var_name(is_big_endian)?)
-            } else {
-                None
-            };
-
-            let mut rest = slicer.rest();
-
-            // While this reduces the amount of duplicated code to a minimum, I am not quite sure if this safe as too nested structs could possibly lead to "infinite" recursion
-            for _ in 0..number_of_entries {
-                (_, rest) = VerboseValue::from_slice(rest, is_big_endian)?;
+                ArrI128(v) => {
+                    dimensions_len(&v.dimensions)
+                        + var_info_len(&v.variable_info)
+                        + scaling_len(&v.scaling)
+                        + v.data.len()
+                }
+                ArrU8(v) => {
+                    dimensions_len(&v.dimensions)
+                        + var_info_len(&v.variable_info)
+                        + scaling_len(&v.scaling)
+                        + v.data.len()
+                }
+                ArrU16(v) => {
+                    dimensions_len(&v.dimensions)
+                        + var_info_len(&v.variable_info)
+                        + scaling_len(&v.scaling)
+                        + v.data.len()
+                }
+                ArrU32(v) => {
+                    dimensions_len(&v.dimensions)
+                        + var_info_len(&v.variable_info)
+                        + scaling_len(&v.scaling)
+                        + v.data.len()
+                }
+                ArrU64(v) => {
+                    dimensions_len(&v.dimensions)
+                        + var_info_len(&v.variable_info)
+                        + scaling_len(&v.scaling)
+                        + v.data.len()
+                }
+                ArrU128(v) => {
+                    dimensions_len(&v.dimensions)
+                        + var_info_len(&v.variable_info)
+                        + scaling_len(&v.scaling)
+                        + v.data.len()
+                }
+                ArrBool(v) => {
+                    dimensions_len(&v.dimensions) + var_info_len(&v.variable_info) + v.data.len()
+                }
+                ArrF16(v) => {
+                    dimensions_len(&v.dimensions) + var_info_len(&v.variable_info) + v.data.len()
+                }
+                ArrF32(v) => {
+                    dimensions_len(&v.dimensions) + var_info_len(&v.variable_info) + v.data.len()
+                }
+                ArrF64(v) => {
+                    dimensions_len(&v.dimensions) + var_info_len(&v.variable_info) + v.data.len()
+                }
+                ArrF128(v) => {
+                    dimensions_len(&v.dimensions) + var_info_len(&v.variable_info) + v.data.len()
+                }
+                Struct(v) => 2 + name_len(v.name) + v.entries_data.len(),
+                Raw(v) => 2 + name_len(v.name) + v.data.len(),
             }
-            let slice_begin = slicer.rest().as_ptr();
-            // Rust allocations are ensured to always be smaller than isize::MAX, hence the distance can't result overflow
-            // This operation is therefore safe
-            let data_len = unsafe { rest.as_ptr().offset_from(slice_begin) as usize };
-
-            Ok((
-                Struct(StructValue {
-                    is_big_endian,
-                    number_of_entries,
-                    name,
-                    entries_data: slicer.read_raw(data_len)?,
-                }),
-                slicer.rest(),
-            ))
-        } else {
-            // nothing matches type info uninterpretable
-            Err(InvalidTypeInfo(type_info))
-        }
     }
 
     /// Returns the name of the value (if it has one).
@@ -830,4 +1092,1188 @@ impl<'a> VerboseValue<'a> {
             Raw(_) => None,
         }
     }
+
+    /// Returns the value of a bool or integer value as an `i128`.
+    ///
+    /// Bools are returned as 0 or 1 and fixed point values as their raw
+    /// (unscaled) value (see [`VerboseValue::physical_as_f64`] for the
+    /// scaled value). `None` is returned for floats, strings, raw data,
+    /// trace infos, arrays & structs and for `u128` values bigger then
+    /// `i128::MAX`.
+    pub fn as_i128(&self) -> Option<i128> {
+        use VerboseValue::*;
+
+        match self {
+            Bool(v) => Some(i128::from(v.value)),
+            I8(v) => Some(i128::from(v.value)),
+            I16(v) => Some(i128::from(v.value)),
+            I32(v) => Some(i128::from(v.value)),
+            I64(v) => Some(i128::from(v.value)),
+            I128(v) => Some(v.value),
+            U8(v) => Some(i128::from(v.value)),
+            U16(v) => Some(i128::from(v.value)),
+            U32(v) => Some(i128::from(v.value)),
+            U64(v) => Some(i128::from(v.value)),
+            U128(v) => i128::try_from(v.value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a bool or integer value as an `u128`.
+    ///
+    /// Bools are returned as 0 or 1 and fixed point values as their raw
+    /// (unscaled) value. `None` is returned for negative values, floats,
+    /// strings, raw data, trace infos, arrays & structs.
+    pub fn as_u128(&self) -> Option<u128> {
+        use VerboseValue::*;
+
+        match self {
+            U128(v) => Some(v.value),
+            other => other.as_i128().and_then(|v| u128::try_from(v).ok()),
+        }
+    }
+
+    /// Returns the value of a bool, integer or float value as an `f64`.
+    ///
+    /// Bools are returned as 0.0 or 1.0 and fixed point values as their raw
+    /// (unscaled) value (see [`VerboseValue::physical_as_f64`] for the
+    /// scaled value). `None` is returned for strings, raw data, trace infos,
+    /// arrays & structs.
+    ///
+    /// The conversion is lossy for 64 & 128 bit integers with more then
+    /// 53 significant bits, these are rounded to the nearest `f64` (same as
+    /// an `as` cast). 128 bit floats are only converted if the `f128_approx`
+    /// feature is active (see `RawF128::to_f64_approx`), otherwise `None`
+    /// is returned for them.
+    pub fn as_f64(&self) -> Option<f64> {
+        use VerboseValue::*;
+
+        match self {
+            F16(v) => Some(f64::from(v.value.to_f32())),
+            F32(v) => Some(f64::from(v.value)),
+            F64(v) => Some(v.value),
+            #[cfg(feature = "f128_approx")]
+            F128(v) => Some(v.value.to_f64_approx()),
+            U128(v) => Some(v.value as f64),
+            other => other.as_i128().map(|v| v as f64),
+        }
+    }
+
+    /// Returns the physical value of a bool, integer or float value as
+    /// an `f64`.
+    ///
+    /// Same as [`VerboseValue::as_f64`] with the difference that the
+    /// scaling of fixed point values is applied
+    /// (`raw * quantization + offset`).
+    pub fn physical_as_f64(&self) -> Option<f64> {
+        use VerboseValue::*;
+
+        fn scale<T: Copy>(raw: f64, scaling: &Option<Scaling<T>>, to_f64: fn(T) -> f64) -> f64 {
+            match scaling {
+                Some(s) => raw * f64::from(s.quantization) + to_f64(s.offset),
+                None => raw,
+            }
+        }
+
+        let raw = self.as_f64()?;
+        Some(match self {
+            I8(v) => scale(raw, &v.scaling, f64::from),
+            I16(v) => scale(raw, &v.scaling, f64::from),
+            I32(v) => scale(raw, &v.scaling, f64::from),
+            I64(v) => scale(raw, &v.scaling, |o| o as f64),
+            I128(v) => scale(raw, &v.scaling, |o| o as f64),
+            U8(v) => scale(raw, &v.scaling, f64::from),
+            U16(v) => scale(raw, &v.scaling, f64::from),
+            U32(v) => scale(raw, &v.scaling, f64::from),
+            U64(v) => scale(raw, &v.scaling, |o| o as f64),
+            U128(v) => scale(raw, &v.scaling, |o| o as f64),
+            _ => raw,
+        })
+    }
+}
+
+/// Type flags in the order they are checked to determine the kind of a
+/// verbose value (index of the type info byte, flag & resulting kind).
+///
+/// The base type flags are mutually exclusive, type infos with more than
+/// one of them set are rejected by [`type_info_kind`]. Other contradicting
+/// flags (e.g. ARAY for a string) are detected afterwards by the decoder
+/// of the selected kind via [`check_type_flags`].
+const TYPE_INFO_KIND_TABLE: [(usize, u8, TypeInfoKind); 8] = [
+    (0, 0b0001_0000, TypeInfoKind::Bool),
+    (0, 0b0010_0000, TypeInfoKind::Signed),
+    (0, 0b0100_0000, TypeInfoKind::Unsigned),
+    (0, 0b1000_0000, TypeInfoKind::Float),
+    (1, 0b0000_0010, TypeInfoKind::String),
+    (1, 0b0000_0100, TypeInfoKind::Raw),
+    (1, 0b0010_0000, TypeInfoKind::TraceInfo),
+    (1, 0b0100_0000, TypeInfoKind::Struct),
+];
+
+/// Returns the kind of the value described by the type info based on
+/// [`TYPE_INFO_KIND_TABLE`].
+///
+/// Returns [`VerboseDecodeError::InvalidTypeInfo`] if no base type flag
+/// is set & [`VerboseDecodeError::ConflictingTypeFlags`] if more than one
+/// is set.
+pub(super) fn type_info_kind(type_info: [u8; 4]) -> Result<TypeInfoKind, VerboseDecodeError> {
+    let mut kinds = TYPE_INFO_KIND_TABLE
+        .iter()
+        .filter(|(index, flag, _)| 0 != type_info[*index] & flag)
+        .map(|(_, _, kind)| *kind);
+    match (kinds.next(), kinds.next()) {
+        (Some(kind), None) => Ok(kind),
+        (Some(first), Some(second)) => Err(VerboseDecodeError::ConflictingTypeFlags {
+            first,
+            second,
+            type_info,
+        }),
+        (None, _) => Err(VerboseDecodeError::InvalidTypeInfo(type_info)),
+    }
+}
+
+/// Checks that none of the `contradicting` flags (excluding the type
+/// length bits) are set in the type info.
+///
+/// A set FIXP or VARI flag is reported via the dedicated
+/// [`VerboseDecodeError::FixpNotAllowedForType`] &
+/// [`VerboseDecodeError::VariNotAllowedForType`] errors.
+pub(super) fn check_type_flags(
+    type_info: [u8; 4],
+    kind: TypeInfoKind,
+    contradicting_0: u8,
+    contradicting_1: u8,
+) -> Result<(), VerboseDecodeError> {
+    use VerboseDecodeError::*;
+
+    const TYPE_LEN_MASK_0: u8 = 0b0000_1111;
+    const VARINFO_FLAG_1: u8 = 0b0000_1000;
+    const FIXP_FLAG_1: u8 = 0b0001_0000;
+
+    let flags_0 = type_info[0] & contradicting_0 & !TYPE_LEN_MASK_0;
+    let flags_1 = type_info[1] & contradicting_1;
+    if 0 != flags_0 || 0 != flags_1 & !(VARINFO_FLAG_1 | FIXP_FLAG_1) {
+        Err(InvalidTypeInfo(type_info))
+    } else if 0 != flags_1 & FIXP_FLAG_1 {
+        Err(FixpNotAllowedForType { kind, type_info })
+    } else if 0 != flags_1 & VARINFO_FLAG_1 {
+        Err(VariNotAllowedForType { kind, type_info })
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that the type length (TYLE) in the type info is in the
+/// `valid` range.
+pub(super) fn check_type_len(
+    type_info: [u8; 4],
+    kind: TypeInfoKind,
+    valid: core::ops::RangeInclusive<u8>,
+) -> Result<(), VerboseDecodeError> {
+    let tyle = type_info[0] & 0b0000_1111;
+    if valid.contains(&tyle) {
+        Ok(())
+    } else {
+        Err(VerboseDecodeError::InvalidTypeLength {
+            kind,
+            tyle,
+            type_info,
+        })
+    }
+}
+
+/// Returns the string data without the terminating zero (a non zero last
+/// byte is only kept if `allow_missing_termination` is set).
+fn strip_termination(data: &[u8], allow_missing_termination: bool) -> &[u8] {
+    match data.split_last() {
+        Some((0, value)) => value,
+        Some(_) if allow_missing_termination => data,
+        Some((_, value)) => value,
+        None => data,
+    }
+}
+
+/// Returns an error if embedded zeros are rejected by the config & the
+/// string data (without terminating zero) contains a zero byte.
+fn check_embedded_null(data: &[u8], config: &ParseConfig) -> Result<(), VerboseDecodeError> {
+    if config.reject_embedded_null {
+        if let Some(position) = data.iter().position(|b| 0 == *b) {
+            return Err(VerboseDecodeError::EmbeddedNull { position });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    /// Returns one value of every variant (with or without the
+    /// optional parts).
+    fn all_variants<'a>(
+        name: &'a str,
+        unit: &'a str,
+        data: &'a [u8],
+        dimensions: &'a [u8],
+        with_optionals: bool,
+    ) -> Vec<VerboseValue<'a>> {
+        use VerboseValue::*;
+
+        let n = if with_optionals { Some(name) } else { None };
+        let var_info = || {
+            if with_optionals {
+                Some(VariableInfoUnit { name, unit })
+            } else {
+                None
+            }
+        };
+        macro_rules! scaling {
+            () => {
+                if with_optionals {
+                    Some(Scaling {
+                        quantization: 1.5,
+                        offset: 2,
+                    })
+                } else {
+                    None
+                }
+            };
+        }
+        let dims = || ArrayDimensions {
+            is_big_endian: true,
+            dimensions,
+        };
+
+        vec![
+            Bool(BoolValue {
+                name: n,
+                value: true,
+            }),
+            Str(StringValue {
+                name: n,
+                value: unit,
+            }),
+            TraceInfo(TraceInfoValue { value: unit }),
+            I8(I8Value {
+                variable_info: var_info(),
+                scaling: scaling!(),
+                value: -1,
+            }),
+            I16(I16Value {
+                variable_info: var_info(),
+                scaling: scaling!(),
+                value: -2,
+            }),
+            I32(I32Value {
+                variable_info: var_info(),
+                scaling: scaling!(),
+                value: -3,
+            }),
+            I64(I64Value {
+                variable_info: var_info(),
+                scaling: scaling!(),
+                value: -4,
+            }),
+            I128(I128Value {
+                variable_info: var_info(),
+                scaling: scaling!(),
+                value: -5,
+            }),
+            U8(U8Value {
+                variable_info: var_info(),
+                scaling: scaling!(),
+                value: 1,
+            }),
+            U16(U16Value {
+                variable_info: var_info(),
+                scaling: scaling!(),
+                value: 2,
+            }),
+            U32(U32Value {
+                variable_info: var_info(),
+                scaling: scaling!(),
+                value: 3,
+            }),
+            U64(U64Value {
+                variable_info: var_info(),
+                scaling: scaling!(),
+                value: 4,
+            }),
+            U128(U128Value {
+                variable_info: var_info(),
+                scaling: scaling!(),
+                value: 5,
+            }),
+            F16(F16Value {
+                variable_info: var_info(),
+                value: RawF16::ONE,
+            }),
+            F32(F32Value {
+                variable_info: var_info(),
+                value: 1.0,
+            }),
+            F64(F64Value {
+                variable_info: var_info(),
+                value: 1.0,
+            }),
+            F128(F128Value {
+                variable_info: var_info(),
+                value: RawF128::from_bits(1),
+            }),
+            ArrBool(ArrayBool {
+                dimensions: dims(),
+                variable_info: var_info(),
+                data,
+            }),
+            ArrI8(ArrayI8 {
+                dimensions: dims(),
+                variable_info: var_info(),
+                scaling: scaling!(),
+                data,
+            }),
+            ArrI16(ArrayI16 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                scaling: scaling!(),
+                data,
+            }),
+            ArrI32(ArrayI32 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                scaling: scaling!(),
+                data,
+            }),
+            ArrI64(ArrayI64 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                scaling: scaling!(),
+                data,
+            }),
+            ArrI128(ArrayI128 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                scaling: scaling!(),
+                data,
+            }),
+            ArrU8(ArrayU8 {
+                dimensions: dims(),
+                variable_info: var_info(),
+                scaling: scaling!(),
+                data,
+            }),
+            ArrU16(ArrayU16 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                scaling: scaling!(),
+                data,
+            }),
+            ArrU32(ArrayU32 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                scaling: scaling!(),
+                data,
+            }),
+            ArrU64(ArrayU64 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                scaling: scaling!(),
+                data,
+            }),
+            ArrU128(ArrayU128 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                scaling: scaling!(),
+                data,
+            }),
+            ArrF16(ArrayF16 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                data,
+            }),
+            ArrF32(ArrayF32 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                data,
+            }),
+            ArrF64(ArrayF64 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                data,
+            }),
+            ArrF128(ArrayF128 {
+                is_big_endian: true,
+                dimensions: dims(),
+                variable_info: var_info(),
+                data,
+            }),
+            Struct(StructValue {
+                is_big_endian: true,
+                number_of_entries: 0,
+                name: n,
+                entries_data: data,
+            }),
+            Raw(RawValue { name: n, data }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn write_serialized_len(
+            ref name in "\\PC{0,10}",
+            ref unit in "\\PC{0,10}",
+            ref data in proptest::collection::vec(any::<u8>(), 0..40),
+            dimension_count in 0usize..4,
+            with_optionals in any::<bool>(),
+            is_big_endian in any::<bool>()
+        ) {
+            let dimensions = [1u8; 8];
+            let values = all_variants(name, unit, data, &dimensions[..dimension_count * 2], with_optionals);
+            prop_assert_eq!(34, values.len());
+
+            for value in values {
+                let expected = value.serialized_len();
+
+                // write
+                let mut buf = ArrayVec::<u8, 256>::new();
+                buf.push(0xff);
+                prop_assert_eq!(Ok(expected), value.write(&mut buf, is_big_endian));
+                prop_assert_eq!(expected + 1, buf.len());
+
+                // add_to_msg
+                let mut buf = ArrayVec::<u8, 256>::new();
+                value.add_to_msg(&mut buf, is_big_endian).unwrap();
+                prop_assert_eq!(expected, buf.len());
+
+                // not enough capacity (buffer stays untouched)
+                let mut buf = ArrayVec::<u8, 256>::new();
+                buf.extend(core::iter::repeat(0xff).take(256 - expected + 1));
+                prop_assert_eq!(
+                    Err(VerboseEncodeError::Capacity(CapacityError::new(()))),
+                    value.write(&mut buf, is_big_endian)
+                );
+                prop_assert_eq!(256 - expected + 1, buf.len());
+            }
+        }
+    }
+
+    #[test]
+    fn write_name_too_long() {
+        // the name is only checked after the type info was written
+        let name = "a".repeat(usize::from(u16::MAX));
+        let value = VerboseValue::F64(F64Value {
+            variable_info: Some(VariableInfoUnit {
+                name: &name,
+                unit: "",
+            }),
+            value: 1.0,
+        });
+        let mut buf = ArrayVec::<u8, 0x10100>::new();
+        buf.push(0xff);
+        assert_eq!(
+            Err(VerboseEncodeError::NameTooLong(name.len())),
+            value.write(&mut buf, false)
+        );
+        assert_eq!(&[0xff], &buf[..]);
+    }
+
+    #[test]
+    fn type_info_kind_table() {
+        use TypeInfoKind::*;
+        use VerboseDecodeError::*;
+
+        assert_eq!(
+            Err(InvalidTypeInfo([0, 0, 0, 0])),
+            type_info_kind([0, 0, 0, 0])
+        );
+        // bits that are not type flags
+        let no_flags = [0x0f, 0b1001_1001, 0xff, 0xff];
+        assert_eq!(Err(InvalidTypeInfo(no_flags)), type_info_kind(no_flags));
+
+        // every flag on its own
+        let flags = [
+            ([0x10, 0, 0, 0], Bool),
+            ([0x20, 0, 0, 0], Signed),
+            ([0x40, 0, 0, 0], Unsigned),
+            ([0x80, 0, 0, 0], Float),
+            ([0, 0x02, 0, 0], String),
+            ([0, 0x04, 0, 0], Raw),
+            ([0, 0x20, 0, 0], TraceInfo),
+            ([0, 0x40, 0, 0], Struct),
+        ];
+        for (type_info, expected) in flags {
+            assert_eq!(Ok(expected), type_info_kind(type_info));
+        }
+
+        // every combination of two flags
+        for (i, (first_info, first)) in flags.iter().enumerate() {
+            for (second_info, second) in &flags[i + 1..] {
+                let combined = [
+                    first_info[0] | second_info[0],
+                    first_info[1] | second_info[1],
+                    0,
+                    0,
+                ];
+                assert_eq!(
+                    Err(ConflictingTypeFlags {
+                        first: *first,
+                        second: *second,
+                        type_info: combined,
+                    }),
+                    type_info_kind(combined)
+                );
+            }
+        }
+
+        // all flags set (first two in the table are reported)
+        let all = [0xf0, 0x66, 0, 0];
+        assert_eq!(
+            Err(ConflictingTypeFlags {
+                first: Bool,
+                second: Signed,
+                type_info: all,
+            }),
+            type_info_kind(all)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn from_slice_conflicting_type_flags(
+            type_info in any::<[u8; 4]>(),
+            ref data in proptest::collection::vec(any::<u8>(), 0..64),
+            is_big_endian in any::<bool>(),
+        ) {
+            let mut bytes = Vec::with_capacity(4 + data.len());
+            bytes.extend_from_slice(&type_info);
+            bytes.extend_from_slice(data);
+
+            // decoding never panics
+            let result = VerboseValue::from_slice(&bytes, is_big_endian);
+
+            let set_flags = TYPE_INFO_KIND_TABLE
+                .iter()
+                .filter(|(index, flag, _)| 0 != type_info[*index] & flag)
+                .count();
+            if set_flags > 1 {
+                let is_conflicting = matches!(
+                    result,
+                    Err(VerboseDecodeError::ConflictingTypeFlags { .. })
+                );
+                prop_assert!(is_conflicting);
+            } else if set_flags == 0 {
+                prop_assert_eq!(Err(VerboseDecodeError::InvalidTypeInfo(type_info)), result.map(|_| ()));
+            }
+        }
+    }
+
+    /// Number of value bytes of a bool or numeric type with the given
+    /// type length as defined by the specification (`None` if the
+    /// combination is not allowed).
+    fn spec_value_len(kind: TypeInfoKind, tyle: u8) -> Option<usize> {
+        use TypeInfoKind::*;
+        match (kind, tyle) {
+            (Bool, 1) => Some(1),
+            (Signed | Unsigned, 1) => Some(1),
+            (Signed | Unsigned | Float, 2) => Some(2),
+            (Signed | Unsigned | Float, 3) => Some(4),
+            (Signed | Unsigned | Float, 4) => Some(8),
+            (Signed | Unsigned | Float, 5) => Some(16),
+            _ => None,
+        }
+    }
+
+    /// Encodes a bool or numeric value (or array with two elements) with
+    /// the given type info properties. The value bytes are taken from
+    /// `data` (repeated if needed).
+    ///
+    /// Returns the encoded bytes & the expected result of decoding them.
+    fn encode_type_info_combination(
+        kind: TypeInfoKind,
+        tyle: u8,
+        vari: bool,
+        fixp: bool,
+        array: bool,
+        is_big_endian: bool,
+        data: &[u8],
+    ) -> (Vec<u8>, Result<(), VerboseDecodeError>) {
+        use TypeInfoKind::*;
+        use VerboseDecodeError::*;
+
+        let u16_bytes = |v: u16| {
+            if is_big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+
+        let type_info = [
+            tyle | match kind {
+                Bool => 0x10,
+                Signed => 0x20,
+                Unsigned => 0x40,
+                _ => 0x80,
+            },
+            u8::from(array) | if vari { 0x08 } else { 0 } | if fixp { 0x10 } else { 0 },
+            0,
+            0,
+        ];
+        let mut result = Vec::new();
+        result.extend_from_slice(&type_info);
+
+        let expected = if fixp && matches!(kind, Bool | Float) {
+            Err(FixpNotAllowedForType { kind, type_info })
+        } else if spec_value_len(kind, tyle).is_none() {
+            Err(InvalidTypeLength {
+                kind,
+                tyle,
+                type_info,
+            })
+        } else {
+            Ok(())
+        };
+        let value_len = spec_value_len(kind, tyle).unwrap_or(0);
+
+        // array dimensions (one dimension with two elements)
+        if array {
+            result.extend_from_slice(&u16_bytes(1));
+            result.extend_from_slice(&u16_bytes(2));
+        }
+        // variable info (scalar bools only have a name)
+        if vari {
+            if kind == Bool && false == array {
+                result.extend_from_slice(&u16_bytes(2));
+                result.extend_from_slice(b"n\0");
+            } else {
+                result.extend_from_slice(&u16_bytes(2));
+                result.extend_from_slice(&u16_bytes(3));
+                result.extend_from_slice(b"n\0u1\0");
+            }
+        }
+        // quantization & offset
+        if fixp {
+            result.extend_from_slice(&1.5f32.to_bits().to_ne_bytes()[..]);
+            let offset_len = if tyle == 5 {
+                16
+            } else if tyle == 4 {
+                8
+            } else {
+                4
+            };
+            result.extend(core::iter::repeat(0x12).take(offset_len));
+        }
+        // values
+        let count = if array { 2 } else { 1 };
+        result.extend(data.iter().cycle().take(value_len * count).map(|b| {
+            if kind == Bool {
+                b & 1
+            } else {
+                *b
+            }
+        }));
+        (result, expected)
+    }
+
+    #[test]
+    fn from_slice_type_info_combinations() {
+        use TypeInfoKind::*;
+
+        const TRAILER: [u8; 3] = [0xaa, 0xbb, 0xcc];
+        for kind in [Bool, Signed, Unsigned, Float] {
+            for tyle in 0..=0xf {
+                for vari in [false, true] {
+                    for fixp in [false, true] {
+                        for array in [false, true] {
+                            for is_big_endian in [false, true] {
+                                let (mut bytes, expected) = encode_type_info_combination(
+                                    kind,
+                                    tyle,
+                                    vari,
+                                    fixp,
+                                    array,
+                                    is_big_endian,
+                                    &[1, 2, 3, 4, 5],
+                                );
+                                let expected_len = bytes.len();
+                                bytes.extend_from_slice(&TRAILER);
+
+                                let result = VerboseValue::from_slice(&bytes, is_big_endian);
+                                let msg = (kind, tyle, vari, fixp, array, is_big_endian);
+                                match expected {
+                                    Ok(()) => {
+                                        let (value, rest) = result.unwrap();
+                                        assert_eq!(&TRAILER[..], rest, "{:?}", msg);
+                                        assert_eq!(
+                                            expected_len,
+                                            value.serialized_len(),
+                                            "{:?}",
+                                            msg
+                                        );
+                                    }
+                                    Err(err) => {
+                                        assert_eq!(Err(err), result.map(|_| ()), "{:?}", msg)
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn from_slice_type_info_round_trip(
+            kind_index in 0usize..4,
+            tyle in 1u8..=5,
+            vari in any::<bool>(),
+            fixp in any::<bool>(),
+            array in any::<bool>(),
+            is_big_endian in any::<bool>(),
+            ref data in proptest::collection::vec(any::<u8>(), 1..40),
+        ) {
+            use TypeInfoKind::*;
+
+            let kind = [Bool, Signed, Unsigned, Float][kind_index];
+            let tyle = match kind {
+                Bool => 1,
+                Float => tyle.max(2),
+                _ => tyle,
+            };
+            let fixp = fixp && matches!(kind, Signed | Unsigned);
+            let (bytes, expected) =
+                encode_type_info_combination(kind, tyle, vari, fixp, array, is_big_endian, data);
+            prop_assert_eq!(Ok(()), expected);
+
+            let (value, rest) = VerboseValue::from_slice(&bytes, is_big_endian).unwrap();
+            prop_assert!(rest.is_empty());
+
+            let mut buf = ArrayVec::<u8, 128>::new();
+            value.add_to_msg(&mut buf, is_big_endian).unwrap();
+            prop_assert_eq!(&bytes[..], &buf[..]);
+        }
+    }
+
+    #[test]
+    fn from_slice_type_info_errors() {
+        use TypeInfoKind::*;
+        use VerboseDecodeError::*;
+
+        // data after the type info so that no end of slice errors occur
+        let decode = |type_info: [u8; 4]| {
+            let mut data = Vec::with_capacity(4 + 32);
+            data.extend_from_slice(&type_info);
+            data.extend_from_slice(&[0u8; 32]);
+            VerboseValue::from_slice(&data, false).map(|_| ())
+        };
+
+        let tests: [([u8; 4], VerboseDecodeError); 20] = [
+            // no type flag set
+            ([0, 0, 0, 0], InvalidTypeInfo([0, 0, 0, 0])),
+            // bool & signed flag set
+            (
+                [0x31, 0, 0, 0],
+                ConflictingTypeFlags {
+                    first: Bool,
+                    second: Signed,
+                    type_info: [0x31, 0, 0, 0],
+                },
+            ),
+            // signed & string flag
+            (
+                [0x23, 0x02, 0, 0],
+                ConflictingTypeFlags {
+                    first: Signed,
+                    second: String,
+                    type_info: [0x23, 0x02, 0, 0],
+                },
+            ),
+            // array of strings
+            ([0, 0x03, 0, 0], InvalidTypeInfo([0, 0x03, 0, 0])),
+            // bool with a length of 2
+            (
+                [0x12, 0, 0, 0],
+                InvalidTypeLength {
+                    kind: Bool,
+                    tyle: 2,
+                    type_info: [0x12, 0, 0, 0],
+                },
+            ),
+            // signed with a length of 6
+            (
+                [0x26, 0, 0, 0],
+                InvalidTypeLength {
+                    kind: Signed,
+                    tyle: 6,
+                    type_info: [0x26, 0, 0, 0],
+                },
+            ),
+            // unsigned array with a length of 0
+            (
+                [0x40, 0x01, 0, 0],
+                InvalidTypeLength {
+                    kind: Unsigned,
+                    tyle: 0,
+                    type_info: [0x40, 0x01, 0, 0],
+                },
+            ),
+            // float with a length of 1 (8 bit float)
+            (
+                [0x81, 0, 0, 0],
+                InvalidTypeLength {
+                    kind: Float,
+                    tyle: 1,
+                    type_info: [0x81, 0, 0, 0],
+                },
+            ),
+            // string with a length
+            (
+                [0x01, 0x02, 0, 0],
+                InvalidTypeLength {
+                    kind: String,
+                    tyle: 1,
+                    type_info: [0x01, 0x02, 0, 0],
+                },
+            ),
+            // struct with a length
+            (
+                [0x03, 0x40, 0, 0],
+                InvalidTypeLength {
+                    kind: Struct,
+                    tyle: 3,
+                    type_info: [0x03, 0x40, 0, 0],
+                },
+            ),
+            // trace info with variable info
+            (
+                [0, 0x28, 0, 0],
+                VariNotAllowedForType {
+                    kind: TraceInfo,
+                    type_info: [0, 0x28, 0, 0],
+                },
+            ),
+            // fixed point float
+            (
+                [0x83, 0x10, 0, 0],
+                FixpNotAllowedForType {
+                    kind: Float,
+                    type_info: [0x83, 0x10, 0, 0],
+                },
+            ),
+            // fixed point bool
+            (
+                [0x11, 0x10, 0, 0],
+                FixpNotAllowedForType {
+                    kind: Bool,
+                    type_info: [0x11, 0x10, 0, 0],
+                },
+            ),
+            // fixed point string
+            (
+                [0, 0x12, 0, 0],
+                FixpNotAllowedForType {
+                    kind: String,
+                    type_info: [0, 0x12, 0, 0],
+                },
+            ),
+            // fixed point raw
+            (
+                [0, 0x14, 0, 0],
+                FixpNotAllowedForType {
+                    kind: Raw,
+                    type_info: [0, 0x14, 0, 0],
+                },
+            ),
+            // fixed point struct
+            (
+                [0, 0x50, 0, 0],
+                FixpNotAllowedForType {
+                    kind: Struct,
+                    type_info: [0, 0x50, 0, 0],
+                },
+            ),
+            // string coding 2
+            (
+                [0, 0x02, 0x01, 0],
+                InvalidStringCoding {
+                    scod: 2,
+                    type_info: [0, 0x02, 0x01, 0],
+                },
+            ),
+            // string coding 7
+            (
+                [0, 0x82, 0x03, 0],
+                InvalidStringCoding {
+                    scod: 7,
+                    type_info: [0, 0x82, 0x03, 0],
+                },
+            ),
+            // trace info with string coding 2
+            (
+                [0, 0x20, 0x01, 0],
+                InvalidStringCoding {
+                    scod: 2,
+                    type_info: [0, 0x20, 0x01, 0],
+                },
+            ),
+            // trace info with string coding 7
+            (
+                [0, 0xA0, 0x03, 0],
+                InvalidStringCoding {
+                    scod: 7,
+                    type_info: [0, 0xA0, 0x03, 0],
+                },
+            ),
+        ];
+        for (type_info, expected) in tests {
+            assert_eq!(Err(expected), decode(type_info), "{:?}", type_info);
+        }
+
+        // ascii & utf-8 string codings are accepted
+        for type_info in [
+            [0, 0x02, 0, 0],
+            [0, 0x82, 0, 0],
+            [0, 0x20, 0, 0],
+            [0, 0xA0, 0, 0],
+        ] {
+            assert_eq!(Ok(()), decode(type_info));
+        }
+    }
+
+    #[test]
+    fn numeric_accessors() {
+        use VerboseValue::*;
+
+        // non numeric values
+        for with_optionals in [false, true] {
+            let dimensions = [0, 1];
+            for value in all_variants("a", "b", &[1, 2], &dimensions, with_optionals) {
+                let is_numeric = matches!(
+                    value,
+                    Bool(_)
+                        | I8(_)
+                        | I16(_)
+                        | I32(_)
+                        | I64(_)
+                        | I128(_)
+                        | U8(_)
+                        | U16(_)
+                        | U32(_)
+                        | U64(_)
+                        | U128(_)
+                        | F16(_)
+                        | F32(_)
+                        | F64(_)
+                        | F128(_)
+                );
+                if false == is_numeric {
+                    assert_eq!(None, value.as_i128());
+                    assert_eq!(None, value.as_u128());
+                    assert_eq!(None, value.as_f64());
+                    assert_eq!(None, value.physical_as_f64());
+                }
+            }
+        }
+
+        macro_rules! int {
+            ($variant:ident, $value_type:ident, $value:expr) => {
+                $variant($value_type {
+                    variable_info: None,
+                    scaling: None,
+                    value: $value,
+                })
+            };
+        }
+        macro_rules! float {
+            ($variant:ident, $value_type:ident, $value:expr) => {
+                $variant($value_type {
+                    variable_info: None,
+                    value: $value,
+                })
+            };
+        }
+
+        // (value, as_i128, as_u128, as_f64)
+        let tests = [
+            (
+                Bool(BoolValue {
+                    name: None,
+                    value: false,
+                }),
+                Some(0),
+                Some(0),
+                Some(0.0),
+            ),
+            (
+                Bool(BoolValue {
+                    name: None,
+                    value: true,
+                }),
+                Some(1),
+                Some(1),
+                Some(1.0),
+            ),
+            (int!(I8, I8Value, i8::MIN), Some(-128), None, Some(-128.0)),
+            (
+                int!(I8, I8Value, i8::MAX),
+                Some(127),
+                Some(127),
+                Some(127.0),
+            ),
+            (
+                int!(I16, I16Value, i16::MIN),
+                Some(i16::MIN.into()),
+                None,
+                Some(i16::MIN.into()),
+            ),
+            (
+                int!(I32, I32Value, i32::MIN),
+                Some(i32::MIN.into()),
+                None,
+                Some(i32::MIN.into()),
+            ),
+            (
+                int!(I64, I64Value, i64::MIN),
+                Some(i64::MIN.into()),
+                None,
+                Some(i64::MIN as f64),
+            ),
+            (
+                int!(I64, I64Value, i64::MAX),
+                Some(i64::MAX.into()),
+                Some(i64::MAX as u128),
+                Some(i64::MAX as f64),
+            ),
+            (
+                int!(I128, I128Value, i128::MIN),
+                Some(i128::MIN),
+                None,
+                Some(i128::MIN as f64),
+            ),
+            (
+                int!(I128, I128Value, i128::MAX),
+                Some(i128::MAX),
+                Some(i128::MAX as u128),
+                Some(i128::MAX as f64),
+            ),
+            (
+                int!(U8, U8Value, u8::MAX),
+                Some(255),
+                Some(255),
+                Some(255.0),
+            ),
+            (
+                int!(U16, U16Value, u16::MAX),
+                Some(u16::MAX.into()),
+                Some(u16::MAX.into()),
+                Some(u16::MAX.into()),
+            ),
+            (
+                int!(U32, U32Value, u32::MAX),
+                Some(u32::MAX.into()),
+                Some(u32::MAX.into()),
+                Some(u32::MAX.into()),
+            ),
+            (
+                int!(U64, U64Value, u64::MAX),
+                Some(u64::MAX.into()),
+                Some(u64::MAX.into()),
+                Some(u64::MAX as f64),
+            ),
+            (int!(U128, U128Value, 0), Some(0), Some(0), Some(0.0)),
+            (
+                int!(U128, U128Value, i128::MAX as u128),
+                Some(i128::MAX),
+                Some(i128::MAX as u128),
+                Some(i128::MAX as f64),
+            ),
+            (
+                int!(U128, U128Value, u128::MAX),
+                None,
+                Some(u128::MAX),
+                Some(u128::MAX as f64),
+            ),
+            (
+                float!(F16, F16Value, RawF16::from_bits(0x3e00)),
+                None,
+                None,
+                Some(1.5),
+            ),
+            (float!(F32, F32Value, -2.5), None, None, Some(-2.5)),
+            (float!(F64, F64Value, f64::MAX), None, None, Some(f64::MAX)),
+            (float!(F64, F64Value, f64::MIN), None, None, Some(f64::MIN)),
+        ];
+        for (value, expected_i128, expected_u128, expected_f64) in tests {
+            assert_eq!(expected_i128, value.as_i128(), "{:?}", value);
+            assert_eq!(expected_u128, value.as_u128(), "{:?}", value);
+            assert_eq!(expected_f64, value.as_f64(), "{:?}", value);
+            // no scaling present
+            assert_eq!(expected_f64, value.physical_as_f64(), "{:?}", value);
+        }
+
+        // 128 bit floats are only converted with the f128_approx feature
+        {
+            let value = float!(F128, F128Value, RawF128::from_bits(0x3fff << 112));
+            assert_eq!(None, value.as_i128());
+            assert_eq!(None, value.as_u128());
+            #[cfg(feature = "f128_approx")]
+            assert_eq!(Some(1.0), value.as_f64());
+            #[cfg(not(feature = "f128_approx"))]
+            assert_eq!(None, value.as_f64());
+        }
+
+        // fixed point values
+        {
+            let value = I32(I32Value {
+                variable_info: None,
+                scaling: Some(Scaling {
+                    quantization: 0.5,
+                    offset: -3,
+                }),
+                value: 10,
+            });
+            assert_eq!(Some(10), value.as_i128());
+            assert_eq!(Some(10), value.as_u128());
+            assert_eq!(Some(10.0), value.as_f64());
+            assert_eq!(Some(2.0), value.physical_as_f64());
+        }
+        {
+            let value = U64(U64Value {
+                variable_info: None,
+                scaling: Some(Scaling {
+                    quantization: 2.0,
+                    offset: 1,
+                }),
+                value: 4,
+            });
+            assert_eq!(Some(9.0), value.physical_as_f64());
+        }
+        {
+            let value = I128(I128Value {
+                variable_info: None,
+                scaling: Some(Scaling {
+                    quantization: 0.25,
+                    offset: 100,
+                }),
+                value: -8,
+            });
+            assert_eq!(Some(-8), value.as_i128());
+            assert_eq!(None, value.as_u128());
+            assert_eq!(Some(98.0), value.physical_as_f64());
+        }
+    }
 }
@@ -0,0 +1,127 @@
+use crate::error::WriteError;
+use crate::DltHeader;
+use std::io;
+
+/// Serializes a batch of DLT messages (header & payload) to the given writer.
+///
+/// The length field of each header is computed from the header & payload
+/// length (the `length` value in the passed headers is ignored). Writing
+/// stops at the first message that can not be written, the index of that
+/// message is contained in the returned error.
+///
+/// # Example
+///
+/// ```
+/// use dlt_parse::{write_messages, DltHeader};
+///
+/// let header = DltHeader {
+///     is_big_endian: true,
+///     message_counter: 0,
+///     length: 0,
+///     ecu_id: None,
+///     session_id: None,
+///     timestamp: None,
+///     extended_header: None,
+/// };
+/// let payload = [1, 2, 3, 4];
+///
+/// let mut buffer = Vec::new();
+/// write_messages(&mut buffer, &[(header.clone(), &payload), (header, &payload)]).unwrap();
+/// assert_eq!(2 * (4 + 4), buffer.len());
+/// ```
+pub fn write_messages<W: io::Write>(
+    writer: &mut W,
+    messages: &[(DltHeader, &[u8])],
+) -> Result<(), WriteError> {
+    for (index, (header, payload)) in messages.iter().enumerate() {
+        let length = usize::from(header.header_len()) + payload.len();
+        let mut header = header.clone();
+        header.length =
+            u16::try_from(length).map_err(|_| WriteError::MessageTooLong { index, length })?;
+
+        header
+            .write(writer)
+            .and_then(|_| writer.write_all(payload))
+            .map_err(|err| WriteError::Io { index, err })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use crate::DltPacketSlice;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn write_read(
+            ref headers in proptest::collection::vec(dlt_header_any(), 0..5),
+            ref payload in proptest::collection::vec(any::<u8>(), 0..100)
+        ) {
+            let messages: Vec<(DltHeader, &[u8])> = headers
+                .iter()
+                .map(|h| (h.clone(), &payload[..]))
+                .collect();
+
+            let mut buffer = Vec::new();
+            write_messages(&mut buffer, &messages).unwrap();
+
+            // read the messages back
+            let mut rest = &buffer[..];
+            for (header, payload) in messages.iter() {
+                let slice = DltPacketSlice::from_slice(rest).unwrap();
+                let mut expected = header.clone();
+                expected.length = header.header_len() + payload.len() as u16;
+                prop_assert_eq!(expected, slice.header());
+                prop_assert_eq!(*payload, slice.payload());
+                rest = &rest[slice.slice().len()..];
+            }
+            prop_assert!(rest.is_empty());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn message_too_long(ref header in dlt_header_any()) {
+            let payload = [0u8; u16::MAX as usize];
+            let messages = [
+                (header.clone(), &[][..]),
+                (header.clone(), &payload[..]),
+                (header.clone(), &[][..]),
+            ];
+
+            let mut buffer = Vec::new();
+            let err = write_messages(&mut buffer, &messages).unwrap_err();
+            assert_matches!(
+                err,
+                WriteError::MessageTooLong{ index: 1, length } if length == usize::from(header.header_len()) + payload.len()
+            );
+            // the first message is written
+            prop_assert_eq!(usize::from(header.header_len()), buffer.len());
+        }
+    }
+
+    #[test]
+    fn io_error() {
+        let header = DltHeader {
+            is_big_endian: false,
+            message_counter: 0,
+            length: 0,
+            ecu_id: None,
+            session_id: None,
+            timestamp: None,
+            extended_header: None,
+        };
+        let payload = [0u8; 4];
+        let messages = [(header.clone(), &payload[..]), (header, &payload[..])];
+
+        // only enough space for the first message
+        let mut buffer = [0u8; 12];
+        let mut writer = &mut buffer[..];
+        let err = write_messages(&mut writer, &messages).unwrap_err();
+        assert_matches!(err, WriteError::Io { index: 1, .. });
+    }
+}